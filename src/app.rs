@@ -2,17 +2,20 @@ use crate::auth::AuthState;
 use crate::billing_rate_store::BillingRateStore;
 use crate::db::DbPool;
 use crate::error::{AppError, AppResult};
+use crate::handlers::CachedNonstreamResponse;
 use crate::handlers::routing::health_key;
+use crate::idempotency::IdempotencyStore;
 use crate::image_transform_cache::ImageTransformCache;
 use crate::model_registry::ModelRegistry;
 use crate::model_registry_store::ModelRegistryStore;
 use crate::monoize_routing::{
-    ChannelAffinityBinding, ChannelHealthState, MonoizeRoutingStore, MonoizeRuntimeConfig,
-    probe_channel_completion,
+    ChannelAffinityBinding, ChannelConcurrencyLimit, ChannelHealthState, ChannelKeyRotation,
+    MonoizeRoutingStore, MonoizeRuntimeConfig, probe_channel_completion,
 };
 use crate::name_cache::NameCaches;
 use crate::rate_limit::RateLimiter;
 use crate::request_capture::RequestCaptureStore;
+use crate::response_cache::ResponseCache;
 use crate::settings::{SettingsStore, normalize_pricing_model_key};
 use crate::transforms::TransformRegistry;
 use crate::users::{InsertRequestLog, UserRole, UserStore};
@@ -39,6 +42,9 @@ pub struct AppState {
     pub auth: AuthState,
     pub model_registry: ModelRegistry,
     pub http: reqwest::Client,
+    /// Client without a read timeout for non-streaming upstream calls, whose
+    /// per-call request timeout bounds the whole exchange (CFG-7).
+    pub http_nonstream: reqwest::Client,
     pub metrics: PrometheusHandle,
     pub group_counters: Arc<Mutex<HashMap<String, u64>>>,
     pub user_store: UserStore,
@@ -48,6 +54,11 @@ pub struct AppState {
     pub monoize_runtime: Arc<tokio::sync::RwLock<MonoizeRuntimeConfig>>,
     pub channel_health: Arc<Mutex<HashMap<String, ChannelHealthState>>>,
     pub channel_affinity: Arc<Mutex<HashMap<String, ChannelAffinityBinding>>>,
+    pub channel_concurrency: Arc<Mutex<HashMap<String, ChannelConcurrencyLimit>>>,
+    pub channel_keys: Arc<Mutex<HashMap<String, ChannelKeyRotation>>>,
+    /// Slots for buffered (synthetic-stream) transcodes, sized by
+    /// `max_buffered_transcodes`; `None` until first use.
+    pub buffered_transcodes: Arc<Mutex<Option<ChannelConcurrencyLimit>>>,
     pub model_registry_store: ModelRegistryStore,
     pub billing_rate_store: BillingRateStore,
     pub transform_registry: Arc<TransformRegistry>,
@@ -57,6 +68,9 @@ pub struct AppState {
     pub sse_connections: Arc<DashMap<String, AtomicUsize>>,
     pub image_transform_cache: Arc<ImageTransformCache>,
     pub request_capture: RequestCaptureStore,
+    pub response_cache: Arc<ResponseCache<CachedNonstreamResponse>>,
+    pub idempotency: Arc<IdempotencyStore>,
+    pub gemini_context_cache: Arc<ResponseCache<String>>,
 }
 
 const ACTIVE_PROBE_CONNECTIVITY_KIND: &str = "active_probe_connectivity";
@@ -66,13 +80,24 @@ static METRICS_HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
 static METRICS_ERROR: OnceLock<AppError> = OnceLock::new();
 static METRICS_INIT: Once = Once::new();
 
+pub(crate) const REQUEST_DURATION_METRIC: &str = "monoize_request_duration_ms";
+pub(crate) const REQUEST_TTFB_METRIC: &str = "monoize_request_ttfb_ms";
+const REQUEST_LATENCY_BUCKETS_MS: [f64; 12] = [
+    50.0, 100.0, 250.0, 500.0, 1_000.0, 2_000.0, 5_000.0, 10_000.0, 20_000.0, 30_000.0, 45_000.0,
+    60_000.0,
+];
+
 #[derive(Debug, Clone)]
 pub struct RuntimeConfig {
     pub listen: String,
     pub metrics_path: String,
     pub database_dsn: String,
+    /// Largest request body accepted before any handler reads it.
+    pub max_request_body_bytes: usize,
 }
 
+pub const DEFAULT_MAX_REQUEST_BODY_BYTES: usize = 50 * 1024 * 1024;
+
 impl RuntimeConfig {
     pub fn from_env() -> Self {
         let listen = std::env::var("MONOIZE_LISTEN")
@@ -84,10 +109,16 @@ impl RuntimeConfig {
             .filter(|v| !v.trim().is_empty())
             .unwrap_or_else(|| "/metrics".to_string());
         let database_dsn = resolve_database_dsn();
+        let max_request_body_bytes = std::env::var("MONOIZE_MAX_REQUEST_BODY_BYTES")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_MAX_REQUEST_BODY_BYTES);
         Self {
             listen,
             metrics_path,
             database_dsn,
+            max_request_body_bytes,
         }
     }
 }
@@ -100,17 +131,6 @@ pub async fn load_state() -> AppResult<AppState> {
 pub async fn load_state_with_runtime(runtime: RuntimeConfig) -> AppResult<AppState> {
     let auth = AuthState::new();
 
-    let http = reqwest::Client::builder()
-        .user_agent("monoize/0.1")
-        .build()
-        .map_err(|err| {
-            AppError::new(
-                axum::http::StatusCode::BAD_REQUEST,
-                "http_client_init_failed",
-                err.to_string(),
-            )
-        })?;
-
     let db = DbPool::connect(&runtime.database_dsn)
         .await
         .map_err(|err| {
@@ -210,39 +230,39 @@ pub async fn load_state_with_runtime(runtime: RuntimeConfig) -> AppResult<AppSta
         )
     })?;
 
+    // Connect and read timeouts are client-level in reqwest, so they apply from process start.
+    // The read timeout also covers the wait for response headers, so non-streaming calls use a
+    // client without it and rely on their per-call request timeout instead.
+    let connect_timeout =
+        std::time::Duration::from_millis(settings_snapshot.monoize_connect_timeout_ms.max(1));
+    let http_client_init_failed = |err: reqwest::Error| {
+        AppError::new(
+            axum::http::StatusCode::BAD_REQUEST,
+            "http_client_init_failed",
+            err.to_string(),
+        )
+    };
+    let http = reqwest::Client::builder()
+        .user_agent("monoize/0.1")
+        .connect_timeout(connect_timeout)
+        .read_timeout(std::time::Duration::from_millis(
+            settings_snapshot.monoize_read_timeout_ms.max(1),
+        ))
+        .build()
+        .map_err(http_client_init_failed)?;
+    let http_nonstream = reqwest::Client::builder()
+        .user_agent("monoize/0.1")
+        .connect_timeout(connect_timeout)
+        .build()
+        .map_err(http_client_init_failed)?;
+
     let mut monoize_runtime = MonoizeRuntimeConfig::default();
-    monoize_runtime.passive_failure_count_threshold =
-        settings_snapshot.monoize_passive_failure_threshold.max(1);
-    monoize_runtime.passive_cooldown_seconds =
-        settings_snapshot.monoize_passive_cooldown_seconds.max(1);
-    monoize_runtime.passive_window_seconds =
-        settings_snapshot.monoize_passive_window_seconds.max(1);
-    monoize_runtime.passive_rate_limit_cooldown_seconds = settings_snapshot
-        .monoize_passive_rate_limit_cooldown_seconds
-        .max(1);
-    monoize_runtime.active_enabled = settings_snapshot.monoize_active_probe_enabled;
-    monoize_runtime.active_interval_seconds = settings_snapshot
-        .monoize_active_probe_interval_seconds
-        .max(1);
-    monoize_runtime.active_success_threshold = settings_snapshot
-        .monoize_active_probe_success_threshold
-        .max(1);
-    monoize_runtime.active_probe_model = settings_snapshot.monoize_active_probe_model.clone();
-    monoize_runtime.global_transforms = settings_snapshot.global_transforms.clone();
-    monoize_runtime.request_timeout_ms = settings_snapshot.monoize_request_timeout_ms.max(1);
-    monoize_runtime.stream_idle_timeout_ms =
-        settings_snapshot.monoize_stream_idle_timeout_ms.max(1);
-    monoize_runtime.enable_estimated_billing = settings_snapshot.monoize_enable_estimated_billing;
-    monoize_runtime.extra_fields_whitelist =
-        settings_snapshot.monoize_extra_fields_whitelist.clone();
-    monoize_runtime.strip_cross_protocol_nested_extra =
-        settings_snapshot.monoize_strip_cross_protocol_nested_extra;
-    monoize_runtime.request_capture_enabled = settings_snapshot.monoize_request_capture_enabled;
-    monoize_runtime.request_capture_retention_days = settings_snapshot
-        .monoize_request_capture_retention_days
-        .max(1);
+    monoize_runtime.apply_settings(&settings_snapshot);
     let channel_health = Arc::new(Mutex::new(HashMap::new()));
     let channel_affinity = Arc::new(Mutex::new(HashMap::new()));
+    let channel_concurrency = Arc::new(Mutex::new(HashMap::new()));
+    let channel_keys = Arc::new(Mutex::new(HashMap::new()));
+    let buffered_transcodes = Arc::new(Mutex::new(None));
     let transform_registry = Arc::new(crate::transforms::registry());
     let image_transform_cache = Arc::new(ImageTransformCache::from_env().await.map_err(|err| {
         AppError::new(
@@ -426,11 +446,7 @@ pub async fn load_state_with_runtime(runtime: RuntimeConfig) -> AppResult<AppSta
                             let state = guard
                                 .entry(health_key(&channel.id, None))
                                 .or_insert_with(ChannelHealthState::new);
-                            state.last_probe_at = Some(now);
-                            state.probe_success_count = state.probe_success_count.saturating_add(1);
-                            if state.probe_success_count >= probe_success_threshold {
-                                clear_channel_health_state(state, now);
-                            }
+                            state.record_probe_success(now, probe_success_threshold);
                         }
                     } else {
                         let cooldown_seconds = channel
@@ -440,20 +456,14 @@ pub async fn load_state_with_runtime(runtime: RuntimeConfig) -> AppResult<AppSta
                         if provider.per_model_circuit_break {
                             for key in channel_health_keys(&guard, &channel.id) {
                                 if let Some(state) = guard.get_mut(&key) {
-                                    state.healthy = false;
-                                    state.probe_success_count = 0;
-                                    state.last_probe_at = Some(now);
-                                    state.cooldown_until = Some(now + cooldown_seconds as i64);
+                                    state.reopen(now, cooldown_seconds);
                                 }
                             }
                         } else {
                             let state = guard
                                 .entry(health_key(&channel.id, None))
                                 .or_insert_with(ChannelHealthState::new);
-                            state.healthy = false;
-                            state.probe_success_count = 0;
-                            state.last_probe_at = Some(now);
-                            state.cooldown_until = Some(now + cooldown_seconds as i64);
+                            state.reopen(now, cooldown_seconds);
                         }
                     }
                 }
@@ -466,6 +476,7 @@ pub async fn load_state_with_runtime(runtime: RuntimeConfig) -> AppResult<AppSta
         auth,
         model_registry,
         http,
+        http_nonstream,
         metrics,
         group_counters: Arc::new(Mutex::new(HashMap::new())),
         user_store,
@@ -475,6 +486,9 @@ pub async fn load_state_with_runtime(runtime: RuntimeConfig) -> AppResult<AppSta
         monoize_runtime,
         channel_health,
         channel_affinity,
+        channel_concurrency,
+        channel_keys,
+        buffered_transcodes,
         model_registry_store,
         billing_rate_store,
         transform_registry,
@@ -484,13 +498,29 @@ pub async fn load_state_with_runtime(runtime: RuntimeConfig) -> AppResult<AppSta
         sse_connections: Arc::new(DashMap::new()),
         image_transform_cache,
         request_capture,
+        response_cache: Arc::new(ResponseCache::new()),
+        idempotency: Arc::new(IdempotencyStore::new()),
+        gemini_context_cache: Arc::new(ResponseCache::new()),
     })
 }
 
 #[allow(clippy::result_large_err)]
 fn init_metrics() -> AppResult<PrometheusHandle> {
     METRICS_INIT.call_once(|| {
-        match metrics_exporter_prometheus::PrometheusBuilder::new().install_recorder() {
+        use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
+        let installed = PrometheusBuilder::new()
+            .set_buckets_for_metric(
+                Matcher::Full(REQUEST_DURATION_METRIC.to_string()),
+                &REQUEST_LATENCY_BUCKETS_MS,
+            )
+            .and_then(|builder| {
+                builder.set_buckets_for_metric(
+                    Matcher::Full(REQUEST_TTFB_METRIC.to_string()),
+                    &REQUEST_LATENCY_BUCKETS_MS,
+                )
+            })
+            .and_then(|builder| builder.install_recorder());
+        match installed {
             Ok(handle) => {
                 let _ = METRICS_HANDLE.set(handle);
             }
@@ -587,29 +617,6 @@ fn parse_pricing_i128(raw: Option<String>) -> Option<i128> {
     raw.and_then(|v| v.parse::<i128>().ok())
 }
 
-fn scale_charge_with_multiplier(base_nano: i128, provider_multiplier: f64) -> Option<i128> {
-    if !provider_multiplier.is_finite() || provider_multiplier < 0.0 {
-        return None;
-    }
-
-    const SCALE: i128 = 1_000_000_000;
-    let multiplier_repr = format!("{provider_multiplier:.18}");
-    let mut parts = multiplier_repr.split('.');
-    let whole = parts.next().unwrap_or("0").parse::<i128>().ok()?;
-    let frac_raw = parts.next().unwrap_or("0");
-    let mut frac_nano = String::with_capacity(9);
-    for ch in frac_raw.chars().take(9) {
-        frac_nano.push(ch);
-    }
-    while frac_nano.len() < 9 {
-        frac_nano.push('0');
-    }
-    let frac = frac_nano.parse::<i128>().ok()?;
-
-    let multiplier_nano = whole.checked_mul(SCALE)?.checked_add(frac)?;
-    base_nano.checked_mul(multiplier_nano)?.checked_div(SCALE)
-}
-
 fn build_probe_usage_breakdown(prompt_tokens: u64, completion_tokens: u64) -> Value {
     json!({
         "version": 1,
@@ -636,6 +643,17 @@ fn build_probe_usage_breakdown(prompt_tokens: u64, completion_tokens: u64) -> Va
     })
 }
 
+/// Scales a probe's base charge by the channel multiplier like any other
+/// charge. A probe must never fail on billing, so an unusable multiplier
+/// bills the base charge instead.
+pub(crate) fn probe_charge_nano(base_charge: i128, provider_multiplier: f64) -> i128 {
+    crate::users::multiplier_to_nano(provider_multiplier)
+        .and_then(|multiplier_nano| {
+            crate::users::scale_nano_by_multiplier(base_charge, multiplier_nano)
+        })
+        .unwrap_or(base_charge)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn build_probe_billing_breakdown(
     provider_name: String,
@@ -748,9 +766,7 @@ fn spawn_active_probe_request_log(
                                 )
                             })
                             .unwrap_or_default();
-                        let final_charge =
-                            scale_charge_with_multiplier(base_charge, provider_multiplier)
-                                .unwrap_or(base_charge);
+                        let final_charge = probe_charge_nano(base_charge, provider_multiplier);
                         let billing = build_probe_billing_breakdown(
                             provider_name.clone(),
                             model.clone(),
@@ -809,6 +825,7 @@ fn spawn_active_probe_request_log(
             request_ip: None,
             reasoning_effort: None,
             tried_providers_json: None,
+            metadata_json: None,
             request_kind: Some(ACTIVE_PROBE_CONNECTIVITY_KIND.to_string()),
             effective_provider_type: None,
             affinity_hit: None,
@@ -836,11 +853,7 @@ fn resolve_database_dsn() -> String {
 }
 
 fn clear_channel_health_state(state: &mut ChannelHealthState, now: i64) {
-    state.healthy = true;
-    state.cooldown_until = None;
-    state.last_success_at = Some(now);
-    state.probe_success_count = 0;
-    state.last_probe_at = None;
+    state.mark_success(now);
 }
 
 fn channel_health_keys(
@@ -857,6 +870,7 @@ fn channel_health_keys(
 
 pub fn build_app(state: AppState) -> Router {
     let metrics_path = state.runtime.metrics_path.clone();
+    let max_request_body_bytes = state.runtime.max_request_body_bytes;
     let root_api_router = build_root_api_router(&metrics_path);
     let dashboard_api_router = build_dashboard_api_router();
     let api_router = root_api_router.clone().merge(dashboard_api_router);
@@ -865,7 +879,7 @@ pub fn build_app(state: AppState) -> Router {
         .nest("/api", api_router)
         .fallback(crate::frontend::frontend_fallback)
         .with_state(state)
-        .layer(DefaultBodyLimit::disable())
+        .layer(DefaultBodyLimit::max(max_request_body_bytes))
         .layer(SetRequestIdLayer::new(
             axum::http::header::HeaderName::from_static("x-request-id"),
             MakeRequestUuid,
@@ -874,8 +888,8 @@ pub fn build_app(state: AppState) -> Router {
             axum::http::header::HeaderName::from_static("x-request-id"),
         ))
         .layer(TraceLayer::new_for_http())
-        // 50 MiB body size limit
-        .layer(RequestBodyLimitLayer::new(50 * 1024 * 1024))
+        // Rejects by Content-Length before the body is read at all.
+        .layer(RequestBodyLimitLayer::new(max_request_body_bytes))
         // Security headers
         .layer(SetResponseHeaderLayer::overriding(
             axum::http::header::HeaderName::from_static("x-content-type-options"),
@@ -907,6 +921,10 @@ fn build_v1_router() -> Router<AppState> {
             post(crate::handlers::create_chat_completions),
         )
         .route("/v1/embeddings", post(crate::handlers::create_embeddings))
+        .route(
+            "/v1/audio/speech",
+            post(crate::handlers::audio_api::create_speech),
+        )
         .route("/v1/messages", post(crate::handlers::create_messages))
         .route(
             "/v1/images/generations",
@@ -1019,6 +1037,10 @@ fn build_dashboard_api_router() -> Router<AppState> {
             "/dashboard/config",
             get(crate::dashboard_handlers::get_config_overview),
         )
+        .route(
+            "/dashboard/admin/reload",
+            post(crate::dashboard_handlers::reload_runtime_config),
+        )
         .route(
             "/dashboard/groups",
             get(crate::dashboard_handlers::list_dashboard_groups),
@@ -1051,6 +1073,10 @@ fn build_dashboard_api_router() -> Router<AppState> {
             "/dashboard/transforms/registry",
             get(crate::dashboard_handlers::get_transform_registry),
         )
+        .route(
+            "/dashboard/transforms/preview",
+            post(crate::dashboard_handlers::preview_transforms),
+        )
         // Model registry API routes
         .route(
             "/dashboard/models",
@@ -1112,6 +1138,14 @@ fn build_dashboard_api_router() -> Router<AppState> {
             "/dashboard/providers/{provider_id}/channels/{channel_id}/test",
             post(crate::dashboard_handlers::test_channel),
         )
+        .route(
+            "/dashboard/channels/health",
+            get(crate::dashboard_handlers::list_channel_health),
+        )
+        .route(
+            "/dashboard/channels/{channel_id}/reset",
+            post(crate::dashboard_handlers::reset_channel_health),
+        )
         .route(
             "/dashboard/fetch-channel-models",
             post(crate::dashboard_handlers::fetch_channel_models),
@@ -16,11 +16,29 @@ pub struct AuthResult {
     pub effective_groups: Option<Vec<String>>,
     pub model_limits_enabled: bool,
     pub model_limits: Vec<String>,
+    pub allow_models: Vec<String>,
+    pub deny_models: Vec<String>,
     pub ip_whitelist: Vec<String>,
     pub sub_account_enabled: bool,
     pub sub_account_balance_nano: String,
     pub reasoning_envelope_enabled: bool,
     pub request_capture_mode: RequestCaptureMode,
+    pub response_cache_enabled: bool,
+    pub max_request_charge_nano_usd: Option<i64>,
+    /// Client-supplied `x-monoize-metadata` tags copied onto request logs.
+    pub request_metadata: Option<serde_json::Value>,
+    /// `x-monoize-force-*` routing overrides; only ever set for admin keys.
+    pub routing_override: Option<RoutingOverride>,
+    /// Client `x-monoize-timeout-ms` override, already clamped to the
+    /// server ceiling.
+    pub request_timeout_ms: Option<u64>,
+}
+
+/// Pins a request to one channel and/or upstream model for ops debugging.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RoutingOverride {
+    pub channel_id: Option<String>,
+    pub upstream_model: Option<String>,
 }
 
 #[derive(Clone)]
@@ -63,11 +81,18 @@ impl AuthState {
                             effective_groups,
                             model_limits_enabled: api_key.model_limits_enabled,
                             model_limits: api_key.model_limits,
+                            allow_models: api_key.allow_models,
+                            deny_models: api_key.deny_models,
                             ip_whitelist: api_key.ip_whitelist,
                             sub_account_enabled: api_key.sub_account_enabled,
                             sub_account_balance_nano: api_key.sub_account_balance_nano,
                             reasoning_envelope_enabled: api_key.reasoning_envelope_enabled,
                             request_capture_mode: api_key.request_capture_mode,
+                            response_cache_enabled: api_key.response_cache_enabled,
+                            max_request_charge_nano_usd: api_key.max_request_charge_nano_usd,
+                            request_metadata: None,
+                            routing_override: None,
+                            request_timeout_ms: None,
                         });
                     }
                     Ok(None) => {}
@@ -118,6 +143,8 @@ mod tests {
                     sub_account_enabled: false,
                     model_limits_enabled: false,
                     model_limits: Vec::new(),
+                    allow_models: Vec::new(),
+                    deny_models: Vec::new(),
                     ip_whitelist: Vec::new(),
                     allowed_groups: Vec::new(),
                     max_multiplier: None,
@@ -125,6 +152,8 @@ mod tests {
                     model_redirects: Vec::new(),
                     reasoning_envelope_enabled: true,
                     request_capture_mode: RequestCaptureMode::Off,
+                    response_cache_enabled: false,
+                    max_request_charge_nano_usd: None,
                 },
                 false,
             )
@@ -162,6 +191,8 @@ mod tests {
                     sub_account_enabled: false,
                     model_limits_enabled: false,
                     model_limits: Vec::new(),
+                    allow_models: Vec::new(),
+                    deny_models: Vec::new(),
                     ip_whitelist: Vec::new(),
                     allowed_groups: vec![" TEAM-B ".to_string()],
                     max_multiplier: None,
@@ -169,6 +200,8 @@ mod tests {
                     model_redirects: Vec::new(),
                     reasoning_envelope_enabled: true,
                     request_capture_mode: RequestCaptureMode::Off,
+                    response_cache_enabled: false,
+                    max_request_charge_nano_usd: None,
                 },
                 false,
             )
@@ -208,6 +241,8 @@ mod tests {
                     sub_account_enabled: false,
                     model_limits_enabled: false,
                     model_limits: Vec::new(),
+                    allow_models: Vec::new(),
+                    deny_models: Vec::new(),
                     ip_whitelist: Vec::new(),
                     allowed_groups: vec!["team-a".to_string()],
                     max_multiplier: None,
@@ -215,6 +250,8 @@ mod tests {
                     model_redirects: Vec::new(),
                     reasoning_envelope_enabled: true,
                     request_capture_mode: RequestCaptureMode::Off,
+                    response_cache_enabled: false,
+                    max_request_charge_nano_usd: None,
                 },
                 false,
             )
@@ -36,6 +36,15 @@ impl DbBillingRateRecord {
             .parse::<i128>()
             .map_err(|_| format!("invalid unit_price_nano_usd for {}", self.id))
     }
+
+    /// Unit price actually billed: zero for a rate tagged `"free": true` in
+    /// its raw definition, whatever its listed price.
+    pub fn charged_unit_price_nano(&self) -> Result<i128, String> {
+        if self.raw_json.get("free").and_then(Value::as_bool) == Some(true) {
+            return Ok(0);
+        }
+        self.unit_price_nano()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -16,9 +16,12 @@ pub struct ProviderConfig {
     pub strategy: Option<GroupStrategyConfig>,
     #[serde(default)]
     pub members: Vec<GroupMemberConfig>,
+    /// Headers added to every upstream request for this provider.
+    #[serde(default)]
+    pub extra_headers: Vec<(String, String)>,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum ProviderType {
     Responses,
@@ -29,6 +29,10 @@ pub struct CreateApiKeyRequest {
     #[serde(default)]
     pub model_limits: Vec<String>,
     #[serde(default)]
+    pub allow_models: Vec<String>,
+    #[serde(default)]
+    pub deny_models: Vec<String>,
+    #[serde(default)]
     pub ip_whitelist: Vec<String>,
     #[serde(default)]
     pub allowed_groups: Vec<String>,
@@ -42,6 +46,10 @@ pub struct CreateApiKeyRequest {
     pub reasoning_envelope_enabled: bool,
     #[serde(default)]
     pub request_capture_mode: RequestCaptureMode,
+    #[serde(default)]
+    pub response_cache_enabled: bool,
+    #[serde(default)]
+    pub max_request_charge_nano_usd: Option<i64>,
 }
 
 fn default_true() -> bool {
@@ -67,6 +75,8 @@ pub struct ApiKeyResponse {
     pub sub_account_balance_usd: String,
     pub model_limits_enabled: bool,
     pub model_limits: Vec<String>,
+    pub allow_models: Vec<String>,
+    pub deny_models: Vec<String>,
     pub ip_whitelist: Vec<String>,
     pub allowed_groups: Vec<String>,
     pub max_multiplier: Option<f64>,
@@ -74,6 +84,8 @@ pub struct ApiKeyResponse {
     pub model_redirects: Vec<ModelRedirectRule>,
     pub reasoning_envelope_enabled: bool,
     pub request_capture_mode: RequestCaptureMode,
+    pub response_cache_enabled: bool,
+    pub max_request_charge_nano_usd: Option<i64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -89,6 +101,8 @@ pub struct ApiKeyCreatedResponse {
     pub sub_account_balance_usd: String,
     pub model_limits_enabled: bool,
     pub model_limits: Vec<String>,
+    pub allow_models: Vec<String>,
+    pub deny_models: Vec<String>,
     pub ip_whitelist: Vec<String>,
     pub allowed_groups: Vec<String>,
     pub max_multiplier: Option<f64>,
@@ -96,6 +110,8 @@ pub struct ApiKeyCreatedResponse {
     pub model_redirects: Vec<ModelRedirectRule>,
     pub reasoning_envelope_enabled: bool,
     pub request_capture_mode: RequestCaptureMode,
+    pub response_cache_enabled: bool,
+    pub max_request_charge_nano_usd: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -105,6 +121,8 @@ pub struct UpdateApiKeyRequest {
     pub sub_account_enabled: Option<bool>,
     pub model_limits_enabled: Option<bool>,
     pub model_limits: Option<Vec<String>>,
+    pub allow_models: Option<Vec<String>>,
+    pub deny_models: Option<Vec<String>>,
     pub ip_whitelist: Option<Vec<String>>,
     pub allowed_groups: Option<Vec<String>>,
     pub max_multiplier: Option<f64>,
@@ -112,6 +130,8 @@ pub struct UpdateApiKeyRequest {
     pub model_redirects: Option<Vec<ModelRedirectRule>>,
     pub reasoning_envelope_enabled: Option<bool>,
     pub request_capture_mode: Option<RequestCaptureMode>,
+    pub response_cache_enabled: Option<bool>,
+    pub max_request_charge_nano_usd: Option<i64>,
     pub expires_at: Option<String>,
 }
 
@@ -151,6 +171,8 @@ pub async fn list_my_api_keys(
                 sub_account_balance_usd: usd,
                 model_limits_enabled: k.model_limits_enabled,
                 model_limits: k.model_limits,
+                allow_models: k.allow_models,
+                deny_models: k.deny_models,
                 ip_whitelist: k.ip_whitelist,
                 allowed_groups: k.allowed_groups,
                 max_multiplier: k.max_multiplier,
@@ -158,6 +180,8 @@ pub async fn list_my_api_keys(
                 model_redirects: k.model_redirects,
                 reasoning_envelope_enabled: k.reasoning_envelope_enabled,
                 request_capture_mode: k.request_capture_mode,
+                response_cache_enabled: k.response_cache_enabled,
+                max_request_charge_nano_usd: k.max_request_charge_nano_usd,
             }
         })
         .collect();
@@ -204,6 +228,8 @@ pub async fn create_api_key(
         sub_account_enabled: body.sub_account_enabled,
         model_limits_enabled: body.model_limits_enabled,
         model_limits: body.model_limits,
+        allow_models: body.allow_models,
+        deny_models: body.deny_models,
         ip_whitelist: body.ip_whitelist,
         allowed_groups: body.allowed_groups,
         max_multiplier: body.max_multiplier,
@@ -211,6 +237,8 @@ pub async fn create_api_key(
         model_redirects: body.model_redirects,
         reasoning_envelope_enabled: body.reasoning_envelope_enabled,
         request_capture_mode: body.request_capture_mode,
+        response_cache_enabled: body.response_cache_enabled,
+        max_request_charge_nano_usd: body.max_request_charge_nano_usd,
     };
 
     let is_admin = user.role.can_manage_system();
@@ -239,6 +267,8 @@ pub async fn create_api_key(
             sub_account_balance_usd: usd,
             model_limits_enabled: api_key.model_limits_enabled,
             model_limits: api_key.model_limits,
+            allow_models: api_key.allow_models,
+            deny_models: api_key.deny_models,
             ip_whitelist: api_key.ip_whitelist,
             allowed_groups: api_key.allowed_groups,
             max_multiplier: api_key.max_multiplier,
@@ -246,6 +276,8 @@ pub async fn create_api_key(
             model_redirects: api_key.model_redirects,
             reasoning_envelope_enabled: api_key.reasoning_envelope_enabled,
             request_capture_mode: api_key.request_capture_mode,
+            response_cache_enabled: api_key.response_cache_enabled,
+            max_request_charge_nano_usd: api_key.max_request_charge_nano_usd,
         }),
     ))
 }
@@ -322,6 +354,8 @@ pub async fn get_api_key(
             sub_account_balance_usd: usd,
             model_limits_enabled: api_key.model_limits_enabled,
             model_limits: api_key.model_limits,
+            allow_models: api_key.allow_models,
+            deny_models: api_key.deny_models,
             ip_whitelist: api_key.ip_whitelist,
             allowed_groups: api_key.allowed_groups,
             max_multiplier: api_key.max_multiplier,
@@ -329,6 +363,8 @@ pub async fn get_api_key(
             model_redirects: api_key.model_redirects,
             reasoning_envelope_enabled: api_key.reasoning_envelope_enabled,
             request_capture_mode: api_key.request_capture_mode,
+            response_cache_enabled: api_key.response_cache_enabled,
+            max_request_charge_nano_usd: api_key.max_request_charge_nano_usd,
         }
     }))
 }
@@ -366,6 +402,8 @@ pub async fn update_api_key(
         sub_account_enabled: body.sub_account_enabled,
         model_limits_enabled: body.model_limits_enabled,
         model_limits: body.model_limits,
+        allow_models: body.allow_models,
+        deny_models: body.deny_models,
         ip_whitelist: body.ip_whitelist,
         allowed_groups: body.allowed_groups,
         max_multiplier: body.max_multiplier,
@@ -373,6 +411,8 @@ pub async fn update_api_key(
         model_redirects: body.model_redirects,
         reasoning_envelope_enabled: body.reasoning_envelope_enabled,
         request_capture_mode: body.request_capture_mode,
+        response_cache_enabled: body.response_cache_enabled,
+        max_request_charge_nano_usd: body.max_request_charge_nano_usd,
         expires_at: body.expires_at,
     };
 
@@ -402,6 +442,8 @@ pub async fn update_api_key(
         sub_account_balance_usd: usd,
         model_limits_enabled: updated_key.model_limits_enabled,
         model_limits: updated_key.model_limits,
+        allow_models: updated_key.allow_models,
+        deny_models: updated_key.deny_models,
         ip_whitelist: updated_key.ip_whitelist,
         allowed_groups: updated_key.allowed_groups,
         max_multiplier: updated_key.max_multiplier,
@@ -409,6 +451,8 @@ pub async fn update_api_key(
         model_redirects: updated_key.model_redirects,
         reasoning_envelope_enabled: updated_key.reasoning_envelope_enabled,
         request_capture_mode: updated_key.request_capture_mode,
+        response_cache_enabled: updated_key.response_cache_enabled,
+        max_request_charge_nano_usd: updated_key.max_request_charge_nano_usd,
     }))
 }
 
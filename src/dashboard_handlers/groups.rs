@@ -85,6 +85,7 @@ mod tests {
             listen: "127.0.0.1:0".to_string(),
             metrics_path: "/metrics".to_string(),
             database_dsn: "sqlite::memory:".to_string(),
+            max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
         })
         .await
         .expect("state loads");
@@ -119,6 +120,8 @@ mod tests {
                     sub_account_enabled: false,
                     model_limits_enabled: false,
                     model_limits: Vec::new(),
+                    allow_models: Vec::new(),
+                    deny_models: Vec::new(),
                     ip_whitelist: Vec::new(),
                     allowed_groups: vec!["gamma".to_string(), "Beta".to_string()],
                     max_multiplier: None,
@@ -126,6 +129,8 @@ mod tests {
                     model_redirects: Vec::new(),
                     reasoning_envelope_enabled: true,
                     request_capture_mode: crate::users::RequestCaptureMode::Off,
+                    response_cache_enabled: false,
+                    max_request_charge_nano_usd: None,
                 },
                 false,
             )
@@ -150,7 +155,12 @@ mod tests {
                     provider_type: crate::monoize_routing::MonoizeProviderType::Responses,
                     base_url: "https://example.com".to_string(),
                     api_key: Some("secret".to_string()),
+                    api_keys: None,
                     weight: 1,
+                    priority: 0,
+                    max_concurrency: None,
+                    org_id: None,
+                    project_id: None,
                     enabled: true,
                     passive_failure_count_threshold_override: None,
                     passive_window_seconds_override: None,
@@ -161,6 +171,7 @@ mod tests {
                         crate::monoize_routing::MonoizeModelEntry {
                             redirect: None,
                             multiplier: 1.0,
+                            supports_streaming: None,
                         },
                     )]),
                     active_probe_enabled_override: None,
@@ -177,6 +188,9 @@ mod tests {
                 request_timeout_ms_override: None,
                 extra_fields_whitelist: None,
                 strip_cross_protocol_nested_extra: None,
+                debug_log_bodies: false,
+                default_temperature: None,
+                default_top_p: None,
             })
             .await
             .expect("provider created");
@@ -7,6 +7,7 @@ mod model_registry;
 mod providers;
 mod session_helpers;
 mod settings;
+mod transforms;
 mod users;
 
 #[cfg(test)]
@@ -38,14 +39,16 @@ pub use model_registry::{
     update_model, upsert_model_metadata,
 };
 pub use providers::{
-    FetchChannelModelsRequest, TestChannelRequest, create_provider, delete_provider,
-    fetch_channel_models, fetch_provider_models, get_provider, get_provider_presets,
-    get_transform_registry, list_providers, reorder_providers, test_channel, update_provider,
+    ChannelHealthEntry, FetchChannelModelsRequest, TestChannelRequest, create_provider,
+    delete_provider, fetch_channel_models, fetch_provider_models, get_provider,
+    get_provider_presets, get_transform_registry, list_channel_health, list_providers,
+    reorder_providers, reset_channel_health, test_channel, update_provider,
 };
 pub use settings::{
     UpdateSettingsRequest, get_config_overview, get_dashboard_stats, get_public_settings,
-    get_settings, update_settings,
+    get_settings, reload_runtime_config, update_settings,
 };
+pub use transforms::{PreviewDownstream, TransformPreviewRequest, preview_transforms};
 pub use users::{
     CreateUserRequest, UpdateUserRequest, create_user, delete_user, get_user, list_users,
     update_user,
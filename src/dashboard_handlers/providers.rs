@@ -12,7 +12,7 @@ use axum::Json;
 use axum::extract::{Path, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use std::collections::{HashMap, HashSet};
 
@@ -842,6 +842,85 @@ pub async fn test_channel(
     })))
 }
 
+#[derive(Debug, Serialize)]
+pub struct ChannelHealthEntry {
+    pub channel_id: String,
+    pub model: Option<String>,
+    pub status: &'static str,
+    pub failure_count: u32,
+    #[serde(flatten)]
+    pub state: ChannelHealthState,
+}
+
+pub async fn list_channel_health(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> AppResult<impl IntoResponse> {
+    require_admin(&headers, &state).await?;
+
+    let now = chrono::Utc::now().timestamp();
+    let health = state.channel_health.lock().await;
+    let mut entries: Vec<ChannelHealthEntry> = health
+        .iter()
+        .map(|(key, health_state)| {
+            let (channel_id, model) = match key.split_once("::") {
+                Some((channel_id, model)) => (channel_id.to_string(), Some(model.to_string())),
+                None => (key.clone(), None),
+            };
+            ChannelHealthEntry {
+                channel_id,
+                model,
+                status: health_state.status(now),
+                failure_count: health_state.failure_count(),
+                state: health_state.clone(),
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        a.channel_id
+            .cmp(&b.channel_id)
+            .then_with(|| a.model.cmp(&b.model))
+    });
+
+    Ok(Json(entries))
+}
+
+pub async fn reset_channel_health(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(channel_id): Path<String>,
+) -> AppResult<impl IntoResponse> {
+    require_admin(&headers, &state).await?;
+
+    let providers = state
+        .monoize_store
+        .list_providers()
+        .await
+        .map_err(|e| AppError::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", e))?;
+    if !providers
+        .iter()
+        .any(|provider| provider.channels.iter().any(|c| c.id == channel_id))
+    {
+        return Err(AppError::new(
+            StatusCode::NOT_FOUND,
+            "not_found",
+            "channel not found",
+        ));
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let prefix = format!("{channel_id}::");
+    let mut health = state.channel_health.lock().await;
+    for (key, entry) in health.iter_mut() {
+        if key.as_str() == channel_id || key.starts_with(&prefix) {
+            entry.mark_success(now);
+            entry.passive_samples.clear();
+        }
+    }
+
+    Ok(Json(json!({ "success": true })))
+}
+
 pub async fn get_transform_registry(State(state): State<AppState>) -> AppResult<impl IntoResponse> {
     let mut items: Vec<Value> = state
         .transform_registry
@@ -1001,7 +1080,12 @@ mod tests {
                 provider_type: MonoizeProviderType::ChatCompletion,
                 base_url,
                 api_key: Some("stored-secret".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -1012,6 +1096,7 @@ mod tests {
                     MonoizeModelEntry {
                         redirect: None,
                         multiplier: 1.0,
+                        supports_streaming: None,
                     },
                 )]),
                 active_probe_enabled_override: None,
@@ -1029,6 +1114,9 @@ mod tests {
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
         }
     }
 
@@ -1039,6 +1127,7 @@ mod tests {
             listen: "127.0.0.1:0".to_string(),
             metrics_path: "/metrics".to_string(),
             database_dsn: "sqlite::memory:".to_string(),
+            max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
         })
         .await
         .expect("state loads");
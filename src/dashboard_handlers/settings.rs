@@ -21,6 +21,10 @@ pub struct UpdateSettingsRequest {
     pub api_base_url: Option<String>,
     pub global_transforms: Option<Vec<TransformRuleConfig>>,
     pub reasoning_suffix_map: Option<std::collections::HashMap<String, String>>,
+    pub model_aliases: Option<std::collections::HashMap<String, String>>,
+    pub model_fallbacks: Option<std::collections::HashMap<String, Vec<String>>>,
+    pub model_hedge_after_ms: Option<std::collections::HashMap<String, u64>>,
+    pub model_fallback_report_actual_model: Option<bool>,
     pub monoize_active_probe_enabled: Option<bool>,
     pub monoize_active_probe_interval_seconds: Option<u64>,
     pub monoize_active_probe_success_threshold: Option<u32>,
@@ -33,11 +37,32 @@ pub struct UpdateSettingsRequest {
     pub monoize_passive_rate_limit_cooldown_seconds: Option<u64>,
     pub monoize_request_timeout_ms: Option<u64>,
     pub monoize_stream_idle_timeout_ms: Option<u64>,
+    pub monoize_max_request_timeout_ms: Option<u64>,
+    pub monoize_connect_timeout_ms: Option<u64>,
+    pub monoize_read_timeout_ms: Option<u64>,
     pub monoize_enable_estimated_billing: Option<bool>,
     pub monoize_extra_fields_whitelist: Option<std::collections::HashMap<String, Vec<String>>>,
     pub monoize_strip_cross_protocol_nested_extra: Option<bool>,
     pub monoize_request_capture_enabled: Option<bool>,
     pub monoize_request_capture_retention_days: Option<u64>,
+    pub monoize_response_cache_ttl_seconds: Option<u64>,
+    pub monoize_response_cache_max_entries: Option<u64>,
+    pub monoize_idempotency_ttl_seconds: Option<u64>,
+    pub monoize_idempotency_max_entries: Option<u64>,
+    pub monoize_decode_retry_limit: Option<u64>,
+    pub monoize_missing_pricing_policy: Option<crate::settings::MissingPricingPolicy>,
+    pub monoize_max_request_charge_nano_usd: Option<u64>,
+    pub monoize_max_embedding_batch: Option<u64>,
+    pub monoize_max_buffered_transcodes: Option<u64>,
+    pub monoize_finish_reason_map:
+        Option<std::collections::HashMap<String, crate::urp::FinishReason>>,
+    pub monoize_expose_routing_headers: Option<bool>,
+    pub monoize_failure_weighted_routing: Option<bool>,
+    pub monoize_estimate_gemini_stream_usage: Option<bool>,
+    pub monoize_validate_requests: Option<bool>,
+    pub monoize_sse_retry_ms: Option<u64>,
+    pub monoize_sse_event_ids: Option<bool>,
+    pub monoize_token_estimator: Option<crate::token_estimator::TokenEstimatorKind>,
 }
 
 pub async fn get_settings(
@@ -97,6 +122,40 @@ pub async fn update_settings(
     if let Some(v) = body.reasoning_suffix_map {
         settings.reasoning_suffix_map = v;
     }
+    if let Some(v) = body.model_aliases {
+        settings.model_aliases = v
+            .into_iter()
+            .map(|(alias, model)| (alias.trim().to_string(), model.trim().to_string()))
+            .filter(|(alias, model)| !alias.is_empty() && !model.is_empty())
+            .collect();
+    }
+    if let Some(v) = body.model_fallbacks {
+        settings.model_fallbacks = v
+            .into_iter()
+            .filter_map(|(model, fallbacks)| {
+                let model = model.trim().to_string();
+                let mut seen = std::collections::HashSet::new();
+                let fallbacks: Vec<String> = fallbacks
+                    .into_iter()
+                    .map(|fallback| fallback.trim().to_string())
+                    .filter(|fallback| {
+                        !fallback.is_empty() && *fallback != model && seen.insert(fallback.clone())
+                    })
+                    .collect();
+                (!model.is_empty() && !fallbacks.is_empty()).then_some((model, fallbacks))
+            })
+            .collect();
+    }
+    if let Some(v) = body.model_hedge_after_ms {
+        settings.model_hedge_after_ms = v
+            .into_iter()
+            .map(|(model, delay_ms)| (model.trim().to_string(), delay_ms))
+            .filter(|(model, delay_ms)| !model.is_empty() && *delay_ms > 0)
+            .collect();
+    }
+    if let Some(v) = body.model_fallback_report_actual_model {
+        settings.model_fallback_report_actual_model = v;
+    }
     if let Some(v) = body.monoize_active_probe_enabled {
         settings.monoize_active_probe_enabled = v;
     }
@@ -142,6 +201,15 @@ pub async fn update_settings(
     if let Some(v) = body.monoize_stream_idle_timeout_ms {
         settings.monoize_stream_idle_timeout_ms = v.max(1);
     }
+    if let Some(v) = body.monoize_max_request_timeout_ms {
+        settings.monoize_max_request_timeout_ms = v.max(1);
+    }
+    if let Some(v) = body.monoize_connect_timeout_ms {
+        settings.monoize_connect_timeout_ms = v.max(1);
+    }
+    if let Some(v) = body.monoize_read_timeout_ms {
+        settings.monoize_read_timeout_ms = v.max(1);
+    }
     if let Some(v) = body.monoize_enable_estimated_billing {
         settings.monoize_enable_estimated_billing = v;
     }
@@ -157,6 +225,57 @@ pub async fn update_settings(
     if let Some(v) = body.monoize_request_capture_retention_days {
         settings.monoize_request_capture_retention_days = v.max(1);
     }
+    if let Some(v) = body.monoize_response_cache_ttl_seconds {
+        settings.monoize_response_cache_ttl_seconds = v.max(1);
+    }
+    if let Some(v) = body.monoize_response_cache_max_entries {
+        settings.monoize_response_cache_max_entries = v.max(1);
+    }
+    if let Some(v) = body.monoize_idempotency_ttl_seconds {
+        settings.monoize_idempotency_ttl_seconds = v.max(1);
+    }
+    if let Some(v) = body.monoize_idempotency_max_entries {
+        settings.monoize_idempotency_max_entries = v.max(1);
+    }
+    if let Some(v) = body.monoize_decode_retry_limit {
+        settings.monoize_decode_retry_limit = v;
+    }
+    if let Some(v) = body.monoize_missing_pricing_policy {
+        settings.monoize_missing_pricing_policy = v;
+    }
+    if let Some(v) = body.monoize_max_request_charge_nano_usd {
+        settings.monoize_max_request_charge_nano_usd = v;
+    }
+    if let Some(v) = body.monoize_max_embedding_batch {
+        settings.monoize_max_embedding_batch = v;
+    }
+    if let Some(v) = body.monoize_max_buffered_transcodes {
+        settings.monoize_max_buffered_transcodes = v;
+    }
+    if let Some(v) = body.monoize_finish_reason_map {
+        settings.monoize_finish_reason_map = v;
+    }
+    if let Some(v) = body.monoize_expose_routing_headers {
+        settings.monoize_expose_routing_headers = v;
+    }
+    if let Some(v) = body.monoize_failure_weighted_routing {
+        settings.monoize_failure_weighted_routing = v;
+    }
+    if let Some(v) = body.monoize_estimate_gemini_stream_usage {
+        settings.monoize_estimate_gemini_stream_usage = v;
+    }
+    if let Some(v) = body.monoize_validate_requests {
+        settings.monoize_validate_requests = v;
+    }
+    if let Some(v) = body.monoize_sse_retry_ms {
+        settings.monoize_sse_retry_ms = v;
+    }
+    if let Some(v) = body.monoize_sse_event_ids {
+        settings.monoize_sse_event_ids = v;
+    }
+    if let Some(v) = body.monoize_token_estimator {
+        settings.monoize_token_estimator = v;
+    }
 
     settings_store
         .update_all(&settings)
@@ -168,26 +287,7 @@ pub async fn update_settings(
         .await
         .map_err(|e| AppError::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", e))?;
 
-    {
-        let mut rt = state.monoize_runtime.write().await;
-        rt.request_timeout_ms = updated.monoize_request_timeout_ms.max(1);
-        rt.stream_idle_timeout_ms = updated.monoize_stream_idle_timeout_ms.max(1);
-        rt.enable_estimated_billing = updated.monoize_enable_estimated_billing;
-        rt.passive_failure_count_threshold = updated.monoize_passive_failure_threshold.max(1);
-        rt.passive_cooldown_seconds = updated.monoize_passive_cooldown_seconds.max(1);
-        rt.passive_window_seconds = updated.monoize_passive_window_seconds.max(1);
-        rt.passive_rate_limit_cooldown_seconds =
-            updated.monoize_passive_rate_limit_cooldown_seconds.max(1);
-        rt.active_enabled = updated.monoize_active_probe_enabled;
-        rt.active_interval_seconds = updated.monoize_active_probe_interval_seconds.max(1);
-        rt.active_success_threshold = updated.monoize_active_probe_success_threshold.max(1);
-        rt.active_probe_model = updated.monoize_active_probe_model.clone();
-        rt.global_transforms = updated.global_transforms.clone();
-        rt.extra_fields_whitelist = updated.monoize_extra_fields_whitelist.clone();
-        rt.strip_cross_protocol_nested_extra = updated.monoize_strip_cross_protocol_nested_extra;
-        rt.request_capture_enabled = updated.monoize_request_capture_enabled;
-        rt.request_capture_retention_days = updated.monoize_request_capture_retention_days.max(1);
-    }
+    state.monoize_runtime.write().await.apply_settings(&updated);
 
     Ok(Json(updated))
 }
@@ -249,6 +349,59 @@ pub async fn get_config_overview(
     })))
 }
 
+/// Re-reads persisted configuration and swaps it into the running process.
+///
+/// Providers and channels are read from the database on every request, so they
+/// need no reload. This refreshes the cached runtime tunables (including global
+/// transforms, aliases and fallbacks) and the model registry. In-flight requests
+/// keep the snapshot they already cloned. Process-level settings are reported
+/// under `restart_required` and are not changed.
+pub async fn reload_runtime_config(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> AppResult<impl IntoResponse> {
+    require_admin(&headers, &state).await?;
+
+    let settings = state
+        .settings_store
+        .get_all()
+        .await
+        .map_err(|e| AppError::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", e))?;
+    let model_records = state
+        .model_registry_store
+        .list_enabled_models()
+        .await
+        .map_err(|e| AppError::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", e))?;
+    let providers_count = state
+        .monoize_store
+        .provider_count()
+        .await
+        .map_err(|e| AppError::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", e))?;
+
+    state
+        .monoize_runtime
+        .write()
+        .await
+        .apply_settings(&settings);
+    let models_count = model_records.len();
+    state.model_registry.replace_db_records(model_records).await;
+
+    Ok(Json(json!({
+        "reloaded": ["runtime_settings", "model_registry"],
+        "live": ["providers"],
+        "restart_required": [
+            "listen",
+            "metrics_path",
+            "database_dsn",
+            "max_request_body_bytes",
+            "monoize_connect_timeout_ms",
+            "monoize_read_timeout_ms",
+        ],
+        "providers_count": providers_count,
+        "model_registry_count": models_count,
+    })))
+}
+
 pub async fn get_public_settings(State(state): State<AppState>) -> AppResult<impl IntoResponse> {
     let settings_store = &state.settings_store;
 
@@ -53,6 +53,7 @@ fn provider_pricing_model_uses_redirect_when_present() {
     let entry = MonoizeModelEntry {
         redirect: Some("  gpt-5-target  ".to_string()),
         multiplier: 1.0,
+        supports_streaming: None,
     };
     assert_eq!(
         provider_pricing_model("gpt-5-logical", &entry),
@@ -65,6 +66,7 @@ fn provider_pricing_model_falls_back_to_logical_when_redirect_blank() {
     let entry = MonoizeModelEntry {
         redirect: Some("   ".to_string()),
         multiplier: 1.0,
+        supports_streaming: None,
     };
     assert_eq!(
         provider_pricing_model("gpt-5-logical", &entry),
@@ -188,7 +190,12 @@ fn dashboard_provider_response_includes_groups_and_channel_hides_api_key() {
         provider_type: MonoizeProviderType::Responses,
         base_url: "https://example.com".to_string(),
         api_key: "secret".to_string(),
+        api_keys: Vec::new(),
         weight: 1,
+        priority: 0,
+        max_concurrency: None,
+        org_id: None,
+        project_id: None,
         enabled: true,
         passive_failure_count_threshold_override: None,
         passive_cooldown_seconds_override: None,
@@ -199,6 +206,7 @@ fn dashboard_provider_response_includes_groups_and_channel_hides_api_key() {
             crate::monoize_routing::MonoizeModelEntry {
                 redirect: None,
                 multiplier: 1.0,
+                supports_streaming: None,
             },
         )]),
         active_probe_enabled_override: None,
@@ -228,6 +236,9 @@ fn dashboard_provider_response_includes_groups_and_channel_hides_api_key() {
         request_timeout_ms_override: None,
         extra_fields_whitelist: None,
         strip_cross_protocol_nested_extra: None,
+        debug_log_bodies: false,
+        default_temperature: None,
+        default_top_p: None,
         groups: vec!["alpha".to_string(), "beta".to_string()],
         enabled: true,
         priority: 0,
@@ -549,6 +560,8 @@ async fn dashboard_api_key_allowed_groups_round_trip_through_store_and_responses
                 sub_account_enabled: create_body.sub_account_enabled,
                 model_limits_enabled: create_body.model_limits_enabled,
                 model_limits: create_body.model_limits,
+                allow_models: create_body.allow_models,
+                deny_models: create_body.deny_models,
                 ip_whitelist: create_body.ip_whitelist,
                 allowed_groups: create_body.allowed_groups,
                 max_multiplier: create_body.max_multiplier,
@@ -556,6 +569,8 @@ async fn dashboard_api_key_allowed_groups_round_trip_through_store_and_responses
                 model_redirects: create_body.model_redirects,
                 reasoning_envelope_enabled: create_body.reasoning_envelope_enabled,
                 request_capture_mode: create_body.request_capture_mode,
+                response_cache_enabled: create_body.response_cache_enabled,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -580,6 +595,8 @@ async fn dashboard_api_key_allowed_groups_round_trip_through_store_and_responses
         sub_account_balance_usd: usd,
         model_limits_enabled: created.model_limits_enabled,
         model_limits: created.model_limits.clone(),
+        allow_models: created.allow_models.clone(),
+        deny_models: created.deny_models.clone(),
         ip_whitelist: created.ip_whitelist.clone(),
         allowed_groups: created.allowed_groups.clone(),
         max_multiplier: created.max_multiplier,
@@ -587,6 +604,8 @@ async fn dashboard_api_key_allowed_groups_round_trip_through_store_and_responses
         model_redirects: created.model_redirects.clone(),
         reasoning_envelope_enabled: created.reasoning_envelope_enabled,
         request_capture_mode: created.request_capture_mode,
+        response_cache_enabled: created.response_cache_enabled,
+        max_request_charge_nano_usd: None,
     })
     .expect("created response serializes");
     assert_eq!(
@@ -615,6 +634,8 @@ async fn dashboard_api_key_allowed_groups_round_trip_through_store_and_responses
                 sub_account_enabled: None,
                 model_limits_enabled: None,
                 model_limits: None,
+                allow_models: None,
+                deny_models: None,
                 ip_whitelist: None,
                 allowed_groups: update_body.allowed_groups,
                 max_multiplier: None,
@@ -622,6 +643,8 @@ async fn dashboard_api_key_allowed_groups_round_trip_through_store_and_responses
                 model_redirects: None,
                 reasoning_envelope_enabled: None,
                 request_capture_mode: update_body.request_capture_mode,
+                response_cache_enabled: update_body.response_cache_enabled,
+                max_request_charge_nano_usd: None,
                 expires_at: None,
             },
             false,
@@ -666,6 +689,8 @@ async fn dashboard_api_key_allowed_groups_round_trip_through_store_and_responses
         sub_account_balance_usd: fusd,
         model_limits_enabled: fetched.model_limits_enabled,
         model_limits: fetched.model_limits,
+        allow_models: fetched.allow_models,
+        deny_models: fetched.deny_models,
         ip_whitelist: fetched.ip_whitelist,
         allowed_groups: fetched.allowed_groups,
         max_multiplier: fetched.max_multiplier,
@@ -673,6 +698,8 @@ async fn dashboard_api_key_allowed_groups_round_trip_through_store_and_responses
         model_redirects: fetched.model_redirects,
         reasoning_envelope_enabled: fetched.reasoning_envelope_enabled,
         request_capture_mode: fetched.request_capture_mode,
+        response_cache_enabled: fetched.response_cache_enabled,
+        max_request_charge_nano_usd: None,
     })
     .expect("response serializes");
     assert_eq!(response_value.get("allowed_groups"), Some(&json!(["beta"])));
@@ -721,6 +748,8 @@ async fn dashboard_api_key_allowed_groups_enforces_user_ceiling() {
                 sub_account_enabled: invalid_create_body.sub_account_enabled,
                 model_limits_enabled: invalid_create_body.model_limits_enabled,
                 model_limits: invalid_create_body.model_limits,
+                allow_models: invalid_create_body.allow_models,
+                deny_models: invalid_create_body.deny_models,
                 ip_whitelist: invalid_create_body.ip_whitelist,
                 allowed_groups: invalid_create_body.allowed_groups,
                 max_multiplier: invalid_create_body.max_multiplier,
@@ -728,6 +757,8 @@ async fn dashboard_api_key_allowed_groups_enforces_user_ceiling() {
                 model_redirects: invalid_create_body.model_redirects,
                 reasoning_envelope_enabled: invalid_create_body.reasoning_envelope_enabled,
                 request_capture_mode: invalid_create_body.request_capture_mode,
+                response_cache_enabled: invalid_create_body.response_cache_enabled,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -745,6 +776,8 @@ async fn dashboard_api_key_allowed_groups_enforces_user_ceiling() {
                 sub_account_enabled: false,
                 model_limits_enabled: false,
                 model_limits: Vec::new(),
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
                 ip_whitelist: Vec::new(),
                 allowed_groups: Vec::new(),
                 max_multiplier: None,
@@ -752,6 +785,8 @@ async fn dashboard_api_key_allowed_groups_enforces_user_ceiling() {
                 model_redirects: Vec::new(),
                 reasoning_envelope_enabled: true,
                 request_capture_mode: crate::users::RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -778,6 +813,8 @@ async fn dashboard_api_key_allowed_groups_enforces_user_ceiling() {
                 sub_account_enabled: None,
                 model_limits_enabled: None,
                 model_limits: None,
+                allow_models: None,
+                deny_models: None,
                 ip_whitelist: None,
                 allowed_groups: invalid_update_body.allowed_groups,
                 max_multiplier: None,
@@ -785,6 +822,8 @@ async fn dashboard_api_key_allowed_groups_enforces_user_ceiling() {
                 model_redirects: None,
                 reasoning_envelope_enabled: None,
                 request_capture_mode: None,
+                response_cache_enabled: None,
+                max_request_charge_nano_usd: None,
                 expires_at: None,
             },
             false,
@@ -807,6 +846,8 @@ async fn dashboard_api_key_allowed_groups_enforces_user_ceiling() {
                 sub_account_enabled: false,
                 model_limits_enabled: false,
                 model_limits: Vec::new(),
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
                 ip_whitelist: Vec::new(),
                 allowed_groups: vec![" Beta ".to_string()],
                 max_multiplier: None,
@@ -814,6 +855,8 @@ async fn dashboard_api_key_allowed_groups_enforces_user_ceiling() {
                 model_redirects: Vec::new(),
                 reasoning_envelope_enabled: true,
                 request_capture_mode: crate::users::RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -858,6 +901,8 @@ async fn dashboard_api_key_model_redirects_round_trip_and_validate() {
                 sub_account_enabled: create_body.sub_account_enabled,
                 model_limits_enabled: create_body.model_limits_enabled,
                 model_limits: create_body.model_limits,
+                allow_models: create_body.allow_models,
+                deny_models: create_body.deny_models,
                 ip_whitelist: create_body.ip_whitelist,
                 allowed_groups: create_body.allowed_groups,
                 max_multiplier: create_body.max_multiplier,
@@ -865,6 +910,8 @@ async fn dashboard_api_key_model_redirects_round_trip_and_validate() {
                 model_redirects: create_body.model_redirects,
                 reasoning_envelope_enabled: create_body.reasoning_envelope_enabled,
                 request_capture_mode: create_body.request_capture_mode,
+                response_cache_enabled: create_body.response_cache_enabled,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -884,6 +931,8 @@ async fn dashboard_api_key_model_redirects_round_trip_and_validate() {
                 sub_account_enabled: None,
                 model_limits_enabled: None,
                 model_limits: None,
+                allow_models: None,
+                deny_models: None,
                 ip_whitelist: None,
                 allowed_groups: None,
                 max_multiplier: None,
@@ -894,6 +943,8 @@ async fn dashboard_api_key_model_redirects_round_trip_and_validate() {
                 }]),
                 reasoning_envelope_enabled: None,
                 request_capture_mode: None,
+                response_cache_enabled: None,
+                max_request_charge_nano_usd: None,
                 expires_at: None,
             },
             false,
@@ -913,6 +964,8 @@ async fn dashboard_api_key_model_redirects_round_trip_and_validate() {
                 sub_account_enabled: false,
                 model_limits_enabled: false,
                 model_limits: Vec::new(),
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
                 ip_whitelist: Vec::new(),
                 allowed_groups: Vec::new(),
                 max_multiplier: None,
@@ -923,6 +976,8 @@ async fn dashboard_api_key_model_redirects_round_trip_and_validate() {
                 }],
                 reasoning_envelope_enabled: true,
                 request_capture_mode: crate::users::RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -947,6 +1002,7 @@ fn request_log_timing_serializes_compatibility_aliases() {
         reasoning_effort: None,
         request_ip: None,
         tried_providers: None,
+        metadata: None,
         provider: RequestLogProvider {
             id: Some("provider-1".to_string()),
             name: Some("Provider".to_string()),
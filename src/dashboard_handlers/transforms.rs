@@ -0,0 +1,202 @@
+use crate::app::AppState;
+use crate::config::ProviderType;
+use crate::dashboard_handlers::session_helpers::require_admin;
+use crate::error::{AppError, AppResult};
+use crate::monoize_routing::MonoizeProviderType;
+use crate::transforms::{
+    self, Phase, TransformError, TransformRuleConfig, TransformRuntimeContext, UrpData,
+};
+use crate::urp;
+use axum::Json;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewDownstream {
+    #[default]
+    Responses,
+    ChatCompletions,
+    Messages,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransformPreviewRequest {
+    pub phase: Phase,
+    pub model: String,
+    #[serde(default)]
+    pub transforms: Vec<TransformRuleConfig>,
+    pub body: Value,
+    #[serde(default)]
+    pub downstream: PreviewDownstream,
+    #[serde(default)]
+    pub provider_type: Option<MonoizeProviderType>,
+}
+
+/// Runs a transform chain against a sample body without routing, calling
+/// upstream, or charging anyone.
+pub async fn preview_transforms(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<TransformPreviewRequest>,
+) -> AppResult<impl IntoResponse> {
+    require_admin(&headers, &state).await?;
+
+    let provider_type = body.provider_type.map(|t| t.to_config_type());
+    let mut states =
+        transforms::build_states_for_rules(&body.transforms, state.transform_registry.as_ref())
+            .map_err(transform_error_to_app)?;
+    let context = TransformRuntimeContext {
+        image_transform_cache: state.image_transform_cache.clone(),
+        http_client: state.http.clone(),
+        upstream_provider_type: provider_type,
+        upstream_channel: None,
+        gemini_context_cache: state.gemini_context_cache.clone(),
+        request_tool_names: Vec::new(),
+    };
+
+    match body.phase {
+        Phase::Request => {
+            let mut req = decode_downstream_request(body.downstream, &body.body)?;
+            if req.model.trim().is_empty() {
+                req.model = body.model.clone();
+            }
+            transforms::apply_transforms(
+                UrpData::Request(&mut req),
+                &body.transforms,
+                &mut states,
+                &body.model,
+                Phase::Request,
+                &context,
+                state.transform_registry.as_ref(),
+            )
+            .await
+            .map_err(transform_error_to_app)?;
+            let upstream_body = match provider_type {
+                Some(provider_type) => Some(encode_upstream_request(provider_type, &req)?),
+                None => None,
+            };
+            Ok(Json(json!({
+                "phase": Phase::Request,
+                "urp": req,
+                "upstream_body": upstream_body,
+            })))
+        }
+        Phase::Response => {
+            let provider_type = provider_type.ok_or_else(|| {
+                AppError::new(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_request",
+                    "provider_type is required for response phase previews",
+                )
+            })?;
+            let mut resp = decode_upstream_response(provider_type, &body.body, &body.model)?;
+            transforms::apply_transforms(
+                UrpData::Response(&mut resp),
+                &body.transforms,
+                &mut states,
+                &body.model,
+                Phase::Response,
+                &context,
+                state.transform_registry.as_ref(),
+            )
+            .await
+            .map_err(transform_error_to_app)?;
+            let downstream_body = encode_downstream_response(body.downstream, &resp, &body.model);
+            Ok(Json(json!({
+                "phase": Phase::Response,
+                "urp": resp,
+                "downstream_body": downstream_body,
+            })))
+        }
+    }
+}
+
+fn transform_error_to_app(err: TransformError) -> AppError {
+    let code = match &err {
+        TransformError::InvalidConfig(_) => "transform_invalid_config",
+        TransformError::NotFound(_) => "transform_not_found",
+        TransformError::Apply(_) => "transform_apply_failed",
+    };
+    AppError::new(StatusCode::BAD_REQUEST, code, err.to_string())
+}
+
+fn invalid_body(message: String) -> AppError {
+    AppError::new(StatusCode::BAD_REQUEST, "invalid_request", message)
+}
+
+#[allow(clippy::result_large_err)]
+fn decode_downstream_request(
+    downstream: PreviewDownstream,
+    body: &Value,
+) -> AppResult<urp::UrpRequest> {
+    match downstream {
+        PreviewDownstream::Responses => urp::decode::openai_responses::decode_request(body),
+        PreviewDownstream::ChatCompletions => urp::decode::openai_chat::decode_request(body),
+        PreviewDownstream::Messages => urp::decode::anthropic::decode_request(body),
+    }
+    .map_err(invalid_body)
+}
+
+#[allow(clippy::result_large_err)]
+fn encode_upstream_request(provider_type: ProviderType, req: &urp::UrpRequest) -> AppResult<Value> {
+    let model = req.model.as_str();
+    Ok(match provider_type {
+        ProviderType::Responses => urp::encode::openai_responses::encode_request(req, model),
+        ProviderType::ChatCompletion => urp::encode::openai_chat::encode_request(req, model),
+        ProviderType::Messages => {
+            urp::encode::anthropic::encode_request_checked(req, model).map_err(invalid_body)?
+        }
+        ProviderType::Gemini => urp::encode::gemini::encode_request(req, model),
+        ProviderType::OpenaiImage => urp::encode::openai_image::encode_request(req, model),
+        ProviderType::Replicate => urp::encode::replicate::encode_request(req, model),
+        ProviderType::Group => {
+            return Err(AppError::new(
+                StatusCode::BAD_REQUEST,
+                "provider_type_not_supported",
+                "group is virtual",
+            ));
+        }
+    })
+}
+
+#[allow(clippy::result_large_err)]
+fn decode_upstream_response(
+    provider_type: ProviderType,
+    body: &Value,
+    model: &str,
+) -> AppResult<urp::UrpResponse> {
+    match provider_type {
+        ProviderType::Responses => urp::decode::openai_responses::decode_response(body),
+        ProviderType::ChatCompletion => urp::decode::openai_chat::decode_response(body),
+        ProviderType::Messages => urp::decode::anthropic::decode_response(body),
+        ProviderType::Gemini => urp::decode::gemini::decode_response(body),
+        ProviderType::OpenaiImage => urp::decode::openai_image::decode_response(body, model),
+        ProviderType::Replicate => urp::decode::replicate::decode_response(body),
+        ProviderType::Group => {
+            return Err(AppError::new(
+                StatusCode::BAD_REQUEST,
+                "provider_type_not_supported",
+                "group is virtual",
+            ));
+        }
+    }
+    .map_err(invalid_body)
+}
+
+fn encode_downstream_response(
+    downstream: PreviewDownstream,
+    resp: &urp::UrpResponse,
+    model: &str,
+) -> Value {
+    match downstream {
+        PreviewDownstream::Responses => urp::encode::openai_responses::encode_response(resp, model),
+        PreviewDownstream::ChatCompletions => {
+            urp::encode::openai_chat::encode_response(resp, model)
+        }
+        PreviewDownstream::Messages => urp::encode::anthropic::encode_response(resp, model),
+    }
+}
@@ -218,6 +218,10 @@ impl RequestLogBatcher {
                     .map(serde_json::Value::to_string)
                     .into(),
                 log.request_kind.clone().into(),
+                log.metadata_json
+                    .as_ref()
+                    .map(serde_json::Value::to_string)
+                    .into(),
                 log.effective_provider_type.clone().into(),
                 log.affinity_hit
                     .map(|v| sea_orm::Value::Int(Some(if v { 1 } else { 0 })))
@@ -236,10 +240,10 @@ impl RequestLogBatcher {
                     billing_breakdown_json, error_code, error_message, error_http_status,
                     duration_ms, ttfb_ms, first_visible_output_ms, last_visible_output_ms,
                     visible_generation_ms, visible_output_tokens, tps_mode,
-                    request_ip, reasoning_effort, tried_providers_json, request_kind,
+                    request_ip, reasoning_effort, tried_providers_json, request_kind, metadata_json,
                     effective_provider_type, affinity_hit, affinity_key_hash, affinity_target,
                     created_at, created_at_unix_ms)
-                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32, $33, $34, $35, $36, $37, $38, $39, $40, $41, $42)"#;
+                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32, $33, $34, $35, $36, $37, $38, $39, $40, $41, $42, $43)"#;
 
             if let Err(e) = tx.execute(db.stmt(sql, values)).await {
                 tracing::warn!("request_log_batcher flush error: {e}");
@@ -441,3 +445,72 @@ impl BalanceCache {
         })
     }
 }
+
+// ---------------------------------------------------------------------------
+// BalanceHolds: in-memory pre-authorization holds against spendable balance
+// ---------------------------------------------------------------------------
+
+/// Amounts reserved by in-flight streaming requests, keyed by the balance
+/// account they will be charged to. Holds are process-local; a restart drops
+/// them together with the streams they covered.
+#[derive(Debug, Clone, Default)]
+pub struct BalanceHolds {
+    held: Arc<DashMap<String, i128>>,
+}
+
+impl BalanceHolds {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn held(&self, account: &str) -> i128 {
+        self.held.get(account).map_or(0, |held| *held)
+    }
+
+    /// Reserves `amount` when `available` minus what is already held covers it.
+    pub fn try_hold(&self, account: &str, available: i128, amount: i128) -> Option<BalanceHold> {
+        let mut held = self.held.entry(account.to_string()).or_insert(0);
+        if available.saturating_sub(*held) < amount {
+            let empty = *held == 0;
+            drop(held);
+            if empty {
+                self.held.remove_if(account, |_, held| *held == 0);
+            }
+            return None;
+        }
+        *held += amount;
+        Some(BalanceHold {
+            holds: self.clone(),
+            account: account.to_string(),
+            amount,
+        })
+    }
+
+    fn release(&self, account: &str, amount: i128) {
+        if let Some(mut held) = self.held.get_mut(account) {
+            *held -= amount;
+        }
+        self.held.remove_if(account, |_, held| *held <= 0);
+    }
+}
+
+/// One reservation placed by [`BalanceHolds::try_hold`]. Dropping it releases
+/// the reserved amount, so error and cancellation paths cannot leak holds.
+#[derive(Debug)]
+pub struct BalanceHold {
+    holds: BalanceHolds,
+    account: String,
+    amount: i128,
+}
+
+impl BalanceHold {
+    pub fn amount(&self) -> i128 {
+        self.amount
+    }
+}
+
+impl Drop for BalanceHold {
+    fn drop(&mut self) {
+        self.holds.release(&self.account, self.amount);
+    }
+}
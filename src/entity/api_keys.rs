@@ -30,6 +30,10 @@ pub struct Model {
     #[sea_orm(column_type = "Text")]
     pub model_limits: String,
     #[sea_orm(column_type = "Text")]
+    pub allow_models: String,
+    #[sea_orm(column_type = "Text")]
+    pub deny_models: String,
+    #[sea_orm(column_type = "Text")]
     pub ip_whitelist: String,
     #[sea_orm(column_type = "Text")]
     pub allowed_groups: String,
@@ -43,6 +47,8 @@ pub struct Model {
     pub reasoning_envelope_enabled: i32,
     #[sea_orm(column_type = "Text")]
     pub request_capture_mode: String,
+    pub response_cache_enabled: i32,
+    pub max_request_charge_nano_usd: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -20,6 +20,10 @@ pub struct Model {
     pub cache_creation_input_cost_per_token_nano: Option<String>,
     #[sea_orm(column_type = "Text")]
     pub output_cost_per_reasoning_token_nano: Option<String>,
+    #[sea_orm(column_type = "Text")]
+    pub image_cost_per_image_nano: Option<String>,
+    #[sea_orm(column_type = "Text")]
+    pub tts_cost_per_char_nano: Option<String>,
     pub max_input_tokens: Option<i64>,
     pub max_output_tokens: Option<i64>,
     pub max_tokens: Option<i64>,
@@ -13,6 +13,7 @@ pub struct Model {
     #[sea_orm(column_type = "Text")]
     pub redirect: Option<String>,
     pub multiplier: f64,
+    pub supports_streaming: Option<i32>,
     #[sea_orm(column_type = "Text")]
     pub created_at: String,
 }
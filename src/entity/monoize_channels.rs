@@ -16,7 +16,15 @@ pub struct Model {
     pub base_url: String,
     #[sea_orm(column_type = "Text")]
     pub api_key: String,
+    #[sea_orm(column_type = "Text")]
+    pub api_keys: Option<String>,
     pub weight: i32,
+    pub priority: i32,
+    pub max_concurrency: Option<i32>,
+    #[sea_orm(column_type = "Text")]
+    pub org_id: Option<String>,
+    #[sea_orm(column_type = "Text")]
+    pub project_id: Option<String>,
     pub enabled: i32,
     pub passive_failure_count_threshold_override: Option<i32>,
     pub passive_cooldown_seconds_override: Option<i32>,
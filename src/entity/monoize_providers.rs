@@ -1,7 +1,7 @@
 use sea_orm::DeriveRelation;
 use sea_orm::entity::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "monoize_providers")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
@@ -26,6 +26,9 @@ pub struct Model {
     #[sea_orm(column_type = "Text")]
     pub extra_fields_whitelist: Option<String>,
     pub strip_cross_protocol_nested_extra: Option<i32>,
+    pub debug_log_bodies: i32,
+    pub default_temperature: Option<f64>,
+    pub default_top_p: Option<f64>,
     #[sea_orm(column_type = "Text")]
     pub groups: String,
     pub enabled: i32,
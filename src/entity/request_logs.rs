@@ -58,6 +58,8 @@ pub struct Model {
     #[sea_orm(column_type = "Text")]
     pub tried_providers_json: Option<String>,
     #[sea_orm(column_type = "Text")]
+    pub metadata_json: Option<String>,
+    #[sea_orm(column_type = "Text")]
     pub request_kind: Option<String>,
     #[sea_orm(column_type = "Text")]
     pub effective_provider_type: Option<String>,
@@ -16,6 +16,9 @@ pub struct AppError {
     /// When set, request logs use this instead of `message` so the client
     /// receives sanitized text while internal logs retain full detail.
     pub internal_message: Option<String>,
+    /// Structured context rendered as `error.details`, e.g. validation
+    /// violations.
+    pub details: Option<serde_json::Value>,
 }
 
 impl AppError {
@@ -31,6 +34,7 @@ impl AppError {
             upstream_type: None,
             upstream_param: None,
             internal_message: None,
+            details: None,
         }
     }
 
@@ -43,6 +47,11 @@ impl AppError {
         self
     }
 
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
     pub fn with_param(mut self, param: impl Into<String>) -> Self {
         self.param = Some(param.into());
         self
@@ -83,6 +92,8 @@ struct ErrorBody {
     upstream_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     upstream_param: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<serde_json::Value>,
 }
 
 impl IntoResponse for AppError {
@@ -97,6 +108,7 @@ impl IntoResponse for AppError {
                 upstream_code: self.upstream_code,
                 upstream_type: self.upstream_type,
                 upstream_param: self.upstream_param,
+                details: self.details,
             },
         };
         (self.status, axum::Json(body)).into_response()
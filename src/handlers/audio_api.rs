@@ -0,0 +1,330 @@
+use super::*;
+use crate::model_registry_store::TTS_CHARACTER_USAGE_CLASS;
+use axum::body::Body;
+use axum::http::header::CONTENT_TYPE;
+use std::collections::HashMap;
+
+/// `request_logs.request_kind` stamped on every speech request log.
+const SPEECH_REQUEST_KIND: &str = "speech";
+
+const SPEECH_RESPONSE_FORMATS: [&str; 6] = ["mp3", "opus", "aac", "flac", "wav", "pcm"];
+
+pub async fn create_speech(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> AppResult<Response> {
+    let auth = auth_tenant(&headers, &state).await?;
+
+    let obj = body.as_object().ok_or_else(|| {
+        AppError::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            "body must be object",
+        )
+    })?;
+
+    let mut logical_model = obj
+        .get("model")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| AppError::new(StatusCode::BAD_REQUEST, "invalid_request", "missing model"))?
+        .to_string();
+    apply_model_redirects_to_model(&mut logical_model, &auth.model_redirects);
+    ensure_model_allowed(&auth, &logical_model)?;
+    ensure_model_passes_filters(&auth, &logical_model)?;
+
+    let input = obj
+        .get("input")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| {
+            AppError::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_request",
+                "input must be a non-empty string",
+            )
+        })?;
+    obj.get("voice")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| {
+            AppError::new(StatusCode::BAD_REQUEST, "invalid_request", "missing voice")
+        })?;
+    let response_format = match obj.get("response_format") {
+        None => "mp3",
+        Some(value) => value
+            .as_str()
+            .filter(|format| SPEECH_RESPONSE_FORMATS.contains(format))
+            .ok_or_else(|| {
+                AppError::new(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_request",
+                    format!(
+                        "response_format must be one of: {}",
+                        SPEECH_RESPONSE_FORMATS.join(", ")
+                    ),
+                )
+            })?,
+    };
+    let usage = speech_usage(input);
+
+    let max_multiplier = resolve_max_multiplier_for_embeddings(&body, &headers, &auth);
+    let request_id = extract_request_id(&headers);
+    let request_ip = extract_client_ip(&headers);
+    let started_at = std::time::Instant::now();
+    let routing_stub = build_embeddings_routing_stub(&logical_model, max_multiplier);
+    let attempts = build_monoize_attempts(&state, &routing_stub, &auth).await?;
+    ensure_balance_before_forward_for_attempts(&state, &auth, &attempts).await?;
+    let _pending_request_log_guard = insert_pending_request_log(
+        &state,
+        &auth,
+        &logical_model,
+        false,
+        request_id.as_deref(),
+        request_ip.as_deref(),
+        started_at,
+    )
+    .await;
+    let mut last_failed_attempt: Option<MonoizeAttempt> = None;
+    let mut tried_providers: Vec<TriedProvider> = Vec::new();
+    let mut execution_state = AttemptExecutionState::default();
+
+    for mut attempt in attempts {
+        execution_state.enter_provider(&attempt.provider_id);
+        if !execution_state.provider_budget_remaining(&attempt) {
+            continue;
+        }
+        let Ok(channel_permit) = try_acquire_channel_permit(&state, &attempt).await else {
+            continue;
+        };
+
+        let max_channel_attempts = (attempt.channel_max_retries + 1).max(1) as usize;
+        // A key rejected with 401/403 is retried on the same channel with the
+        // pool's next key without using up a channel retry.
+        let max_key_retries = attempt.api_key_pool.len().saturating_sub(1);
+        let mut key_retries = 0;
+        for send_index in 0..max_channel_attempts + max_key_retries {
+            let channel_attempt = send_index - key_retries;
+            if channel_attempt >= max_channel_attempts
+                || !execution_state.provider_budget_remaining(&attempt)
+            {
+                break;
+            }
+
+            let attempt_number = execution_state.record_upstream_attempt();
+            if let Some(api_key) = next_channel_key(&state, &attempt).await {
+                attempt.api_key = api_key;
+            }
+            let mut upstream_body = body.clone();
+            if let Some(upstream_obj) = upstream_body.as_object_mut() {
+                upstream_obj.insert(
+                    "model".to_string(),
+                    Value::String(attempt.upstream_model.clone()),
+                );
+                upstream_obj.remove("max_multiplier");
+            }
+
+            let provider = build_channel_provider_config(&attempt);
+            let result = upstream::call_upstream_binary(
+                client_http(&state),
+                &provider,
+                &attempt.api_key,
+                "/v1/audio/speech",
+                &upstream_body,
+                attempt.request_timeout_ms,
+                &[],
+            )
+            .await;
+
+            match result {
+                Ok(upstream_resp) => {
+                    update_pending_channel_info(
+                        &state,
+                        &auth,
+                        &attempt,
+                        &logical_model,
+                        false,
+                        request_id.as_deref(),
+                        request_ip.as_deref(),
+                        started_at,
+                    )
+                    .await;
+                    mark_channel_success(&state, &attempt).await;
+                    // Characters are known before any audio is produced, so the
+                    // charge is settled once upstream accepts the request.
+                    let charge =
+                        maybe_charge_usage(&state, &auth, &attempt, &logical_model, &usage).await?;
+                    let content_type = upstream_resp
+                        .headers()
+                        .get(CONTENT_TYPE)
+                        .cloned()
+                        .unwrap_or_else(|| {
+                            axum::http::HeaderValue::from_static(speech_content_type(
+                                response_format,
+                            ))
+                        });
+
+                    spawn_request_log(
+                        &state,
+                        &auth,
+                        &attempt,
+                        &logical_model,
+                        Some(usage),
+                        charge.charge_nano_usd,
+                        charge.billing_breakdown,
+                        false,
+                        started_at,
+                        request_id.clone(),
+                        request_ip.clone(),
+                        attempt.channel_id.clone(),
+                        None,
+                        None,
+                        None,
+                        None,
+                        tried_providers,
+                        Some(SPEECH_REQUEST_KIND),
+                    );
+
+                    // The channel permit rides along with the body so concurrency
+                    // stays reserved until the audio finishes streaming.
+                    let stream = upstream_resp.bytes_stream().map(move |chunk| {
+                        let _ = &channel_permit;
+                        chunk
+                    });
+                    let mut response = Response::new(Body::from_stream(stream));
+                    response.headers_mut().insert(CONTENT_TYPE, content_type);
+                    return Ok(response);
+                }
+                Err(err) => {
+                    let key_rejected = suspend_rejected_channel_key(&state, &attempt, &err).await;
+                    let retry_next_key = key_rejected && key_retries < max_key_retries;
+                    let non_retryable = is_non_retryable_client_error(&err) && !retry_next_key;
+                    let retryable = is_retryable_error(&err);
+                    let retryable_failure_class = classify_retryable_failure(&err);
+                    let app_err = upstream_error_to_app(err);
+                    if non_retryable {
+                        spawn_request_log_error(
+                            &state,
+                            &auth,
+                            &attempt,
+                            &logical_model,
+                            false,
+                            started_at,
+                            request_id.clone(),
+                            request_ip.clone(),
+                            &app_err,
+                            None,
+                            tried_providers,
+                            Some(SPEECH_REQUEST_KIND),
+                        );
+                        return Err(app_err);
+                    }
+                    if retry_next_key {
+                        key_retries += 1;
+                        tried_providers.push(TriedProvider::from_app_error(
+                            attempt_number,
+                            &attempt,
+                            &app_err,
+                        ));
+                        last_failed_attempt = Some(attempt.clone());
+                        continue;
+                    }
+                    if retryable {
+                        tried_providers.push(TriedProvider::from_app_error(
+                            attempt_number,
+                            &attempt,
+                            &app_err,
+                        ));
+                        mark_channel_retryable_failure(&state, &attempt, retryable_failure_class)
+                            .await;
+                        last_failed_attempt = Some(attempt.clone());
+                        if !is_attempt_channel_healthy(&state, &attempt).await {
+                            break;
+                        }
+                        if execution_state.provider_budget_remaining(&attempt) {
+                            if channel_attempt + 1 < max_channel_attempts {
+                                maybe_sleep_before_channel_retry(
+                                    &attempt,
+                                    retryable_failure_class.retry_after(),
+                                )
+                                .await;
+                            }
+                            continue;
+                        }
+                        break;
+                    }
+                    spawn_request_log_error(
+                        &state,
+                        &auth,
+                        &attempt,
+                        &logical_model,
+                        false,
+                        started_at,
+                        request_id.clone(),
+                        request_ip.clone(),
+                        &app_err,
+                        None,
+                        tried_providers,
+                        Some(SPEECH_REQUEST_KIND),
+                    );
+                    return Err(app_err);
+                }
+            }
+        }
+    }
+    let final_err = build_exhausted_upstream_error(&logical_model, &tried_providers);
+    if let Some(attempt) = last_failed_attempt {
+        spawn_request_log_error(
+            &state,
+            &auth,
+            &attempt,
+            &logical_model,
+            false,
+            started_at,
+            request_id,
+            request_ip,
+            &final_err,
+            None,
+            tried_providers,
+            Some(SPEECH_REQUEST_KIND),
+        );
+    } else {
+        spawn_request_log_error_no_attempt(
+            &state,
+            &auth,
+            &logical_model,
+            false,
+            started_at,
+            request_id,
+            request_ip,
+            &final_err,
+            None,
+            tried_providers,
+            Some(SPEECH_REQUEST_KIND),
+        );
+    }
+    Err(final_err)
+}
+
+/// Speech is billed per input character through the `tts_character` meter.
+fn speech_usage(input: &str) -> urp::Usage {
+    urp::Usage {
+        extra_body: HashMap::from([(
+            TTS_CHARACTER_USAGE_CLASS.to_string(),
+            json!(input.chars().count()),
+        )]),
+        ..urp::Usage::default()
+    }
+}
+
+fn speech_content_type(response_format: &str) -> &'static str {
+    match response_format {
+        "opus" => "audio/opus",
+        "aac" => "audio/aac",
+        "flac" => "audio/flac",
+        "wav" => "audio/wav",
+        "pcm" => "audio/pcm",
+        _ => "audio/mpeg",
+    }
+}
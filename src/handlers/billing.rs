@@ -2,6 +2,9 @@ use super::*;
 use crate::billing_rate_store::DbBillingRateRecord;
 #[cfg(test)]
 use crate::model_registry_store::ModelPricing;
+use crate::model_registry_store::{IMAGE_GENERATION_USAGE_CLASS, TTS_CHARACTER_USAGE_CLASS};
+use crate::settings::MissingPricingPolicy;
+use crate::token_estimator::TokenEstimator;
 
 #[derive(Debug, Clone)]
 pub(super) struct BillingRateResolution {
@@ -158,7 +161,10 @@ pub(super) fn calculate_charge_components(
     let base_charge = prompt_charge
         .checked_add(completion_charge)?
         .checked_add(cache_creation_charge)?;
-    let final_charge = scale_charge_with_multiplier(base_charge, provider_multiplier)?;
+    let final_charge = crate::users::scale_nano_by_multiplier(
+        base_charge,
+        crate::users::multiplier_to_nano(provider_multiplier)?,
+    )?;
 
     Some(ChargeComponents {
         prompt_tokens,
@@ -279,7 +285,7 @@ pub(super) fn build_usage_breakdown(usage: &urp::Usage) -> Value {
         .and_then(|d| d.modality_breakdown.as_ref())
         .and_then(|m| m.image_tokens);
 
-    json!({
+    let mut breakdown = json!({
         "version": 1,
         "input": {
             "total_tokens": usage.input_tokens,
@@ -306,7 +312,11 @@ pub(super) fn build_usage_breakdown(usage: &urp::Usage) -> Value {
             "image_tokens": output_image
         },
         "raw_usage_extra": usage.extra_body
-    })
+    });
+    if usage.extra_body.get("estimated").and_then(Value::as_bool) == Some(true) {
+        breakdown["estimated"] = Value::Bool(true);
+    }
+    breakdown
 }
 
 #[cfg(test)]
@@ -445,6 +455,16 @@ async fn metadata_pricing_profile_for_model(
         .filter(|profile| !profile.is_empty()))
 }
 
+/// True when the matrix can never produce a charge: every rate is priced at
+/// zero or carries `"free": true` in its raw definition.
+pub(super) fn billing_rate_matrix_is_free(resolution: &BillingRateResolution) -> bool {
+    !resolution.rates.is_empty()
+        && resolution
+            .rates
+            .iter()
+            .all(|rate| rate.charged_unit_price_nano().is_ok_and(|price| price == 0))
+}
+
 pub(super) fn billing_rate_matrix_allows_request(
     resolution: &BillingRateResolution,
     server_tool_usage_classes: &[String],
@@ -457,7 +477,7 @@ pub(super) fn billing_rate_matrix_allows_request(
         .rates
         .iter()
         .any(|r| r.rate_kind == "token" && r.usage_class == "output");
-    if !has_input || !has_output {
+    if (!has_input || !has_output) && !is_meter_only_matrix(&resolution.rates) {
         return Ok(false);
     }
     let context_tiers: std::collections::BTreeSet<String> = resolution
@@ -787,7 +807,7 @@ fn add_token_line_for_usage_classes(
             usage_classes.join("|"), modality, context_tier, cache_ttl
         )
     })?;
-    let unit_price = rate.unit_price_nano()?;
+    let unit_price = rate.charged_unit_price_nano()?;
     let charge = i128::from(quantity)
         .checked_mul(unit_price)
         .ok_or_else(|| "token charge overflow".to_string())?;
@@ -904,6 +924,37 @@ fn authoritative_meter_quantity(usage: &urp::Usage, usage_class: &str, unit: &st
     None
 }
 
+/// A matrix is meter-only when it carries an `image_generation` or `tts_character`
+/// meter and no input token rate; such models bill only by generated image count
+/// or synthesized input characters.
+fn is_meter_only_matrix(rates: &[DbBillingRateRecord]) -> bool {
+    rates.iter().any(|r| {
+        r.rate_kind == "meter"
+            && (r.usage_class == IMAGE_GENERATION_USAGE_CLASS
+                || r.usage_class == TTS_CHARACTER_USAGE_CLASS)
+    }) && !rates
+        .iter()
+        .any(|r| r.rate_kind == "token" && r.usage_class == "input_uncached")
+}
+
+pub(super) fn decoded_output_image_count(output: Option<&[urp::Node]>) -> u64 {
+    let Some(output) = output else {
+        return 0;
+    };
+    output
+        .iter()
+        .filter(|node| {
+            matches!(
+                node,
+                urp::Node::Image {
+                    role: urp::OrdinaryRole::Assistant,
+                    ..
+                }
+            )
+        })
+        .count() as u64
+}
+
 fn decoded_provider_item_count(output: Option<&[urp::Node]>, usage_class: &str) -> u64 {
     let Some(output) = output else {
         return 0;
@@ -950,12 +1001,12 @@ fn add_meter_lines(
                 rate.usage_class
             ));
         }
-        let mut quantity = authoritative.unwrap_or_else(|| {
-            if rate.unit == "call" {
-                decoded_provider_item_count(output, &rate.usage_class)
-            } else {
-                0
+        let mut quantity = authoritative.unwrap_or_else(|| match rate.unit.as_str() {
+            "call" => decoded_provider_item_count(output, &rate.usage_class),
+            "image" if rate.usage_class == IMAGE_GENERATION_USAGE_CLASS => {
+                decoded_output_image_count(output)
             }
+            _ => 0,
         });
         if quantity == 0 {
             continue;
@@ -979,7 +1030,7 @@ fn add_meter_lines(
         {
             quantity = quantity.max(minimum);
         }
-        let unit_price = rate.unit_price_nano()?;
+        let unit_price = rate.charged_unit_price_nano()?;
         let charge = i128::from(quantity)
             .checked_mul(unit_price)
             .ok_or_else(|| "meter charge overflow".to_string())?;
@@ -1003,9 +1054,36 @@ pub(super) fn calculate_rate_matrix_charge_components(
     usage: &urp::Usage,
     output: Option<&[urp::Node]>,
     resolution: &BillingRateResolution,
-    provider_multiplier: f64,
+    multiplier_nano: Option<i128>,
     requested_usage_classes: &[String],
 ) -> Result<MatrixChargeComponents, String> {
+    let scale = |base_charge: i128| {
+        multiplier_nano
+            .and_then(|multiplier_nano| {
+                crate::users::scale_nano_by_multiplier(base_charge, multiplier_nano)
+            })
+            .ok_or_else(|| "charge overflow".to_string())
+    };
+    if is_meter_only_matrix(&resolution.rates) {
+        let mut meter_line_items = Vec::new();
+        let base_charge = add_meter_lines(
+            &mut meter_line_items,
+            &resolution.rates,
+            usage,
+            output,
+            requested_usage_classes,
+        )?;
+        let final_charge = scale(base_charge)?;
+        return Ok(MatrixChargeComponents {
+            token_line_items: Vec::new(),
+            meter_line_items,
+            context_tier: None,
+            service_tier: usage_service_tier(usage),
+            base_charge,
+            final_charge,
+        });
+    }
+
     let input_details = usage.input_details.as_ref();
     let output_details = usage.output_details.as_ref();
     let context_tier = determine_context_tier(usage, &resolution.rates)?;
@@ -1193,8 +1271,7 @@ pub(super) fn calculate_rate_matrix_charge_components(
     let base_charge = token_total
         .checked_add(meter_total)
         .ok_or_else(|| "charge overflow".to_string())?;
-    let final_charge = scale_charge_with_multiplier(base_charge, provider_multiplier)
-        .ok_or_else(|| "charge overflow".to_string())?;
+    let final_charge = scale(base_charge)?;
 
     Ok(MatrixChargeComponents {
         token_line_items,
@@ -1232,32 +1309,6 @@ fn build_matrix_billing_breakdown(
     })
 }
 
-pub(super) fn scale_charge_with_multiplier(
-    base_nano: i128,
-    provider_multiplier: f64,
-) -> Option<i128> {
-    if !provider_multiplier.is_finite() || provider_multiplier < 0.0 {
-        return None;
-    }
-
-    pub(super) const SCALE: i128 = 1_000_000_000;
-    let multiplier_repr = format!("{provider_multiplier:.18}");
-    let mut parts = multiplier_repr.split('.');
-    let whole = parts.next().unwrap_or("0").parse::<i128>().ok()?;
-    let frac_raw = parts.next().unwrap_or("0");
-    let mut frac_nano = String::with_capacity(9);
-    for ch in frac_raw.chars().take(9) {
-        frac_nano.push(ch);
-    }
-    while frac_nano.len() < 9 {
-        frac_nano.push('0');
-    }
-    let frac = frac_nano.parse::<i128>().ok()?;
-
-    let multiplier_nano = whole.checked_mul(SCALE)?.checked_add(frac)?;
-    base_nano.checked_mul(multiplier_nano)?.checked_div(SCALE)
-}
-
 pub(super) async fn maybe_charge_usage(
     state: &AppState,
     auth: &crate::auth::AuthResult,
@@ -1268,6 +1319,144 @@ pub(super) async fn maybe_charge_usage(
     maybe_charge_usage_with_output(state, auth, attempt, logical_model, usage, None).await
 }
 
+/// Returns the cap a charge must be clamped to, or `None` when it is within
+/// limits. The API key override wins over the global setting, where 0 means
+/// no cap.
+pub(super) fn exceeded_request_charge_cap(
+    charge_nano: i128,
+    key_cap_nano: Option<i64>,
+    global_cap_nano: u64,
+) -> Option<i128> {
+    let cap = match key_cap_nano {
+        Some(cap) if cap > 0 => i128::from(cap),
+        _ if global_cap_nano > 0 => i128::from(global_cap_nano),
+        _ => return None,
+    };
+    (charge_nano > cap).then_some(cap)
+}
+
+/// Usage a request can at most produce: its visible input as counted by
+/// `estimator` for `model`, and the full output budget for each of its `n`
+/// choices.
+pub(super) fn worst_case_usage(
+    req: &urp::UrpRequest,
+    estimator: &dyn TokenEstimator,
+    model: &str,
+) -> Option<urp::Usage> {
+    let max_output_tokens = req.max_output_tokens?;
+    let choices = req
+        .extra_body
+        .get("n")
+        .and_then(Value::as_u64)
+        .unwrap_or(1)
+        .max(1);
+    Some(urp::Usage {
+        input_tokens: estimator.estimate(&req.input, model),
+        output_tokens: max_output_tokens.saturating_mul(choices),
+        input_details: None,
+        output_details: None,
+        extra_body: std::collections::HashMap::new(),
+    })
+}
+
+/// Worst-case charge of a request on one attempt: its visible input plus the
+/// whole `max_output_tokens` budget for each of the `n` requested choices,
+/// capped like a real charge. `None` when the request sets no output budget
+/// or the attempt is unpriced.
+pub(super) async fn estimate_max_charge_nano(
+    state: &AppState,
+    auth: &crate::auth::AuthResult,
+    attempt: &MonoizeAttempt,
+    req: &urp::UrpRequest,
+) -> AppResult<Option<i128>> {
+    let estimator = state
+        .monoize_runtime
+        .read()
+        .await
+        .token_estimator
+        .estimator();
+    let Some(usage) = worst_case_usage(req, estimator, &attempt.upstream_model) else {
+        return Ok(None);
+    };
+    let Some(resolution) = resolve_billing_rate_matrix(
+        state,
+        &attempt.upstream_model,
+        &attempt.logical_model,
+        attempt.provider_type,
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+    let Ok(components) = calculate_rate_matrix_charge_components(
+        &usage,
+        None,
+        &resolution,
+        attempt.model_multiplier_nano,
+        &attempt.server_tool_usage_classes,
+    ) else {
+        return Ok(None);
+    };
+    let global_cap = state
+        .monoize_runtime
+        .read()
+        .await
+        .max_request_charge_nano_usd;
+    let charge = exceeded_request_charge_cap(
+        components.final_charge,
+        auth.max_request_charge_nano_usd,
+        global_cap,
+    )
+    .unwrap_or(components.final_charge);
+    Ok((charge > 0).then_some(charge))
+}
+
+/// Places a pre-authorization hold for the worst-case charge of a streamed
+/// attempt, failing with 402 when the balance cannot cover it. The hold is
+/// released when dropped, so it must live until the attempt has been charged.
+pub(super) async fn place_stream_balance_hold(
+    state: &AppState,
+    auth: &crate::auth::AuthResult,
+    attempt: &MonoizeAttempt,
+    req: &urp::UrpRequest,
+) -> AppResult<Option<crate::db_cache::BalanceHold>> {
+    if !attempt.billable_pricing_available || attempt.free_pricing {
+        return Ok(None);
+    }
+    let Some(amount) = estimate_max_charge_nano(state, auth, attempt, req).await? else {
+        return Ok(None);
+    };
+    let placed = if auth.sub_account_enabled {
+        let Some(api_key_id) = auth.api_key_id.as_deref() else {
+            return Ok(None);
+        };
+        state
+            .user_store
+            .place_sub_account_hold(api_key_id, amount)
+            .await
+    } else {
+        let Some(user_id) = auth.user_id.as_deref() else {
+            return Ok(None);
+        };
+        state.user_store.place_hold(user_id, amount).await
+    };
+    placed.map_err(|err| match err.kind {
+        BillingErrorKind::InsufficientBalance => AppError::new(
+            StatusCode::PAYMENT_REQUIRED,
+            "insufficient_balance",
+            "insufficient balance for the request's max_output_tokens",
+        ),
+        BillingErrorKind::NotFound => {
+            AppError::new(StatusCode::UNAUTHORIZED, "unauthorized", err.message)
+        }
+        _ => AppError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal_error",
+            err.message,
+        ),
+    })
+}
+
 async fn maybe_charge_usage_with_output(
     state: &AppState,
     auth: &crate::auth::AuthResult,
@@ -1276,24 +1465,56 @@ async fn maybe_charge_usage_with_output(
     usage: &urp::Usage,
     output: Option<&[urp::Node]>,
 ) -> AppResult<ChargeComputation> {
+    // `attempt.logical_model` differs from `logical_model` only for model
+    // fallbacks, which are priced as the fallback model.
     let resolution = match resolve_billing_rate_matrix(
         state,
         &attempt.upstream_model,
-        logical_model,
+        &attempt.logical_model,
         attempt.provider_type,
     )
     .await?
     {
         Some(v) => v,
         None => {
-            return Err(AppError::new(
-                StatusCode::FORBIDDEN,
-                "model_pricing_required",
-                format!(
-                    "pricing metadata required for model: {}",
-                    attempt.upstream_model
-                ),
-            ));
+            return match state.monoize_runtime.read().await.missing_pricing_policy {
+                MissingPricingPolicy::Reject => Err(AppError::new(
+                    StatusCode::PAYMENT_REQUIRED,
+                    "pricing_unavailable",
+                    format!(
+                        "pricing metadata required for model: {}",
+                        attempt.upstream_model
+                    ),
+                )),
+                MissingPricingPolicy::Free => Ok(ChargeComputation::default()),
+                MissingPricingPolicy::Flag => {
+                    tracing::warn!(
+                        "billing: serving unpriced model={} logical_model={} without charge",
+                        attempt.upstream_model,
+                        logical_model
+                    );
+                    metrics::counter!(
+                        "monoize_pricing_missing_requests_total",
+                        "model" => attempt.upstream_model.clone()
+                    )
+                    .increment(1);
+                    Ok(ChargeComputation {
+                        charge_nano_usd: None,
+                        billing_breakdown: Some(json!({
+                            "version": 2,
+                            "currency": "nano_usd",
+                            "logical_model": logical_model,
+                            "upstream_model": attempt.upstream_model,
+                            "provider_id": attempt.provider_id,
+                            "pricing_missing": true,
+                            "token_line_items": [],
+                            "meter_line_items": [],
+                            "base_charge_nano": "0",
+                            "final_charge_nano": "0",
+                        })),
+                    })
+                }
+            };
         }
     };
     let Some(user_id) = auth.user_id.as_deref() else {
@@ -1304,7 +1525,7 @@ async fn maybe_charge_usage_with_output(
         usage,
         output,
         &resolution,
-        attempt.model_multiplier,
+        attempt.model_multiplier_nano,
         &attempt.server_tool_usage_classes,
     ) {
         Ok(v) => v,
@@ -1314,8 +1535,8 @@ async fn maybe_charge_usage_with_output(
                 || err.contains("authoritative usage required")
             {
                 return Err(AppError::new(
-                    StatusCode::FORBIDDEN,
-                    "model_pricing_required",
+                    StatusCode::PAYMENT_REQUIRED,
+                    "pricing_unavailable",
                     err,
                 ));
             }
@@ -1345,9 +1566,30 @@ async fn maybe_charge_usage_with_output(
             ),
         ));
     }
-    let billing_breakdown =
+    let mut billing_breakdown =
         build_matrix_billing_breakdown(logical_model, attempt, &resolution, &components);
-    let charge_nano = components.final_charge;
+    let mut charge_nano = components.final_charge;
+    let global_cap = state
+        .monoize_runtime
+        .read()
+        .await
+        .max_request_charge_nano_usd;
+    if let Some(cap) =
+        exceeded_request_charge_cap(charge_nano, auth.max_request_charge_nano_usd, global_cap)
+    {
+        tracing::warn!(
+            "request_charge_capped: model={} logical_model={} api_key_id={:?} charge_nano={} cap_nano={}",
+            attempt.upstream_model,
+            logical_model,
+            auth.api_key_id,
+            charge_nano,
+            cap
+        );
+        billing_breakdown["charge_capped"] = json!(true);
+        billing_breakdown["uncapped_charge_nano"] = json!(charge_nano.to_string());
+        billing_breakdown["final_charge_nano"] = json!(cap.to_string());
+        charge_nano = cap;
+    }
     if charge_nano <= 0 {
         return Ok(ChargeComputation {
             charge_nano_usd: None,
@@ -1446,8 +1688,16 @@ pub(super) async fn maybe_charge_response(
     logical_model: &str,
     response: &urp::UrpResponse,
 ) -> AppResult<ChargeComputation> {
-    let Some(usage) = response.usage.as_ref() else {
-        return Ok(ChargeComputation::default());
+    // Image endpoints such as DALL·E report no token usage at all, yet generated
+    // images remain billable through the per-image meter.
+    let image_only_usage;
+    let usage = match response.usage.as_ref() {
+        Some(usage) => usage,
+        None if decoded_output_image_count(Some(&response.output)) > 0 => {
+            image_only_usage = urp::Usage::default();
+            &image_only_usage
+        }
+        None => return Ok(ChargeComputation::default()),
     };
     maybe_charge_usage_with_output(
         state,
@@ -38,6 +38,7 @@ pub async fn compact_response(
         .to_string();
     apply_model_redirects_to_model(&mut logical_model, &auth.model_redirects);
     ensure_model_allowed(&auth, &logical_model)?;
+    ensure_model_passes_filters(&auth, &logical_model)?;
     body_obj.insert("model".to_string(), Value::String(logical_model.clone()));
 
     let max_multiplier = resolve_max_multiplier_for_embeddings(&body, &headers, &auth);
@@ -91,17 +92,30 @@ pub async fn compact_response(
     let mut tried_providers = Vec::new();
     let mut execution_state = AttemptExecutionState::default();
 
-    for attempt in attempts {
+    for mut attempt in attempts {
         execution_state.enter_provider(&attempt.provider_id);
         if !execution_state.provider_budget_remaining(&attempt) {
             continue;
         }
+        let Ok(_channel_permit) = try_acquire_channel_permit(&state, &attempt).await else {
+            continue;
+        };
         let max_channel_attempts = (attempt.channel_max_retries + 1).max(1) as usize;
-        for channel_attempt in 0..max_channel_attempts {
-            if !execution_state.provider_budget_remaining(&attempt) {
+        // A key rejected with 401/403 is retried on the same channel with the
+        // pool's next key without using up a channel retry.
+        let max_key_retries = attempt.api_key_pool.len().saturating_sub(1);
+        let mut key_retries = 0;
+        for send_index in 0..max_channel_attempts + max_key_retries {
+            let channel_attempt = send_index - key_retries;
+            if channel_attempt >= max_channel_attempts
+                || !execution_state.provider_budget_remaining(&attempt)
+            {
                 break;
             }
             let attempt_number = execution_state.record_upstream_attempt();
+            if let Some(api_key) = next_channel_key(&state, &attempt).await {
+                attempt.api_key = api_key;
+            }
             let mut upstream_body = crate::urp::encode::sanitize_provider_item_wire_body(&body);
             if let Some(obj) = upstream_body.as_object_mut() {
                 obj.insert(
@@ -112,7 +126,7 @@ pub async fn compact_response(
             }
             let provider = build_channel_provider_config(&attempt);
             let result = upstream::call_upstream_with_timeout_and_headers(
-                client_http(&state),
+                client_http_nonstream(&state),
                 &provider,
                 &attempt.api_key,
                 "/v1/responses/compact",
@@ -182,6 +196,7 @@ pub async fn compact_response(
                         None,
                         None,
                         tried_providers,
+                        None,
                     );
                     if let Some(session) = capture.session.as_ref() {
                         session.persist_with_result(usage.as_ref(), false).await;
@@ -212,7 +227,9 @@ pub async fn compact_response(
                             ))
                             .await;
                     }
-                    let non_retryable = is_non_retryable_client_error(&err);
+                    let key_rejected = suspend_rejected_channel_key(&state, &attempt, &err).await;
+                    let retry_next_key = key_rejected && key_retries < max_key_retries;
+                    let non_retryable = is_non_retryable_client_error(&err) && !retry_next_key;
                     let retryable = is_retryable_error(&err);
                     let failure_class = classify_retryable_failure(&err);
                     let app_err = upstream_error_to_app(err);
@@ -229,12 +246,23 @@ pub async fn compact_response(
                             &app_err,
                             None,
                             tried_providers,
+                            None,
                         );
                         if let Some(session) = capture.session.as_ref() {
                             session.persist_with_result(None, true).await;
                         }
                         return Err(app_err);
                     }
+                    if retry_next_key {
+                        key_retries += 1;
+                        tried_providers.push(TriedProvider::from_app_error(
+                            attempt_number,
+                            &attempt,
+                            &app_err,
+                        ));
+                        last_failed_attempt = Some(attempt.clone());
+                        continue;
+                    }
                     if retryable {
                         clear_channel_affinity(&state, &attempt).await;
                         tried_providers.push(TriedProvider::from_app_error(
@@ -249,7 +277,11 @@ pub async fn compact_response(
                         }
                         if execution_state.provider_budget_remaining(&attempt) {
                             if channel_attempt + 1 < max_channel_attempts {
-                                maybe_sleep_before_channel_retry(&attempt).await;
+                                maybe_sleep_before_channel_retry(
+                                    &attempt,
+                                    failure_class.retry_after(),
+                                )
+                                .await;
                             }
                             continue;
                         }
@@ -267,6 +299,7 @@ pub async fn compact_response(
                         &app_err,
                         None,
                         tried_providers,
+                        None,
                     );
                     if let Some(session) = capture.session.as_ref() {
                         session.persist_with_result(None, true).await;
@@ -291,6 +324,7 @@ pub async fn compact_response(
             &final_err,
             None,
             tried_providers,
+            None,
         );
     } else {
         spawn_request_log_error_no_attempt(
@@ -304,6 +338,7 @@ pub async fn compact_response(
             &final_err,
             None,
             tried_providers,
+            None,
         );
     }
     if let Some(session) = capture.session.as_ref() {
@@ -0,0 +1,101 @@
+use super::*;
+use std::collections::HashMap;
+
+/// Reserved logical model served in-process: the response repeats the last
+/// user text and no upstream is contacted. Used to exercise the full
+/// decode/encode/stream path deterministically in CI.
+pub(super) const ECHO_MODEL: &str = "monoize-echo";
+const ECHO_ROUTE_ID: &str = "monoize-echo";
+
+pub(super) fn is_echo_model(model: &str) -> bool {
+    model == ECHO_MODEL
+}
+
+/// Synthetic attempt that attributes echo requests in logs and routing
+/// headers. It carries no pricing, so the request is charged zero.
+pub(super) fn echo_attempt() -> MonoizeAttempt {
+    MonoizeAttempt {
+        provider_id: ECHO_ROUTE_ID.to_string(),
+        provider_type: ProviderType::Responses,
+        channel_id: ECHO_ROUTE_ID.to_string(),
+        base_url: String::new(),
+        api_key: String::new(),
+        api_key_pool: Vec::new(),
+        logical_model: ECHO_MODEL.to_string(),
+        upstream_model: ECHO_MODEL.to_string(),
+        model_multiplier: 0.0,
+        model_multiplier_nano: Some(0),
+        server_tool_usage_classes: Vec::new(),
+        provider_transforms: Vec::new(),
+        passive_failure_count_threshold: 0,
+        passive_cooldown_seconds: 0,
+        passive_window_seconds: 0,
+        passive_rate_limit_cooldown_seconds: 0,
+        probe_success_threshold: 0,
+        channel_max_retries: 0,
+        channel_retry_interval_ms: 0,
+        circuit_breaker_enabled: false,
+        per_model_circuit_break: false,
+        provider_attempt_limit: None,
+        request_timeout_ms: 0,
+        stream_idle_timeout_ms: 0,
+        max_concurrency: None,
+        org_id: None,
+        project_id: None,
+        extra_fields_whitelist: None,
+        strip_cross_protocol_nested_extra: false,
+        debug_log_bodies: false,
+        default_temperature: None,
+        default_top_p: None,
+        supports_streaming: true,
+        billable_pricing_available: false,
+        free_pricing: true,
+        affinity_key: None,
+        affinity_key_hash: None,
+        affinity_hit: None,
+        affinity_target: None,
+    }
+}
+
+/// Builds the echo reply: one assistant text equal to the last user text
+/// (empty when there is none). Usage counts whitespace-separated words of all
+/// input text and of the reply, so it is deterministic for a given request.
+pub(super) fn echo_response(req: &urp::UrpRequest) -> urp::UrpResponse {
+    let reply = req
+        .input
+        .iter()
+        .rev()
+        .find_map(|node| match node {
+            urp::Node::Text {
+                role: urp::OrdinaryRole::User,
+                content,
+                ..
+            } => Some(content.clone()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let input_tokens = req
+        .input
+        .iter()
+        .filter_map(|node| match node {
+            urp::Node::Text { content, .. } => Some(content.split_whitespace().count() as u64),
+            _ => None,
+        })
+        .sum();
+    let output_tokens = reply.split_whitespace().count() as u64;
+    urp::UrpResponse {
+        id: format!("resp_echo_{}", uuid::Uuid::new_v4().simple()),
+        model: req.model.clone(),
+        created_at: Some(chrono::Utc::now().timestamp()),
+        output: vec![urp::Node::assistant_text(reply)],
+        finish_reason: Some(urp::FinishReason::Stop),
+        usage: Some(urp::Usage {
+            input_tokens,
+            output_tokens,
+            input_details: None,
+            output_details: None,
+            extra_body: HashMap::new(),
+        }),
+        extra_body: HashMap::new(),
+    }
+}
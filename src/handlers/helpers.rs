@@ -56,6 +56,90 @@ pub(super) fn extract_client_ip(headers: &HeaderMap) -> Option<String> {
         })
 }
 
+pub(super) const REQUEST_METADATA_HEADER: &str = "x-monoize-metadata";
+pub(super) const MAX_REQUEST_METADATA_BYTES: usize = 4096;
+
+/// Parses the `x-monoize-metadata` header into a flat string-to-string JSON
+/// object. Oversized, malformed, or nested values are ignored rather than
+/// rejected so that tagging never fails a request.
+pub(super) fn extract_request_metadata(headers: &HeaderMap) -> Option<Value> {
+    let raw = headers.get(REQUEST_METADATA_HEADER)?.to_str().ok()?.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    if raw.len() > MAX_REQUEST_METADATA_BYTES {
+        tracing::debug!(
+            "ignoring {REQUEST_METADATA_HEADER}: {} bytes exceeds {MAX_REQUEST_METADATA_BYTES}",
+            raw.len()
+        );
+        return None;
+    }
+    let map = match serde_json::from_str::<Value>(raw) {
+        Ok(Value::Object(map)) => map,
+        Ok(_) => {
+            tracing::debug!("ignoring {REQUEST_METADATA_HEADER}: not a JSON object");
+            return None;
+        }
+        Err(err) => {
+            tracing::debug!("ignoring {REQUEST_METADATA_HEADER}: {err}");
+            return None;
+        }
+    };
+    if !map.values().all(Value::is_string) {
+        tracing::debug!("ignoring {REQUEST_METADATA_HEADER}: values must be strings");
+        return None;
+    }
+    (!map.is_empty()).then_some(Value::Object(map))
+}
+
+pub(super) const FORCE_CHANNEL_HEADER: &str = "x-monoize-force-channel";
+pub(super) const FORCE_UPSTREAM_MODEL_HEADER: &str = "x-monoize-force-upstream-model";
+
+/// Reads the `x-monoize-force-*` ops headers. They are honored only for keys
+/// owned by an admin; for everyone else they are ignored, not rejected.
+pub(super) fn extract_routing_override(
+    headers: &HeaderMap,
+    user_role: crate::users::UserRole,
+) -> Option<crate::auth::RoutingOverride> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let channel_id = header(FORCE_CHANNEL_HEADER);
+    let upstream_model = header(FORCE_UPSTREAM_MODEL_HEADER);
+    if channel_id.is_none() && upstream_model.is_none() {
+        return None;
+    }
+    if !user_role.can_manage_system() {
+        tracing::debug!("ignoring x-monoize-force-* headers from non-admin key");
+        return None;
+    }
+    Some(crate::auth::RoutingOverride {
+        channel_id,
+        upstream_model,
+    })
+}
+
+pub(super) const REQUEST_TIMEOUT_HEADER: &str = "x-monoize-timeout-ms";
+
+/// Reads the `x-monoize-timeout-ms` header, clamped to `max_timeout_ms`.
+/// Missing, unparsable, or zero values leave the configured timeout in place.
+pub(super) fn extract_request_timeout_override(
+    headers: &HeaderMap,
+    max_timeout_ms: u64,
+) -> Option<u64> {
+    headers
+        .get(REQUEST_TIMEOUT_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .map(|v| v.min(max_timeout_ms.max(1)))
+}
+
 /// Reject the request if the API key has an IP whitelist and the client IP is not in it.
 #[allow(clippy::result_large_err)]
 pub(super) fn check_ip_whitelist(
@@ -88,6 +172,75 @@ pub(super) fn extract_request_id(headers: &HeaderMap) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+/// Outcome of the `Idempotency-Key` check for a non-stream request.
+pub(super) enum IdempotencyGate {
+    Replay(Value),
+    Proceed(Option<IdempotencyClaim>),
+}
+
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Streaming requests ignore the header: a replay could not reproduce the
+/// original event timing, and partially delivered streams are not retryable.
+pub(super) async fn begin_idempotent_request(
+    state: &AppState,
+    auth: &crate::auth::AuthResult,
+    headers: &HeaderMap,
+    downstream: DownstreamProtocol,
+    stream: bool,
+) -> AppResult<IdempotencyGate> {
+    let Some(raw) = headers.get("idempotency-key") else {
+        return Ok(IdempotencyGate::Proceed(None));
+    };
+    if stream {
+        return Ok(IdempotencyGate::Proceed(None));
+    }
+    let idempotency_key = raw.to_str().map(str::trim).unwrap_or_default();
+    if idempotency_key.is_empty() || idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(AppError::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_idempotency_key",
+            format!(
+                "Idempotency-Key must be 1 to {MAX_IDEMPOTENCY_KEY_LEN} visible ASCII characters"
+            ),
+        ));
+    }
+    let downstream_label = match downstream {
+        DownstreamProtocol::Responses => "responses",
+        DownstreamProtocol::ChatCompletions => "chat_completions",
+        DownstreamProtocol::AnthropicMessages => "messages",
+    };
+    let user = auth.user_id.as_deref().unwrap_or(&auth.tenant_id);
+    let key = IdempotencyStore::key(user, downstream_label, idempotency_key);
+    let ttl =
+        std::time::Duration::from_secs(state.monoize_runtime.read().await.idempotency_ttl_seconds);
+    match state.idempotency.begin(key, ttl) {
+        IdempotencyLookup::Replay(value) => Ok(IdempotencyGate::Replay(value)),
+        IdempotencyLookup::Claimed(claim) => Ok(IdempotencyGate::Proceed(Some(claim))),
+        IdempotencyLookup::InFlight => Err(AppError::new(
+            StatusCode::CONFLICT,
+            "idempotency_key_in_use",
+            "a request with this Idempotency-Key is still in progress",
+        )),
+    }
+}
+
+pub(super) async fn complete_idempotent_request(
+    state: &AppState,
+    claim: Option<IdempotencyClaim>,
+    value: &Value,
+) {
+    let Some(claim) = claim else {
+        return;
+    };
+    let max_entries = state.monoize_runtime.read().await.idempotency_max_entries;
+    state.idempotency.complete(
+        claim,
+        value.clone(),
+        usize::try_from(max_entries).unwrap_or(usize::MAX),
+    );
+}
+
 pub(super) fn read_max_multiplier_from_extra(req: &urp::UrpRequest) -> Option<f64> {
     req.extra_body
         .get("max_multiplier")
@@ -102,6 +255,10 @@ pub(super) fn read_max_multiplier_from_extra(req: &urp::UrpRequest) -> Option<f6
 }
 
 pub(super) fn inject_monoize_context(auth: &crate::auth::AuthResult, req: &mut urp::UrpRequest) {
+    if let Some(user_id) = &auth.user_id {
+        req.extra_body
+            .insert("__monoize_user_id".to_string(), json!(user_id.clone()));
+    }
     if let Some(username) = &auth.username {
         req.extra_body
             .insert("__monoize_username".to_string(), json!(username.clone()));
@@ -115,6 +272,7 @@ pub(super) fn inject_monoize_context(auth: &crate::auth::AuthResult, req: &mut u
 }
 
 pub(super) fn strip_monoize_context(req: &mut urp::UrpRequest) {
+    req.extra_body.remove("__monoize_user_id");
     req.extra_body.remove("__monoize_username");
     req.extra_body.remove("__monoize_api_key_id");
 }
@@ -124,7 +282,7 @@ pub(super) async fn apply_transform_rules_request(
     req: &mut urp::UrpRequest,
     rules: &[TransformRuleConfig],
     match_model: &str,
-    upstream_provider_type: Option<ProviderType>,
+    attempt: &MonoizeAttempt,
 ) -> AppResult<()> {
     if rules.is_empty() {
         return Ok(());
@@ -140,7 +298,15 @@ pub(super) async fn apply_transform_rules_request(
     let context = transforms::TransformRuntimeContext {
         image_transform_cache: state.image_transform_cache.clone(),
         http_client: state.http.clone(),
-        upstream_provider_type,
+        upstream_provider_type: Some(attempt.provider_type),
+        upstream_channel: Some(transforms::UpstreamChannelContext {
+            channel_id: attempt.channel_id.clone(),
+            provider: build_channel_provider_config(attempt),
+            api_key: attempt.api_key.clone(),
+            request_timeout_ms: attempt.request_timeout_ms,
+        }),
+        gemini_context_cache: state.gemini_context_cache.clone(),
+        request_tool_names: Vec::new(),
     };
     transforms::apply_transforms(
         transforms::UrpData::Request(req),
@@ -161,12 +327,25 @@ pub(super) async fn apply_transform_rules_request(
     })
 }
 
+/// Function tool names as the client sent them, before any request-phase
+/// transform renamed them.
+pub(super) fn request_function_tool_names(req: &urp::UrpRequest) -> Vec<String> {
+    req.tools
+        .iter()
+        .flatten()
+        .filter(|tool| tool.tool_type == "function")
+        .filter_map(|tool| tool.function.as_ref())
+        .map(|function| function.name.clone())
+        .collect()
+}
+
 pub(super) async fn apply_transform_rules_response(
     state: &AppState,
     resp: &mut urp::UrpResponse,
     rules: &[TransformRuleConfig],
     model: &str,
     upstream_provider_type: Option<ProviderType>,
+    request_tool_names: &[String],
 ) -> AppResult<()> {
     if rules.is_empty() {
         return Ok(());
@@ -183,6 +362,9 @@ pub(super) async fn apply_transform_rules_response(
         image_transform_cache: state.image_transform_cache.clone(),
         http_client: state.http.clone(),
         upstream_provider_type,
+        upstream_channel: None,
+        gemini_context_cache: state.gemini_context_cache.clone(),
+        request_tool_names: request_tool_names.to_vec(),
     };
     transforms::apply_transforms(
         transforms::UrpData::Response(resp),
@@ -203,6 +385,7 @@ pub(super) async fn apply_transform_rules_response(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn transform_urp_stream(
     state: &AppState,
     mut rx: mpsc::Receiver<urp::UrpStreamEvent>,
@@ -213,6 +396,7 @@ pub(super) async fn transform_urp_stream(
     model: &str,
     upstream_provider_type: Option<ProviderType>,
     reasoning_envelope: Option<(&str, &str)>,
+    request_tool_names: Vec<String>,
 ) -> AppResult<()> {
     let mut provider_states =
         transforms::build_states_for_rules(provider_rules, state.transform_registry.as_ref())
@@ -246,9 +430,21 @@ pub(super) async fn transform_urp_stream(
         image_transform_cache: state.image_transform_cache.clone(),
         http_client: state.http.clone(),
         upstream_provider_type,
+        upstream_channel: None,
+        gemini_context_cache: state.gemini_context_cache.clone(),
+        request_tool_names,
     };
+    let finish_reason_map = state.monoize_runtime.read().await.finish_reason_map.clone();
 
     while let Some(mut event) = rx.recv().await {
+        if let urp::UrpStreamEvent::ResponseDone {
+            finish_reason,
+            extra_body,
+            ..
+        } = &mut event
+        {
+            urp::normalize_finish_reason_other(finish_reason, extra_body, &finish_reason_map);
+        }
         // Wrap newly produced encrypted reasoning payloads in mz2 envelopes
         // BEFORE any response-phase stream transform observes the event. Per
         // spec/urp-transform-system.spec.md PIPE-1 step 12 and PIPE-1d, and
@@ -361,9 +557,10 @@ fn affinity_value_from_json(value: &Value) -> Option<String> {
 
 fn stable_affinity_field(req: &urp::UrpRequest) -> Option<String> {
     if let Some(previous_response_id) = req
-        .extra_body
-        .get("previous_response_id")
-        .and_then(affinity_value_from_json)
+        .previous_response_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
     {
         return Some(format!("previous_response_id:{previous_response_id}"));
     }
@@ -481,6 +678,120 @@ pub(super) fn is_valid_embeddings_input(input: &Value) -> bool {
         .is_some_and(|arr| arr.iter().all(|item| item.as_str().is_some()))
 }
 
+pub(super) const EMBEDDINGS_CHUNK_CONCURRENCY: usize = 4;
+
+/// Splits an embeddings body whose `input` array exceeds `max_batch` into one
+/// body per chunk, returning the bodies and their chunk lengths. Returns `None`
+/// when no split is needed; a `max_batch` of 0 disables splitting.
+pub(super) fn split_embeddings_input(
+    body: &Value,
+    max_batch: usize,
+) -> Option<(Vec<Value>, Vec<usize>)> {
+    let items = body.get("input")?.as_array()?;
+    if max_batch == 0 || items.len() <= max_batch {
+        return None;
+    }
+    let mut base = body.as_object()?.clone();
+    base.remove("input");
+    let (bodies, lens) = items
+        .chunks(max_batch)
+        .map(|chunk| {
+            let mut chunk_body = base.clone();
+            chunk_body.insert("input".to_string(), Value::Array(chunk.to_vec()));
+            (Value::Object(chunk_body), chunk.len())
+        })
+        .unzip();
+    Some((bodies, lens))
+}
+
+/// Merges per-chunk embeddings responses in request order: `data[].index` is
+/// offset by the preceding chunk lengths and `usage` counters are summed.
+pub(super) fn merge_embeddings_chunks(responses: Vec<Value>, chunk_lens: &[usize]) -> Value {
+    let mut merged = Map::new();
+    let mut data = Vec::new();
+    let mut usage = Map::new();
+    let mut offset = 0u64;
+    for (response, chunk_len) in responses.into_iter().zip(chunk_lens) {
+        let Value::Object(mut obj) = response else {
+            offset += *chunk_len as u64;
+            continue;
+        };
+        if let Some(Value::Array(items)) = obj.remove("data") {
+            for (position, mut item) in items.into_iter().enumerate() {
+                let index = item
+                    .get("index")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(position as u64);
+                if let Some(item_obj) = item.as_object_mut() {
+                    item_obj.insert("index".to_string(), json!(offset + index));
+                }
+                data.push(item);
+            }
+        }
+        if let Some(Value::Object(chunk_usage)) = obj.remove("usage") {
+            for (key, value) in chunk_usage {
+                let Some(count) = value.as_u64() else {
+                    continue;
+                };
+                let total = usage.get(&key).and_then(Value::as_u64).unwrap_or(0);
+                usage.insert(key, json!(total + count));
+            }
+        }
+        for (key, value) in obj {
+            merged.entry(key).or_insert(value);
+        }
+        offset += *chunk_len as u64;
+    }
+    data.sort_by_key(|item| item.get("index").and_then(Value::as_u64));
+    merged.insert("data".to_string(), Value::Array(data));
+    if !usage.is_empty() {
+        merged.insert("usage".to_string(), Value::Object(usage));
+    }
+    Value::Object(merged)
+}
+
+/// Rewrites `data[].embedding` to the client's `encoding_format` when the
+/// upstream answered in the other one. `base64` is the little-endian `f32`
+/// byte sequence of the vector, as in the OpenAI API. Embeddings that cannot
+/// be converted are left untouched.
+pub(super) fn reencode_embeddings(value: &mut Value, encoding_format: &str) {
+    use base64::Engine as _;
+    let Some(items) = value.get_mut("data").and_then(Value::as_array_mut) else {
+        return;
+    };
+    for embedding in items
+        .iter_mut()
+        .filter_map(|item| item.get_mut("embedding"))
+    {
+        let converted = match (encoding_format, &*embedding) {
+            ("base64", Value::Array(floats)) => floats
+                .iter()
+                .map(|v| v.as_f64().map(|f| (f as f32).to_le_bytes()))
+                .collect::<Option<Vec<_>>>()
+                .map(|bytes| {
+                    Value::String(base64::engine::general_purpose::STANDARD.encode(bytes.concat()))
+                }),
+            ("float", Value::String(encoded)) => base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .ok()
+                .filter(|bytes| bytes.len() % 4 == 0)
+                .map(|bytes| {
+                    bytes
+                        .chunks_exact(4)
+                        .map(|chunk| {
+                            let f = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+                            json!(f as f64)
+                        })
+                        .collect()
+                }),
+            _ => None,
+        };
+        if let Some(converted) = converted {
+            *embedding = converted;
+        }
+    }
+}
+
 pub(super) fn read_max_multiplier_from_embeddings_body(body: &Value) -> Option<f64> {
     body.as_object()
         .and_then(|obj| obj.get("max_multiplier"))
@@ -573,6 +884,27 @@ pub(super) fn requires_buffered_response_stream(
         })
 }
 
+/// Whether any enabled Response-phase rule applies to `model`. Streams with
+/// such rules must go through the URP transcoding pipeline.
+pub(super) fn has_active_response_rules(
+    provider_rules: &[TransformRuleConfig],
+    global_rules: &[TransformRuleConfig],
+    auth_rules: &[TransformRuleConfig],
+    model: &str,
+) -> bool {
+    provider_rules
+        .iter()
+        .chain(global_rules.iter())
+        .chain(auth_rules.iter())
+        .filter(|rule| rule.enabled && rule.phase == Phase::Response)
+        .any(|rule| match &rule.models {
+            None => true,
+            Some(patterns) => patterns
+                .iter()
+                .any(|pattern| model_glob_match(pattern, model)),
+        })
+}
+
 pub(super) fn convert_assistant_images_to_markdown(resp: &mut urp::UrpResponse) {
     let mut pending_markdown = String::new();
     let mut last_assistant_text_idx: Option<usize> = None;
@@ -777,6 +1109,20 @@ fn default_extra_whitelist(provider_type: ProviderType) -> &'static [&'static st
     }
 }
 
+/// Fill `temperature` / `top_p` from the attempt's provider defaults when the
+/// client left them unset. Client-supplied values always win.
+pub(super) fn apply_provider_sampling_defaults(
+    req: &mut urp::UrpRequest,
+    attempt: &MonoizeAttempt,
+) {
+    if req.temperature.is_none() {
+        req.temperature = attempt.default_temperature;
+    }
+    if req.top_p.is_none() {
+        req.top_p = attempt.default_top_p;
+    }
+}
+
 /// Filter `req.extra_body` to only contain fields allowed by the upstream
 /// provider type's whitelist, optionally extended by a provider-level override.
 ///
@@ -1046,10 +1392,287 @@ pub(super) fn filter_tools_for_provider(
     }
 }
 
+const REDACTED_VALUE: &str = "[redacted]";
+const MIN_REDACTED_BASE64_CHARS: usize = 256;
+pub(super) const DEBUG_LOG_SSE_LINES: usize = 20;
+
+/// Copy of `value` that is safe to write to logs: credential-bearing keys are
+/// blanked and long base64 payloads (images, audio, encrypted reasoning) are
+/// replaced with their length.
+pub(super) fn redact_sensitive(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let value = if is_sensitive_key(key) && !value.is_null() {
+                        Value::String(REDACTED_VALUE.to_string())
+                    } else {
+                        redact_sensitive(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact_sensitive).collect()),
+        Value::String(text) if looks_like_base64(text) => {
+            Value::String(format!("[base64 {} chars]", text.len()))
+        }
+        other => other.clone(),
+    }
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    matches!(
+        key.to_ascii_lowercase().replace('-', "_").as_str(),
+        "api_key" | "apikey" | "x_api_key" | "x_goog_api_key" | "authorization"
+    )
+}
+
+fn looks_like_base64(text: &str) -> bool {
+    let payload = text
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(";base64,"))
+        .map_or(text, |(_, data)| data);
+    payload.len() >= MIN_REDACTED_BASE64_CHARS
+        && payload
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=' | b'-' | b'_'))
+}
+
+fn redact_sse_line(line: &str) -> String {
+    let Some(data) = line.strip_prefix("data:") else {
+        return line.to_string();
+    };
+    match serde_json::from_str::<Value>(data.trim_start()) {
+        Ok(value) => format!("data: {}", redact_sensitive(&value)),
+        Err(_) => line.to_string(),
+    }
+}
+
+/// Re-wraps a streaming upstream response so its first `max_lines` non-empty
+/// SSE lines are logged (redacted) at debug level as the decoder reads them.
+pub(super) fn log_upstream_sse_lines(
+    resp: reqwest::Response,
+    provider_id: String,
+    max_lines: usize,
+) -> reqwest::Response {
+    let mut builder = axum::http::Response::builder()
+        .status(resp.status())
+        .version(resp.version());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = resp.headers().clone();
+    }
+    let mut logged = 0usize;
+    let mut pending = String::new();
+    let stream = resp.bytes_stream().inspect(move |chunk| {
+        let Ok(chunk) = chunk else {
+            return;
+        };
+        if logged >= max_lines {
+            return;
+        }
+        pending.push_str(&String::from_utf8_lossy(chunk));
+        while let Some(pos) = pending.find('\n') {
+            let line = pending[..pos].trim_end_matches('\r').to_string();
+            pending.drain(..=pos);
+            if line.is_empty() {
+                continue;
+            }
+            tracing::debug!(
+                provider_id = %provider_id,
+                line = %redact_sse_line(&line),
+                "upstream stream line"
+            );
+            logged += 1;
+            if logged >= max_lines {
+                pending.clear();
+                break;
+            }
+        }
+    });
+    let resp = builder
+        .body(reqwest::Body::wrap_stream(stream))
+        .expect("status and version copied from a valid upstream response");
+    reqwest::Response::from(resp)
+}
+
+/// Forwards every upstream Responses SSE frame to `tx` as it arrives, while
+/// still yielding the original bytes so the URP decoder can collect usage and
+/// terminal diagnostics. Frames are forwarded verbatim, including their
+/// `sequence_number` and event types the URP model does not know about; only a
+/// top-level `response.model` is rewritten to `downstream_model`. The upstream
+/// `[DONE]` sentinel is dropped; the handler emits its own after the stream.
+pub(super) fn tee_responses_sse_passthrough(
+    resp: reqwest::Response,
+    tx: mpsc::Sender<Event>,
+    downstream_model: String,
+) -> reqwest::Response {
+    let mut builder = axum::http::Response::builder()
+        .status(resp.status())
+        .version(resp.version());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = resp.headers().clone();
+    }
+    let upstream = resp.bytes_stream().boxed();
+    let stream = futures_util::stream::unfold(
+        (upstream, Vec::<u8>::new(), tx, downstream_model, false),
+        |(mut upstream, mut pending, tx, model, done)| async move {
+            if done {
+                return None;
+            }
+            let Some(chunk) = upstream.next().await else {
+                // Flush a trailing frame the upstream did not terminate with a blank line.
+                let frame = String::from_utf8_lossy(&pending).into_owned();
+                forward_passthrough_sse_frame(&tx, &frame, &model).await;
+                return None;
+            };
+            if let Ok(bytes) = &chunk {
+                pending.extend(bytes.iter().copied().filter(|b| *b != b'\r'));
+                while let Some(pos) = pending.windows(2).position(|w| w == b"\n\n") {
+                    let frame = String::from_utf8_lossy(&pending[..pos]).into_owned();
+                    pending.drain(..pos + 2);
+                    forward_passthrough_sse_frame(&tx, &frame, &model).await;
+                }
+            }
+            let done = chunk.is_err();
+            Some((chunk, (upstream, pending, tx, model, done)))
+        },
+    );
+    let resp = builder
+        .body(reqwest::Body::wrap_stream(stream))
+        .expect("status and version copied from a valid upstream response");
+    reqwest::Response::from(resp)
+}
+
+async fn forward_passthrough_sse_frame(tx: &mpsc::Sender<Event>, frame: &str, model: &str) {
+    let mut name: Option<&str> = None;
+    let mut data_lines = Vec::new();
+    for line in frame.lines() {
+        if let Some(rest) = line.strip_prefix("event:") {
+            name = Some(rest.trim());
+        } else if let Some(rest) = line.strip_prefix("data:") {
+            data_lines.push(rest.strip_prefix(' ').unwrap_or(rest));
+        }
+    }
+    let mut data = data_lines.join("\n");
+    if data_lines.is_empty() || data == "[DONE]" {
+        return;
+    }
+    if let Ok(mut value) = serde_json::from_str::<Value>(&data)
+        && let Some(slot) = value
+            .get_mut("response")
+            .and_then(|response| response.get_mut("model"))
+            .filter(|slot| slot.is_string())
+    {
+        *slot = Value::String(model.to_string());
+        data = value.to_string();
+    }
+    let _ = match name {
+        Some(name) if !name.is_empty() => {
+            urp::stream_helpers::send_named_sse_data(tx, name, data).await
+        }
+        _ => urp::stream_helpers::send_plain_sse_data(tx, data).await,
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn reencode_embeddings_converts_float_arrays_to_base64_and_back() {
+        let mut value = json!({
+            "data": [
+                { "object": "embedding", "index": 0, "embedding": [0.5, -1.25, 3.0] },
+                { "object": "embedding", "index": 1, "embedding": [] }
+            ]
+        });
+        reencode_embeddings(&mut value, "base64");
+        // 0.5f32, -1.25f32, 3.0f32 as little-endian bytes.
+        assert_eq!(value["data"][0]["embedding"], json!("AAAAPwAAoL8AAEBA"));
+        assert_eq!(value["data"][1]["embedding"], json!(""));
+
+        reencode_embeddings(&mut value, "float");
+        assert_eq!(value["data"][0]["embedding"], json!([0.5, -1.25, 3.0]));
+        assert_eq!(value["data"][1]["embedding"], json!([]));
+    }
+
+    #[test]
+    fn reencode_embeddings_keeps_matching_or_unconvertible_payloads() {
+        let mut value = json!({
+            "data": [
+                { "index": 0, "embedding": [0.5] },
+                { "index": 1, "embedding": "AAA" },
+                { "index": 2, "embedding": "not base64!" }
+            ]
+        });
+        let original = value.clone();
+        reencode_embeddings(&mut value, "float");
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn redact_sensitive_blanks_credentials_and_long_base64() {
+        let image = "iVBORw0KGgo".repeat(40);
+        let body = json!({
+            "model": "gpt-5",
+            "api_key": "sk-live",
+            "headers": { "Authorization": "Bearer sk-live", "x-api-key": "sk-ant" },
+            "input": [
+                { "type": "input_image", "image_url": format!("data:image/png;base64,{image}") },
+                { "type": "input_text", "text": "short text stays" },
+                { "inline_data": { "data": image.clone() } }
+            ],
+            "user": null
+        });
+
+        let redacted = redact_sensitive(&body);
+
+        assert_eq!(redacted["model"], json!("gpt-5"));
+        assert_eq!(redacted["api_key"], json!("[redacted]"));
+        assert_eq!(redacted["headers"]["Authorization"], json!("[redacted]"));
+        assert_eq!(redacted["headers"]["x-api-key"], json!("[redacted]"));
+        assert_eq!(
+            redacted["input"][0]["image_url"],
+            json!(format!("[base64 {} chars]", image.len() + 22))
+        );
+        assert_eq!(redacted["input"][1]["text"], json!("short text stays"));
+        assert_eq!(
+            redacted["input"][2]["inline_data"]["data"],
+            json!(format!("[base64 {} chars]", image.len()))
+        );
+        assert!(redacted["user"].is_null());
+    }
+
+    #[test]
+    fn redact_sensitive_keeps_long_prose() {
+        let prose = "a sentence with spaces ".repeat(20);
+        assert_eq!(redact_sensitive(&json!(prose)), json!(prose));
+        assert_eq!(
+            redact_sse_line(r#"data: {"authorization":"secret","delta":"hi"}"#),
+            r#"data: {"authorization":"[redacted]","delta":"hi"}"#
+        );
+        assert_eq!(redact_sse_line("event: ping"), "event: ping");
+    }
+
+    #[tokio::test]
+    async fn logged_upstream_stream_passes_bytes_through() {
+        let raw = "event: message\ndata: {\"api_key\":\"sk\"}\n\ndata: [DONE]\n\n";
+        let resp = reqwest::Response::from(
+            axum::http::Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", "text/event-stream")
+                .body(raw)
+                .unwrap(),
+        );
+
+        let logged = log_upstream_sse_lines(resp, "p1".to_string(), 1);
+
+        assert_eq!(logged.status(), StatusCode::OK);
+        assert_eq!(logged.headers()["content-type"], "text/event-stream");
+        assert_eq!(logged.text().await.unwrap(), raw);
+    }
+
     #[test]
     fn default_extra_whitelists_cover_current_protocol_extension_fields() {
         for field in [
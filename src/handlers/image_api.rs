@@ -3,6 +3,9 @@ use axum::extract::Multipart;
 use base64::Engine as _;
 use std::collections::HashMap;
 
+/// `request_logs.request_kind` stamped on every Image API sub-request log.
+const IMAGE_REQUEST_KIND: &str = "image";
+
 pub async fn create_image_generation(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -43,6 +46,7 @@ pub async fn create_image_generation(
     let n = parse_n_field(obj.get("n"))?;
 
     ensure_model_allowed(&auth, &model)?;
+    ensure_model_passes_filters(&auth, &model)?;
 
     let max_multiplier_val =
         resolve_image_max_multiplier(obj.get("max_multiplier"), &headers, &auth);
@@ -180,6 +184,7 @@ pub async fn create_image_edit(
     };
 
     ensure_model_allowed(&auth, &model)?;
+    ensure_model_passes_filters(&auth, &model)?;
 
     let max_multiplier_val = {
         let ceiling = auth.max_multiplier;
@@ -381,6 +386,7 @@ async fn fan_out_subrequests(
             stream: Some(false),
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -390,6 +396,11 @@ async fn fan_out_subrequests(
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: extra_body.clone(),
         };
         let rid = request_id
@@ -455,6 +466,7 @@ async fn execute_image_subrequest_typed(
             raw_input: Value::Object(serde_json::Map::new()),
             session: None,
         },
+        Some(IMAGE_REQUEST_KIND),
     )
     .await
 }
@@ -472,6 +484,7 @@ async fn execute_stream_collected_image_typed(
     let transform_match_model =
         normalized_logical_model_for_matching(state, &requested_model).await;
     resolve_model_suffix(state, &mut req).await;
+    ensure_model_passes_filters(auth, &req.model)?;
     let original_req = req.clone();
     let logical_model = req.model.clone();
     let routing_stub = build_routing_stub(&req, max_multiplier);
@@ -492,19 +505,32 @@ async fn execute_stream_collected_image_typed(
     let mut tried_providers: Vec<TriedProvider> = Vec::new();
     let mut execution_state = AttemptExecutionState::default();
 
-    for attempt in attempts {
+    for mut attempt in attempts {
         execution_state.enter_provider(&attempt.provider_id);
         if !execution_state.provider_budget_remaining(&attempt) {
             continue;
         }
+        let Ok(_channel_permit) = try_acquire_channel_permit(state, &attempt).await else {
+            continue;
+        };
 
         let max_channel_attempts = (attempt.channel_max_retries + 1).max(1) as usize;
-        for channel_attempt in 0..max_channel_attempts {
-            if !execution_state.provider_budget_remaining(&attempt) {
+        // A key rejected with 401/403 is retried on the same channel with the
+        // pool's next key without using up a channel retry.
+        let max_key_retries = attempt.api_key_pool.len().saturating_sub(1);
+        let mut key_retries = 0;
+        for send_index in 0..max_channel_attempts + max_key_retries {
+            let channel_attempt = send_index - key_retries;
+            if channel_attempt >= max_channel_attempts
+                || !execution_state.provider_budget_remaining(&attempt)
+            {
                 break;
             }
 
             let attempt_number = execution_state.record_upstream_attempt();
+            if let Some(api_key) = next_channel_key(state, &attempt).await {
+                attempt.api_key = api_key;
+            }
             let mut req_attempt = original_req.clone();
             if let Some(target_protocol) = super::provider_type_protocol(attempt.provider_type) {
                 urp::retain_provider_items_for_protocol(&mut req_attempt.input, target_protocol);
@@ -519,12 +545,13 @@ async fn execute_stream_collected_image_typed(
             }
             inject_monoize_context(auth, &mut req_attempt);
             req_attempt.model = attempt.upstream_model.clone();
+            apply_provider_sampling_defaults(&mut req_attempt, &attempt);
             apply_transform_rules_request(
                 state,
                 &mut req_attempt,
                 &attempt.provider_transforms,
                 &transform_match_model,
-                Some(attempt.provider_type),
+                &attempt,
             )
             .await?;
             let global_transforms = state.monoize_runtime.read().await.global_transforms.clone();
@@ -533,7 +560,7 @@ async fn execute_stream_collected_image_typed(
                 &mut req_attempt,
                 &global_transforms,
                 &transform_match_model,
-                Some(attempt.provider_type),
+                &attempt,
             )
             .await?;
             apply_transform_rules_request(
@@ -541,29 +568,46 @@ async fn execute_stream_collected_image_typed(
                 &mut req_attempt,
                 &auth.transforms,
                 &transform_match_model,
-                Some(attempt.provider_type),
+                &attempt,
             )
             .await?;
             strip_monoize_context(&mut req_attempt);
             req_attempt.stream = Some(true);
 
-            let upstream_body = encode_request_for_provider(
+            let encoded = encode_request_for_provider(
+                state,
                 &mut req_attempt,
                 &attempt,
                 super::DownstreamProtocol::Responses,
-            )?;
+            )
+            .await?;
             let provider = build_channel_provider_config(&attempt);
             let path = upstream_path_for_model(attempt.provider_type, &req_attempt.model, true);
-            let call = upstream::call_upstream_raw_with_timeout_and_headers(
-                client_http(state),
-                &provider,
-                &attempt.api_key,
-                &path,
-                &upstream_body,
-                attempt.request_timeout_ms.saturating_mul(10).max(600_000),
-                provider_extra_headers(attempt.provider_type, &upstream_body),
-            )
-            .await;
+            let call = match encoded {
+                Ok(EncodedRequest { value, bytes }) => {
+                    upstream::call_upstream_stream_json_bytes_with_headers(
+                        client_http(state),
+                        &provider,
+                        &attempt.api_key,
+                        &path,
+                        bytes,
+                        attempt.request_timeout_ms,
+                        provider_extra_headers(attempt.provider_type, &value),
+                    )
+                    .await
+                }
+                Err(EncodeFailure::Upstream(err)) => Err(err),
+                Err(EncodeFailure::Oversized(app_err)) => {
+                    execution_state.record_size_rejection(&app_err);
+                    tried_providers.push(TriedProvider::from_app_error(
+                        attempt_number,
+                        &attempt,
+                        &app_err,
+                    ));
+                    last_failed_attempt = Some(attempt.clone());
+                    break;
+                }
+            };
 
             match call {
                 Ok(upstream_resp) => {
@@ -595,12 +639,7 @@ async fn execute_stream_collected_image_typed(
                     let (transformed_tx, mut transformed_rx) =
                         mpsc::channel::<crate::urp::UrpStreamEvent>(64);
                     let runtime_metrics = Arc::new(Mutex::new(StreamRuntimeMetrics::default()));
-                    let stream_idle_timeout_ms = state
-                        .monoize_runtime
-                        .read()
-                        .await
-                        .stream_idle_timeout_ms
-                        .max(1);
+                    let stream_idle_timeout_ms = attempt.stream_idle_timeout_ms;
 
                     let decode_handle = {
                         let runtime_metrics = runtime_metrics.clone();
@@ -637,6 +676,7 @@ async fn execute_stream_collected_image_typed(
                             &model_for_transform,
                             Some(transform_provider_type),
                             None,
+                            Vec::new(),
                         )
                         .await
                     });
@@ -733,11 +773,14 @@ async fn execute_stream_collected_image_typed(
                         None,
                         req.reasoning.as_ref().and_then(|r| r.effort.clone()),
                         tried_providers,
+                        Some(IMAGE_REQUEST_KIND),
                     );
                     return Ok((resp, logical_model.clone()));
                 }
                 Err(err) => {
-                    let non_retryable = is_non_retryable_client_error(&err);
+                    let key_rejected = suspend_rejected_channel_key(state, &attempt, &err).await;
+                    let retry_next_key = key_rejected && key_retries < max_key_retries;
+                    let non_retryable = is_non_retryable_client_error(&err) && !retry_next_key;
                     let retryable = is_retryable_error(&err);
                     let retryable_failure_class = classify_retryable_failure(&err);
                     let app_err = upstream_error_to_app(err);
@@ -754,9 +797,20 @@ async fn execute_stream_collected_image_typed(
                             &app_err,
                             req.reasoning.as_ref().and_then(|r| r.effort.clone()),
                             tried_providers,
+                            Some(IMAGE_REQUEST_KIND),
                         );
                         return Err(app_err);
                     }
+                    if retry_next_key {
+                        key_retries += 1;
+                        tried_providers.push(TriedProvider::from_app_error(
+                            attempt_number,
+                            &attempt,
+                            &app_err,
+                        ));
+                        last_failed_attempt = Some(attempt.clone());
+                        continue;
+                    }
                     if retryable {
                         clear_channel_affinity(state, &attempt).await;
                         tried_providers.push(TriedProvider::from_app_error(
@@ -772,7 +826,11 @@ async fn execute_stream_collected_image_typed(
                         }
                         if execution_state.provider_budget_remaining(&attempt) {
                             if channel_attempt + 1 < max_channel_attempts {
-                                maybe_sleep_before_channel_retry(&attempt).await;
+                                maybe_sleep_before_channel_retry(
+                                    &attempt,
+                                    retryable_failure_class.retry_after(),
+                                )
+                                .await;
                             }
                             continue;
                         }
@@ -790,6 +848,7 @@ async fn execute_stream_collected_image_typed(
                         &app_err,
                         req.reasoning.as_ref().and_then(|r| r.effort.clone()),
                         tried_providers,
+                        Some(IMAGE_REQUEST_KIND),
                     );
                     return Err(app_err);
                 }
@@ -797,7 +856,9 @@ async fn execute_stream_collected_image_typed(
         }
     }
 
-    let final_err = build_exhausted_upstream_error(&logical_model, &tried_providers);
+    let final_err = execution_state
+        .exhaustive_size_rejection(tried_providers.len())
+        .unwrap_or_else(|| build_exhausted_upstream_error(&logical_model, &tried_providers));
     if let Some(attempt) = last_failed_attempt {
         spawn_request_log_error(
             state,
@@ -811,6 +872,7 @@ async fn execute_stream_collected_image_typed(
             &final_err,
             req.reasoning.as_ref().and_then(|r| r.effort.clone()),
             tried_providers,
+            Some(IMAGE_REQUEST_KIND),
         );
     } else {
         spawn_request_log_error_no_attempt(
@@ -824,6 +886,7 @@ async fn execute_stream_collected_image_typed(
             &final_err,
             req.reasoning.as_ref().and_then(|r| r.effort.clone()),
             tried_providers,
+            Some(IMAGE_REQUEST_KIND),
         );
     }
     Err(final_err)
@@ -909,10 +972,11 @@ fn assemble_image_response(
     let mut data_items: Vec<Value> = Vec::new();
     let mut last_error: Option<AppError> = None;
     let mut total_usage: Option<AggregatedUsage> = None;
+    let mut response_model: Option<String> = None;
 
     for result in results {
         match result {
-            Ok((resp, _logical_model)) => {
+            Ok((resp, logical_model)) => {
                 let images = extract_images_from_response(&resp);
                 if images.is_empty() {
                     let upstream_text = collect_response_text(&resp);
@@ -930,6 +994,7 @@ fn assemble_image_response(
                     ));
                     continue;
                 }
+                response_model.get_or_insert(logical_model);
                 for img in images {
                     let mut item = Map::new();
                     if let Some(b64) = img.b64_json {
@@ -989,6 +1054,12 @@ fn assemble_image_response(
         "created": created,
         "data": data_items,
     });
+    if let Some(model) = response_model {
+        response
+            .as_object_mut()
+            .unwrap()
+            .insert("model".to_string(), Value::String(model));
+    }
 
     if let Some(usage) = total_usage {
         response.as_object_mut().unwrap().insert(
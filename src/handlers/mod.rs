@@ -1,5 +1,7 @@
+pub(crate) mod audio_api;
 mod billing;
 mod compact;
+mod echo;
 pub(crate) mod helpers;
 pub(crate) mod image_api;
 mod nonstream;
@@ -17,6 +19,7 @@ mod tests;
 use crate::app::AppState;
 use crate::config::{ProviderAuthConfig, ProviderAuthType, ProviderConfig, ProviderType};
 use crate::error::{AppError, AppResult};
+use crate::idempotency::{IdempotencyClaim, IdempotencyLookup, IdempotencyStore};
 use crate::request_capture::RequestCaptureSession;
 use crate::settings::normalize_pricing_model_key;
 use crate::transforms::{self, Phase, TransformRuleConfig};
@@ -29,7 +32,7 @@ use axum::extract::State;
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::sse::{Event, KeepAlive};
 use axum::response::{IntoResponse, Response, Sse};
-use futures_util::StreamExt;
+use futures_util::{StreamExt, TryStreamExt};
 use serde_json::{Map, Value, json};
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -37,6 +40,7 @@ use std::time::Duration;
 use tokio::sync::{Mutex, mpsc};
 
 use billing::*;
+use echo::*;
 use helpers::*;
 use nonstream::*;
 use request_logging::*;
@@ -45,6 +49,7 @@ use streaming::*;
 use usage::*;
 
 pub use compact::compact_response;
+pub use nonstream::CachedNonstreamResponse;
 pub use responses_websocket::responses_websocket;
 
 #[allow(clippy::result_large_err)]
@@ -62,6 +67,85 @@ fn ensure_model_allowed(auth: &crate::auth::AuthResult, logical_model: &str) ->
     ))
 }
 
+/// Strict gateway mode: rejects a request that breaks URP invariants with
+/// every violation listed, rather than leaving it to an upstream 400.
+async fn ensure_request_valid(state: &AppState, req: &urp::UrpRequest) -> AppResult<()> {
+    if !state.monoize_runtime.read().await.validate_requests {
+        return Ok(());
+    }
+    let violations = urp::validate::validate_request(req);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    Err(request_validation_error(violations))
+}
+
+/// Strict gateway mode, once the attempts are known: drops attempts whose
+/// provider would reject the request's role order. The request fails only
+/// when no attempt remains, with the violations of the first dropped one.
+async fn retain_role_order_attempts(
+    state: &AppState,
+    attempts: &mut Vec<MonoizeAttempt>,
+    req: &urp::UrpRequest,
+) -> AppResult<()> {
+    if !state.monoize_runtime.read().await.validate_requests {
+        return Ok(());
+    }
+    let mut first_rejection = None;
+    attempts.retain(|attempt| {
+        let Some(target) = provider_type_protocol(attempt.provider_type) else {
+            return true;
+        };
+        let violations = urp::validate::validate_role_order(req, target);
+        if violations.is_empty() {
+            return true;
+        }
+        first_rejection.get_or_insert(violations);
+        false
+    });
+    match first_rejection {
+        Some(violations) if attempts.is_empty() => Err(request_validation_error(violations)),
+        _ => Ok(()),
+    }
+}
+
+fn request_validation_error(violations: Vec<urp::validate::RequestViolation>) -> AppError {
+    let message = violations
+        .iter()
+        .map(|violation| format!("{}: {}", violation.path, violation.message))
+        .collect::<Vec<_>>()
+        .join("; ");
+    AppError::new(
+        StatusCode::BAD_REQUEST,
+        "request_validation_failed",
+        message,
+    )
+    .with_details(json!({ "violations": violations }))
+}
+
+/// Checks the API key's `allow_models` / `deny_models` globs against the
+/// model after alias and suffix resolution. A deny match always wins.
+#[allow(clippy::result_large_err)]
+fn ensure_model_passes_filters(auth: &crate::auth::AuthResult, model: &str) -> AppResult<()> {
+    let denied = auth
+        .deny_models
+        .iter()
+        .any(|pattern| model_glob_match(pattern, model));
+    let allowed = auth.allow_models.is_empty()
+        || auth
+            .allow_models
+            .iter()
+            .any(|pattern| model_glob_match(pattern, model));
+    if !denied && allowed {
+        return Ok(());
+    }
+    Err(AppError::new(
+        StatusCode::FORBIDDEN,
+        "model_not_allowed",
+        format!("model '{model}' is not allowed for this API key"),
+    ))
+}
+
 fn apply_model_redirects_to_model(model: &mut String, rules: &[crate::users::ModelRedirectRule]) {
     for rule in rules {
         if let Ok(re) = regex::Regex::new(&format!("^(?:{})$", rule.pattern)) {
@@ -93,6 +177,33 @@ fn messages_stream_keep_alive() -> KeepAlive {
         .event(Event::default().event("ping").data(r#"{"type":"ping"}"#))
 }
 
+/// Applies the opt-in SSE framing settings to an API stream: a `retry:` hint on
+/// the first event and sequential `id:` fields. Keep-alive frames are not
+/// numbered.
+async fn frame_api_stream<S>(
+    state: &AppState,
+    stream: S,
+) -> impl futures_util::Stream<Item = Result<Event, std::convert::Infallible>> + Send + 'static
+where
+    S: futures_util::Stream<Item = Result<Event, std::convert::Infallible>> + Send + 'static,
+{
+    let (retry_ms, event_ids) = {
+        let runtime = state.monoize_runtime.read().await;
+        (runtime.sse_retry_ms, runtime.sse_event_ids)
+    };
+    stream.enumerate().map(move |(index, item)| {
+        item.map(|mut event| {
+            if index == 0 && retry_ms > 0 {
+                event = event.retry(Duration::from_millis(retry_ms));
+            }
+            if event_ids {
+                event = event.id((index + 1).to_string());
+            }
+            event
+        })
+    })
+}
+
 pub async fn list_models(State(state): State<AppState>, headers: HeaderMap) -> AppResult<Response> {
     let auth = auth_tenant(&headers, &state).await?;
     let providers =
@@ -141,9 +252,22 @@ pub async fn create_response(
     let mut req = decode_urp_request(DownstreamProtocol::Responses, known, extra)?;
     apply_model_redirects(&mut req, &auth);
     ensure_model_allowed(&auth, &req.model)?;
+    ensure_request_valid(&state, &req).await?;
     let max_multiplier = resolve_max_multiplier(&req, &headers, &auth);
     let request_id = extract_request_id(&headers);
     let request_ip = extract_client_ip(&headers);
+    let idempotency_claim = match begin_idempotent_request(
+        &state,
+        &auth,
+        &headers,
+        DownstreamProtocol::Responses,
+        req.stream.unwrap_or(false),
+    )
+    .await?
+    {
+        IdempotencyGate::Replay(value) => return Ok(Json(value).into_response()),
+        IdempotencyGate::Proceed(claim) => claim,
+    };
     let capture = RequestCaptureContext {
         raw_input,
         session: state
@@ -184,16 +308,18 @@ pub async fn create_response(
         )
         .await
         {
-            Ok(stream) => {
-                return Ok(Sse::new(stream)
+            Ok((stream, route)) => {
+                let mut response = Sse::new(frame_api_stream(&state, stream).await)
                     .keep_alive(api_stream_keep_alive())
-                    .into_response());
+                    .into_response();
+                attach_routing_headers(&state, &mut response, route.as_ref()).await;
+                return Ok(response);
             }
             Err(err) => return Err(err),
         }
     }
 
-    let value = forward_nonstream_typed(
+    let (value, route) = forward_nonstream_typed(
         &state,
         &auth,
         req,
@@ -204,7 +330,10 @@ pub async fn create_response(
         capture,
     )
     .await?;
-    Ok(Json(value).into_response())
+    complete_idempotent_request(&state, idempotency_claim, &value).await;
+    let mut response = Json(value).into_response();
+    attach_routing_headers(&state, &mut response, Some(&route)).await;
+    Ok(response)
 }
 
 pub async fn create_chat_completions(
@@ -218,9 +347,22 @@ pub async fn create_chat_completions(
     let mut req = decode_urp_request(DownstreamProtocol::ChatCompletions, known, extra)?;
     apply_model_redirects(&mut req, &auth);
     ensure_model_allowed(&auth, &req.model)?;
+    ensure_request_valid(&state, &req).await?;
     let max_multiplier = resolve_max_multiplier(&req, &headers, &auth);
     let request_id = extract_request_id(&headers);
     let request_ip = extract_client_ip(&headers);
+    let idempotency_claim = match begin_idempotent_request(
+        &state,
+        &auth,
+        &headers,
+        DownstreamProtocol::ChatCompletions,
+        req.stream.unwrap_or(false),
+    )
+    .await?
+    {
+        IdempotencyGate::Replay(value) => return Ok(Json(value).into_response()),
+        IdempotencyGate::Proceed(claim) => claim,
+    };
     let capture = RequestCaptureContext {
         raw_input,
         session: state
@@ -248,15 +390,17 @@ pub async fn create_chat_completions(
         )
         .await
         {
-            Ok(stream) => {
-                return Ok(Sse::new(stream)
+            Ok((stream, route)) => {
+                let mut response = Sse::new(frame_api_stream(&state, stream).await)
                     .keep_alive(api_stream_keep_alive())
-                    .into_response());
+                    .into_response();
+                attach_routing_headers(&state, &mut response, route.as_ref()).await;
+                return Ok(response);
             }
             Err(err) => return Err(err),
         }
     }
-    let value = forward_nonstream_typed(
+    let (value, route) = forward_nonstream_typed(
         &state,
         &auth,
         req,
@@ -267,7 +411,10 @@ pub async fn create_chat_completions(
         capture,
     )
     .await?;
-    Ok(Json(value).into_response())
+    complete_idempotent_request(&state, idempotency_claim, &value).await;
+    let mut response = Json(value).into_response();
+    attach_routing_headers(&state, &mut response, Some(&route)).await;
+    Ok(response)
 }
 
 pub async fn create_messages(
@@ -293,9 +440,22 @@ async fn create_messages_inner(
     let mut req = decode_urp_request(DownstreamProtocol::AnthropicMessages, known, extra)?;
     apply_model_redirects(&mut req, &auth);
     ensure_model_allowed(&auth, &req.model)?;
+    ensure_request_valid(&state, &req).await?;
     let max_multiplier = resolve_max_multiplier(&req, &headers, &auth);
     let request_id = extract_request_id(&headers);
     let request_ip = extract_client_ip(&headers);
+    let idempotency_claim = match begin_idempotent_request(
+        &state,
+        &auth,
+        &headers,
+        DownstreamProtocol::AnthropicMessages,
+        req.stream.unwrap_or(false),
+    )
+    .await?
+    {
+        IdempotencyGate::Replay(value) => return Ok(Json(value).into_response()),
+        IdempotencyGate::Proceed(claim) => claim,
+    };
     let capture = RequestCaptureContext {
         raw_input,
         session: state
@@ -323,15 +483,17 @@ async fn create_messages_inner(
         )
         .await
         {
-            Ok(stream) => {
-                return Ok(Sse::new(stream)
+            Ok((stream, route)) => {
+                let mut response = Sse::new(frame_api_stream(&state, stream).await)
                     .keep_alive(messages_stream_keep_alive())
-                    .into_response());
+                    .into_response();
+                attach_routing_headers(&state, &mut response, route.as_ref()).await;
+                return Ok(response);
             }
             Err(err) => return Err(err),
         }
     }
-    let value = forward_nonstream_typed(
+    let (value, route) = forward_nonstream_typed(
         &state,
         &auth,
         req,
@@ -342,7 +504,10 @@ async fn create_messages_inner(
         capture,
     )
     .await?;
-    Ok(Json(value).into_response())
+    complete_idempotent_request(&state, idempotency_claim, &value).await;
+    let mut response = Json(value).into_response();
+    attach_routing_headers(&state, &mut response, Some(&route)).await;
+    Ok(response)
 }
 
 fn anthropic_error_response(err: AppError, request_id: Option<&str>) -> Response {
@@ -368,6 +533,9 @@ fn anthropic_error_response(err: AppError, request_id: Option<&str>) -> Response
         if let Some(param) = err.upstream_param {
             obj.insert("upstream_param".to_string(), Value::String(param));
         }
+        if let Some(details) = err.details {
+            obj.insert("details".to_string(), details);
+        }
     }
     let mut body = json!({ "type": "error", "error": error });
     if let Some(request_id) = request_id.filter(|value| !value.is_empty()) {
@@ -411,6 +579,7 @@ pub async fn create_embeddings(
         .to_string();
     apply_model_redirects_to_model(&mut logical_model, &auth.model_redirects);
     ensure_model_allowed(&auth, &logical_model)?;
+    ensure_model_passes_filters(&auth, &logical_model)?;
 
     let input = obj.get("input").ok_or_else(|| {
         AppError::new(StatusCode::BAD_REQUEST, "invalid_request", "missing input")
@@ -423,21 +592,23 @@ pub async fn create_embeddings(
         ));
     }
 
-    if let Some(encoding_format) = obj.get("encoding_format") {
-        let encoding_format = encoding_format.as_str().ok_or_else(|| {
+    let mut encoding_format = "float".to_string();
+    if let Some(requested) = obj.get("encoding_format") {
+        let requested = requested.as_str().ok_or_else(|| {
             AppError::new(
                 StatusCode::BAD_REQUEST,
                 "invalid_request",
                 "encoding_format must be 'float' or 'base64'",
             )
         })?;
-        if encoding_format != "float" && encoding_format != "base64" {
+        if requested != "float" && requested != "base64" {
             return Err(AppError::new(
                 StatusCode::BAD_REQUEST,
                 "invalid_request",
                 "encoding_format must be 'float' or 'base64'",
             ));
         }
+        encoding_format = requested.to_string();
     }
 
     let max_multiplier = resolve_max_multiplier_for_embeddings(&body, &headers, &auth);
@@ -457,23 +628,37 @@ pub async fn create_embeddings(
         started_at,
     )
     .await;
+    let max_embedding_batch = state.monoize_runtime.read().await.max_embedding_batch as usize;
     let mut last_failed_attempt: Option<MonoizeAttempt> = None;
     let mut tried_providers: Vec<TriedProvider> = Vec::new();
     let mut execution_state = AttemptExecutionState::default();
 
-    for attempt in attempts {
+    for mut attempt in attempts {
         execution_state.enter_provider(&attempt.provider_id);
         if !execution_state.provider_budget_remaining(&attempt) {
             continue;
         }
+        let Ok(_channel_permit) = try_acquire_channel_permit(&state, &attempt).await else {
+            continue;
+        };
 
         let max_channel_attempts = (attempt.channel_max_retries + 1).max(1) as usize;
-        for channel_attempt in 0..max_channel_attempts {
-            if !execution_state.provider_budget_remaining(&attempt) {
+        // A key rejected with 401/403 is retried on the same channel with the
+        // pool's next key without using up a channel retry.
+        let max_key_retries = attempt.api_key_pool.len().saturating_sub(1);
+        let mut key_retries = 0;
+        for send_index in 0..max_channel_attempts + max_key_retries {
+            let channel_attempt = send_index - key_retries;
+            if channel_attempt >= max_channel_attempts
+                || !execution_state.provider_budget_remaining(&attempt)
+            {
                 break;
             }
 
             let attempt_number = execution_state.record_upstream_attempt();
+            if let Some(api_key) = next_channel_key(&state, &attempt).await {
+                attempt.api_key = api_key;
+            }
             let mut upstream_body = body.clone();
             if let Some(upstream_obj) = upstream_body.as_object_mut() {
                 upstream_obj.insert(
@@ -482,17 +667,9 @@ pub async fn create_embeddings(
                 );
             }
 
-            let provider = build_channel_provider_config(&attempt);
-            let result = upstream::call_upstream_with_timeout_and_headers(
-                client_http(&state),
-                &provider,
-                &attempt.api_key,
-                "/v1/embeddings",
-                &upstream_body,
-                attempt.request_timeout_ms,
-                &[],
-            )
-            .await;
+            let result =
+                call_upstream_embeddings(&state, &attempt, upstream_body, max_embedding_batch)
+                    .await;
 
             match result {
                 Ok(mut value) => {
@@ -520,6 +697,7 @@ pub async fn create_embeddings(
                     if let Some(obj) = value.as_object_mut() {
                         obj.insert("model".to_string(), Value::String(logical_model.clone()));
                     }
+                    reencode_embeddings(&mut value, &encoding_format);
 
                     spawn_request_log(
                         &state,
@@ -539,12 +717,15 @@ pub async fn create_embeddings(
                         None,
                         None,
                         tried_providers,
+                        None,
                     );
 
                     return Ok(Json(value).into_response());
                 }
                 Err(err) => {
-                    let non_retryable = is_non_retryable_client_error(&err);
+                    let key_rejected = suspend_rejected_channel_key(&state, &attempt, &err).await;
+                    let retry_next_key = key_rejected && key_retries < max_key_retries;
+                    let non_retryable = is_non_retryable_client_error(&err) && !retry_next_key;
                     let retryable = is_retryable_error(&err);
                     let retryable_failure_class = classify_retryable_failure(&err);
                     let app_err = upstream_error_to_app(err);
@@ -561,9 +742,20 @@ pub async fn create_embeddings(
                             &app_err,
                             None,
                             tried_providers,
+                            None,
                         );
                         return Err(app_err);
                     }
+                    if retry_next_key {
+                        key_retries += 1;
+                        tried_providers.push(TriedProvider::from_app_error(
+                            attempt_number,
+                            &attempt,
+                            &app_err,
+                        ));
+                        last_failed_attempt = Some(attempt.clone());
+                        continue;
+                    }
                     if retryable {
                         tried_providers.push(TriedProvider::from_app_error(
                             attempt_number,
@@ -578,7 +770,11 @@ pub async fn create_embeddings(
                         }
                         if execution_state.provider_budget_remaining(&attempt) {
                             if channel_attempt + 1 < max_channel_attempts {
-                                maybe_sleep_before_channel_retry(&attempt).await;
+                                maybe_sleep_before_channel_retry(
+                                    &attempt,
+                                    retryable_failure_class.retry_after(),
+                                )
+                                .await;
                             }
                             continue;
                         }
@@ -596,6 +792,7 @@ pub async fn create_embeddings(
                         &app_err,
                         None,
                         tried_providers,
+                        None,
                     );
                     return Err(app_err);
                 }
@@ -616,6 +813,7 @@ pub async fn create_embeddings(
             &final_err,
             None,
             tried_providers,
+            None,
         );
     } else {
         spawn_request_log_error_no_attempt(
@@ -629,11 +827,47 @@ pub async fn create_embeddings(
             &final_err,
             None,
             tried_providers,
+            None,
         );
     }
     Err(final_err)
 }
 
+/// Calls upstream embeddings, splitting an `input` array longer than
+/// `max_batch` into chunks that run with bounded concurrency. The first failed
+/// chunk fails the whole call.
+async fn call_upstream_embeddings(
+    state: &AppState,
+    attempt: &MonoizeAttempt,
+    upstream_body: Value,
+    max_batch: usize,
+) -> Result<Value, UpstreamCallError> {
+    let provider = build_channel_provider_config(attempt);
+    let call = |body: Value| {
+        let provider = &provider;
+        async move {
+            upstream::call_upstream_with_timeout_and_headers(
+                client_http_nonstream(state),
+                provider,
+                &attempt.api_key,
+                "/v1/embeddings",
+                &body,
+                attempt.request_timeout_ms,
+                &[],
+            )
+            .await
+        }
+    };
+    let Some((chunk_bodies, chunk_lens)) = split_embeddings_input(&upstream_body, max_batch) else {
+        return call(upstream_body).await;
+    };
+    let responses: Vec<Value> = futures_util::stream::iter(chunk_bodies.into_iter().map(call))
+        .buffered(EMBEDDINGS_CHUNK_CONCURRENCY)
+        .try_collect()
+        .await?;
+    Ok(merge_embeddings_chunks(responses, &chunk_lens))
+}
+
 const URP_KNOWN_RESPONSE_FIELDS: [&str; 13] = [
     "model",
     "input",
@@ -688,24 +922,43 @@ struct MonoizeAttempt {
     channel_id: String,
     base_url: String,
     api_key: String,
+    /// Distinct keys the channel rotates over. `api_key` is drawn from this
+    /// pool only when the attempt is sent (see `next_channel_key`).
+    api_key_pool: Vec<String>,
     logical_model: String,
     upstream_model: String,
     model_multiplier: f64,
+    /// `model_multiplier` as a nano-scaled integer, converted once at routing
+    /// time; all charge math uses this value.
+    model_multiplier_nano: Option<i128>,
     server_tool_usage_classes: Vec<String>,
     provider_transforms: Vec<TransformRuleConfig>,
     passive_failure_count_threshold: u32,
     passive_cooldown_seconds: u64,
     passive_window_seconds: u64,
     passive_rate_limit_cooldown_seconds: u64,
+    probe_success_threshold: u32,
     channel_max_retries: i32,
     channel_retry_interval_ms: u64,
     circuit_breaker_enabled: bool,
     per_model_circuit_break: bool,
     provider_attempt_limit: Option<usize>,
     request_timeout_ms: u64,
+    stream_idle_timeout_ms: u64,
+    max_concurrency: Option<u32>,
+    org_id: Option<String>,
+    project_id: Option<String>,
     extra_fields_whitelist: Option<Vec<String>>,
     strip_cross_protocol_nested_extra: bool,
+    debug_log_bodies: bool,
+    default_temperature: Option<f64>,
+    default_top_p: Option<f64>,
+    /// False when the model entry opts out of upstream streaming.
+    supports_streaming: bool,
     billable_pricing_available: bool,
+    /// Every resolved rate is zero-priced or tagged `free`, so the attempt
+    /// never charges and does not need a spendable balance.
+    free_pricing: bool,
     affinity_key: Option<String>,
     affinity_key_hash: Option<String>,
     affinity_hit: Option<bool>,
@@ -724,14 +977,16 @@ fn reasoning_envelope_provider_type(provider_type: ProviderType) -> &'static str
     }
 }
 
-async fn maybe_sleep_before_channel_retry(attempt: &MonoizeAttempt) {
-    if attempt.channel_retry_interval_ms == 0 {
+/// Waits the channel retry interval, or the upstream `Retry-After` delay when
+/// that is longer, capped by the attempt's request timeout.
+async fn maybe_sleep_before_channel_retry(attempt: &MonoizeAttempt, retry_after: Option<Duration>) {
+    let delay = Duration::from_millis(attempt.channel_retry_interval_ms)
+        .max(retry_after.unwrap_or_default())
+        .min(Duration::from_millis(attempt.request_timeout_ms));
+    if delay.is_zero() {
         return;
     }
-    tokio::time::sleep(std::time::Duration::from_millis(
-        attempt.channel_retry_interval_ms,
-    ))
-    .await;
+    tokio::time::sleep(delay).await;
 }
 
 async fn normalized_pricing_model_key(state: &AppState, model_id: &str) -> String {
@@ -775,6 +1030,9 @@ struct AttemptExecutionState {
     current_provider_id: Option<String>,
     provider_attempts_used: usize,
     next_attempt_number: u32,
+    decode_failures: u64,
+    size_rejections: usize,
+    last_size_rejection: Option<AppError>,
 }
 
 impl AttemptExecutionState {
@@ -798,6 +1056,37 @@ impl AttemptExecutionState {
         self.next_attempt_number = self.next_attempt_number.saturating_add(1);
         self.next_attempt_number
     }
+
+    /// Numbers a hedged attempt without charging it against the budget of
+    /// the provider currently being retried.
+    fn record_hedged_attempt(&mut self) -> u32 {
+        self.next_attempt_number = self.next_attempt_number.saturating_add(1);
+        self.next_attempt_number
+    }
+
+    /// Counts an undecodable upstream response and reports whether the
+    /// request may still move on to the next attempt.
+    fn record_decode_failure(&mut self, limit: u64) -> bool {
+        self.decode_failures = self.decode_failures.saturating_add(1);
+        self.decode_failures <= limit
+    }
+
+    /// Counts an attempt that was not sent because its body exceeds the
+    /// provider's size limit.
+    fn record_size_rejection(&mut self, err: &AppError) {
+        self.size_rejections = self.size_rejections.saturating_add(1);
+        self.last_size_rejection = Some(err.clone());
+    }
+
+    /// The size rejection to return when all `tried` attempts were rejected
+    /// for size.
+    fn exhaustive_size_rejection(&self, tried: usize) -> Option<AppError> {
+        if tried > 0 && self.size_rejections == tried {
+            self.last_size_rejection.clone()
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -861,6 +1150,8 @@ pub(crate) struct StreamRuntimeMetrics {
     response_id: Option<String>,
     terminal: StreamTerminalDiagnostics,
     pub(crate) estimated_output_tokens: u64,
+    /// Counts `estimated_output_tokens`; the heuristic estimator when unset.
+    pub(crate) token_estimator: Option<&'static dyn crate::token_estimator::TokenEstimator>,
     first_visible_output_ms: Option<u64>,
     last_visible_output_ms: Option<u64>,
     visible_output_bytes: u64,
@@ -914,18 +1205,25 @@ async fn auth_tenant(headers: &HeaderMap, state: &AppState) -> AppResult<crate::
         ));
     };
 
-    let auth_result = state
+    let mut auth_result = state
         .auth
         .authenticate_token(token, Some(&state.user_store))
         .await
         .ok_or_else(|| AppError::new(StatusCode::UNAUTHORIZED, "unauthorized", "invalid token"))?;
     check_ip_whitelist(&auth_result, headers)?;
+    auth_result.request_metadata = extract_request_metadata(headers);
+    auth_result.routing_override = extract_routing_override(headers, auth_result.user_role);
+    auth_result.request_timeout_ms = extract_request_timeout_override(
+        headers,
+        state.monoize_runtime.read().await.max_request_timeout_ms,
+    );
     Ok(auth_result)
 }
 
 async fn ensure_balance_before_forward(
     state: &AppState,
     auth: &crate::auth::AuthResult,
+    min_amount_nano_usd: i128,
 ) -> AppResult<()> {
     if auth.sub_account_enabled {
         let Some(api_key_id) = auth.api_key_id.as_deref() else {
@@ -933,7 +1231,7 @@ async fn ensure_balance_before_forward(
         };
         return match state
             .user_store
-            .ensure_sub_account_can_spend(api_key_id)
+            .ensure_sub_account_can_spend(api_key_id, min_amount_nano_usd)
             .await
         {
             Ok(()) => Ok(()),
@@ -959,7 +1257,11 @@ async fn ensure_balance_before_forward(
     let Some(user_id) = auth.user_id.as_deref() else {
         return Ok(());
     };
-    match state.user_store.ensure_user_can_spend(user_id).await {
+    match state
+        .user_store
+        .ensure_user_can_spend(user_id, min_amount_nano_usd)
+        .await
+    {
         Ok(()) => Ok(()),
         Err(err) => match err.kind {
             BillingErrorKind::InsufficientBalance => Err(AppError::new(
@@ -984,7 +1286,7 @@ async fn ensure_balance_before_forward(
 fn attempts_require_balance(attempts: &[MonoizeAttempt]) -> bool {
     attempts
         .iter()
-        .any(|attempt| attempt.billable_pricing_available)
+        .any(|attempt| attempt.billable_pricing_available && !attempt.free_pricing)
 }
 
 async fn ensure_balance_before_forward_for_attempts(
@@ -995,7 +1297,31 @@ async fn ensure_balance_before_forward_for_attempts(
     if !attempts_require_balance(attempts) {
         return Ok(());
     }
-    ensure_balance_before_forward(state, auth).await
+    ensure_balance_before_forward(state, auth, 0).await
+}
+
+/// Like [`ensure_balance_before_forward_for_attempts`], but also requires the
+/// balance to cover the request's worst-case charge on its first billable
+/// attempt, so `max_output_tokens` and `n` are priced before forwarding.
+async fn ensure_balance_before_forward_for_request(
+    state: &AppState,
+    auth: &crate::auth::AuthResult,
+    attempts: &[MonoizeAttempt],
+    req: &urp::UrpRequest,
+) -> AppResult<()> {
+    if !attempts_require_balance(attempts) {
+        return Ok(());
+    }
+    let estimate = match attempts
+        .iter()
+        .find(|attempt| attempt.billable_pricing_available && !attempt.free_pricing)
+    {
+        Some(attempt) => estimate_max_charge_nano(state, auth, attempt, req)
+            .await?
+            .unwrap_or(0),
+        None => 0,
+    };
+    ensure_balance_before_forward(state, auth, estimate).await
 }
 
 #[allow(clippy::result_large_err)]
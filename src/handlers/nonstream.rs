@@ -1,5 +1,6 @@
 use super::*;
 use crate::urp::stream_decode::stream_upstream_to_urp_events;
+use std::borrow::Cow;
 use std::collections::HashSet;
 
 pub(crate) fn strip_orphaned_tool_calls(req: &mut urp::UrpRequest) {
@@ -28,26 +29,85 @@ pub(crate) fn strip_orphaned_tool_calls(req: &mut urp::UrpRequest) {
 }
 
 fn has_responses_state_reference(req: &urp::UrpRequest) -> bool {
-    ["previous_response_id", "conversation"]
-        .into_iter()
-        .any(|key| {
-            req.extra_body
-                .get(key)
-                .is_some_and(|value| !value.is_null())
-        })
+    req.previous_response_id.is_some()
+        || req
+            .extra_body
+            .get("conversation")
+            .is_some_and(|value| !value.is_null())
+}
+
+const RESPONSE_CACHE_REQUEST_KIND: &str = "cached";
+
+/// Successful non-stream result replayed for identical deterministic requests.
+#[derive(Clone)]
+pub struct CachedNonstreamResponse {
+    response: urp::UrpResponse,
+    attempt: MonoizeAttempt,
+}
+
+/// Keyed per API key so tenants never observe each other's responses. The
+/// request is hashed after model-suffix resolution, before any per-attempt
+/// rewriting, so the key covers model, input nodes, and sampling parameters.
+fn response_cache_key(auth: &crate::auth::AuthResult, req: &urp::UrpRequest) -> Option<u128> {
+    if !auth.response_cache_enabled
+        || auth.routing_override.is_some()
+        || req.temperature != Some(0.0)
+        || req.tools.as_ref().is_some_and(|tools| !tools.is_empty())
+        || has_responses_state_reference(req)
+    {
+        return None;
+    }
+    let material = serde_json::to_vec(&json!({
+        "api_key_id": auth.api_key_id,
+        "request": req,
+    }))
+    .ok()?;
+    Some(xxhash_rust::xxh3::xxh3_128(&material))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn execute_nonstream_typed(
     state: &AppState,
     auth: &crate::auth::AuthResult,
-    mut req: urp::UrpRequest,
+    req: urp::UrpRequest,
     max_multiplier: Option<f64>,
     downstream: DownstreamProtocol,
     request_id: Option<String>,
     request_ip: Option<String>,
     capture: RequestCaptureContext,
+    request_kind: Option<&'static str>,
 ) -> AppResult<(urp::UrpResponse, String)> {
+    let (resp, response_model, _) = execute_nonstream_routed(
+        state,
+        auth,
+        req,
+        max_multiplier,
+        downstream,
+        request_id,
+        request_ip,
+        capture,
+        request_kind,
+    )
+    .await?;
+    Ok((resp, response_model))
+}
+
+/// Same as [`execute_nonstream_typed`], additionally reporting which attempt
+/// served the response.
+#[allow(clippy::too_many_arguments)]
+async fn execute_nonstream_routed(
+    state: &AppState,
+    auth: &crate::auth::AuthResult,
+    mut req: urp::UrpRequest,
+    max_multiplier: Option<f64>,
+    downstream: DownstreamProtocol,
+    request_id: Option<String>,
+    request_ip: Option<String>,
+    capture: RequestCaptureContext,
+    request_kind: Option<&'static str>,
+) -> AppResult<(urp::UrpResponse, String, ServedRoute)> {
     let started_at = std::time::Instant::now();
+    resolve_model_alias(state, &mut req).await;
     let requested_model = req.model.clone();
     let transform_match_model =
         normalized_logical_model_for_matching(state, &requested_model).await;
@@ -57,11 +117,85 @@ pub(super) async fn execute_nonstream_typed(
     // because cross-family strip runs BEFORE all transforms per-attempt
     // (auto_cache_* etc. must observe the stripped request so their cache
     // breakpoints actually survive into the upstream encoding).
+    ensure_model_passes_filters(auth, &req.model)?;
     let original_req = req.clone();
+    let request_tool_names = request_function_tool_names(&original_req);
     let logical_model = req.model.clone();
+    if is_echo_model(&logical_model) {
+        let attempt = echo_attempt();
+        let resp = echo_response(&req);
+        spawn_request_log(
+            state,
+            auth,
+            &attempt,
+            &logical_model,
+            resp.usage.clone(),
+            Some(0),
+            None,
+            false,
+            started_at,
+            request_id,
+            request_ip,
+            attempt.channel_id.clone(),
+            None,
+            None,
+            None,
+            req.reasoning.as_ref().and_then(|r| r.effort.clone()),
+            Vec::new(),
+            request_kind,
+        );
+        if let Some(session) = capture.session.as_ref() {
+            session
+                .persist_with_result(resp.usage.as_ref(), false)
+                .await;
+        }
+        let route = ServedRoute::from_attempt(&attempt);
+        return Ok((resp, logical_model, route));
+    }
+    let cache_key = response_cache_key(auth, &req);
+    if let Some(key) = cache_key {
+        let ttl = Duration::from_secs(
+            state
+                .monoize_runtime
+                .read()
+                .await
+                .response_cache_ttl_seconds,
+        );
+        if let Some(cached) = state.response_cache.get(key, ttl) {
+            spawn_request_log(
+                state,
+                auth,
+                &cached.attempt,
+                &logical_model,
+                cached.response.usage.clone(),
+                Some(0),
+                None,
+                false,
+                started_at,
+                request_id,
+                request_ip,
+                cached.attempt.channel_id.clone(),
+                None,
+                None,
+                None,
+                req.reasoning.as_ref().and_then(|r| r.effort.clone()),
+                Vec::new(),
+                Some(RESPONSE_CACHE_REQUEST_KIND),
+            );
+            if let Some(session) = capture.session.as_ref() {
+                session
+                    .persist_with_result(cached.response.usage.as_ref(), false)
+                    .await;
+            }
+            let route = ServedRoute::from_attempt(&cached.attempt);
+            return Ok((cached.response, logical_model, route));
+        }
+    }
     let routing_stub = build_routing_stub(&req, max_multiplier);
-    let attempts = build_monoize_attempts(state, &routing_stub, auth).await?;
-    ensure_balance_before_forward_for_attempts(state, auth, &attempts).await?;
+    let mut attempts = build_monoize_attempts(state, &routing_stub, auth).await?;
+    retain_conversation_chaining_attempts(&mut attempts, &req)?;
+    retain_role_order_attempts(state, &mut attempts, &req).await?;
+    ensure_balance_before_forward_for_request(state, auth, &attempts, &req).await?;
     let _pending_request_log_guard = insert_pending_request_log(
         state,
         auth,
@@ -75,183 +209,168 @@ pub(super) async fn execute_nonstream_typed(
     let mut last_failed_attempt: Option<MonoizeAttempt> = None;
     let mut tried_providers: Vec<TriedProvider> = Vec::new();
     let mut execution_state = AttemptExecutionState::default();
-    for attempt in attempts {
+    let decode_retry_limit = state.monoize_runtime.read().await.decode_retry_limit;
+    let hedge_after = state
+        .monoize_runtime
+        .read()
+        .await
+        .model_hedge_after_ms
+        .get(&logical_model)
+        .copied()
+        .filter(|delay_ms| *delay_ms > 0)
+        .map(Duration::from_millis);
+    // At most one hedge per request; the channel it ran on is not retried.
+    let mut hedged_index: Option<usize> = None;
+    let mut last_decode_error: Option<AppError> = None;
+    for (attempt_index, attempt) in attempts.iter().enumerate() {
+        if hedged_index == Some(attempt_index) {
+            continue;
+        }
         execution_state.enter_provider(&attempt.provider_id);
-        if !execution_state.provider_budget_remaining(&attempt) {
+        if !execution_state.provider_budget_remaining(attempt) {
             continue;
         }
+        let Ok(_channel_permit) = try_acquire_channel_permit(state, attempt).await else {
+            continue;
+        };
 
         let max_channel_attempts = (attempt.channel_max_retries + 1).max(1) as usize;
-        for channel_attempt in 0..max_channel_attempts {
-            if !execution_state.provider_budget_remaining(&attempt) {
+        // A key rejected with 401/403 is retried on the same channel with the
+        // pool's next key without using up a channel retry.
+        let max_key_retries = attempt.api_key_pool.len().saturating_sub(1);
+        let mut key_retries = 0;
+        for send_index in 0..max_channel_attempts + max_key_retries {
+            let channel_attempt = send_index - key_retries;
+            if channel_attempt >= max_channel_attempts
+                || !execution_state.provider_budget_remaining(attempt)
+            {
                 break;
             }
 
-            let attempt_number = execution_state.record_upstream_attempt();
-            // Clone from the pristine original request (pre-transforms) so
-            // that the cross-family strip can run BEFORE provider, global,
-            // and API-key transforms. This guarantees that transforms which
-            // inject upstream-specific part-level metadata (e.g.
-            // `auto_cache_system`, `auto_cache_tool_use`) survive into the
-            // encoded upstream request even when the downstream and upstream
-            // protocol families differ.
-            let mut req_attempt = original_req.clone();
-            if let Some(target_protocol) = provider_type_protocol(attempt.provider_type) {
-                urp::retain_provider_items_for_protocol(&mut req_attempt.input, target_protocol);
-                if target_protocol == urp::ProviderProtocol::Responses {
-                    urp::remove_downstream_only_reasoning_for_responses(&mut req_attempt.input);
-                }
-            }
-            if attempt.strip_cross_protocol_nested_extra
-                && !downstream.is_same_family(attempt.provider_type)
-            {
-                urp::strip_nested_extra_body(&mut req_attempt.input);
-            }
-            inject_monoize_context(auth, &mut req_attempt);
-            req_attempt.model = attempt.upstream_model.clone();
-            // Unwrap mz2 reasoning envelopes BEFORE any request-phase transform
-            // observes the request input. Per spec/urp-transform-system.spec.md
-            // PIPE-1 step 6 and PIPE-1d, transforms must not see encrypted
-            // reasoning replays still in `mz2.` envelope form, and they must not
-            // be allowed to mutate the reasoning payload before envelope-bound
-            // provider/model checks (PR4c.6) decide whether to keep or drop the
-            // replayed reasoning node for this attempt.
-            urp::filter_and_unwrap_reasoning_envelopes_for_upstream(
-                &mut req_attempt.input,
-                reasoning_envelope_provider_type(attempt.provider_type),
-                &req_attempt.model,
-                auth.reasoning_envelope_enabled,
-            );
-            apply_transform_rules_request(
-                state,
-                &mut req_attempt,
-                &attempt.provider_transforms,
-                &transform_match_model,
-                Some(attempt.provider_type),
-            )
-            .await?;
+            let mut attempt_number = execution_state.record_upstream_attempt();
             let global_transforms = state.monoize_runtime.read().await.global_transforms.clone();
-            apply_transform_rules_request(
+            let primary = send_nonstream_attempt(
                 state,
-                &mut req_attempt,
+                auth,
+                &original_req,
+                attempt,
                 &global_transforms,
                 &transform_match_model,
-                Some(attempt.provider_type),
-            )
-            .await?;
-            apply_transform_rules_request(
-                state,
-                &mut req_attempt,
-                &auth.transforms,
-                &transform_match_model,
-                Some(attempt.provider_type),
-            )
-            .await?;
-            strip_monoize_context(&mut req_attempt);
-
-            let upstream_body =
-                encode_request_for_provider(&mut req_attempt, &attempt, downstream)?;
-            let provider = build_channel_provider_config(&attempt);
-            let openai_image_edit = attempt.provider_type == ProviderType::OpenaiImage
-                && urp::encode::openai_image::has_user_image_input(&req_attempt);
-            let path = if openai_image_edit {
-                "/v1/images/edits".to_string()
-            } else {
-                upstream_path_for_model(
-                    attempt.provider_type,
-                    &req_attempt.model,
-                    req_attempt.stream.unwrap_or(false),
-                )
-            };
-            let call_value = if req_attempt.stream == Some(true)
-                && supports_nonstream_upstream_stream_collection(attempt.provider_type)
-            {
-                let stream_idle_timeout_ms = state
-                    .monoize_runtime
-                    .read()
-                    .await
-                    .stream_idle_timeout_ms
-                    .max(1);
-                let call = upstream::call_upstream_raw_with_timeout_and_headers(
-                    client_http(state),
-                    &provider,
-                    &attempt.api_key,
-                    &path,
-                    &upstream_body,
-                    attempt.request_timeout_ms.saturating_mul(10).max(600_000),
-                    provider_extra_headers(attempt.provider_type, &upstream_body),
-                )
-                .await;
-                match call {
-                    Ok(upstream_resp) => match collect_streamed_upstream_response(
-                        &req_attempt,
-                        max_multiplier,
-                        attempt.provider_type,
-                        upstream_resp,
-                        started_at,
-                        &logical_model,
-                        stream_idle_timeout_ms,
-                    )
-                    .await
-                    {
-                        Ok(resp) => Ok((None, Some(resp))),
-                        Err(err) => return Err(err),
-                    },
-                    Err(err) => Err(err),
-                }
-            } else if openai_image_edit {
-                let form =
-                    urp::encode::openai_image::multipart_form(&req_attempt, &req_attempt.model)
-                        .map_err(|e| {
-                            AppError::new(StatusCode::BAD_REQUEST, "invalid_request", e)
-                        })?;
-                match upstream::call_upstream_multipart_with_timeout_and_headers(
-                    client_http(state),
-                    &provider,
-                    &attempt.api_key,
-                    &path,
-                    form,
-                    attempt.request_timeout_ms,
-                    provider_extra_headers(attempt.provider_type, &upstream_body),
-                )
-                .await
-                {
-                    Ok(resp) => {
-                        let status = resp.status();
-                        match resp.text().await {
-                            Ok(text) => serde_json::from_str::<Value>(&text)
-                                .map(|value| (Some(value), None))
-                                .map_err(|err| {
-                                    upstream::UpstreamCallError::new(
-                                        upstream::UpstreamErrorKind::Http,
-                                        Some(status),
-                                        err.to_string(),
-                                    )
-                                }),
-                            Err(err) => Err(upstream::UpstreamCallError::new(
-                                upstream::UpstreamErrorKind::Network,
-                                Some(status),
-                                err.to_string(),
-                            )),
-                        }
+                downstream,
+                max_multiplier,
+                started_at,
+                &logical_model,
+            );
+            let hedge_candidate = hedge_after
+                .filter(|_| channel_attempt == 0 && hedged_index.is_none())
+                .and_then(|delay| {
+                    attempts
+                        .iter()
+                        .enumerate()
+                        .skip(attempt_index + 1)
+                        .find(|(_, candidate)| {
+                            candidate.logical_model == attempt.logical_model
+                                && candidate.channel_id != attempt.channel_id
+                        })
+                        .map(|(index, candidate)| (delay, index, candidate))
+                });
+            let (sent, hedge_winner_number) = match hedge_candidate {
+                Some((delay, hedge_index, hedge_attempt)) => {
+                    let hedge = async {
+                        let Ok(_hedge_permit) =
+                            try_acquire_channel_permit(state, hedge_attempt).await
+                        else {
+                            return None;
+                        };
+                        Some(
+                            send_nonstream_attempt(
+                                state,
+                                auth,
+                                &original_req,
+                                hedge_attempt,
+                                &global_transforms,
+                                &transform_match_model,
+                                downstream,
+                                max_multiplier,
+                                started_at,
+                                &logical_model,
+                            )
+                            .await,
+                        )
+                    };
+                    let outcome = race_hedged(primary, delay, hedge, sent_attempt_succeeded).await;
+                    let mut hedge_number = None;
+                    let (sent, primary_failure, hedge_failure) = match outcome.winner {
+                        HedgeWinner::Hedge => (outcome.result, outcome.loser, None),
+                        HedgeWinner::Primary => (outcome.result, None, outcome.loser),
+                    };
+                    if outcome.winner == HedgeWinner::Hedge || hedge_failure.is_some() {
+                        hedged_index = Some(hedge_index);
+                        hedge_number = Some(execution_state.record_hedged_attempt());
                     }
-                    Err(err) => Err(err),
+                    if let Some(failure) = primary_failure {
+                        record_lost_attempt(
+                            state,
+                            &mut execution_state,
+                            &mut tried_providers,
+                            attempt_number,
+                            attempt,
+                            failure,
+                        )
+                        .await;
+                    }
+                    if let (Some(failure), Some(number)) = (hedge_failure, hedge_number) {
+                        record_lost_attempt(
+                            state,
+                            &mut execution_state,
+                            &mut tried_providers,
+                            number,
+                            hedge_attempt,
+                            failure,
+                        )
+                        .await;
+                    }
+                    let winner_number =
+                        hedge_number.filter(|_| outcome.winner == HedgeWinner::Hedge);
+                    (sent, winner_number)
+                }
+                None => (primary.await, None),
+            };
+            if let Some(number) = hedge_winner_number {
+                attempt_number = number;
+            }
+            let SentAttempt {
+                attempt,
+                req: req_attempt,
+                body: upstream_body,
+                path,
+                result: call_value,
+            } = match sent? {
+                Ok(sent) => sent,
+                Err(app_err) => {
+                    execution_state.record_size_rejection(&app_err);
+                    tried_providers.push(TriedProvider::from_app_error(
+                        attempt_number,
+                        attempt,
+                        &app_err,
+                    ));
+                    last_failed_attempt = Some(attempt.clone());
+                    break;
                 }
-            } else {
-                upstream::call_upstream_with_timeout_and_headers(
-                    client_http(state),
-                    &provider,
-                    &attempt.api_key,
-                    &path,
-                    &upstream_body,
-                    attempt.request_timeout_ms,
-                    provider_extra_headers(attempt.provider_type, &upstream_body),
-                )
-                .await
-                .map(|value| (Some(value), None))
             };
+            let attempt: &MonoizeAttempt = &attempt;
             match call_value {
                 Ok((value, collected_resp)) => {
+                    if attempt.debug_log_bodies
+                        && let Some(value) = value.as_ref()
+                    {
+                        tracing::debug!(
+                            provider_id = %attempt.provider_id,
+                            channel_id = %attempt.channel_id,
+                            body = %redact_sensitive(value),
+                            "upstream response body"
+                        );
+                    }
                     if let Some(session) = capture.session.as_ref() {
                         session
                             .push_attempt(crate::request_capture::build_attempt_dump(
@@ -274,7 +393,7 @@ pub(super) async fn execute_nonstream_typed(
                     update_pending_channel_info(
                         state,
                         auth,
-                        &attempt,
+                        attempt,
                         &logical_model,
                         false,
                         request_id.as_deref(),
@@ -282,8 +401,8 @@ pub(super) async fn execute_nonstream_typed(
                         started_at,
                     )
                     .await;
-                    mark_channel_success(state, &attempt).await;
-                    refresh_channel_affinity(state, &attempt).await;
+                    mark_channel_success(state, attempt).await;
+                    refresh_channel_affinity(state, attempt).await;
                     let mut resp = match collected_resp {
                         Some(resp) => resp,
                         None => match decode_response_from_provider(
@@ -293,20 +412,51 @@ pub(super) async fn execute_nonstream_typed(
                         ) {
                             Ok(resp) => resp,
                             Err(err) => {
-                                if let Some(session) = capture.session.as_ref() {
-                                    session.persist_with_result(None, false).await;
+                                if !execution_state.record_decode_failure(decode_retry_limit) {
+                                    if let Some(session) = capture.session.as_ref() {
+                                        session.persist_with_result(None, false).await;
+                                    }
+                                    return Err(err);
                                 }
-                                return Err(err);
+                                clear_channel_affinity(state, attempt).await;
+                                tried_providers.push(TriedProvider::from_app_error(
+                                    attempt_number,
+                                    attempt,
+                                    &err,
+                                ));
+                                mark_channel_retryable_failure(
+                                    state,
+                                    attempt,
+                                    RetryableFailureClass::Transient,
+                                )
+                                .await;
+                                last_failed_attempt = Some(attempt.clone());
+                                last_decode_error = Some(err);
+                                if !is_attempt_channel_healthy(state, attempt).await {
+                                    break;
+                                }
+                                if execution_state.provider_budget_remaining(attempt) {
+                                    if channel_attempt + 1 < max_channel_attempts {
+                                        maybe_sleep_before_channel_retry(attempt, None).await;
+                                    }
+                                    continue;
+                                }
+                                break;
                             }
                         },
                     };
+                    urp::normalize_finish_reason_other(
+                        &mut resp.finish_reason,
+                        &resp.extra_body,
+                        &state.monoize_runtime.read().await.finish_reason_map,
+                    );
                     if attempt.provider_type == ProviderType::Responses {
                         refresh_response_id_affinity(
                             state,
                             auth,
                             &logical_model,
                             &resp.id,
-                            &attempt,
+                            attempt,
                         )
                         .await;
                     }
@@ -330,6 +480,7 @@ pub(super) async fn execute_nonstream_typed(
                         &attempt.provider_transforms,
                         &req.model,
                         Some(attempt.provider_type),
+                        &request_tool_names,
                     )
                     .await
                     {
@@ -344,6 +495,7 @@ pub(super) async fn execute_nonstream_typed(
                         &global_transforms,
                         &req.model,
                         Some(attempt.provider_type),
+                        &request_tool_names,
                     )
                     .await
                     {
@@ -358,6 +510,7 @@ pub(super) async fn execute_nonstream_typed(
                         &auth.transforms,
                         &req.model,
                         Some(attempt.provider_type),
+                        &request_tool_names,
                     )
                     .await
                     {
@@ -372,7 +525,7 @@ pub(super) async fn execute_nonstream_typed(
                         convert_assistant_images_to_markdown(&mut resp);
                     }
                     let charge =
-                        match maybe_charge_response(state, auth, &attempt, &logical_model, &resp)
+                        match maybe_charge_response(state, auth, attempt, &logical_model, &resp)
                             .await
                         {
                             Ok(charge) => charge,
@@ -386,7 +539,7 @@ pub(super) async fn execute_nonstream_typed(
                     spawn_request_log(
                         state,
                         auth,
-                        &attempt,
+                        attempt,
                         &logical_model,
                         resp.usage.clone(),
                         charge.charge_nano_usd,
@@ -401,13 +554,31 @@ pub(super) async fn execute_nonstream_typed(
                         None,
                         req.reasoning.as_ref().and_then(|r| r.effort.clone()),
                         tried_providers,
+                        request_kind,
                     );
                     if let Some(session) = capture.session.as_ref() {
                         session
                             .persist_with_result(resp.usage.as_ref(), false)
                             .await;
                     }
-                    return Ok((resp, logical_model.clone()));
+                    if let Some(key) = cache_key {
+                        let max_entries = state
+                            .monoize_runtime
+                            .read()
+                            .await
+                            .response_cache_max_entries;
+                        state.response_cache.insert(
+                            key,
+                            CachedNonstreamResponse {
+                                response: resp.clone(),
+                                attempt: attempt.clone(),
+                            },
+                            usize::try_from(max_entries).unwrap_or(usize::MAX),
+                        );
+                    }
+                    let response_model =
+                        downstream_model_for_attempt(state, &logical_model, attempt).await;
+                    return Ok((resp, response_model, ServedRoute::from_attempt(attempt)));
                 }
                 Err(err) => {
                     if let Some(session) = capture.session.as_ref() {
@@ -433,15 +604,28 @@ pub(super) async fn execute_nonstream_typed(
                             ))
                             .await;
                     }
-                    let non_retryable = is_non_retryable_client_error(&err);
-                    let retryable = is_retryable_error(&err);
+                    let key_rejected = suspend_rejected_channel_key(state, attempt, &err).await;
+                    let retry_next_key = key_rejected && key_retries < max_key_retries;
+                    let non_retryable = is_non_retryable_client_error(&err) && !retry_next_key;
+                    let decode_failure = is_malformed_success_response(&err);
+                    let retryable = is_retryable_error(&err)
+                        || (decode_failure
+                            && execution_state.record_decode_failure(decode_retry_limit));
                     let retryable_failure_class = classify_retryable_failure(&err);
-                    let app_err = upstream_error_to_app(err);
+                    let app_err = if decode_failure {
+                        AppError::new(
+                            StatusCode::BAD_GATEWAY,
+                            "invalid_upstream_response",
+                            err.message,
+                        )
+                    } else {
+                        upstream_error_to_app(err)
+                    };
                     if non_retryable {
                         spawn_request_log_error(
                             state,
                             auth,
-                            &attempt,
+                            attempt,
                             &logical_model,
                             false,
                             started_at,
@@ -450,28 +634,44 @@ pub(super) async fn execute_nonstream_typed(
                             &app_err,
                             req.reasoning.as_ref().and_then(|r| r.effort.clone()),
                             tried_providers,
+                            request_kind,
                         );
                         if let Some(session) = capture.session.as_ref() {
                             session.persist_with_result(None, true).await;
                         }
                         return Err(app_err);
                     }
+                    if retry_next_key {
+                        key_retries += 1;
+                        tried_providers.push(TriedProvider::from_app_error(
+                            attempt_number,
+                            attempt,
+                            &app_err,
+                        ));
+                        last_failed_attempt = Some(attempt.clone());
+                        continue;
+                    }
                     if retryable {
-                        clear_channel_affinity(state, &attempt).await;
+                        clear_channel_affinity(state, attempt).await;
                         tried_providers.push(TriedProvider::from_app_error(
                             attempt_number,
-                            &attempt,
+                            attempt,
                             &app_err,
                         ));
-                        mark_channel_retryable_failure(state, &attempt, retryable_failure_class)
+                        mark_channel_retryable_failure(state, attempt, retryable_failure_class)
                             .await;
                         last_failed_attempt = Some(attempt.clone());
-                        if !is_attempt_channel_healthy(state, &attempt).await {
+                        last_decode_error = decode_failure.then(|| app_err.clone());
+                        if !is_attempt_channel_healthy(state, attempt).await {
                             break;
                         }
-                        if execution_state.provider_budget_remaining(&attempt) {
+                        if execution_state.provider_budget_remaining(attempt) {
                             if channel_attempt + 1 < max_channel_attempts {
-                                maybe_sleep_before_channel_retry(&attempt).await;
+                                maybe_sleep_before_channel_retry(
+                                    attempt,
+                                    retryable_failure_class.retry_after(),
+                                )
+                                .await;
                             }
                             continue;
                         }
@@ -480,7 +680,7 @@ pub(super) async fn execute_nonstream_typed(
                     spawn_request_log_error(
                         state,
                         auth,
-                        &attempt,
+                        attempt,
                         &logical_model,
                         false,
                         started_at,
@@ -489,6 +689,7 @@ pub(super) async fn execute_nonstream_typed(
                         &app_err,
                         req.reasoning.as_ref().and_then(|r| r.effort.clone()),
                         tried_providers,
+                        request_kind,
                     );
                     if let Some(session) = capture.session.as_ref() {
                         session.persist_with_result(None, true).await;
@@ -498,7 +699,12 @@ pub(super) async fn execute_nonstream_typed(
             }
         }
     }
-    let final_err = build_exhausted_upstream_error(&logical_model, &tried_providers);
+    // When the last failure was an undecodable body, or every attempt was
+    // too large to send, surface that error rather than the generic
+    // exhaustion summary.
+    let final_err = last_decode_error
+        .or_else(|| execution_state.exhaustive_size_rejection(tried_providers.len()))
+        .unwrap_or_else(|| build_exhausted_upstream_error(&logical_model, &tried_providers));
     if let Some(attempt) = last_failed_attempt {
         spawn_request_log_error(
             state,
@@ -512,6 +718,7 @@ pub(super) async fn execute_nonstream_typed(
             &final_err,
             req.reasoning.as_ref().and_then(|r| r.effort.clone()),
             tried_providers,
+            request_kind,
         );
     } else {
         spawn_request_log_error_no_attempt(
@@ -525,6 +732,7 @@ pub(super) async fn execute_nonstream_typed(
             &final_err,
             req.reasoning.as_ref().and_then(|r| r.effort.clone()),
             tried_providers,
+            request_kind,
         );
     }
     if let Some(session) = capture.session.as_ref() {
@@ -533,6 +741,351 @@ pub(super) async fn execute_nonstream_typed(
     Err(final_err)
 }
 
+/// Upstream call made for one attempt, with the request it was encoded from.
+struct SentAttempt<'a> {
+    /// The attempt as sent, carrying the pool key that was used.
+    attempt: Cow<'a, MonoizeAttempt>,
+    req: urp::UrpRequest,
+    body: Value,
+    path: String,
+    result: Result<(Option<Value>, Option<urp::UrpResponse>), UpstreamCallError>,
+}
+
+fn sent_attempt_succeeded(sent: &AppResult<Result<SentAttempt<'_>, AppError>>) -> bool {
+    matches!(sent, Ok(Ok(sent)) if sent.result.is_ok())
+}
+
+/// Derives the upstream request for `attempt` and sends it. A streamed
+/// collection or encoding failure is returned as the outer error and ends the
+/// request. A body over the provider's size limit is not sent and is returned
+/// as the inner error so the caller moves on to the next attempt. An upstream
+/// call failure is returned in `SentAttempt::result` so the caller can decide
+/// whether to retry.
+#[allow(clippy::too_many_arguments)]
+async fn send_nonstream_attempt<'a>(
+    state: &AppState,
+    auth: &crate::auth::AuthResult,
+    original_req: &urp::UrpRequest,
+    routed: &'a MonoizeAttempt,
+    global_transforms: &[TransformRuleConfig],
+    transform_match_model: &str,
+    downstream: DownstreamProtocol,
+    max_multiplier: Option<f64>,
+    started_at: std::time::Instant,
+    logical_model: &str,
+) -> AppResult<Result<SentAttempt<'a>, AppError>> {
+    let attempt = match next_channel_key(state, routed).await {
+        Some(api_key) => Cow::Owned(MonoizeAttempt {
+            api_key,
+            ..routed.clone()
+        }),
+        None => Cow::Borrowed(routed),
+    };
+    // Clone from the pristine original request (pre-transforms) so
+    // that the cross-family strip can run BEFORE provider, global,
+    // and API-key transforms. This guarantees that transforms which
+    // inject upstream-specific part-level metadata (e.g.
+    // `auto_cache_system`, `auto_cache_tool_use`) survive into the
+    // encoded upstream request even when the downstream and upstream
+    // protocol families differ.
+    let mut req_attempt = original_req.clone();
+    if let Some(target_protocol) = provider_type_protocol(attempt.provider_type) {
+        urp::retain_provider_items_for_protocol(&mut req_attempt.input, target_protocol);
+        if target_protocol == urp::ProviderProtocol::Responses {
+            urp::remove_downstream_only_reasoning_for_responses(&mut req_attempt.input);
+        }
+    }
+    if attempt.strip_cross_protocol_nested_extra
+        && !downstream.is_same_family(attempt.provider_type)
+    {
+        urp::strip_nested_extra_body(&mut req_attempt.input);
+    }
+    inject_monoize_context(auth, &mut req_attempt);
+    req_attempt.model = attempt.upstream_model.clone();
+    apply_provider_sampling_defaults(&mut req_attempt, &attempt);
+    // Unwrap mz2 reasoning envelopes BEFORE any request-phase transform
+    // observes the request input. Per spec/urp-transform-system.spec.md
+    // PIPE-1 step 6 and PIPE-1d, transforms must not see encrypted
+    // reasoning replays still in `mz2.` envelope form, and they must not
+    // be allowed to mutate the reasoning payload before envelope-bound
+    // provider/model checks (PR4c.6) decide whether to keep or drop the
+    // replayed reasoning node for this attempt.
+    urp::filter_and_unwrap_reasoning_envelopes_for_upstream(
+        &mut req_attempt.input,
+        reasoning_envelope_provider_type(attempt.provider_type),
+        &req_attempt.model,
+        auth.reasoning_envelope_enabled,
+    );
+    apply_transform_rules_request(
+        state,
+        &mut req_attempt,
+        &attempt.provider_transforms,
+        transform_match_model,
+        &attempt,
+    )
+    .await?;
+    apply_transform_rules_request(
+        state,
+        &mut req_attempt,
+        global_transforms,
+        transform_match_model,
+        &attempt,
+    )
+    .await?;
+    apply_transform_rules_request(
+        state,
+        &mut req_attempt,
+        &auth.transforms,
+        transform_match_model,
+        &attempt,
+    )
+    .await?;
+    strip_monoize_context(&mut req_attempt);
+
+    let EncodedRequest {
+        value: upstream_body,
+        bytes: upstream_bytes,
+    } = match encode_request_for_provider(state, &mut req_attempt, &attempt, downstream).await? {
+        Ok(encoded) => encoded,
+        Err(EncodeFailure::Upstream(err)) => {
+            let path = upstream_path_for_model(
+                attempt.provider_type,
+                &req_attempt.model,
+                req_attempt.stream.unwrap_or(false),
+            );
+            return Ok(Ok(SentAttempt {
+                attempt,
+                req: req_attempt,
+                body: Value::Null,
+                path,
+                result: Err(err),
+            }));
+        }
+        Err(EncodeFailure::Oversized(err)) => return Ok(Err(err)),
+    };
+    if attempt.debug_log_bodies {
+        tracing::debug!(
+            provider_id = %attempt.provider_id,
+            channel_id = %attempt.channel_id,
+            body = %redact_sensitive(&upstream_body),
+            "upstream request body"
+        );
+    }
+    let provider = build_channel_provider_config(&attempt);
+    let openai_image_edit = attempt.provider_type == ProviderType::OpenaiImage
+        && urp::encode::openai_image::has_user_image_input(&req_attempt);
+    let path = if openai_image_edit {
+        "/v1/images/edits".to_string()
+    } else {
+        upstream_path_for_model(
+            attempt.provider_type,
+            &req_attempt.model,
+            req_attempt.stream.unwrap_or(false),
+        )
+    };
+    let result = if req_attempt.stream == Some(true)
+        && supports_nonstream_upstream_stream_collection(attempt.provider_type)
+    {
+        let stream_idle_timeout_ms = attempt.stream_idle_timeout_ms;
+        let call = upstream::call_upstream_stream_json_bytes_with_headers(
+            client_http(state),
+            &provider,
+            &attempt.api_key,
+            &path,
+            upstream_bytes,
+            attempt.request_timeout_ms,
+            provider_extra_headers(attempt.provider_type, &upstream_body),
+        )
+        .await;
+        let call = call.map(|upstream_resp| {
+            if attempt.debug_log_bodies {
+                log_upstream_sse_lines(
+                    upstream_resp,
+                    attempt.provider_id.clone(),
+                    DEBUG_LOG_SSE_LINES,
+                )
+            } else {
+                upstream_resp
+            }
+        });
+        match call {
+            Ok(upstream_resp) => match collect_streamed_upstream_response(
+                &req_attempt,
+                max_multiplier,
+                attempt.provider_type,
+                upstream_resp,
+                started_at,
+                logical_model,
+                stream_idle_timeout_ms,
+            )
+            .await
+            {
+                Ok(resp) => Ok((None, Some(resp))),
+                Err(err) => return Err(err),
+            },
+            Err(err) => Err(err),
+        }
+    } else if openai_image_edit {
+        let form = urp::encode::openai_image::multipart_form(&req_attempt, &req_attempt.model)
+            .map_err(|e| AppError::new(StatusCode::BAD_REQUEST, "invalid_request", e))?;
+        match upstream::call_upstream_multipart_with_timeout_and_headers(
+            client_http_nonstream(state),
+            &provider,
+            &attempt.api_key,
+            &path,
+            form,
+            attempt.request_timeout_ms,
+            provider_extra_headers(attempt.provider_type, &upstream_body),
+        )
+        .await
+        {
+            Ok(resp) => {
+                let status = resp.status();
+                match resp.text().await {
+                    Ok(text) => serde_json::from_str::<Value>(&text)
+                        .map(|value| (Some(value), None))
+                        .map_err(|err| {
+                            upstream::UpstreamCallError::new(
+                                upstream::UpstreamErrorKind::Http,
+                                Some(status),
+                                err.to_string(),
+                            )
+                        }),
+                    Err(err) => Err(upstream::UpstreamCallError::new(
+                        upstream::UpstreamErrorKind::Network,
+                        Some(status),
+                        err.to_string(),
+                    )),
+                }
+            }
+            Err(err) => Err(err),
+        }
+    } else {
+        upstream::call_upstream_json_bytes_with_timeout_and_headers(
+            client_http_nonstream(state),
+            &provider,
+            &attempt.api_key,
+            &path,
+            upstream_bytes,
+            attempt.request_timeout_ms,
+            provider_extra_headers(attempt.provider_type, &upstream_body),
+        )
+        .await
+        .map(|value| (Some(value), None))
+    };
+    Ok(Ok(SentAttempt {
+        attempt,
+        req: req_attempt,
+        body: upstream_body,
+        path,
+        result,
+    }))
+}
+
+/// Records the failed side of a hedged race in the request's tried-provider
+/// list and passive health state. The losing call is not retried.
+async fn record_lost_attempt(
+    state: &AppState,
+    execution_state: &mut AttemptExecutionState,
+    tried_providers: &mut Vec<TriedProvider>,
+    attempt_number: u32,
+    attempt: &MonoizeAttempt,
+    failure: AppResult<Result<SentAttempt<'_>, AppError>>,
+) {
+    let app_err = match failure {
+        Ok(Ok(SentAttempt {
+            attempt: sent,
+            result: Err(err),
+            ..
+        })) => {
+            suspend_rejected_channel_key(state, &sent, &err).await;
+            if is_retryable_error(&err) {
+                mark_channel_retryable_failure(state, attempt, classify_retryable_failure(&err))
+                    .await;
+            }
+            upstream_error_to_app(err)
+        }
+        Ok(Ok(_)) => return,
+        Ok(Err(err)) => {
+            execution_state.record_size_rejection(&err);
+            err
+        }
+        Err(err) => err,
+    };
+    tried_providers.push(TriedProvider::from_app_error(
+        attempt_number,
+        attempt,
+        &app_err,
+    ));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum HedgeWinner {
+    Primary,
+    Hedge,
+}
+
+pub(super) struct HedgedOutcome<T> {
+    pub(super) winner: HedgeWinner,
+    pub(super) result: T,
+    /// Result of the other side when it completed, i.e. the hedge's failure
+    /// when the primary is reported, or the primary's failure when the
+    /// hedge won. A side that was still running when the race ended is
+    /// dropped, which cancels its upstream call.
+    pub(super) loser: Option<T>,
+}
+
+/// Runs `primary` alone for `hedge_after`, then races it against `hedge`.
+/// The first result satisfying `is_success` wins; when neither succeeds the
+/// primary's result is reported. `hedge` resolving to `None` means it could
+/// not start (e.g. its channel is saturated) and leaves the primary alone.
+pub(super) async fn race_hedged<T, P, H>(
+    primary: P,
+    hedge_after: Duration,
+    hedge: H,
+    is_success: impl Fn(&T) -> bool,
+) -> HedgedOutcome<T>
+where
+    P: std::future::Future<Output = T>,
+    H: std::future::Future<Output = Option<T>>,
+{
+    tokio::pin!(primary);
+    tokio::select! {
+        result = &mut primary => {
+            return HedgedOutcome { winner: HedgeWinner::Primary, result, loser: None };
+        }
+        _ = tokio::time::sleep(hedge_after) => {}
+    }
+    tokio::pin!(hedge);
+    tokio::select! {
+        result = &mut primary => {
+            if is_success(&result) {
+                return HedgedOutcome { winner: HedgeWinner::Primary, result, loser: None };
+            }
+            match hedge.await {
+                Some(hedged) if is_success(&hedged) => HedgedOutcome {
+                    winner: HedgeWinner::Hedge,
+                    result: hedged,
+                    loser: Some(result),
+                },
+                hedged => HedgedOutcome { winner: HedgeWinner::Primary, result, loser: hedged },
+            }
+        }
+        hedged = &mut hedge => match hedged {
+            Some(hedged) if is_success(&hedged) => HedgedOutcome {
+                winner: HedgeWinner::Hedge,
+                result: hedged,
+                loser: None,
+            },
+            hedged => HedgedOutcome {
+                winner: HedgeWinner::Primary,
+                result: primary.await,
+                loser: hedged,
+            },
+        }
+    }
+}
+
 fn supports_nonstream_upstream_stream_collection(provider_type: ProviderType) -> bool {
     matches!(
         provider_type,
@@ -650,8 +1203,8 @@ pub(super) async fn forward_nonstream_typed(
     request_id: Option<String>,
     request_ip: Option<String>,
     capture: RequestCaptureContext,
-) -> AppResult<Value> {
-    let (resp, logical_model) = execute_nonstream_typed(
+) -> AppResult<(Value, ServedRoute)> {
+    let (resp, logical_model, route) = execute_nonstream_routed(
         state,
         auth,
         req,
@@ -660,27 +1213,94 @@ pub(super) async fn forward_nonstream_typed(
         request_id,
         request_ip,
         capture,
+        None,
     )
     .await?;
-    Ok(encode_response_for_downstream(
-        downstream,
-        &resp,
-        &logical_model,
+    Ok((
+        encode_response_for_downstream(downstream, &resp, &logical_model),
+        route,
     ))
 }
 
+/// Drops attempts whose provider cannot resolve the request's
+/// `previous_response_id`. Only a Responses upstream holds the chained
+/// conversation, so the request fails only when no Responses attempt remains.
 #[allow(clippy::result_large_err)]
-pub(super) fn encode_request_for_provider(
+pub(super) fn retain_conversation_chaining_attempts(
+    attempts: &mut Vec<MonoizeAttempt>,
+    req: &urp::UrpRequest,
+) -> AppResult<()> {
+    if req.previous_response_id.is_none() {
+        return Ok(());
+    }
+    let Some(unsupported) = attempts
+        .iter()
+        .find(|attempt| attempt.provider_type != ProviderType::Responses)
+        .map(|attempt| attempt.provider_type)
+    else {
+        return Ok(());
+    };
+    attempts.retain(|attempt| attempt.provider_type == ProviderType::Responses);
+    if attempts.is_empty() {
+        return Err(AppError::new(
+            StatusCode::BAD_REQUEST,
+            "conversation_chaining_not_supported",
+            format!(
+                "conversation chaining not supported for provider {}",
+                reasoning_envelope_provider_type(unsupported)
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Upstream request body for one attempt, with the serialization that is sent.
+pub(super) struct EncodedRequest {
+    pub(super) value: Value,
+    pub(super) bytes: bytes::Bytes,
+}
+
+/// Why an encoded attempt is not sent.
+pub(super) enum EncodeFailure {
+    /// A call made while encoding (the Gemini Files API upload) failed. It is
+    /// handled like a failed upstream call of the attempt.
+    Upstream(UpstreamCallError),
+    /// The body exceeds the provider's size limit. The attempt is skipped and
+    /// the next one is tried.
+    Oversized(AppError),
+}
+
+/// Encodes `req` for the attempt's provider. Failures that end the whole
+/// request are returned as the outer error; failures of this attempt only are
+/// returned as the inner error.
+pub(super) async fn encode_request_for_provider(
+    state: &AppState,
     req: &mut urp::UrpRequest,
     attempt: &MonoizeAttempt,
     downstream: DownstreamProtocol,
-) -> AppResult<Value> {
+) -> AppResult<Result<EncodedRequest, EncodeFailure>> {
     if matches!(downstream, DownstreamProtocol::Responses)
         && attempt.provider_type != ProviderType::Responses
     {
         req.extra_body.remove("store");
         req.extra_body.remove("conversation");
-        req.extra_body.remove("previous_response_id");
+    }
+    if matches!(
+        attempt.provider_type,
+        ProviderType::Messages | ProviderType::OpenaiImage
+    ) && req
+        .input
+        .iter()
+        .any(|node| matches!(node, urp::Node::Video { .. }))
+    {
+        return Err(AppError::new(
+            StatusCode::BAD_REQUEST,
+            "video_input_not_supported",
+            format!(
+                "video input not supported for provider {}",
+                reasoning_envelope_provider_type(attempt.provider_type)
+            ),
+        ));
     }
     filter_extra_body_for_provider(req, attempt.provider_type, &attempt.extra_fields_whitelist);
     filter_tools_for_provider(req, attempt.provider_type, downstream);
@@ -698,7 +1318,22 @@ pub(super) fn encode_request_for_provider(
             .map_err(|message| {
                 AppError::new(StatusCode::BAD_REQUEST, "invalid_request", message)
             })?,
-        ProviderType::Gemini => urp::encode::gemini::encode_request(req, &model),
+        ProviderType::Gemini => {
+            let file_uris = match upload_large_gemini_files(
+                client_http_nonstream(state),
+                &build_channel_provider_config(attempt),
+                &attempt.api_key,
+                req,
+                urp::encode::gemini::INLINE_FILE_MAX_BYTES,
+                attempt.request_timeout_ms,
+            )
+            .await?
+            {
+                Ok(file_uris) => file_uris,
+                Err(err) => return Ok(Err(EncodeFailure::Upstream(err))),
+            };
+            urp::encode::gemini::encode_request_with_file_uris(req, &model, &file_uris)
+        }
         ProviderType::OpenaiImage => urp::encode::openai_image::encode_request(req, &model),
         ProviderType::Replicate => urp::encode::replicate::encode_request(req, &model),
         ProviderType::Group => {
@@ -709,7 +1344,97 @@ pub(super) fn encode_request_for_provider(
             ));
         }
     };
-    Ok(value)
+    let bytes = serde_json::to_vec(&value).map_err(|err| {
+        AppError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal_error",
+            format!("failed to serialize upstream request: {err}"),
+        )
+    })?;
+    if let Err(err) = check_provider_request_body_size(attempt.provider_type, bytes.len()) {
+        return Ok(Err(EncodeFailure::Oversized(err)));
+    }
+    Ok(Ok(EncodedRequest {
+        value,
+        bytes: bytes.into(),
+    }))
+}
+
+/// Documented upstream request size ceilings. Bodies above these fail
+/// upstream with opaque errors, so they are not sent.
+fn provider_max_request_body_bytes(provider_type: ProviderType) -> Option<usize> {
+    match provider_type {
+        ProviderType::Messages => Some(32 * 1024 * 1024),
+        ProviderType::Gemini => Some(20 * 1024 * 1024),
+        ProviderType::Responses
+        | ProviderType::ChatCompletion
+        | ProviderType::OpenaiImage
+        | ProviderType::Replicate
+        | ProviderType::Group => None,
+    }
+}
+
+#[allow(clippy::result_large_err)]
+pub(super) fn check_provider_request_body_size(
+    provider_type: ProviderType,
+    size: usize,
+) -> AppResult<()> {
+    let Some(limit) = provider_max_request_body_bytes(provider_type) else {
+        return Ok(());
+    };
+    if size > limit {
+        return Err(AppError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "payload_too_large",
+            format!(
+                "encoded request body is {size} bytes, exceeding the {limit}-byte limit for provider {}",
+                reasoning_envelope_provider_type(provider_type)
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Uploads each distinct base64 file larger than `max_inline_bytes` through
+/// the Gemini Files API and returns the file URIs keyed by content hash. Undecodable
+/// file data ends the request; a failed upload is returned as the inner error.
+pub(super) async fn upload_large_gemini_files(
+    client: &reqwest::Client,
+    provider: &ProviderConfig,
+    api_key: &str,
+    req: &urp::UrpRequest,
+    max_inline_bytes: usize,
+    timeout_ms: u64,
+) -> AppResult<Result<std::collections::HashMap<String, String>, UpstreamCallError>> {
+    use base64::Engine as _;
+
+    let mut file_uris = std::collections::HashMap::new();
+    for file in urp::encode::gemini::large_inline_files(req, max_inline_bytes) {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(file.data)
+            .map_err(|err| {
+                AppError::new(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_request",
+                    format!("invalid base64 file data: {err}"),
+                )
+            })?;
+        let uri = match upstream::upload_gemini_file(
+            client,
+            provider,
+            api_key,
+            file.media_type,
+            bytes,
+            timeout_ms,
+        )
+        .await
+        {
+            Ok(uri) => uri,
+            Err(err) => return Ok(Err(err)),
+        };
+        file_uris.insert(file.content_hash, uri);
+    }
+    Ok(Ok(file_uris))
 }
 
 #[allow(clippy::result_large_err)]
@@ -18,6 +18,50 @@ fn request_created_at(started_at: std::time::Instant) -> chrono::DateTime<Utc> {
     Utc::now() - elapsed
 }
 
+/// Distinct `model` label values on the latency histograms; later models are
+/// reported as `other` so arbitrary client model names cannot explode series.
+const MAX_LATENCY_MODEL_LABELS: usize = 256;
+
+fn latency_model_label(model: &str) -> String {
+    static SEEN: std::sync::OnceLock<std::sync::Mutex<HashSet<String>>> =
+        std::sync::OnceLock::new();
+    let mut seen = SEEN
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if seen.contains(model) {
+        return model.to_string();
+    }
+    if seen.len() < MAX_LATENCY_MODEL_LABELS {
+        seen.insert(model.to_string());
+        return model.to_string();
+    }
+    "other".to_string()
+}
+
+pub(super) fn record_request_latency_metrics(
+    model: &str,
+    provider_id: &str,
+    duration_ms: u64,
+    ttfb_ms: Option<u64>,
+) {
+    let model = latency_model_label(model);
+    metrics::histogram!(
+        crate::app::REQUEST_DURATION_METRIC,
+        "model" => model.clone(),
+        "provider_id" => provider_id.to_string()
+    )
+    .record(duration_ms as f64);
+    if let Some(ttfb_ms) = ttfb_ms {
+        metrics::histogram!(
+            crate::app::REQUEST_TTFB_METRIC,
+            "model" => model,
+            "provider_id" => provider_id.to_string()
+        )
+        .record(ttfb_ms as f64);
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn broadcast_pending_snapshot(
     state: &AppState,
@@ -75,6 +119,7 @@ fn broadcast_pending_snapshot(
         request_ip: request_ip.map(ToOwned::to_owned),
         reasoning_effort: None,
         tried_providers_json: None,
+        metadata_json: auth.request_metadata.clone(),
         request_kind: None,
         effective_provider_type: effective_provider_type.map(ToOwned::to_owned),
         affinity_hit,
@@ -184,11 +229,13 @@ pub(super) fn spawn_request_log(
     stream_terminal_diagnostics: Option<StreamTerminalDiagnostics>,
     reasoning_effort: Option<String>,
     tried_providers: Vec<TriedProvider>,
+    request_kind: Option<&'static str>,
 ) {
     let Some(user_id) = auth.user_id.clone() else {
         return;
     };
     let api_key_id = auth.api_key_id.clone();
+    let metadata_json = auth.request_metadata.clone();
     let provider_id = attempt.provider_id.clone();
     let upstream_model = attempt.upstream_model.clone();
     let model_multiplier = attempt.model_multiplier;
@@ -199,6 +246,7 @@ pub(super) fn spawn_request_log(
     let affinity_target = attempt.affinity_target.clone();
     let model = model.to_string();
     let duration_ms = started_at.elapsed().as_millis() as u64;
+    record_request_latency_metrics(&model, &provider_id, duration_ms, ttfb_ms);
     let created_at = request_created_at(started_at);
     let user_store = state.user_store.clone();
     let usage_breakdown_json = usage.as_ref().map(build_usage_breakdown);
@@ -304,7 +352,8 @@ pub(super) fn spawn_request_log(
             request_ip,
             reasoning_effort,
             tried_providers_json,
-            request_kind: None,
+            metadata_json,
+            request_kind: request_kind.map(str::to_string),
             effective_provider_type: Some(effective_provider_type),
             affinity_hit,
             affinity_key_hash,
@@ -330,11 +379,13 @@ pub(super) fn spawn_request_log_error(
     error: &AppError,
     reasoning_effort: Option<String>,
     tried_providers: Vec<TriedProvider>,
+    request_kind: Option<&'static str>,
 ) {
     let Some(user_id) = auth.user_id.clone() else {
         return;
     };
     let api_key_id = auth.api_key_id.clone();
+    let metadata_json = auth.request_metadata.clone();
     let model = model.to_string();
     let provider_id = attempt.provider_id.clone();
     let upstream_model = attempt.upstream_model.clone();
@@ -398,7 +449,8 @@ pub(super) fn spawn_request_log_error(
             request_ip,
             reasoning_effort,
             tried_providers_json,
-            request_kind: None,
+            metadata_json,
+            request_kind: request_kind.map(str::to_string),
             effective_provider_type: Some(effective_provider_type),
             affinity_hit,
             affinity_key_hash,
@@ -422,6 +474,7 @@ pub(super) fn spawn_request_log_stream_terminal_error(
     request_ip: Option<String>,
     ttfb_ms: Option<u64>,
     terminal_error: StreamTerminalError,
+    usage: Option<urp::Usage>,
     reasoning_effort: Option<String>,
     tried_providers: Vec<TriedProvider>,
 ) {
@@ -429,6 +482,7 @@ pub(super) fn spawn_request_log_stream_terminal_error(
         return;
     };
     let api_key_id = auth.api_key_id.clone();
+    let metadata_json = auth.request_metadata.clone();
     let model = model.to_string();
     let provider_id = attempt.provider_id.clone();
     let upstream_model = attempt.upstream_model.clone();
@@ -442,6 +496,7 @@ pub(super) fn spawn_request_log_stream_terminal_error(
     let duration_ms = started_at.elapsed().as_millis() as u64;
     let created_at = request_created_at(started_at);
     let user_store = state.user_store.clone();
+    let usage_breakdown_json = usage.as_ref().map(build_usage_breakdown);
     let tried_providers_json = if tried_providers.is_empty() {
         None
     } else {
@@ -458,18 +513,24 @@ pub(super) fn spawn_request_log_stream_terminal_error(
             upstream_model: Some(upstream_model),
             channel_id: Some(channel_id),
             is_stream: true,
-            input_tokens: None,
-            output_tokens: None,
-            cache_read_tokens: None,
-            cache_creation_tokens: None,
-            tool_prompt_tokens: None,
-            reasoning_tokens: None,
+            input_tokens: usage.as_ref().map(|u| u.input_tokens),
+            output_tokens: usage.as_ref().map(|u| u.output_tokens),
+            cache_read_tokens: usage.as_ref().and_then(|u| u.cached_tokens()),
+            cache_creation_tokens: usage
+                .as_ref()
+                .and_then(|u| u.input_details.as_ref().map(|d| d.cache_creation_tokens))
+                .filter(|&v| v > 0),
+            tool_prompt_tokens: usage
+                .as_ref()
+                .and_then(|u| u.input_details.as_ref().map(|d| d.tool_prompt_tokens))
+                .filter(|&v| v > 0),
+            reasoning_tokens: usage.as_ref().and_then(|u| u.reasoning_tokens()),
             accepted_prediction_tokens: None,
             rejected_prediction_tokens: None,
             provider_multiplier: Some(model_multiplier),
             charge_nano_usd: None,
             status: REQUEST_LOG_STATUS_ERROR.to_string(),
-            usage_breakdown_json: None,
+            usage_breakdown_json,
             billing_breakdown_json: None,
             error_code: Some(terminal_error.code),
             error_message: Some(terminal_error.message),
@@ -484,6 +545,7 @@ pub(super) fn spawn_request_log_stream_terminal_error(
             request_ip,
             reasoning_effort,
             tried_providers_json,
+            metadata_json,
             request_kind: None,
             effective_provider_type: Some(effective_provider_type),
             affinity_hit,
@@ -509,11 +571,13 @@ pub(super) fn spawn_request_log_error_no_attempt(
     error: &AppError,
     reasoning_effort: Option<String>,
     tried_providers: Vec<TriedProvider>,
+    request_kind: Option<&'static str>,
 ) {
     let Some(user_id) = auth.user_id.clone() else {
         return;
     };
     let api_key_id = auth.api_key_id.clone();
+    let metadata_json = auth.request_metadata.clone();
     let model = model.to_string();
     let duration_ms = started_at.elapsed().as_millis() as u64;
     let created_at = request_created_at(started_at);
@@ -568,7 +632,8 @@ pub(super) fn spawn_request_log_error_no_attempt(
             request_ip,
             reasoning_effort,
             tried_providers_json,
-            request_kind: None,
+            metadata_json,
+            request_kind: request_kind.map(str::to_string),
             effective_provider_type: None,
             affinity_hit: None,
             affinity_key_hash: None,
@@ -5,8 +5,6 @@ use axum::response::IntoResponse;
 use futures_util::StreamExt;
 use serde_json::{Map, Value, json};
 
-const MAX_WEBSOCKET_MESSAGE_BYTES: usize = 50 * 1024 * 1024;
-
 #[derive(Default)]
 struct ResponsesWebsocketSession {
     last_request: Option<Map<String, Value>>,
@@ -54,9 +52,10 @@ pub async fn responses_websocket(
     ws: WebSocketUpgrade,
 ) -> AppResult<Response> {
     auth_tenant(&headers, &state).await?;
+    let max_message_bytes = state.runtime.max_request_body_bytes;
     Ok(ws
-        .max_message_size(MAX_WEBSOCKET_MESSAGE_BYTES)
-        .max_frame_size(MAX_WEBSOCKET_MESSAGE_BYTES)
+        .max_message_size(max_message_bytes)
+        .max_frame_size(max_message_bytes)
         .on_upgrade(move |socket| serve_responses_websocket(socket, state, headers))
         .into_response())
 }
@@ -1,5 +1,5 @@
 use super::*;
-use crate::settings::BUILTIN_REASONING_EFFORT_SUFFIXES;
+use crate::settings::{BUILTIN_REASONING_EFFORT_SUFFIXES, MissingPricingPolicy};
 
 pub(crate) fn now_ts() -> i64 {
     chrono::Utc::now().timestamp()
@@ -9,6 +9,11 @@ pub(super) fn client_http(state: &AppState) -> &reqwest::Client {
     &state.http
 }
 
+/// Client for calls bounded as a whole by the attempt's request timeout.
+pub(super) fn client_http_nonstream(state: &AppState) -> &reqwest::Client {
+    &state.http_nonstream
+}
+
 pub(crate) fn health_key(channel_id: &str, model: Option<&str>) -> String {
     match model {
         Some(m) => format!("{channel_id}::{m}"),
@@ -56,6 +61,45 @@ pub(super) fn upstream_path_for_model(
     }
 }
 
+const MAX_MODEL_ALIAS_DEPTH: usize = 8;
+
+/// Rewrites `req.model` through the settings alias map so routing, suffix
+/// handling, and transform matching all see the concrete model.
+pub(super) async fn resolve_model_alias(state: &AppState, req: &mut urp::UrpRequest) {
+    let resolved = {
+        let runtime = state.monoize_runtime.read().await;
+        resolve_alias_chain(&runtime.model_aliases, &req.model)
+    };
+    if let Some(model) = resolved {
+        req.model = model;
+    }
+}
+
+/// Follows aliases up to `MAX_MODEL_ALIAS_DEPTH` hops. Returns `None` when
+/// `model` is not an alias, or when the chain is cyclic or too deep, in which
+/// case the requested name is left as is.
+pub(super) fn resolve_alias_chain(
+    aliases: &std::collections::HashMap<String, String>,
+    model: &str,
+) -> Option<String> {
+    let mut current = model;
+    for _ in 0..MAX_MODEL_ALIAS_DEPTH {
+        match aliases.get(current) {
+            Some(next) => current = next,
+            None => return (current != model).then(|| current.to_string()),
+        }
+    }
+    if aliases.contains_key(current) {
+        tracing::warn!(
+            "model alias {} does not resolve within {} hops, leaving it unresolved",
+            model,
+            MAX_MODEL_ALIAS_DEPTH
+        );
+        return None;
+    }
+    Some(current.to_string())
+}
+
 pub(super) async fn resolve_model_suffix(state: &AppState, req: &mut urp::UrpRequest) {
     let requested_model = req.model.clone();
     let normalized = normalized_logical_model_for_matching(state, &requested_model).await;
@@ -89,6 +133,7 @@ pub(super) async fn resolve_model_suffix(state: &AppState, req: &mut urp::UrpReq
                     None => {
                         req.reasoning = Some(urp::ReasoningConfig {
                             effort: Some(effort.to_string()),
+                            summary: None,
                             extra_body: std::collections::HashMap::new(),
                         });
                     }
@@ -157,11 +202,129 @@ pub(super) async fn build_monoize_attempts(
     build_monoize_attempts_for_provider_type(state, urp, auth, None).await
 }
 
+/// Builds attempts for `urp.model`, then appends attempts for each configured
+/// fallback model in order. Fallback attempts carry the fallback as their
+/// `logical_model`, so they are only reached after every primary attempt has
+/// failed and they are priced as the fallback model.
 pub(super) async fn build_monoize_attempts_for_provider_type(
     state: &AppState,
     urp: &UrpRequest,
     auth: &crate::auth::AuthResult,
     required_provider_type: Option<ProviderType>,
+) -> AppResult<Vec<MonoizeAttempt>> {
+    let primary = build_model_attempts(state, urp, auth, required_provider_type).await;
+    let fallbacks = state
+        .monoize_runtime
+        .read()
+        .await
+        .model_fallbacks
+        .get(&urp.model)
+        .cloned()
+        .unwrap_or_default();
+    if fallbacks.is_empty() {
+        return primary;
+    }
+
+    let mut fallback_attempts = Vec::new();
+    for fallback in fallbacks {
+        if fallback == urp.model
+            || ensure_model_allowed(auth, &fallback).is_err()
+            || ensure_model_passes_filters(auth, &fallback).is_err()
+        {
+            continue;
+        }
+        let mut fallback_urp = urp.clone();
+        fallback_urp.model = fallback;
+        match build_model_attempts(state, &fallback_urp, auth, required_provider_type).await {
+            Ok(attempts) => fallback_attempts.extend(attempts),
+            Err(err) => tracing::debug!(
+                "skipping fallback model {} for {}: {}",
+                fallback_urp.model,
+                urp.model,
+                err.message
+            ),
+        }
+    }
+    match primary {
+        Ok(mut attempts) => {
+            attempts.extend(fallback_attempts);
+            Ok(attempts)
+        }
+        Err(_) if !fallback_attempts.is_empty() => Ok(fallback_attempts),
+        Err(err) => Err(err),
+    }
+}
+
+const PROVIDER_ID_HEADER: &str = "x-monoize-provider-id";
+const CHANNEL_ID_HEADER: &str = "x-monoize-channel-id";
+const UPSTREAM_MODEL_HEADER: &str = "x-monoize-upstream-model";
+
+/// Provider, channel, and upstream model of the attempt that served a request.
+#[derive(Debug, Clone)]
+pub(super) struct ServedRoute {
+    provider_id: String,
+    channel_id: String,
+    upstream_model: String,
+}
+
+impl ServedRoute {
+    pub(super) fn from_attempt(attempt: &MonoizeAttempt) -> Self {
+        Self {
+            provider_id: attempt.provider_id.clone(),
+            channel_id: attempt.channel_id.clone(),
+            upstream_model: attempt.upstream_model.clone(),
+        }
+    }
+}
+
+/// Adds the `x-monoize-*` routing headers for `route` when the
+/// `expose_routing_headers` runtime flag is on.
+pub(super) async fn attach_routing_headers(
+    state: &AppState,
+    response: &mut Response,
+    route: Option<&ServedRoute>,
+) {
+    let Some(route) = route else {
+        return;
+    };
+    if !state.monoize_runtime.read().await.expose_routing_headers {
+        return;
+    }
+    let headers = response.headers_mut();
+    for (name, value) in [
+        (PROVIDER_ID_HEADER, &route.provider_id),
+        (CHANNEL_ID_HEADER, &route.channel_id),
+        (UPSTREAM_MODEL_HEADER, &route.upstream_model),
+    ] {
+        if let Ok(value) = axum::http::HeaderValue::from_str(value) {
+            headers.insert(axum::http::HeaderName::from_static(name), value);
+        }
+    }
+}
+
+/// Model name echoed to the client for a successful attempt.
+pub(super) async fn downstream_model_for_attempt(
+    state: &AppState,
+    requested_model: &str,
+    attempt: &MonoizeAttempt,
+) -> String {
+    if attempt.logical_model != requested_model
+        && state
+            .monoize_runtime
+            .read()
+            .await
+            .model_fallback_report_actual_model
+    {
+        return attempt.logical_model.clone();
+    }
+    requested_model.to_string()
+}
+
+async fn build_model_attempts(
+    state: &AppState,
+    urp: &UrpRequest,
+    auth: &crate::auth::AuthResult,
+    required_provider_type: Option<ProviderType>,
 ) -> AppResult<Vec<MonoizeAttempt>> {
     let providers =
         state.monoize_store.list_providers().await.map_err(|e| {
@@ -175,14 +338,21 @@ pub(super) async fn build_monoize_attempts_for_provider_type(
     if let Some(required_provider_type) = required_provider_type {
         attempts.retain(|attempt| attempt.provider_type == required_provider_type);
     }
+    if let Some(routing_override) = auth.routing_override.as_ref() {
+        apply_routing_override(routing_override, &mut attempts);
+    }
+    if let Some(request_timeout_ms) = auth.request_timeout_ms {
+        apply_request_timeout_override(request_timeout_ms, &mut attempts);
+    }
     if attempts.is_empty() {
         return Ok(attempts);
     }
 
-    let mut pricing_cache: std::collections::HashMap<
-        (String, String, String),
-        Result<bool, String>,
-    > = std::collections::HashMap::new();
+    let missing_pricing_policy = state.monoize_runtime.read().await.missing_pricing_policy;
+    // (complete rate matrix or meter error, matrix is free)
+    type AttemptPricing = (Result<bool, String>, bool);
+    let mut pricing_cache: std::collections::HashMap<(String, String, String), AttemptPricing> =
+        std::collections::HashMap::new();
     let mut blocked_models: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
     let mut blocked_meter_errors: std::collections::BTreeSet<String> =
         std::collections::BTreeSet::new();
@@ -198,7 +368,7 @@ pub(super) async fn build_monoize_attempts_for_provider_type(
                 reasoning_envelope_provider_type(attempt.provider_type)
             ),
         );
-        let has_pricing = if let Some(cached) = pricing_cache.get(&cache_key) {
+        let (has_pricing, free_pricing) = if let Some(cached) = pricing_cache.get(&cache_key) {
             cached.clone()
         } else {
             let priced = match resolve_billing_rate_matrix(
@@ -209,17 +379,23 @@ pub(super) async fn build_monoize_attempts_for_provider_type(
             )
             .await?
             {
-                Some(resolution) => {
-                    billing_rate_matrix_allows_request(&resolution, &urp.server_tool_usage_classes)
-                }
+                Some(resolution) => (
+                    billing_rate_matrix_allows_request(&resolution, &urp.server_tool_usage_classes),
+                    billing_rate_matrix_is_free(&resolution),
+                ),
                 None => {
-                    if urp.server_tool_usage_classes.is_empty() {
-                        Ok(false)
+                    if urp.server_tool_usage_classes.is_empty()
+                        || missing_pricing_policy != MissingPricingPolicy::Reject
+                    {
+                        (Ok(false), false)
                     } else {
-                        Err(format!(
-                            "meter rate required for server-native tool usage class: {}",
-                            urp.server_tool_usage_classes.join(", ")
-                        ))
+                        (
+                            Err(format!(
+                                "meter rate required for server-native tool usage class: {}",
+                                urp.server_tool_usage_classes.join(", ")
+                            )),
+                            false,
+                        )
                     }
                 }
             };
@@ -233,11 +409,13 @@ pub(super) async fn build_monoize_attempts_for_provider_type(
             }
             Ok(true) => {
                 attempt.billable_pricing_available = true;
+                attempt.free_pricing = free_pricing;
                 allowed_attempts.push(attempt);
             }
-            Ok(false) => {
+            Ok(false) if missing_pricing_policy == MissingPricingPolicy::Reject => {
                 blocked_models.insert(attempt.upstream_model);
             }
+            Ok(false) => allowed_attempts.push(attempt),
         }
     }
 
@@ -247,8 +425,8 @@ pub(super) async fn build_monoize_attempts_for_provider_type(
             .collect::<Vec<_>>()
             .join(", ");
         return Err(AppError::new(
-            StatusCode::FORBIDDEN,
-            "model_pricing_required",
+            StatusCode::PAYMENT_REQUIRED,
+            "pricing_unavailable",
             blocked_list,
         ));
     }
@@ -256,14 +434,42 @@ pub(super) async fn build_monoize_attempts_for_provider_type(
     if allowed_attempts.is_empty() && !blocked_models.is_empty() {
         let blocked_list = blocked_models.into_iter().collect::<Vec<_>>().join(", ");
         return Err(AppError::new(
-            StatusCode::FORBIDDEN,
-            "model_pricing_required",
+            StatusCode::PAYMENT_REQUIRED,
+            "pricing_unavailable",
             format!("pricing metadata required for model(s): {blocked_list}"),
         ));
     }
     apply_channel_affinity(state, urp, auth, allowed_attempts).await
 }
 
+/// Restricts attempts to the forced channel and rewrites their upstream model.
+/// Runs before pricing so the forced model is priced like a configured one.
+pub(super) fn apply_routing_override(
+    routing_override: &crate::auth::RoutingOverride,
+    attempts: &mut Vec<MonoizeAttempt>,
+) {
+    if let Some(channel_id) = routing_override.channel_id.as_deref() {
+        attempts.retain(|attempt| attempt.channel_id == channel_id);
+    }
+    if let Some(upstream_model) = routing_override.upstream_model.as_deref() {
+        for attempt in attempts.iter_mut() {
+            attempt.upstream_model = upstream_model.to_string();
+        }
+    }
+}
+
+/// Replaces each attempt's request timeout with the client's
+/// `x-monoize-timeout-ms` value; the stream idle timeout never exceeds it.
+pub(super) fn apply_request_timeout_override(
+    request_timeout_ms: u64,
+    attempts: &mut [MonoizeAttempt],
+) {
+    for attempt in attempts.iter_mut() {
+        attempt.request_timeout_ms = request_timeout_ms;
+        attempt.stream_idle_timeout_ms = attempt.stream_idle_timeout_ms.min(request_timeout_ms);
+    }
+}
+
 fn affinity_tenant(auth: &crate::auth::AuthResult) -> Option<String> {
     auth.api_key_id
         .as_ref()
@@ -431,7 +637,29 @@ pub(super) async fn collect_provider_attempts(
         return;
     }
 
-    let ordered = weighted_shuffle_channels(channels);
+    let (failure_weighted_routing, passive_window_seconds) = {
+        let runtime = state.monoize_runtime.read().await;
+        (
+            runtime.failure_weighted_routing,
+            runtime.passive_window_seconds,
+        )
+    };
+    let failure_counts = if failure_weighted_routing {
+        Some(
+            recent_channel_failure_counts(
+                state,
+                &channels,
+                provider
+                    .per_model_circuit_break
+                    .then_some(urp.model.as_str()),
+                passive_window_seconds,
+            )
+            .await,
+        )
+    } else {
+        None
+    };
+    let ordered = weighted_shuffle_channels(channels, failure_counts.as_ref());
     let provider_attempt_limit = if provider.max_retries == -1 {
         None
     } else {
@@ -468,31 +696,44 @@ pub(super) async fn collect_provider_attempts(
             .passive_rate_limit_cooldown_seconds_override
             .unwrap_or(runtime.passive_rate_limit_cooldown_seconds)
             .max(1);
+        let probe_success_threshold = channel
+            .active_probe_success_threshold_override
+            .or(provider.active_probe_success_threshold_override)
+            .unwrap_or(runtime.active_success_threshold)
+            .max(1);
         let request_timeout_ms = provider
             .request_timeout_ms_override
             .unwrap_or(runtime.request_timeout_ms)
             .max(1);
+        let stream_idle_timeout_ms = runtime.stream_idle_timeout_ms.max(1);
         out.push(MonoizeAttempt {
             provider_id: provider.id.clone(),
             provider_type: effective_provider_type.to_config_type(),
             channel_id: channel.id.clone(),
             base_url: channel.base_url.clone(),
             api_key: channel.api_key.clone(),
+            api_key_pool: channel.key_pool(),
             logical_model: urp.model.clone(),
             upstream_model,
             model_multiplier: model_entry.multiplier,
+            model_multiplier_nano: crate::users::multiplier_to_nano(model_entry.multiplier),
             server_tool_usage_classes: urp.server_tool_usage_classes.clone(),
             provider_transforms: provider.transforms.clone(),
             passive_failure_count_threshold,
             passive_cooldown_seconds,
             passive_window_seconds,
             passive_rate_limit_cooldown_seconds,
+            probe_success_threshold,
             channel_max_retries: provider.channel_max_retries,
             channel_retry_interval_ms: provider.channel_retry_interval_ms.max(0) as u64,
             circuit_breaker_enabled: provider.circuit_breaker_enabled,
             per_model_circuit_break: provider.per_model_circuit_break,
             provider_attempt_limit,
             request_timeout_ms,
+            stream_idle_timeout_ms,
+            max_concurrency: channel.max_concurrency,
+            org_id: channel.org_id.clone(),
+            project_id: channel.project_id.clone(),
             extra_fields_whitelist: merge_extra_fields_whitelist(
                 &runtime.extra_fields_whitelist,
                 &provider.extra_fields_whitelist,
@@ -501,7 +742,12 @@ pub(super) async fn collect_provider_attempts(
             strip_cross_protocol_nested_extra: provider
                 .strip_cross_protocol_nested_extra
                 .unwrap_or(runtime.strip_cross_protocol_nested_extra),
+            debug_log_bodies: provider.debug_log_bodies,
+            default_temperature: provider.default_temperature,
+            default_top_p: provider.default_top_p,
+            supports_streaming: model_entry.supports_streaming != Some(false),
             billable_pricing_available: false,
+            free_pricing: false,
             affinity_key: None,
             affinity_key_hash: None,
             affinity_hit: None,
@@ -560,6 +806,74 @@ pub(super) async fn filter_eligible_channels(
     out
 }
 
+/// Returned when a channel has no free concurrency permit; the caller skips the channel.
+pub(super) struct ChannelSaturated;
+
+/// Takes a concurrency permit for the attempt's channel without waiting. The permit must be
+/// held until the upstream call, including any streamed body, has finished.
+pub(super) async fn try_acquire_channel_permit(
+    state: &AppState,
+    attempt: &MonoizeAttempt,
+) -> Result<Option<tokio::sync::OwnedSemaphorePermit>, ChannelSaturated> {
+    let Some(limit) = attempt.max_concurrency else {
+        return Ok(None);
+    };
+    let semaphore = {
+        let mut limiters = state.channel_concurrency.lock().await;
+        let entry = limiters
+            .entry(attempt.channel_id.clone())
+            .or_insert_with(|| crate::monoize_routing::ChannelConcurrencyLimit {
+                limit,
+                semaphore: Arc::new(tokio::sync::Semaphore::new(limit as usize)),
+            });
+        if entry.limit != limit {
+            entry.limit = limit;
+            entry.semaphore = Arc::new(tokio::sync::Semaphore::new(limit as usize));
+        }
+        entry.semaphore.clone()
+    };
+    semaphore
+        .try_acquire_owned()
+        .map(Some)
+        .map_err(|_| ChannelSaturated)
+}
+
+/// Waits for a buffered-transcode slot when `max_buffered_transcodes` is
+/// non-zero. Gives up with 503 `buffered_transcode_busy` after `wait`.
+pub(super) async fn acquire_buffered_transcode_permit(
+    state: &AppState,
+    wait: std::time::Duration,
+) -> AppResult<Option<tokio::sync::OwnedSemaphorePermit>> {
+    let limit = state.monoize_runtime.read().await.max_buffered_transcodes;
+    if limit == 0 {
+        return Ok(None);
+    }
+    let limit = u32::try_from(limit).unwrap_or(u32::MAX);
+    let semaphore = {
+        let mut slot = state.buffered_transcodes.lock().await;
+        match slot.as_mut() {
+            Some(entry) if entry.limit == limit => entry.semaphore.clone(),
+            _ => {
+                let entry = crate::monoize_routing::ChannelConcurrencyLimit {
+                    limit,
+                    semaphore: Arc::new(tokio::sync::Semaphore::new(limit as usize)),
+                };
+                let semaphore = entry.semaphore.clone();
+                *slot = Some(entry);
+                semaphore
+            }
+        }
+    };
+    match tokio::time::timeout(wait, semaphore.acquire_owned()).await {
+        Ok(Ok(permit)) => Ok(Some(permit)),
+        _ => Err(AppError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "buffered_transcode_busy",
+            "too many buffered streaming requests in progress",
+        )),
+    }
+}
+
 fn attempt_health_model(attempt: &MonoizeAttempt) -> Option<&str> {
     attempt
         .per_model_circuit_break
@@ -579,21 +893,104 @@ pub(super) async fn is_attempt_channel_healthy(state: &AppState, attempt: &Monoi
         .healthy
 }
 
+/// Failed passive samples inside each channel's breaker window, keyed by
+/// channel id.
+async fn recent_channel_failure_counts(
+    state: &AppState,
+    channels: &[crate::monoize_routing::MonoizeChannel],
+    model: Option<&str>,
+    default_window_seconds: u64,
+) -> std::collections::HashMap<String, u32> {
+    let now = now_ts();
+    let health = state.channel_health.lock().await;
+    channels
+        .iter()
+        .map(|channel| {
+            let window_seconds = channel
+                .passive_window_seconds_override
+                .unwrap_or(default_window_seconds)
+                .max(1);
+            let cutoff = now.saturating_sub(window_seconds as i64);
+            let failures = health
+                .get(&health_key(&channel.id, model))
+                .map_or(0, |entry| {
+                    entry
+                        .passive_samples
+                        .iter()
+                        .filter(|sample| sample.failed && sample.at_ts >= cutoff)
+                        .count() as u32
+                });
+            (channel.id.clone(), failures)
+        })
+        .collect()
+}
+
+/// Orders channels by ascending `priority` tier, weighted-shuffling within each tier.
+/// With `failure_counts`, each channel's weight is divided by `1 + failures`.
 pub(super) fn weighted_shuffle_channels(
+    channels: Vec<crate::monoize_routing::MonoizeChannel>,
+    failure_counts: Option<&std::collections::HashMap<String, u32>>,
+) -> Vec<crate::monoize_routing::MonoizeChannel> {
+    weighted_shuffle_channels_with(channels, failure_counts, &mut uuid_random_u64)
+}
+
+/// [`weighted_shuffle_channels`] drawing uniform `u64` samples from `rng`.
+pub(super) fn weighted_shuffle_channels_with(
+    mut channels: Vec<crate::monoize_routing::MonoizeChannel>,
+    failure_counts: Option<&std::collections::HashMap<String, u32>>,
+    rng: &mut impl FnMut() -> u64,
+) -> Vec<crate::monoize_routing::MonoizeChannel> {
+    channels.sort_by_key(|channel| channel.priority);
+    let mut ordered = Vec::with_capacity(channels.len());
+    while !channels.is_empty() {
+        let tier_priority = channels[0].priority;
+        let tier_len = channels
+            .iter()
+            .take_while(|channel| channel.priority == tier_priority)
+            .count();
+        let rest = channels.split_off(tier_len);
+        ordered.extend(weighted_shuffle_tier(channels, failure_counts, rng));
+        channels = rest;
+    }
+    ordered
+}
+
+/// Base weights are scaled up before failure de-weighting so that
+/// `weight / (1 + failures)` keeps its ratios in integer arithmetic.
+const FAILURE_WEIGHT_SCALE: u64 = 1_000;
+
+fn channel_draw_weight(
+    channel: &crate::monoize_routing::MonoizeChannel,
+    failure_counts: Option<&std::collections::HashMap<String, u32>>,
+) -> u64 {
+    let base = channel.weight.max(1) as u64;
+    let Some(failure_counts) = failure_counts else {
+        return base;
+    };
+    let failures = failure_counts.get(&channel.id).copied().unwrap_or(0) as u64;
+    (base * FAILURE_WEIGHT_SCALE / (1 + failures)).max(1)
+}
+
+fn weighted_shuffle_tier(
     mut channels: Vec<crate::monoize_routing::MonoizeChannel>,
+    failure_counts: Option<&std::collections::HashMap<String, u32>>,
+    rng: &mut impl FnMut() -> u64,
 ) -> Vec<crate::monoize_routing::MonoizeChannel> {
     let mut ordered = Vec::with_capacity(channels.len());
     while !channels.is_empty() {
-        let total_weight: u64 = channels.iter().map(|c| c.weight.max(1) as u64).sum();
+        let total_weight: u64 = channels
+            .iter()
+            .map(|c| channel_draw_weight(c, failure_counts))
+            .sum();
         if total_weight == 0 {
             ordered.append(&mut channels);
             break;
         }
-        let target = random_u64(total_weight);
+        let target = random_below(total_weight, rng);
         let mut cumulative = 0u64;
         let mut chosen = 0usize;
         for (idx, channel) in channels.iter().enumerate() {
-            cumulative += channel.weight.max(1) as u64;
+            cumulative += channel_draw_weight(channel, failure_counts);
             if target < cumulative {
                 chosen = idx;
                 break;
@@ -604,14 +1001,18 @@ pub(super) fn weighted_shuffle_channels(
     ordered
 }
 
-pub(super) fn random_u64(bound: u64) -> u64 {
+fn uuid_random_u64() -> u64 {
+    uuid::Uuid::new_v4().as_u128() as u64
+}
+
+fn random_below(bound: u64, rng: &mut impl FnMut() -> u64) -> u64 {
     if bound <= 1 {
         return 0;
     }
     // Rejection sampling to avoid modulo bias
     let limit = u64::MAX - (u64::MAX % bound);
     loop {
-        let sample = uuid::Uuid::new_v4().as_u128() as u64;
+        let sample = rng();
         if sample < limit {
             return sample % bound;
         }
@@ -640,7 +1041,26 @@ pub(super) fn build_channel_provider_config(attempt: &MonoizeAttempt) -> Provide
         model_map: Vec::new(),
         strategy: None,
         members: Vec::new(),
+        extra_headers: openai_account_headers(attempt),
+    }
+}
+
+/// `OpenAI-Organization`/`OpenAI-Project` bill enterprise keys to the right
+/// account; other protocols never receive them.
+fn openai_account_headers(attempt: &MonoizeAttempt) -> Vec<(String, String)> {
+    if !matches!(
+        attempt.provider_type,
+        ProviderType::Responses | ProviderType::ChatCompletion | ProviderType::OpenaiImage
+    ) {
+        return Vec::new();
     }
+    [
+        ("OpenAI-Organization", &attempt.org_id),
+        ("OpenAI-Project", &attempt.project_id),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| value.clone().map(|value| (name.to_string(), value)))
+    .collect()
 }
 
 pub(super) fn provider_extra_headers(
@@ -713,6 +1133,52 @@ pub(super) fn build_exhausted_upstream_error(model: &str, tried: &[TriedProvider
     err
 }
 
+/// Takes the next key of the attempt's channel pool, or `None` when the channel
+/// has a single key. Called when the attempt is about to be sent, so fallback
+/// attempts that never run do not advance the rotation.
+pub(super) async fn next_channel_key(state: &AppState, attempt: &MonoizeAttempt) -> Option<String> {
+    if attempt.api_key_pool.len() < 2 {
+        return None;
+    }
+    state
+        .channel_keys
+        .lock()
+        .await
+        .entry(attempt.channel_id.clone())
+        .or_default()
+        .next_key(&attempt.api_key_pool, now_ts())
+}
+
+/// Suspends the attempt's key for the channel's passive cooldown when the
+/// upstream rejects it with 401/403 and the channel has other keys to rotate
+/// to. Channel health is left untouched. Returns whether the key was
+/// suspended, in which case the caller may retry the channel with its next key
+/// instead of failing the request.
+pub(super) async fn suspend_rejected_channel_key(
+    state: &AppState,
+    attempt: &MonoizeAttempt,
+    err: &UpstreamCallError,
+) -> bool {
+    if attempt.api_key_pool.len() < 2
+        || !matches!(
+            err.status,
+            Some(StatusCode::UNAUTHORIZED) | Some(StatusCode::FORBIDDEN)
+        )
+    {
+        return false;
+    }
+    let until = now_ts().saturating_add(attempt.passive_cooldown_seconds as i64);
+    state
+        .channel_keys
+        .lock()
+        .await
+        .entry(attempt.channel_id.clone())
+        .or_default()
+        .suspended_until
+        .insert(attempt.api_key.clone(), until);
+    true
+}
+
 pub(super) fn is_non_retryable_client_error(err: &UpstreamCallError) -> bool {
     matches!(
         err.status,
@@ -723,6 +1189,11 @@ pub(super) fn is_non_retryable_client_error(err: &UpstreamCallError) -> bool {
     )
 }
 
+/// A 2xx upstream status whose body is empty or not JSON.
+pub(super) fn is_malformed_success_response(err: &UpstreamCallError) -> bool {
+    matches!(err.kind, UpstreamErrorKind::Http) && err.status.is_some_and(|s| s.is_success())
+}
+
 pub(super) fn is_retryable_error(err: &UpstreamCallError) -> bool {
     if matches!(err.kind, UpstreamErrorKind::Network) {
         return true;
@@ -739,13 +1210,24 @@ pub(super) fn is_retryable_error(err: &UpstreamCallError) -> bool {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum RetryableFailureClass {
-    RateLimited,
+    RateLimited { retry_after: Option<Duration> },
     Transient,
 }
 
+impl RetryableFailureClass {
+    pub(super) fn retry_after(self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after } => retry_after,
+            Self::Transient => None,
+        }
+    }
+}
+
 pub(super) fn classify_retryable_failure(err: &UpstreamCallError) -> RetryableFailureClass {
     if matches!(err.status, Some(StatusCode::TOO_MANY_REQUESTS)) {
-        return RetryableFailureClass::RateLimited;
+        return RetryableFailureClass::RateLimited {
+            retry_after: err.retry_after,
+        };
     }
     RetryableFailureClass::Transient
 }
@@ -772,12 +1254,14 @@ pub(super) async fn mark_channel_success(state: &AppState, attempt: &MonoizeAtte
     let entry = health
         .entry(key)
         .or_insert_with(crate::monoize_routing::ChannelHealthState::new);
-    let was_unhealthy = !entry.healthy;
-    entry.healthy = true;
-    entry.cooldown_until = None;
-    entry.last_success_at = Some(now);
-    entry.probe_success_count = 0;
-    entry.last_probe_at = None;
+    // An unhealthy entry is half-open: each success is a probe, and the channel
+    // recovers only after `probe_success_threshold` consecutive ones.
+    let recovered = if entry.healthy {
+        entry.mark_success(now);
+        false
+    } else {
+        entry.record_probe_success(now, attempt.probe_success_threshold)
+    };
     entry
         .passive_samples
         .push_back(crate::monoize_routing::PassiveHealthSample {
@@ -789,8 +1273,15 @@ pub(super) async fn mark_channel_success(state: &AppState, attempt: &MonoizeAtte
         now,
         attempt.passive_window_seconds,
     );
-    if was_unhealthy {
+    if recovered {
         tracing::info!(channel_id = %attempt.channel_id, "channel recovered to healthy after success");
+    } else if !entry.healthy {
+        tracing::info!(
+            channel_id = %attempt.channel_id,
+            probe_success_count = entry.probe_success_count,
+            probe_success_threshold = attempt.probe_success_threshold,
+            "half-open channel probe succeeded"
+        );
     }
 }
 
@@ -820,14 +1311,26 @@ pub(super) async fn mark_channel_retryable_failure(
         attempt.passive_window_seconds,
     );
 
-    let failure_samples = entry.passive_samples.iter().filter(|s| s.failed).count() as u32;
+    let cooldown_seconds = match failure_class {
+        RetryableFailureClass::RateLimited { retry_after } => attempt
+            .passive_rate_limit_cooldown_seconds
+            .max(retry_after.map_or(0, |delay| delay.as_secs_f64().ceil() as u64)),
+        RetryableFailureClass::Transient => attempt.passive_cooldown_seconds,
+    };
+    if entry.is_half_open(now) {
+        entry.reopen(now, cooldown_seconds);
+        tracing::info!(
+            channel_id = %attempt.channel_id,
+            failure_class = ?failure_class,
+            cooldown_seconds,
+            "half-open channel probe failed; cooldown restarted"
+        );
+        return;
+    }
+
+    let failure_samples = entry.failure_count();
     if failure_samples >= attempt.passive_failure_count_threshold {
         entry.healthy = false;
-        let cooldown_seconds = if failure_class == RetryableFailureClass::RateLimited {
-            attempt.passive_rate_limit_cooldown_seconds
-        } else {
-            attempt.passive_cooldown_seconds
-        };
         entry.cooldown_until = Some(now + cooldown_seconds as i64);
         entry.probe_success_count = 0;
         entry.last_probe_at = None;
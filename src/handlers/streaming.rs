@@ -84,24 +84,92 @@ pub(super) async fn forward_stream_typed(
     request_id: Option<String>,
     request_ip: Option<String>,
     capture: RequestCaptureContext,
-) -> AppResult<
+) -> AppResult<(
     impl futures_util::Stream<Item = Result<Event, std::convert::Infallible>> + Send + 'static,
-> {
+    Option<ServedRoute>,
+)> {
     let started_at = std::time::Instant::now();
     let mut last_failed_attempt: Option<MonoizeAttempt> = None;
     let mut tried_providers: Vec<TriedProvider> = Vec::new();
+    resolve_model_alias(&state, &mut req).await;
     let requested_model = req.model.clone();
     let transform_match_model =
         normalized_logical_model_for_matching(&state, &requested_model).await;
     resolve_model_suffix(&state, &mut req).await;
+    ensure_model_passes_filters(&auth, &req.model)?;
     // Preserve the suffix-normalized request so each per-attempt iteration can
     // re-derive the transformed request from a pristine base (see the matching
     // comment in `execute_nonstream_typed`).
     let original_req = req.clone();
+    let request_tool_names = request_function_tool_names(&original_req);
     let logical_model = req.model.clone();
+    // Chat clients opt into the trailing usage chunk per request; upstream
+    // usage is still requested unconditionally for billing.
+    let chat_include_usage = original_req
+        .extra_body
+        .get("stream_options")
+        .and_then(|options| options.get("include_usage"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if is_echo_model(&logical_model) {
+        let attempt = echo_attempt();
+        let resp = echo_response(&req);
+        let global_transforms = state.monoize_runtime.read().await.global_transforms.clone();
+        let sse_max_frame_length = effective_sse_max_frame_length(
+            &[],
+            &global_transforms,
+            &auth.transforms,
+            &logical_model,
+        );
+        spawn_request_log(
+            &state,
+            &auth,
+            &attempt,
+            &logical_model,
+            resp.usage.clone(),
+            Some(0),
+            None,
+            true,
+            started_at,
+            request_id,
+            request_ip,
+            attempt.channel_id.clone(),
+            Some(started_at.elapsed().as_millis() as u64),
+            None,
+            None,
+            req.reasoning.as_ref().and_then(|r| r.effort.clone()),
+            Vec::new(),
+            None,
+        );
+        if let Some(session) = capture.session.as_ref() {
+            session
+                .persist_with_result(resp.usage.as_ref(), false)
+                .await;
+        }
+        let (tx, rx) = mpsc::channel::<Event>(64);
+        tokio::spawn(async move {
+            if let Err(err) = crate::urp::stream_encode::emit_synthetic_stream_from_urp_response(
+                downstream,
+                &logical_model,
+                &resp,
+                None,
+                sse_max_frame_length,
+                chat_include_usage,
+                tx,
+            )
+            .await
+            {
+                tracing::warn!("echo stream failed: {}", err.message);
+            }
+        });
+        let route = ServedRoute::from_attempt(&attempt);
+        return Ok((receiver_event_stream(rx), Some(route)));
+    }
     let routing_stub = build_routing_stub(&req, max_multiplier);
-    let attempts = build_monoize_attempts(&state, &routing_stub, &auth).await?;
-    ensure_balance_before_forward_for_attempts(&state, &auth, &attempts).await?;
+    let mut attempts = build_monoize_attempts(&state, &routing_stub, &auth).await?;
+    retain_conversation_chaining_attempts(&mut attempts, &req)?;
+    retain_role_order_attempts(&state, &mut attempts, &req).await?;
+    ensure_balance_before_forward_for_request(&state, &auth, &attempts, &req).await?;
     let pending_request_log_guard = insert_pending_request_log(
         &state,
         &auth,
@@ -114,12 +182,17 @@ pub(super) async fn forward_stream_typed(
     .await;
 
     let mut execution_state = AttemptExecutionState::default();
+    let decode_retry_limit = state.monoize_runtime.read().await.decode_retry_limit;
+    let mut last_decode_error: Option<AppError> = None;
 
-    for attempt in attempts {
+    for mut attempt in attempts {
         execution_state.enter_provider(&attempt.provider_id);
         if !execution_state.provider_budget_remaining(&attempt) {
             continue;
         }
+        let Ok(channel_permit) = try_acquire_channel_permit(&state, &attempt).await else {
+            continue;
+        };
 
         let global_transforms = state.monoize_runtime.read().await.global_transforms.clone();
 
@@ -135,15 +208,30 @@ pub(super) async fn forward_stream_typed(
             &auth.transforms,
             &logical_model,
             downstream,
-        ) || attempt.provider_type == ProviderType::Replicate;
+        ) || attempt.provider_type == ProviderType::Replicate
+            || !attempt.supports_streaming;
         let max_channel_attempts = (attempt.channel_max_retries + 1).max(1) as usize;
+        // Reserves the attempt's worst-case charge until it is settled; dropping
+        // the hold on any error or retry path releases it.
+        let mut balance_hold =
+            place_stream_balance_hold(&state, &auth, &attempt, &original_req).await?;
 
-        for channel_attempt in 0..max_channel_attempts {
-            if !execution_state.provider_budget_remaining(&attempt) {
+        // A key rejected with 401/403 is retried on the same channel with the
+        // pool's next key without using up a channel retry.
+        let max_key_retries = attempt.api_key_pool.len().saturating_sub(1);
+        let mut key_retries = 0;
+        for send_index in 0..max_channel_attempts + max_key_retries {
+            let channel_attempt = send_index - key_retries;
+            if channel_attempt >= max_channel_attempts
+                || !execution_state.provider_budget_remaining(&attempt)
+            {
                 break;
             }
 
             let attempt_number = execution_state.record_upstream_attempt();
+            if let Some(api_key) = next_channel_key(&state, &attempt).await {
+                attempt.api_key = api_key;
+            }
             // Clone from the pristine original request (pre-transforms) so
             // that the cross-family strip runs BEFORE provider, global, and
             // API-key transforms; see `execute_nonstream_typed`.
@@ -161,6 +249,7 @@ pub(super) async fn forward_stream_typed(
             }
             inject_monoize_context(&auth, &mut req_attempt);
             req_attempt.model = attempt.upstream_model.clone();
+            apply_provider_sampling_defaults(&mut req_attempt, &attempt);
             // Unwrap mz2 reasoning envelopes BEFORE any request-phase transform
             // observes the request input. See `nonstream.rs` for rationale and
             // spec references (urp-transform-system PIPE-1 step 6, PIPE-1d).
@@ -175,7 +264,7 @@ pub(super) async fn forward_stream_typed(
                 &mut req_attempt,
                 &attempt.provider_transforms,
                 &transform_match_model,
-                Some(attempt.provider_type),
+                &attempt,
             )
             .await?;
             apply_transform_rules_request(
@@ -183,7 +272,7 @@ pub(super) async fn forward_stream_typed(
                 &mut req_attempt,
                 &global_transforms,
                 &transform_match_model,
-                Some(attempt.provider_type),
+                &attempt,
             )
             .await?;
             apply_transform_rules_request(
@@ -191,29 +280,55 @@ pub(super) async fn forward_stream_typed(
                 &mut req_attempt,
                 &auth.transforms,
                 &transform_match_model,
-                Some(attempt.provider_type),
+                &attempt,
             )
             .await?;
             strip_monoize_context(&mut req_attempt);
 
             if requires_buffered_stream {
+                // Held through the upstream call and the synthetic emission.
+                let transcode_permit = acquire_buffered_transcode_permit(
+                    &state,
+                    std::time::Duration::from_millis(attempt.request_timeout_ms),
+                )
+                .await?;
                 let mut nonstream_req = req_attempt.clone();
                 nonstream_req.stream = Some(false);
-                let upstream_body =
-                    encode_request_for_provider(&mut nonstream_req, &attempt, downstream)?;
+                let encoded =
+                    encode_request_for_provider(&state, &mut nonstream_req, &attempt, downstream)
+                        .await?;
                 let provider = build_channel_provider_config(&attempt);
                 let path =
                     upstream_path_for_model(attempt.provider_type, &req_attempt.model, false);
-                let call = upstream::call_upstream_with_timeout_and_headers(
-                    client_http(&state),
-                    &provider,
-                    &attempt.api_key,
-                    &path,
-                    &upstream_body,
-                    attempt.request_timeout_ms,
-                    provider_extra_headers(attempt.provider_type, &upstream_body),
-                )
-                .await;
+                let (upstream_body, call) = match encoded {
+                    Ok(EncodedRequest {
+                        value: upstream_body,
+                        bytes,
+                    }) => {
+                        let call = upstream::call_upstream_json_bytes_with_timeout_and_headers(
+                            client_http_nonstream(&state),
+                            &provider,
+                            &attempt.api_key,
+                            &path,
+                            bytes,
+                            attempt.request_timeout_ms,
+                            provider_extra_headers(attempt.provider_type, &upstream_body),
+                        )
+                        .await;
+                        (upstream_body, call)
+                    }
+                    Err(EncodeFailure::Upstream(err)) => (Value::Null, Err(err)),
+                    Err(EncodeFailure::Oversized(app_err)) => {
+                        execution_state.record_size_rejection(&app_err);
+                        tried_providers.push(TriedProvider::from_app_error(
+                            attempt_number,
+                            &attempt,
+                            &app_err,
+                        ));
+                        last_failed_attempt = Some(attempt.clone());
+                        break;
+                    }
+                };
                 match call {
                     Ok(value) => {
                         if let Some(session) = capture.session.as_ref() {
@@ -255,12 +370,43 @@ pub(super) async fn forward_stream_typed(
                         ) {
                             Ok(resp) => resp,
                             Err(err) => {
-                                if let Some(session) = capture.session.as_ref() {
-                                    session.persist_with_result(None, false).await;
+                                if !execution_state.record_decode_failure(decode_retry_limit) {
+                                    if let Some(session) = capture.session.as_ref() {
+                                        session.persist_with_result(None, false).await;
+                                    }
+                                    return Err(err);
                                 }
-                                return Err(err);
+                                clear_channel_affinity(&state, &attempt).await;
+                                tried_providers.push(TriedProvider::from_app_error(
+                                    attempt_number,
+                                    &attempt,
+                                    &err,
+                                ));
+                                mark_channel_retryable_failure(
+                                    &state,
+                                    &attempt,
+                                    RetryableFailureClass::Transient,
+                                )
+                                .await;
+                                last_failed_attempt = Some(attempt.clone());
+                                last_decode_error = Some(err);
+                                if !is_attempt_channel_healthy(&state, &attempt).await {
+                                    break;
+                                }
+                                if execution_state.provider_budget_remaining(&attempt) {
+                                    if channel_attempt + 1 < max_channel_attempts {
+                                        maybe_sleep_before_channel_retry(&attempt, None).await;
+                                    }
+                                    continue;
+                                }
+                                break;
                             }
                         };
+                        urp::normalize_finish_reason_other(
+                            &mut resp.finish_reason,
+                            &resp.extra_body,
+                            &state.monoize_runtime.read().await.finish_reason_map,
+                        );
                         // Wrap newly produced encrypted reasoning payloads in
                         // mz2 envelopes BEFORE response-phase transforms run.
                         // See `nonstream.rs` and PIPE-1d in
@@ -278,6 +424,7 @@ pub(super) async fn forward_stream_typed(
                             &attempt.provider_transforms,
                             &logical_model,
                             Some(attempt.provider_type),
+                            &request_tool_names,
                         )
                         .await
                         {
@@ -292,6 +439,7 @@ pub(super) async fn forward_stream_typed(
                             &global_transforms,
                             &logical_model,
                             Some(attempt.provider_type),
+                            &request_tool_names,
                         )
                         .await
                         {
@@ -306,6 +454,7 @@ pub(super) async fn forward_stream_typed(
                             &auth.transforms,
                             &logical_model,
                             Some(attempt.provider_type),
+                            &request_tool_names,
                         )
                         .await
                         {
@@ -336,6 +485,9 @@ pub(super) async fn forward_stream_typed(
                                 return Err(err);
                             }
                         };
+                        if let Some(hold) = balance_hold.take() {
+                            state.user_store.release_hold(hold);
+                        }
                         spawn_request_log(
                             &state,
                             &auth,
@@ -354,6 +506,7 @@ pub(super) async fn forward_stream_typed(
                             None,
                             req.reasoning.as_ref().and_then(|r| r.effort.clone()),
                             tried_providers,
+                            None,
                         );
                         if let Some(session) = capture.session.as_ref() {
                             session
@@ -361,8 +514,10 @@ pub(super) async fn forward_stream_typed(
                                 .await;
                         }
                         let (tx, rx) = mpsc::channel::<Event>(64);
-                        let logical_model_for_stream = logical_model.clone();
+                        let logical_model_for_stream =
+                            downstream_model_for_attempt(&state, &logical_model, &attempt).await;
                         tokio::spawn(async move {
+                            let _transcode_permit = transcode_permit;
                             let tx_err = tx.clone();
                             let synthetic_reasoning_duration_secs =
                                 Some(started_at.elapsed().as_secs());
@@ -373,6 +528,7 @@ pub(super) async fn forward_stream_typed(
                                     &resp,
                                     synthetic_reasoning_duration_secs,
                                     sse_max_frame_length,
+                                    chat_include_usage,
                                     tx,
                                 )
                                 .await;
@@ -387,7 +543,8 @@ pub(super) async fn forward_stream_typed(
                                 }
                             }
                         });
-                        return Ok(receiver_event_stream(rx));
+                        let route = ServedRoute::from_attempt(&attempt);
+                        return Ok((receiver_event_stream(rx), Some(route)));
                     }
                     Err(err) => {
                         if let Some(session) = capture.session.as_ref() {
@@ -413,10 +570,24 @@ pub(super) async fn forward_stream_typed(
                                 ))
                                 .await;
                         }
-                        let non_retryable = is_non_retryable_client_error(&err);
-                        let retryable = is_retryable_error(&err);
+                        let key_rejected =
+                            suspend_rejected_channel_key(&state, &attempt, &err).await;
+                        let retry_next_key = key_rejected && key_retries < max_key_retries;
+                        let non_retryable = is_non_retryable_client_error(&err) && !retry_next_key;
+                        let decode_failure = is_malformed_success_response(&err);
+                        let retryable = is_retryable_error(&err)
+                            || (decode_failure
+                                && execution_state.record_decode_failure(decode_retry_limit));
                         let retryable_failure_class = classify_retryable_failure(&err);
-                        let app_err = upstream_error_to_app(err);
+                        let app_err = if decode_failure {
+                            AppError::new(
+                                StatusCode::BAD_GATEWAY,
+                                "invalid_upstream_response",
+                                err.message,
+                            )
+                        } else {
+                            upstream_error_to_app(err)
+                        };
                         if non_retryable {
                             let terminal_error = stream_terminal_error_from_app(&app_err);
                             spawn_request_log_stream_terminal_error(
@@ -429,13 +600,24 @@ pub(super) async fn forward_stream_typed(
                                 request_ip.clone(),
                                 None,
                                 terminal_error,
+                                None,
                                 req.reasoning.as_ref().and_then(|r| r.effort.clone()),
                                 tried_providers,
                             );
                             if let Some(session) = capture.session.as_ref() {
                                 session.persist_with_result(None, true).await;
                             }
-                            return Ok(prestream_error_stream(downstream, app_err));
+                            return Ok((prestream_error_stream(downstream, app_err), None));
+                        }
+                        if retry_next_key {
+                            key_retries += 1;
+                            tried_providers.push(TriedProvider::from_app_error(
+                                attempt_number,
+                                &attempt,
+                                &app_err,
+                            ));
+                            last_failed_attempt = Some(attempt.clone());
+                            continue;
                         }
                         if retryable {
                             clear_channel_affinity(&state, &attempt).await;
@@ -451,12 +633,17 @@ pub(super) async fn forward_stream_typed(
                             )
                             .await;
                             last_failed_attempt = Some(attempt.clone());
+                            last_decode_error = decode_failure.then(|| app_err.clone());
                             if !is_attempt_channel_healthy(&state, &attempt).await {
                                 break;
                             }
                             if execution_state.provider_budget_remaining(&attempt) {
                                 if channel_attempt + 1 < max_channel_attempts {
-                                    maybe_sleep_before_channel_retry(&attempt).await;
+                                    maybe_sleep_before_channel_retry(
+                                        &attempt,
+                                        retryable_failure_class.retry_after(),
+                                    )
+                                    .await;
                                 }
                                 continue;
                             }
@@ -473,33 +660,70 @@ pub(super) async fn forward_stream_typed(
                             request_ip.clone(),
                             None,
                             terminal_error,
+                            None,
                             req.reasoning.as_ref().and_then(|r| r.effort.clone()),
                             tried_providers,
                         );
                         if let Some(session) = capture.session.as_ref() {
                             session.persist_with_result(None, true).await;
                         }
-                        return Ok(prestream_error_stream(downstream, app_err));
+                        return Ok((prestream_error_stream(downstream, app_err), None));
                     }
                 }
             }
 
-            let upstream_body =
-                encode_request_for_provider(&mut req_attempt, &attempt, downstream)?;
+            let encoded =
+                encode_request_for_provider(&state, &mut req_attempt, &attempt, downstream).await?;
             let provider = build_channel_provider_config(&attempt);
             let path = upstream_path_for_model(attempt.provider_type, &req_attempt.model, true);
-            let call = upstream::call_upstream_raw_with_timeout_and_headers(
-                client_http(&state),
-                &provider,
-                &attempt.api_key,
-                &path,
-                &upstream_body,
-                attempt.request_timeout_ms.saturating_mul(10).max(600_000),
-                provider_extra_headers(attempt.provider_type, &upstream_body),
-            )
-            .await;
+            let (upstream_body, call) = match encoded {
+                Ok(EncodedRequest {
+                    value: upstream_body,
+                    bytes,
+                }) => {
+                    if attempt.debug_log_bodies {
+                        tracing::debug!(
+                            provider_id = %attempt.provider_id,
+                            channel_id = %attempt.channel_id,
+                            body = %redact_sensitive(&upstream_body),
+                            "upstream request body"
+                        );
+                    }
+                    let call = upstream::call_upstream_stream_json_bytes_with_headers(
+                        client_http(&state),
+                        &provider,
+                        &attempt.api_key,
+                        &path,
+                        bytes,
+                        attempt.request_timeout_ms,
+                        provider_extra_headers(attempt.provider_type, &upstream_body),
+                    )
+                    .await;
+                    (upstream_body, call)
+                }
+                Err(EncodeFailure::Upstream(err)) => (Value::Null, Err(err)),
+                Err(EncodeFailure::Oversized(app_err)) => {
+                    execution_state.record_size_rejection(&app_err);
+                    tried_providers.push(TriedProvider::from_app_error(
+                        attempt_number,
+                        &attempt,
+                        &app_err,
+                    ));
+                    last_failed_attempt = Some(attempt.clone());
+                    break;
+                }
+            };
             match call {
                 Ok(upstream_resp) => {
+                    let upstream_resp = if attempt.debug_log_bodies {
+                        log_upstream_sse_lines(
+                            upstream_resp,
+                            attempt.provider_id.clone(),
+                            DEBUG_LOG_SSE_LINES,
+                        )
+                    } else {
+                        upstream_resp
+                    };
                     update_pending_channel_info(
                         &state,
                         &auth,
@@ -528,12 +752,19 @@ pub(super) async fn forward_stream_typed(
                         .session
                         .as_ref()
                         .map(|_| std::sync::Arc::new(Mutex::new(Vec::<String>::new())));
+                    let token_estimator = state
+                        .monoize_runtime
+                        .read()
+                        .await
+                        .token_estimator
+                        .estimator();
                     let runtime_metrics = Arc::new(Mutex::new(StreamRuntimeMetrics {
                         ttfb_ms: None,
                         usage: None,
                         response_id: None,
                         terminal: StreamTerminalDiagnostics::default(),
                         estimated_output_tokens: 0,
+                        token_estimator: Some(token_estimator),
                         first_visible_output_ms: None,
                         last_visible_output_ms: None,
                         visible_output_bytes: 0,
@@ -543,8 +774,10 @@ pub(super) async fn forward_stream_typed(
                     let auth_for_log = auth.clone();
                     let attempt_for_log = attempt.clone();
                     let model_for_log = logical_model.clone();
-                    let model_for_encode = logical_model.clone();
+                    let model_for_encode =
+                        downstream_model_for_attempt(&state, &logical_model, &attempt).await;
                     let model_for_transform = logical_model.clone();
+                    let request_tool_names_for_transform = request_tool_names.clone();
                     let request_id_for_log = request_id.clone();
                     let request_ip_for_log = request_ip.clone();
                     let channel_id_for_log = attempt.channel_id.clone();
@@ -564,14 +797,11 @@ pub(super) async fn forward_stream_typed(
                     let reasoning_effort_for_log =
                         req.reasoning.as_ref().and_then(|r| r.effort.clone());
                     let tried_providers_for_log = tried_providers.clone();
-                    let enable_estimated_billing =
-                        state.monoize_runtime.read().await.enable_estimated_billing;
-                    let stream_idle_timeout_ms = state
-                        .monoize_runtime
-                        .read()
-                        .await
-                        .stream_idle_timeout_ms
-                        .max(1);
+                    let enable_estimated_billing = usage::stream_usage_estimate_enabled(
+                        &*state.monoize_runtime.read().await,
+                        attempt.provider_type,
+                    );
+                    let stream_idle_timeout_ms = attempt.stream_idle_timeout_ms;
                     let state_for_transform = state.clone();
                     let provider_rules_for_transform = attempt.provider_transforms.clone();
                     let global_rules_for_transform = global_transforms.clone();
@@ -583,9 +813,33 @@ pub(super) async fn forward_stream_typed(
                                 req_attempt.model.clone(),
                             )
                         });
+                    // Responses -> Responses with nothing to rewrite: forward the upstream
+                    // frames verbatim and run the decoder only for metrics and billing.
+                    let passthrough = matches!(downstream, DownstreamProtocol::Responses)
+                        && provider_type == ProviderType::Responses
+                        && reasoning_envelope_for_transform.is_none()
+                        && pending_request_envelope_extra.is_none()
+                        && !has_active_response_rules(
+                            &provider_rules_for_transform,
+                            &global_rules_for_transform,
+                            &auth_rules_for_transform,
+                            &model_for_transform,
+                        );
+                    let upstream_resp = if passthrough {
+                        tee_responses_sse_passthrough(
+                            upstream_resp,
+                            tx.clone(),
+                            model_for_encode.clone(),
+                        )
+                    } else {
+                        upstream_resp
+                    };
                     let pending_request_log_guard_for_stream = pending_request_log_guard;
+                    let channel_permit_for_stream = channel_permit;
+                    let balance_hold_for_stream = balance_hold.take();
                     tokio::spawn(async move {
                         let _pending_request_log_guard = pending_request_log_guard_for_stream;
+                        let _channel_permit = channel_permit_for_stream;
                         let tx_err = tx.clone();
                         let stream_future = async {
                             let (decoded_tx, decoded_rx) =
@@ -610,7 +864,14 @@ pub(super) async fn forward_stream_typed(
                                 })
                             };
 
-                            let transform_handle =
+                            let transform_handle = if passthrough {
+                                crate::request_capture::spawn_with_sse_capture(async move {
+                                    let mut decoded_rx = decoded_rx;
+                                    while decoded_rx.recv().await.is_some() {}
+                                    drop(transformed_tx);
+                                    Ok(())
+                                })
+                            } else {
                                 crate::request_capture::spawn_with_sse_capture(async move {
                                     let reasoning_envelope = reasoning_envelope_for_transform
                                         .as_ref()
@@ -627,11 +888,19 @@ pub(super) async fn forward_stream_typed(
                                         &model_for_transform,
                                         Some(transform_provider_type),
                                         reasoning_envelope,
+                                        request_tool_names_for_transform,
                                     )
                                     .await
-                                });
+                                })
+                            };
 
-                            let encode_handle =
+                            let encode_handle = if passthrough {
+                                drop(tx);
+                                crate::request_capture::spawn_with_sse_capture(async move {
+                                    drop(transformed_rx);
+                                    Ok(())
+                                })
+                            } else {
                                 crate::request_capture::spawn_with_sse_capture(async move {
                                     encode_urp_stream(
                                         downstream,
@@ -640,13 +909,15 @@ pub(super) async fn forward_stream_typed(
                                         &model_for_encode,
                                         started_at,
                                         sse_max_frame_length,
+                                        chat_include_usage,
                                     )
                                     .await
-                                });
+                                })
+                            };
 
                             let (decode_result, transform_result, encode_result) =
                                 tokio::join!(decode_handle, transform_handle, encode_handle);
-                            decode_result
+                            let result = decode_result
                                 .unwrap_or_else(|e| {
                                     Err(AppError::new(
                                         StatusCode::INTERNAL_SERVER_ERROR,
@@ -667,7 +938,17 @@ pub(super) async fn forward_stream_typed(
                                         "task_panic",
                                         e.to_string(),
                                     ))
-                                }))
+                                }));
+                            // The decoder stops reading at the upstream terminal event, so the
+                            // passthrough tee never forwards `[DONE]`; emit it once here.
+                            if passthrough && result.is_ok() {
+                                urp::stream_helpers::send_plain_sse_data(
+                                    &tx_err,
+                                    "[DONE]".to_string(),
+                                )
+                                .await?;
+                            }
+                            result
                         };
                         let stream_result = if let Some(frames) = capture_frames_for_task.clone() {
                             crate::request_capture::with_sse_capture(frames, stream_future).await
@@ -701,7 +982,10 @@ pub(super) async fn forward_stream_typed(
                                             output_tokens: guard.estimated_output_tokens,
                                             input_details: None,
                                             output_details: None,
-                                            extra_body: std::collections::HashMap::new(),
+                                            extra_body: std::collections::HashMap::from([(
+                                                "estimated".to_string(),
+                                                serde_json::Value::Bool(true),
+                                            )]),
                                         }),
                                         true,
                                     )
@@ -735,6 +1019,7 @@ pub(super) async fn forward_stream_typed(
                                 request_ip_for_log,
                                 ttfb_ms,
                                 terminal_error,
+                                actual_upstream_usage.clone(),
                                 reasoning_effort_for_log,
                                 tried_providers_for_log,
                             );
@@ -798,6 +1083,9 @@ pub(super) async fn forward_stream_typed(
                             },
                             None => ChargeComputation::default(),
                         };
+                        if let Some(hold) = balance_hold_for_stream {
+                            state_for_log.user_store.release_hold(hold);
+                        }
                         if is_estimated {
                             if let Some(ref mut breakdown) = charge.billing_breakdown {
                                 if let Some(obj) = breakdown.as_object_mut() {
@@ -846,6 +1134,7 @@ pub(super) async fn forward_stream_typed(
                             Some(terminal_diagnostics),
                             reasoning_effort_for_log,
                             tried_providers_for_log,
+                            None,
                         );
 
                         if let Err(ref err) = stream_result {
@@ -945,7 +1234,8 @@ pub(super) async fn forward_stream_typed(
                                 .await;
                         }
                     });
-                    return Ok(receiver_event_stream(rx));
+                    let route = ServedRoute::from_attempt(&attempt);
+                    return Ok((receiver_event_stream(rx), Some(route)));
                 }
                 Err(err) => {
                     if let Some(session) = capture.session.as_ref() {
@@ -971,7 +1261,9 @@ pub(super) async fn forward_stream_typed(
                             ))
                             .await;
                     }
-                    let non_retryable = is_non_retryable_client_error(&err);
+                    let key_rejected = suspend_rejected_channel_key(&state, &attempt, &err).await;
+                    let retry_next_key = key_rejected && key_retries < max_key_retries;
+                    let non_retryable = is_non_retryable_client_error(&err) && !retry_next_key;
                     let retryable = is_retryable_error(&err);
                     let retryable_failure_class = classify_retryable_failure(&err);
                     let app_err = upstream_error_to_app(err);
@@ -987,13 +1279,24 @@ pub(super) async fn forward_stream_typed(
                             request_ip.clone(),
                             None,
                             terminal_error,
+                            None,
                             req.reasoning.as_ref().and_then(|r| r.effort.clone()),
                             tried_providers,
                         );
                         if let Some(session) = capture.session.as_ref() {
                             session.persist_with_result(None, true).await;
                         }
-                        return Ok(prestream_error_stream(downstream, app_err));
+                        return Ok((prestream_error_stream(downstream, app_err), None));
+                    }
+                    if retry_next_key {
+                        key_retries += 1;
+                        tried_providers.push(TriedProvider::from_app_error(
+                            attempt_number,
+                            &attempt,
+                            &app_err,
+                        ));
+                        last_failed_attempt = Some(attempt.clone());
+                        continue;
                     }
                     if retryable {
                         clear_channel_affinity(&state, &attempt).await;
@@ -1005,12 +1308,17 @@ pub(super) async fn forward_stream_typed(
                         mark_channel_retryable_failure(&state, &attempt, retryable_failure_class)
                             .await;
                         last_failed_attempt = Some(attempt.clone());
+                        last_decode_error = None;
                         if !is_attempt_channel_healthy(&state, &attempt).await {
                             break;
                         }
                         if execution_state.provider_budget_remaining(&attempt) {
                             if channel_attempt + 1 < max_channel_attempts {
-                                maybe_sleep_before_channel_retry(&attempt).await;
+                                maybe_sleep_before_channel_retry(
+                                    &attempt,
+                                    retryable_failure_class.retry_after(),
+                                )
+                                .await;
                             }
                             continue;
                         }
@@ -1027,18 +1335,21 @@ pub(super) async fn forward_stream_typed(
                         request_ip.clone(),
                         None,
                         terminal_error,
+                        None,
                         req.reasoning.as_ref().and_then(|r| r.effort.clone()),
                         tried_providers,
                     );
                     if let Some(session) = capture.session.as_ref() {
                         session.persist_with_result(None, true).await;
                     }
-                    return Ok(prestream_error_stream(downstream, app_err));
+                    return Ok((prestream_error_stream(downstream, app_err), None));
                 }
             }
         }
     }
-    let final_err = build_exhausted_upstream_error(&logical_model, &tried_providers);
+    let final_err = last_decode_error
+        .or_else(|| execution_state.exhaustive_size_rejection(tried_providers.len()))
+        .unwrap_or_else(|| build_exhausted_upstream_error(&logical_model, &tried_providers));
     if let Some(attempt) = last_failed_attempt {
         let terminal_error = stream_terminal_error_from_app(&final_err);
         spawn_request_log_stream_terminal_error(
@@ -1051,6 +1362,7 @@ pub(super) async fn forward_stream_typed(
             request_ip,
             None,
             terminal_error,
+            None,
             req.reasoning.as_ref().and_then(|r| r.effort.clone()),
             tried_providers,
         );
@@ -1066,10 +1378,11 @@ pub(super) async fn forward_stream_typed(
             &final_err,
             req.reasoning.as_ref().and_then(|r| r.effort.clone()),
             tried_providers,
+            None,
         );
     }
     if let Some(session) = capture.session.as_ref() {
         session.persist_with_result(None, true).await;
     }
-    Ok(prestream_error_stream(downstream, final_err))
+    Ok((prestream_error_stream(downstream, final_err), None))
 }
@@ -7,8 +7,11 @@ use crate::monoize_routing::{
     CreateMonoizeChannelInput, CreateMonoizeProviderInput, MonoizeModelEntry, MonoizeProviderType,
 };
 use crate::settings::normalize_pricing_model_key;
+use crate::token_estimator::TokenEstimator;
 use crate::urp;
-use crate::users::{ModelRedirectRule, RequestCaptureMode, UserRole};
+use crate::users::{
+    ModelRedirectRule, RequestCaptureMode, UserRole, multiplier_to_nano, scale_nano_by_multiplier,
+};
 use axum::http::StatusCode;
 use serde_json::Value;
 use std::collections::{BTreeSet, HashMap};
@@ -103,11 +106,18 @@ fn build_test_auth_with_role(
         effective_groups,
         model_limits_enabled: false,
         model_limits: Vec::new(),
+        allow_models: Vec::new(),
+        deny_models: Vec::new(),
         ip_whitelist: Vec::new(),
         sub_account_enabled: false,
         sub_account_balance_nano: "0".to_string(),
         reasoning_envelope_enabled: true,
         request_capture_mode: RequestCaptureMode::Off,
+        response_cache_enabled: false,
+        max_request_charge_nano_usd: None,
+        request_metadata: None,
+        routing_override: None,
+        request_timeout_ms: None,
     }
 }
 
@@ -124,6 +134,7 @@ fn build_test_urp_request(model: &str) -> urp::UrpRequest {
         stream: Some(false),
         temperature: None,
         top_p: None,
+        top_k: None,
         max_output_tokens: None,
         reasoning: None,
         tools: None,
@@ -133,6 +144,11 @@ fn build_test_urp_request(model: &str) -> urp::UrpRequest {
         verbosity: None,
         response_format: None,
         user: None,
+        service_tier: None,
+        include: None,
+        logprobs: None,
+        top_logprobs: None,
+        previous_response_id: None,
         extra_body: HashMap::new(),
     }
 }
@@ -248,6 +264,7 @@ fn strip_orphaned_tool_calls_keeps_only_closed_stateless_pairs() {
         stream: None,
         temperature: None,
         top_p: None,
+        top_k: None,
         max_output_tokens: None,
         reasoning: None,
         tools: None,
@@ -257,6 +274,11 @@ fn strip_orphaned_tool_calls_keeps_only_closed_stateless_pairs() {
         verbosity: None,
         response_format: None,
         user: None,
+        service_tier: None,
+        include: None,
+        logprobs: None,
+        top_logprobs: None,
+        previous_response_id: None,
         extra_body: HashMap::new(),
     };
 
@@ -332,6 +354,7 @@ fn responses_tool_replay_preserves_plaintext_raw_cot_reasoning() {
         stream: None,
         temperature: None,
         top_p: None,
+        top_k: None,
         max_output_tokens: None,
         reasoning: None,
         tools: None,
@@ -341,6 +364,11 @@ fn responses_tool_replay_preserves_plaintext_raw_cot_reasoning() {
         verbosity: None,
         response_format: None,
         user: None,
+        service_tier: None,
+        include: None,
+        logprobs: None,
+        top_logprobs: None,
+        previous_response_id: None,
         extra_body: HashMap::new(),
     };
 
@@ -389,6 +417,9 @@ async fn seed_group_routing_provider(
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(0),
             groups,
@@ -411,6 +442,8 @@ async fn seed_model_pricing(state: &AppState, model: &str) {
                 cache_read_input_cost_per_token_nano: None,
                 cache_creation_input_cost_per_token_nano: None,
                 output_cost_per_reasoning_token_nano: None,
+                image_cost_per_image_nano: None,
+                tts_cost_per_char_nano: None,
                 max_input_tokens: None,
                 max_output_tokens: None,
                 max_tokens: None,
@@ -426,6 +459,7 @@ async fn routing_uses_channel_model_multiplier_and_redirect_per_attempt() {
         listen: "127.0.0.1:0".to_string(),
         metrics_path: "/metrics".to_string(),
         database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
     };
     let state = load_state_with_runtime(runtime).await.expect("state loads");
     seed_model_pricing(&state, "channel-owned-model").await;
@@ -436,7 +470,12 @@ async fn routing_uses_channel_model_multiplier_and_redirect_per_attempt() {
         provider_type: MonoizeProviderType::Responses,
         base_url: format!("https://{id}.example.com"),
         api_key: Some("secret".to_string()),
+        api_keys: None,
         weight: 1,
+        priority: 0,
+        max_concurrency: None,
+        org_id: None,
+        project_id: None,
         enabled: true,
         passive_failure_count_threshold_override: None,
         passive_cooldown_seconds_override: None,
@@ -447,6 +486,7 @@ async fn routing_uses_channel_model_multiplier_and_redirect_per_attempt() {
             MonoizeModelEntry {
                 redirect: Some(redirect.to_string()),
                 multiplier,
+                supports_streaming: None,
             },
         )]),
         active_probe_enabled_override: None,
@@ -477,6 +517,9 @@ async fn routing_uses_channel_model_multiplier_and_redirect_per_attempt() {
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             groups: Vec::new(),
             enabled: true,
             priority: Some(0),
@@ -496,6 +539,105 @@ async fn routing_uses_channel_model_multiplier_and_redirect_per_attempt() {
     assert_eq!(attempts[0].model_multiplier, 1.0);
 }
 
+#[tokio::test]
+async fn routing_rotates_channel_api_keys_and_skips_rejected_keys() {
+    let runtime = RuntimeConfig {
+        listen: "127.0.0.1:0".to_string(),
+        metrics_path: "/metrics".to_string(),
+        database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
+    };
+    let state = load_state_with_runtime(runtime).await.expect("state loads");
+    seed_model_pricing(&state, "rotated-model").await;
+    seed_group_routing_provider(
+        &state,
+        "rotated",
+        true,
+        Vec::new(),
+        vec![CreateMonoizeChannelInput {
+            id: Some("rotated".to_string()),
+            name: "rotated".to_string(),
+            provider_type: MonoizeProviderType::Responses,
+            base_url: "https://rotated.example.com".to_string(),
+            api_key: Some("key-a".to_string()),
+            api_keys: Some(vec![
+                "key-b".to_string(),
+                " key-c ".to_string(),
+                "key-a".to_string(),
+                String::new(),
+            ]),
+            weight: 1,
+            priority: 0,
+            max_concurrency: None,
+            org_id: None,
+            project_id: None,
+            enabled: true,
+            passive_failure_count_threshold_override: None,
+            passive_cooldown_seconds_override: None,
+            passive_window_seconds_override: None,
+            passive_rate_limit_cooldown_seconds_override: None,
+            models: std::collections::HashMap::from([(
+                "rotated-model".to_string(),
+                MonoizeModelEntry {
+                    redirect: None,
+                    multiplier: 1.0,
+                    supports_streaming: None,
+                },
+            )]),
+            active_probe_enabled_override: None,
+            active_probe_interval_seconds_override: None,
+            active_probe_success_threshold_override: None,
+            active_probe_model_override: None,
+        }],
+    )
+    .await;
+
+    async fn route(state: &AppState) -> MonoizeAttempt {
+        build_monoize_attempts(
+            state,
+            &build_test_routing_request("rotated-model"),
+            &build_test_auth(None),
+        )
+        .await
+        .expect("routing succeeds")
+        .remove(0)
+    }
+    // Routing alone never advances the rotation; only sent attempts do.
+    for _ in 0..3 {
+        assert_eq!(route(&state).await.api_key_pool.len(), 3);
+    }
+    let routed = route(&state).await;
+    let mut keys = Vec::new();
+    for _ in 0..4 {
+        keys.push(next_channel_key(&state, &routed).await.expect("pool key"));
+    }
+    assert_eq!(keys, ["key-a", "key-b", "key-c", "key-a"]);
+
+    let rejected = MonoizeAttempt {
+        api_key: next_channel_key(&state, &routed).await.expect("pool key"),
+        ..routed.clone()
+    };
+    assert_eq!(rejected.api_key, "key-b");
+    assert!(
+        suspend_rejected_channel_key(
+            &state,
+            &rejected,
+            &UpstreamCallError::new(
+                UpstreamErrorKind::Http,
+                Some(StatusCode::UNAUTHORIZED),
+                "invalid api key".to_string(),
+            ),
+        )
+        .await
+    );
+    let mut keys = Vec::new();
+    for _ in 0..4 {
+        keys.push(next_channel_key(&state, &routed).await.expect("pool key"));
+    }
+    assert_eq!(keys, ["key-c", "key-a", "key-c", "key-a"]);
+    assert!(is_attempt_channel_healthy(&state, &rejected).await);
+}
+
 fn attempt_channel_ids(attempts: &[MonoizeAttempt]) -> BTreeSet<&str> {
     attempts
         .iter()
@@ -503,6 +645,215 @@ fn attempt_channel_ids(attempts: &[MonoizeAttempt]) -> BTreeSet<&str> {
         .collect()
 }
 
+fn shuffle_test_channel(
+    id: &str,
+    priority: i32,
+    weight: i32,
+) -> crate::monoize_routing::MonoizeChannel {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "name": id,
+        "provider_type": "responses",
+        "base_url": "https://example.com",
+        "api_key": "secret",
+        "weight": weight,
+        "priority": priority
+    }))
+    .expect("channel")
+}
+
+#[test]
+fn weighted_shuffle_orders_channels_by_priority_tier() {
+    for _ in 0..20 {
+        let ordered = weighted_shuffle_channels(
+            vec![
+                shuffle_test_channel("backup-a", 1, 100),
+                shuffle_test_channel("primary-a", 0, 1),
+                shuffle_test_channel("backup-b", 1, 100),
+                shuffle_test_channel("primary-b", 0, 1),
+            ],
+            None,
+        );
+        let priorities: Vec<i32> = ordered.iter().map(|channel| channel.priority).collect();
+        assert_eq!(priorities, vec![0, 0, 1, 1]);
+    }
+}
+
+#[test]
+fn weighted_shuffle_picks_first_channel_in_proportion_to_weight() {
+    use rand::{RngCore, SeedableRng};
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0x5eed);
+    let mut next = || rng.next_u64();
+    let iterations = 20_000;
+    let mut first_counts: HashMap<String, usize> = HashMap::new();
+    for _ in 0..iterations {
+        let ordered = weighted_shuffle_channels_with(
+            vec![
+                shuffle_test_channel("light", 0, 1),
+                shuffle_test_channel("medium", 0, 3),
+                shuffle_test_channel("heavy", 0, 6),
+            ],
+            None,
+            &mut next,
+        );
+        assert_eq!(ordered.len(), 3);
+        *first_counts.entry(ordered[0].id.clone()).or_default() += 1;
+    }
+    for (id, expected) in [("light", 0.1), ("medium", 0.3), ("heavy", 0.6)] {
+        let observed = first_counts.get(id).copied().unwrap_or(0) as f64 / iterations as f64;
+        assert!(
+            (observed - expected).abs() < 0.02,
+            "{id}: observed {observed}, expected {expected}"
+        );
+    }
+}
+
+#[test]
+fn weighted_shuffle_second_pick_follows_remaining_weights() {
+    use rand::{RngCore, SeedableRng};
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    let mut next = || rng.next_u64();
+    let iterations = 20_000;
+    let mut heavy_first = 0usize;
+    let mut light_second_after_heavy = 0usize;
+    for _ in 0..iterations {
+        let ordered = weighted_shuffle_channels_with(
+            vec![
+                shuffle_test_channel("light", 0, 1),
+                shuffle_test_channel("medium", 0, 3),
+                shuffle_test_channel("heavy", 0, 6),
+            ],
+            None,
+            &mut next,
+        );
+        if ordered[0].id == "heavy" {
+            heavy_first += 1;
+            if ordered[1].id == "light" {
+                light_second_after_heavy += 1;
+            }
+        }
+    }
+    // Once `heavy` is taken, `light` competes with weight 1 against `medium`'s 3.
+    let observed = light_second_after_heavy as f64 / heavy_first as f64;
+    assert!((observed - 0.25).abs() < 0.03, "observed {observed}");
+}
+
+#[test]
+fn failure_weighted_shuffle_picks_a_failing_channel_less_often() {
+    use rand::{RngCore, SeedableRng};
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0xfa11);
+    let mut next = || rng.next_u64();
+    let failure_counts =
+        HashMap::from([("healthy".to_string(), 0u32), ("failing".to_string(), 3u32)]);
+    let iterations = 20_000;
+    let mut failing_first = 0usize;
+    for _ in 0..iterations {
+        let ordered = weighted_shuffle_channels_with(
+            vec![
+                shuffle_test_channel("healthy", 0, 5),
+                shuffle_test_channel("failing", 0, 5),
+            ],
+            Some(&failure_counts),
+            &mut next,
+        );
+        if ordered[0].id == "failing" {
+            failing_first += 1;
+        }
+    }
+    // Effective weights are 5 and 5 / (1 + 3), so `failing` leads 1/5 of the time.
+    let observed = failing_first as f64 / iterations as f64;
+    assert!((observed - 0.2).abs() < 0.02, "observed {observed}");
+}
+
+#[test]
+fn weighted_shuffle_is_deterministic_for_a_seeded_source() {
+    use rand::{RngCore, SeedableRng};
+
+    let order = |seed: u64| {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        weighted_shuffle_channels_with(
+            (0..8)
+                .map(|i| shuffle_test_channel(&format!("ch-{i}"), i % 2, i + 1))
+                .collect(),
+            None,
+            &mut || rng.next_u64(),
+        )
+        .into_iter()
+        .map(|channel| channel.id)
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(order(99), order(99));
+}
+
+#[test]
+fn usage_breakdown_marks_estimated_stream_usage() {
+    let output = vec![
+        urp::Node::Text {
+            id: None,
+            role: urp::OrdinaryRole::Assistant,
+            content: "hello world!".to_string(),
+            phase: None,
+            extra_body: HashMap::new(),
+        },
+        urp::Node::Reasoning {
+            id: None,
+            content: Some("why".to_string()),
+            encrypted: None,
+            summary: None,
+            source: None,
+            extra_body: HashMap::new(),
+        },
+    ];
+    assert_eq!(
+        crate::token_estimator::HeuristicEstimator.estimate(&output, "gpt-5-mini"),
+        4
+    );
+
+    let estimated = urp::Usage {
+        output_tokens: 4,
+        extra_body: HashMap::from([("estimated".to_string(), Value::Bool(true))]),
+        ..urp::Usage::default()
+    };
+    assert_eq!(
+        build_usage_breakdown(&estimated)["estimated"],
+        Value::Bool(true)
+    );
+
+    let reported = urp::Usage {
+        output_tokens: 4,
+        ..urp::Usage::default()
+    };
+    assert!(build_usage_breakdown(&reported).get("estimated").is_none());
+}
+
+#[test]
+fn gemini_stream_usage_estimate_is_off_by_default() {
+    let mut runtime = crate::monoize_routing::MonoizeRuntimeConfig::default();
+    assert!(runtime.enable_estimated_billing);
+    assert!(!usage::stream_usage_estimate_enabled(
+        &runtime,
+        ProviderType::Gemini
+    ));
+    assert!(usage::stream_usage_estimate_enabled(
+        &runtime,
+        ProviderType::ChatCompletion
+    ));
+
+    runtime.estimate_gemini_stream_usage = true;
+    assert!(usage::stream_usage_estimate_enabled(
+        &runtime,
+        ProviderType::Gemini
+    ));
+    runtime.enable_estimated_billing = false;
+    assert!(!usage::stream_usage_estimate_enabled(
+        &runtime,
+        ProviderType::Gemini
+    ));
+}
+
 #[test]
 fn calculate_charge_nano_uses_model_price_and_multiplier() {
     let usage = urp::Usage {
@@ -518,6 +869,8 @@ fn calculate_charge_nano_uses_model_price_and_multiplier() {
         cache_read_input_cost_per_token_nano: None,
         cache_creation_input_cost_per_token_nano: None,
         output_cost_per_reasoning_token_nano: None,
+        image_cost_per_image_nano: None,
+        tts_cost_per_char_nano: None,
     };
 
     let charged = calculate_charge_nano(&usage, &pricing, 1.234_567_891);
@@ -555,6 +908,8 @@ fn calculate_charge_nano_handles_cached_and_reasoning_tokens() {
         cache_read_input_cost_per_token_nano: Some(100),
         cache_creation_input_cost_per_token_nano: None,
         output_cost_per_reasoning_token_nano: Some(3000),
+        image_cost_per_image_nano: None,
+        tts_cost_per_char_nano: None,
     };
 
     let charged = calculate_charge_nano(&usage, &pricing, 1.0);
@@ -589,6 +944,8 @@ fn calculate_charge_nano_messages_treats_cache_creation_as_disjoint_bucket() {
         cache_read_input_cost_per_token_nano: None,
         cache_creation_input_cost_per_token_nano: Some(250),
         output_cost_per_reasoning_token_nano: None,
+        image_cost_per_image_nano: None,
+        tts_cost_per_char_nano: None,
     };
 
     let charged = calculate_charge_nano(&usage, &pricing, 1.0);
@@ -620,6 +977,8 @@ fn calculate_charge_nano_responses_excludes_cache_creation_from_inclusive_input_
         cache_read_input_cost_per_token_nano: Some(100),
         cache_creation_input_cost_per_token_nano: Some(250),
         output_cost_per_reasoning_token_nano: None,
+        image_cost_per_image_nano: None,
+        tts_cost_per_char_nano: None,
     };
 
     let charged = calculate_charge_nano(&usage, &pricing, 1.0);
@@ -652,6 +1011,8 @@ fn calculate_charge_nano_responses_avoids_double_count_when_cache_read_and_creat
         cache_read_input_cost_per_token_nano: Some(100),
         cache_creation_input_cost_per_token_nano: Some(250),
         output_cost_per_reasoning_token_nano: None,
+        image_cost_per_image_nano: None,
+        tts_cost_per_char_nano: None,
     };
 
     let charged = calculate_charge_nano(&usage, &pricing, 1.0);
@@ -712,14 +1073,62 @@ fn rate_matrix_selects_short_vs_long_context_tier() {
         extra_body: HashMap::new(),
     };
 
-    let components =
-        calculate_rate_matrix_charge_components(&usage, None, &resolution, 1.0, &Vec::new())
-            .expect("charge succeeds");
+    let components = calculate_rate_matrix_charge_components(
+        &usage,
+        None,
+        &resolution,
+        multiplier_to_nano(1.0),
+        &Vec::new(),
+    )
+    .expect("charge succeeds");
 
     assert_eq!(components.context_tier.as_deref(), Some("long"));
     assert_eq!(components.base_charge, 1_280_210);
 }
 
+#[test]
+fn free_tagged_priced_rates_skip_the_balance_gate_and_charge_zero() {
+    let mut input = test_rate(
+        "input",
+        "input_uncached",
+        5,
+        None,
+        None,
+        None,
+        serde_json::json!({}),
+    );
+    input.raw_json = serde_json::json!({ "free": true });
+    let mut output = test_rate(
+        "output",
+        "output",
+        7,
+        None,
+        None,
+        None,
+        serde_json::json!({}),
+    );
+    output.raw_json = serde_json::json!({ "free": true });
+    let resolution = test_resolution(vec![input, output]);
+    assert!(billing_rate_matrix_is_free(&resolution));
+
+    let usage = urp::Usage {
+        input_tokens: 100,
+        output_tokens: 10,
+        input_details: None,
+        output_details: None,
+        extra_body: HashMap::new(),
+    };
+    let components = calculate_rate_matrix_charge_components(
+        &usage,
+        None,
+        &resolution,
+        multiplier_to_nano(1.0),
+        &Vec::new(),
+    )
+    .expect("charge succeeds");
+    assert_eq!(components.base_charge, 0);
+}
+
 #[test]
 fn rate_matrix_bills_anthropic_cache_ttl_split_and_read() {
     let resolution = test_resolution(vec![
@@ -786,9 +1195,14 @@ fn rate_matrix_bills_anthropic_cache_ttl_split_and_read() {
         extra_body: HashMap::new(),
     };
 
-    let components =
-        calculate_rate_matrix_charge_components(&usage, None, &resolution, 1.0, &Vec::new())
-            .expect("charge succeeds");
+    let components = calculate_rate_matrix_charge_components(
+        &usage,
+        None,
+        &resolution,
+        multiplier_to_nano(1.0),
+        &Vec::new(),
+    )
+    .expect("charge succeeds");
 
     assert_eq!(components.base_charge, 1850);
     assert_eq!(components.token_line_items.len(), 5);
@@ -851,8 +1265,14 @@ fn rate_matrix_rejects_aggregate_cache_creation_without_ttl_split() {
         extra_body: HashMap::new(),
     };
 
-    let err = calculate_rate_matrix_charge_components(&usage, None, &resolution, 1.0, &Vec::new())
-        .expect_err("aggregate cache write must not be guessed");
+    let err = calculate_rate_matrix_charge_components(
+        &usage,
+        None,
+        &resolution,
+        multiplier_to_nano(1.0),
+        &Vec::new(),
+    )
+    .expect_err("aggregate cache write must not be guessed");
 
     assert!(err.contains("requires 5m/1h split"));
 }
@@ -947,9 +1367,14 @@ fn rate_matrix_bills_gpt_image_2_modality_token_lines() {
         extra_body: HashMap::new(),
     };
 
-    let components =
-        calculate_rate_matrix_charge_components(&usage, None, &resolution, 1.0, &Vec::new())
-            .expect("charge succeeds");
+    let components = calculate_rate_matrix_charge_components(
+        &usage,
+        None,
+        &resolution,
+        multiplier_to_nano(1.0),
+        &Vec::new(),
+    )
+    .expect("charge succeeds");
 
     assert_eq!(components.base_charge, 304);
     assert_eq!(components.token_line_items.len(), 5);
@@ -1003,9 +1428,14 @@ fn rate_matrix_supports_input_cached_usage_class_alias() {
         extra_body: HashMap::new(),
     };
 
-    let components =
-        calculate_rate_matrix_charge_components(&usage, None, &resolution, 1.0, &Vec::new())
-            .expect("charge succeeds");
+    let components = calculate_rate_matrix_charge_components(
+        &usage,
+        None,
+        &resolution,
+        multiplier_to_nano(1.0),
+        &Vec::new(),
+    )
+    .expect("charge succeeds");
 
     assert_eq!(components.base_charge, 170);
     assert_eq!(
@@ -1057,7 +1487,7 @@ fn rate_matrix_counts_call_meter_from_decoded_native_events_and_requires_duratio
         &usage,
         Some(&output),
         &call_resolution,
-        1.0,
+        multiplier_to_nano(1.0),
         &["web_search".to_string()],
     )
     .expect("decoded call is billable");
@@ -1095,7 +1525,7 @@ fn rate_matrix_counts_call_meter_from_decoded_native_events_and_requires_duratio
         &usage,
         None,
         &duration_resolution,
-        1.0,
+        multiplier_to_nano(1.0),
         &["code_interpreter_duration".to_string()],
     )
     .expect_err("duration meter must require authoritative usage");
@@ -1103,13 +1533,277 @@ fn rate_matrix_counts_call_meter_from_decoded_native_events_and_requires_duratio
     assert!(err.contains("authoritative usage required"));
 }
 
+#[test]
+fn request_charge_cap_applies_only_above_the_effective_limit() {
+    assert_eq!(exceeded_request_charge_cap(1_000, None, 1_000), None);
+    assert_eq!(exceeded_request_charge_cap(1_001, None, 1_000), Some(1_000));
+    assert_eq!(exceeded_request_charge_cap(i128::MAX, None, 0), None);
+
+    assert_eq!(exceeded_request_charge_cap(500, Some(500), 100), None);
+    assert_eq!(exceeded_request_charge_cap(501, Some(500), 100), Some(500));
+    assert_eq!(exceeded_request_charge_cap(501, Some(0), 100), Some(100));
+}
+
+#[test]
+fn worst_case_usage_scales_output_budget_by_requested_choices() {
+    let mut req = build_test_urp_request("gpt-5-mini");
+    let estimator = &crate::token_estimator::HeuristicEstimator;
+    assert!(worst_case_usage(&req, estimator, "gpt-5-mini").is_none());
+
+    req.max_output_tokens = Some(4_000);
+    let single = worst_case_usage(&req, estimator, "gpt-5-mini").expect("budgeted request");
+    assert_eq!((single.input_tokens, single.output_tokens), (2, 4_000));
+
+    req.extra_body.insert("n".to_string(), json!(5));
+    let multi = worst_case_usage(&req, estimator, "gpt-5-mini").expect("budgeted request");
+    assert_eq!((multi.input_tokens, multi.output_tokens), (2, 20_000));
+}
+
+#[test]
+fn model_alias_resolves_simple_and_chained_aliases() {
+    let aliases = HashMap::from([
+        ("smart".to_string(), "gpt-4o".to_string()),
+        ("default".to_string(), "smart".to_string()),
+    ]);
+    assert_eq!(
+        resolve_alias_chain(&aliases, "smart").as_deref(),
+        Some("gpt-4o")
+    );
+    assert_eq!(
+        resolve_alias_chain(&aliases, "default").as_deref(),
+        Some("gpt-4o")
+    );
+    assert_eq!(resolve_alias_chain(&aliases, "gpt-4o"), None);
+}
+
+#[test]
+fn model_alias_cycle_is_left_unresolved() {
+    let aliases = HashMap::from([
+        ("fast".to_string(), "quick".to_string()),
+        ("quick".to_string(), "fast".to_string()),
+        ("self".to_string(), "self".to_string()),
+    ]);
+    assert_eq!(resolve_alias_chain(&aliases, "fast"), None);
+    assert_eq!(resolve_alias_chain(&aliases, "self"), None);
+}
+
+#[test]
+fn request_metadata_header_accepts_only_flat_string_maps() {
+    let parse = |raw: &str| {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_METADATA_HEADER, raw.parse().unwrap());
+        extract_request_metadata(&headers)
+    };
+    assert_eq!(
+        parse(r#"{"trace_id":"t-1"}"#),
+        Some(serde_json::json!({ "trace_id": "t-1" }))
+    );
+    assert_eq!(parse(r#"{"nested":{"a":"b"}}"#), None);
+    assert_eq!(parse(r#"{"count":1}"#), None);
+    assert_eq!(parse(r#"["trace"]"#), None);
+    assert_eq!(parse("{}"), None);
+    assert_eq!(parse("not json"), None);
+    let oversized = format!(r#"{{"k":"{}"}}"#, "x".repeat(MAX_REQUEST_METADATA_BYTES));
+    assert_eq!(parse(&oversized), None);
+    assert_eq!(extract_request_metadata(&HeaderMap::new()), None);
+}
+
+#[test]
+fn request_timeout_header_is_clamped_and_ignores_invalid_values() {
+    let parse = |raw: &str| {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_TIMEOUT_HEADER, raw.parse().unwrap());
+        extract_request_timeout_override(&headers, 10_000)
+    };
+    assert_eq!(parse("2500"), Some(2_500));
+    assert_eq!(parse(" 2500 "), Some(2_500));
+    assert_eq!(parse("60000"), Some(10_000));
+    assert_eq!(parse("0"), None);
+    assert_eq!(parse("-5"), None);
+    assert_eq!(parse("1.5"), None);
+    assert_eq!(parse("soon"), None);
+    assert_eq!(
+        extract_request_timeout_override(&HeaderMap::new(), 10_000),
+        None
+    );
+}
+
+#[test]
+fn rate_matrix_bills_per_image_meter_by_generated_image_count() {
+    let resolution = test_resolution(vec![test_meter_rate(
+        "image",
+        "image_generation",
+        "image",
+        40_000_000,
+        serde_json::json!({}),
+    )]);
+    assert!(
+        billing_rate_matrix_allows_request(&resolution, &[]).expect("matrix is valid"),
+        "image meter alone must make the model billable"
+    );
+
+    let image = urp::Node::Image {
+        id: None,
+        role: urp::OrdinaryRole::Assistant,
+        source: urp::ImageSource::Base64 {
+            media_type: "image/png".to_string(),
+            data: "AAAA".to_string(),
+        },
+        extra_body: HashMap::new(),
+    };
+    let output = vec![image.clone(), image.clone(), image];
+    let components = calculate_rate_matrix_charge_components(
+        &urp::Usage::default(),
+        Some(&output),
+        &resolution,
+        multiplier_to_nano(1.5),
+        &[],
+    )
+    .expect("per-image matrix is billable without token usage");
+
+    assert!(components.token_line_items.is_empty());
+    assert_eq!(components.meter_line_items.len(), 1);
+    assert_eq!(components.meter_line_items[0]["quantity"], 3);
+    assert_eq!(components.base_charge, 120_000_000);
+    assert_eq!(components.final_charge, 180_000_000);
+}
+
 #[test]
 fn scale_charge_quantizes_multiplier_to_nano_precision() {
     let base = 1_000_000_000i128;
-    let charged = scale_charge_with_multiplier(base, 1.000_000_000_9);
+    let multiplier_nano = multiplier_to_nano(1.000_000_000_9).expect("multiplier");
+    let charged = scale_nano_by_multiplier(base, multiplier_nano);
     assert_eq!(charged, Some(1_000_000_000));
 }
 
+#[test]
+fn probe_charge_scales_by_multiplier_and_falls_back_to_base() {
+    use crate::app::probe_charge_nano;
+
+    assert_eq!(probe_charge_nano(1_000_000_000, 1.5), 1_500_000_000);
+    assert_eq!(probe_charge_nano(1_000_000_000, 0.0), 0);
+    assert_eq!(probe_charge_nano(1_000_000_000, -1.0), 1_000_000_000);
+    assert_eq!(probe_charge_nano(1_000_000_000, f64::NAN), 1_000_000_000);
+    assert_eq!(probe_charge_nano(i128::MAX, 2.0), i128::MAX);
+}
+
+/// The pre-fixed-point implementation, kept as the reference the integer
+/// conversion must reproduce bit-for-bit.
+fn legacy_scale_charge_with_multiplier(base_nano: i128, provider_multiplier: f64) -> Option<i128> {
+    if !provider_multiplier.is_finite() || provider_multiplier < 0.0 {
+        return None;
+    }
+    const SCALE: i128 = 1_000_000_000;
+    let multiplier_repr = format!("{provider_multiplier:.18}");
+    let mut parts = multiplier_repr.split('.');
+    let whole = parts.next().unwrap_or("0").parse::<i128>().ok()?;
+    let frac_raw = parts.next().unwrap_or("0");
+    let mut frac_nano = String::with_capacity(9);
+    for ch in frac_raw.chars().take(9) {
+        frac_nano.push(ch);
+    }
+    while frac_nano.len() < 9 {
+        frac_nano.push('0');
+    }
+    let frac = frac_nano.parse::<i128>().ok()?;
+    let multiplier_nano = whole.checked_mul(SCALE)?.checked_add(frac)?;
+    base_nano.checked_mul(multiplier_nano)?.checked_div(SCALE)
+}
+
+fn fixed_point_scale_charge(base_nano: i128, multiplier: f64) -> Option<i128> {
+    scale_nano_by_multiplier(base_nano, multiplier_to_nano(multiplier)?)
+}
+
+#[test]
+fn multiplier_to_nano_matches_legacy_on_float_noise_and_edge_values() {
+    let cases = [
+        0.0,
+        -0.0,
+        1e-10,
+        1e-9,
+        f64::MIN_POSITIVE,
+        5e-324,
+        0.1,
+        0.29,
+        0.3,
+        0.7,
+        1.0,
+        1.1,
+        1.15,
+        1.2,
+        1.234_567_891,
+        1.000_000_000_5,
+        1.000_000_000_9,
+        1.999_999_999_5,
+        2.5,
+        3.3,
+        99.999_999_999,
+        123_456.789_012_345,
+        1e6,
+    ];
+    for multiplier in cases {
+        assert_eq!(
+            multiplier_to_nano(multiplier),
+            legacy_scale_charge_with_multiplier(1_000_000_000, multiplier),
+            "multiplier {multiplier:e}"
+        );
+    }
+    for invalid in [-1.0, -1e-12, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert_eq!(multiplier_to_nano(invalid), None, "{invalid}");
+        assert_eq!(legacy_scale_charge_with_multiplier(1, invalid), None);
+    }
+}
+
+#[test]
+fn fixed_point_charge_scaling_matches_legacy_for_random_inputs() {
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0x00b1_11ed);
+    for _ in 0..20_000 {
+        // Configured multipliers are short decimals, uniform floats, or raw
+        // bit patterns; all stay within the range validate_models accepts in practice.
+        let multiplier = match rng.gen_range(0..3) {
+            0 => {
+                let digits = rng.gen_range(0..=12);
+                rng.gen_range(0u64..10u64.pow(digits + 3)) as f64 / 10f64.powi(digits as i32)
+            }
+            1 => rng.gen_range(0.0..1_000.0),
+            _ => f64::from_bits(rng.gen_range(0..=1e6f64.to_bits())),
+        };
+        let tokens = i128::from(rng.gen_range(0u64..=10_000_000));
+        let rate = i128::from(rng.gen_range(0u64..=2_000_000));
+        let base = tokens * rate;
+        assert_eq!(
+            fixed_point_scale_charge(base, multiplier),
+            legacy_scale_charge_with_multiplier(base, multiplier),
+            "base {base} multiplier {multiplier:e} ({:#x})",
+            multiplier.to_bits()
+        );
+    }
+}
+
+#[test]
+fn fixed_point_charge_scaling_matches_legacy_near_nano_boundaries() {
+    use rand::{Rng, SeedableRng};
+
+    // Multipliers one or two ulps around an exact nano value are where a
+    // float multiply-and-truncate would disagree with the decimal rounding.
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0x0b0a_4d42);
+    for _ in 0..20_000 {
+        let nano = rng.gen_range(0u64..=100_000_000_000);
+        let center = nano as f64 / 1e9;
+        let offset = rng.gen_range(-2i64..=2);
+        let multiplier = f64::from_bits((center.to_bits() as i64 + offset).max(0) as u64);
+        let base = i128::from(rng.gen_range(0u64..=u64::from(u32::MAX)));
+        assert_eq!(
+            fixed_point_scale_charge(base, multiplier),
+            legacy_scale_charge_with_multiplier(base, multiplier),
+            "base {base} multiplier {multiplier:e} ({:#x})",
+            multiplier.to_bits()
+        );
+    }
+}
+
 #[test]
 fn normalize_pricing_model_key_strips_recognized_reasoning_suffix() {
     let suffix_map = std::collections::HashMap::from([
@@ -1133,6 +1827,7 @@ async fn resolve_model_suffix_preserves_reasoning_effort_on_attempt_base_request
         listen: "127.0.0.1:0".to_string(),
         metrics_path: "/metrics".to_string(),
         database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
     };
     let state = load_state_with_runtime(runtime).await.expect("state loads");
 
@@ -1154,6 +1849,9 @@ async fn resolve_model_suffix_preserves_reasoning_effort_on_attempt_base_request
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             groups: Vec::new(),
             enabled: true,
             priority: Some(0),
@@ -1163,8 +1861,13 @@ async fn resolve_model_suffix_preserves_reasoning_effort_on_attempt_base_request
                 provider_type: MonoizeProviderType::Responses,
                 base_url: "https://example.com".to_string(),
                 api_key: Some("secret".to_string()),
+                api_keys: None,
                 enabled: true,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
                 passive_window_seconds_override: None,
@@ -1174,6 +1877,7 @@ async fn resolve_model_suffix_preserves_reasoning_effort_on_attempt_base_request
                     MonoizeModelEntry {
                         redirect: None,
                         multiplier: 1.0,
+                        supports_streaming: None,
                     },
                 )]),
                 active_probe_enabled_override: None,
@@ -1204,6 +1908,7 @@ fn resolve_upstream_model_prefers_non_empty_redirect() {
     let entry = MonoizeModelEntry {
         redirect: Some("  gpt-5-target  ".to_string()),
         multiplier: 1.0,
+        supports_streaming: None,
     };
     assert_eq!(
         resolve_upstream_model("gpt-5-logical", &entry),
@@ -1216,6 +1921,7 @@ fn resolve_upstream_model_falls_back_to_requested_when_redirect_blank() {
     let entry = MonoizeModelEntry {
         redirect: Some("   ".to_string()),
         multiplier: 1.0,
+        supports_streaming: None,
     };
     assert_eq!(
         resolve_upstream_model("gpt-5-logical", &entry),
@@ -1229,6 +1935,7 @@ async fn build_monoize_attempts_rejects_unpriced_models_before_forwarding() {
         listen: "127.0.0.1:0".to_string(),
         metrics_path: "/metrics".to_string(),
         database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
     };
     let state = load_state_with_runtime(runtime).await.expect("state loads");
 
@@ -1250,6 +1957,9 @@ async fn build_monoize_attempts_rejects_unpriced_models_before_forwarding() {
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             groups: Vec::new(),
             enabled: true,
             priority: Some(0),
@@ -1259,8 +1969,13 @@ async fn build_monoize_attempts_rejects_unpriced_models_before_forwarding() {
                 provider_type: MonoizeProviderType::Responses,
                 base_url: "https://example.com".to_string(),
                 api_key: Some("secret".to_string()),
+                api_keys: None,
                 enabled: true,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
                 passive_window_seconds_override: None,
@@ -1270,6 +1985,7 @@ async fn build_monoize_attempts_rejects_unpriced_models_before_forwarding() {
                     MonoizeModelEntry {
                         redirect: Some("gpt-unpriced-upstream".to_string()),
                         multiplier: 1.0,
+                        supports_streaming: None,
                     },
                 )]),
                 active_probe_enabled_override: None,
@@ -1287,8 +2003,8 @@ async fn build_monoize_attempts_rejects_unpriced_models_before_forwarding() {
         .await
         .expect_err("must reject unpriced model");
 
-    assert_eq!(err.status, StatusCode::FORBIDDEN);
-    assert_eq!(err.code, "model_pricing_required");
+    assert_eq!(err.status, StatusCode::PAYMENT_REQUIRED);
+    assert_eq!(err.code, "pricing_unavailable");
     assert!(err.message.contains("gpt-unpriced-upstream"));
 }
 
@@ -1298,6 +2014,7 @@ async fn build_monoize_attempts_rejects_admin_unpriced_models_without_pricing()
         listen: "127.0.0.1:0".to_string(),
         metrics_path: "/metrics".to_string(),
         database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
     };
     let state = load_state_with_runtime(runtime).await.expect("state loads");
 
@@ -1319,6 +2036,9 @@ async fn build_monoize_attempts_rejects_admin_unpriced_models_without_pricing()
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             groups: Vec::new(),
             enabled: true,
             priority: Some(0),
@@ -1328,8 +2048,13 @@ async fn build_monoize_attempts_rejects_admin_unpriced_models_without_pricing()
                 provider_type: MonoizeProviderType::Responses,
                 base_url: "https://example.com".to_string(),
                 api_key: Some("secret".to_string()),
+                api_keys: None,
                 enabled: true,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
                 passive_window_seconds_override: None,
@@ -1339,6 +2064,7 @@ async fn build_monoize_attempts_rejects_admin_unpriced_models_without_pricing()
                     MonoizeModelEntry {
                         redirect: Some("gpt-unpriced-upstream".to_string()),
                         multiplier: 1.0,
+                        supports_streaming: None,
                     },
                 )]),
                 active_probe_enabled_override: None,
@@ -1356,8 +2082,8 @@ async fn build_monoize_attempts_rejects_admin_unpriced_models_without_pricing()
         .await
         .expect_err("admin unpriced request must be rejected");
 
-    assert_eq!(err.status, StatusCode::FORBIDDEN);
-    assert_eq!(err.code, "model_pricing_required");
+    assert_eq!(err.status, StatusCode::PAYMENT_REQUIRED);
+    assert_eq!(err.code, "pricing_unavailable");
 }
 
 #[tokio::test]
@@ -1366,6 +2092,7 @@ async fn build_monoize_attempts_rejects_admin_missing_server_tool_meter_rate() {
         listen: "127.0.0.1:0".to_string(),
         metrics_path: "/metrics".to_string(),
         database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
     };
     let state = load_state_with_runtime(runtime).await.expect("state loads");
 
@@ -1387,6 +2114,9 @@ async fn build_monoize_attempts_rejects_admin_missing_server_tool_meter_rate() {
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             groups: Vec::new(),
             enabled: true,
             priority: Some(0),
@@ -1396,8 +2126,13 @@ async fn build_monoize_attempts_rejects_admin_missing_server_tool_meter_rate() {
                 provider_type: MonoizeProviderType::Responses,
                 base_url: "https://example.com".to_string(),
                 api_key: Some("secret".to_string()),
+                api_keys: None,
                 enabled: true,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
                 passive_window_seconds_override: None,
@@ -1407,6 +2142,7 @@ async fn build_monoize_attempts_rejects_admin_missing_server_tool_meter_rate() {
                     MonoizeModelEntry {
                         redirect: None,
                         multiplier: 1.0,
+                        supports_streaming: None,
                     },
                 )]),
                 active_probe_enabled_override: None,
@@ -1426,8 +2162,8 @@ async fn build_monoize_attempts_rejects_admin_missing_server_tool_meter_rate() {
         .await
         .expect_err("missing meter rate must reject admin");
 
-    assert_eq!(err.status, StatusCode::FORBIDDEN);
-    assert_eq!(err.code, "model_pricing_required");
+    assert_eq!(err.status, StatusCode::PAYMENT_REQUIRED);
+    assert_eq!(err.code, "pricing_unavailable");
     assert!(err.message.contains("meter rate required"));
 }
 
@@ -1437,6 +2173,7 @@ async fn build_monoize_attempts_accepts_redirected_model_when_logical_fallback_i
         listen: "127.0.0.1:0".to_string(),
         metrics_path: "/metrics".to_string(),
         database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
     };
     let state = load_state_with_runtime(runtime).await.expect("state loads");
 
@@ -1458,6 +2195,9 @@ async fn build_monoize_attempts_accepts_redirected_model_when_logical_fallback_i
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             groups: Vec::new(),
             enabled: true,
             priority: Some(0),
@@ -1467,8 +2207,13 @@ async fn build_monoize_attempts_accepts_redirected_model_when_logical_fallback_i
                 provider_type: MonoizeProviderType::Responses,
                 base_url: "https://example.com".to_string(),
                 api_key: Some("secret".to_string()),
+                api_keys: None,
                 enabled: true,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
                 passive_window_seconds_override: None,
@@ -1478,6 +2223,7 @@ async fn build_monoize_attempts_accepts_redirected_model_when_logical_fallback_i
                     MonoizeModelEntry {
                         redirect: Some("gpt-fallback-dest".to_string()),
                         multiplier: 1.0,
+                        supports_streaming: None,
                     },
                 )]),
                 active_probe_enabled_override: None,
@@ -1501,6 +2247,8 @@ async fn build_monoize_attempts_accepts_redirected_model_when_logical_fallback_i
                 cache_read_input_cost_per_token_nano: None,
                 cache_creation_input_cost_per_token_nano: None,
                 output_cost_per_reasoning_token_nano: None,
+                image_cost_per_image_nano: None,
+                tts_cost_per_char_nano: None,
                 max_input_tokens: None,
                 max_output_tokens: None,
                 max_tokens: None,
@@ -1521,6 +2269,8 @@ async fn build_monoize_attempts_accepts_redirected_model_when_logical_fallback_i
                 cache_read_input_cost_per_token_nano: None,
                 cache_creation_input_cost_per_token_nano: None,
                 output_cost_per_reasoning_token_nano: None,
+                image_cost_per_image_nano: None,
+                tts_cost_per_char_nano: None,
                 max_input_tokens: None,
                 max_output_tokens: None,
                 max_tokens: None,
@@ -1556,6 +2306,7 @@ async fn build_monoize_attempts_uses_metadata_pricing_profile_fallback() {
         listen: "127.0.0.1:0".to_string(),
         metrics_path: "/metrics".to_string(),
         database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
     };
     let state = load_state_with_runtime(runtime).await.expect("state loads");
 
@@ -1577,6 +2328,9 @@ async fn build_monoize_attempts_uses_metadata_pricing_profile_fallback() {
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             groups: Vec::new(),
             enabled: true,
             priority: Some(0),
@@ -1586,8 +2340,13 @@ async fn build_monoize_attempts_uses_metadata_pricing_profile_fallback() {
                 provider_type: MonoizeProviderType::ChatCompletion,
                 base_url: "https://example.com".to_string(),
                 api_key: Some("secret".to_string()),
+                api_keys: None,
                 enabled: true,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
                 passive_window_seconds_override: None,
@@ -1597,6 +2356,7 @@ async fn build_monoize_attempts_uses_metadata_pricing_profile_fallback() {
                     MonoizeModelEntry {
                         redirect: None,
                         multiplier: 1.0,
+                        supports_streaming: None,
                     },
                 )]),
                 active_probe_enabled_override: None,
@@ -1620,6 +2380,8 @@ async fn build_monoize_attempts_uses_metadata_pricing_profile_fallback() {
                 cache_read_input_cost_per_token_nano: None,
                 cache_creation_input_cost_per_token_nano: None,
                 output_cost_per_reasoning_token_nano: None,
+                image_cost_per_image_nano: None,
+                tts_cost_per_char_nano: None,
                 max_input_tokens: None,
                 max_output_tokens: None,
                 max_tokens: None,
@@ -1644,6 +2406,7 @@ async fn build_monoize_attempts_filters_providers_by_effective_groups_before_hea
         listen: "127.0.0.1:0".to_string(),
         metrics_path: "/metrics".to_string(),
         database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
     };
     let state = load_state_with_runtime(runtime).await.expect("state loads");
 
@@ -1658,8 +2421,13 @@ async fn build_monoize_attempts_filters_providers_by_effective_groups_before_hea
             provider_type: MonoizeProviderType::Responses,
             base_url: "https://public.example.com".to_string(),
             api_key: Some("secret".to_string()),
+            api_keys: None,
             enabled: true,
             weight: 1,
+            priority: 0,
+            max_concurrency: None,
+            org_id: None,
+            project_id: None,
             passive_failure_count_threshold_override: None,
             passive_cooldown_seconds_override: None,
             passive_window_seconds_override: None,
@@ -1669,6 +2437,7 @@ async fn build_monoize_attempts_filters_providers_by_effective_groups_before_hea
                 MonoizeModelEntry {
                     redirect: None,
                     multiplier: 1.0,
+                    supports_streaming: None,
                 },
             )]),
             active_probe_enabled_override: None,
@@ -1689,8 +2458,13 @@ async fn build_monoize_attempts_filters_providers_by_effective_groups_before_hea
             provider_type: MonoizeProviderType::Responses,
             base_url: "https://team-a.example.com".to_string(),
             api_key: Some("secret".to_string()),
+            api_keys: None,
             enabled: true,
             weight: 1,
+            priority: 0,
+            max_concurrency: None,
+            org_id: None,
+            project_id: None,
             passive_failure_count_threshold_override: None,
             passive_cooldown_seconds_override: None,
             passive_window_seconds_override: None,
@@ -1700,6 +2474,7 @@ async fn build_monoize_attempts_filters_providers_by_effective_groups_before_hea
                 MonoizeModelEntry {
                     redirect: None,
                     multiplier: 1.0,
+                    supports_streaming: None,
                 },
             )]),
             active_probe_enabled_override: None,
@@ -1720,8 +2495,13 @@ async fn build_monoize_attempts_filters_providers_by_effective_groups_before_hea
             provider_type: MonoizeProviderType::Responses,
             base_url: "https://team-b.example.com".to_string(),
             api_key: Some("secret".to_string()),
+            api_keys: None,
             enabled: true,
             weight: 1,
+            priority: 0,
+            max_concurrency: None,
+            org_id: None,
+            project_id: None,
             passive_failure_count_threshold_override: None,
             passive_cooldown_seconds_override: None,
             passive_window_seconds_override: None,
@@ -1731,6 +2511,7 @@ async fn build_monoize_attempts_filters_providers_by_effective_groups_before_hea
                 MonoizeModelEntry {
                     redirect: None,
                     multiplier: 1.0,
+                    supports_streaming: None,
                 },
             )]),
             active_probe_enabled_override: None,
@@ -1774,6 +2555,7 @@ async fn execute_nonstream_typed_keeps_bad_gateway_when_groups_filter_every_chan
         listen: "127.0.0.1:0".to_string(),
         metrics_path: "/metrics".to_string(),
         database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
     };
     let state = load_state_with_runtime(runtime).await.expect("state loads");
 
@@ -1788,8 +2570,13 @@ async fn execute_nonstream_typed_keeps_bad_gateway_when_groups_filter_every_chan
             provider_type: MonoizeProviderType::Responses,
             base_url: "https://team-a.example.com".to_string(),
             api_key: Some("secret".to_string()),
+            api_keys: None,
             enabled: true,
             weight: 1,
+            priority: 0,
+            max_concurrency: None,
+            org_id: None,
+            project_id: None,
             passive_failure_count_threshold_override: None,
             passive_cooldown_seconds_override: None,
             passive_window_seconds_override: None,
@@ -1799,6 +2586,7 @@ async fn execute_nonstream_typed_keeps_bad_gateway_when_groups_filter_every_chan
                 MonoizeModelEntry {
                     redirect: None,
                     multiplier: 1.0,
+                    supports_streaming: None,
                 },
             )]),
             active_probe_enabled_override: None,
@@ -1821,6 +2609,7 @@ async fn execute_nonstream_typed_keeps_bad_gateway_when_groups_filter_every_chan
             raw_input: json!({}),
             session: None,
         },
+        None,
     )
     .await
     .expect_err("public-only restriction should leave no attempts");
@@ -1857,3 +2646,526 @@ fn apply_model_redirects_to_model_leaves_unmatched_model_unchanged() {
 
     assert_eq!(model, "gpt-5-mini");
 }
+
+#[tokio::test]
+async fn stream_call_connect_failure_is_retryable_network_error() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    drop(listener);
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_millis(500))
+        .build()
+        .expect("client");
+    let provider = ProviderConfig {
+        id: "closed".to_string(),
+        provider_type: ProviderType::ChatCompletion,
+        base_url: Some(format!("http://{addr}")),
+        auth: Some(ProviderAuthConfig {
+            auth_type: ProviderAuthType::Bearer,
+            value: String::new(),
+            header_name: None,
+            query_name: None,
+        }),
+        model_map: Vec::new(),
+        strategy: None,
+        members: Vec::new(),
+        extra_headers: Vec::new(),
+    };
+
+    let err = upstream::call_upstream_stream_with_headers(
+        &client,
+        &provider,
+        "sk-test",
+        "/v1/chat/completions",
+        &serde_json::json!({ "model": "gpt-5", "stream": true }),
+        5_000,
+        &[],
+    )
+    .await
+    .expect_err("closed port must fail");
+
+    assert!(matches!(err.kind, UpstreamErrorKind::Network));
+    assert!(err.status.is_none());
+    assert!(is_retryable_error(&err));
+}
+
+#[test]
+fn upstream_error_to_app_propagates_structured_upstream_fields() {
+    let err = UpstreamCallError::new(
+        UpstreamErrorKind::Http,
+        Some(StatusCode::BAD_REQUEST),
+        "Invalid value for 'temperature'.".to_string(),
+    )
+    .with_error_info(upstream::UpstreamErrorInfo {
+        code: Some("invalid_value".to_string()),
+        error_type: Some("invalid_request_error".to_string()),
+        param: Some("temperature".to_string()),
+        message: None,
+    });
+
+    let app_err = upstream_error_to_app(err);
+
+    assert_eq!(app_err.status, StatusCode::BAD_REQUEST);
+    assert_eq!(app_err.code, "upstream_error");
+    assert_eq!(app_err.error_type, "invalid_request_error");
+    assert_eq!(app_err.param.as_deref(), Some("temperature"));
+    assert_eq!(app_err.upstream_status, Some(400));
+    assert_eq!(app_err.upstream_code.as_deref(), Some("invalid_value"));
+    assert_eq!(
+        app_err.upstream_type.as_deref(),
+        Some("invalid_request_error")
+    );
+    assert_eq!(app_err.upstream_param.as_deref(), Some("temperature"));
+}
+
+#[tokio::test]
+async fn large_gemini_files_upload_once_and_encode_as_file_data() {
+    use base64::Engine as _;
+
+    let uploads = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let captured = uploads.clone();
+    let router = axum::Router::new().route(
+        "/upload/v1beta/files",
+        axum::routing::post(move |headers: HeaderMap, body: axum::body::Bytes| {
+            let captured = captured.clone();
+            async move {
+                let header = |name: &str| {
+                    headers
+                        .get(name)
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string)
+                };
+                captured.lock().expect("uploads").push((
+                    header("content-type"),
+                    header("x-goog-api-key"),
+                    body.len(),
+                ));
+                Json(json!({
+                    "file": {
+                        "name": "files/abc123",
+                        "uri": "https://files.example/v1beta/files/abc123"
+                    }
+                }))
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.expect("upload server");
+    });
+    let provider = ProviderConfig {
+        id: "gemini".to_string(),
+        provider_type: ProviderType::Gemini,
+        base_url: Some(format!("http://{addr}/v1beta")),
+        auth: Some(ProviderAuthConfig {
+            auth_type: ProviderAuthType::Header,
+            value: String::new(),
+            header_name: Some("x-goog-api-key".to_string()),
+            query_name: None,
+        }),
+        model_map: Vec::new(),
+        strategy: None,
+        members: Vec::new(),
+        extra_headers: Vec::new(),
+    };
+
+    let large = base64::engine::general_purpose::STANDARD.encode(vec![7u8; 3000]);
+    let small = base64::engine::general_purpose::STANDARD.encode(b"tiny");
+    let file = |data: &str| urp::Node::File {
+        id: None,
+        role: urp::OrdinaryRole::User,
+        source: urp::FileSource::Base64 {
+            filename: Some("doc.pdf".to_string()),
+            media_type: "application/pdf".to_string(),
+            data: data.to_string(),
+        },
+        extra_body: HashMap::new(),
+    };
+    let mut req = build_test_urp_request("gemini-2.5-pro");
+    req.input.extend([file(&large), file(&large), file(&small)]);
+
+    let file_uris = upload_large_gemini_files(
+        &reqwest::Client::new(),
+        &provider,
+        "gm-test",
+        &req,
+        1024,
+        5_000,
+    )
+    .await
+    .expect("valid file data")
+    .expect("upload");
+
+    assert_eq!(
+        uploads.lock().expect("uploads").as_slice(),
+        &[(
+            Some("application/pdf".to_string()),
+            Some("gm-test".to_string()),
+            3000
+        )]
+    );
+    let body =
+        urp::encode::gemini::encode_request_with_file_uris(&req, "gemini-2.5-pro", &file_uris);
+    let parts = body["contents"][0]["parts"].as_array().expect("parts");
+    assert_eq!(parts.len(), 4);
+    for part in &parts[1..3] {
+        assert_eq!(
+            part,
+            &json!({
+                "fileData": {
+                    "mimeType": "application/pdf",
+                    "fileUri": "https://files.example/v1beta/files/abc123"
+                }
+            })
+        );
+    }
+    assert_eq!(parts[3]["inlineData"]["data"], json!(small));
+}
+
+#[test]
+fn conversation_chaining_keeps_only_responses_attempts() {
+    let attempt = |provider_type, channel_id: &str| MonoizeAttempt {
+        provider_type,
+        channel_id: channel_id.to_string(),
+        ..echo_attempt()
+    };
+    let channel_ids = |attempts: &[MonoizeAttempt]| {
+        attempts
+            .iter()
+            .map(|a| a.channel_id.clone())
+            .collect::<Vec<_>>()
+    };
+    let mut req = build_test_urp_request("gpt-5");
+    let mut attempts = vec![
+        attempt(ProviderType::ChatCompletion, "chat"),
+        attempt(ProviderType::Responses, "responses"),
+    ];
+    retain_conversation_chaining_attempts(&mut attempts, &req).expect("unchained request");
+    assert_eq!(channel_ids(&attempts), vec!["chat", "responses"]);
+
+    req.previous_response_id = Some("resp_prev".to_string());
+    retain_conversation_chaining_attempts(&mut attempts, &req).expect("responses attempt remains");
+    assert_eq!(channel_ids(&attempts), vec!["responses"]);
+
+    let mut chat_only = vec![attempt(ProviderType::ChatCompletion, "chat")];
+    let err = retain_conversation_chaining_attempts(&mut chat_only, &req)
+        .expect_err("no attempt can resolve the chain");
+    assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    assert_eq!(err.code, "conversation_chaining_not_supported");
+}
+
+#[tokio::test]
+async fn strict_mode_drops_attempts_whose_provider_rejects_the_role_order() {
+    let runtime = RuntimeConfig {
+        listen: "127.0.0.1:0".to_string(),
+        metrics_path: "/metrics".to_string(),
+        database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
+    };
+    let state = load_state_with_runtime(runtime).await.expect("state loads");
+    let attempt = |provider_type, channel_id: &str| MonoizeAttempt {
+        provider_type,
+        channel_id: channel_id.to_string(),
+        ..echo_attempt()
+    };
+    let mut req = build_test_urp_request("gpt-5");
+    req.input = vec![
+        urp::Node::text(urp::OrdinaryRole::Assistant, "hi, how can I help?"),
+        urp::Node::text(urp::OrdinaryRole::User, "weather?"),
+    ];
+    let mut attempts = vec![
+        attempt(ProviderType::Messages, "messages"),
+        attempt(ProviderType::ChatCompletion, "chat"),
+    ];
+    retain_role_order_attempts(&state, &mut attempts, &req)
+        .await
+        .expect("validation is off");
+    assert_eq!(attempts.len(), 2);
+
+    state.monoize_runtime.write().await.validate_requests = true;
+    retain_role_order_attempts(&state, &mut attempts, &req)
+        .await
+        .expect("chat attempt remains");
+    let channel_ids: Vec<&str> = attempts.iter().map(|a| a.channel_id.as_str()).collect();
+    assert_eq!(channel_ids, vec!["chat"]);
+
+    let mut gemini_only = vec![attempt(ProviderType::Gemini, "gemini")];
+    let err = retain_role_order_attempts(&state, &mut gemini_only, &req)
+        .await
+        .expect_err("no attempt accepts the role order");
+    assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    assert_eq!(err.code, "request_validation_failed");
+    assert_eq!(
+        err.message,
+        "input[0]: gemini conversations must start with a user message"
+    );
+}
+
+#[tokio::test]
+async fn nonstream_calls_outlive_the_client_read_timeout_up_to_their_request_timeout() {
+    let temp_dir = tempfile::tempdir().expect("temp dir");
+    let runtime = RuntimeConfig {
+        listen: "127.0.0.1:0".to_string(),
+        metrics_path: "/metrics".to_string(),
+        database_dsn: format!("sqlite://{}", temp_dir.path().join("monoize.db").display()),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
+    };
+    let state = load_state_with_runtime(runtime.clone())
+        .await
+        .expect("state loads");
+    let mut settings = state.settings_store.get_all().await.expect("settings");
+    settings.monoize_read_timeout_ms = 100;
+    state
+        .settings_store
+        .update_all(&settings)
+        .await
+        .expect("settings saved");
+    drop(state);
+    let state = load_state_with_runtime(runtime)
+        .await
+        .expect("state reloads with the new read timeout");
+
+    let router = axum::Router::new().route(
+        "/v1/slow",
+        axum::routing::post(|| async {
+            tokio::time::sleep(Duration::from_millis(400)).await;
+            Json(json!({"ok": true}))
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.expect("slow server");
+    });
+    let provider = ProviderConfig {
+        id: "slow".to_string(),
+        provider_type: ProviderType::Responses,
+        base_url: Some(format!("http://{addr}")),
+        auth: Some(ProviderAuthConfig {
+            auth_type: ProviderAuthType::Bearer,
+            value: String::new(),
+            header_name: None,
+            query_name: None,
+        }),
+        model_map: Vec::new(),
+        strategy: None,
+        members: Vec::new(),
+        extra_headers: Vec::new(),
+    };
+
+    let value = upstream::call_upstream_with_timeout_and_headers(
+        client_http_nonstream(&state),
+        &provider,
+        "sk-test",
+        "/v1/slow",
+        &json!({}),
+        5_000,
+        &[],
+    )
+    .await
+    .expect("request timeout above the read timeout applies");
+    assert_eq!(value, json!({"ok": true}));
+
+    let err = upstream::call_upstream_with_timeout_and_headers(
+        client_http(&state),
+        &provider,
+        "sk-test",
+        "/v1/slow",
+        &json!({}),
+        5_000,
+        &[],
+    )
+    .await
+    .expect_err("the streaming client cuts the header wait at the read timeout");
+    assert!(matches!(err.kind, upstream::UpstreamErrorKind::Network));
+}
+
+#[tokio::test]
+async fn failed_gemini_file_upload_is_a_retryable_attempt_failure() {
+    use base64::Engine as _;
+
+    let router = axum::Router::new().route(
+        "/upload/v1beta/files",
+        axum::routing::post(|| async {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(json!({"error": {"message": "upload backend unavailable"}})),
+            )
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.expect("upload server");
+    });
+    let provider = ProviderConfig {
+        id: "gemini".to_string(),
+        provider_type: ProviderType::Gemini,
+        base_url: Some(format!("http://{addr}/v1beta")),
+        auth: Some(ProviderAuthConfig {
+            auth_type: ProviderAuthType::Header,
+            value: String::new(),
+            header_name: Some("x-goog-api-key".to_string()),
+            query_name: None,
+        }),
+        model_map: Vec::new(),
+        strategy: None,
+        members: Vec::new(),
+        extra_headers: Vec::new(),
+    };
+    let mut req = build_test_urp_request("gemini-2.5-pro");
+    req.input.push(urp::Node::File {
+        id: None,
+        role: urp::OrdinaryRole::User,
+        source: urp::FileSource::Base64 {
+            filename: Some("doc.pdf".to_string()),
+            media_type: "application/pdf".to_string(),
+            data: base64::engine::general_purpose::STANDARD.encode(vec![7u8; 3000]),
+        },
+        extra_body: HashMap::new(),
+    });
+
+    let err = upload_large_gemini_files(
+        &reqwest::Client::new(),
+        &provider,
+        "gm-test",
+        &req,
+        1024,
+        5_000,
+    )
+    .await
+    .expect("valid file data")
+    .expect_err("upload fails");
+
+    assert_eq!(err.status, Some(StatusCode::SERVICE_UNAVAILABLE));
+    assert!(is_retryable_error(&err));
+}
+
+#[test]
+fn provider_request_body_size_limit_rejects_oversized_encoded_bodies() {
+    let oversized = 21 * 1024 * 1024;
+
+    let err = check_provider_request_body_size(ProviderType::Gemini, oversized)
+        .expect_err("gemini body over 20 MiB is rejected");
+    assert_eq!(err.status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(err.code, "payload_too_large");
+
+    check_provider_request_body_size(ProviderType::Messages, oversized)
+        .expect("messages allows up to 32 MiB");
+    check_provider_request_body_size(ProviderType::Responses, oversized)
+        .expect("responses has no local ceiling");
+}
+
+#[tokio::test]
+async fn request_latency_histograms_observe_recorded_durations() {
+    let runtime = RuntimeConfig {
+        listen: "127.0.0.1:0".to_string(),
+        metrics_path: "/metrics".to_string(),
+        database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
+    };
+    let state = load_state_with_runtime(runtime).await.expect("state loads");
+
+    record_request_latency_metrics("latency-test-model", "latency-provider", 1_234, Some(80));
+
+    let rendered = state.metrics.render();
+    let labels = r#"model="latency-test-model",provider_id="latency-provider""#;
+    for expected in [
+        format!(r#"monoize_request_duration_ms_bucket{{{labels},le="1000"}} 0"#),
+        format!(r#"monoize_request_duration_ms_bucket{{{labels},le="2000"}} 1"#),
+        format!("monoize_request_duration_ms_sum{{{labels}}} 1234"),
+        format!(r#"monoize_request_ttfb_ms_bucket{{{labels},le="100"}} 1"#),
+    ] {
+        assert!(
+            rendered.contains(&expected),
+            "missing {expected} in:\n{rendered}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn race_hedged_prefers_the_first_success_after_the_delay() {
+    let slow_primary = async {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        Ok::<_, &str>("primary")
+    };
+    let fast_hedge = async { Some(Ok("hedge")) };
+    let outcome = race_hedged(
+        slow_primary,
+        Duration::from_millis(10),
+        fast_hedge,
+        Result::is_ok,
+    )
+    .await;
+    assert_eq!(outcome.winner, HedgeWinner::Hedge);
+    assert_eq!(outcome.result, Ok("hedge"));
+    assert!(outcome.loser.is_none());
+
+    let failing_primary = async { Err::<&str, _>("primary failed") };
+    let outcome = race_hedged(
+        failing_primary,
+        Duration::from_millis(10),
+        async { Some(Ok("hedge")) },
+        Result::is_ok,
+    )
+    .await;
+    assert_eq!(outcome.winner, HedgeWinner::Primary);
+    assert_eq!(outcome.result, Err("primary failed"));
+
+    let outcome = race_hedged(
+        async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Err::<&str, _>("primary failed")
+        },
+        Duration::from_millis(10),
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Some(Ok("hedge"))
+        },
+        Result::is_ok,
+    )
+    .await;
+    assert_eq!(outcome.winner, HedgeWinner::Hedge);
+    assert_eq!(outcome.loser, Some(Err("primary failed")));
+}
+
+#[tokio::test]
+async fn buffered_transcode_permits_respect_configured_limit() {
+    let runtime = RuntimeConfig {
+        listen: "127.0.0.1:0".to_string(),
+        metrics_path: "/metrics".to_string(),
+        database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: crate::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
+    };
+    let state = load_state_with_runtime(runtime).await.expect("state loads");
+    let wait = std::time::Duration::from_millis(20);
+
+    let unlimited = acquire_buffered_transcode_permit(&state, wait)
+        .await
+        .expect("unlimited by default");
+    assert!(unlimited.is_none());
+
+    state.monoize_runtime.write().await.max_buffered_transcodes = 1;
+    let first = acquire_buffered_transcode_permit(&state, wait)
+        .await
+        .expect("first slot is free")
+        .expect("permit is issued when limited");
+    let err = acquire_buffered_transcode_permit(&state, wait)
+        .await
+        .expect_err("second transcode waits and times out");
+    assert_eq!(err.status, axum::http::StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(err.code, "buffered_transcode_busy");
+
+    drop(first);
+    let second = acquire_buffered_transcode_permit(&state, wait)
+        .await
+        .expect("slot is released");
+    assert!(second.is_some());
+}
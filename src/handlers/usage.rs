@@ -90,15 +90,33 @@ pub(crate) async fn record_stream_done_sentinel(
     guard.terminal.saw_done_sentinel = true;
 }
 
+/// Whether a stream that ended without upstream usage may be billed from the
+/// visible-output estimate. Gemini streams need their own opt-in on top of
+/// `enable_estimated_billing`.
+pub(crate) fn stream_usage_estimate_enabled(
+    runtime: &crate::monoize_routing::MonoizeRuntimeConfig,
+    provider_type: crate::config::ProviderType,
+) -> bool {
+    runtime.enable_estimated_billing
+        && (provider_type != crate::config::ProviderType::Gemini
+            || runtime.estimate_gemini_stream_usage)
+}
+
+/// Adds the estimated token count of the decoded output `nodes`, the basis
+/// for billing when an upstream stream omits usage.
 pub(crate) async fn increment_estimated_output_tokens(
     runtime_metrics: &Option<Arc<Mutex<StreamRuntimeMetrics>>>,
-    chars: u64,
+    model: &str,
+    nodes: &[urp::Node],
 ) {
     let Some(runtime_metrics) = runtime_metrics.as_ref() else {
         return;
     };
     let mut guard = runtime_metrics.lock().await;
-    guard.estimated_output_tokens += (chars + 3) / 4;
+    let estimator = guard
+        .token_estimator
+        .unwrap_or(&crate::token_estimator::HeuristicEstimator);
+    guard.estimated_output_tokens += estimator.estimate(nodes, model);
 }
 
 pub(crate) async fn record_visible_output_delta(
@@ -475,9 +493,10 @@ pub(crate) fn parse_usage_from_chat_object(obj: &Value) -> Option<urp::Usage> {
             "completion_tokens",
             "input_tokens",
             "output_tokens",
+            "total_tokens",
         ],
     );
-    Some(urp::Usage {
+    let mut usage = urp::Usage {
         input_tokens,
         output_tokens,
         input_details: make_input_details(
@@ -496,7 +515,9 @@ pub(crate) fn parse_usage_from_chat_object(obj: &Value) -> Option<urp::Usage> {
             parse_modality_breakdown_from_detail_object(completion_details),
         ),
         extra_body,
-    })
+    };
+    usage.clamp_subset_counts("chat_completion");
+    Some(usage)
 }
 
 pub(crate) fn parse_usage_from_responses_object(obj: &Value) -> Option<urp::Usage> {
@@ -586,9 +607,10 @@ pub(crate) fn parse_usage_from_responses_object(obj: &Value) -> Option<urp::Usag
             "output_tokens",
             "prompt_tokens",
             "completion_tokens",
+            "total_tokens",
         ],
     );
-    Some(urp::Usage {
+    let mut usage = urp::Usage {
         input_tokens,
         output_tokens,
         input_details: make_input_details(
@@ -607,7 +629,9 @@ pub(crate) fn parse_usage_from_responses_object(obj: &Value) -> Option<urp::Usag
             parse_modality_breakdown_from_detail_object(output_details_obj),
         ),
         extra_body,
-    })
+    };
+    usage.clamp_subset_counts("responses");
+    Some(usage)
 }
 
 pub(crate) fn parse_usage_from_gemini_object(obj: &Value) -> Option<urp::Usage> {
@@ -783,4 +807,37 @@ mod tests {
         assert_eq!(basis.visible_output_tokens, 3);
         assert_eq!(basis.tps_mode, "estimated");
     }
+
+    #[test]
+    fn stream_usage_parsers_clamp_inconsistent_subset_counts() {
+        let chat = parse_usage_from_chat_object(&json!({
+            "usage": {
+                "prompt_tokens": 10,
+                "completion_tokens": 4,
+                "prompt_tokens_details": { "cached_tokens": 25 },
+                "completion_tokens_details": { "reasoning_tokens": 9 }
+            }
+        }))
+        .expect("chat usage");
+        assert_eq!(chat.cached_tokens(), Some(10));
+        assert_eq!(chat.reasoning_tokens(), Some(4));
+        assert_eq!(chat.total_tokens(), 14);
+
+        let responses = parse_usage_from_responses_object(&json!({
+            "response": {
+                "usage": {
+                    "input_tokens": 7,
+                    "output_tokens": 3,
+                    "total_tokens": 99,
+                    "input_tokens_details": { "cached_tokens": 8 },
+                    "output_tokens_details": { "reasoning_tokens": 3 }
+                }
+            }
+        }))
+        .expect("responses usage");
+        assert_eq!(responses.cached_tokens(), Some(7));
+        assert_eq!(responses.reasoning_tokens(), Some(3));
+        assert_eq!(responses.total_tokens(), 10);
+        assert!(!responses.extra_body.contains_key("total_tokens"));
+    }
 }
@@ -0,0 +1,125 @@
+use crate::response_cache::ResponseCache;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Replay store for non-stream requests carrying an `Idempotency-Key` header.
+///
+/// Completed responses live in a bounded LRU; keys whose first request is
+/// still running are tracked separately so a concurrent retry cannot reach
+/// the upstream a second time.
+#[derive(Default)]
+pub struct IdempotencyStore {
+    responses: ResponseCache<Value>,
+    in_flight: Arc<Mutex<HashSet<u128>>>,
+}
+
+pub enum IdempotencyLookup {
+    /// A completed response for this key is still within its TTL.
+    Replay(Value),
+    /// The caller owns the key until the claim is completed or dropped.
+    Claimed(IdempotencyClaim),
+    /// Another request with the same key has not finished yet.
+    InFlight,
+}
+
+/// Ownership of an in-flight key. Dropping the claim without completing it
+/// releases the key so a retry can run again.
+pub struct IdempotencyClaim {
+    key: u128,
+    in_flight: Arc<Mutex<HashSet<u128>>>,
+}
+
+impl Drop for IdempotencyClaim {
+    fn drop(&mut self) {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.key);
+    }
+}
+
+impl IdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn key(user_id: &str, downstream: &str, idempotency_key: &str) -> u128 {
+        let material = serde_json::json!([user_id, downstream, idempotency_key]);
+        xxhash_rust::xxh3::xxh3_128(material.to_string().as_bytes())
+    }
+
+    pub fn begin(&self, key: u128, ttl: Duration) -> IdempotencyLookup {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(value) = self.responses.get(key, ttl) {
+            return IdempotencyLookup::Replay(value);
+        }
+        if !in_flight.insert(key) {
+            return IdempotencyLookup::InFlight;
+        }
+        IdempotencyLookup::Claimed(IdempotencyClaim {
+            key,
+            in_flight: self.in_flight.clone(),
+        })
+    }
+
+    pub fn complete(&self, claim: IdempotencyClaim, value: Value, max_entries: usize) {
+        // Hold the in-flight lock across the insert so a concurrent `begin`
+        // sees either the claim or the stored response, never neither.
+        let in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        self.responses.insert(claim.key, value, max_entries);
+        drop(in_flight);
+        drop(claim);
+    }
+
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn concurrent_claim_is_rejected_and_completion_replays() {
+        let store = IdempotencyStore::new();
+        let key = IdempotencyStore::key("u1", "chat_completions", "abc");
+
+        let IdempotencyLookup::Claimed(claim) = store.begin(key, TTL) else {
+            panic!("first request should claim the key");
+        };
+        assert!(matches!(store.begin(key, TTL), IdempotencyLookup::InFlight));
+
+        store.complete(claim, json!({ "id": "resp_1" }), 10);
+        assert!(matches!(
+            store.begin(key, TTL),
+            IdempotencyLookup::Replay(value) if value["id"] == "resp_1"
+        ));
+    }
+
+    #[test]
+    fn dropped_claim_releases_the_key() {
+        let store = IdempotencyStore::new();
+        let key = IdempotencyStore::key("u1", "responses", "abc");
+        assert_ne!(key, IdempotencyStore::key("u2", "responses", "abc"));
+
+        let IdempotencyLookup::Claimed(claim) = store.begin(key, TTL) else {
+            panic!("first request should claim the key");
+        };
+        drop(claim);
+        assert!(matches!(
+            store.begin(key, TTL),
+            IdempotencyLookup::Claimed(_)
+        ));
+        assert!(store.is_empty());
+    }
+}
@@ -9,6 +9,7 @@ pub mod entity;
 pub mod error;
 pub mod frontend;
 pub mod handlers;
+pub mod idempotency;
 pub mod image_transform_cache;
 pub mod migration;
 pub mod model_registry;
@@ -18,7 +19,9 @@ pub mod name_cache;
 pub mod presets;
 pub mod rate_limit;
 pub mod request_capture;
+pub mod response_cache;
 pub mod settings;
+pub mod token_estimator;
 pub mod transforms;
 pub mod upstream;
 pub mod urp;
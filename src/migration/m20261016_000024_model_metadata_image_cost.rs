@@ -0,0 +1,59 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        let backend = manager.get_database_backend();
+        if column_exists(
+            conn,
+            backend,
+            "model_metadata_records",
+            "image_cost_per_image_nano",
+        )
+        .await?
+        {
+            return Ok(());
+        }
+        let sql = match backend {
+            DbBackend::Sqlite => {
+                "ALTER TABLE model_metadata_records ADD COLUMN image_cost_per_image_nano TEXT"
+            }
+            DbBackend::Postgres => {
+                "ALTER TABLE model_metadata_records ADD COLUMN IF NOT EXISTS image_cost_per_image_nano TEXT"
+            }
+            _ => return Ok(()),
+        };
+        conn.execute(Statement::from_string(backend, sql.to_string()))
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
+
+async fn column_exists(
+    conn: &SchemaManagerConnection<'_>,
+    backend: DbBackend,
+    table: &str,
+    column: &str,
+) -> Result<bool, DbErr> {
+    let sql = match backend {
+        DbBackend::Sqlite => format!("PRAGMA table_info({table})"),
+        DbBackend::Postgres => format!(
+            "SELECT column_name AS name FROM information_schema.columns WHERE table_name = '{table}'"
+        ),
+        _ => return Ok(false),
+    };
+    let rows = conn.query_all(Statement::from_string(backend, sql)).await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| row.try_get::<String>("", "name").ok())
+        .any(|name| name == column))
+}
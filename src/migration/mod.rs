@@ -29,6 +29,19 @@ impl MigratorTrait for Migrator {
             Box::new(m20260620_000021_pricing_profile_pattern_defaults::Migration),
             Box::new(m20260718_000022_move_models_to_channels::Migration),
             Box::new(m20260718_000023_channel_model_multiplier_float8::Migration),
+            Box::new(m20261016_000024_model_metadata_image_cost::Migration),
+            Box::new(m20261016_000025_channel_priority::Migration),
+            Box::new(m20261016_000026_channel_max_concurrency::Migration),
+            Box::new(m20261016_000027_api_key_response_cache::Migration),
+            Box::new(m20261017_000028_model_metadata_tts_cost::Migration),
+            Box::new(m20261017_000029_provider_debug_log_bodies::Migration),
+            Box::new(m20261017_000030_api_key_max_request_charge::Migration),
+            Box::new(m20261017_000031_request_log_metadata::Migration),
+            Box::new(m20261017_000032_channel_openai_org_project::Migration),
+            Box::new(m20261017_000033_api_key_model_filters::Migration),
+            Box::new(m20261017_000034_provider_sampling_defaults::Migration),
+            Box::new(m20261017_000035_channel_model_supports_streaming::Migration),
+            Box::new(m20261017_000036_channel_api_keys::Migration),
         ]
     }
 }
@@ -56,3 +69,16 @@ mod m20260619_000020_default_pricing_profile;
 mod m20260620_000021_pricing_profile_pattern_defaults;
 mod m20260718_000022_move_models_to_channels;
 mod m20260718_000023_channel_model_multiplier_float8;
+mod m20261016_000024_model_metadata_image_cost;
+mod m20261016_000025_channel_priority;
+mod m20261016_000026_channel_max_concurrency;
+mod m20261016_000027_api_key_response_cache;
+mod m20261017_000028_model_metadata_tts_cost;
+mod m20261017_000029_provider_debug_log_bodies;
+mod m20261017_000030_api_key_max_request_charge;
+mod m20261017_000031_request_log_metadata;
+mod m20261017_000032_channel_openai_org_project;
+mod m20261017_000033_api_key_model_filters;
+mod m20261017_000034_provider_sampling_defaults;
+mod m20261017_000035_channel_model_supports_streaming;
+mod m20261017_000036_channel_api_keys;
@@ -66,6 +66,8 @@ pub struct DbModelMetadataRecord {
     pub cache_read_input_cost_per_token_nano: Option<String>,
     pub cache_creation_input_cost_per_token_nano: Option<String>,
     pub output_cost_per_reasoning_token_nano: Option<String>,
+    pub image_cost_per_image_nano: Option<String>,
+    pub tts_cost_per_char_nano: Option<String>,
     pub max_input_tokens: Option<i64>,
     pub max_output_tokens: Option<i64>,
     pub max_tokens: Option<i64>,
@@ -74,6 +76,12 @@ pub struct DbModelMetadataRecord {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Meter usage class billed once per generated output image.
+pub const IMAGE_GENERATION_USAGE_CLASS: &str = "image_generation";
+
+/// Meter usage class billed once per input character of a text-to-speech request.
+pub const TTS_CHARACTER_USAGE_CLASS: &str = "tts_character";
+
 #[derive(Debug, Clone)]
 pub struct ModelPricing {
     pub input_cost_per_token_nano: i128,
@@ -81,6 +89,8 @@ pub struct ModelPricing {
     pub cache_read_input_cost_per_token_nano: Option<i128>,
     pub cache_creation_input_cost_per_token_nano: Option<i128>,
     pub output_cost_per_reasoning_token_nano: Option<i128>,
+    pub image_cost_per_image_nano: Option<i128>,
+    pub tts_cost_per_char_nano: Option<i128>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -92,6 +102,8 @@ pub struct UpsertModelMetadataInput {
     pub cache_read_input_cost_per_token_nano: Option<String>,
     pub cache_creation_input_cost_per_token_nano: Option<String>,
     pub output_cost_per_reasoning_token_nano: Option<String>,
+    pub image_cost_per_image_nano: Option<String>,
+    pub tts_cost_per_char_nano: Option<String>,
     pub max_input_tokens: Option<i64>,
     pub max_output_tokens: Option<i64>,
     pub max_tokens: Option<i64>,
@@ -373,8 +385,9 @@ impl ModelRegistryStore {
                 "SELECT model_id, models_dev_provider, mode, input_cost_per_token_nano,
                         output_cost_per_token_nano, cache_read_input_cost_per_token_nano,
                         cache_creation_input_cost_per_token_nano,
-                        output_cost_per_reasoning_token_nano, max_input_tokens, max_output_tokens,
-                        max_tokens, raw_json, source, updated_at
+                        output_cost_per_reasoning_token_nano, image_cost_per_image_nano,
+                        tts_cost_per_char_nano, max_input_tokens, max_output_tokens, max_tokens,
+                        raw_json, source, updated_at
                  FROM model_metadata_records
                  ORDER BY model_id ASC",
                 vec![],
@@ -415,8 +428,9 @@ impl ModelRegistryStore {
                 "SELECT model_id, models_dev_provider, mode, input_cost_per_token_nano,
                         output_cost_per_token_nano, cache_read_input_cost_per_token_nano,
                         cache_creation_input_cost_per_token_nano,
-                        output_cost_per_reasoning_token_nano, max_input_tokens, max_output_tokens,
-                        max_tokens, raw_json, source, updated_at
+                        output_cost_per_reasoning_token_nano, image_cost_per_image_nano,
+                        tts_cost_per_char_nano, max_input_tokens, max_output_tokens, max_tokens,
+                        raw_json, source, updated_at
                  FROM model_metadata_records
                  WHERE model_id = $1",
                 vec![model_id.into()],
@@ -435,11 +449,28 @@ impl ModelRegistryStore {
         let Some(row) = row else {
             return Ok(None);
         };
-        let Some(input_raw) = row.input_cost_per_token_nano else {
-            return Ok(None);
-        };
-        let Some(output_raw) = row.output_cost_per_token_nano else {
-            return Ok(None);
+        let image_cost_per_image_nano = row
+            .image_cost_per_image_nano
+            .map(|v| v.parse::<i128>())
+            .transpose()
+            .map_err(|_| "invalid image_cost_per_image_nano".to_string())?;
+        let tts_cost_per_char_nano = row
+            .tts_cost_per_char_nano
+            .map(|v| v.parse::<i128>())
+            .transpose()
+            .map_err(|_| "invalid tts_cost_per_char_nano".to_string())?;
+        // Per-image and per-character priced models (e.g. DALL·E, TTS) may carry no
+        // token prices at all; their token costs then default to zero instead of
+        // making the model unpriced.
+        let (input_raw, output_raw) = match (
+            row.input_cost_per_token_nano,
+            row.output_cost_per_token_nano,
+        ) {
+            (Some(input), Some(output)) => (input, output),
+            _ if image_cost_per_image_nano.is_some() || tts_cost_per_char_nano.is_some() => {
+                ("0".to_string(), "0".to_string())
+            }
+            _ => return Ok(None),
         };
         let input_cost_per_token_nano = input_raw
             .parse::<i128>()
@@ -469,6 +500,8 @@ impl ModelRegistryStore {
             cache_read_input_cost_per_token_nano,
             cache_creation_input_cost_per_token_nano,
             output_cost_per_reasoning_token_nano,
+            image_cost_per_image_nano,
+            tts_cost_per_char_nano,
         }))
     }
 
@@ -484,21 +517,23 @@ impl ModelRegistryStore {
                 "INSERT INTO model_metadata_records
                  (model_id, models_dev_provider, mode, input_cost_per_token_nano, output_cost_per_token_nano,
                   cache_read_input_cost_per_token_nano, cache_creation_input_cost_per_token_nano, output_cost_per_reasoning_token_nano,
-                  max_input_tokens, max_output_tokens, max_tokens, raw_json, source, updated_at)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, '{}', 'manual', $12)
+                  image_cost_per_image_nano, tts_cost_per_char_nano, max_input_tokens, max_output_tokens, max_tokens, raw_json, source, updated_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, '{}', 'manual', $14)
                  ON CONFLICT(model_id) DO UPDATE SET
-                   models_dev_provider = COALESCE($13, model_metadata_records.models_dev_provider),
-                   mode = COALESCE($14, model_metadata_records.mode),
-                   input_cost_per_token_nano = $15,
-                   output_cost_per_token_nano = $16,
-                   cache_read_input_cost_per_token_nano = $17,
-                   cache_creation_input_cost_per_token_nano = $18,
-                   output_cost_per_reasoning_token_nano = $19,
-                   max_input_tokens = COALESCE($20, model_metadata_records.max_input_tokens),
-                   max_output_tokens = COALESCE($21, model_metadata_records.max_output_tokens),
-                   max_tokens = COALESCE($22, model_metadata_records.max_tokens),
+                   models_dev_provider = COALESCE($15, model_metadata_records.models_dev_provider),
+                   mode = COALESCE($16, model_metadata_records.mode),
+                   input_cost_per_token_nano = $17,
+                   output_cost_per_token_nano = $18,
+                   cache_read_input_cost_per_token_nano = $19,
+                   cache_creation_input_cost_per_token_nano = $20,
+                   output_cost_per_reasoning_token_nano = $21,
+                   image_cost_per_image_nano = $22,
+                   tts_cost_per_char_nano = $23,
+                   max_input_tokens = COALESCE($24, model_metadata_records.max_input_tokens),
+                   max_output_tokens = COALESCE($25, model_metadata_records.max_output_tokens),
+                   max_tokens = COALESCE($26, model_metadata_records.max_tokens),
                    source = 'manual',
-                   updated_at = $23",
+                   updated_at = $27",
                 vec![
                     // INSERT binds
                     model_id.into(),
@@ -509,6 +544,8 @@ impl ModelRegistryStore {
                     input.cache_read_input_cost_per_token_nano.clone().into(),
                     input.cache_creation_input_cost_per_token_nano.clone().into(),
                     input.output_cost_per_reasoning_token_nano.clone().into(),
+                    input.image_cost_per_image_nano.clone().into(),
+                    input.tts_cost_per_char_nano.clone().into(),
                     input.max_input_tokens.into(),
                     input.max_output_tokens.into(),
                     input.max_tokens.into(),
@@ -521,6 +558,8 @@ impl ModelRegistryStore {
                     input.cache_read_input_cost_per_token_nano.into(),
                     input.cache_creation_input_cost_per_token_nano.into(),
                     input.output_cost_per_reasoning_token_nano.into(),
+                    input.image_cost_per_image_nano.into(),
+                    input.tts_cost_per_char_nano.into(),
                     input.max_input_tokens.into(),
                     input.max_output_tokens.into(),
                     input.max_tokens.into(),
@@ -806,6 +845,8 @@ fn row_to_model_metadata(row: &sea_orm::QueryResult) -> Result<DbModelMetadataRe
         output_cost_per_reasoning_token_nano: row
             .try_get("", "output_cost_per_reasoning_token_nano")
             .unwrap_or(None),
+        image_cost_per_image_nano: row.try_get("", "image_cost_per_image_nano").unwrap_or(None),
+        tts_cost_per_char_nano: row.try_get("", "tts_cost_per_char_nano").unwrap_or(None),
         max_input_tokens: row.try_get("", "max_input_tokens").unwrap_or(None),
         max_output_tokens: row.try_get("", "max_output_tokens").unwrap_or(None),
         max_tokens: row.try_get("", "max_tokens").unwrap_or(None),
@@ -908,21 +949,49 @@ async fn upsert_model_metadata_billing_rates(
         .models_dev_provider
         .clone()
         .unwrap_or_else(|| "default".to_string());
-    for (usage_class, price) in [
-        ("input_uncached", record.input_cost_per_token_nano.as_ref()),
-        ("output", record.output_cost_per_token_nano.as_ref()),
+    for (rate_kind, usage_class, unit, price) in [
+        (
+            "token",
+            "input_uncached",
+            "token",
+            record.input_cost_per_token_nano.as_ref(),
+        ),
+        (
+            "token",
+            "output",
+            "token",
+            record.output_cost_per_token_nano.as_ref(),
+        ),
         (
+            "token",
             "cache_read",
+            "token",
             record.cache_read_input_cost_per_token_nano.as_ref(),
         ),
         (
+            "token",
             "cache_write_5m",
+            "token",
             record.cache_creation_input_cost_per_token_nano.as_ref(),
         ),
         (
+            "token",
             "reasoning_output",
+            "token",
             record.output_cost_per_reasoning_token_nano.as_ref(),
         ),
+        (
+            "meter",
+            IMAGE_GENERATION_USAGE_CLASS,
+            "image",
+            record.image_cost_per_image_nano.as_ref(),
+        ),
+        (
+            "meter",
+            TTS_CHARACTER_USAGE_CLASS,
+            "character",
+            record.tts_cost_per_char_nano.as_ref(),
+        ),
     ] {
         let Some(price) = price else {
             continue;
@@ -933,12 +1002,14 @@ async fn upsert_model_metadata_billing_rates(
                 "INSERT INTO billing_rate_records
                  (id, source, pricing_profile, model_pattern, provider_type, rate_kind, usage_class,
                   unit, unit_price_nano_usd, match_json, priority, enabled, raw_json, updated_at)
-                 VALUES ($1, $2, $3, $4, NULL, 'token', $5, 'token', $6, '{}', 0, 1, $7, $8)
+                 VALUES ($1, $2, $3, $4, NULL, $5, $6, $7, $8, '{}', 0, 1, $9, $10)
                  ON CONFLICT(id) DO UPDATE SET
                    source = excluded.source,
                    pricing_profile = excluded.pricing_profile,
                    model_pattern = excluded.model_pattern,
+                   rate_kind = excluded.rate_kind,
                    usage_class = excluded.usage_class,
+                   unit = excluded.unit,
                    unit_price_nano_usd = excluded.unit_price_nano_usd,
                    raw_json = excluded.raw_json,
                    updated_at = excluded.updated_at",
@@ -947,7 +1018,9 @@ async fn upsert_model_metadata_billing_rates(
                     record.source.clone().into(),
                     pricing_profile.clone().into(),
                     record.model_id.clone().into(),
+                    rate_kind.into(),
                     usage_class.into(),
+                    unit.into(),
                     price.clone().into(),
                     serde_json::json!({ "source": "model_metadata_records" })
                         .to_string()
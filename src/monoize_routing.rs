@@ -66,6 +66,10 @@ pub struct ApiTypeOverride {
 pub struct MonoizeModelEntry {
     pub redirect: Option<String>,
     pub multiplier: f64,
+    /// `Some(false)` marks a model the upstream cannot stream; streaming
+    /// requests routed to it are served from a non-stream call instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_streaming: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,8 +80,21 @@ pub struct MonoizeChannel {
     pub base_url: String,
     #[serde(skip_serializing)]
     pub api_key: String,
+    /// Extra keys rotated together with `api_key` for the same account.
+    #[serde(default, skip_serializing)]
+    pub api_keys: Vec<String>,
     #[serde(default = "default_channel_weight")]
     pub weight: i32,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrency: Option<u32>,
+    /// Sent as `OpenAI-Organization` on OpenAI-protocol upstream calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>,
+    /// Sent as `OpenAI-Project` on OpenAI-protocol upstream calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
     #[serde(default = "default_enabled")]
     pub enabled: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -106,6 +123,19 @@ pub struct MonoizeChannel {
     pub _health_status: Option<String>,
 }
 
+impl MonoizeChannel {
+    /// `api_key` followed by `api_keys`, without empty or repeated keys.
+    pub fn key_pool(&self) -> Vec<String> {
+        let mut pool: Vec<String> = Vec::with_capacity(1 + self.api_keys.len());
+        for key in std::iter::once(&self.api_key).chain(&self.api_keys) {
+            if !key.is_empty() && !pool.contains(key) {
+                pool.push(key.clone());
+            }
+        }
+        pool
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonoizeProvider {
     pub id: String,
@@ -130,6 +160,14 @@ pub struct MonoizeProvider {
     #[serde(default)]
     pub strip_cross_protocol_nested_extra: Option<bool>,
     #[serde(default)]
+    pub debug_log_bodies: bool,
+    /// Sampling temperature sent upstream when the client omits `temperature`.
+    #[serde(default)]
+    pub default_temperature: Option<f64>,
+    /// Nucleus sampling value sent upstream when the client omits `top_p`.
+    #[serde(default)]
+    pub default_top_p: Option<f64>,
+    #[serde(default)]
     pub groups: Vec<String>,
     pub enabled: bool,
     pub priority: i32,
@@ -146,8 +184,19 @@ pub struct CreateMonoizeChannelInput {
     pub base_url: String,
     #[serde(default)]
     pub api_key: Option<String>,
+    /// `None` keeps the stored extra keys; `Some` replaces them.
+    #[serde(default)]
+    pub api_keys: Option<Vec<String>>,
     #[serde(default = "default_channel_weight")]
     pub weight: i32,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub max_concurrency: Option<u32>,
+    #[serde(default)]
+    pub org_id: Option<String>,
+    #[serde(default)]
+    pub project_id: Option<String>,
     #[serde(default = "default_enabled")]
     pub enabled: bool,
     #[serde(default)]
@@ -195,6 +244,14 @@ pub struct CreateMonoizeProviderInput {
     #[serde(default)]
     pub strip_cross_protocol_nested_extra: Option<bool>,
     #[serde(default)]
+    pub debug_log_bodies: bool,
+    /// Sampling temperature sent upstream when the client omits `temperature`.
+    #[serde(default)]
+    pub default_temperature: Option<f64>,
+    /// Nucleus sampling value sent upstream when the client omits `top_p`.
+    #[serde(default)]
+    pub default_top_p: Option<f64>,
+    #[serde(default)]
     pub groups: Vec<String>,
     #[serde(default = "default_enabled")]
     pub enabled: bool,
@@ -220,6 +277,9 @@ pub struct UpdateMonoizeProviderInput {
     pub request_timeout_ms_override: Option<Option<u64>>,
     pub extra_fields_whitelist: Option<Option<Vec<String>>>,
     pub strip_cross_protocol_nested_extra: Option<Option<bool>>,
+    pub debug_log_bodies: Option<bool>,
+    pub default_temperature: Option<Option<f64>>,
+    pub default_top_p: Option<Option<f64>>,
     pub groups: Option<Vec<String>>,
     pub enabled: Option<bool>,
     pub priority: Option<i32>,
@@ -234,6 +294,8 @@ pub struct ReorderProvidersInput {
 pub struct MonoizeRuntimeConfig {
     pub request_timeout_ms: u64,
     pub stream_idle_timeout_ms: u64,
+    /// Ceiling for the per-request `x-monoize-timeout-ms` header.
+    pub max_request_timeout_ms: u64,
     pub enable_estimated_billing: bool,
     pub passive_failure_count_threshold: u32,
     pub passive_cooldown_seconds: u64,
@@ -245,10 +307,31 @@ pub struct MonoizeRuntimeConfig {
     pub active_method: String,
     pub active_probe_model: Option<String>,
     pub global_transforms: Vec<TransformRuleConfig>,
+    pub model_aliases: HashMap<String, String>,
+    pub model_fallbacks: HashMap<String, Vec<String>>,
+    pub model_hedge_after_ms: HashMap<String, u64>,
+    pub model_fallback_report_actual_model: bool,
     pub extra_fields_whitelist: HashMap<String, Vec<String>>,
     pub strip_cross_protocol_nested_extra: bool,
     pub request_capture_enabled: bool,
     pub request_capture_retention_days: u64,
+    pub response_cache_ttl_seconds: u64,
+    pub response_cache_max_entries: u64,
+    pub idempotency_ttl_seconds: u64,
+    pub idempotency_max_entries: u64,
+    pub decode_retry_limit: u64,
+    pub missing_pricing_policy: crate::settings::MissingPricingPolicy,
+    pub max_request_charge_nano_usd: u64,
+    pub max_embedding_batch: u64,
+    pub max_buffered_transcodes: u64,
+    pub finish_reason_map: HashMap<String, crate::urp::FinishReason>,
+    pub validate_requests: bool,
+    pub sse_retry_ms: u64,
+    pub sse_event_ids: bool,
+    pub token_estimator: crate::token_estimator::TokenEstimatorKind,
+    pub expose_routing_headers: bool,
+    pub failure_weighted_routing: bool,
+    pub estimate_gemini_stream_usage: bool,
 }
 
 impl Default for MonoizeRuntimeConfig {
@@ -256,6 +339,7 @@ impl Default for MonoizeRuntimeConfig {
         Self {
             request_timeout_ms: 30_000,
             stream_idle_timeout_ms: 120_000,
+            max_request_timeout_ms: 600_000,
             enable_estimated_billing: true,
             passive_failure_count_threshold: 3,
             passive_cooldown_seconds: 60,
@@ -267,27 +351,96 @@ impl Default for MonoizeRuntimeConfig {
             active_method: "completion".to_string(),
             active_probe_model: None,
             global_transforms: Vec::new(),
+            model_aliases: HashMap::new(),
+            model_fallbacks: HashMap::new(),
+            model_hedge_after_ms: HashMap::new(),
+            model_fallback_report_actual_model: false,
             extra_fields_whitelist: HashMap::new(),
             strip_cross_protocol_nested_extra: true,
             request_capture_enabled: false,
             request_capture_retention_days: 1,
+            response_cache_ttl_seconds: 300,
+            response_cache_max_entries: 1_000,
+            idempotency_ttl_seconds: 86_400,
+            idempotency_max_entries: 10_000,
+            decode_retry_limit: 1,
+            missing_pricing_policy: crate::settings::MissingPricingPolicy::default(),
+            max_request_charge_nano_usd: 0,
+            max_embedding_batch: 2_048,
+            max_buffered_transcodes: 0,
+            finish_reason_map: HashMap::new(),
+            validate_requests: false,
+            sse_retry_ms: 0,
+            sse_event_ids: false,
+            token_estimator: crate::token_estimator::TokenEstimatorKind::default(),
+            expose_routing_headers: false,
+            failure_weighted_routing: false,
+            estimate_gemini_stream_usage: false,
         }
     }
 }
 
+impl MonoizeRuntimeConfig {
+    /// Copies every hot-reloadable tunable from persisted system settings.
+    /// `active_method` is not a setting and is left untouched.
+    pub fn apply_settings(&mut self, settings: &crate::settings::SystemSettings) {
+        self.request_timeout_ms = settings.monoize_request_timeout_ms.max(1);
+        self.stream_idle_timeout_ms = settings.monoize_stream_idle_timeout_ms.max(1);
+        self.max_request_timeout_ms = settings.monoize_max_request_timeout_ms.max(1);
+        self.enable_estimated_billing = settings.monoize_enable_estimated_billing;
+        self.passive_failure_count_threshold = settings.monoize_passive_failure_threshold.max(1);
+        self.passive_cooldown_seconds = settings.monoize_passive_cooldown_seconds.max(1);
+        self.passive_window_seconds = settings.monoize_passive_window_seconds.max(1);
+        self.passive_rate_limit_cooldown_seconds =
+            settings.monoize_passive_rate_limit_cooldown_seconds.max(1);
+        self.active_enabled = settings.monoize_active_probe_enabled;
+        self.active_interval_seconds = settings.monoize_active_probe_interval_seconds.max(1);
+        self.active_success_threshold = settings.monoize_active_probe_success_threshold.max(1);
+        self.active_probe_model = settings.monoize_active_probe_model.clone();
+        self.global_transforms = settings.global_transforms.clone();
+        self.model_aliases = settings.model_aliases.clone();
+        self.model_fallbacks = settings.model_fallbacks.clone();
+        self.model_hedge_after_ms = settings.model_hedge_after_ms.clone();
+        self.model_fallback_report_actual_model = settings.model_fallback_report_actual_model;
+        self.extra_fields_whitelist = settings.monoize_extra_fields_whitelist.clone();
+        self.strip_cross_protocol_nested_extra = settings.monoize_strip_cross_protocol_nested_extra;
+        self.request_capture_enabled = settings.monoize_request_capture_enabled;
+        self.request_capture_retention_days =
+            settings.monoize_request_capture_retention_days.max(1);
+        self.response_cache_ttl_seconds = settings.monoize_response_cache_ttl_seconds.max(1);
+        self.response_cache_max_entries = settings.monoize_response_cache_max_entries.max(1);
+        self.idempotency_ttl_seconds = settings.monoize_idempotency_ttl_seconds.max(1);
+        self.idempotency_max_entries = settings.monoize_idempotency_max_entries.max(1);
+        self.decode_retry_limit = settings.monoize_decode_retry_limit;
+        self.missing_pricing_policy = settings.monoize_missing_pricing_policy;
+        self.max_request_charge_nano_usd = settings.monoize_max_request_charge_nano_usd;
+        self.max_embedding_batch = settings.monoize_max_embedding_batch;
+        self.max_buffered_transcodes = settings.monoize_max_buffered_transcodes;
+        self.finish_reason_map = settings.monoize_finish_reason_map.clone();
+        self.validate_requests = settings.monoize_validate_requests;
+        self.sse_retry_ms = settings.monoize_sse_retry_ms;
+        self.sse_event_ids = settings.monoize_sse_event_ids;
+        self.token_estimator = settings.monoize_token_estimator;
+        self.expose_routing_headers = settings.monoize_expose_routing_headers;
+        self.failure_weighted_routing = settings.monoize_failure_weighted_routing;
+        self.estimate_gemini_stream_usage = settings.monoize_estimate_gemini_stream_usage;
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PassiveHealthSample {
     pub at_ts: i64,
     pub failed: bool,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ChannelHealthState {
     pub healthy: bool,
     pub last_success_at: Option<i64>,
     pub cooldown_until: Option<i64>,
     pub probe_success_count: u32,
     pub last_probe_at: Option<i64>,
+    #[serde(skip)]
     pub passive_samples: VecDeque<PassiveHealthSample>,
 }
 
@@ -298,6 +451,41 @@ pub struct ChannelAffinityBinding {
     pub updated_at: i64,
 }
 
+#[derive(Debug, Clone)]
+pub struct ChannelConcurrencyLimit {
+    pub limit: u32,
+    pub semaphore: std::sync::Arc<tokio::sync::Semaphore>,
+}
+
+/// Round-robin position and rejected keys of one multi-key channel.
+#[derive(Debug, Clone, Default)]
+pub struct ChannelKeyRotation {
+    pub cursor: usize,
+    pub suspended_until: HashMap<String, i64>,
+}
+
+impl ChannelKeyRotation {
+    /// Returns the next key of `pool` in round-robin order, skipping keys
+    /// suspended past `now_ts`. When every key is suspended the rotation
+    /// continues over the whole pool so the channel keeps serving.
+    pub fn next_key(&mut self, pool: &[String], now_ts: i64) -> Option<String> {
+        if pool.is_empty() {
+            return None;
+        }
+        self.suspended_until.retain(|_, until| *until > now_ts);
+        for _ in 0..pool.len() {
+            let key = &pool[self.cursor % pool.len()];
+            self.cursor = self.cursor.wrapping_add(1);
+            if !self.suspended_until.contains_key(key) {
+                return Some(key.clone());
+            }
+        }
+        let key = pool[self.cursor % pool.len()].clone();
+        self.cursor = self.cursor.wrapping_add(1);
+        Some(key)
+    }
+}
+
 pub const CHANNEL_AFFINITY_IDLE_TTL_SECONDS: i64 = 30 * 60;
 
 impl ChannelHealthState {
@@ -312,6 +500,47 @@ impl ChannelHealthState {
         }
     }
 
+    /// Restores the entry to healthy as of `now_ts` without touching passive samples.
+    pub fn mark_success(&mut self, now_ts: i64) {
+        self.healthy = true;
+        self.cooldown_until = None;
+        self.last_success_at = Some(now_ts);
+        self.probe_success_count = 0;
+        self.last_probe_at = None;
+    }
+
+    /// Whether the cooldown has elapsed for an unhealthy entry, so the next
+    /// request acts as a trial probe.
+    pub fn is_half_open(&self, now_ts: i64) -> bool {
+        !self.healthy && self.cooldown_until.is_none_or(|until| now_ts >= until)
+    }
+
+    /// Counts one successful probe against an unhealthy entry. The entry turns
+    /// healthy only once `threshold` consecutive probes have succeeded; returns
+    /// whether it recovered.
+    pub fn record_probe_success(&mut self, now_ts: i64, threshold: u32) -> bool {
+        self.last_probe_at = Some(now_ts);
+        self.probe_success_count = self.probe_success_count.saturating_add(1);
+        if self.probe_success_count >= threshold.max(1) {
+            self.mark_success(now_ts);
+            return true;
+        }
+        false
+    }
+
+    /// Re-opens the breaker after a failed probe: restarts the cooldown and
+    /// discards the consecutive probe successes collected so far.
+    pub fn reopen(&mut self, now_ts: i64, cooldown_seconds: u64) {
+        self.healthy = false;
+        self.probe_success_count = 0;
+        self.last_probe_at = Some(now_ts);
+        self.cooldown_until = Some(now_ts + cooldown_seconds as i64);
+    }
+
+    pub fn failure_count(&self) -> u32 {
+        self.passive_samples.iter().filter(|s| s.failed).count() as u32
+    }
+
     pub fn status(&self, now_ts: i64) -> &'static str {
         if self.healthy {
             return "healthy";
@@ -350,6 +579,22 @@ fn serialize_provider_groups_json(groups: &[String]) -> Result<String, String> {
     serde_json::to_string(&canonicalize_groups(groups)).map_err(|e| e.to_string())
 }
 
+fn parse_channel_api_keys_json(raw: Option<&str>) -> Vec<String> {
+    raw.and_then(|raw| serde_json::from_str::<Vec<String>>(raw).ok())
+        .map(|keys| normalize_channel_api_keys(&keys))
+        .unwrap_or_default()
+}
+
+fn normalize_channel_api_keys(keys: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::with_capacity(keys.len());
+    for key in keys.iter().map(|key| key.trim()) {
+        if !key.is_empty() && !out.iter().any(|existing| existing == key) {
+            out.push(key.to_string());
+        }
+    }
+    out
+}
+
 fn generate_short_id() -> String {
     const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
     let bytes = uuid::Uuid::new_v4().into_bytes();
@@ -426,8 +671,9 @@ impl MonoizeRoutingStore {
                           per_model_circuit_break, transforms, api_type_overrides,
                           active_probe_enabled_override, active_probe_interval_seconds_override,
                           active_probe_success_threshold_override, active_probe_model_override,
-                          request_timeout_ms_override, extra_fields_whitelist, groups,
-                          enabled, priority, created_at, updated_at
+                          request_timeout_ms_override, extra_fields_whitelist, debug_log_bodies,
+                          default_temperature, default_top_p,
+                          groups, enabled, priority, created_at, updated_at
                    FROM monoize_providers
                    ORDER BY priority ASC, created_at ASC"#,
                 vec![],
@@ -465,8 +711,9 @@ impl MonoizeRoutingStore {
                           per_model_circuit_break, transforms, api_type_overrides,
                           active_probe_enabled_override, active_probe_interval_seconds_override,
                           active_probe_success_threshold_override, active_probe_model_override,
-                          request_timeout_ms_override, extra_fields_whitelist, groups,
-                          enabled, priority, created_at, updated_at
+                          request_timeout_ms_override, extra_fields_whitelist, debug_log_bodies,
+                          default_temperature, default_top_p,
+                          groups, enabled, priority, created_at, updated_at
                    FROM monoize_providers
                    WHERE id = $1"#,
                 vec![id.into()],
@@ -512,6 +759,7 @@ impl MonoizeRoutingStore {
         if input.channel_retry_interval_ms < 0 {
             return Err("channel_retry_interval_ms must be >= 0".to_string());
         }
+        validate_sampling_defaults(input.default_temperature, input.default_top_p)?;
 
         let id = generate_short_id();
         let now = Utc::now();
@@ -558,9 +806,10 @@ impl MonoizeRoutingStore {
                         active_probe_enabled_override, active_probe_interval_seconds_override,
                         active_probe_success_threshold_override, active_probe_model_override,
                         request_timeout_ms_override, extra_fields_whitelist,
-                        strip_cross_protocol_nested_extra, groups,
+                        strip_cross_protocol_nested_extra, debug_log_bodies,
+                        default_temperature, default_top_p, groups,
                         enabled, priority, created_at, updated_at
-                   ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21)"#,
+                   ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24)"#,
                 vec![
                         id.clone().into(),
                         input.name.clone().into(),
@@ -582,6 +831,9 @@ impl MonoizeRoutingStore {
                         opt_u64_to_value(input.request_timeout_ms_override),
                         extra_fields_whitelist_json.into(),
                         opt_bool_to_value(strip_cross_proto),
+                        SeaValue::Int(Some(if input.debug_log_bodies { 1 } else { 0 })),
+                        SeaValue::Double(input.default_temperature),
+                        SeaValue::Double(input.default_top_p),
                         groups_json.into(),
                         SeaValue::Int(Some(if input.enabled { 1 } else { 0 })),
                         SeaValue::Int(Some(priority)),
@@ -682,6 +934,12 @@ impl MonoizeRoutingStore {
         let strip_cross_protocol_nested_extra = input
             .strip_cross_protocol_nested_extra
             .unwrap_or(existing.strip_cross_protocol_nested_extra);
+        let debug_log_bodies = input.debug_log_bodies.unwrap_or(existing.debug_log_bodies);
+        let default_temperature = input
+            .default_temperature
+            .unwrap_or(existing.default_temperature);
+        let default_top_p = input.default_top_p.unwrap_or(existing.default_top_p);
+        validate_sampling_defaults(default_temperature, default_top_p)?;
         let groups = canonicalize_groups(input.groups.as_deref().unwrap_or(&existing.groups));
         let enabled = input.enabled.unwrap_or(existing.enabled);
         let priority = input.priority.unwrap_or(existing.priority);
@@ -713,9 +971,12 @@ impl MonoizeRoutingStore {
                        request_timeout_ms_override = $13,
                        extra_fields_whitelist = $14,
                        strip_cross_protocol_nested_extra = $15,
-                       groups = $16,
-                       enabled = $17, priority = $18, updated_at = $19
-                   WHERE id = $20"#,
+                       debug_log_bodies = $16,
+                       default_temperature = $17,
+                       default_top_p = $18,
+                       groups = $19,
+                       enabled = $20, priority = $21, updated_at = $22
+                   WHERE id = $23"#,
             vec![
                 name.into(),
                 SeaValue::Int(Some(max_retries)),
@@ -732,6 +993,9 @@ impl MonoizeRoutingStore {
                 opt_u64_to_value(request_timeout_ms_override),
                 extra_fields_whitelist_json.into(),
                 opt_bool_to_value(strip_cross_protocol_nested_extra),
+                SeaValue::Int(Some(if debug_log_bodies { 1 } else { 0 })),
+                SeaValue::Double(default_temperature),
+                SeaValue::Double(default_top_p),
                 groups_json.into(),
                 SeaValue::Int(Some(if enabled { 1 } else { 0 })),
                 SeaValue::Int(Some(priority)),
@@ -831,7 +1095,7 @@ impl MonoizeRoutingStore {
     ) -> Result<(), String> {
         let existing_rows = conn
             .query_all(self.db.stmt(
-                "SELECT id, api_key
+                "SELECT id, api_key, api_keys
                  FROM monoize_channels
                  WHERE provider_id = $1",
                 vec![provider_id.into()],
@@ -842,6 +1106,7 @@ impl MonoizeRoutingStore {
         #[derive(Clone)]
         struct ExistingChannel {
             api_key: String,
+            api_keys: Vec<String>,
         }
         let mut existing_channels: HashMap<String, ExistingChannel> = HashMap::new();
         for row in &existing_rows {
@@ -850,6 +1115,11 @@ impl MonoizeRoutingStore {
                 id,
                 ExistingChannel {
                     api_key: row.try_get("", "api_key").map_err(|e| e.to_string())?,
+                    api_keys: parse_channel_api_keys_json(
+                        row.try_get::<Option<String>>("", "api_keys")
+                            .map_err(|e| e.to_string())?
+                            .as_deref(),
+                    ),
                 },
             );
         }
@@ -879,18 +1149,27 @@ impl MonoizeRoutingStore {
                         )
                     })?,
             };
+            let api_keys = match input.api_keys.as_deref() {
+                Some(keys) => normalize_channel_api_keys(keys),
+                None => existing_channels
+                    .get(&id)
+                    .map(|c| c.api_keys.clone())
+                    .unwrap_or_default(),
+            };
+            let api_keys_json = serde_json::to_string(&api_keys).map_err(|e| e.to_string())?;
             let now_str = Utc::now().to_rfc3339();
             let models = canonicalize_models(&input.models);
 
             conn.execute(self.db.stmt(
                     r#"INSERT INTO monoize_channels
-                       (id, provider_id, name, provider_type, base_url, api_key, weight, enabled,
+                       (id, provider_id, name, provider_type, base_url, api_key, weight, priority,
+                          max_concurrency, org_id, project_id, enabled,
                           passive_failure_count_threshold_override, passive_cooldown_seconds_override,
                           passive_window_seconds_override, passive_rate_limit_cooldown_seconds_override,
                           active_probe_enabled_override, active_probe_interval_seconds_override,
                           active_probe_success_threshold_override, active_probe_model_override,
-                          created_at, updated_at)
-                       VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)"#,
+                          created_at, updated_at, api_keys)
+                       VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)"#,
                     vec![
                         id.clone().into(),
                         provider_id.into(),
@@ -899,6 +1178,10 @@ impl MonoizeRoutingStore {
                         input.base_url.as_str().into(),
                         api_key.into(),
                         SeaValue::Int(Some(input.weight)),
+                        SeaValue::Int(Some(input.priority)),
+                        opt_u64_to_value(input.max_concurrency.map(|v| v as u64)),
+                        normalize_optional_header_value(input.org_id.as_deref()).into(),
+                        normalize_optional_header_value(input.project_id.as_deref()).into(),
                         SeaValue::Int(Some(if input.enabled { 1 } else { 0 })),
                         opt_u64_to_value(
                             input.passive_failure_count_threshold_override.map(|v| v as u64),
@@ -912,6 +1195,7 @@ impl MonoizeRoutingStore {
                         input.active_probe_model_override.clone().into(),
                         now_str.clone().into(),
                         now_str.into(),
+                        api_keys_json.into(),
                     ],
                 ))
                 .await
@@ -920,14 +1204,15 @@ impl MonoizeRoutingStore {
             for (model, entry) in models {
                 conn.execute(self.db.stmt(
                     r#"INSERT INTO monoize_channel_models
-                       (id, channel_id, model_name, redirect, multiplier, created_at)
-                       VALUES ($1, $2, $3, $4, $5, $6)"#,
+                       (id, channel_id, model_name, redirect, multiplier, supports_streaming, created_at)
+                       VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
                     vec![
                         format!("mono_ch_model_{}", uuid::Uuid::new_v4().simple()).into(),
                         id.clone().into(),
                         model.into(),
                         entry.redirect.into(),
                         SeaValue::Double(Some(entry.multiplier)),
+                        opt_bool_to_value(entry.supports_streaming),
                         Utc::now().to_rfc3339().into(),
                     ],
                 ))
@@ -944,7 +1229,8 @@ impl MonoizeRoutingStore {
             .db
             .read()
             .query_all(self.db.stmt(
-                r#"SELECT id, name, base_url, api_key, weight, enabled,
+                r#"SELECT id, name, base_url, api_key, api_keys, weight, priority, max_concurrency,
+                          org_id, project_id, enabled,
                           provider_type,
                           passive_failure_count_threshold_override,
                           passive_cooldown_seconds_override,
@@ -976,7 +1262,7 @@ impl MonoizeRoutingStore {
                 .db
                 .read()
                 .query_all(self.db.stmt(
-                    r#"SELECT model_name, redirect, multiplier
+                    r#"SELECT model_name, redirect, multiplier, supports_streaming
                        FROM monoize_channel_models
                        WHERE channel_id = $1
                        ORDER BY model_name ASC"#,
@@ -998,6 +1284,10 @@ impl MonoizeRoutingStore {
                         multiplier: model_row
                             .try_get("", "multiplier")
                             .map_err(|e| e.to_string())?,
+                        supports_streaming: model_row
+                            .try_get::<Option<i32>>("", "supports_streaming")
+                            .map_err(|e| e.to_string())?
+                            .map(|v| v != 0),
                     },
                 );
             }
@@ -1007,7 +1297,20 @@ impl MonoizeRoutingStore {
                 provider_type,
                 base_url: cr.try_get("", "base_url").map_err(|e| e.to_string())?,
                 api_key: cr.try_get("", "api_key").map_err(|e| e.to_string())?,
+                api_keys: parse_channel_api_keys_json(
+                    cr.try_get::<Option<String>>("", "api_keys")
+                        .map_err(|e| e.to_string())?
+                        .as_deref(),
+                ),
                 weight: cr.try_get("", "weight").map_err(|e| e.to_string())?,
+                priority: cr.try_get("", "priority").map_err(|e| e.to_string())?,
+                max_concurrency: cr
+                    .try_get::<Option<i32>>("", "max_concurrency")
+                    .map_err(|e| e.to_string())?
+                    .map(|v| decode_positive_u32(&id, "max_concurrency", i64::from(v)))
+                    .transpose()?,
+                org_id: cr.try_get("", "org_id").map_err(|e| e.to_string())?,
+                project_id: cr.try_get("", "project_id").map_err(|e| e.to_string())?,
                 enabled: cr
                     .try_get::<i32>("", "enabled")
                     .map_err(|e| e.to_string())?
@@ -1162,6 +1465,12 @@ impl MonoizeRoutingStore {
             request_timeout_ms_override,
             extra_fields_whitelist,
             strip_cross_protocol_nested_extra,
+            debug_log_bodies: row
+                .try_get::<i32>("", "debug_log_bodies")
+                .map_err(|e| e.to_string())?
+                != 0,
+            default_temperature: row.try_get("", "default_temperature").unwrap_or(None),
+            default_top_p: row.try_get("", "default_top_p").unwrap_or(None),
             groups,
             enabled: row
                 .try_get::<i32>("", "enabled")
@@ -1178,6 +1487,16 @@ impl MonoizeRoutingStore {
     }
 }
 
+fn validate_sampling_defaults(temperature: Option<f64>, top_p: Option<f64>) -> Result<(), String> {
+    if temperature.is_some_and(|v| !(0.0..=2.0).contains(&v)) {
+        return Err("default_temperature must be between 0 and 2".to_string());
+    }
+    if top_p.is_some_and(|v| !(0.0..=1.0).contains(&v)) {
+        return Err("default_top_p must be between 0 and 1".to_string());
+    }
+    Ok(())
+}
+
 fn opt_bool_to_value(v: Option<bool>) -> SeaValue {
     match v {
         Some(b) => SeaValue::Int(Some(if b { 1 } else { 0 })),
@@ -1192,6 +1511,13 @@ fn opt_u64_to_value(v: Option<u64>) -> SeaValue {
     }
 }
 
+fn normalize_optional_header_value(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
 fn decode_positive_u32(provider_id: &str, field: &str, value: i64) -> Result<u32, String> {
     u32::try_from(value)
         .ok()
@@ -1225,6 +1551,7 @@ fn canonicalize_models(
                     .filter(|value| !value.is_empty())
                     .map(str::to_string),
                 multiplier: entry.multiplier,
+                supports_streaming: entry.supports_streaming,
             },
         );
     }
@@ -1270,6 +1597,18 @@ fn validate_channels(
         if c.weight < 0 {
             return Err("channel weight must be >= 0".to_string());
         }
+        if c.max_concurrency
+            .is_some_and(|v| !(1..=i32::MAX as u32).contains(&v))
+        {
+            return Err("channel max_concurrency must be between 1 and 2147483647".to_string());
+        }
+        for (field, value) in [("org_id", &c.org_id), ("project_id", &c.project_id)] {
+            if let Some(value) = normalize_optional_header_value(value.as_deref())
+                && axum::http::HeaderValue::from_str(&value).is_err()
+            {
+                return Err(format!("channel {field} must be a valid header value"));
+            }
+        }
         if let Some(v) = c.passive_failure_count_threshold_override {
             if !(1..=i32::MAX as u32).contains(&v) {
                 return Err(
@@ -1558,6 +1897,28 @@ fn extract_probe_usage(body: &Value) -> Option<Value> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn half_open_channel_requires_consecutive_probe_successes() {
+        let mut state = ChannelHealthState::new();
+        state.reopen(100, 60);
+        assert!(!state.is_half_open(120));
+        assert!(state.is_half_open(160));
+
+        assert!(!state.record_probe_success(160, 2));
+        assert!(!state.healthy);
+        assert_eq!(state.probe_success_count, 1);
+
+        state.reopen(170, 60);
+        assert_eq!(state.probe_success_count, 0);
+        assert_eq!(state.cooldown_until, Some(230));
+
+        assert!(!state.record_probe_success(230, 2));
+        assert!(state.record_probe_success(231, 2));
+        assert!(state.healthy);
+        assert_eq!(state.cooldown_until, None);
+        assert_eq!(state.probe_success_count, 0);
+    }
+
     #[test]
     fn probe_request_plan_routes_each_api_type() {
         let (resp_url, resp_body, resp_headers, resp_google_auth) = build_probe_request(
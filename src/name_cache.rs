@@ -92,6 +92,7 @@ impl NameCaches {
             reasoning_effort: raw.reasoning_effort.clone(),
             request_ip: raw.request_ip.clone(),
             tried_providers: raw.tried_providers_json.clone(),
+            metadata: raw.metadata_json.clone(),
             provider: RequestLogProvider {
                 id: raw.provider_id.clone(),
                 name: raw
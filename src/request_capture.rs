@@ -337,11 +337,18 @@ mod tests {
             effective_groups: None,
             model_limits_enabled: false,
             model_limits: Vec::new(),
+            allow_models: Vec::new(),
+            deny_models: Vec::new(),
             ip_whitelist: Vec::new(),
             sub_account_enabled: false,
             sub_account_balance_nano: "0".to_string(),
             reasoning_envelope_enabled: true,
             request_capture_mode,
+            response_cache_enabled: false,
+            max_request_charge_nano_usd: None,
+            request_metadata: None,
+            routing_override: None,
+            request_timeout_ms: None,
         }
     }
 
@@ -0,0 +1,128 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// In-memory LRU cache for replayable upstream responses.
+///
+/// TTL and capacity are passed per call so runtime setting changes apply
+/// without rebuilding the cache.
+pub struct ResponseCache<V> {
+    inner: Mutex<Inner<V>>,
+}
+
+struct Inner<V> {
+    entries: HashMap<u128, Entry<V>>,
+    recency: BTreeMap<u64, u128>,
+    next_tick: u64,
+}
+
+struct Entry<V> {
+    value: V,
+    inserted_at: Instant,
+    tick: u64,
+}
+
+impl<V: Clone> Default for ResponseCache<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Clone> ResponseCache<V> {
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+                next_tick: 0,
+            }),
+        }
+    }
+
+    pub fn get(&self, key: u128, ttl: Duration) -> Option<V> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let tick = inner.bump_tick();
+        let entry = inner.entries.get_mut(&key)?;
+        if entry.inserted_at.elapsed() >= ttl {
+            let stale_tick = entry.tick;
+            inner.entries.remove(&key);
+            inner.recency.remove(&stale_tick);
+            return None;
+        }
+        let previous_tick = std::mem::replace(&mut entry.tick, tick);
+        let value = entry.value.clone();
+        inner.recency.remove(&previous_tick);
+        inner.recency.insert(tick, key);
+        Some(value)
+    }
+
+    pub fn insert(&self, key: u128, value: V, max_entries: usize) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let tick = inner.bump_tick();
+        if let Some(previous) = inner.entries.insert(
+            key,
+            Entry {
+                value,
+                inserted_at: Instant::now(),
+                tick,
+            },
+        ) {
+            inner.recency.remove(&previous.tick);
+        }
+        inner.recency.insert(tick, key);
+        while inner.entries.len() > max_entries.max(1) {
+            let Some((_, oldest)) = inner.recency.pop_first() else {
+                break;
+            };
+            inner.entries.remove(&oldest);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entries
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<V> Inner<V> {
+    fn bump_tick(&mut self) -> u64 {
+        self.next_tick += 1;
+        self.next_tick
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn evicts_least_recently_used_entry() {
+        let cache = ResponseCache::new();
+        cache.insert(1, "a", 2);
+        cache.insert(2, "b", 2);
+        assert_eq!(cache.get(1, TTL), Some("a"));
+        cache.insert(3, "c", 2);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(2, TTL), None);
+        assert_eq!(cache.get(1, TTL), Some("a"));
+        assert_eq!(cache.get(3, TTL), Some("c"));
+    }
+
+    #[test]
+    fn expired_entries_are_dropped_on_lookup() {
+        let cache = ResponseCache::new();
+        cache.insert(1, "a", 10);
+        assert_eq!(cache.get(1, Duration::ZERO), None);
+        assert!(cache.is_empty());
+    }
+}
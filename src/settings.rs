@@ -12,6 +12,38 @@ pub struct PricingProfilePattern {
     pub pricing_profile: String,
 }
 
+/// How forwarding treats candidate attempts whose model has no billable pricing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MissingPricingPolicy {
+    /// Serve unpriced attempts without charging.
+    Free,
+    /// Drop unpriced attempts and reject the request if none remain.
+    #[default]
+    Reject,
+    /// Serve unpriced attempts without charging, marking the request log and
+    /// counting the request in metrics.
+    Flag,
+}
+
+impl MissingPricingPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Free => "free",
+            Self::Reject => "reject",
+            Self::Flag => "flag",
+        }
+    }
+
+    pub fn from_db_value(raw: &str) -> Self {
+        match raw.trim() {
+            "free" => Self::Free,
+            "flag" => Self::Flag,
+            _ => Self::Reject,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemSettings {
     pub registration_enabled: bool,
@@ -24,6 +56,19 @@ pub struct SystemSettings {
     #[serde(default)]
     pub global_transforms: Vec<TransformRuleConfig>,
     pub reasoning_suffix_map: HashMap<String, String>,
+    /// Friendly model names resolved to concrete models before routing.
+    #[serde(default)]
+    pub model_aliases: HashMap<String, String>,
+    /// Ordered models tried when every attempt for the requested model fails.
+    #[serde(default)]
+    pub model_fallbacks: HashMap<String, Vec<String>>,
+    /// Per-model delay in milliseconds after which a still-pending non-stream
+    /// attempt is raced against the next eligible channel.
+    #[serde(default)]
+    pub model_hedge_after_ms: HashMap<String, u64>,
+    /// Echo the fallback model that served the request instead of the requested one.
+    #[serde(default)]
+    pub model_fallback_report_actual_model: bool,
     #[serde(default = "default_pricing_profile_model_patterns")]
     pub pricing_profile_model_patterns: Vec<PricingProfilePattern>,
     pub monoize_active_probe_enabled: bool,
@@ -38,6 +83,13 @@ pub struct SystemSettings {
     pub monoize_passive_rate_limit_cooldown_seconds: u64,
     pub monoize_request_timeout_ms: u64,
     pub monoize_stream_idle_timeout_ms: u64,
+    /// Ceiling for the per-request `x-monoize-timeout-ms` header.
+    #[serde(default = "default_max_request_timeout_ms")]
+    pub monoize_max_request_timeout_ms: u64,
+    #[serde(default = "default_connect_timeout_ms")]
+    pub monoize_connect_timeout_ms: u64,
+    #[serde(default = "default_read_timeout_ms")]
+    pub monoize_read_timeout_ms: u64,
     pub monoize_enable_estimated_billing: bool,
     #[serde(default)]
     pub monoize_extra_fields_whitelist: HashMap<String, Vec<String>>,
@@ -45,6 +97,65 @@ pub struct SystemSettings {
     pub monoize_strip_cross_protocol_nested_extra: bool,
     pub monoize_request_capture_enabled: bool,
     pub monoize_request_capture_retention_days: u64,
+    #[serde(default = "default_response_cache_ttl_seconds")]
+    pub monoize_response_cache_ttl_seconds: u64,
+    #[serde(default = "default_response_cache_max_entries")]
+    pub monoize_response_cache_max_entries: u64,
+    #[serde(default = "default_idempotency_ttl_seconds")]
+    pub monoize_idempotency_ttl_seconds: u64,
+    #[serde(default = "default_idempotency_max_entries")]
+    pub monoize_idempotency_max_entries: u64,
+    /// Undecodable 2xx upstream bodies retried per request before failing.
+    #[serde(default = "default_decode_retry_limit")]
+    pub monoize_decode_retry_limit: u64,
+    #[serde(default)]
+    pub monoize_missing_pricing_policy: MissingPricingPolicy,
+    /// Upper bound on what a single request is charged; 0 disables the cap.
+    #[serde(default)]
+    pub monoize_max_request_charge_nano_usd: u64,
+    /// Embeddings `input` arrays longer than this are split across several
+    /// upstream calls; 0 disables splitting.
+    #[serde(default = "default_max_embedding_batch")]
+    pub monoize_max_embedding_batch: u64,
+    /// Streaming requests served by buffering a full upstream response and
+    /// re-emitting it as a synthetic stream that may run at once; 0 disables
+    /// the limit.
+    #[serde(default)]
+    pub monoize_max_buffered_transcodes: u64,
+    /// Canonical finish reason for raw upstream finish reasons that decoders
+    /// map to `other`, keyed by the raw string.
+    #[serde(default)]
+    pub monoize_finish_reason_map: HashMap<String, crate::urp::FinishReason>,
+    /// Attach `x-monoize-*` response headers naming the provider, channel, and
+    /// upstream model that served a request. Off by default because it
+    /// reveals routing internals to API clients.
+    #[serde(default)]
+    pub monoize_expose_routing_headers: bool,
+    /// Divide each channel's routing weight by `1 + failure_count` of its
+    /// recent passive samples, so a degrading channel sheds traffic before
+    /// the breaker ejects it. Off keeps plain weighted selection.
+    #[serde(default)]
+    pub monoize_failure_weighted_routing: bool,
+    /// Bill Gemini streams that end without `usageMetadata` from the
+    /// visible-output estimate. Off skips billing for such streams even when
+    /// estimated billing is enabled for other providers.
+    #[serde(default)]
+    pub monoize_estimate_gemini_stream_usage: bool,
+    /// Strict gateway mode: reject decoded requests that break URP invariants
+    /// with a 400 listing every violation, before routing.
+    #[serde(default)]
+    pub monoize_validate_requests: bool,
+    /// `retry:` reconnection hint in milliseconds sent on the first event of
+    /// every API stream; 0 omits it.
+    #[serde(default)]
+    pub monoize_sse_retry_ms: u64,
+    /// Number API stream events with sequential `id:` fields starting at 1.
+    #[serde(default)]
+    pub monoize_sse_event_ids: bool,
+    /// Counts tokens for worst-case pre-charges and for streams billed from
+    /// an estimate: `heuristic` (four bytes per token) or `tiktoken`.
+    #[serde(default)]
+    pub monoize_token_estimator: crate::token_estimator::TokenEstimatorKind,
     pub updated_at: DateTime<Utc>,
 }
 
@@ -91,6 +202,42 @@ fn default_true() -> bool {
     true
 }
 
+fn default_max_request_timeout_ms() -> u64 {
+    600_000
+}
+
+fn default_connect_timeout_ms() -> u64 {
+    10_000
+}
+
+fn default_read_timeout_ms() -> u64 {
+    300_000
+}
+
+fn default_response_cache_ttl_seconds() -> u64 {
+    300
+}
+
+fn default_response_cache_max_entries() -> u64 {
+    1_000
+}
+
+fn default_idempotency_ttl_seconds() -> u64 {
+    86_400
+}
+
+fn default_idempotency_max_entries() -> u64 {
+    10_000
+}
+
+fn default_decode_retry_limit() -> u64 {
+    1
+}
+
+fn default_max_embedding_batch() -> u64 {
+    2_048
+}
+
 fn default_reasoning_suffix_map() -> HashMap<String, String> {
     let mut m = HashMap::new();
     m.insert("-thinking".to_string(), "high".to_string());
@@ -148,6 +295,10 @@ impl Default for SystemSettings {
             api_base_url: String::new(),
             global_transforms: Vec::new(),
             reasoning_suffix_map: default_reasoning_suffix_map(),
+            model_aliases: HashMap::new(),
+            model_fallbacks: HashMap::new(),
+            model_hedge_after_ms: HashMap::new(),
+            model_fallback_report_actual_model: false,
             pricing_profile_model_patterns: default_pricing_profile_model_patterns(),
             monoize_active_probe_enabled: true,
             monoize_active_probe_interval_seconds: 30,
@@ -161,11 +312,31 @@ impl Default for SystemSettings {
             monoize_passive_rate_limit_cooldown_seconds: 15,
             monoize_request_timeout_ms: 30000,
             monoize_stream_idle_timeout_ms: 120000,
+            monoize_max_request_timeout_ms: default_max_request_timeout_ms(),
+            monoize_connect_timeout_ms: default_connect_timeout_ms(),
+            monoize_read_timeout_ms: default_read_timeout_ms(),
             monoize_enable_estimated_billing: true,
             monoize_extra_fields_whitelist: HashMap::new(),
             monoize_strip_cross_protocol_nested_extra: true,
             monoize_request_capture_enabled: false,
             monoize_request_capture_retention_days: 1,
+            monoize_response_cache_ttl_seconds: default_response_cache_ttl_seconds(),
+            monoize_response_cache_max_entries: default_response_cache_max_entries(),
+            monoize_idempotency_ttl_seconds: default_idempotency_ttl_seconds(),
+            monoize_idempotency_max_entries: default_idempotency_max_entries(),
+            monoize_decode_retry_limit: default_decode_retry_limit(),
+            monoize_missing_pricing_policy: MissingPricingPolicy::default(),
+            monoize_max_request_charge_nano_usd: 0,
+            monoize_max_embedding_batch: default_max_embedding_batch(),
+            monoize_max_buffered_transcodes: 0,
+            monoize_finish_reason_map: HashMap::new(),
+            monoize_expose_routing_headers: false,
+            monoize_failure_weighted_routing: false,
+            monoize_estimate_gemini_stream_usage: false,
+            monoize_validate_requests: false,
+            monoize_sse_retry_ms: 0,
+            monoize_sse_event_ids: false,
+            monoize_token_estimator: crate::token_estimator::TokenEstimatorKind::default(),
             updated_at: Utc::now(),
         }
     }
@@ -237,6 +408,26 @@ impl SettingsStore {
             &serde_json::to_string(&defaults.reasoning_suffix_map).unwrap(),
         )
         .await?;
+        self.set_if_not_exists(
+            "model_aliases",
+            &serde_json::to_string(&defaults.model_aliases).unwrap(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "model_fallbacks",
+            &serde_json::to_string(&defaults.model_fallbacks).unwrap(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "model_hedge_after_ms",
+            &serde_json::to_string(&defaults.model_hedge_after_ms).unwrap(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "model_fallback_report_actual_model",
+            &defaults.model_fallback_report_actual_model.to_string(),
+        )
+        .await?;
         self.set_if_not_exists(
             "pricing_profile_model_patterns",
             &serde_json::to_string(&defaults.pricing_profile_model_patterns)
@@ -308,6 +499,21 @@ impl SettingsStore {
             &defaults.monoize_stream_idle_timeout_ms.to_string(),
         )
         .await?;
+        self.set_if_not_exists(
+            "monoize_max_request_timeout_ms",
+            &defaults.monoize_max_request_timeout_ms.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_connect_timeout_ms",
+            &defaults.monoize_connect_timeout_ms.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_read_timeout_ms",
+            &defaults.monoize_read_timeout_ms.to_string(),
+        )
+        .await?;
         self.set_if_not_exists(
             "monoize_enable_estimated_billing",
             &defaults.monoize_enable_estimated_billing.to_string(),
@@ -336,6 +542,92 @@ impl SettingsStore {
             &defaults.monoize_request_capture_retention_days.to_string(),
         )
         .await?;
+        self.set_if_not_exists(
+            "monoize_response_cache_ttl_seconds",
+            &defaults.monoize_response_cache_ttl_seconds.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_response_cache_max_entries",
+            &defaults.monoize_response_cache_max_entries.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_idempotency_ttl_seconds",
+            &defaults.monoize_idempotency_ttl_seconds.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_idempotency_max_entries",
+            &defaults.monoize_idempotency_max_entries.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_decode_retry_limit",
+            &defaults.monoize_decode_retry_limit.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_missing_pricing_policy",
+            defaults.monoize_missing_pricing_policy.as_str(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_max_request_charge_nano_usd",
+            &defaults.monoize_max_request_charge_nano_usd.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_max_embedding_batch",
+            &defaults.monoize_max_embedding_batch.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_max_buffered_transcodes",
+            &defaults.monoize_max_buffered_transcodes.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_finish_reason_map",
+            &serde_json::to_string(&defaults.monoize_finish_reason_map)
+                .unwrap_or_else(|_| "{}".to_string()),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_expose_routing_headers",
+            &defaults.monoize_expose_routing_headers.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_failure_weighted_routing",
+            &defaults.monoize_failure_weighted_routing.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_estimate_gemini_stream_usage",
+            &defaults.monoize_estimate_gemini_stream_usage.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_validate_requests",
+            &defaults.monoize_validate_requests.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_sse_retry_ms",
+            &defaults.monoize_sse_retry_ms.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_sse_event_ids",
+            &defaults.monoize_sse_event_ids.to_string(),
+        )
+        .await?;
+        self.set_if_not_exists(
+            "monoize_token_estimator",
+            defaults.monoize_token_estimator.as_str(),
+        )
+        .await?;
         Ok(())
     }
 
@@ -449,6 +741,25 @@ impl SettingsStore {
                         settings.reasoning_suffix_map = map;
                     }
                 }
+                "model_aliases" => {
+                    if let Ok(map) = serde_json::from_str(&row.value) {
+                        settings.model_aliases = map;
+                    }
+                }
+                "model_fallbacks" => {
+                    if let Ok(map) = serde_json::from_str(&row.value) {
+                        settings.model_fallbacks = map;
+                    }
+                }
+                "model_hedge_after_ms" => {
+                    if let Ok(map) = serde_json::from_str(&row.value) {
+                        settings.model_hedge_after_ms = map;
+                    }
+                }
+                "model_fallback_report_actual_model" => {
+                    settings.model_fallback_report_actual_model =
+                        row.value.parse().unwrap_or(false);
+                }
                 "pricing_profile_model_patterns" => {
                     if let Ok(patterns) = serde_json::from_str(&row.value) {
                         settings.pricing_profile_model_patterns = patterns;
@@ -499,6 +810,24 @@ impl SettingsStore {
                 "monoize_stream_idle_timeout_ms" => {
                     settings.monoize_stream_idle_timeout_ms = row.value.parse().unwrap_or(120000);
                 }
+                "monoize_max_request_timeout_ms" => {
+                    settings.monoize_max_request_timeout_ms = row
+                        .value
+                        .parse()
+                        .unwrap_or_else(|_| default_max_request_timeout_ms());
+                }
+                "monoize_connect_timeout_ms" => {
+                    settings.monoize_connect_timeout_ms = row
+                        .value
+                        .parse()
+                        .unwrap_or_else(|_| default_connect_timeout_ms());
+                }
+                "monoize_read_timeout_ms" => {
+                    settings.monoize_read_timeout_ms = row
+                        .value
+                        .parse()
+                        .unwrap_or_else(|_| default_read_timeout_ms());
+                }
                 "monoize_enable_estimated_billing" => {
                     settings.monoize_enable_estimated_billing = row.value.parse().unwrap_or(true);
                 }
@@ -518,6 +847,80 @@ impl SettingsStore {
                     settings.monoize_request_capture_retention_days =
                         row.value.parse().unwrap_or(1);
                 }
+                "monoize_response_cache_ttl_seconds" => {
+                    settings.monoize_response_cache_ttl_seconds = row
+                        .value
+                        .parse()
+                        .unwrap_or_else(|_| default_response_cache_ttl_seconds());
+                }
+                "monoize_response_cache_max_entries" => {
+                    settings.monoize_response_cache_max_entries = row
+                        .value
+                        .parse()
+                        .unwrap_or_else(|_| default_response_cache_max_entries());
+                }
+                "monoize_idempotency_ttl_seconds" => {
+                    settings.monoize_idempotency_ttl_seconds = row
+                        .value
+                        .parse()
+                        .unwrap_or_else(|_| default_idempotency_ttl_seconds());
+                }
+                "monoize_idempotency_max_entries" => {
+                    settings.monoize_idempotency_max_entries = row
+                        .value
+                        .parse()
+                        .unwrap_or_else(|_| default_idempotency_max_entries());
+                }
+                "monoize_decode_retry_limit" => {
+                    settings.monoize_decode_retry_limit = row
+                        .value
+                        .parse()
+                        .unwrap_or_else(|_| default_decode_retry_limit());
+                }
+                "monoize_missing_pricing_policy" => {
+                    settings.monoize_missing_pricing_policy =
+                        MissingPricingPolicy::from_db_value(&row.value);
+                }
+                "monoize_max_request_charge_nano_usd" => {
+                    settings.monoize_max_request_charge_nano_usd = row.value.parse().unwrap_or(0);
+                }
+                "monoize_max_embedding_batch" => {
+                    settings.monoize_max_embedding_batch = row
+                        .value
+                        .parse()
+                        .unwrap_or_else(|_| default_max_embedding_batch());
+                }
+                "monoize_max_buffered_transcodes" => {
+                    settings.monoize_max_buffered_transcodes = row.value.parse().unwrap_or(0);
+                }
+                "monoize_finish_reason_map" => {
+                    if let Ok(map) = serde_json::from_str(&row.value) {
+                        settings.monoize_finish_reason_map = map;
+                    }
+                }
+                "monoize_expose_routing_headers" => {
+                    settings.monoize_expose_routing_headers = row.value.parse().unwrap_or(false);
+                }
+                "monoize_failure_weighted_routing" => {
+                    settings.monoize_failure_weighted_routing = row.value.parse().unwrap_or(false);
+                }
+                "monoize_estimate_gemini_stream_usage" => {
+                    settings.monoize_estimate_gemini_stream_usage =
+                        row.value.parse().unwrap_or(false);
+                }
+                "monoize_validate_requests" => {
+                    settings.monoize_validate_requests = row.value.parse().unwrap_or(false);
+                }
+                "monoize_sse_retry_ms" => {
+                    settings.monoize_sse_retry_ms = row.value.parse().unwrap_or(0);
+                }
+                "monoize_sse_event_ids" => {
+                    settings.monoize_sse_event_ids = row.value.parse().unwrap_or(false);
+                }
+                "monoize_token_estimator" => {
+                    settings.monoize_token_estimator =
+                        crate::token_estimator::TokenEstimatorKind::from_db_value(&row.value);
+                }
                 _ => {}
             }
         }
@@ -559,6 +962,27 @@ impl SettingsStore {
                 .unwrap_or_else(|_| "{}".to_string()),
         )
         .await?;
+        self.set(
+            "model_aliases",
+            &serde_json::to_string(&settings.model_aliases).unwrap_or_else(|_| "{}".to_string()),
+        )
+        .await?;
+        self.set(
+            "model_fallbacks",
+            &serde_json::to_string(&settings.model_fallbacks).unwrap_or_else(|_| "{}".to_string()),
+        )
+        .await?;
+        self.set(
+            "model_hedge_after_ms",
+            &serde_json::to_string(&settings.model_hedge_after_ms)
+                .unwrap_or_else(|_| "{}".to_string()),
+        )
+        .await?;
+        self.set(
+            "model_fallback_report_actual_model",
+            &settings.model_fallback_report_actual_model.to_string(),
+        )
+        .await?;
         self.set(
             "pricing_profile_model_patterns",
             &serde_json::to_string(&settings.pricing_profile_model_patterns)
@@ -627,6 +1051,21 @@ impl SettingsStore {
             &settings.monoize_stream_idle_timeout_ms.to_string(),
         )
         .await?;
+        self.set(
+            "monoize_max_request_timeout_ms",
+            &settings.monoize_max_request_timeout_ms.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_connect_timeout_ms",
+            &settings.monoize_connect_timeout_ms.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_read_timeout_ms",
+            &settings.monoize_read_timeout_ms.to_string(),
+        )
+        .await?;
         self.set(
             "monoize_enable_estimated_billing",
             &settings.monoize_enable_estimated_billing.to_string(),
@@ -658,6 +1097,98 @@ impl SettingsStore {
                 .to_string(),
         )
         .await?;
+        self.set(
+            "monoize_response_cache_ttl_seconds",
+            &settings
+                .monoize_response_cache_ttl_seconds
+                .max(1)
+                .to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_response_cache_max_entries",
+            &settings
+                .monoize_response_cache_max_entries
+                .max(1)
+                .to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_idempotency_ttl_seconds",
+            &settings.monoize_idempotency_ttl_seconds.max(1).to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_idempotency_max_entries",
+            &settings.monoize_idempotency_max_entries.max(1).to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_decode_retry_limit",
+            &settings.monoize_decode_retry_limit.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_missing_pricing_policy",
+            settings.monoize_missing_pricing_policy.as_str(),
+        )
+        .await?;
+        self.set(
+            "monoize_max_request_charge_nano_usd",
+            &settings.monoize_max_request_charge_nano_usd.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_max_embedding_batch",
+            &settings.monoize_max_embedding_batch.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_max_buffered_transcodes",
+            &settings.monoize_max_buffered_transcodes.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_finish_reason_map",
+            &serde_json::to_string(&settings.monoize_finish_reason_map)
+                .unwrap_or_else(|_| "{}".to_string()),
+        )
+        .await?;
+        self.set(
+            "monoize_expose_routing_headers",
+            &settings.monoize_expose_routing_headers.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_failure_weighted_routing",
+            &settings.monoize_failure_weighted_routing.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_estimate_gemini_stream_usage",
+            &settings.monoize_estimate_gemini_stream_usage.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_validate_requests",
+            &settings.monoize_validate_requests.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_sse_retry_ms",
+            &settings.monoize_sse_retry_ms.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_sse_event_ids",
+            &settings.monoize_sse_event_ids.to_string(),
+        )
+        .await?;
+        self.set(
+            "monoize_token_estimator",
+            settings.monoize_token_estimator.as_str(),
+        )
+        .await?;
         Ok(())
     }
 
@@ -0,0 +1,177 @@
+//! Token counts for text whose upstream usage is unknown: worst-case
+//! pre-charge input and streams that end without a usage report.
+
+use crate::urp::Node;
+use serde::{Deserialize, Serialize};
+use tiktoken_rs::CoreBPE;
+use tiktoken_rs::tokenizer::{Tokenizer, get_tokenizer};
+
+pub trait TokenEstimator: Send + Sync {
+    /// Estimated token count of the visible text in `nodes` when sent to or
+    /// produced by `model`.
+    fn estimate(&self, nodes: &[Node], model: &str) -> u64;
+}
+
+/// Four bytes per token over the summed visible text.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicEstimator;
+
+impl TokenEstimator for HeuristicEstimator {
+    fn estimate(&self, nodes: &[Node], _model: &str) -> u64 {
+        let bytes: u64 = visible_text(nodes).map(|text| text.len() as u64).sum();
+        bytes.div_ceil(4)
+    }
+}
+
+/// BPE token counts. Models tiktoken maps to `o200k_base` (the o-series,
+/// `gpt-4o`, `gpt-4.1`, `gpt-5`, ...) use it; every other model, including
+/// non-OpenAI ones, is counted with `cl100k_base`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TiktokenEstimator;
+
+impl TiktokenEstimator {
+    fn bpe_for_model(model: &str) -> &'static CoreBPE {
+        match get_tokenizer(model) {
+            Some(Tokenizer::O200kBase | Tokenizer::O200kHarmony) => {
+                tiktoken_rs::o200k_base_singleton()
+            }
+            _ => tiktoken_rs::cl100k_base_singleton(),
+        }
+    }
+}
+
+impl TokenEstimator for TiktokenEstimator {
+    fn estimate(&self, nodes: &[Node], model: &str) -> u64 {
+        let bpe = Self::bpe_for_model(model);
+        visible_text(nodes)
+            .map(|text| bpe.encode_ordinary(text).len() as u64)
+            .sum()
+    }
+}
+
+/// Which estimator counts tokens, selected by the `monoize_token_estimator`
+/// setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenEstimatorKind {
+    #[default]
+    Heuristic,
+    Tiktoken,
+}
+
+impl TokenEstimatorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Heuristic => "heuristic",
+            Self::Tiktoken => "tiktoken",
+        }
+    }
+
+    pub fn from_db_value(raw: &str) -> Self {
+        match raw.trim() {
+            "tiktoken" => Self::Tiktoken,
+            _ => Self::Heuristic,
+        }
+    }
+
+    pub fn estimator(self) -> &'static dyn TokenEstimator {
+        match self {
+            Self::Heuristic => &HeuristicEstimator,
+            Self::Tiktoken => &TiktokenEstimator,
+        }
+    }
+}
+
+/// Text and reasoning content of `nodes`; tool calls, media, and provider
+/// items are not counted.
+fn visible_text(nodes: &[Node]) -> impl Iterator<Item = &str> {
+    nodes.iter().flat_map(|node| {
+        let (first, second) = match node {
+            Node::Text { content, .. } | Node::Refusal { content, .. } => {
+                (Some(content.as_str()), None)
+            }
+            Node::Reasoning {
+                content, summary, ..
+            } => (content.as_deref(), summary.as_deref()),
+            _ => (None, None),
+        };
+        first.into_iter().chain(second)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::urp::OrdinaryRole;
+    use std::collections::HashMap;
+
+    fn text(content: &str) -> Node {
+        Node::Text {
+            id: None,
+            role: OrdinaryRole::User,
+            content: content.to_string(),
+            phase: None,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn tiktoken_matches_known_counts_for_sample_prompts() {
+        let cases = [
+            ("hello world", "gpt-4", 2),
+            ("hello world", "gpt-4o", 2),
+            ("tiktoken is great!", "gpt-4", 6),
+            ("tiktoken is great!", "gpt-4o", 6),
+            ("お誕生日おめでとう", "gpt-4", 9),
+            ("お誕生日おめでとう", "gpt-4o", 8),
+        ];
+        for (prompt, model, expected) in cases {
+            assert_eq!(
+                TiktokenEstimator.estimate(&[text(prompt)], model),
+                expected,
+                "{prompt:?} on {model}"
+            );
+        }
+    }
+
+    #[test]
+    fn tiktoken_selects_the_encoding_by_model_family() {
+        assert!(std::ptr::eq(
+            TiktokenEstimator::bpe_for_model("gpt-5-mini"),
+            tiktoken_rs::o200k_base_singleton()
+        ));
+        assert!(std::ptr::eq(
+            TiktokenEstimator::bpe_for_model("gpt-3.5-turbo"),
+            tiktoken_rs::cl100k_base_singleton()
+        ));
+        assert!(std::ptr::eq(
+            TiktokenEstimator::bpe_for_model("claude-sonnet-4"),
+            tiktoken_rs::cl100k_base_singleton()
+        ));
+    }
+
+    #[test]
+    fn estimators_count_text_and_reasoning_but_not_tool_calls() {
+        let nodes = vec![
+            text("hello world"),
+            Node::Reasoning {
+                id: None,
+                content: Some("hello world".to_string()),
+                encrypted: None,
+                summary: Some("hello world".to_string()),
+                source: None,
+                extra_body: HashMap::new(),
+            },
+            Node::ToolCall {
+                id: None,
+                tool_type: Default::default(),
+                call_id: "call_1".to_string(),
+                name: "lookup".to_string(),
+                arguments: "{\"q\":\"hello world\"}".to_string(),
+                extra_body: HashMap::new(),
+            },
+        ];
+        assert_eq!(TiktokenEstimator.estimate(&nodes, "gpt-4o"), 6);
+        assert_eq!(HeuristicEstimator.estimate(&nodes, "gpt-4o"), 9);
+    }
+}
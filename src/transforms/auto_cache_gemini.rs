@@ -0,0 +1,434 @@
+use crate::config::ProviderType;
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformState, UrpData,
+};
+use crate::urp::{Node, OrdinaryRole, UrpRequest};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+use std::time::Duration;
+
+const DEFAULT_MIN_CHARS: usize = 16_000;
+const DEFAULT_TTL_SECONDS: u64 = 3_600;
+/// A cached name stops being reused this long before Gemini expires it, so a
+/// request sent at the end of the window does not reference a deleted cache.
+const REUSE_MARGIN_SECONDS: u64 = 60;
+const MIN_TTL_SECONDS: u64 = 2 * REUSE_MARGIN_SECONDS;
+const MAX_TRACKED_CACHES: usize = 1_024;
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Config {
+    min_chars: usize,
+    ttl_seconds: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            min_chars: DEFAULT_MIN_CHARS,
+            ttl_seconds: DEFAULT_TTL_SECONDS,
+        }
+    }
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct AutoCacheGeminiTransform;
+
+/// Moves a large system prompt into a Gemini `cachedContent` resource and
+/// references it from the request. Resources are created once per channel,
+/// model, and system prompt, then reused until shortly before they expire.
+#[async_trait]
+impl Transform for AutoCacheGeminiTransform {
+    fn type_id(&self) -> &'static str {
+        "auto_cache_gemini"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "min_chars": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "default": DEFAULT_MIN_CHARS,
+                    "description": "Minimum total system prompt length in characters before a cache is used"
+                },
+                "ttl_seconds": {
+                    "type": "integer",
+                    "minimum": MIN_TTL_SECONDS,
+                    "default": DEFAULT_TTL_SECONDS,
+                    "description": "Lifetime of each created cachedContent resource"
+                }
+            },
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        if cfg.ttl_seconds < MIN_TTL_SECONDS {
+            return Err(TransformError::InvalidConfig(format!(
+                "ttl_seconds must be at least {MIN_TTL_SECONDS}"
+            )));
+        }
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        let UrpData::Request(req) = data else {
+            return Ok(());
+        };
+        let Some(channel) = context.upstream_channel.as_ref() else {
+            return Ok(());
+        };
+        if context.upstream_provider_type != Some(ProviderType::Gemini)
+            || !is_cacheable(req, cfg.min_chars)
+        {
+            return Ok(());
+        }
+
+        let system_instruction = encode_system_instruction(req);
+        let key = xxhash_rust::xxh3::xxh3_128(
+            json!({
+                "base_url": channel.provider.base_url,
+                "api_key": channel.api_key,
+                "model": req.model,
+                "ttl_seconds": cfg.ttl_seconds,
+                "system_instruction": system_instruction,
+            })
+            .to_string()
+            .as_bytes(),
+        );
+        let reuse_window = Duration::from_secs(cfg.ttl_seconds - REUSE_MARGIN_SECONDS);
+        let name = match context.gemini_context_cache.get(key, reuse_window) {
+            Some(name) => name,
+            None => {
+                let body = json!({
+                    "model": gemini_model_resource(&req.model),
+                    "systemInstruction": system_instruction,
+                    "ttl": format!("{}s", cfg.ttl_seconds),
+                });
+                match crate::upstream::create_gemini_cache(
+                    &context.http_client,
+                    &channel.provider,
+                    &channel.api_key,
+                    &body,
+                    channel.request_timeout_ms,
+                )
+                .await
+                {
+                    Ok(name) => {
+                        context
+                            .gemini_context_cache
+                            .insert(key, name.clone(), MAX_TRACKED_CACHES);
+                        name
+                    }
+                    Err(err) => {
+                        // The request is still valid without the cache; send it
+                        // uncached rather than failing the attempt.
+                        tracing::warn!(
+                            channel_id = %channel.channel_id,
+                            error = %err.message,
+                            "gemini cachedContent creation failed"
+                        );
+                        return Ok(());
+                    }
+                }
+            }
+        };
+
+        req.input.retain(|node| !is_system_text(node));
+        req.extra_body
+            .insert("cachedContent".to_string(), Value::String(name));
+        Ok(())
+    }
+}
+
+/// Gemini rejects `cachedContent` combined with a request-level
+/// `systemInstruction`, `tools`, or `toolConfig`, so only tool-free requests
+/// are rewritten.
+fn is_cacheable(req: &UrpRequest, min_chars: usize) -> bool {
+    if req.extra_body.contains_key("cachedContent")
+        || req.tools.as_ref().is_some_and(|tools| !tools.is_empty())
+        || req.tool_choice.is_some()
+    {
+        return false;
+    }
+    let system_chars: usize = req
+        .input
+        .iter()
+        .filter_map(|node| match node {
+            Node::Text {
+                role: OrdinaryRole::System | OrdinaryRole::Developer,
+                content,
+                ..
+            } => Some(content.chars().count()),
+            _ => None,
+        })
+        .sum();
+    system_chars > 0 && system_chars >= min_chars
+}
+
+fn is_system_text(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::Text {
+            role: OrdinaryRole::System | OrdinaryRole::Developer,
+            ..
+        }
+    )
+}
+
+/// Builds `systemInstruction` the same way the Gemini encoder does: one text
+/// part per non-empty system or developer text node.
+fn encode_system_instruction(req: &UrpRequest) -> Value {
+    let parts: Vec<Value> = req
+        .input
+        .iter()
+        .filter_map(|node| match node {
+            Node::Text {
+                role: OrdinaryRole::System | OrdinaryRole::Developer,
+                content,
+                ..
+            } if !content.is_empty() => Some(json!({ "text": content })),
+            _ => None,
+        })
+        .collect();
+    json!({ "parts": parts })
+}
+
+fn gemini_model_resource(model: &str) -> String {
+    if model.starts_with("models/") {
+        model.to_string()
+    } else {
+        format!("models/{model}")
+    }
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(AutoCacheGeminiTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ProviderAuthConfig, ProviderAuthType, ProviderConfig};
+    use crate::image_transform_cache::ImageTransformCache;
+    use crate::transforms::UpstreamChannelContext;
+    use axum::Json;
+    use axum::routing::post;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+
+    type Created = Arc<Mutex<Vec<Value>>>;
+
+    async fn mock_gemini() -> (String, Created) {
+        let created: Created = Arc::new(Mutex::new(Vec::new()));
+        let router = axum::Router::new().route(
+            "/v1beta/cachedContents",
+            post({
+                let created = created.clone();
+                move |Json(body): Json<Value>| {
+                    let created = created.clone();
+                    async move {
+                        let mut created = created.lock().expect("created");
+                        created.push(body);
+                        Json(json!({ "name": format!("cachedContents/c{}", created.len()) }))
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.expect("mock gemini");
+        });
+        (format!("http://{addr}"), created)
+    }
+
+    async fn context(base_url: &str, temp_dir: &TempDir) -> TransformRuntimeContext {
+        let cache =
+            ImageTransformCache::new(temp_dir.path().join("cache"), Duration::from_secs(60))
+                .await
+                .expect("cache");
+        TransformRuntimeContext {
+            image_transform_cache: Arc::new(cache),
+            http_client: reqwest::Client::new(),
+            upstream_provider_type: Some(ProviderType::Gemini),
+            upstream_channel: Some(UpstreamChannelContext {
+                channel_id: "ch-1".to_string(),
+                provider: ProviderConfig {
+                    id: "gemini_ch-1".to_string(),
+                    provider_type: ProviderType::Gemini,
+                    base_url: Some(base_url.to_string()),
+                    auth: Some(ProviderAuthConfig {
+                        auth_type: ProviderAuthType::Header,
+                        value: String::new(),
+                        header_name: Some("x-goog-api-key".to_string()),
+                        query_name: None,
+                    }),
+                    model_map: Vec::new(),
+                    strategy: None,
+                    members: Vec::new(),
+                    extra_headers: Vec::new(),
+                },
+                api_key: "gm-test".to_string(),
+                request_timeout_ms: 5_000,
+            }),
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
+        }
+    }
+
+    fn request(system: &str) -> UrpRequest {
+        UrpRequest {
+            model: "gemini-2.5-pro".to_string(),
+            input: vec![
+                Node::text(OrdinaryRole::System, system),
+                Node::text(OrdinaryRole::User, "summarize"),
+            ],
+            stream: None,
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            max_output_tokens: None,
+            reasoning: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            stop: None,
+            verbosity: None,
+            response_format: None,
+            user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    async fn apply(context: &TransformRuntimeContext, raw: Value, req: &mut UrpRequest) {
+        let transform = AutoCacheGeminiTransform;
+        let config = transform.parse_config(raw).expect("config");
+        let mut state = transform.init_state();
+        transform
+            .apply(
+                UrpData::Request(req),
+                Phase::Request,
+                context,
+                config.as_ref(),
+                state.as_mut(),
+            )
+            .await
+            .expect("apply");
+    }
+
+    #[tokio::test]
+    async fn reuses_created_cache_for_identical_system_prompt() {
+        let (base_url, created) = mock_gemini().await;
+        let temp_dir = TempDir::new().expect("temp dir");
+        let context = context(&base_url, &temp_dir).await;
+        let cfg = json!({ "min_chars": 10 });
+
+        let mut first = request("You are a long-context analyst.");
+        apply(&context, cfg.clone(), &mut first).await;
+        let mut second = request("You are a long-context analyst.");
+        apply(&context, cfg.clone(), &mut second).await;
+
+        for req in [&first, &second] {
+            assert_eq!(
+                req.extra_body.get("cachedContent"),
+                Some(&json!("cachedContents/c1"))
+            );
+            assert_eq!(req.input.len(), 1);
+            let encoded = crate::urp::encode::gemini::encode_request(req, "gemini-2.5-pro");
+            assert!(encoded.get("systemInstruction").is_none());
+            assert_eq!(encoded["cachedContent"], json!("cachedContents/c1"));
+        }
+        {
+            let created = created.lock().expect("created");
+            assert_eq!(created.len(), 1);
+            assert_eq!(created[0]["model"], json!("models/gemini-2.5-pro"));
+            assert_eq!(created[0]["ttl"], json!("3600s"));
+            assert_eq!(
+                created[0]["systemInstruction"],
+                json!({ "parts": [{ "text": "You are a long-context analyst." }] })
+            );
+        }
+
+        let mut other = request("You are a different long-context analyst.");
+        apply(&context, cfg, &mut other).await;
+        assert_eq!(
+            other.extra_body.get("cachedContent"),
+            Some(&json!("cachedContents/c2"))
+        );
+        assert_eq!(created.lock().expect("created").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn leaves_short_prompts_and_tool_requests_untouched() {
+        let (base_url, created) = mock_gemini().await;
+        let temp_dir = TempDir::new().expect("temp dir");
+        let context = context(&base_url, &temp_dir).await;
+
+        let mut short = request("Be brief.");
+        apply(&context, json!({}), &mut short).await;
+        assert_eq!(short.input.len(), 2);
+
+        let mut with_tools = request("You are a long-context analyst.");
+        with_tools.tool_choice = Some(crate::urp::ToolChoice::Mode("auto".to_string()));
+        apply(&context, json!({ "min_chars": 10 }), &mut with_tools).await;
+        assert_eq!(with_tools.input.len(), 2);
+        assert!(!with_tools.extra_body.contains_key("cachedContent"));
+
+        assert!(created.lock().expect("created").is_empty());
+    }
+
+    #[test]
+    fn rejects_ttl_shorter_than_reuse_margin() {
+        let transform = AutoCacheGeminiTransform;
+        assert!(
+            transform
+                .parse_config(json!({ "ttl_seconds": 60 }))
+                .is_err()
+        );
+        assert!(
+            transform
+                .parse_config(json!({ "ttl_seconds": 120 }))
+                .is_ok()
+        );
+    }
+}
@@ -273,6 +273,9 @@ mod tests {
             image_transform_cache: std::sync::Arc::new(cache),
             http_client: reqwest::Client::new(),
             upstream_provider_type: provider_type,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
         }
     }
 
@@ -298,6 +301,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(vec![ToolDefinition {
@@ -325,6 +329,11 @@ mod tests {
             verbosity: None,
             response_format: Some(ResponseFormat::Text),
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         }
     }
@@ -133,6 +133,7 @@ fn node_has_cache_control(node: &Node) -> bool {
         Node::Text { extra_body, .. }
         | Node::Image { extra_body, .. }
         | Node::Audio { extra_body, .. }
+        | Node::Video { extra_body, .. }
         | Node::File { extra_body, .. }
         | Node::Refusal { extra_body, .. }
         | Node::Reasoning { extra_body, .. }
@@ -103,6 +103,7 @@ fn has_any_cache_control(req: &crate::urp::UrpRequest) -> bool {
         Node::Text { extra_body, .. }
         | Node::Image { extra_body, .. }
         | Node::Audio { extra_body, .. }
+        | Node::Video { extra_body, .. }
         | Node::File { extra_body, .. }
         | Node::Refusal { extra_body, .. }
         | Node::Reasoning { extra_body, .. }
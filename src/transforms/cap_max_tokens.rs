@@ -0,0 +1,190 @@
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformState, UrpData,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    max: u64,
+    #[serde(default)]
+    default: Option<u64>,
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct CapMaxTokensTransform;
+
+#[async_trait]
+impl Transform for CapMaxTokensTransform {
+    fn type_id(&self) -> &'static str {
+        "cap_max_tokens"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "max": { "type": "integer", "minimum": 1 },
+                "default": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Value to set when the client sent no max tokens; must not exceed max"
+                }
+            },
+            "required": ["max"],
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        if cfg.max == 0 || cfg.default == Some(0) {
+            return Err(TransformError::InvalidConfig(
+                "max and default must be >= 1".to_string(),
+            ));
+        }
+        if cfg.default.is_some_and(|default| default > cfg.max) {
+            return Err(TransformError::InvalidConfig(
+                "default must not exceed max".to_string(),
+            ));
+        }
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        _context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        if let UrpData::Request(req) = data {
+            cap_max_output_tokens(&mut req.max_output_tokens, cfg.max, cfg.default);
+        }
+        Ok(())
+    }
+}
+
+fn cap_max_output_tokens(value: &mut Option<u64>, max: u64, default: Option<u64>) {
+    match value {
+        Some(requested) if *requested > max => *requested = max,
+        Some(_) => {}
+        None => *value = default,
+    }
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(CapMaxTokensTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::urp::UrpRequest;
+    use crate::urp::encode::{anthropic, gemini, openai_chat, openai_responses};
+    use std::collections::HashMap;
+
+    fn request(max_output_tokens: Option<u64>) -> UrpRequest {
+        UrpRequest {
+            model: "gpt-5".to_string(),
+            input: Vec::new(),
+            stream: None,
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            max_output_tokens,
+            reasoning: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            stop: None,
+            verbosity: None,
+            response_format: None,
+            user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn keeps_values_below_cap() {
+        let mut value = Some(512);
+        cap_max_output_tokens(&mut value, 1024, Some(256));
+        assert_eq!(value, Some(512));
+
+        let mut value = Some(1024);
+        cap_max_output_tokens(&mut value, 1024, None);
+        assert_eq!(value, Some(1024));
+    }
+
+    #[test]
+    fn lowers_values_above_cap() {
+        let mut value = Some(200_000);
+        cap_max_output_tokens(&mut value, 4096, Some(256));
+        assert_eq!(value, Some(4096));
+    }
+
+    #[test]
+    fn absent_value_uses_optional_default() {
+        let mut value = None;
+        cap_max_output_tokens(&mut value, 4096, None);
+        assert_eq!(value, None);
+
+        let mut value = None;
+        cap_max_output_tokens(&mut value, 4096, Some(1024));
+        assert_eq!(value, Some(1024));
+    }
+
+    #[test]
+    fn rejects_default_above_max() {
+        let transform = CapMaxTokensTransform;
+        assert!(
+            transform
+                .parse_config(json!({ "max": 100, "default": 200 }))
+                .is_err()
+        );
+        assert!(transform.parse_config(json!({ "max": 0 })).is_err());
+    }
+
+    #[test]
+    fn capped_value_encodes_to_each_provider_field() {
+        let mut req = request(Some(200_000));
+        cap_max_output_tokens(&mut req.max_output_tokens, 4096, None);
+
+        let chat = openai_chat::encode_request(&req, "gpt-5");
+        assert_eq!(chat["max_completion_tokens"], json!(4096));
+        let responses = openai_responses::encode_request(&req, "gpt-5");
+        assert_eq!(responses["max_output_tokens"], json!(4096));
+        let messages = anthropic::encode_request(&req, "claude-sonnet-4-5");
+        assert_eq!(messages["max_tokens"], json!(4096));
+        let gemini = gemini::encode_request(&req, "gemini-2.5-pro");
+        assert_eq!(gemini["generationConfig"]["maxOutputTokens"], json!(4096));
+    }
+}
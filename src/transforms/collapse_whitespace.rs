@@ -0,0 +1,270 @@
+use crate::transforms::{
+    Phase, Transform, TransformConfig, TransformEntry, TransformError, TransformRuntimeContext,
+    TransformScope, TransformState, UrpData,
+};
+use crate::urp::{Node, NodeDelta, NodeHeader, UrpStreamEvent};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    max_consecutive_newlines: u32,
+    trim_trailing: bool,
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[derive(Default)]
+struct StreamState {
+    collapsers: HashMap<u32, Collapser>,
+}
+
+impl TransformState for StreamState {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+pub struct CollapseWhitespaceTransform;
+
+#[async_trait]
+impl Transform for CollapseWhitespaceTransform {
+    fn type_id(&self) -> &'static str {
+        "collapse_whitespace"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Response]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[TransformScope::Provider, TransformScope::ApiKey]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "max_consecutive_newlines": { "type": "integer", "minimum": 1 },
+                "trim_trailing": { "type": "boolean" }
+            },
+            "required": ["max_consecutive_newlines", "trim_trailing"],
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        if cfg.max_consecutive_newlines == 0 {
+            return Err(TransformError::InvalidConfig(
+                "max_consecutive_newlines must be >= 1".to_string(),
+            ));
+        }
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(StreamState::default())
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        _context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        match data {
+            UrpData::Response(resp) => collapse_text_nodes(&mut resp.output, cfg),
+            UrpData::Stream(event) => {
+                let Some(stream_state) = state.as_any_mut().downcast_mut::<StreamState>() else {
+                    return Err(TransformError::Apply("invalid stream state".to_string()));
+                };
+                apply_stream(event, stream_state, cfg);
+            }
+            UrpData::Request(_) => {}
+        }
+        Ok(())
+    }
+}
+
+fn apply_stream(event: &mut UrpStreamEvent, state: &mut StreamState, cfg: &Config) {
+    match event {
+        UrpStreamEvent::NodeStart {
+            node_index,
+            header: NodeHeader::Text { .. },
+            ..
+        } => {
+            state.collapsers.insert(*node_index, Collapser::new(cfg));
+        }
+        UrpStreamEvent::NodeDelta {
+            node_index,
+            delta: NodeDelta::Text { content },
+            ..
+        } => {
+            if let Some(collapser) = state.collapsers.get_mut(node_index) {
+                *content = collapser.push(content);
+            }
+        }
+        UrpStreamEvent::NodeDone {
+            node_index, node, ..
+        } => {
+            state.collapsers.remove(node_index);
+            collapse_text_nodes(std::slice::from_mut(node), cfg);
+        }
+        UrpStreamEvent::ResponseDone { output, .. } => collapse_text_nodes(output, cfg),
+        _ => {}
+    }
+}
+
+fn collapse_text_nodes(nodes: &mut [Node], cfg: &Config) {
+    for node in nodes {
+        if let Node::Text { content, .. } = node {
+            *content = Collapser::new(cfg).push(content);
+        }
+    }
+}
+
+/// Incremental rewriter shared by whole-text and streamed rewriting, so that
+/// concatenated deltas always equal the rewritten final node. Whitespace that
+/// may still be collapsed or trimmed is held back until non-whitespace text
+/// arrives; whatever is still held at the end of the node is dropped.
+struct Collapser {
+    max_newlines: u32,
+    trim_trailing: bool,
+    in_fence: bool,
+    pending_newlines: u32,
+    pending_ws: String,
+    line_ticks: u8,
+    line_prefix_done: bool,
+}
+
+impl Collapser {
+    fn new(cfg: &Config) -> Self {
+        Self {
+            max_newlines: cfg.max_consecutive_newlines.max(1),
+            trim_trailing: cfg.trim_trailing,
+            in_fence: false,
+            pending_newlines: 0,
+            pending_ws: String::new(),
+            line_ticks: 0,
+            line_prefix_done: false,
+        }
+    }
+
+    fn push(&mut self, input: &str) -> String {
+        let mut out = String::with_capacity(input.len());
+        for c in input.chars() {
+            if c == '\n' {
+                if self.in_fence {
+                    out.push('\n');
+                } else {
+                    self.pending_ws.clear();
+                    self.pending_newlines += 1;
+                }
+                self.line_ticks = 0;
+                self.line_prefix_done = false;
+                continue;
+            }
+            if c.is_whitespace() {
+                if self.in_fence {
+                    out.push(c);
+                } else if self.trim_trailing {
+                    self.pending_ws.push(c);
+                } else {
+                    self.flush_pending(&mut out);
+                    out.push(c);
+                }
+                continue;
+            }
+            if !self.in_fence {
+                self.flush_pending(&mut out);
+            }
+            out.push(c);
+            if !self.line_prefix_done {
+                if c == '`' {
+                    self.line_ticks += 1;
+                    if self.line_ticks == 3 {
+                        self.in_fence = !self.in_fence;
+                        self.line_prefix_done = true;
+                    }
+                } else {
+                    self.line_prefix_done = true;
+                }
+            }
+        }
+        out
+    }
+
+    fn flush_pending(&mut self, out: &mut String) {
+        for _ in 0..self.pending_newlines.min(self.max_newlines) {
+            out.push('\n');
+        }
+        self.pending_newlines = 0;
+        out.push_str(&self.pending_ws);
+        self.pending_ws.clear();
+    }
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(CollapseWhitespaceTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collapse(text: &str, max_consecutive_newlines: u32, trim_trailing: bool) -> String {
+        Collapser::new(&Config {
+            max_consecutive_newlines,
+            trim_trailing,
+        })
+        .push(text)
+    }
+
+    #[test]
+    fn collapses_blank_lines_and_trailing_whitespace() {
+        assert_eq!(
+            collapse("Intro.  \n\n\n\n\nNext line\t\n  \nEnd\n\n", 2, true),
+            "Intro.\n\nNext line\n\nEnd"
+        );
+        assert_eq!(collapse("a  \n\n\nb", 1, false), "a  \nb");
+    }
+
+    #[test]
+    fn preserves_code_fence_contents() {
+        let text = "Before\n\n\n\n```rust\nfn main() {  \n\n\n\n    body();\n}\n```\n\n\n\nAfter  ";
+        assert_eq!(
+            collapse(text, 2, true),
+            "Before\n\n```rust\nfn main() {  \n\n\n\n    body();\n}\n```\n\nAfter"
+        );
+    }
+
+    #[test]
+    fn streamed_deltas_match_whole_text_rewrite() {
+        let text = "One\n\n\n\n  ```\nkeep   \n\n\n```  \n\n\nTwo   \n";
+        let cfg = Config {
+            max_consecutive_newlines: 1,
+            trim_trailing: true,
+        };
+        let mut collapser = Collapser::new(&cfg);
+        let streamed: String = text
+            .chars()
+            .map(|c| collapser.push(&c.to_string()))
+            .collect();
+        assert_eq!(streamed, Collapser::new(&cfg).push(text));
+    }
+}
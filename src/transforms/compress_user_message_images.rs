@@ -610,6 +610,9 @@ mod tests {
             image_transform_cache: std::sync::Arc::new(cache),
             http_client: reqwest::Client::new(),
             upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
         };
         let input_png = build_png_data_url_source();
         let mut req = UrpRequest {
@@ -629,6 +632,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -638,6 +642,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
         let rules = vec![crate::transforms::TransformRuleConfig {
@@ -701,6 +710,9 @@ mod tests {
             image_transform_cache: std::sync::Arc::new(cache),
             http_client: reqwest::Client::new(),
             upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
         };
         let input_png = build_png_data_url_source();
         let input_data_url = format!("data:image/png;base64,{input_png}");
@@ -721,6 +733,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -730,6 +743,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
         let rules = vec![crate::transforms::TransformRuleConfig {
@@ -791,6 +809,9 @@ mod tests {
             image_transform_cache: std::sync::Arc::new(cache),
             http_client: reqwest::Client::new(),
             upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
         };
         let input_png = build_png_data_url_source();
         let mut resp = UrpResponse {
@@ -861,6 +882,9 @@ mod tests {
             image_transform_cache: std::sync::Arc::new(cache),
             http_client: reqwest::Client::new(),
             upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
         };
         let rules = vec![crate::transforms::TransformRuleConfig {
             transform: "compress_assistant_output_images".to_string(),
@@ -0,0 +1,291 @@
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformScope, TransformState, UrpData,
+};
+use crate::urp::{Node, OrdinaryRole};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    #[serde(default = "default_separator")]
+    separator: String,
+}
+
+fn default_separator() -> String {
+    "\n\n".to_string()
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct ConsolidateSystemTransform;
+
+/// Unlike `merge_consecutive_roles`, this gathers every system/developer node
+/// in `request.input`, adjacent or not, into one leading system message for
+/// providers that accept only a single system prompt at the start.
+#[async_trait]
+impl Transform for ConsolidateSystemTransform {
+    fn type_id(&self) -> &'static str {
+        "consolidate_system"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[TransformScope::Provider, TransformScope::Global]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "separator": {
+                    "type": "string",
+                    "default": "\n\n",
+                    "description": "Text inserted between consolidated system text segments"
+                }
+            },
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        _context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        if let UrpData::Request(req) = data {
+            req.input = consolidate_system_nodes(std::mem::take(&mut req.input), &cfg.separator);
+        }
+        Ok(())
+    }
+}
+
+fn system_role_mut(node: &mut Node) -> Option<&mut OrdinaryRole> {
+    match node {
+        Node::Text { role, .. }
+        | Node::Image { role, .. }
+        | Node::Audio { role, .. }
+        | Node::Video { role, .. }
+        | Node::File { role, .. }
+        | Node::ProviderItem { role, .. }
+            if matches!(role, OrdinaryRole::System | OrdinaryRole::Developer) =>
+        {
+            Some(role)
+        }
+        _ => None,
+    }
+}
+
+fn consolidate_system_nodes(nodes: Vec<Node>, separator: &str) -> Vec<Node> {
+    let mut text: Option<Node> = None;
+    let mut non_text: Vec<Node> = Vec::new();
+    let mut rest: Vec<Node> = Vec::with_capacity(nodes.len());
+    // Leading control nodes stay ahead of the system message they annotate.
+    let mut insert_at = 0;
+    let mut seen_ordinary = false;
+
+    for mut node in nodes {
+        let Some(role) = system_role_mut(&mut node) else {
+            if matches!(node, Node::NextDownstreamEnvelopeExtra { .. }) {
+                if !seen_ordinary {
+                    insert_at += 1;
+                }
+            } else {
+                if !seen_ordinary {
+                    insert_at = 0;
+                }
+                seen_ordinary = true;
+            }
+            rest.push(node);
+            continue;
+        };
+        *role = OrdinaryRole::System;
+        seen_ordinary = true;
+        match (&mut text, node) {
+            (
+                Some(Node::Text {
+                    content: merged,
+                    extra_body: merged_extra,
+                    ..
+                }),
+                Node::Text {
+                    content,
+                    extra_body,
+                    ..
+                },
+            ) => {
+                merged.push_str(separator);
+                merged.push_str(&content);
+                for (k, v) in extra_body {
+                    merged_extra.entry(k).or_insert(v);
+                }
+            }
+            (slot @ None, node @ Node::Text { .. }) => *slot = Some(node),
+            (_, node) => non_text.push(node),
+        }
+    }
+
+    if text.is_none() && non_text.is_empty() {
+        return rest;
+    }
+    let insert_at = insert_at.min(rest.len());
+    let tail = rest.split_off(insert_at);
+    rest.extend(text);
+    rest.extend(non_text);
+    rest.extend(tail);
+    rest
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(ConsolidateSystemTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::urp::ImageSource;
+    use std::collections::HashMap;
+
+    fn text(role: OrdinaryRole, content: &str) -> Node {
+        Node::Text {
+            id: None,
+            role,
+            content: content.to_string(),
+            phase: None,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn image(role: OrdinaryRole) -> Node {
+        Node::Image {
+            id: None,
+            role,
+            source: ImageSource::Url {
+                url: "https://example.com/a.png".to_string(),
+                detail: None,
+            },
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn summary(nodes: &[Node]) -> Vec<String> {
+        nodes
+            .iter()
+            .map(|node| match node {
+                Node::Text { role, content, .. } => format!("{role:?}:{content}"),
+                Node::Image { role, .. } => format!("{role:?}:<image>"),
+                Node::NextDownstreamEnvelopeExtra { .. } => "<envelope>".to_string(),
+                _ => "<other>".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn gathers_interleaved_system_and_developer_text_in_order() {
+        let output = consolidate_system_nodes(
+            vec![
+                text(OrdinaryRole::User, "hi"),
+                text(OrdinaryRole::System, "be brief"),
+                text(OrdinaryRole::Assistant, "ok"),
+                text(OrdinaryRole::Developer, "use metric units"),
+                text(OrdinaryRole::User, "how far?"),
+                text(OrdinaryRole::System, "no emoji"),
+            ],
+            "\n\n",
+        );
+
+        assert_eq!(
+            summary(&output),
+            vec![
+                "System:be brief\n\nuse metric units\n\nno emoji",
+                "User:hi",
+                "Assistant:ok",
+                "User:how far?",
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_non_text_system_parts_in_consolidated_message() {
+        let output = consolidate_system_nodes(
+            vec![
+                text(OrdinaryRole::System, "first"),
+                image(OrdinaryRole::Developer),
+                text(OrdinaryRole::User, "look"),
+                text(OrdinaryRole::System, "second"),
+            ],
+            "\n",
+        );
+
+        assert_eq!(
+            summary(&output),
+            vec!["System:first\nsecond", "System:<image>", "User:look"]
+        );
+    }
+
+    #[test]
+    fn leading_envelope_control_stays_before_system_message() {
+        let envelope = Node::NextDownstreamEnvelopeExtra {
+            extra_body: HashMap::from([("name".to_string(), json!("ops"))]),
+        };
+        let output = consolidate_system_nodes(
+            vec![
+                envelope.clone(),
+                text(OrdinaryRole::System, "a"),
+                text(OrdinaryRole::User, "q"),
+                text(OrdinaryRole::System, "b"),
+            ],
+            "\n\n",
+        );
+        assert_eq!(
+            summary(&output),
+            vec!["<envelope>", "System:a\n\nb", "User:q"]
+        );
+
+        let output = consolidate_system_nodes(
+            vec![
+                envelope,
+                text(OrdinaryRole::User, "q"),
+                text(OrdinaryRole::System, "b"),
+            ],
+            "\n\n",
+        );
+        assert_eq!(summary(&output), vec!["System:b", "<envelope>", "User:q"]);
+    }
+
+    #[test]
+    fn input_without_system_nodes_is_unchanged() {
+        let input = vec![
+            text(OrdinaryRole::User, "hi"),
+            text(OrdinaryRole::Assistant, "hello"),
+        ];
+        let output = consolidate_system_nodes(input.clone(), "\n\n");
+        assert_eq!(output, input);
+    }
+}
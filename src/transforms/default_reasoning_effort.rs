@@ -0,0 +1,169 @@
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformScope, TransformState, UrpData,
+};
+use crate::urp::ReasoningConfig;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Effort {
+    Low,
+    Medium,
+    High,
+}
+
+impl Effort {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    effort: Effort,
+    #[serde(default, rename = "override")]
+    override_existing: bool,
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct DefaultReasoningEffortTransform;
+
+#[async_trait]
+impl Transform for DefaultReasoningEffortTransform {
+    fn type_id(&self) -> &'static str {
+        "default_reasoning_effort"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[TransformScope::Provider, TransformScope::Global]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "effort": {
+                    "type": "string",
+                    "enum": ["low", "medium", "high"]
+                },
+                "override": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "Replace an effort the client already set"
+                }
+            },
+            "required": ["effort"],
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        _context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        if let UrpData::Request(req) = data {
+            apply_default_effort(&mut req.reasoning, cfg.effort, cfg.override_existing);
+        }
+        Ok(())
+    }
+}
+
+fn apply_default_effort(
+    reasoning: &mut Option<ReasoningConfig>,
+    effort: Effort,
+    override_existing: bool,
+) {
+    let reasoning = reasoning.get_or_insert_with(|| ReasoningConfig {
+        effort: None,
+        summary: None,
+        extra_body: Default::default(),
+    });
+    if override_existing || reasoning.effort.is_none() {
+        reasoning.effort = Some(effort.as_str().to_string());
+    }
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(DefaultReasoningEffortTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn reasoning(effort: Option<&str>) -> Option<ReasoningConfig> {
+        Some(ReasoningConfig {
+            effort: effort.map(str::to_string),
+            summary: None,
+            extra_body: HashMap::from([("summary".to_string(), json!("auto"))]),
+        })
+    }
+
+    #[test]
+    fn creates_reasoning_config_when_absent() {
+        let mut value = None;
+        apply_default_effort(&mut value, Effort::Medium, false);
+        let value = value.expect("reasoning config");
+        assert_eq!(value.effort.as_deref(), Some("medium"));
+        assert!(value.extra_body.is_empty());
+
+        let mut value = reasoning(None);
+        apply_default_effort(&mut value, Effort::Low, false);
+        let value = value.expect("reasoning config");
+        assert_eq!(value.effort.as_deref(), Some("low"));
+        assert_eq!(value.extra_body.get("summary"), Some(&json!("auto")));
+    }
+
+    #[test]
+    fn keeps_existing_effort_without_override() {
+        let mut value = reasoning(Some("low"));
+        apply_default_effort(&mut value, Effort::High, false);
+        assert_eq!(value.and_then(|r| r.effort).as_deref(), Some("low"));
+    }
+
+    #[test]
+    fn replaces_existing_effort_with_override() {
+        let mut value = reasoning(Some("low"));
+        apply_default_effort(&mut value, Effort::High, true);
+        let value = value.expect("reasoning config");
+        assert_eq!(value.effort.as_deref(), Some("high"));
+        assert_eq!(value.extra_body.get("summary"), Some(&json!("auto")));
+    }
+}
@@ -0,0 +1,393 @@
+use crate::transforms::resolve_image_urls::{
+    DEFAULT_MAX_BYTES, DEFAULT_TIMEOUT_SECONDS, fetch_image_as_base64, is_data_url,
+    split_base64_data_url,
+};
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformScope, TransformState, UrpData,
+};
+use crate::urp::{ImageSource, Node};
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::imageops::FilterType;
+use image::{GenericImageView, ImageFormat};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+
+const JPEG_QUALITY: u8 = 90;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    max_edge: u32,
+    #[serde(default)]
+    fetch_urls: bool,
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct DownscaleImagesTransform;
+
+/// Unlike `compress_user_message_images`, this only shrinks oversized images
+/// and keeps their format where it can; images already within `max_edge` are
+/// left byte-for-byte intact.
+#[async_trait]
+impl Transform for DownscaleImagesTransform {
+    fn type_id(&self) -> &'static str {
+        "downscale_images"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[TransformScope::Provider, TransformScope::Global]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "max_edge": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Maximum width or height in pixels"
+                },
+                "fetch_urls": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "Fetch remote image URLs and inline them before downscaling"
+                }
+            },
+            "required": ["max_edge"],
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        if cfg.max_edge == 0 {
+            return Err(TransformError::InvalidConfig(
+                "max_edge must be >= 1".to_string(),
+            ));
+        }
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?
+            .clone();
+        let UrpData::Request(req) = data else {
+            return Ok(());
+        };
+
+        if cfg.fetch_urls {
+            inline_remote_images(&mut req.input, context).await?;
+        }
+        for node in &mut req.input {
+            let Node::Image { source, .. } = node else {
+                continue;
+            };
+            if let Some(next_source) = downscale_image_source(source, cfg.max_edge).await? {
+                *source = next_source;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn inline_remote_images(
+    nodes: &mut [Node],
+    context: &TransformRuntimeContext,
+) -> Result<(), TransformError> {
+    let timeout = std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECONDS);
+    let mut fetches = Vec::new();
+    for (node_idx, node) in nodes.iter().enumerate() {
+        let Node::Image {
+            source: ImageSource::Url { url, .. },
+            ..
+        } = node
+        else {
+            continue;
+        };
+        if is_data_url(url) {
+            continue;
+        }
+        let client = context.http_client.clone();
+        let url = url.clone();
+        fetches.push((
+            node_idx,
+            tokio::spawn(async move {
+                fetch_image_as_base64(&client, &url, timeout, DEFAULT_MAX_BYTES).await
+            }),
+        ));
+    }
+
+    for (node_idx, handle) in fetches {
+        let result = handle
+            .await
+            .map_err(|e| TransformError::Apply(format!("image fetch task failed: {e}")))?;
+        match result {
+            Ok((media_type, data)) => {
+                if let Some(Node::Image { source, .. }) = nodes.get_mut(node_idx) {
+                    *source = ImageSource::Base64 { media_type, data };
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    node_idx,
+                    error = %e,
+                    "downscale_images: failed to fetch image, keeping original URL"
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn downscale_image_source(
+    source: &ImageSource,
+    max_edge: u32,
+) -> Result<Option<ImageSource>, TransformError> {
+    match source {
+        ImageSource::Base64 { media_type, data } => {
+            let Some((media_type, data)) = downscale_base64(media_type, data, max_edge).await?
+            else {
+                return Ok(None);
+            };
+            Ok(Some(ImageSource::Base64 { media_type, data }))
+        }
+        ImageSource::Url { url, detail } => {
+            let Some((media_type, data)) = split_base64_data_url(url) else {
+                return Ok(None);
+            };
+            let Some((media_type, data)) = downscale_base64(media_type, data, max_edge).await?
+            else {
+                return Ok(None);
+            };
+            Ok(Some(ImageSource::Url {
+                url: format!("data:{media_type};base64,{data}"),
+                detail: detail.clone(),
+            }))
+        }
+        ImageSource::FileId { .. } => Ok(None),
+    }
+}
+
+async fn downscale_base64(
+    media_type: &str,
+    data: &str,
+    max_edge: u32,
+) -> Result<Option<(String, String)>, TransformError> {
+    let original = match STANDARD.decode(data.as_bytes()) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            tracing::warn!(
+                media_type,
+                error = %err,
+                "downscale_images: invalid base64 image data, skipping"
+            );
+            return Ok(None);
+        }
+    };
+    let media_type = media_type.to_string();
+    let resized = tokio::task::spawn_blocking(move || {
+        downscale_image_bytes(&media_type, &original, max_edge)
+    })
+    .await
+    .map_err(|err| TransformError::Apply(format!("image downscale task join failed: {err}")))?;
+    Ok(resized.map(|(media_type, bytes)| (media_type, STANDARD.encode(bytes))))
+}
+
+/// Returns the re-encoded image and its media type, or `None` when the image
+/// is already small enough or cannot be decoded. JPEG input stays JPEG; every
+/// other format is re-encoded as PNG so transparency survives.
+fn downscale_image_bytes(
+    media_type: &str,
+    original: &[u8],
+    max_edge: u32,
+) -> Option<(String, Vec<u8>)> {
+    let format = match image::guess_format(original) {
+        Ok(format) => format,
+        Err(err) => {
+            tracing::warn!(
+                media_type,
+                error = %err,
+                "downscale_images: unrecognized image data, skipping"
+            );
+            return None;
+        }
+    };
+    let decoded = match image::load_from_memory_with_format(original, format) {
+        Ok(image) => image,
+        Err(err) => {
+            tracing::warn!(
+                media_type,
+                error = %err,
+                "downscale_images: failed to decode image, skipping"
+            );
+            return None;
+        }
+    };
+    let (width, height) = decoded.dimensions();
+    if width.max(height) <= max_edge {
+        return None;
+    }
+    let resized = decoded.resize(max_edge, max_edge, FilterType::Lanczos3);
+
+    let mut out = Vec::new();
+    let (media_type, result) = if format == ImageFormat::Jpeg {
+        let rgb = resized.to_rgb8();
+        (
+            "image/jpeg",
+            rgb.write_with_encoder(JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY)),
+        )
+    } else {
+        (
+            "image/png",
+            resized.write_with_encoder(PngEncoder::new(&mut out)),
+        )
+    };
+    if let Err(err) = result {
+        tracing::warn!(
+            media_type,
+            error = %err,
+            "downscale_images: failed to encode image, skipping"
+        );
+        return None;
+    }
+    Some((media_type.to_string(), out))
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(DownscaleImagesTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb, Rgba};
+
+    fn encode(image: image::DynamicImage, format: ImageFormat) -> Vec<u8> {
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut bytes, format).expect("encode image");
+        bytes.into_inner()
+    }
+
+    fn large_png() -> Vec<u8> {
+        let image = ImageBuffer::from_fn(2000, 800, |x, y| {
+            Rgba([(x % 255) as u8, (y % 255) as u8, ((x + y) % 255) as u8, 200])
+        });
+        encode(image::DynamicImage::ImageRgba8(image), ImageFormat::Png)
+    }
+
+    #[test]
+    fn shrinks_large_png_to_max_edge_and_keeps_png() {
+        let (media_type, bytes) =
+            downscale_image_bytes("image/png", &large_png(), 1000).expect("downscaled");
+
+        assert_eq!(media_type, "image/png");
+        let resized = image::load_from_memory(&bytes).expect("decode output");
+        assert_eq!(resized.dimensions(), (1000, 400));
+        assert!(resized.color().has_alpha());
+    }
+
+    #[test]
+    fn jpeg_stays_jpeg_and_other_formats_become_png() {
+        let rgb = image::DynamicImage::ImageRgb8(ImageBuffer::from_fn(800, 600, |x, y| {
+            Rgb([(x % 255) as u8, (y % 255) as u8, 128])
+        }));
+
+        let (media_type, bytes) =
+            downscale_image_bytes("image/jpeg", &encode(rgb.clone(), ImageFormat::Jpeg), 400)
+                .expect("downscaled jpeg");
+        assert_eq!(media_type, "image/jpeg");
+        assert_eq!(
+            image::load_from_memory(&bytes)
+                .expect("decode")
+                .dimensions(),
+            (400, 300)
+        );
+
+        let (media_type, _) =
+            downscale_image_bytes("image/gif", &encode(rgb, ImageFormat::Gif), 400)
+                .expect("downscaled gif");
+        assert_eq!(media_type, "image/png");
+    }
+
+    #[test]
+    fn small_and_undecodable_images_are_skipped() {
+        assert!(downscale_image_bytes("image/png", &large_png(), 2000).is_none());
+        assert!(downscale_image_bytes("image/png", b"not an image", 100).is_none());
+    }
+
+    #[tokio::test]
+    async fn replaces_base64_and_data_url_sources() {
+        let data = STANDARD.encode(large_png());
+        let source = ImageSource::Base64 {
+            media_type: "image/webp".to_string(),
+            data: data.clone(),
+        };
+        let Some(ImageSource::Base64 {
+            media_type,
+            data: resized,
+        }) = downscale_image_source(&source, 600).await.expect("apply")
+        else {
+            panic!("expected base64 source");
+        };
+        assert_eq!(media_type, "image/png");
+        let bytes = STANDARD.decode(resized).expect("base64");
+        assert_eq!(
+            image::load_from_memory(&bytes)
+                .expect("decode")
+                .dimensions(),
+            (600, 240)
+        );
+
+        let source = ImageSource::Url {
+            url: format!("data:image/png;base64,{data}"),
+            detail: Some("high".to_string()),
+        };
+        let Some(ImageSource::Url { url, detail }) =
+            downscale_image_source(&source, 600).await.expect("apply")
+        else {
+            panic!("expected url source");
+        };
+        assert!(url.starts_with("data:image/png;base64,"));
+        assert_eq!(detail.as_deref(), Some("high"));
+
+        let corrupt = ImageSource::Base64 {
+            media_type: "image/png".to_string(),
+            data: STANDARD.encode(b"garbage"),
+        };
+        assert!(
+            downscale_image_source(&corrupt, 600)
+                .await
+                .expect("apply")
+                .is_none()
+        );
+    }
+}
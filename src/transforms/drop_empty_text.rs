@@ -0,0 +1,201 @@
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformScope, TransformState, UrpData,
+};
+use crate::urp::{Node, node_is_empty_text};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum EmptyMessage {
+    #[default]
+    Drop,
+    Placeholder,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    #[serde(default)]
+    empty_message: EmptyMessage,
+    #[serde(default = "default_placeholder")]
+    placeholder: String,
+}
+
+fn default_placeholder() -> String {
+    " ".to_string()
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct DropEmptyTextTransform;
+
+/// Removes `Text` nodes with empty content, which Anthropic and Gemini reject
+/// as empty content blocks. A message is a run of consecutive nodes with the
+/// same role; one left with no nodes is dropped or keeps a placeholder text.
+#[async_trait]
+impl Transform for DropEmptyTextTransform {
+    fn type_id(&self) -> &'static str {
+        "drop_empty_text"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[
+            TransformScope::Provider,
+            TransformScope::ApiKey,
+            TransformScope::Global,
+        ]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "empty_message": {
+                    "type": "string",
+                    "enum": ["drop", "placeholder"],
+                    "default": "drop",
+                    "description": "What to do with a message whose only parts were empty text"
+                },
+                "placeholder": {
+                    "type": "string",
+                    "default": " ",
+                    "description": "Text kept for an emptied message when empty_message is placeholder"
+                }
+            },
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        _context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        if let UrpData::Request(req) = data {
+            req.input = drop_empty_text(std::mem::take(&mut req.input), cfg);
+        }
+        Ok(())
+    }
+}
+
+fn drop_empty_text(nodes: Vec<Node>, cfg: &Config) -> Vec<Node> {
+    let mut output = Vec::with_capacity(nodes.len());
+    for message in
+        nodes.chunk_by(|left, right| left.role().is_some() && left.role() == right.role())
+    {
+        if message.iter().all(node_is_empty_text) {
+            if cfg.empty_message == EmptyMessage::Placeholder {
+                let mut placeholder = message[0].clone();
+                if let Node::Text { content, .. } = &mut placeholder {
+                    content.clone_from(&cfg.placeholder);
+                }
+                output.push(placeholder);
+            }
+            continue;
+        }
+        output.extend(
+            message
+                .iter()
+                .filter(|node| !node_is_empty_text(node))
+                .cloned(),
+        );
+    }
+    output
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(DropEmptyTextTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::urp::{OrdinaryRole, ToolCallType};
+    use std::collections::HashMap;
+
+    fn config(raw: Value) -> Config {
+        serde_json::from_value(raw).expect("config")
+    }
+
+    fn tool_call() -> Node {
+        Node::ToolCall {
+            id: None,
+            tool_type: ToolCallType::Function,
+            call_id: "call_1".to_string(),
+            name: "lookup".to_string(),
+            arguments: "{}".to_string(),
+            extra_body: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn empty_text_part_is_removed_and_tool_calls_survive() {
+        let nodes = vec![
+            Node::text(OrdinaryRole::User, "hi"),
+            Node::text(OrdinaryRole::User, ""),
+            Node::assistant_text(""),
+            tool_call(),
+        ];
+
+        let output = drop_empty_text(nodes, &config(json!({})));
+
+        assert_eq!(output.len(), 2);
+        assert!(matches!(&output[0], Node::Text { content, .. } if content == "hi"));
+        assert!(matches!(&output[1], Node::ToolCall { call_id, .. } if call_id == "call_1"));
+    }
+
+    #[test]
+    fn emptied_message_is_dropped_or_replaced_with_a_placeholder() {
+        let nodes = vec![
+            Node::text(OrdinaryRole::User, "hi"),
+            Node::assistant_text(""),
+            Node::assistant_text(""),
+            Node::text(OrdinaryRole::User, "again"),
+        ];
+
+        let dropped = drop_empty_text(nodes.clone(), &config(json!({})));
+        assert_eq!(dropped.len(), 2);
+        assert!(
+            dropped
+                .iter()
+                .all(|node| node.role() == Some(OrdinaryRole::User))
+        );
+
+        let placeholder =
+            drop_empty_text(nodes, &config(json!({ "empty_message": "placeholder" })));
+        assert_eq!(placeholder.len(), 3);
+        assert!(matches!(
+            &placeholder[1],
+            Node::Text { role: OrdinaryRole::Assistant, content, .. } if content == " "
+        ));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformScope, TransformState, UrpData,
+};
+use crate::urp::Node;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+use std::collections::HashSet;
+
+#[derive(Debug, Deserialize)]
+struct Config {}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct DropOrphanToolResultsTransform;
+
+/// Anthropic rejects a `tool_result` that is not preceded by the matching
+/// `tool_use`. Clients that replay truncated histories can start with such a
+/// result, so this transform walks `request.input` in order and removes every
+/// `ToolResult` whose `call_id` has not been introduced by an earlier
+/// `ToolCall`. Requests that reference server-side state
+/// (`previous_response_id` / `conversation`) are left untouched because their
+/// calls live upstream.
+#[async_trait]
+impl Transform for DropOrphanToolResultsTransform {
+    fn type_id(&self) -> &'static str {
+        "drop_orphan_tool_results"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[
+            TransformScope::Provider,
+            TransformScope::Global,
+            TransformScope::ApiKey,
+        ]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        _context: &TransformRuntimeContext,
+        _config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let UrpData::Request(req) = data else {
+            return Ok(());
+        };
+        let stateful = req.previous_response_id.is_some()
+            || req
+                .extra_body
+                .get("conversation")
+                .is_some_and(|v| !v.is_null());
+        if stateful {
+            return Ok(());
+        }
+
+        let mut seen_calls: HashSet<String> = HashSet::new();
+        req.input.retain(|node| match node {
+            Node::ToolCall { call_id, .. } => {
+                seen_calls.insert(call_id.clone());
+                true
+            }
+            Node::ToolResult { call_id, .. } => seen_calls.contains(call_id),
+            _ => true,
+        });
+        Ok(())
+    }
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(DropOrphanToolResultsTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_transform_cache::ImageTransformCache;
+    use crate::urp::{OrdinaryRole, ToolCallType, ToolResultContent, UrpRequest};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    async fn run(input: Vec<Node>, extra_body: HashMap<String, Value>) -> Vec<Node> {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let cache = ImageTransformCache::new(
+            temp_dir.path().join("cache"),
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .expect("cache");
+        let context = TransformRuntimeContext {
+            image_transform_cache: std::sync::Arc::new(cache),
+            http_client: reqwest::Client::new(),
+            upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
+        };
+        let mut req = UrpRequest {
+            model: "claude-sonnet-4-5".to_string(),
+            input,
+            stream: None,
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            max_output_tokens: None,
+            reasoning: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            stop: None,
+            verbosity: None,
+            response_format: None,
+            user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
+            extra_body,
+        };
+        let transform = DropOrphanToolResultsTransform;
+        let config = transform.parse_config(json!({})).expect("config");
+        let mut state = transform.init_state();
+        transform
+            .apply(
+                UrpData::Request(&mut req),
+                Phase::Request,
+                &context,
+                config.as_ref(),
+                state.as_mut(),
+            )
+            .await
+            .expect("apply");
+        req.input
+    }
+
+    fn call(call_id: &str) -> Node {
+        Node::ToolCall {
+            id: None,
+            tool_type: ToolCallType::Function,
+            call_id: call_id.to_string(),
+            name: "lookup".to_string(),
+            arguments: "{}".to_string(),
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn result(call_id: &str) -> Node {
+        Node::ToolResult {
+            id: None,
+            tool_type: ToolCallType::Function,
+            call_id: call_id.to_string(),
+            is_error: false,
+            content: vec![ToolResultContent::Text {
+                text: "ok".to_string(),
+                extra_body: HashMap::new(),
+            }],
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn user_text(text: &str) -> Node {
+        Node::Text {
+            id: None,
+            role: OrdinaryRole::User,
+            content: text.to_string(),
+            phase: None,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn result_ids(nodes: &[Node]) -> Vec<&str> {
+        nodes
+            .iter()
+            .filter_map(|node| match node {
+                Node::ToolResult { call_id, .. } => Some(call_id.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn drops_tool_result_without_prior_call() {
+        let output = run(
+            vec![
+                result("call_orphan"),
+                user_text("continue"),
+                call("call_late"),
+            ],
+            HashMap::new(),
+        )
+        .await;
+
+        assert_eq!(output.len(), 2);
+        assert!(result_ids(&output).is_empty());
+        assert!(matches!(output[0], Node::Text { .. }));
+    }
+
+    #[tokio::test]
+    async fn keeps_matched_call_result_pair() {
+        let output = run(
+            vec![user_text("hi"), call("call_1"), result("call_1")],
+            HashMap::new(),
+        )
+        .await;
+
+        assert_eq!(output.len(), 3);
+        assert_eq!(result_ids(&output), vec!["call_1"]);
+    }
+
+    #[tokio::test]
+    async fn keeps_results_for_stateful_continuations() {
+        let output = run(
+            vec![result("call_upstream")],
+            HashMap::from([("conversation".to_string(), json!("conv_1"))]),
+        )
+        .await;
+
+        assert_eq!(result_ids(&output), vec!["call_upstream"]);
+    }
+}
@@ -196,6 +196,9 @@ mod tests {
             image_transform_cache: std::sync::Arc::new(cache),
             http_client: reqwest::Client::new(),
             upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
         }
     }
 
@@ -212,6 +215,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(Vec::new()),
@@ -221,6 +225,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
 
@@ -255,6 +264,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(vec![ToolDefinition {
@@ -274,6 +284,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
 
@@ -316,6 +331,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(Vec::new()),
@@ -325,6 +341,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
 
@@ -369,6 +390,7 @@ mod tests {
             stream: Some(false),
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(vec![ToolDefinition {
@@ -385,6 +407,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
 
@@ -420,6 +447,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(Vec::new()),
@@ -429,6 +457,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
 
@@ -469,6 +502,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(Vec::new()),
@@ -478,6 +512,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
 
@@ -510,6 +549,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(Vec::new()),
@@ -519,6 +559,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::from([
                 ("size".to_string(), json!("1280x720")),
                 ("quality".to_string(), json!("high")),
@@ -562,6 +607,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(Vec::new()),
@@ -571,6 +617,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::from([
                 ("size".to_string(), json!("1280x720")),
                 ("quality".to_string(), json!("high")),
@@ -615,6 +666,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(Vec::new()),
@@ -624,6 +676,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
 
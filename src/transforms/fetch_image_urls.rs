@@ -0,0 +1,401 @@
+use crate::config::ProviderType;
+use crate::transforms::resolve_image_urls::{
+    DEFAULT_MAX_BYTES, fetch_image_as_base64, is_data_url, split_base64_data_url,
+};
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformScope, TransformState, UrpData,
+};
+use crate::urp::{ImageSource, Node};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+use std::collections::HashSet;
+
+const DEFAULT_TIMEOUT_SECONDS: u64 = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum OnError {
+    Drop,
+    #[default]
+    Fail,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    #[serde(default = "default_max_bytes")]
+    max_bytes: usize,
+    #[serde(default = "default_timeout_seconds")]
+    timeout_seconds: u64,
+    #[serde(default)]
+    on_error: OnError,
+}
+
+fn default_max_bytes() -> usize {
+    DEFAULT_MAX_BYTES
+}
+
+fn default_timeout_seconds() -> u64 {
+    DEFAULT_TIMEOUT_SECONDS
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct FetchImageUrlsTransform;
+
+/// Inlines every image URL for providers that only accept inline image data.
+/// Unlike `resolve_image_urls`, a failed fetch never leaves the URL in place:
+/// the image is dropped or the request fails, per `on_error`.
+#[async_trait]
+impl Transform for FetchImageUrlsTransform {
+    fn type_id(&self) -> &'static str {
+        "fetch_image_urls"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[TransformScope::Provider, TransformScope::Global]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "max_bytes": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "default": DEFAULT_MAX_BYTES
+                },
+                "timeout_seconds": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "default": DEFAULT_TIMEOUT_SECONDS
+                },
+                "on_error": {
+                    "type": "string",
+                    "enum": ["drop", "fail"],
+                    "default": "fail",
+                    "description": "Drop the image or fail the request when a fetch fails"
+                }
+            },
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        if cfg.max_bytes == 0 || cfg.timeout_seconds == 0 {
+            return Err(TransformError::InvalidConfig(
+                "max_bytes and timeout_seconds must be >= 1".to_string(),
+            ));
+        }
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        let UrpData::Request(req) = data else {
+            return Ok(());
+        };
+        inline_image_urls(
+            &mut req.input,
+            &context.http_client,
+            cfg,
+            context.upstream_provider_type,
+        )
+        .await
+    }
+}
+
+async fn inline_image_urls(
+    nodes: &mut Vec<Node>,
+    client: &reqwest::Client,
+    cfg: &Config,
+    provider_type: Option<ProviderType>,
+) -> Result<(), TransformError> {
+    let timeout = std::time::Duration::from_secs(cfg.timeout_seconds);
+    let mut fetches = Vec::new();
+    for (node_idx, node) in nodes.iter_mut().enumerate() {
+        let Node::Image { source, .. } = node else {
+            continue;
+        };
+        let ImageSource::Url { url, detail } = source else {
+            continue;
+        };
+        if is_data_url(url) {
+            if let Some((media_type, data)) = split_base64_data_url(url) {
+                let (media_type, data) = (media_type.to_string(), data.to_string());
+                *source = inline_source(media_type, data, detail.take(), provider_type);
+            }
+            continue;
+        }
+        let client = client.clone();
+        let url = url.clone();
+        let max_bytes = cfg.max_bytes;
+        fetches.push((
+            node_idx,
+            tokio::spawn(async move {
+                let (media_type, data) =
+                    fetch_image_as_base64(&client, &url, timeout, max_bytes).await?;
+                if !media_type.starts_with("image/") {
+                    return Err(format!("unexpected content type {media_type} for {url}"));
+                }
+                Ok((media_type, data))
+            }),
+        ));
+    }
+
+    let mut dropped = HashSet::new();
+    for (node_idx, handle) in fetches {
+        let result = handle
+            .await
+            .map_err(|e| TransformError::Apply(format!("image fetch task failed: {e}")))?;
+        match result {
+            Ok((media_type, data)) => {
+                if let Some(Node::Image { source, .. }) = nodes.get_mut(node_idx) {
+                    let detail = match source {
+                        ImageSource::Url { detail, .. } => detail.take(),
+                        _ => None,
+                    };
+                    *source = inline_source(media_type, data, detail, provider_type);
+                }
+            }
+            Err(e) if cfg.on_error == OnError::Fail => {
+                return Err(TransformError::Apply(format!(
+                    "fetch_image_urls: failed to fetch image: {e}"
+                )));
+            }
+            Err(e) => {
+                tracing::warn!(
+                    node_idx,
+                    error = %e,
+                    "fetch_image_urls: failed to fetch image, dropping it"
+                );
+                dropped.insert(node_idx);
+            }
+        }
+    }
+
+    if !dropped.is_empty() {
+        let mut node_idx = 0;
+        nodes.retain(|_| {
+            let keep = !dropped.contains(&node_idx);
+            node_idx += 1;
+            keep
+        });
+    }
+    Ok(())
+}
+
+/// Only the OpenAI wire formats carry `detail`, and they accept it next to a
+/// `data:` URL, so keep the URL form there instead of losing the hint.
+fn inline_source(
+    media_type: String,
+    data: String,
+    detail: Option<String>,
+    provider_type: Option<ProviderType>,
+) -> ImageSource {
+    match (detail, provider_type) {
+        (Some(detail), Some(ProviderType::Responses | ProviderType::ChatCompletion)) => {
+            ImageSource::Url {
+                url: format!("data:{media_type};base64,{data}"),
+                detail: Some(detail),
+            }
+        }
+        _ => ImageSource::Base64 { media_type, data },
+    }
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(FetchImageUrlsTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::urp::OrdinaryRole;
+    use axum::http::header::CONTENT_TYPE;
+    use axum::routing::get;
+    use std::collections::HashMap;
+
+    const PNG_BYTES: &[u8] = b"\x89PNG\r\n\x1a\nfake";
+
+    async fn spawn_image_server() -> String {
+        let router = axum::Router::new()
+            .route(
+                "/cat.png",
+                get(|| async { ([(CONTENT_TYPE, "image/png")], PNG_BYTES) }),
+            )
+            .route(
+                "/page.html",
+                get(|| async { ([(CONTENT_TYPE, "text/html")], "<html></html>") }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind image server");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.expect("image server");
+        });
+        format!("http://{addr}")
+    }
+
+    fn image(url: String, detail: Option<&str>) -> Node {
+        Node::Image {
+            id: None,
+            role: OrdinaryRole::User,
+            source: ImageSource::Url {
+                url,
+                detail: detail.map(str::to_string),
+            },
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn config(on_error: OnError) -> Config {
+        Config {
+            max_bytes: DEFAULT_MAX_BYTES,
+            timeout_seconds: DEFAULT_TIMEOUT_SECONDS,
+            on_error,
+        }
+    }
+
+    fn sources(nodes: &[Node]) -> Vec<ImageSource> {
+        nodes
+            .iter()
+            .filter_map(|node| match node {
+                Node::Image { source, .. } => Some(source.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn inlines_fetched_images_and_keeps_detail_for_openai_providers() {
+        let base = spawn_image_server().await;
+        let client = reqwest::Client::new();
+        let expected_data = "iVBORw0KGgpmYWtl";
+
+        let mut nodes = vec![image(format!("{base}/cat.png"), Some("low"))];
+        inline_image_urls(
+            &mut nodes,
+            &client,
+            &config(OnError::Fail),
+            Some(ProviderType::Messages),
+        )
+        .await
+        .expect("inline");
+        assert_eq!(
+            sources(&nodes),
+            vec![ImageSource::Base64 {
+                media_type: "image/png".to_string(),
+                data: expected_data.to_string(),
+            }]
+        );
+
+        let mut nodes = vec![image(format!("{base}/cat.png"), Some("low"))];
+        inline_image_urls(
+            &mut nodes,
+            &client,
+            &config(OnError::Fail),
+            Some(ProviderType::ChatCompletion),
+        )
+        .await
+        .expect("inline");
+        assert_eq!(
+            sources(&nodes),
+            vec![ImageSource::Url {
+                url: format!("data:image/png;base64,{expected_data}"),
+                detail: Some("low".to_string()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_fetches_drop_or_fail_per_config() {
+        let base = spawn_image_server().await;
+        let client = reqwest::Client::new();
+        let input = || {
+            vec![
+                Node::text(OrdinaryRole::User, "look"),
+                image(format!("{base}/missing.png"), None),
+                image(format!("{base}/page.html"), None),
+                image(format!("{base}/cat.png"), None),
+            ]
+        };
+
+        let mut nodes = input();
+        inline_image_urls(
+            &mut nodes,
+            &client,
+            &config(OnError::Drop),
+            Some(ProviderType::Gemini),
+        )
+        .await
+        .expect("drop failures");
+        assert_eq!(nodes.len(), 2);
+        assert!(matches!(
+            sources(&nodes).as_slice(),
+            [ImageSource::Base64 { media_type, .. }] if media_type == "image/png"
+        ));
+
+        let mut nodes = input();
+        let err = inline_image_urls(
+            &mut nodes,
+            &client,
+            &config(OnError::Fail),
+            Some(ProviderType::Gemini),
+        )
+        .await
+        .expect_err("fail on fetch error");
+        assert!(err.to_string().contains("missing.png"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn converts_data_urls_without_fetching() {
+        let mut nodes = vec![image(
+            "data:image/jpeg;base64,/9j/AA==".to_string(),
+            Some("high"),
+        )];
+        inline_image_urls(
+            &mut nodes,
+            &reqwest::Client::new(),
+            &config(OnError::Fail),
+            Some(ProviderType::Messages),
+        )
+        .await
+        .expect("inline");
+        assert_eq!(
+            sources(&nodes),
+            vec![ImageSource::Base64 {
+                media_type: "image/jpeg".to_string(),
+                data: "/9j/AA==".to_string(),
+            }]
+        );
+    }
+}
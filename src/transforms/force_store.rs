@@ -0,0 +1,180 @@
+use crate::config::ProviderType;
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformScope, TransformState, UrpData,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    store: bool,
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct ForceStoreTransform;
+
+/// Pins the OpenAI Responses `store` flag regardless of what the client sent,
+/// e.g. `store: false` for tenants whose responses must not be retained.
+/// Other provider types never receive the key.
+#[async_trait]
+impl Transform for ForceStoreTransform {
+    fn type_id(&self) -> &'static str {
+        "force_store"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[
+            TransformScope::Provider,
+            TransformScope::Global,
+            TransformScope::ApiKey,
+        ]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "store": { "type": "boolean" }
+            },
+            "required": ["store"],
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        let UrpData::Request(req) = data else {
+            return Ok(());
+        };
+        match context.upstream_provider_type {
+            Some(ProviderType::Responses) | None => {
+                req.extra_body
+                    .insert("store".to_string(), Value::Bool(cfg.store));
+            }
+            Some(_) => {
+                req.extra_body.remove("store");
+            }
+        }
+        Ok(())
+    }
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(ForceStoreTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_transform_cache::ImageTransformCache;
+    use crate::urp::UrpRequest;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    async fn run(provider_type: Option<ProviderType>, store: Value) -> UrpRequest {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let cache = ImageTransformCache::new(
+            temp_dir.path().join("cache"),
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .expect("cache");
+        let context = TransformRuntimeContext {
+            image_transform_cache: std::sync::Arc::new(cache),
+            http_client: reqwest::Client::new(),
+            upstream_provider_type: provider_type,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
+        };
+        let mut req = UrpRequest {
+            model: "gpt-5-mini".to_string(),
+            input: Vec::new(),
+            stream: None,
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            max_output_tokens: None,
+            reasoning: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            stop: None,
+            verbosity: None,
+            response_format: None,
+            user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
+            extra_body: HashMap::from([("store".to_string(), store)]),
+        };
+        let transform = ForceStoreTransform;
+        let config = transform
+            .parse_config(json!({ "store": false }))
+            .expect("config");
+        let mut state = transform.init_state();
+        transform
+            .apply(
+                UrpData::Request(&mut req),
+                Phase::Request,
+                &context,
+                config.as_ref(),
+                state.as_mut(),
+            )
+            .await
+            .expect("apply");
+        req
+    }
+
+    #[tokio::test]
+    async fn overrides_client_store_for_responses_upstreams() {
+        let req = run(Some(ProviderType::Responses), json!(true)).await;
+        assert_eq!(req.extra_body.get("store"), Some(&json!(false)));
+
+        let encoded = crate::urp::encode::openai_responses::encode_request(&req, "gpt-5-mini");
+        assert_eq!(encoded["store"], json!(false));
+    }
+
+    #[tokio::test]
+    async fn drops_store_for_other_provider_types() {
+        for provider_type in [ProviderType::ChatCompletion, ProviderType::Messages] {
+            let req = run(Some(provider_type), json!(true)).await;
+            assert!(!req.extra_body.contains_key("store"));
+        }
+    }
+}
@@ -93,6 +93,9 @@ mod tests {
             image_transform_cache: std::sync::Arc::new(cache),
             http_client: reqwest::Client::new(),
             upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
         }
     }
 
@@ -109,6 +112,7 @@ mod tests {
             stream,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -118,6 +122,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         }
     }
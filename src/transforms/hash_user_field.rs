@@ -0,0 +1,162 @@
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformScope, TransformState, UrpData,
+};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use sha2::Sha256;
+use std::any::Any;
+use std::fmt::Write as _;
+
+const DEFAULT_SALT_ENV: &str = "MONOIZE_USER_HASH_SALT";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    #[serde(default = "default_salt_env")]
+    salt_env: String,
+}
+
+fn default_salt_env() -> String {
+    DEFAULT_SALT_ENV.to_string()
+}
+
+struct Config {
+    salt: Vec<u8>,
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct HashUserFieldTransform;
+
+/// Replaces the end-user identifier with a salted HMAC-SHA256 so upstreams
+/// still see a stable per-user value without the cleartext. The salt is read
+/// from an environment variable, never from the stored rule config.
+#[async_trait]
+impl Transform for HashUserFieldTransform {
+    fn type_id(&self) -> &'static str {
+        "hash_user_field"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[
+            TransformScope::Provider,
+            TransformScope::ApiKey,
+            TransformScope::Global,
+        ]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "salt_env": {
+                    "type": "string",
+                    "default": DEFAULT_SALT_ENV,
+                    "description": "Environment variable holding the HMAC salt"
+                }
+            },
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let raw: RawConfig = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        let salt = std::env::var(&raw.salt_env)
+            .ok()
+            .filter(|salt| !salt.is_empty())
+            .ok_or_else(|| {
+                TransformError::InvalidConfig(format!(
+                    "environment variable {} must hold a non-empty salt",
+                    raw.salt_env
+                ))
+            })?;
+        Ok(Box::new(Config {
+            salt: salt.into_bytes(),
+        }))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        _context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        let UrpData::Request(req) = data else {
+            return Ok(());
+        };
+        if let Some(user) = req.user.as_mut() {
+            *user = hash_user(&cfg.salt, user);
+        }
+        if let Some(Value::String(user_id)) = req
+            .extra_body
+            .get_mut("metadata")
+            .and_then(|metadata| metadata.get_mut("user_id"))
+        {
+            *user_id = hash_user(&cfg.salt, user_id);
+        }
+        Ok(())
+    }
+}
+
+/// Lowercase hex HMAC-SHA256 of `user` keyed by `salt`.
+fn hash_user(salt: &[u8], user: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(salt).expect("HMAC accepts any key length");
+    mac.update(user.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(HashUserFieldTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_user_hashes_stably_and_different_users_differ() {
+        let alice = hash_user(b"salt-1", "alice@example.com");
+        assert_eq!(alice, hash_user(b"salt-1", "alice@example.com"));
+        assert_eq!(alice.len(), 64);
+        assert!(!alice.contains("alice"));
+        assert_ne!(alice, hash_user(b"salt-1", "bob@example.com"));
+        assert_ne!(alice, hash_user(b"salt-2", "alice@example.com"));
+    }
+
+    #[test]
+    fn rejects_config_when_salt_env_is_unset() {
+        assert!(matches!(
+            HashUserFieldTransform
+                .parse_config(json!({ "salt_env": "MONOIZE_TEST_UNSET_USER_HASH_SALT" })),
+            Err(TransformError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,247 @@
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformScope, TransformState, UrpData,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+
+const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("{user_id}", "__monoize_user_id"),
+    ("{username}", "__monoize_username"),
+    ("{api_key_id}", "__monoize_api_key_id"),
+];
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    fields: BTreeMap<String, String>,
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct InjectMetadataTransform;
+
+/// Merges configured key/value pairs into `extra_body["metadata"]`, expanding
+/// identity placeholders from the request context injected by the handler.
+#[async_trait]
+impl Transform for InjectMetadataTransform {
+    fn type_id(&self) -> &'static str {
+        "inject_metadata"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[
+            TransformScope::Provider,
+            TransformScope::Global,
+            TransformScope::ApiKey,
+        ]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "fields": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" }
+                }
+            },
+            "required": ["fields"],
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        _context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        let UrpData::Request(req) = data else {
+            return Ok(());
+        };
+
+        let resolved: Vec<(String, String)> = cfg
+            .fields
+            .iter()
+            .filter_map(|(key, template)| {
+                expand_template(template, &req.extra_body).map(|value| (key.clone(), value))
+            })
+            .collect();
+        if resolved.is_empty() {
+            return Ok(());
+        }
+
+        let metadata = req
+            .extra_body
+            .entry("metadata".to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !metadata.is_object() {
+            *metadata = Value::Object(Map::new());
+        }
+        if let Some(obj) = metadata.as_object_mut() {
+            for (key, value) in resolved {
+                obj.insert(key, Value::String(value));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns `None` when the template references an identity the request does not carry.
+fn expand_template(template: &str, extra_body: &HashMap<String, Value>) -> Option<String> {
+    let mut out = template.to_string();
+    for (placeholder, context_key) in PLACEHOLDERS {
+        if !out.contains(placeholder) {
+            continue;
+        }
+        let value = extra_body.get(*context_key).and_then(Value::as_str)?;
+        out = out.replace(placeholder, value);
+    }
+    Some(out)
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(InjectMetadataTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_transform_cache::ImageTransformCache;
+    use crate::urp::UrpRequest;
+    use tempfile::TempDir;
+
+    async fn context() -> TransformRuntimeContext {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let cache = ImageTransformCache::new(
+            temp_dir.path().join("cache"),
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .expect("cache");
+        TransformRuntimeContext {
+            image_transform_cache: std::sync::Arc::new(cache),
+            http_client: reqwest::Client::new(),
+            upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
+        }
+    }
+
+    fn request(extra_body: HashMap<String, Value>) -> UrpRequest {
+        UrpRequest {
+            model: "gpt-5".to_string(),
+            input: Vec::new(),
+            stream: None,
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            max_output_tokens: None,
+            reasoning: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            stop: None,
+            verbosity: None,
+            response_format: None,
+            user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
+            extra_body,
+        }
+    }
+
+    async fn apply(req: &mut UrpRequest, fields: Value) {
+        let transform = InjectMetadataTransform;
+        let config = transform
+            .parse_config(json!({ "fields": fields }))
+            .expect("config");
+        let mut state = transform.init_state();
+        transform
+            .apply(
+                UrpData::Request(req),
+                Phase::Request,
+                &context().await,
+                config.as_ref(),
+                state.as_mut(),
+            )
+            .await
+            .expect("apply");
+    }
+
+    #[tokio::test]
+    async fn inject_metadata_expands_identity_placeholders_into_metadata() {
+        let mut req = request(HashMap::from([
+            ("__monoize_user_id".to_string(), json!("usr_1")),
+            ("__monoize_api_key_id".to_string(), json!("key_9")),
+            (
+                "metadata".to_string(),
+                json!({ "trace": "keep", "tenant": "old" }),
+            ),
+        ]));
+        apply(
+            &mut req,
+            json!({
+                "tenant": "tenant-{user_id}",
+                "key": "{api_key_id}",
+                "source": "monoize"
+            }),
+        )
+        .await;
+
+        assert_eq!(
+            req.extra_body["metadata"],
+            json!({
+                "trace": "keep",
+                "tenant": "tenant-usr_1",
+                "key": "key_9",
+                "source": "monoize"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn inject_metadata_skips_fields_with_missing_identity() {
+        let mut req = request(HashMap::new());
+        apply(&mut req, json!({ "key": "{api_key_id}" })).await;
+        assert!(!req.extra_body.contains_key("metadata"));
+
+        apply(
+            &mut req,
+            json!({ "key": "{api_key_id}", "source": "monoize" }),
+        )
+        .await;
+        assert_eq!(req.extra_body["metadata"], json!({ "source": "monoize" }));
+    }
+}
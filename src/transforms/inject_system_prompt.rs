@@ -2,7 +2,7 @@ use crate::transforms::{
     NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
     TransformRuntimeContext, TransformScope, TransformState, UrpData, text_node,
 };
-use crate::urp::OrdinaryRole;
+use crate::urp::{Node, OrdinaryRole};
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{Value, json};
@@ -13,12 +13,16 @@ use std::any::Any;
 enum Position {
     Prepend,
     Append,
+    Replace,
 }
 
 #[derive(Debug, Deserialize)]
 struct Config {
     content: String,
     position: Position,
+    /// Skip injection when the request already carries a system or developer prompt.
+    #[serde(default)]
+    only_if_absent: bool,
 }
 
 impl TransformConfig for Config {
@@ -48,7 +52,11 @@ impl Transform for InjectSystemPromptTransform {
             "type": "object",
             "properties": {
                 "content": { "type": "string" },
-                "position": { "type": "string", "enum": ["prepend", "append"] }
+                "position": { "type": "string", "enum": ["prepend", "append", "replace"] },
+                "only_if_absent": {
+                    "type": "boolean",
+                    "description": "Only inject when the request has no system or developer message. Defaults to false."
+                }
             },
             "required": ["content", "position"],
             "additionalProperties": false
@@ -77,46 +85,162 @@ impl Transform for InjectSystemPromptTransform {
             .as_any()
             .downcast_ref::<Config>()
             .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
-        let UrpData::Request(req) = data else {
-            return Ok(());
-        };
-
-        let mut target_index: Option<usize> = None;
-        match cfg.position {
-            Position::Prepend => {
-                for (idx, node) in req.input.iter().enumerate() {
-                    if matches!(node.role(), Some(OrdinaryRole::System)) {
-                        target_index = Some(idx);
-                        break;
-                    }
-                }
-            }
-            Position::Append => {
-                for (idx, node) in req.input.iter().enumerate().rev() {
-                    if matches!(node.role(), Some(OrdinaryRole::System)) {
-                        target_index = Some(idx);
-                        break;
-                    }
-                }
-            }
+        if let UrpData::Request(req) = data {
+            inject_system_prompt(&mut req.input, cfg);
         }
+        Ok(())
+    }
+}
 
-        if let Some(idx) = target_index {
-            req.input.insert(
-                idx + 1,
-                text_node(OrdinaryRole::System, cfg.content.clone()),
-            );
-        } else {
-            let message = text_node(OrdinaryRole::System, cfg.content.clone());
-            match cfg.position {
-                Position::Prepend => req.input.insert(0, message),
-                Position::Append => req.input.push(message),
+fn is_system_prompt_node(node: &Node) -> bool {
+    matches!(
+        node.role(),
+        Some(OrdinaryRole::System | OrdinaryRole::Developer)
+    )
+}
+
+fn inject_system_prompt(input: &mut Vec<Node>, cfg: &Config) {
+    if cfg.only_if_absent && input.iter().any(is_system_prompt_node) {
+        return;
+    }
+    let message = text_node(OrdinaryRole::System, cfg.content.clone());
+    match cfg.position {
+        Position::Prepend => input.insert(0, message),
+        Position::Replace => {
+            input.retain(|node| !is_system_prompt_node(node));
+            input.insert(0, message);
+        }
+        Position::Append => {
+            match input
+                .iter()
+                .rposition(|node| matches!(node.role(), Some(OrdinaryRole::System)))
+            {
+                Some(idx) => input.insert(idx + 1, message),
+                None => input.push(message),
             }
         }
-        Ok(())
     }
 }
 
 inventory::submit!(TransformEntry {
     factory: || Box::new(InjectSystemPromptTransform),
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(position: Position, only_if_absent: bool) -> Config {
+        Config {
+            content: "You are a helpful assistant, be concise.".to_string(),
+            position,
+            only_if_absent,
+        }
+    }
+
+    fn texts(input: &[Node]) -> Vec<(Option<OrdinaryRole>, &str)> {
+        input
+            .iter()
+            .map(|node| match node {
+                Node::Text { role, content, .. } => (Some(*role), content.as_str()),
+                other => (other.role(), ""),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn prepend_adds_leading_system_message_when_one_is_present() {
+        let mut input = vec![
+            Node::text(OrdinaryRole::System, "client prompt"),
+            Node::text(OrdinaryRole::User, "hi"),
+        ];
+        inject_system_prompt(&mut input, &config(Position::Prepend, false));
+
+        assert_eq!(
+            texts(&input),
+            vec![
+                (
+                    Some(OrdinaryRole::System),
+                    "You are a helpful assistant, be concise."
+                ),
+                (Some(OrdinaryRole::System), "client prompt"),
+                (Some(OrdinaryRole::User), "hi"),
+            ]
+        );
+    }
+
+    #[test]
+    fn replace_drops_existing_system_and_developer_messages() {
+        let mut input = vec![
+            Node::text(OrdinaryRole::System, "client prompt"),
+            Node::text(OrdinaryRole::User, "hi"),
+            Node::text(OrdinaryRole::Developer, "late instructions"),
+            Node::text(OrdinaryRole::Assistant, "hello"),
+        ];
+        inject_system_prompt(&mut input, &config(Position::Replace, false));
+
+        assert_eq!(
+            texts(&input),
+            vec![
+                (
+                    Some(OrdinaryRole::System),
+                    "You are a helpful assistant, be concise."
+                ),
+                (Some(OrdinaryRole::User), "hi"),
+                (Some(OrdinaryRole::Assistant), "hello"),
+            ]
+        );
+    }
+
+    #[test]
+    fn append_inserts_after_last_system_message() {
+        let mut input = vec![
+            Node::text(OrdinaryRole::System, "client prompt"),
+            Node::text(OrdinaryRole::User, "hi"),
+        ];
+        inject_system_prompt(&mut input, &config(Position::Append, false));
+
+        assert_eq!(
+            texts(&input)[1],
+            (
+                Some(OrdinaryRole::System),
+                "You are a helpful assistant, be concise."
+            )
+        );
+    }
+
+    #[test]
+    fn only_if_absent_skips_requests_with_a_system_prompt() {
+        let mut present = vec![
+            Node::text(OrdinaryRole::Developer, "client prompt"),
+            Node::text(OrdinaryRole::User, "hi"),
+        ];
+        inject_system_prompt(&mut present, &config(Position::Prepend, true));
+        assert_eq!(present.len(), 2);
+
+        let mut absent = vec![Node::text(OrdinaryRole::User, "hi")];
+        inject_system_prompt(&mut absent, &config(Position::Prepend, true));
+        assert_eq!(
+            texts(&absent)[0],
+            (
+                Some(OrdinaryRole::System),
+                "You are a helpful assistant, be concise."
+            )
+        );
+    }
+
+    #[test]
+    fn config_defaults_only_if_absent_to_false() {
+        let transform = InjectSystemPromptTransform;
+        assert!(
+            transform
+                .parse_config(json!({ "content": "x", "position": "replace" }))
+                .is_ok()
+        );
+        assert!(
+            transform
+                .parse_config(json!({ "content": "x", "position": "middle" }))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformScope, TransformState, UrpData,
+};
+use crate::urp::ResponseFormat;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::any::Any;
+
+const DEFAULT_MAX_DEPTH: usize = 8;
+const DEFINITION_KEYS: [&str; 2] = ["$defs", "definitions"];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    #[serde(default = "default_max_depth")]
+    max_depth: usize,
+}
+
+fn default_max_depth() -> usize {
+    DEFAULT_MAX_DEPTH
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct InlineSchemaRefsTransform;
+
+/// Inlines local `$ref` pointers into `$defs`/`definitions` for upstreams that
+/// reject schema references in tool parameters and structured output schemas.
+#[async_trait]
+impl Transform for InlineSchemaRefsTransform {
+    fn type_id(&self) -> &'static str {
+        "inline_schema_refs"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[TransformScope::Provider, TransformScope::Global]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "max_depth": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "default": DEFAULT_MAX_DEPTH,
+                    "description": "Maximum nested $ref expansions before a reference is left in place"
+                }
+            },
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        if cfg.max_depth == 0 {
+            return Err(TransformError::InvalidConfig(
+                "max_depth must be >= 1".to_string(),
+            ));
+        }
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        _context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        let UrpData::Request(req) = data else {
+            return Ok(());
+        };
+        for tool in req.tools.iter_mut().flatten() {
+            if let Some(parameters) = tool
+                .function
+                .as_mut()
+                .and_then(|function| function.parameters.as_mut())
+            {
+                inline_schema_refs(parameters, cfg.max_depth);
+            }
+        }
+        if let Some(ResponseFormat::JsonSchema { json_schema }) = req.response_format.as_mut() {
+            inline_schema_refs(&mut json_schema.schema, cfg.max_depth);
+        }
+        Ok(())
+    }
+}
+
+/// Replaces every local `$ref` in `schema` with the definition it points to.
+/// References still present afterwards (external, unknown, or past
+/// `max_depth`) keep the root definitions so they stay resolvable; otherwise
+/// the definitions are dropped.
+fn inline_schema_refs(schema: &mut Value, max_depth: usize) {
+    let Some(root) = schema.as_object_mut() else {
+        return;
+    };
+    let mut definitions = Map::new();
+    for key in DEFINITION_KEYS {
+        if let Some(defs) = root.get(key) {
+            definitions.insert(key.to_string(), defs.clone());
+        }
+    }
+    if definitions.is_empty() {
+        return;
+    }
+    let definitions = Value::Object(definitions);
+
+    let mut unresolved = false;
+    for (key, value) in root.iter_mut() {
+        if !DEFINITION_KEYS.contains(&key.as_str()) {
+            inline_node(value, &definitions, 0, max_depth, &mut unresolved);
+        }
+    }
+    if let Some(reference) = root.get("$ref").and_then(Value::as_str) {
+        match resolve_local_ref(&definitions, reference) {
+            Some(target) => {
+                let mut resolved = target.clone();
+                inline_node(&mut resolved, &definitions, 1, max_depth, &mut unresolved);
+                root.remove("$ref");
+                merge_siblings(&mut resolved, std::mem::take(root));
+                if let Value::Object(resolved) = resolved {
+                    *root = resolved;
+                }
+            }
+            None => {
+                tracing::warn!(
+                    reference,
+                    "inline_schema_refs: unresolvable $ref, leaving it in place"
+                );
+                unresolved = true;
+            }
+        }
+    }
+    if unresolved {
+        for key in DEFINITION_KEYS {
+            if let Some(defs) = definitions.get(key) {
+                root.insert(key.to_string(), defs.clone());
+            }
+        }
+    } else {
+        for key in DEFINITION_KEYS {
+            root.remove(key);
+        }
+    }
+}
+
+fn inline_node(
+    node: &mut Value,
+    definitions: &Value,
+    depth: usize,
+    max_depth: usize,
+    unresolved: &mut bool,
+) {
+    match node {
+        Value::Object(obj) => {
+            if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+                let Some(target) = resolve_local_ref(definitions, reference) else {
+                    tracing::warn!(
+                        reference,
+                        "inline_schema_refs: unresolvable $ref, leaving it in place"
+                    );
+                    *unresolved = true;
+                    return;
+                };
+                if depth >= max_depth {
+                    tracing::warn!(
+                        reference,
+                        max_depth,
+                        "inline_schema_refs: max depth reached, leaving $ref in place"
+                    );
+                    *unresolved = true;
+                    return;
+                }
+                let mut resolved = target.clone();
+                inline_node(&mut resolved, definitions, depth + 1, max_depth, unresolved);
+                let mut siblings = std::mem::take(obj);
+                siblings.remove("$ref");
+                for (_, value) in siblings.iter_mut() {
+                    inline_node(value, definitions, depth, max_depth, unresolved);
+                }
+                merge_siblings(&mut resolved, siblings);
+                *node = resolved;
+                return;
+            }
+            for (_, value) in obj.iter_mut() {
+                inline_node(value, definitions, depth, max_depth, unresolved);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                inline_node(item, definitions, depth, max_depth, unresolved);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Keywords written next to a `$ref` override the inlined definition's.
+fn merge_siblings(resolved: &mut Value, siblings: Map<String, Value>) {
+    if siblings.is_empty() {
+        return;
+    }
+    match resolved {
+        Value::Object(obj) => obj.extend(siblings),
+        _ => {
+            let mut all_of = siblings;
+            all_of.insert(
+                "allOf".to_string(),
+                Value::Array(vec![std::mem::take(resolved)]),
+            );
+            *resolved = Value::Object(all_of);
+        }
+    }
+}
+
+/// Resolves `#/$defs/...` and `#/definitions/...` JSON pointers.
+fn resolve_local_ref<'a>(definitions: &'a Value, reference: &str) -> Option<&'a Value> {
+    let pointer = reference.strip_prefix('#')?;
+    let first = pointer.strip_prefix('/')?.split('/').next()?;
+    if !DEFINITION_KEYS.contains(&first) {
+        return None;
+    }
+    definitions.pointer(pointer)
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(InlineSchemaRefsTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inlines_simple_ref_and_drops_definitions() {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "address": { "$ref": "#/$defs/Address", "description": "Home" }
+            },
+            "$defs": {
+                "Address": {
+                    "type": "object",
+                    "properties": { "city": { "type": "string" } }
+                }
+            }
+        });
+        inline_schema_refs(&mut schema, DEFAULT_MAX_DEPTH);
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "address": {
+                        "type": "object",
+                        "properties": { "city": { "type": "string" } },
+                        "description": "Home"
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn inlines_nested_refs_through_definitions() {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "people": { "type": "array", "items": { "$ref": "#/definitions/Person" } }
+            },
+            "definitions": {
+                "Person": {
+                    "type": "object",
+                    "properties": { "address": { "$ref": "#/definitions/Address" } }
+                },
+                "Address": { "type": "string" }
+            }
+        });
+        inline_schema_refs(&mut schema, DEFAULT_MAX_DEPTH);
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "people": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": { "address": { "type": "string" } }
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn recursive_schema_stops_at_max_depth_and_keeps_definitions() {
+        let mut schema = json!({
+            "$ref": "#/$defs/Node",
+            "$defs": {
+                "Node": {
+                    "type": "object",
+                    "properties": { "child": { "$ref": "#/$defs/Node" } }
+                }
+            }
+        });
+        inline_schema_refs(&mut schema, 2);
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "child": {
+                        "type": "object",
+                        "properties": { "child": { "$ref": "#/$defs/Node" } }
+                    }
+                },
+                "$defs": {
+                    "Node": {
+                        "type": "object",
+                        "properties": { "child": { "$ref": "#/$defs/Node" } }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_external_refs_and_schemas_without_definitions() {
+        let original = json!({
+            "type": "object",
+            "properties": { "x": { "$ref": "https://example.com/x.json" } }
+        });
+        let mut schema = original.clone();
+        inline_schema_refs(&mut schema, DEFAULT_MAX_DEPTH);
+        assert_eq!(schema, original);
+
+        let mut schema = json!({
+            "properties": {
+                "x": { "$ref": "#/$defs/Missing" },
+                "y": { "$ref": "#/$defs/Y" }
+            },
+            "$defs": { "Y": { "type": "integer" } }
+        });
+        inline_schema_refs(&mut schema, DEFAULT_MAX_DEPTH);
+        assert_eq!(
+            schema["properties"]["x"],
+            json!({ "$ref": "#/$defs/Missing" })
+        );
+        assert_eq!(schema["properties"]["y"], json!({ "type": "integer" }));
+        assert!(schema.get("$defs").is_some());
+    }
+}
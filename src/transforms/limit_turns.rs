@@ -0,0 +1,321 @@
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformScope, TransformState, UrpData,
+};
+use crate::urp::{Node, OrdinaryRole};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Keep {
+    #[default]
+    Recent,
+    Oldest,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    max_turns: u32,
+    #[serde(default)]
+    keep: Keep,
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct LimitTurnsTransform;
+
+/// Caps `request.input` at `max_turns` turns. A turn starts at a user node
+/// that follows a non-user node and runs until the next such node, so tool
+/// calls and their results always share a turn and are kept or dropped
+/// together. Leading system/developer nodes are never counted or dropped.
+#[async_trait]
+impl Transform for LimitTurnsTransform {
+    fn type_id(&self) -> &'static str {
+        "limit_turns"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[
+            TransformScope::Provider,
+            TransformScope::Global,
+            TransformScope::ApiKey,
+        ]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "max_turns": { "type": "integer", "minimum": 1 },
+                "keep": {
+                    "type": "string",
+                    "enum": ["recent", "oldest"],
+                    "default": "recent"
+                }
+            },
+            "required": ["max_turns"],
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        if cfg.max_turns == 0 {
+            return Err(TransformError::InvalidConfig(
+                "max_turns must be >= 1".to_string(),
+            ));
+        }
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        _context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let UrpData::Request(req) = data else {
+            return Ok(());
+        };
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+
+        let (prefix_len, turn_starts) = turn_boundaries(&req.input);
+        let max_turns = cfg.max_turns as usize;
+        if turn_starts.len() <= max_turns {
+            return Ok(());
+        }
+        let kept = match cfg.keep {
+            Keep::Recent => turn_starts[turn_starts.len() - max_turns]..req.input.len(),
+            Keep::Oldest => turn_starts[0]..turn_starts[max_turns],
+        };
+        req.input.drain(kept.end..);
+        req.input.drain(prefix_len..kept.start);
+        Ok(())
+    }
+}
+
+fn is_system_node(node: &Node) -> bool {
+    matches!(
+        node.role(),
+        Some(OrdinaryRole::System | OrdinaryRole::Developer)
+    )
+}
+
+/// Returns the length of the leading system prefix and the start index of
+/// every turn after it. Envelope control nodes directly in front of a turn's
+/// first user node belong to that turn.
+fn turn_boundaries(input: &[Node]) -> (usize, Vec<usize>) {
+    let prefix_len = input
+        .iter()
+        .position(|node| {
+            !is_system_node(node) && !matches!(node, Node::NextDownstreamEnvelopeExtra { .. })
+        })
+        .unwrap_or(input.len());
+    // Control nodes in the scan above belong to the first turn, not the prefix.
+    let prefix_len = input[..prefix_len]
+        .iter()
+        .rposition(is_system_node)
+        .map_or(0, |last| last + 1);
+
+    let mut starts = Vec::new();
+    let mut in_user = false;
+    let mut pending_controls = None;
+    for (index, node) in input.iter().enumerate().skip(prefix_len) {
+        if matches!(node, Node::NextDownstreamEnvelopeExtra { .. }) {
+            pending_controls.get_or_insert(index);
+            continue;
+        }
+        let is_user = node.role() == Some(OrdinaryRole::User);
+        if starts.is_empty() || (is_user && !in_user) {
+            starts.push(pending_controls.unwrap_or(index));
+        }
+        in_user = is_user;
+        pending_controls = None;
+    }
+    (prefix_len, starts)
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(LimitTurnsTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_transform_cache::ImageTransformCache;
+    use crate::urp::{ToolCallType, ToolResultContent, UrpRequest};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    async fn run(input: Vec<Node>, config: Value) -> Vec<Node> {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let cache = ImageTransformCache::new(
+            temp_dir.path().join("cache"),
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .expect("cache");
+        let context = TransformRuntimeContext {
+            image_transform_cache: std::sync::Arc::new(cache),
+            http_client: reqwest::Client::new(),
+            upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
+        };
+        let mut req = UrpRequest {
+            model: "gpt-5-mini".to_string(),
+            input,
+            stream: None,
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            max_output_tokens: None,
+            reasoning: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            stop: None,
+            verbosity: None,
+            response_format: None,
+            user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
+            extra_body: HashMap::new(),
+        };
+        let transform = LimitTurnsTransform;
+        let config = transform.parse_config(config).expect("config");
+        let mut state = transform.init_state();
+        transform
+            .apply(
+                UrpData::Request(&mut req),
+                Phase::Request,
+                &context,
+                config.as_ref(),
+                state.as_mut(),
+            )
+            .await
+            .expect("apply");
+        req.input
+    }
+
+    fn text(role: OrdinaryRole, content: &str) -> Node {
+        Node::Text {
+            id: None,
+            role,
+            content: content.to_string(),
+            phase: None,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn call(call_id: &str) -> Node {
+        Node::ToolCall {
+            id: None,
+            tool_type: ToolCallType::Function,
+            call_id: call_id.to_string(),
+            name: "lookup".to_string(),
+            arguments: "{}".to_string(),
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn result(call_id: &str) -> Node {
+        Node::ToolResult {
+            id: None,
+            tool_type: ToolCallType::Function,
+            call_id: call_id.to_string(),
+            is_error: false,
+            content: vec![ToolResultContent::Text {
+                text: "ok".to_string(),
+                extra_body: HashMap::new(),
+            }],
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn labels(nodes: &[Node]) -> Vec<String> {
+        nodes
+            .iter()
+            .map(|node| match node {
+                Node::Text { content, .. } => content.clone(),
+                Node::ToolCall { call_id, .. } => format!("call:{call_id}"),
+                Node::ToolResult { call_id, .. } => format!("result:{call_id}"),
+                _ => "other".to_string(),
+            })
+            .collect()
+    }
+
+    fn history() -> Vec<Node> {
+        vec![
+            text(OrdinaryRole::System, "sys"),
+            text(OrdinaryRole::User, "u1"),
+            call("call_1"),
+            result("call_1"),
+            text(OrdinaryRole::Assistant, "a1"),
+            text(OrdinaryRole::User, "u2"),
+            text(OrdinaryRole::Assistant, "a2"),
+            text(OrdinaryRole::User, "u3"),
+            call("call_3"),
+            result("call_3"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn keeps_recent_turns_with_their_tool_pairs() {
+        let output = run(history(), json!({ "max_turns": 2 })).await;
+        assert_eq!(
+            labels(&output),
+            vec!["sys", "u2", "a2", "u3", "call:call_3", "result:call_3"]
+        );
+    }
+
+    #[tokio::test]
+    async fn keeps_oldest_turns_with_their_tool_pairs() {
+        let output = run(history(), json!({ "max_turns": 1, "keep": "oldest" })).await;
+        assert_eq!(
+            labels(&output),
+            vec!["sys", "u1", "call:call_1", "result:call_1", "a1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn leaves_short_histories_untouched() {
+        let output = run(history(), json!({ "max_turns": 3 })).await;
+        assert_eq!(labels(&output), labels(&history()));
+    }
+
+    #[test]
+    fn rejects_zero_max_turns() {
+        assert!(
+            LimitTurnsTransform
+                .parse_config(json!({ "max_turns": 0 }))
+                .is_err()
+        );
+    }
+}
@@ -10,18 +10,33 @@ use std::sync::Arc;
 pub mod append_empty_user_message;
 pub mod assistant_markdown_images_to_output;
 pub mod assistant_output_images_to_markdown;
+pub mod auto_cache_gemini;
 pub mod auto_cache_openai_prompt;
 pub mod auto_cache_system;
 pub mod auto_cache_tool_use;
 pub mod auto_cache_user_id;
+pub mod cap_max_tokens;
+pub mod collapse_whitespace;
 pub mod compress_user_message_images;
+pub mod consolidate_system;
+pub mod default_reasoning_effort;
 pub mod developer_to_system_role;
+pub mod downscale_images;
+pub mod drop_empty_text;
+pub mod drop_orphan_tool_results;
 pub mod enable_openai_image_generation_tool;
+pub mod fetch_image_urls;
+pub mod force_store;
 pub mod force_stream;
+pub mod hash_user_field;
+pub mod inject_metadata;
 pub mod inject_system_prompt;
+pub mod inline_schema_refs;
+pub mod limit_turns;
 pub mod merge_consecutive_roles;
 pub mod override_max_tokens;
 pub mod plaintext_reasoning_to_summary;
+pub mod prefill_assistant;
 pub mod reasoning_content_delta;
 pub mod reasoning_effort_to_budget;
 pub mod reasoning_effort_to_model_suffix;
@@ -29,6 +44,7 @@ pub mod reasoning_summary_to_raw_cot;
 pub mod reasoning_to_think_xml;
 pub mod remove_field;
 pub mod resolve_image_urls;
+pub mod sanitize_tool_names;
 pub mod set_field;
 pub mod split_sse_frames;
 pub mod strip_anthropic_billing_header;
@@ -36,6 +52,7 @@ pub mod strip_encrypted_reasoning;
 pub mod strip_input_reasoning;
 pub mod strip_orphaned_tool_use;
 pub mod strip_reasoning;
+pub mod strip_unsupported_params;
 pub mod system_to_developer_role;
 pub mod think_xml_to_reasoning;
 
@@ -137,6 +154,22 @@ pub struct TransformRuntimeContext {
     pub image_transform_cache: Arc<crate::image_transform_cache::ImageTransformCache>,
     pub http_client: reqwest::Client,
     pub upstream_provider_type: Option<crate::config::ProviderType>,
+    /// Channel of the current upstream attempt. `None` outside forwarding,
+    /// e.g. in response-phase transforms and transform previews.
+    pub upstream_channel: Option<UpstreamChannelContext>,
+    /// Gemini `cachedContent` names keyed by content hash, shared across requests.
+    pub gemini_context_cache: Arc<crate::response_cache::ResponseCache<String>>,
+    /// Function tool names of the client request before request-phase
+    /// transforms ran. Empty where no client request is in scope.
+    pub request_tool_names: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct UpstreamChannelContext {
+    pub channel_id: String,
+    pub provider: crate::config::ProviderConfig,
+    pub api_key: String,
+    pub request_timeout_ms: u64,
 }
 
 #[async_trait]
@@ -181,14 +214,19 @@ fn builtin_transforms() -> Vec<Box<dyn Transform>> {
     vec![
         Box::new(append_empty_user_message::AppendEmptyUserMessageTransform),
         Box::new(force_stream::ForceStreamTransform),
+        Box::new(inject_metadata::InjectMetadataTransform),
         Box::new(inject_system_prompt::InjectSystemPromptTransform),
         Box::new(merge_consecutive_roles::MergeConsecutiveRolesTransform),
+        Box::new(consolidate_system::ConsolidateSystemTransform),
         Box::new(override_max_tokens::OverrideMaxTokensTransform),
+        Box::new(cap_max_tokens::CapMaxTokensTransform),
+        Box::new(prefill_assistant::PrefillAssistantTransform),
         Box::new(plaintext_reasoning_to_summary::PlaintextReasoningToSummaryTransform),
         Box::new(reasoning_content_delta::ReasoningContentDeltaTransform),
         Box::new(reasoning_summary_to_raw_cot::ReasoningSummaryToRawCotTransform),
         Box::new(reasoning_effort_to_budget::ReasoningEffortToBudgetTransform),
         Box::new(reasoning_effort_to_model_suffix::ReasoningEffortToModelSuffixTransform),
+        Box::new(default_reasoning_effort::DefaultReasoningEffortTransform),
         Box::new(reasoning_to_think_xml::ReasoningToThinkXmlTransform),
         Box::new(remove_field::RemoveFieldTransform),
         Box::new(set_field::SetFieldTransform),
@@ -198,10 +236,15 @@ fn builtin_transforms() -> Vec<Box<dyn Transform>> {
         Box::new(strip_reasoning::StripReasoningTransform),
         Box::new(strip_encrypted_reasoning::StripEncryptedReasoningTransform),
         Box::new(strip_orphaned_tool_use::StripOrphanedToolUseTransform),
+        Box::new(strip_unsupported_params::StripUnsupportedParamsTransform),
+        Box::new(drop_orphan_tool_results::DropOrphanToolResultsTransform),
+        Box::new(drop_empty_text::DropEmptyTextTransform),
         Box::new(system_to_developer_role::SystemToDeveloperRoleTransform),
         Box::new(think_xml_to_reasoning::ThinkXmlToReasoningTransform),
+        Box::new(collapse_whitespace::CollapseWhitespaceTransform),
         Box::new(assistant_markdown_images_to_output::AssistantMarkdownImagesToOutputTransform),
         Box::new(assistant_output_images_to_markdown::AssistantOutputImagesToMarkdownTransform),
+        Box::new(auto_cache_gemini::AutoCacheGeminiTransform),
         Box::new(auto_cache_openai_prompt::AutoCacheOpenAiPromptTransform),
         Box::new(auto_cache_system::AutoCacheSystemTransform),
         Box::new(auto_cache_tool_use::AutoCacheToolUseTransform),
@@ -211,6 +254,13 @@ fn builtin_transforms() -> Vec<Box<dyn Transform>> {
         Box::new(developer_to_system_role::DeveloperToSystemRoleTransform),
         Box::new(enable_openai_image_generation_tool::EnableOpenAiImageGenerationToolTransform),
         Box::new(resolve_image_urls::ResolveImageUrlsTransform),
+        Box::new(downscale_images::DownscaleImagesTransform),
+        Box::new(fetch_image_urls::FetchImageUrlsTransform),
+        Box::new(inline_schema_refs::InlineSchemaRefsTransform),
+        Box::new(sanitize_tool_names::SanitizeToolNamesTransform),
+        Box::new(hash_user_field::HashUserFieldTransform),
+        Box::new(limit_turns::LimitTurnsTransform),
+        Box::new(force_store::ForceStoreTransform),
     ]
 }
 
@@ -371,6 +421,7 @@ pub fn move_system_to_developer_nodes(nodes: &mut [Node]) {
             Node::Text { role, .. }
             | Node::Image { role, .. }
             | Node::Audio { role, .. }
+            | Node::Video { role, .. }
             | Node::File { role, .. }
             | Node::ProviderItem { role, .. } => {
                 if *role == OrdinaryRole::System {
@@ -388,6 +439,7 @@ pub fn move_developer_to_system_nodes(nodes: &mut [Node]) {
             Node::Text { role, .. }
             | Node::Image { role, .. }
             | Node::Audio { role, .. }
+            | Node::Video { role, .. }
             | Node::File { role, .. }
             | Node::ProviderItem { role, .. } => {
                 if *role == OrdinaryRole::Developer {
@@ -0,0 +1,228 @@
+use crate::config::ProviderType;
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformState, UrpData,
+};
+use crate::urp::encode::normalize_tool_choice;
+use crate::urp::{Node, OrdinaryRole, ToolChoice, UrpRequest};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    text: String,
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct PrefillAssistantTransform;
+
+#[async_trait]
+impl Transform for PrefillAssistantTransform {
+    fn type_id(&self) -> &'static str {
+        "prefill_assistant"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "text": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "Partial assistant turn the model continues from; must not end with whitespace"
+                }
+            },
+            "required": ["text"],
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        if cfg.text.is_empty() {
+            return Err(TransformError::InvalidConfig(
+                "text must not be empty".to_string(),
+            ));
+        }
+        if cfg.text.ends_with(char::is_whitespace) {
+            return Err(TransformError::InvalidConfig(
+                "text must not end with whitespace".to_string(),
+            ));
+        }
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        _context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        if let UrpData::Request(req) = data {
+            prefill_assistant(req, &cfg.text);
+        }
+        Ok(())
+    }
+}
+
+fn prefill_assistant(req: &mut UrpRequest, text: &str) {
+    if req.tool_choice.as_ref().is_some_and(tool_choice_forces_use) {
+        return;
+    }
+    if req
+        .input
+        .last()
+        .is_some_and(|node| node.role() == Some(OrdinaryRole::Assistant))
+    {
+        return;
+    }
+    req.input.push(Node::Text {
+        id: None,
+        role: OrdinaryRole::Assistant,
+        content: text.to_string(),
+        phase: None,
+        extra_body: HashMap::new(),
+    });
+}
+
+/// Messages rejects an assistant prefill combined with `tool_choice` of type
+/// `any` or `tool`.
+fn tool_choice_forces_use(choice: &ToolChoice) -> bool {
+    matches!(
+        normalize_tool_choice(choice, ProviderType::Messages)
+            .get("type")
+            .and_then(Value::as_str),
+        Some("any" | "tool")
+    )
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(PrefillAssistantTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::urp::encode::anthropic;
+
+    fn request(input: Vec<Node>, tool_choice: Option<ToolChoice>) -> UrpRequest {
+        UrpRequest {
+            model: "claude-sonnet-4-5".to_string(),
+            input,
+            stream: None,
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            max_output_tokens: None,
+            reasoning: None,
+            tools: None,
+            tool_choice,
+            parallel_tool_calls: None,
+            stop: None,
+            verbosity: None,
+            response_format: None,
+            user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn appends_assistant_prefill_after_user_turn() {
+        let mut req = request(vec![Node::text(OrdinaryRole::User, "list colors")], None);
+        prefill_assistant(&mut req, "[");
+
+        assert_eq!(req.input.len(), 2);
+        assert!(matches!(
+            req.input.last(),
+            Some(Node::Text {
+                role: OrdinaryRole::Assistant,
+                content,
+                ..
+            }) if content == "["
+        ));
+
+        let encoded = anthropic::encode_request(&req, "claude-sonnet-4-5");
+        let messages = encoded["messages"].as_array().expect("messages array");
+        assert_eq!(
+            messages.last().expect("last message")["role"],
+            json!("assistant")
+        );
+    }
+
+    #[test]
+    fn skips_when_last_turn_is_already_assistant() {
+        let mut req = request(
+            vec![
+                Node::text(OrdinaryRole::User, "list colors"),
+                Node::text(OrdinaryRole::Assistant, "{"),
+            ],
+            None,
+        );
+        prefill_assistant(&mut req, "[");
+
+        assert_eq!(req.input.len(), 2);
+        assert!(matches!(
+            req.input.last(),
+            Some(Node::Text { content, .. }) if content == "{"
+        ));
+    }
+
+    #[test]
+    fn skips_when_tool_use_is_forced() {
+        for choice in [
+            ToolChoice::Mode("required".to_string()),
+            ToolChoice::Specific(json!({ "type": "function", "function": { "name": "lookup" } })),
+        ] {
+            let mut req = request(
+                vec![Node::text(OrdinaryRole::User, "look it up")],
+                Some(choice),
+            );
+            prefill_assistant(&mut req, "[");
+            assert_eq!(req.input.len(), 1);
+        }
+
+        let mut req = request(
+            vec![Node::text(OrdinaryRole::User, "look it up")],
+            Some(ToolChoice::Mode("auto".to_string())),
+        );
+        prefill_assistant(&mut req, "[");
+        assert_eq!(req.input.len(), 2);
+    }
+
+    #[test]
+    fn rejects_empty_or_whitespace_terminated_text() {
+        let transform = PrefillAssistantTransform;
+        assert!(transform.parse_config(json!({ "text": "" })).is_err());
+        assert!(transform.parse_config(json!({ "text": "Sure, " })).is_err());
+        assert!(transform.parse_config(json!({ "text": "{" })).is_ok());
+    }
+}
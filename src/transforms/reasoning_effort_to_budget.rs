@@ -7,6 +7,13 @@ use serde::Deserialize;
 use serde_json::{Value, json};
 use std::any::Any;
 
+/// Smallest `thinking.budget_tokens` Anthropic accepts for manual thinking.
+pub(crate) const MIN_THINKING_BUDGET_TOKENS: u32 = 1024;
+
+/// Room left for the visible answer when `max_output_tokens` has to be raised
+/// above the thinking budget.
+const ANSWER_TOKEN_HEADROOM: u64 = 1024;
+
 #[derive(Debug, Deserialize)]
 struct Config {
     low: u32,
@@ -93,6 +100,8 @@ impl Transform for ReasoningEffortToBudgetTransform {
             "max" => cfg.max.unwrap_or_else(|| cfg.xhigh.unwrap_or(cfg.high)),
             _ => return Ok(()),
         };
+        let budget = clamp_thinking_budget(budget);
+        ensure_max_tokens_above_budget(&mut req.max_output_tokens, budget);
         set_extra_path(
             &mut req.extra_body,
             "thinking.budget_tokens",
@@ -102,6 +111,152 @@ impl Transform for ReasoningEffortToBudgetTransform {
     }
 }
 
+fn clamp_thinking_budget(budget: u32) -> u32 {
+    if budget < MIN_THINKING_BUDGET_TOKENS {
+        tracing::warn!(
+            configured = budget,
+            applied = MIN_THINKING_BUDGET_TOKENS,
+            "raising thinking budget to the provider minimum"
+        );
+        return MIN_THINKING_BUDGET_TOKENS;
+    }
+    budget
+}
+
+/// Anthropic requires `max_tokens` to be strictly greater than the thinking
+/// budget, so a client cap at or below the budget is raised.
+fn ensure_max_tokens_above_budget(max_output_tokens: &mut Option<u64>, budget: u32) {
+    let budget = u64::from(budget);
+    if let Some(requested) = max_output_tokens.filter(|requested| *requested <= budget) {
+        let applied = budget + ANSWER_TOKEN_HEADROOM;
+        tracing::warn!(
+            requested,
+            applied,
+            budget,
+            "raising max_output_tokens above the thinking budget"
+        );
+        *max_output_tokens = Some(applied);
+    }
+}
+
 inventory::submit!(TransformEntry {
     factory: || Box::new(ReasoningEffortToBudgetTransform),
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_transform_cache::ImageTransformCache;
+    use crate::urp::encode::anthropic;
+    use crate::urp::{Node, OrdinaryRole, ReasoningConfig, UrpRequest};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    async fn context() -> TransformRuntimeContext {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let cache = ImageTransformCache::new(
+            temp_dir.path().join("cache"),
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .expect("cache");
+        TransformRuntimeContext {
+            image_transform_cache: std::sync::Arc::new(cache),
+            http_client: reqwest::Client::new(),
+            upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
+        }
+    }
+
+    fn request(effort: &str, max_output_tokens: Option<u64>) -> UrpRequest {
+        UrpRequest {
+            model: "claude-3.5-sonnet".to_string(),
+            input: vec![Node::Text {
+                id: None,
+                role: OrdinaryRole::User,
+                content: "reason".to_string(),
+                phase: None,
+                extra_body: HashMap::new(),
+            }],
+            stream: None,
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            max_output_tokens,
+            reasoning: Some(ReasoningConfig {
+                effort: Some(effort.to_string()),
+                summary: None,
+                extra_body: HashMap::new(),
+            }),
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            stop: None,
+            verbosity: None,
+            response_format: None,
+            user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    async fn apply(req: &mut UrpRequest, config: Value) {
+        let transform = ReasoningEffortToBudgetTransform;
+        let config = transform.parse_config(config).expect("config");
+        let mut state = transform.init_state();
+        transform
+            .apply(
+                UrpData::Request(req),
+                Phase::Request,
+                &context().await,
+                config.as_ref(),
+                state.as_mut(),
+            )
+            .await
+            .expect("apply");
+    }
+
+    #[tokio::test]
+    async fn budget_below_provider_minimum_is_clamped() {
+        let mut req = request("low", Some(8192));
+        apply(&mut req, json!({ "low": 512, "med": 4096, "high": 16384 })).await;
+
+        assert_eq!(req.extra_body["thinking"]["budget_tokens"], json!(1024));
+        assert_eq!(req.max_output_tokens, Some(8192));
+
+        let encoded = anthropic::encode_request_checked(&req, "claude-3-5-sonnet-20241022")
+            .expect("clamped budget passes Messages validation");
+        assert_eq!(
+            encoded["thinking"],
+            json!({ "type": "enabled", "budget_tokens": 1024 })
+        );
+    }
+
+    #[tokio::test]
+    async fn max_output_tokens_is_raised_above_budget() {
+        let mut req = request("high", Some(4096));
+        apply(&mut req, json!({ "low": 1024, "med": 4096, "high": 16384 })).await;
+
+        assert_eq!(req.extra_body["thinking"]["budget_tokens"], json!(16384));
+        assert_eq!(req.max_output_tokens, Some(16384 + ANSWER_TOKEN_HEADROOM));
+
+        let encoded = anthropic::encode_request_checked(&req, "claude-3-5-sonnet-20241022")
+            .expect("raised max_tokens passes Messages validation");
+        assert_eq!(encoded["thinking"]["budget_tokens"], json!(16384));
+        assert_eq!(encoded["max_tokens"], json!(16384 + ANSWER_TOKEN_HEADROOM));
+
+        let mut roomy = request("medium", Some(32000));
+        apply(
+            &mut roomy,
+            json!({ "low": 1024, "med": 4096, "high": 16384 }),
+        )
+        .await;
+        assert_eq!(roomy.max_output_tokens, Some(32000));
+    }
+}
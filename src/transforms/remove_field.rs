@@ -62,7 +62,14 @@ impl Transform for RemoveFieldTransform {
             .downcast_ref::<Config>()
             .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
         match data {
-            UrpData::Request(req) => remove_extra_path(&mut req.extra_body, &cfg.path),
+            UrpData::Request(req) => {
+                match cfg.path.as_str() {
+                    "service_tier" => req.service_tier = None,
+                    "include" => req.include = None,
+                    _ => {}
+                }
+                remove_extra_path(&mut req.extra_body, &cfg.path)
+            }
             UrpData::Response(resp) => remove_extra_path(&mut resp.extra_body, &cfg.path),
             UrpData::Stream(event) => match event {
                 crate::urp::UrpStreamEvent::ResponseStart { extra_body, .. }
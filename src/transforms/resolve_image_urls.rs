@@ -9,8 +9,8 @@ use serde::Deserialize;
 use serde_json::{Value, json};
 use std::any::Any;
 
-const DEFAULT_TIMEOUT_SECONDS: u64 = 30;
-const DEFAULT_MAX_BYTES: usize = 20 * 1024 * 1024;
+pub(super) const DEFAULT_TIMEOUT_SECONDS: u64 = 30;
+pub(super) const DEFAULT_MAX_BYTES: usize = 20 * 1024 * 1024;
 
 #[derive(Debug, Deserialize, Clone)]
 struct Config {
@@ -182,11 +182,20 @@ fn parse_allowed_roles(roles: &Option<Vec<String>>) -> Vec<OrdinaryRole> {
     }
 }
 
-fn is_data_url(url: &str) -> bool {
+pub(super) fn is_data_url(url: &str) -> bool {
     url.starts_with("data:")
 }
 
-async fn fetch_image_as_base64(
+/// Splits a base64 `data:image/...` URL into its media type and payload.
+pub(super) fn split_base64_data_url(url: &str) -> Option<(&str, &str)> {
+    let (meta, data) = url.strip_prefix("data:")?.split_once(',')?;
+    let media_type = meta.strip_suffix(";base64")?;
+    media_type
+        .starts_with("image/")
+        .then_some((media_type, data))
+}
+
+pub(super) async fn fetch_image_as_base64(
     client: &reqwest::Client,
     url: &str,
     timeout: std::time::Duration,
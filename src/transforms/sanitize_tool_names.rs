@@ -0,0 +1,595 @@
+use crate::transforms::{
+    Phase, Transform, TransformConfig, TransformEntry, TransformError, TransformRuntimeContext,
+    TransformScope, TransformState, UrpData,
+};
+use crate::urp::{Node, NodeHeader, ToolCallType, ToolChoice, UrpRequest, UrpStreamEvent};
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+
+const DEFAULT_PATTERN: &str = "[^a-zA-Z0-9_]";
+const DEFAULT_REPLACEMENT: &str = "_";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    #[serde(default = "default_pattern")]
+    pattern: String,
+    #[serde(default = "default_replacement")]
+    replacement: String,
+}
+
+fn default_pattern() -> String {
+    DEFAULT_PATTERN.to_string()
+}
+
+fn default_replacement() -> String {
+    DEFAULT_REPLACEMENT.to_string()
+}
+
+struct Config {
+    pattern: Regex,
+    replacement: String,
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Original-to-sanitized function names assigned while rewriting one request.
+/// In the response phase `restored` holds the inverse map, built once per
+/// response or stream.
+#[derive(Default)]
+struct NameMapState {
+    renamed: HashMap<String, String>,
+    taken: HashSet<String>,
+    restored: Option<HashMap<String, String>>,
+}
+
+impl TransformState for NameMapState {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl NameMapState {
+    /// Keeps `original` unchanged when it is already valid, so a rewritten
+    /// name never displaces a tool that needed no rewrite.
+    fn reserve_if_valid(&mut self, original: &str, cfg: &Config) {
+        if sanitized_base(original, cfg) == original && self.taken.insert(original.to_string()) {
+            self.renamed
+                .insert(original.to_string(), original.to_string());
+        }
+    }
+
+    /// Returns the sanitized name for `original`, assigning one on first use.
+    /// A sanitized name that collides with another tool's gets a numeric
+    /// suffix so distinct tools stay distinct upstream.
+    fn resolve(&mut self, original: &str, cfg: &Config) -> String {
+        if let Some(name) = self.renamed.get(original) {
+            return name.clone();
+        }
+        let base = sanitized_base(original, cfg);
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while self.taken.contains(&name) {
+            name = format!("{base}_{suffix}");
+            suffix += 1;
+        }
+        if name != original {
+            tracing::debug!(
+                original,
+                sanitized = name.as_str(),
+                "sanitize_tool_names: renamed tool"
+            );
+        }
+        self.taken.insert(name.clone());
+        self.renamed.insert(original.to_string(), name.clone());
+        name
+    }
+
+    /// Returns the sanitized-to-original map for the client's tool list,
+    /// building it on first use. The request phase assigns names
+    /// deterministically from the same config and tools, so replaying it
+    /// yields the names the upstream saw.
+    fn restored(&mut self, tool_names: &[String], cfg: &Config) -> &HashMap<String, String> {
+        self.restored.get_or_insert_with(|| {
+            let mut names = NameMapState::default();
+            for name in tool_names {
+                names.reserve_if_valid(name, cfg);
+            }
+            tool_names
+                .iter()
+                .filter_map(|original| {
+                    let sanitized = names.resolve(original, cfg);
+                    (sanitized != *original).then(|| (sanitized, original.clone()))
+                })
+                .collect()
+        })
+    }
+}
+
+/// Applies the configured substitution and prefixes `_` when the result does
+/// not start with an ASCII letter or underscore.
+fn sanitized_base(original: &str, cfg: &Config) -> String {
+    let mut base = cfg
+        .pattern
+        .replace_all(original, cfg.replacement.as_str())
+        .into_owned();
+    if !base
+        .chars()
+        .next()
+        .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+    {
+        base.insert(0, '_');
+    }
+    base
+}
+
+pub struct SanitizeToolNamesTransform;
+
+/// Rewrites function tool names that the upstream rejects (e.g. Gemini's
+/// dotted `fs.read`) and applies the same rename to every `ToolCall`,
+/// `ToolResult` name hint, and `tool_choice` that refers to the tool, so the
+/// model's calls still resolve against the rewritten definitions. In the
+/// response phase it maps the model's calls back to the client's names.
+#[async_trait]
+impl Transform for SanitizeToolNamesTransform {
+    fn type_id(&self) -> &'static str {
+        "sanitize_tool_names"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request, Phase::Response]
+    }
+
+    fn supported_scopes(&self) -> &'static [TransformScope] {
+        &[TransformScope::Provider, TransformScope::Global]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": {
+                    "type": "string",
+                    "default": DEFAULT_PATTERN,
+                    "description": "Regex matching the characters the upstream rejects in tool names"
+                },
+                "replacement": {
+                    "type": "string",
+                    "default": DEFAULT_REPLACEMENT,
+                    "description": "Text substituted for every pattern match"
+                }
+            },
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let raw: RawConfig = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        let pattern = Regex::new(&raw.pattern)
+            .map_err(|e| TransformError::InvalidConfig(format!("invalid pattern: {e}")))?;
+        Ok(Box::new(Config {
+            pattern,
+            replacement: raw.replacement,
+        }))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NameMapState::default())
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        let state = state
+            .as_any_mut()
+            .downcast_mut::<NameMapState>()
+            .ok_or_else(|| TransformError::Apply("invalid state type".to_string()))?;
+        match data {
+            UrpData::Request(req) => sanitize_request(req, cfg, state),
+            UrpData::Response(resp) => {
+                let restored = state.restored(&context.request_tool_names, cfg);
+                restore_nodes(&mut resp.output, restored);
+            }
+            UrpData::Stream(event) => {
+                let restored = state.restored(&context.request_tool_names, cfg);
+                restore_stream_event(event, restored);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn sanitize_request(req: &mut UrpRequest, cfg: &Config, state: &mut NameMapState) {
+    for function in req
+        .tools
+        .iter()
+        .flatten()
+        .filter(|tool| tool.tool_type == "function")
+        .filter_map(|tool| tool.function.as_ref())
+    {
+        state.reserve_if_valid(&function.name, cfg);
+    }
+    for function in req
+        .tools
+        .iter_mut()
+        .flatten()
+        .filter(|tool| tool.tool_type == "function")
+        .filter_map(|tool| tool.function.as_mut())
+    {
+        function.name = state.resolve(&function.name, cfg);
+    }
+
+    let mut names_by_call_id: HashMap<String, String> = HashMap::new();
+    for node in req.input.iter_mut() {
+        match node {
+            Node::ToolCall {
+                tool_type: ToolCallType::Function,
+                call_id,
+                name,
+                ..
+            } => {
+                *name = state.resolve(name, cfg);
+                names_by_call_id.insert(call_id.clone(), name.clone());
+            }
+            Node::ToolResult {
+                tool_type: ToolCallType::Function,
+                call_id,
+                extra_body,
+                ..
+            } => {
+                if let Some(Value::String(name)) = extra_body.get_mut("name") {
+                    *name = names_by_call_id
+                        .get(call_id)
+                        .cloned()
+                        .unwrap_or_else(|| state.resolve(name, cfg));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(ToolChoice::Specific(Value::Object(selector))) = req.tool_choice.as_mut()
+        && selector.get("type").and_then(Value::as_str) == Some("function")
+    {
+        let name = match selector.get_mut("function") {
+            Some(Value::Object(function)) => function.get_mut("name"),
+            _ => selector.get_mut("name"),
+        };
+        if let Some(Value::String(name)) = name {
+            *name = state.resolve(name, cfg);
+        }
+    }
+}
+
+fn restore_name(name: &mut String, restored: &HashMap<String, String>) {
+    if let Some(original) = restored.get(name.as_str()) {
+        *name = original.clone();
+    }
+}
+
+fn restore_nodes(nodes: &mut [Node], restored: &HashMap<String, String>) {
+    for node in nodes {
+        if let Node::ToolCall {
+            tool_type: ToolCallType::Function,
+            name,
+            ..
+        } = node
+        {
+            restore_name(name, restored);
+        }
+    }
+}
+
+fn restore_stream_event(event: &mut UrpStreamEvent, restored: &HashMap<String, String>) {
+    match event {
+        UrpStreamEvent::NodeStart {
+            header:
+                NodeHeader::ToolCall {
+                    tool_type: ToolCallType::Function,
+                    name,
+                    ..
+                },
+            ..
+        } => restore_name(name, restored),
+        UrpStreamEvent::NodeDone { node, .. } => {
+            restore_nodes(std::slice::from_mut(node), restored)
+        }
+        UrpStreamEvent::ResponseDone { output, .. } => restore_nodes(output, restored),
+        _ => {}
+    }
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(SanitizeToolNamesTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_transform_cache::ImageTransformCache;
+    use crate::urp::{FunctionDefinition, ToolDefinition, ToolResultContent, UrpResponse};
+    use tempfile::TempDir;
+
+    fn request(tools: &[&str], input: Vec<Node>) -> UrpRequest {
+        UrpRequest {
+            model: "gemini-2.5-pro".to_string(),
+            input,
+            stream: None,
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            max_output_tokens: None,
+            reasoning: None,
+            tools: Some(
+                tools
+                    .iter()
+                    .map(|name| ToolDefinition {
+                        tool_type: "function".to_string(),
+                        name: None,
+                        description: None,
+                        function: Some(FunctionDefinition {
+                            name: (*name).to_string(),
+                            description: None,
+                            parameters: None,
+                            strict: None,
+                            extra_body: HashMap::new(),
+                        }),
+                        custom: None,
+                        extra_body: HashMap::new(),
+                    })
+                    .collect(),
+            ),
+            tool_choice: None,
+            parallel_tool_calls: None,
+            stop: None,
+            verbosity: None,
+            response_format: None,
+            user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn default_config() -> Box<dyn TransformConfig> {
+        SanitizeToolNamesTransform
+            .parse_config(json!({}))
+            .expect("config")
+    }
+
+    fn run(req: &mut UrpRequest) {
+        let config = default_config();
+        let cfg = config.as_any().downcast_ref::<Config>().expect("config");
+        sanitize_request(req, cfg, &mut NameMapState::default());
+    }
+
+    fn tool_names(req: &UrpRequest) -> Vec<&str> {
+        req.tools
+            .iter()
+            .flatten()
+            .filter_map(|tool| tool.function.as_ref())
+            .map(|function| function.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn dotted_name_is_sanitized_consistently_across_definition_call_and_result() {
+        let mut req = request(
+            &["fs.read"],
+            vec![
+                Node::ToolCall {
+                    id: None,
+                    tool_type: ToolCallType::Function,
+                    call_id: "call_1".to_string(),
+                    name: "fs.read".to_string(),
+                    arguments: "{}".to_string(),
+                    extra_body: HashMap::new(),
+                },
+                Node::ToolResult {
+                    id: None,
+                    tool_type: ToolCallType::Function,
+                    call_id: "call_1".to_string(),
+                    is_error: false,
+                    content: vec![ToolResultContent::Text {
+                        text: "ok".to_string(),
+                        extra_body: HashMap::new(),
+                    }],
+                    extra_body: HashMap::from([("name".to_string(), json!("fs.read"))]),
+                },
+            ],
+        );
+        req.tool_choice = Some(ToolChoice::Specific(
+            json!({ "type": "function", "function": { "name": "fs.read" } }),
+        ));
+
+        run(&mut req);
+
+        assert_eq!(tool_names(&req), vec!["fs_read"]);
+        let Node::ToolCall { name, call_id, .. } = &req.input[0] else {
+            panic!("expected tool call");
+        };
+        assert_eq!(name, "fs_read");
+        assert_eq!(call_id, "call_1");
+        let Node::ToolResult {
+            call_id,
+            extra_body,
+            ..
+        } = &req.input[1]
+        else {
+            panic!("expected tool result");
+        };
+        assert_eq!(call_id, "call_1");
+        assert_eq!(extra_body.get("name"), Some(&json!("fs_read")));
+        let Some(ToolChoice::Specific(choice)) = &req.tool_choice else {
+            panic!("expected specific tool choice");
+        };
+        assert_eq!(choice["function"]["name"], json!("fs_read"));
+    }
+
+    #[test]
+    fn colliding_and_leading_digit_names_stay_distinct_and_valid() {
+        let mut req = request(&["fs.read", "fs_read", "1password"], Vec::new());
+        run(&mut req);
+        assert_eq!(tool_names(&req), vec!["fs_read_2", "fs_read", "_1password"]);
+    }
+
+    fn tool_call(call_id: &str, name: &str) -> Node {
+        Node::ToolCall {
+            id: None,
+            tool_type: ToolCallType::Function,
+            call_id: call_id.to_string(),
+            name: name.to_string(),
+            arguments: "{}".to_string(),
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn tool_call_name(node: &Node) -> &str {
+        let Node::ToolCall { name, .. } = node else {
+            panic!("expected tool call");
+        };
+        name
+    }
+
+    async fn response_context(request_tool_names: &[&str]) -> (TempDir, TransformRuntimeContext) {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let cache = ImageTransformCache::new(
+            temp_dir.path().join("cache"),
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .expect("cache");
+        let context = TransformRuntimeContext {
+            image_transform_cache: std::sync::Arc::new(cache),
+            http_client: reqwest::Client::new(),
+            upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: request_tool_names
+                .iter()
+                .map(|name| (*name).to_string())
+                .collect(),
+        };
+        (temp_dir, context)
+    }
+
+    #[tokio::test]
+    async fn response_and_stream_tool_calls_round_trip_to_client_names() {
+        let client_tools = ["fs.read", "fs_read"];
+        let mut req = request(&client_tools, Vec::new());
+        run(&mut req);
+        assert_eq!(tool_names(&req), vec!["fs_read_2", "fs_read"]);
+
+        let (_temp_dir, context) = response_context(&client_tools).await;
+        let config = default_config();
+
+        let mut resp = UrpResponse {
+            id: "resp_1".to_string(),
+            model: "gemini-2.5-pro".to_string(),
+            created_at: None,
+            output: vec![
+                tool_call("call_1", "fs_read_2"),
+                tool_call("call_2", "fs_read"),
+            ],
+            finish_reason: None,
+            usage: None,
+            extra_body: HashMap::new(),
+        };
+        let mut state = SanitizeToolNamesTransform.init_state();
+        SanitizeToolNamesTransform
+            .apply(
+                UrpData::Response(&mut resp),
+                Phase::Response,
+                &context,
+                config.as_ref(),
+                state.as_mut(),
+            )
+            .await
+            .expect("apply response");
+        let names: Vec<&str> = resp.output.iter().map(tool_call_name).collect();
+        assert_eq!(names, vec!["fs.read", "fs_read"]);
+
+        let mut events = [
+            UrpStreamEvent::NodeStart {
+                node_index: 0,
+                header: NodeHeader::ToolCall {
+                    id: None,
+                    tool_type: ToolCallType::Function,
+                    call_id: "call_1".to_string(),
+                    name: "fs_read_2".to_string(),
+                },
+                extra_body: HashMap::new(),
+            },
+            UrpStreamEvent::NodeDone {
+                node_index: 0,
+                node: tool_call("call_1", "fs_read_2"),
+                usage: None,
+                extra_body: HashMap::new(),
+            },
+            UrpStreamEvent::ResponseDone {
+                finish_reason: None,
+                usage: None,
+                output: vec![tool_call("call_1", "fs_read_2")],
+                extra_body: HashMap::new(),
+            },
+        ];
+        let mut state = SanitizeToolNamesTransform.init_state();
+        for event in events.iter_mut() {
+            SanitizeToolNamesTransform
+                .apply(
+                    UrpData::Stream(event),
+                    Phase::Response,
+                    &context,
+                    config.as_ref(),
+                    state.as_mut(),
+                )
+                .await
+                .expect("apply stream event");
+        }
+        let UrpStreamEvent::NodeStart {
+            header: NodeHeader::ToolCall { name, .. },
+            ..
+        } = &events[0]
+        else {
+            panic!("expected tool call header");
+        };
+        assert_eq!(name, "fs.read");
+        let UrpStreamEvent::NodeDone { node, .. } = &events[1] else {
+            panic!("expected node done");
+        };
+        assert_eq!(tool_call_name(node), "fs.read");
+        let UrpStreamEvent::ResponseDone { output, .. } = &events[2] else {
+            panic!("expected response done");
+        };
+        assert_eq!(tool_call_name(&output[0]), "fs.read");
+    }
+
+    #[test]
+    fn rejects_invalid_pattern() {
+        assert!(matches!(
+            SanitizeToolNamesTransform.parse_config(json!({ "pattern": "[" })),
+            Err(TransformError::InvalidConfig(_))
+        ));
+    }
+}
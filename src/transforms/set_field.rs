@@ -66,9 +66,12 @@ impl Transform for SetFieldTransform {
             .downcast_ref::<Config>()
             .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
         match data {
-            UrpData::Request(req) => {
-                set_extra_path(&mut req.extra_body, &cfg.path, cfg.value.clone())
-            }
+            // `service_tier` is typed on requests; route it there so the rule
+            // still overrides a client-supplied tier.
+            UrpData::Request(req) => match (cfg.path.as_str(), cfg.value.as_str()) {
+                ("service_tier", Some(tier)) => req.service_tier = Some(tier.to_string()),
+                _ => set_extra_path(&mut req.extra_body, &cfg.path, cfg.value.clone()),
+            },
             UrpData::Response(resp) => {
                 set_extra_path(&mut resp.extra_body, &cfg.path, cfg.value.clone())
             }
@@ -127,6 +127,9 @@ mod tests {
             image_transform_cache: std::sync::Arc::new(cache),
             http_client: reqwest::Client::new(),
             upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
         }
     }
 
@@ -150,6 +153,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -159,6 +163,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
 
@@ -197,6 +206,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -206,6 +216,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
 
@@ -1,13 +1,13 @@
 use crate::transforms::{
     Phase, Transform, TransformConfig, TransformEntry, TransformError, TransformRuntimeContext,
-    TransformScope, TransformState, UrpData, strip_reasoning_nodes,
+    TransformScope, TransformState, UrpData,
 };
-use crate::urp::{Node, NodeDelta, NodeHeader, OrdinaryRole, UrpStreamEvent};
+use crate::urp::{Node, NodeHeader, UrpStreamEvent};
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{Value, json};
 use std::any::Any;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Deserialize)]
 struct Config {}
@@ -23,12 +23,21 @@ pub struct StripReasoningTransform;
 #[derive(Default)]
 struct StripState {
     stripped_indices: HashSet<u32>,
+    drop_current: bool,
 }
 
 impl TransformState for StripState {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn finalize_stream_event(&mut self, event: UrpStreamEvent) -> Vec<UrpStreamEvent> {
+        if std::mem::take(&mut self.drop_current) {
+            Vec::new()
+        } else {
+            vec![event]
+        }
+    }
 }
 
 #[async_trait]
@@ -73,7 +82,7 @@ impl Transform for StripReasoningTransform {
     ) -> Result<(), TransformError> {
         match data {
             UrpData::Response(resp) => {
-                resp.output = strip_reasoning_nodes(&resp.output);
+                resp.output.retain(|node| !is_reasoning_node(node));
             }
             UrpData::Stream(event) => strip_stream_reasoning(event, state),
             UrpData::Request(_) => {}
@@ -82,60 +91,211 @@ impl Transform for StripReasoningTransform {
     }
 }
 
+/// Drops the whole lifecycle of reasoning nodes, including reasoning-item
+/// envelope extras that only carry encrypted reasoning.
 fn strip_stream_reasoning(event: &mut UrpStreamEvent, state: &mut dyn TransformState) {
     let Some(strip_state) = state.as_any_mut().downcast_mut::<StripState>() else {
         return;
     };
     match event {
         UrpStreamEvent::NodeStart {
-            node_index, header, ..
+            node_index,
+            header,
+            extra_body,
         } => {
-            if let NodeHeader::Reasoning { id } = header {
+            let is_reasoning = match header {
+                NodeHeader::Reasoning { .. } => true,
+                NodeHeader::NextDownstreamEnvelopeExtra => envelope_is_reasoning(extra_body),
+                _ => false,
+            };
+            if is_reasoning {
                 strip_state.stripped_indices.insert(*node_index);
-                *header = NodeHeader::Text {
-                    id: id.take(),
-                    role: OrdinaryRole::Assistant,
-                    phase: None,
-                };
+                strip_state.drop_current = true;
             }
         }
-        UrpStreamEvent::NodeDelta {
-            node_index, delta, ..
-        } => {
-            if strip_state.stripped_indices.contains(node_index)
-                && matches!(delta, NodeDelta::Reasoning { .. })
-            {
-                *delta = NodeDelta::Text {
-                    content: String::new(),
-                };
-            }
+        UrpStreamEvent::NodeDelta { node_index, .. } => {
+            strip_state.drop_current = strip_state.stripped_indices.contains(node_index);
         }
         UrpStreamEvent::NodeDone {
             node_index, node, ..
         } => {
-            if strip_state.stripped_indices.remove(node_index) {
-                let (id, extra_body) = match node {
-                    Node::Reasoning { id, extra_body, .. } => {
-                        (id.take(), std::mem::take(extra_body))
-                    }
-                    _ => (None, Default::default()),
-                };
-                *node = Node::Text {
-                    id,
-                    role: OrdinaryRole::Assistant,
-                    content: String::new(),
-                    phase: None,
-                    extra_body,
-                };
-            }
+            let started_reasoning = strip_state.stripped_indices.remove(node_index);
+            strip_state.drop_current = started_reasoning || is_reasoning_node(node);
         }
         UrpStreamEvent::ResponseDone { output, .. } => {
-            *output = strip_reasoning_nodes(output);
+            output.retain(|node| !is_reasoning_node(node));
         }
         _ => {}
     }
 }
 
+fn is_reasoning_node(node: &Node) -> bool {
+    match node {
+        Node::Reasoning { .. } => true,
+        Node::NextDownstreamEnvelopeExtra { extra_body } => envelope_is_reasoning(extra_body),
+        _ => false,
+    }
+}
+
+fn envelope_is_reasoning(extra_body: &HashMap<String, Value>) -> bool {
+    extra_body.contains_key("encrypted_content")
+        || extra_body.get("type").and_then(Value::as_str) == Some("reasoning")
+}
+
 inventory::submit!(TransformEntry {
     factory: || Box::new(StripReasoningTransform),
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_transform_cache::ImageTransformCache;
+    use crate::urp::{NodeDelta, OrdinaryRole};
+    use tempfile::TempDir;
+
+    async fn ctx() -> (TempDir, TransformRuntimeContext) {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let cache = ImageTransformCache::new(
+            temp_dir.path().join("cache"),
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .expect("cache");
+        let context = TransformRuntimeContext {
+            image_transform_cache: std::sync::Arc::new(cache),
+            http_client: reqwest::Client::new(),
+            upstream_provider_type: None,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
+        };
+        (temp_dir, context)
+    }
+
+    fn reasoning_node() -> Node {
+        Node::Reasoning {
+            id: Some("rs_1".into()),
+            content: Some("thinking".into()),
+            encrypted: Some(json!("mz2.aaaaaaaa")),
+            summary: None,
+            source: None,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn drops_reasoning_lifecycle_from_stream() {
+        let transform = StripReasoningTransform;
+        let cfg = transform.parse_config(json!({})).unwrap();
+        let mut state = transform.init_state();
+        let (_tmp, context) = ctx().await;
+        let envelope_extra: HashMap<String, Value> =
+            [("encrypted_content".to_string(), json!("mz2.bbbbbbbb"))]
+                .into_iter()
+                .collect();
+        let events = vec![
+            UrpStreamEvent::NodeStart {
+                node_index: 0,
+                header: NodeHeader::Reasoning {
+                    id: Some("rs_1".into()),
+                },
+                extra_body: HashMap::new(),
+            },
+            UrpStreamEvent::NodeDelta {
+                node_index: 0,
+                delta: NodeDelta::Reasoning {
+                    content: Some("thinking".into()),
+                    encrypted: Some(json!("mz2.aaaaaaaa")),
+                    summary: None,
+                    source: None,
+                },
+                usage: None,
+                extra_body: HashMap::new(),
+            },
+            UrpStreamEvent::NodeDone {
+                node_index: 0,
+                node: reasoning_node(),
+                usage: None,
+                extra_body: HashMap::new(),
+            },
+            UrpStreamEvent::NodeStart {
+                node_index: 1,
+                header: NodeHeader::NextDownstreamEnvelopeExtra,
+                extra_body: envelope_extra.clone(),
+            },
+            UrpStreamEvent::NodeDone {
+                node_index: 1,
+                node: Node::NextDownstreamEnvelopeExtra {
+                    extra_body: envelope_extra.clone(),
+                },
+                usage: None,
+                extra_body: HashMap::new(),
+            },
+            UrpStreamEvent::NodeStart {
+                node_index: 2,
+                header: NodeHeader::Text {
+                    id: None,
+                    role: OrdinaryRole::Assistant,
+                    phase: None,
+                },
+                extra_body: HashMap::new(),
+            },
+            UrpStreamEvent::NodeDelta {
+                node_index: 2,
+                delta: NodeDelta::Text {
+                    content: "hi".into(),
+                },
+                usage: None,
+                extra_body: HashMap::new(),
+            },
+            UrpStreamEvent::NodeDone {
+                node_index: 2,
+                node: Node::assistant_text("hi"),
+                usage: None,
+                extra_body: HashMap::new(),
+            },
+            UrpStreamEvent::ResponseDone {
+                finish_reason: None,
+                usage: None,
+                output: vec![
+                    reasoning_node(),
+                    Node::NextDownstreamEnvelopeExtra {
+                        extra_body: envelope_extra,
+                    },
+                    Node::assistant_text("hi"),
+                ],
+                extra_body: HashMap::new(),
+            },
+        ];
+
+        let mut emitted = Vec::new();
+        for mut event in events {
+            transform
+                .apply(
+                    UrpData::Stream(&mut event),
+                    Phase::Response,
+                    &context,
+                    cfg.as_ref(),
+                    state.as_mut(),
+                )
+                .await
+                .unwrap();
+            emitted.extend(state.finalize_stream_event(event));
+        }
+
+        assert_eq!(emitted.len(), 4);
+        for event in &emitted[..3] {
+            let index = match event {
+                UrpStreamEvent::NodeStart { node_index, .. }
+                | UrpStreamEvent::NodeDelta { node_index, .. }
+                | UrpStreamEvent::NodeDone { node_index, .. } => *node_index,
+                other => panic!("unexpected event {other:?}"),
+            };
+            assert_eq!(index, 2);
+        }
+        let UrpStreamEvent::ResponseDone { output, .. } = &emitted[3] else {
+            panic!("expected response done");
+        };
+        assert_eq!(output, &vec![Node::assistant_text("hi")]);
+    }
+}
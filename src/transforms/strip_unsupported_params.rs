@@ -0,0 +1,332 @@
+use crate::config::ProviderType;
+use crate::transforms::{
+    NoState, Phase, Transform, TransformConfig, TransformEntry, TransformError,
+    TransformRuntimeContext, TransformState, UrpData,
+};
+use crate::urp::{Node, UrpRequest};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// OpenAI-only request parameters that Anthropic Messages rejects.
+const MESSAGES_DENY: &[&str] = &[
+    "frequency_penalty",
+    "logit_bias",
+    "logprobs",
+    "modalities",
+    "n",
+    "prediction",
+    "presence_penalty",
+    "prompt_cache_key",
+    "safety_identifier",
+    "seed",
+    "store",
+    "stream_options",
+    "top_logprobs",
+    "web_search_options",
+];
+
+/// Gemini accepts sampling controls only inside `generationConfig`, so the
+/// OpenAI top-level spellings are rejected as unknown fields.
+const GEMINI_DENY: &[&str] = &[
+    "frequency_penalty",
+    "logit_bias",
+    "logprobs",
+    "metadata",
+    "modalities",
+    "n",
+    "prediction",
+    "presence_penalty",
+    "prompt_cache_key",
+    "safety_identifier",
+    "seed",
+    "service_tier",
+    "store",
+    "stream_options",
+    "top_logprobs",
+    "web_search_options",
+];
+
+fn builtin_deny_list(provider_type: ProviderType) -> &'static [&'static str] {
+    match provider_type {
+        ProviderType::Messages => MESSAGES_DENY,
+        ProviderType::Gemini => GEMINI_DENY,
+        ProviderType::Responses
+        | ProviderType::ChatCompletion
+        | ProviderType::OpenaiImage
+        | ProviderType::Replicate
+        | ProviderType::Group => &[],
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Config {
+    /// Per provider type, replaces the built-in deny list for that type.
+    deny: HashMap<ProviderType, Vec<String>>,
+}
+
+impl TransformConfig for Config {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct StripUnsupportedParamsTransform;
+
+#[async_trait]
+impl Transform for StripUnsupportedParamsTransform {
+    fn type_id(&self) -> &'static str {
+        "strip_unsupported_params"
+    }
+
+    fn supported_phases(&self) -> &'static [Phase] {
+        &[Phase::Request]
+    }
+
+    fn config_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "deny": {
+                    "type": "object",
+                    "description": "Per provider type (e.g. \"messages\"), replaces the built-in list of keys to strip.",
+                    "additionalProperties": {
+                        "type": "array",
+                        "items": { "type": "string", "minLength": 1 }
+                    }
+                }
+            },
+            "additionalProperties": false
+        })
+    }
+
+    fn parse_config(&self, raw: Value) -> Result<Box<dyn TransformConfig>, TransformError> {
+        let cfg: Config = serde_json::from_value(raw)
+            .map_err(|e| TransformError::InvalidConfig(e.to_string()))?;
+        Ok(Box::new(cfg))
+    }
+
+    fn init_state(&self) -> Box<dyn TransformState> {
+        Box::new(NoState)
+    }
+
+    async fn apply(
+        &self,
+        data: UrpData<'_>,
+        _phase: Phase,
+        context: &TransformRuntimeContext,
+        config: &dyn TransformConfig,
+        _state: &mut dyn TransformState,
+    ) -> Result<(), TransformError> {
+        let cfg = config
+            .as_any()
+            .downcast_ref::<Config>()
+            .ok_or_else(|| TransformError::Apply("invalid config type".to_string()))?;
+        let UrpData::Request(req) = data else {
+            return Ok(());
+        };
+        let Some(provider_type) = context.upstream_provider_type else {
+            return Ok(());
+        };
+        match cfg.deny.get(&provider_type) {
+            Some(keys) => strip_keys(req, keys),
+            None => strip_keys(req, builtin_deny_list(provider_type)),
+        }
+        Ok(())
+    }
+}
+
+fn strip_keys<K: AsRef<str>>(req: &mut UrpRequest, keys: &[K]) {
+    if keys.is_empty() {
+        return;
+    }
+    let strip = |extra_body: &mut HashMap<String, Value>| {
+        for key in keys {
+            extra_body.remove(key.as_ref());
+        }
+    };
+    strip(&mut req.extra_body);
+    for node in &mut req.input {
+        strip(node.extra_body_mut());
+        if let Node::ToolResult { content, .. } = node {
+            for part in content {
+                strip(part.extra_body_mut());
+            }
+        }
+    }
+}
+
+inventory::submit!(TransformEntry {
+    factory: || Box::new(StripUnsupportedParamsTransform),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image_transform_cache::ImageTransformCache;
+    use crate::urp::{OrdinaryRole, ToolCallType, ToolResultContent};
+    use tempfile::TempDir;
+
+    async fn context(provider_type: Option<ProviderType>) -> TransformRuntimeContext {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let cache = ImageTransformCache::new(
+            temp_dir.path().join("cache"),
+            std::time::Duration::from_secs(60),
+        )
+        .await
+        .expect("cache");
+        TransformRuntimeContext {
+            image_transform_cache: std::sync::Arc::new(cache),
+            http_client: reqwest::Client::new(),
+            upstream_provider_type: provider_type,
+            upstream_channel: None,
+            gemini_context_cache: Default::default(),
+            request_tool_names: Vec::new(),
+        }
+    }
+
+    fn openai_flavoured_request() -> UrpRequest {
+        let openai_extra = || {
+            HashMap::from([
+                ("seed".to_string(), json!(7)),
+                ("logit_bias".to_string(), json!({ "50256": -100 })),
+                ("cache_control".to_string(), json!({ "type": "ephemeral" })),
+            ])
+        };
+        let mut extra_body = openai_extra();
+        extra_body.insert("frequency_penalty".to_string(), json!(0.5));
+        extra_body.insert("top_k".to_string(), json!(40));
+        UrpRequest {
+            model: "claude-sonnet".to_string(),
+            input: vec![
+                Node::Text {
+                    id: None,
+                    role: OrdinaryRole::User,
+                    content: "hi".to_string(),
+                    phase: None,
+                    extra_body: openai_extra(),
+                },
+                Node::ToolResult {
+                    id: None,
+                    tool_type: ToolCallType::Function,
+                    call_id: "call_1".to_string(),
+                    is_error: false,
+                    content: vec![ToolResultContent::Text {
+                        text: "ok".to_string(),
+                        extra_body: openai_extra(),
+                    }],
+                    extra_body: openai_extra(),
+                },
+            ],
+            stream: None,
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            max_output_tokens: None,
+            reasoning: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            stop: None,
+            verbosity: None,
+            response_format: None,
+            user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
+            extra_body,
+        }
+    }
+
+    async fn run(req: &mut UrpRequest, provider_type: Option<ProviderType>, raw: Value) {
+        let transform = StripUnsupportedParamsTransform;
+        let config = transform.parse_config(raw).expect("config");
+        let mut state = transform.init_state();
+        transform
+            .apply(
+                UrpData::Request(req),
+                Phase::Request,
+                &context(provider_type).await,
+                config.as_ref(),
+                state.as_mut(),
+            )
+            .await
+            .expect("apply");
+    }
+
+    fn all_extra_keys(req: &UrpRequest) -> Vec<Vec<String>> {
+        let sorted = |extra: &HashMap<String, Value>| {
+            let mut keys: Vec<String> = extra.keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+        let mut out = vec![sorted(&req.extra_body)];
+        for node in &req.input {
+            match node {
+                Node::Text { extra_body, .. } => out.push(sorted(extra_body)),
+                Node::ToolResult {
+                    content,
+                    extra_body,
+                    ..
+                } => {
+                    out.push(sorted(extra_body));
+                    for part in content {
+                        if let ToolResultContent::Text { extra_body, .. } = part {
+                            out.push(sorted(extra_body));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn anthropic_deny_list_strips_request_and_nested_extra() {
+        let mut req = openai_flavoured_request();
+        run(&mut req, Some(ProviderType::Messages), json!({})).await;
+
+        let keys = all_extra_keys(&req);
+        assert_eq!(keys[0], vec!["cache_control", "top_k"]);
+        for nested in &keys[1..] {
+            assert_eq!(nested, &vec!["cache_control"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn passes_through_for_openai_targets_and_unknown_provider() {
+        for provider_type in [Some(ProviderType::ChatCompletion), None] {
+            let mut req = openai_flavoured_request();
+            let before = all_extra_keys(&req);
+            run(&mut req, provider_type, json!({})).await;
+            assert_eq!(all_extra_keys(&req), before);
+        }
+    }
+
+    #[tokio::test]
+    async fn config_override_replaces_builtin_list_for_that_provider_type() {
+        let mut req = openai_flavoured_request();
+        run(
+            &mut req,
+            Some(ProviderType::Messages),
+            json!({ "deny": { "messages": ["top_k"] } }),
+        )
+        .await;
+
+        assert_eq!(
+            all_extra_keys(&req)[0],
+            vec!["cache_control", "frequency_penalty", "logit_bias", "seed"]
+        );
+        assert!(
+            StripUnsupportedParamsTransform
+                .parse_config(json!({ "deny": { "bogus": [] } }))
+                .is_err()
+        );
+    }
+}
@@ -20,15 +20,27 @@ impl TransformConfig for Config {
     }
 }
 
+#[derive(Default)]
+struct NodeParseState {
+    in_reasoning: bool,
+    /// Trailing text that may be the start of the next open/close tag.
+    pending: String,
+}
+
 #[derive(Default)]
 struct StreamState {
-    in_reasoning: HashMap<u32, bool>,
+    nodes: HashMap<u32, NodeParseState>,
+    replacement: Option<Vec<UrpStreamEvent>>,
 }
 
 impl TransformState for StreamState {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn finalize_stream_event(&mut self, event: UrpStreamEvent) -> Vec<UrpStreamEvent> {
+        self.replacement.take().unwrap_or_else(|| vec![event])
+    }
 }
 
 pub struct ThinkXmlToReasoningTransform;
@@ -166,9 +178,9 @@ fn extract_text_and_reasoning(content: &str, tag: &str) -> Vec<Node> {
     nodes
 }
 
+/// Tags may be split across deltas, so text that could still turn into a tag
+/// is held back until the next delta (or the node's end) settles it.
 fn apply_stream(event: &mut UrpStreamEvent, state: &mut StreamState, tag: &str) {
-    let open = format!("<{tag}>");
-    let close = format!("</{tag}>");
     match event {
         UrpStreamEvent::NodeStart {
             node_index,
@@ -179,36 +191,57 @@ fn apply_stream(event: &mut UrpStreamEvent, state: &mut StreamState, tag: &str)
                 },
             ..
         } => {
-            state.in_reasoning.insert(*node_index, false);
+            state.nodes.insert(*node_index, NodeParseState::default());
         }
         UrpStreamEvent::NodeDelta {
-            node_index, delta, ..
+            node_index,
+            delta: NodeDelta::Text { content },
+            usage,
+            extra_body,
         } => {
-            let Some(in_reasoning) = state.in_reasoning.get_mut(node_index) else {
+            let Some(node_state) = state.nodes.get_mut(node_index) else {
                 return;
             };
-            if let NodeDelta::Text { content } = delta {
-                if content.contains(&open) || *in_reasoning {
-                    let mut s = content.clone();
-                    if let Some(pos) = s.find(&open) {
-                        s = s[(pos + open.len())..].to_string();
-                        *in_reasoning = true;
-                    }
-                    if let Some(end) = s.find(&close) {
-                        s = s[..end].to_string();
-                        *in_reasoning = false;
-                    }
-                    *delta = NodeDelta::Reasoning {
-                        content: Some(s),
-                        encrypted: None,
-                        summary: None,
-                        source: None,
-                    };
+            let segments = split_stream_text(node_state, content, tag);
+            let mut emitted: Vec<UrpStreamEvent> = segments
+                .into_iter()
+                .map(|(is_reasoning, content)| UrpStreamEvent::NodeDelta {
+                    node_index: *node_index,
+                    delta: stream_delta(is_reasoning, content),
+                    usage: None,
+                    extra_body: extra_body.clone(),
+                })
+                .collect();
+            if let Some(usage) = usage.take() {
+                match emitted.last_mut() {
+                    Some(UrpStreamEvent::NodeDelta { usage: last, .. }) => *last = Some(usage),
+                    _ => emitted.push(UrpStreamEvent::NodeDelta {
+                        node_index: *node_index,
+                        delta: NodeDelta::Text {
+                            content: String::new(),
+                        },
+                        usage: Some(usage),
+                        extra_body: extra_body.clone(),
+                    }),
                 }
             }
+            state.replacement = Some(emitted);
         }
         UrpStreamEvent::NodeDone { node_index, .. } => {
-            state.in_reasoning.remove(node_index);
+            let Some(node_state) = state.nodes.remove(node_index) else {
+                return;
+            };
+            if !node_state.pending.is_empty() {
+                state.replacement = Some(vec![
+                    UrpStreamEvent::NodeDelta {
+                        node_index: *node_index,
+                        delta: stream_delta(node_state.in_reasoning, node_state.pending),
+                        usage: None,
+                        extra_body: HashMap::new(),
+                    },
+                    event.clone(),
+                ]);
+            }
         }
         UrpStreamEvent::ResponseDone { output, .. } => {
             let mut rewritten = Vec::with_capacity(output.len());
@@ -228,6 +261,182 @@ fn apply_stream(event: &mut UrpStreamEvent, state: &mut StreamState, tag: &str)
     }
 }
 
+/// Splits buffered plus new text into `(is_reasoning, text)` segments and
+/// keeps any trailing partial tag in `pending`.
+fn split_stream_text(
+    node_state: &mut NodeParseState,
+    content: &str,
+    tag: &str,
+) -> Vec<(bool, String)> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut buffer = std::mem::take(&mut node_state.pending);
+    buffer.push_str(content);
+    let mut segments = Vec::new();
+    let mut rest = buffer.as_str();
+    loop {
+        let marker = if node_state.in_reasoning {
+            &close
+        } else {
+            &open
+        };
+        if let Some(pos) = rest.find(marker.as_str()) {
+            if pos > 0 {
+                segments.push((node_state.in_reasoning, rest[..pos].to_string()));
+            }
+            node_state.in_reasoning = !node_state.in_reasoning;
+            rest = &rest[pos + marker.len()..];
+            continue;
+        }
+        let held = partial_marker_suffix_len(rest, marker);
+        let settled = &rest[..rest.len() - held];
+        if !settled.is_empty() {
+            segments.push((node_state.in_reasoning, settled.to_string()));
+        }
+        node_state.pending = rest[rest.len() - held..].to_string();
+        break;
+    }
+    segments
+}
+
+/// Length of the longest suffix of `text` that is a proper prefix of `marker`.
+fn partial_marker_suffix_len(text: &str, marker: &str) -> usize {
+    (1..marker.len())
+        .rev()
+        .filter(|&len| marker.is_char_boundary(len))
+        .find(|&len| text.ends_with(&marker[..len]))
+        .unwrap_or(0)
+}
+
+fn stream_delta(is_reasoning: bool, content: String) -> NodeDelta {
+    if is_reasoning {
+        NodeDelta::Reasoning {
+            content: Some(content),
+            encrypted: None,
+            summary: None,
+            source: None,
+        }
+    } else {
+        NodeDelta::Text { content }
+    }
+}
+
 inventory::submit!(TransformEntry {
     factory: || Box::new(ThinkXmlToReasoningTransform),
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(state: &mut StreamState, mut event: UrpStreamEvent) -> Vec<UrpStreamEvent> {
+        apply_stream(&mut event, state, "think");
+        state.finalize_stream_event(event)
+    }
+
+    fn text_delta(content: &str) -> UrpStreamEvent {
+        UrpStreamEvent::NodeDelta {
+            node_index: 0,
+            delta: NodeDelta::Text {
+                content: content.to_string(),
+            },
+            usage: None,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn collect_deltas(events: &[UrpStreamEvent]) -> Vec<(bool, String)> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                UrpStreamEvent::NodeDelta {
+                    delta: NodeDelta::Text { content },
+                    ..
+                } => Some((false, content.clone())),
+                UrpStreamEvent::NodeDelta {
+                    delta: NodeDelta::Reasoning { content, .. },
+                    ..
+                } => Some((true, content.clone().unwrap_or_default())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tags_split_across_three_deltas_become_reasoning_deltas() {
+        let mut state = StreamState::default();
+        run(
+            &mut state,
+            UrpStreamEvent::NodeStart {
+                node_index: 0,
+                header: NodeHeader::Text {
+                    id: None,
+                    role: OrdinaryRole::Assistant,
+                    phase: None,
+                },
+                extra_body: HashMap::new(),
+            },
+        );
+
+        let mut events = Vec::new();
+        for chunk in ["Sure <thi", "nk>step one, ste", "p two</th", "ink>Answer"] {
+            events.extend(run(&mut state, text_delta(chunk)));
+        }
+        events.extend(run(
+            &mut state,
+            UrpStreamEvent::NodeDone {
+                node_index: 0,
+                node: Node::Text {
+                    id: None,
+                    role: OrdinaryRole::Assistant,
+                    content: "Sure <think>step one, step two</think>Answer".to_string(),
+                    phase: None,
+                    extra_body: HashMap::new(),
+                },
+                usage: None,
+                extra_body: HashMap::new(),
+            },
+        ));
+
+        assert_eq!(
+            collect_deltas(&events),
+            vec![
+                (false, "Sure ".to_string()),
+                (true, "step one, ste".to_string()),
+                (true, "p two".to_string()),
+                (false, "Answer".to_string()),
+            ]
+        );
+        assert!(matches!(
+            events.last(),
+            Some(UrpStreamEvent::NodeDone { .. })
+        ));
+    }
+
+    #[test]
+    fn unfinished_partial_tag_is_flushed_as_text_on_node_done() {
+        let mut state = StreamState::default();
+        state.nodes.insert(0, NodeParseState::default());
+
+        let events = run(&mut state, text_delta("a < b <th"));
+        assert_eq!(collect_deltas(&events), vec![(false, "a < b ".to_string())]);
+
+        let events = run(
+            &mut state,
+            UrpStreamEvent::NodeDone {
+                node_index: 0,
+                node: Node::Text {
+                    id: None,
+                    role: OrdinaryRole::Assistant,
+                    content: "a < b <th".to_string(),
+                    phase: None,
+                    extra_body: HashMap::new(),
+                },
+                usage: None,
+                extra_body: HashMap::new(),
+            },
+        );
+        assert_eq!(collect_deltas(&events), vec![(false, "<th".to_string())]);
+        assert_eq!(events.len(), 2);
+    }
+}
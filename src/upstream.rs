@@ -17,6 +17,8 @@ pub struct UpstreamCallError {
     pub error_type: Option<String>,
     pub param: Option<String>,
     pub message: String,
+    /// Delay requested by the upstream `Retry-After` header.
+    pub retry_after: Option<std::time::Duration>,
 }
 
 impl UpstreamCallError {
@@ -28,6 +30,7 @@ impl UpstreamCallError {
             error_type: None,
             param: None,
             message,
+            retry_after: None,
         }
     }
 
@@ -37,6 +40,29 @@ impl UpstreamCallError {
         self.param = info.param;
         self
     }
+
+    pub fn with_retry_after(mut self, retry_after: Option<std::time::Duration>) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+}
+
+/// Parses a `Retry-After` value given either as delay seconds or as an
+/// HTTP-date. A date in the past yields a zero delay.
+pub fn parse_retry_after(
+    value: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<std::time::Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(std::time::Duration::from_secs(seconds));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (at.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
 }
 
 #[derive(Debug, Clone, Default)]
@@ -115,6 +141,30 @@ pub async fn call_upstream_with_timeout_and_headers(
         extra_headers,
     )
     .await?;
+    read_json_response(resp).await
+}
+
+/// Same as `call_upstream_with_timeout_and_headers`, for a body that is already
+/// serialized JSON, so callers that check the encoded size send exactly the
+/// bytes they measured.
+pub async fn call_upstream_json_bytes_with_timeout_and_headers(
+    client: &reqwest::Client,
+    provider: &ProviderConfig,
+    auth_value: &str,
+    path: &str,
+    body: bytes::Bytes,
+    timeout_ms: u64,
+    extra_headers: &[(&str, &str)],
+) -> Result<Value, UpstreamCallError> {
+    let req = build_upstream_post(client, provider, auth_value, path, extra_headers)?
+        .timeout(std::time::Duration::from_millis(timeout_ms))
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body);
+    let resp = send_upstream(req).await?;
+    read_json_response(resp).await
+}
+
+async fn read_json_response(resp: reqwest::Response) -> Result<Value, UpstreamCallError> {
     let status = resp.status();
     let text = resp.text().await.map_err(|err| {
         UpstreamCallError::new(UpstreamErrorKind::Network, Some(status), err.to_string())
@@ -154,58 +204,196 @@ pub async fn call_upstream_raw_with_timeout_and_headers(
     timeout_ms: u64,
     extra_headers: &[(&str, &str)],
 ) -> Result<reqwest::Response, UpstreamCallError> {
-    let base = provider.base_url.as_ref().ok_or_else(|| {
+    let req = build_upstream_post(client, provider, auth_value, path, extra_headers)?
+        .timeout(std::time::Duration::from_millis(timeout_ms))
+        .json(body);
+    send_upstream(req).await
+}
+
+/// Streaming variant: `header_timeout_ms` bounds only the wait for response headers.
+/// Once headers arrive, the body is limited by the client read timeout and the
+/// handler's stream idle timeout instead of a total deadline.
+pub async fn call_upstream_stream_with_headers(
+    client: &reqwest::Client,
+    provider: &ProviderConfig,
+    auth_value: &str,
+    path: &str,
+    body: &Value,
+    header_timeout_ms: u64,
+    extra_headers: &[(&str, &str)],
+) -> Result<reqwest::Response, UpstreamCallError> {
+    let req = build_upstream_post(client, provider, auth_value, path, extra_headers)?.json(body);
+    send_upstream_with_header_timeout(req, header_timeout_ms).await
+}
+
+/// Same as `call_upstream_stream_with_headers`, for a body that is already
+/// serialized JSON.
+pub async fn call_upstream_stream_json_bytes_with_headers(
+    client: &reqwest::Client,
+    provider: &ProviderConfig,
+    auth_value: &str,
+    path: &str,
+    body: bytes::Bytes,
+    header_timeout_ms: u64,
+    extra_headers: &[(&str, &str)],
+) -> Result<reqwest::Response, UpstreamCallError> {
+    let req = build_upstream_post(client, provider, auth_value, path, extra_headers)?
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body);
+    send_upstream_with_header_timeout(req, header_timeout_ms).await
+}
+
+async fn send_upstream_with_header_timeout(
+    req: reqwest::RequestBuilder,
+    header_timeout_ms: u64,
+) -> Result<reqwest::Response, UpstreamCallError> {
+    tokio::time::timeout(
+        std::time::Duration::from_millis(header_timeout_ms),
+        send_upstream(req),
+    )
+    .await
+    .map_err(|_| {
+        UpstreamCallError::new(
+            UpstreamErrorKind::Network,
+            None,
+            format!("upstream response headers not received within {header_timeout_ms}ms"),
+        )
+    })?
+}
+
+/// Binary variant for endpoints such as `/v1/audio/speech` whose success body is
+/// not JSON. Error bodies are still parsed as JSON envelopes; a successful
+/// response is returned untouched so the caller can stream its bytes through.
+pub async fn call_upstream_binary(
+    client: &reqwest::Client,
+    provider: &ProviderConfig,
+    auth_value: &str,
+    path: &str,
+    body: &Value,
+    header_timeout_ms: u64,
+    extra_headers: &[(&str, &str)],
+) -> Result<reqwest::Response, UpstreamCallError> {
+    let mut headers = vec![("accept", "*/*")];
+    headers.extend_from_slice(extra_headers);
+    call_upstream_stream_with_headers(
+        client,
+        provider,
+        auth_value,
+        path,
+        body,
+        header_timeout_ms,
+        &headers,
+    )
+    .await
+}
+
+pub async fn call_upstream_multipart_with_timeout_and_headers(
+    client: &reqwest::Client,
+    provider: &ProviderConfig,
+    auth_value: &str,
+    path: &str,
+    form: reqwest::multipart::Form,
+    timeout_ms: u64,
+    extra_headers: &[(&str, &str)],
+) -> Result<reqwest::Response, UpstreamCallError> {
+    let req = build_upstream_post(client, provider, auth_value, path, extra_headers)?
+        .timeout(std::time::Duration::from_millis(timeout_ms))
+        .multipart(form);
+    send_upstream(req).await
+}
+
+/// Uploads `bytes` through the Gemini Files API and returns the `file.uri`
+/// that `fileData` parts reference. The upload endpoint lives at the host
+/// root, so a `/v1beta` or `/v1` suffix on the channel base URL is dropped.
+pub async fn upload_gemini_file(
+    client: &reqwest::Client,
+    provider: &ProviderConfig,
+    auth_value: &str,
+    media_type: &str,
+    bytes: Vec<u8>,
+    timeout_ms: u64,
+) -> Result<String, UpstreamCallError> {
+    let base = provider.base_url.as_deref().ok_or_else(|| {
         UpstreamCallError::new(
             UpstreamErrorKind::Http,
             None,
             "missing base_url".to_string(),
         )
     })?;
-    let url = join_url(base, path);
-    let mut req = client
-        .post(url)
-        .timeout(std::time::Duration::from_millis(timeout_ms))
-        .json(body);
     let auth = provider.auth.as_ref().ok_or_else(|| {
         UpstreamCallError::new(UpstreamErrorKind::Http, None, "missing auth".to_string())
     })?;
-    req = apply_auth(req, auth, auth_value)
-        .map_err(|err| UpstreamCallError::new(UpstreamErrorKind::Http, None, err.message))?;
-    for (k, v) in extra_headers {
-        req = req.header(*k, *v);
-    }
-    let resp = req
-        .send()
+    let req = apply_auth(client.post(gemini_upload_url(base)), auth, auth_value)
+        .map_err(|err| UpstreamCallError::new(UpstreamErrorKind::Http, None, err.message))?
+        .query(&[("uploadType", "media")])
+        .header("x-goog-upload-protocol", "raw")
+        .header(reqwest::header::CONTENT_TYPE, media_type)
+        .timeout(std::time::Duration::from_millis(timeout_ms))
+        .body(bytes);
+    let resp = send_upstream(req).await?;
+    let body = resp
+        .json::<Value>()
         .await
         .map_err(|err| UpstreamCallError::new(UpstreamErrorKind::Network, None, err.to_string()))?;
-    let status = resp.status();
-    if !status.is_success() {
-        let text = resp.text().await.unwrap_or_default();
-        let info = extract_error_info(&text);
-        let message = info.message.clone().unwrap_or_else(|| {
-            if text.is_empty() {
-                "upstream returned an empty error body".to_string()
-            } else {
-                text
-            }
-        });
-        return Err(
-            UpstreamCallError::new(UpstreamErrorKind::Http, Some(status), message)
-                .with_error_info(info),
-        );
-    }
-    Ok(resp)
+    body.pointer("/file/uri")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| {
+            UpstreamCallError::new(
+                UpstreamErrorKind::Http,
+                None,
+                "gemini file upload response is missing file.uri".to_string(),
+            )
+        })
 }
 
-pub async fn call_upstream_multipart_with_timeout_and_headers(
+/// Creates a Gemini `cachedContent` resource from `body` and returns its
+/// resource `name` (`cachedContents/...`), which generate requests reference
+/// through the `cachedContent` field.
+pub async fn create_gemini_cache(
     client: &reqwest::Client,
     provider: &ProviderConfig,
     auth_value: &str,
-    path: &str,
-    form: reqwest::multipart::Form,
+    body: &Value,
     timeout_ms: u64,
+) -> Result<String, UpstreamCallError> {
+    let req = build_upstream_post(client, provider, auth_value, "/v1beta/cachedContents", &[])?
+        .timeout(std::time::Duration::from_millis(timeout_ms))
+        .json(body);
+    let resp = send_upstream(req).await?;
+    let body = resp
+        .json::<Value>()
+        .await
+        .map_err(|err| UpstreamCallError::new(UpstreamErrorKind::Network, None, err.to_string()))?;
+    body.get("name")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| {
+            UpstreamCallError::new(
+                UpstreamErrorKind::Http,
+                None,
+                "gemini cachedContents response is missing name".to_string(),
+            )
+        })
+}
+
+fn gemini_upload_url(base: &str) -> String {
+    let base = base.trim_end_matches('/');
+    let root = base
+        .strip_suffix("/v1beta")
+        .or_else(|| base.strip_suffix("/v1"))
+        .unwrap_or(base);
+    format!("{root}/upload/v1beta/files")
+}
+
+#[allow(clippy::result_large_err)]
+fn build_upstream_post(
+    client: &reqwest::Client,
+    provider: &ProviderConfig,
+    auth_value: &str,
+    path: &str,
     extra_headers: &[(&str, &str)],
-) -> Result<reqwest::Response, UpstreamCallError> {
+) -> Result<reqwest::RequestBuilder, UpstreamCallError> {
     let base = provider.base_url.as_ref().ok_or_else(|| {
         UpstreamCallError::new(
             UpstreamErrorKind::Http,
@@ -214,26 +402,38 @@ pub async fn call_upstream_multipart_with_timeout_and_headers(
         )
     })?;
     let url = join_url(base, path);
-    let mut req = client
-        .post(url)
-        .timeout(std::time::Duration::from_millis(timeout_ms))
-        .multipart(form);
     let auth = provider.auth.as_ref().ok_or_else(|| {
         UpstreamCallError::new(UpstreamErrorKind::Http, None, "missing auth".to_string())
     })?;
-    req = apply_auth(req, auth, auth_value)
+    let mut req = apply_auth(client.post(url), auth, auth_value)
         .map_err(|err| UpstreamCallError::new(UpstreamErrorKind::Http, None, err.message))?;
     for (k, v) in extra_headers {
         req = req.header(*k, *v);
     }
+    for (k, v) in &provider.extra_headers {
+        req = req.header(k, v);
+    }
+    Ok(req)
+}
+
+async fn send_upstream(
+    req: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, UpstreamCallError> {
     let resp = req
         .send()
         .await
         .map_err(|err| UpstreamCallError::new(UpstreamErrorKind::Network, None, err.to_string()))?;
     let status = resp.status();
     if !status.is_success() {
+        let retry_after = resp
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, chrono::Utc::now()));
         let text = resp.text().await.unwrap_or_default();
-        let info = extract_error_info(&text);
+        tracing::debug!(status = %status, upstream_body = %text, "upstream error body");
+        let body = serde_json::from_str::<Value>(&text).ok();
+        let info = body.as_ref().map(extract_error_info).unwrap_or_default();
         let message = info.message.clone().unwrap_or_else(|| {
             if text.is_empty() {
                 "upstream returned an empty error body".to_string()
@@ -243,7 +443,8 @@ pub async fn call_upstream_multipart_with_timeout_and_headers(
         });
         return Err(
             UpstreamCallError::new(UpstreamErrorKind::Http, Some(status), message)
-                .with_error_info(info),
+                .with_error_info(info)
+                .with_retry_after(retry_after),
         );
     }
     Ok(resp)
@@ -301,28 +502,54 @@ fn apply_auth(
     }
 }
 
+/// Joins a channel `base_url` and an upstream path. When the base already
+/// ends in an API version segment (`/v1`, `/v1beta`, ...), a leading version
+/// segment of the path is dropped so the channel's version wins and is never
+/// doubled, e.g. `https://host/v1` + `/v1/chat/completions`.
 fn join_url(base: &str, path: &str) -> String {
     let base = base.trim_end_matches('/');
     let mut path = path.trim_start_matches('/');
-    if base.ends_with("/v1") {
-        if path == "v1" {
-            path = "";
-        } else if let Some(stripped) = path.strip_prefix("v1/") {
-            path = stripped;
+    let base_has_version = base
+        .rsplit_once('/')
+        .is_some_and(|(_, last)| is_api_version_segment(last));
+    if base_has_version {
+        let (first, rest) = path.split_once('/').unwrap_or((path, ""));
+        let first = first.split_once('?').map_or(first, |(segment, _)| segment);
+        if is_api_version_segment(first) {
+            path = if rest.is_empty() {
+                &path[first.len()..]
+            } else {
+                rest
+            };
         }
     }
     if path.is_empty() {
         base.to_string()
+    } else if path.starts_with('?') {
+        format!("{base}{path}")
     } else {
         format!("{base}/{path}")
     }
 }
 
-fn extract_error_info(text: &str) -> UpstreamErrorInfo {
-    let Ok(value) = serde_json::from_str::<Value>(text) else {
-        return UpstreamErrorInfo::default();
+/// `v` followed by a digit and then only ASCII alphanumerics: `v1`, `v2`,
+/// `v1beta`, `v1alpha1`.
+fn is_api_version_segment(segment: &str) -> bool {
+    let mut chars = segment.chars();
+    chars.next() == Some('v')
+        && chars.next().is_some_and(|ch| ch.is_ascii_digit())
+        && chars.all(|ch| ch.is_ascii_alphanumeric())
+}
+
+/// Reads the OpenAI (`error.code`/`type`/`param`), Anthropic (`error.type`),
+/// and Gemini (`error.status`, `BadRequest` field violations) envelopes. Gemini
+/// may wrap the envelope in a single-element array.
+fn extract_error_info(body: &Value) -> UpstreamErrorInfo {
+    let body = match body {
+        Value::Array(items) => items.first().unwrap_or(body),
+        _ => body,
     };
-    let Some(error) = value.get("error") else {
+    let Some(error) = body.get("error") else {
         return UpstreamErrorInfo::default();
     };
     let metadata = error.get("metadata").and_then(Value::as_object);
@@ -332,15 +559,20 @@ fn extract_error_info(text: &str) -> UpstreamErrorInfo {
                 .and_then(|metadata| metadata.get("provider_code"))
                 .and_then(json_scalar_string)
         }),
-        error_type: error.get("type").and_then(json_scalar_string).or_else(|| {
-            metadata
-                .and_then(|metadata| metadata.get("error_type"))
-                .and_then(json_scalar_string)
-        }),
+        error_type: error
+            .get("type")
+            .and_then(json_scalar_string)
+            .or_else(|| {
+                metadata
+                    .and_then(|metadata| metadata.get("error_type"))
+                    .and_then(json_scalar_string)
+            })
+            .or_else(|| error.get("status").and_then(json_scalar_string)),
         param: error
             .get("param")
             .and_then(|v| v.as_str())
-            .map(|s| s.to_string()),
+            .map(|s| s.to_string())
+            .or_else(|| gemini_field_violation(error)),
         message: error
             .get("message")
             .and_then(|v| v.as_str())
@@ -348,6 +580,16 @@ fn extract_error_info(text: &str) -> UpstreamErrorInfo {
     }
 }
 
+fn gemini_field_violation(error: &Value) -> Option<String> {
+    error
+        .get("details")?
+        .as_array()?
+        .iter()
+        .filter_map(|detail| detail.get("fieldViolations").and_then(Value::as_array))
+        .flatten()
+        .find_map(|violation| violation.get("field").and_then(json_scalar_string))
+}
+
 fn json_scalar_string(value: &Value) -> Option<String> {
     match value {
         Value::String(value) if !value.is_empty() => Some(value.clone()),
@@ -360,19 +602,175 @@ fn json_scalar_string(value: &Value) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
-    fn openrouter_error_info_accepts_numeric_code_and_metadata_fallbacks() {
-        let info = extract_error_info(
-            r#"{"error":{"code":502,"message":"provider failed","metadata":{"provider_code":"P502","error_type":"provider_error"}}}"#,
+    fn retry_after_accepts_delay_seconds_and_http_dates() {
+        let now = chrono::DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(
+            parse_retry_after(" 120 ", now),
+            Some(std::time::Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(std::time::Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(std::time::Duration::ZERO)
         );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn openrouter_error_info_accepts_numeric_code_and_metadata_fallbacks() {
+        let info = extract_error_info(&json!({
+            "error": {
+                "code": 502,
+                "message": "provider failed",
+                "metadata": { "provider_code": "P502", "error_type": "provider_error" }
+            }
+        }));
         assert_eq!(info.code.as_deref(), Some("502"));
         assert_eq!(info.error_type.as_deref(), Some("provider_error"));
 
-        let fallback = extract_error_info(
-            r#"{"error":{"message":"provider failed","metadata":{"provider_code":529,"error_type":"upstream_error"}}}"#,
-        );
+        let fallback = extract_error_info(&json!({
+            "error": {
+                "message": "provider failed",
+                "metadata": { "provider_code": 529, "error_type": "upstream_error" }
+            }
+        }));
         assert_eq!(fallback.code.as_deref(), Some("529"));
         assert_eq!(fallback.error_type.as_deref(), Some("upstream_error"));
     }
+
+    #[test]
+    fn openai_error_info_reads_type_param_and_code() {
+        let info = extract_error_info(&json!({
+            "error": {
+                "message": "Invalid value for 'temperature'.",
+                "type": "invalid_request_error",
+                "param": "temperature",
+                "code": "invalid_value"
+            }
+        }));
+        assert_eq!(info.code.as_deref(), Some("invalid_value"));
+        assert_eq!(info.error_type.as_deref(), Some("invalid_request_error"));
+        assert_eq!(info.param.as_deref(), Some("temperature"));
+        assert_eq!(
+            info.message.as_deref(),
+            Some("Invalid value for 'temperature'.")
+        );
+    }
+
+    #[test]
+    fn anthropic_error_info_reads_nested_error_type() {
+        let info = extract_error_info(&json!({
+            "type": "error",
+            "error": {
+                "type": "invalid_request_error",
+                "message": "max_tokens: Field required"
+            }
+        }));
+        assert_eq!(info.code, None);
+        assert_eq!(info.error_type.as_deref(), Some("invalid_request_error"));
+        assert_eq!(info.param, None);
+        assert_eq!(info.message.as_deref(), Some("max_tokens: Field required"));
+    }
+
+    #[test]
+    fn gemini_error_info_uses_status_as_type_and_field_violation_as_param() {
+        let envelope = json!({
+            "error": {
+                "code": 400,
+                "message": "Invalid value at 'generation_config.temperature'",
+                "status": "INVALID_ARGUMENT",
+                "details": [{
+                    "@type": "type.googleapis.com/google.rpc.BadRequest",
+                    "fieldViolations": [{
+                        "field": "generation_config.temperature",
+                        "description": "Invalid value"
+                    }]
+                }]
+            }
+        });
+        let info = extract_error_info(&envelope);
+        assert_eq!(info.code.as_deref(), Some("400"));
+        assert_eq!(info.error_type.as_deref(), Some("INVALID_ARGUMENT"));
+        assert_eq!(info.param.as_deref(), Some("generation_config.temperature"));
+
+        let wrapped = extract_error_info(&json!([envelope]));
+        assert_eq!(wrapped.error_type.as_deref(), Some("INVALID_ARGUMENT"));
+    }
+
+    #[test]
+    fn join_url_never_doubles_or_drops_version_segments() {
+        for (base, path, expected) in [
+            (
+                "https://api.x.ai",
+                "/v1/responses",
+                "https://api.x.ai/v1/responses",
+            ),
+            (
+                "https://api.x.ai/",
+                "/v1/responses",
+                "https://api.x.ai/v1/responses",
+            ),
+            (
+                "https://host/v1",
+                "/v1/chat/completions",
+                "https://host/v1/chat/completions",
+            ),
+            (
+                "https://host/v1/",
+                "v1/chat/completions",
+                "https://host/v1/chat/completions",
+            ),
+            (
+                "https://gateway.example/openai/v1",
+                "/v1/messages",
+                "https://gateway.example/openai/v1/messages",
+            ),
+            (
+                "https://generativelanguage.googleapis.com/v1beta",
+                "/v1beta/models/gemini-2.5-pro:generateContent",
+                "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-pro:generateContent",
+            ),
+            (
+                "https://generativelanguage.googleapis.com/v1",
+                "/v1beta/models/gemini-2.5-pro:streamGenerateContent?alt=sse",
+                "https://generativelanguage.googleapis.com/v1/models/gemini-2.5-pro:streamGenerateContent?alt=sse",
+            ),
+            ("https://host/v1", "/v1", "https://host/v1"),
+            (
+                "https://host/api",
+                "/v1/responses",
+                "https://host/api/v1/responses",
+            ),
+            (
+                "https://host/video",
+                "/v1/models",
+                "https://host/video/v1/models",
+            ),
+        ] {
+            assert_eq!(join_url(base, path), expected, "{base} + {path}");
+        }
+    }
+
+    #[test]
+    fn gemini_upload_url_uses_host_root() {
+        for base in [
+            "https://generativelanguage.googleapis.com",
+            "https://generativelanguage.googleapis.com/",
+            "https://generativelanguage.googleapis.com/v1beta",
+            "https://generativelanguage.googleapis.com/v1",
+        ] {
+            assert_eq!(
+                gemini_upload_url(base),
+                "https://generativelanguage.googleapis.com/upload/v1beta/files"
+            );
+        }
+    }
 }
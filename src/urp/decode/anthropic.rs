@@ -1,8 +1,8 @@
 use crate::urp::decode::{
     deserialize_u64ish_default, is_internal_extra_key, parse_file_node_from_obj,
     parse_file_source_from_obj, parse_image_node_from_obj, parse_image_source_from_obj,
-    parse_tool_definition, remove_untrusted_internal_keys, retain_wire_extra_fields, split_extra,
-    value_to_text, value_to_u64,
+    parse_tool_definition, parse_top_k, remove_untrusted_internal_keys, retain_wire_extra_fields,
+    split_extra, value_to_text, value_to_u64,
 };
 use crate::urp::{
     FILE_ID_ORIGIN_EXTRA_KEY, FILE_ID_ORIGIN_MESSAGES, FileSource, FinishReason, ImageSource,
@@ -134,7 +134,11 @@ fn decode_anthropic_reasoning_config(obj: &Map<String, Value>) -> Option<Reasoni
             Value::Object(output_config),
         );
     }
-    Some(ReasoningConfig { effort, extra_body })
+    Some(ReasoningConfig {
+        effort,
+        summary: None,
+        extra_body,
+    })
 }
 
 fn decode_anthropic_response_format(obj: &Map<String, Value>) -> Option<ResponseFormat> {
@@ -538,6 +542,7 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
             "stream",
             "temperature",
             "top_p",
+            "top_k",
             "max_tokens",
             "thinking",
             "output_config",
@@ -546,6 +551,7 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
             "parallel_tool_calls",
             "stop_sequences",
             "metadata",
+            "service_tier",
         ],
     );
     if let Some(mut metadata) = obj.get("metadata").and_then(Value::as_object).cloned() {
@@ -561,6 +567,7 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
         stream: obj.get("stream").and_then(|v| v.as_bool()),
         temperature: obj.get("temperature").and_then(|v| v.as_f64()),
         top_p: obj.get("top_p").and_then(|v| v.as_f64()),
+        top_k: parse_top_k(obj.get("top_k"))?,
         max_output_tokens: obj.get("max_tokens").and_then(|v| v.as_u64()),
         reasoning,
         tools,
@@ -584,6 +591,14 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
             .and_then(|v| v.get("user_id"))
             .and_then(|v| v.as_str())
             .map(|s| s.to_string()),
+        service_tier: obj
+            .get("service_tier")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        include: None,
+        logprobs: None,
+        top_logprobs: None,
+        previous_response_id: None,
         extra_body,
     })
 }
@@ -663,9 +678,10 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
         }
     }
 
-    let finish_reason = match obj.get("stop_reason").and_then(|v| v.as_str()) {
+    let raw_stop_reason = obj.get("stop_reason").and_then(|v| v.as_str());
+    let finish_reason = match raw_stop_reason {
         Some("end_turn" | "stop_sequence") => Some(FinishReason::Stop),
-        Some("max_tokens") => Some(FinishReason::Length),
+        Some("max_tokens" | "model_context_window_exceeded") => Some(FinishReason::Length),
         Some("tool_use") => Some(FinishReason::ToolCalls),
         Some("refusal") => Some(FinishReason::ContentFilter),
         _ => Some(FinishReason::Other),
@@ -676,6 +692,8 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
         .cloned()
         .and_then(|v| serde_json::from_value::<AnthropicUsage>(v).ok())
         .map(Usage::from);
+    let mut extra_body = split_extra(obj, &["id", "type", "role", "model", "content", "usage"]);
+    crate::urp::record_raw_finish_reason(&mut extra_body, finish_reason, raw_stop_reason);
 
     Ok(UrpResponse {
         id: obj
@@ -692,7 +710,7 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
         output: output_nodes,
         finish_reason,
         usage,
-        extra_body: split_extra(obj, &["id", "type", "role", "model", "content", "usage"]),
+        extra_body,
     })
 }
 
@@ -1166,6 +1184,94 @@ mod tests {
         assert!(matches!(content[0], ToolResultContent::Text { .. }));
     }
 
+    #[test]
+    fn messages_image_tool_result_encodes_multimodal_for_each_provider() {
+        let value = json!({
+            "model": "claude-sonnet-4-6",
+            "messages": [
+                { "role": "user", "content": "take a screenshot" },
+                {
+                    "role": "assistant",
+                    "content": [{
+                        "type": "tool_use",
+                        "id": "toolu_1",
+                        "name": "screenshot",
+                        "input": {}
+                    }]
+                },
+                {
+                    "role": "user",
+                    "content": [{
+                        "type": "tool_result",
+                        "tool_use_id": "toolu_1",
+                        "content": [
+                            { "type": "text", "text": "captured" },
+                            {
+                                "type": "image",
+                                "source": { "type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo=" }
+                            }
+                        ]
+                    }]
+                }
+            ]
+        });
+
+        let decoded = decode_request(&value).expect("messages request decodes");
+        let Some(Node::ToolResult { content, .. }) = decoded.input.last() else {
+            panic!("expected tool result");
+        };
+        assert!(matches!(
+            &content[1],
+            ToolResultContent::Image {
+                source: ImageSource::Base64 { media_type, data },
+                ..
+            } if media_type == "image/png" && data == "iVBORw0KGgo="
+        ));
+
+        let messages = crate::urp::encode::anthropic::encode_request(&decoded, "claude-sonnet-4-6");
+        assert_eq!(
+            messages["messages"][2]["content"][0]["content"],
+            value["messages"][2]["content"][0]["content"]
+        );
+
+        let responses = crate::urp::encode::openai_responses::encode_request(&decoded, "gpt-5.4");
+        let output = responses["input"]
+            .as_array()
+            .expect("responses input")
+            .iter()
+            .find(|item| item["type"] == json!("function_call_output"))
+            .expect("function_call_output item")["output"]
+            .clone();
+        assert_eq!(output[1]["type"], json!("input_image"));
+        assert_eq!(
+            output[1]["image_url"],
+            json!("data:image/png;base64,iVBORw0KGgo=")
+        );
+
+        let chat = crate::urp::encode::openai_chat::encode_request(&decoded, "gpt-5.4");
+        let chat_messages = chat["messages"].as_array().expect("chat messages");
+        assert_eq!(chat_messages[2]["role"], json!("tool"));
+        assert_eq!(chat_messages[2]["content"], json!("captured"));
+        assert_eq!(chat_messages[3]["role"], json!("user"));
+        assert_eq!(
+            chat_messages[3]["content"],
+            json!([{
+                "type": "image_url",
+                "image_url": { "url": "data:image/png;base64,iVBORw0KGgo=" }
+            }])
+        );
+
+        let gemini = crate::urp::encode::gemini::encode_request(&decoded, "gemini-2.5-pro");
+        let parts = gemini["contents"][2]["parts"]
+            .as_array()
+            .expect("gemini tool result parts");
+        assert_eq!(parts[0]["functionResponse"]["name"], json!("screenshot"));
+        assert_eq!(
+            parts[1],
+            json!({ "inlineData": { "mimeType": "image/png", "data": "iVBORw0KGgo=" } })
+        );
+    }
+
     #[test]
     fn messages_tool_result_file_ids_keep_files_api_provenance() {
         let value = json!({
@@ -1522,4 +1628,85 @@ mod tests {
         assert_eq!(encoded["content"][0], value["content"][0]);
         assert_eq!(encoded["stop_reason"], json!("pause_turn"));
     }
+
+    #[test]
+    fn stop_reasons_map_to_finish_reasons_and_re_encode_per_downstream() {
+        let cases = [
+            // (stop_reason, finish_reason, chat finish_reason, responses status, incomplete reason)
+            ("end_turn", FinishReason::Stop, "stop", "completed", None),
+            (
+                "stop_sequence",
+                FinishReason::Stop,
+                "stop",
+                "completed",
+                None,
+            ),
+            (
+                "max_tokens",
+                FinishReason::Length,
+                "length",
+                "incomplete",
+                Some("max_output_tokens"),
+            ),
+            (
+                "model_context_window_exceeded",
+                FinishReason::Length,
+                "length",
+                "incomplete",
+                Some("max_output_tokens"),
+            ),
+            (
+                "tool_use",
+                FinishReason::ToolCalls,
+                "tool_calls",
+                "completed",
+                None,
+            ),
+            (
+                "refusal",
+                FinishReason::ContentFilter,
+                "content_filter",
+                "incomplete",
+                Some("content_filter"),
+            ),
+            ("pause_turn", FinishReason::Other, "error", "failed", None),
+        ];
+        for (stop_reason, finish_reason, chat_reason, status, incomplete_reason) in cases {
+            let value = json!({
+                "id": "msg_stop",
+                "type": "message",
+                "role": "assistant",
+                "model": "claude-sonnet-4-6",
+                "content": [{ "type": "text", "text": "hi" }],
+                "stop_reason": stop_reason,
+                "stop_sequence": if stop_reason == "stop_sequence" { json!("<END>") } else { Value::Null },
+                "usage": { "input_tokens": 1, "output_tokens": 1 }
+            });
+
+            let decoded = decode_response(&value).expect("messages response decodes");
+            assert_eq!(decoded.finish_reason, Some(finish_reason), "{stop_reason}");
+
+            let messages = crate::urp::encode::anthropic::encode_response(&decoded, "m");
+            assert_eq!(messages["stop_reason"], json!(stop_reason));
+            assert_eq!(messages["stop_sequence"], value["stop_sequence"]);
+
+            let chat = crate::urp::encode::openai_chat::encode_response(&decoded, "m");
+            assert_eq!(
+                chat["choices"][0]["finish_reason"],
+                json!(chat_reason),
+                "{stop_reason}"
+            );
+
+            let mut without_native_extra = decoded.clone();
+            without_native_extra.extra_body.clear();
+            let responses =
+                crate::urp::encode::openai_responses::encode_response(&without_native_extra, "m");
+            assert_eq!(responses["status"], json!(status), "{stop_reason}");
+            assert_eq!(
+                responses["incomplete_details"]["reason"].as_str(),
+                incomplete_reason,
+                "{stop_reason}"
+            );
+        }
+    }
 }
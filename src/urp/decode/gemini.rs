@@ -1,5 +1,5 @@
 use crate::urp::decode::{
-    deserialize_u64ish_default, parse_file_part_from_obj, parse_image_part_from_obj,
+    deserialize_u64ish_default, parse_file_part_from_obj, parse_image_part_from_obj, parse_top_k,
     retain_wire_extra_fields, split_extra,
 };
 use crate::urp::internal_legacy_bridge::{Part, Role};
@@ -204,6 +204,7 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
             };
             reasoning = Some(ReasoningConfig {
                 effort,
+                summary: None,
                 extra_body: split_extra(
                     thinking,
                     &["thinkingBudget", "includeThoughts", "thinkingLevel"],
@@ -238,6 +239,7 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
             .get("generationConfig")
             .and_then(|v| v.get("topP"))
             .and_then(|v| v.as_f64()),
+        top_k: parse_top_k(obj.get("generationConfig").and_then(|v| v.get("topK")))?,
         max_output_tokens: obj
             .get("generationConfig")
             .and_then(|v| v.get("maxOutputTokens"))
@@ -250,6 +252,11 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
         verbosity: None,
         response_format: None,
         user: None,
+        service_tier: None,
+        include: None,
+        logprobs: None,
+        top_logprobs: None,
+        previous_response_id: None,
         extra_body: split_extra(
             obj,
             &[
@@ -275,25 +282,61 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
         .get("candidates")
         .and_then(|v| v.as_array())
         .and_then(|arr| arr.first())
-        .and_then(|v| v.as_object())
-        .ok_or_else(|| "missing candidates[0]".to_string())?;
+        .and_then(|v| v.as_object());
+    // A prompt blocked by safety settings returns `promptFeedback.blockReason`
+    // and no candidates.
+    let prompt_blocked = candidate.is_none()
+        && obj
+            .get("promptFeedback")
+            .and_then(|v| v.get("blockReason"))
+            .and_then(|v| v.as_str())
+            .is_some_and(|reason| !reason.is_empty());
+    if candidate.is_none() && !prompt_blocked {
+        return Err("missing candidates[0]".to_string());
+    }
 
     let content = candidate
-        .get("content")
+        .and_then(|candidate| candidate.get("content"))
         .and_then(|v| v.as_object())
         .cloned()
         .unwrap_or_default();
 
-    let output_nodes = decode_response_nodes(&content);
-    let finish_reason = candidate
-        .get("finishReason")
-        .and_then(|v| v.as_str())
-        .map(parse_finish_reason);
+    let mut output_nodes = decode_response_nodes(&content);
+    if output_nodes.is_empty()
+        && let Some(content) = blocked_response_refusal(value)
+    {
+        output_nodes.push(Node::Refusal {
+            id: None,
+            content,
+            extra_body: HashMap::new(),
+        });
+    }
+    let raw_finish_reason = candidate
+        .and_then(|candidate| candidate.get("finishReason"))
+        .and_then(|v| v.as_str());
+    let finish_reason = if prompt_blocked {
+        Some(FinishReason::ContentFilter)
+    } else {
+        raw_finish_reason.map(parse_finish_reason)
+    };
 
     let usage = obj
         .get("usageMetadata")
         .and_then(|v| v.as_object())
         .map(parse_usage);
+    let mut extra_body = split_extra(
+        obj,
+        &[
+            "candidates",
+            "promptFeedback",
+            "usageMetadata",
+            "modelVersion",
+            "responseId",
+            "id",
+            "model",
+        ],
+    );
+    crate::urp::record_raw_finish_reason(&mut extra_body, finish_reason, raw_finish_reason);
 
     Ok(UrpResponse {
         id: obj
@@ -312,18 +355,7 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
         output: output_nodes,
         finish_reason,
         usage,
-        extra_body: split_extra(
-            obj,
-            &[
-                "candidates",
-                "promptFeedback",
-                "usageMetadata",
-                "modelVersion",
-                "responseId",
-                "id",
-                "model",
-            ],
-        ),
+        extra_body,
     })
 }
 
@@ -522,6 +554,14 @@ fn decode_content_parts(obj: &Map<String, Value>) -> Vec<Part> {
                 },
                 extra_body: split_extra(obj, &["inlineData"]),
             });
+        } else if mime.starts_with("video/") {
+            out.push(Part::Video {
+                source: crate::urp::VideoSource::Base64 {
+                    media_type: mime,
+                    data,
+                },
+                extra_body: split_extra(obj, &["inlineData"]),
+            });
         } else {
             out.push(Part::File {
                 source: crate::urp::FileSource::Base64 {
@@ -552,6 +592,11 @@ fn decode_content_parts(obj: &Map<String, Value>) -> Vec<Part> {
                 },
                 extra_body: split_extra(obj, &["fileData"]),
             });
+        } else if mime.starts_with("video/") {
+            out.push(Part::Video {
+                source: crate::urp::VideoSource::Url { url: uri },
+                extra_body: split_extra(obj, &["fileData"]),
+            });
         } else {
             out.push(Part::File {
                 source: crate::urp::FileSource::Url { url: uri },
@@ -595,11 +640,15 @@ fn decode_function_response(fr: &Map<String, Value>) -> Node {
         .unwrap_or("")
         .to_string();
     let response_value = fr.get("response").cloned().unwrap_or(Value::Null);
+    // Gemini reports a failed call as `response.error`.
+    let is_error = response_value
+        .get("error")
+        .is_some_and(|error| !error.is_null());
     Node::ToolResult {
         id: fr.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()),
         tool_type: crate::urp::ToolCallType::Function,
         call_id: name.clone(),
-        is_error: false,
+        is_error,
         content: vec![ToolResultContent::Text {
             text: serde_json::to_string(&response_value).unwrap_or_default(),
             extra_body: HashMap::new(),
@@ -664,10 +713,55 @@ fn take_output_extra(
     }
 }
 
+/// Refusal text for a Gemini response that was blocked instead of answered:
+/// either the prompt was rejected (`promptFeedback.blockReason`, no
+/// candidates) or the candidate stopped on a content-filter `finishReason`.
+pub(crate) fn blocked_response_refusal(response: &Value) -> Option<String> {
+    fn non_empty_str(value: Option<&Value>) -> Option<&str> {
+        value.and_then(Value::as_str).filter(|s| !s.is_empty())
+    }
+    let candidate = response
+        .get("candidates")
+        .and_then(Value::as_array)
+        .and_then(|candidates| candidates.first());
+    let (subject, reason, message) = match candidate {
+        None => {
+            let feedback = response.get("promptFeedback")?;
+            (
+                "prompt",
+                non_empty_str(feedback.get("blockReason"))?,
+                non_empty_str(feedback.get("blockReasonMessage")),
+            )
+        }
+        Some(candidate) => {
+            let reason = non_empty_str(candidate.get("finishReason"))?;
+            if parse_finish_reason(reason) != FinishReason::ContentFilter {
+                return None;
+            }
+            (
+                "response",
+                reason,
+                non_empty_str(candidate.get("finishMessage")),
+            )
+        }
+    };
+    Some(match message {
+        Some(message) => format!("The {subject} was blocked by Gemini ({reason}): {message}"),
+        None => format!("The {subject} was blocked by Gemini ({reason})."),
+    })
+}
+
 fn parse_finish_reason(reason: &str) -> FinishReason {
     match reason {
         "MAX_TOKENS" => FinishReason::Length,
-        "SAFETY" => FinishReason::ContentFilter,
+        "SAFETY"
+        | "RECITATION"
+        | "BLOCKLIST"
+        | "PROHIBITED_CONTENT"
+        | "SPII"
+        | "IMAGE_SAFETY"
+        | "IMAGE_PROHIBITED_CONTENT"
+        | "IMAGE_RECITATION" => FinishReason::ContentFilter,
         "STOP" => FinishReason::Stop,
         _ => FinishReason::Other,
     }
@@ -705,6 +799,7 @@ fn collect_content_text(value: &Value) -> String {
 #[cfg(test)]
 mod tests {
     use super::{decode_response, parse_usage};
+    use crate::urp::Node;
     use crate::urp::internal_legacy_bridge::nodes_to_items;
     use serde_json::{Value, json};
 
@@ -724,6 +819,86 @@ mod tests {
         assert!(!usage.extra_body.contains_key("_monoize_spoofed_usage"));
     }
 
+    #[test]
+    fn decode_response_maps_content_blocks_to_content_filter() {
+        let blocked = decode_response(&json!({
+            "promptFeedback": { "blockReason": "PROHIBITED_CONTENT" },
+            "usageMetadata": { "promptTokenCount": 4, "totalTokenCount": 4 }
+        }))
+        .expect("prompt-blocked response decodes");
+        assert!(matches!(
+            blocked.output.as_slice(),
+            [Node::Refusal { content, .. }]
+                if content == "The prompt was blocked by Gemini (PROHIBITED_CONTENT)."
+        ));
+        assert_eq!(
+            blocked.finish_reason,
+            Some(crate::urp::FinishReason::ContentFilter)
+        );
+
+        for reason in [
+            "SAFETY",
+            "RECITATION",
+            "BLOCKLIST",
+            "PROHIBITED_CONTENT",
+            "SPII",
+            "IMAGE_SAFETY",
+        ] {
+            let response = decode_response(&json!({
+                "candidates": [{ "finishReason": reason }]
+            }))
+            .expect("decode");
+            assert_eq!(
+                response.finish_reason,
+                Some(crate::urp::FinishReason::ContentFilter)
+            );
+            assert!(matches!(
+                response.output.as_slice(),
+                [Node::Refusal { content, .. }] if content.contains(reason)
+            ));
+        }
+
+        let with_message = decode_response(&json!({
+            "candidates": [{
+                "finishReason": "SAFETY",
+                "finishMessage": "harassment threshold exceeded"
+            }]
+        }))
+        .expect("decode");
+        assert!(matches!(
+            with_message.output.as_slice(),
+            [Node::Refusal { content, .. }]
+                if content == "The response was blocked by Gemini (SAFETY): harassment threshold exceeded"
+        ));
+
+        assert!(decode_response(&json!({ "usageMetadata": {} })).is_err());
+    }
+
+    #[test]
+    fn decode_response_maps_stop_and_max_tokens_without_refusal() {
+        for (reason, expected) in [
+            ("STOP", crate::urp::FinishReason::Stop),
+            ("MAX_TOKENS", crate::urp::FinishReason::Length),
+            ("MALFORMED_FUNCTION_CALL", crate::urp::FinishReason::Other),
+        ] {
+            let response = decode_response(&json!({
+                "candidates": [{
+                    "finishReason": reason,
+                    "content": { "role": "model", "parts": [{ "text": "partial" }] }
+                }]
+            }))
+            .expect("decode");
+            assert_eq!(response.finish_reason, Some(expected), "{reason}");
+            assert!(
+                !response
+                    .output
+                    .iter()
+                    .any(|node| matches!(node, Node::Refusal { .. })),
+                "{reason}"
+            );
+        }
+    }
+
     #[test]
     fn decode_response_greedy_merges_assistant_parts_and_extracts_tool_results() {
         let response = decode_response(&json!({
@@ -9,7 +9,7 @@ use crate::urp::internal_legacy_bridge::Part;
 use crate::urp::{
     AudioSource, CustomToolDefinition, FILE_ID_ORIGIN_EXTRA_KEY, FILE_ID_ORIGIN_MESSAGES,
     FILE_ID_ORIGIN_OPENAI, FileSource, FunctionDefinition, ImageSource, Node, OrdinaryRole,
-    ToolDefinition,
+    ToolDefinition, VideoSource,
 };
 use serde::{Deserialize, Deserializer};
 use serde_json::{Map, Value};
@@ -80,6 +80,17 @@ pub fn value_to_u64(value: &Value) -> Option<u64> {
     }
 }
 
+/// Parses an optional `top_k` sampling control, rejecting anything but a positive integer.
+pub fn parse_top_k(value: Option<&Value>) -> Result<Option<u64>, String> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => match value.as_u64() {
+            Some(top_k) if top_k > 0 => Ok(Some(top_k)),
+            _ => Err("top_k must be a positive integer".to_string()),
+        },
+    }
+}
+
 fn normalize_tool_parameters(params: Option<Value>) -> Option<Value> {
     let mut v = params?;
     if let Some(obj) = v.as_object_mut() {
@@ -630,6 +641,33 @@ pub fn parse_audio_part_from_obj(obj: &Map<String, Value>) -> Option<Part> {
     })
 }
 
+/// Parses an OpenAI-compatible `video_url` content part. A `data:` URL is
+/// split into an inline base64 source so encoders can send it inline.
+pub fn parse_video_part_from_obj(obj: &Map<String, Value>) -> Option<Part> {
+    if obj.get("type").and_then(Value::as_str) != Some("video_url") {
+        return None;
+    }
+    let url = match obj.get("video_url")? {
+        Value::String(url) => url.as_str(),
+        Value::Object(video_url) => video_url.get("url")?.as_str()?,
+        _ => return None,
+    };
+    let source = url
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(";base64,"))
+        .map(|(media_type, data)| VideoSource::Base64 {
+            media_type: media_type.to_string(),
+            data: data.to_string(),
+        })
+        .unwrap_or_else(|| VideoSource::Url {
+            url: url.to_string(),
+        });
+    Some(Part::Video {
+        source,
+        extra_body: split_extra(obj, &["type", "video_url"]),
+    })
+}
+
 fn file_id_origin_for_obj(obj: &Map<String, Value>) -> &'static str {
     if obj
         .get("source")
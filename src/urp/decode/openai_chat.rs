@@ -1,14 +1,15 @@
 use crate::urp::decode::{
     deserialize_u64ish_default, is_internal_extra_key, normalize_reasoning_effort,
     parse_audio_part_from_obj, parse_file_part_from_obj, parse_image_part_from_obj,
-    parse_tool_call_part_from_obj, parse_tool_definition, remove_untrusted_internal_keys,
-    retain_wire_extra_fields, split_extra, value_to_text,
+    parse_tool_call_part_from_obj, parse_tool_definition, parse_video_part_from_obj,
+    remove_untrusted_internal_keys, retain_wire_extra_fields, split_extra, value_to_text,
 };
 use crate::urp::internal_legacy_bridge::{Part, Role};
 use crate::urp::{
-    CHAT_LEGACY_FUNCTION_CALL_EXTRA_KEY, CHAT_LEGACY_FUNCTION_CHOICE_EXTRA_KEY,
+    AudioSource, CHAT_LEGACY_FUNCTION_CALL_EXTRA_KEY, CHAT_LEGACY_FUNCTION_CHOICE_EXTRA_KEY,
     CHAT_LEGACY_FUNCTION_DEFINITION_EXTRA_KEY, CHAT_LEGACY_FUNCTION_RESULT_EXTRA_KEY,
-    CHAT_MESSAGE_AUDIO_EXTRA_KEY, CHAT_REASONING_CONFIG_EXTRA_KEY, CHAT_REASONING_DETAIL_EXTRA_KEY,
+    CHAT_MESSAGE_AUDIO_EXTRA_KEY, CHAT_MESSAGE_AUDIO_TRANSCRIPT_EXTRA_KEY,
+    CHAT_REASONING_CONFIG_EXTRA_KEY, CHAT_REASONING_DETAIL_EXTRA_KEY,
     CHAT_REASONING_SURFACE_EXTRA_KEY, CHAT_REASONING_SURFACE_REASONING,
     CHAT_REASONING_SURFACE_REASONING_CONTENT, CHAT_THINKING_CONFIG_EXTRA_KEY, FinishReason,
     InputDetails, Node, OrdinaryRole, OutputDetails, ProviderProtocol, ReasoningConfig,
@@ -95,6 +96,9 @@ impl From<OpenAiChatUsage> for Usage {
         } = value;
 
         retain_wire_extra_fields(&mut extra);
+        // Encoders derive `total_tokens` from the typed counters; a stale
+        // upstream copy must not override it.
+        extra.remove("total_tokens");
         for details in [&mut prompt_tokens_details, &mut input_tokens_details]
             .into_iter()
             .flatten()
@@ -289,6 +293,45 @@ fn parse_chat_message_audio_part(value: &Value) -> Option<Part> {
     })
 }
 
+/// Response `message.audio` with inline `data` becomes a typed audio part
+/// followed by its transcript as text. Both are marked so the Chat encoder can
+/// fold them back into one `audio` object; anything else stays opaque.
+fn push_chat_response_audio_parts(parts: &mut Vec<Part>, value: &Value) {
+    let Some(obj) = value.as_object() else {
+        return;
+    };
+    let Some(data) = obj.get("data").and_then(Value::as_str) else {
+        parts.extend(parse_chat_message_audio_part(value));
+        return;
+    };
+    let media_type = match obj.get("format").and_then(Value::as_str) {
+        Some("mp3") => "audio/mpeg",
+        Some("flac") => "audio/flac",
+        Some("opus") => "audio/opus",
+        Some("aac") => "audio/aac",
+        Some("pcm16") => "audio/pcm",
+        _ => "audio/wav",
+    };
+    let mut extra_body = split_extra(obj, &["data", "transcript"]);
+    extra_body.insert(CHAT_MESSAGE_AUDIO_EXTRA_KEY.to_string(), Value::Bool(true));
+    parts.push(Part::Audio {
+        source: AudioSource::Base64 {
+            media_type: media_type.to_string(),
+            data: data.to_string(),
+        },
+        extra_body,
+    });
+    if let Some(transcript) = obj.get("transcript").and_then(Value::as_str) {
+        parts.push(Part::Text {
+            content: transcript.to_string(),
+            extra_body: HashMap::from([(
+                CHAT_MESSAGE_AUDIO_TRANSCRIPT_EXTRA_KEY.to_string(),
+                Value::Bool(true),
+            )]),
+        });
+    }
+}
+
 fn push_chat_content_parts(parts: &mut Vec<Part>, content: &Value, message_phase: Option<&str>) {
     if let Some(s) = content.as_str() {
         if !s.is_empty() {
@@ -335,6 +378,10 @@ fn push_chat_content_parts(parts: &mut Vec<Part>, content: &Value, message_phase
             parts.push(audio_part);
             recognized = true;
         }
+        if let Some(video_part) = parse_video_part_from_obj(item_obj) {
+            parts.push(video_part);
+            recognized = true;
+        }
         if let Some(tool_call_part) = parse_tool_call_part_from_obj(item_obj) {
             parts.push(tool_call_part);
             recognized = true;
@@ -606,6 +653,9 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
             "verbosity",
             "response_format",
             "user",
+            "service_tier",
+            "logprobs",
+            "top_logprobs",
         ],
     );
     if let Some(raw_choice) = legacy_function_choice_raw {
@@ -621,6 +671,7 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
         stream: obj.get("stream").and_then(|v| v.as_bool()),
         temperature: obj.get("temperature").and_then(|v| v.as_f64()),
         top_p: obj.get("top_p").and_then(|v| v.as_f64()),
+        top_k: None,
         max_output_tokens: obj
             .get("max_completion_tokens")
             .or_else(|| obj.get("max_tokens"))
@@ -651,6 +702,17 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
             .get("user")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string()),
+        service_tier: obj
+            .get("service_tier")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        include: None,
+        logprobs: obj.get("logprobs").and_then(Value::as_bool),
+        top_logprobs: obj
+            .get("top_logprobs")
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok()),
+        previous_response_id: None,
         extra_body,
     })
 }
@@ -710,12 +772,8 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
 
     parse_chat_reasoning_fields(msg_obj, &mut parts);
 
-    if let Some(audio) = msg_obj
-        .get("audio")
-        .filter(|audio| !audio.is_null())
-        .and_then(parse_chat_message_audio_part)
-    {
-        parts.push(audio);
+    if let Some(audio) = msg_obj.get("audio").filter(|audio| !audio.is_null()) {
+        push_chat_response_audio_parts(&mut parts, audio);
     }
 
     if let Some(content) = msg_obj.get("content") {
@@ -781,12 +839,18 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
             Value::Object(choice_extra),
         );
     }
+    crate::urp::record_raw_finish_reason(
+        &mut extra_body,
+        finish_reason,
+        native_finish_reason.as_deref(),
+    );
     if let Some(native_finish_reason) = native_finish_reason {
         extra_body.insert(
             CHAT_NATIVE_FINISH_REASON_EXTRA_KEY.to_string(),
             Value::String(native_finish_reason),
         );
     }
+    crate::urp::tool_call_ids::dedupe_tool_call_ids(&mut output_nodes);
 
     Ok(UrpResponse {
         id: obj
@@ -863,7 +927,11 @@ fn extract_reasoning(obj: &Map<String, Value>) -> Option<ReasoningConfig> {
             Value::Object(thinking),
         );
     }
-    Some(ReasoningConfig { effort, extra_body })
+    Some(ReasoningConfig {
+        effort,
+        summary: None,
+        extra_body,
+    })
 }
 
 fn parse_chat_reasoning_fields(msg_obj: &Map<String, Value>, parts: &mut Vec<Part>) {
@@ -973,6 +1041,7 @@ fn tool_choice_from_value(mut v: Value) -> ToolChoice {
 fn parse_response_format(v: Value) -> Option<crate::urp::ResponseFormat> {
     if let Some(obj) = v.as_object() {
         match obj.get("type").and_then(|x| x.as_str()) {
+            Some("text") => return Some(crate::urp::ResponseFormat::Text),
             Some("json_object") => return Some(crate::urp::ResponseFormat::JsonObject),
             Some("json_schema") => {
                 let schema_obj = obj.get("json_schema")?.as_object()?;
@@ -1019,7 +1088,7 @@ fn parse_finish_reason(s: &str) -> FinishReason {
 }
 
 fn parse_usage_from_chat(obj: &Map<String, Value>) -> Usage {
-    serde_json::from_value::<OpenAiChatUsage>(Value::Object(obj.clone()))
+    let mut usage = serde_json::from_value::<OpenAiChatUsage>(Value::Object(obj.clone()))
         .map(Usage::from)
         .unwrap_or_else(|_| Usage {
             input_tokens: 0,
@@ -1027,7 +1096,9 @@ fn parse_usage_from_chat(obj: &Map<String, Value>) -> Usage {
             input_details: None,
             output_details: None,
             extra_body: split_extra(obj, &[]),
-        })
+        });
+    usage.clamp_subset_counts("chat_completion");
+    usage
 }
 
 #[cfg(test)]
@@ -1047,6 +1118,24 @@ mod tests {
             .unwrap_or_default()
     }
 
+    #[test]
+    fn chat_usage_clamps_reasoning_and_cached_tokens_to_totals() {
+        let usage = parse_usage_from_chat(
+            json!({
+                "prompt_tokens": 6,
+                "completion_tokens": 2,
+                "prompt_tokens_details": { "cached_tokens": 6 },
+                "completion_tokens_details": { "reasoning_tokens": 40 }
+            })
+            .as_object()
+            .expect("usage json object expected"),
+        );
+
+        assert_eq!(usage.cached_tokens(), Some(6));
+        assert_eq!(usage.reasoning_tokens(), Some(2));
+        assert_eq!(usage.output_tokens, 2);
+    }
+
     #[test]
     fn chat_usage_preserves_nested_unknown_details() {
         let response = json!({
@@ -1422,6 +1511,13 @@ mod tests {
                 .and_then(|extra| extra.get("provider_marker")),
             Some(&json!("deepseek"))
         );
+        assert_eq!(
+            decoded
+                .extra_body
+                .get(crate::urp::RAW_FINISH_REASON_EXTRA_KEY)
+                .and_then(Value::as_str),
+            Some("insufficient_system_resource")
+        );
     }
 
     #[test]
@@ -1477,6 +1573,45 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn chat_video_url_parts_decode_to_video_and_encode_back_as_files() {
+        let decoded = decode_request(&json!({
+            "model": "gemini-2.5-flash",
+            "messages": [{
+                "role": "user",
+                "content": [
+                    {
+                        "type": "video_url",
+                        "video_url": { "url": "data:video/mp4;base64,AAAAIGZ0eXA=" }
+                    },
+                    { "type": "video_url", "video_url": { "url": "https://example.com/clip.mp4" } }
+                ]
+            }]
+        }))
+        .expect("chat request decodes");
+
+        assert!(matches!(
+            &decoded.input[0],
+            Node::Video {
+                source: crate::urp::VideoSource::Base64 { media_type, data },
+                ..
+            } if media_type == "video/mp4" && data == "AAAAIGZ0eXA="
+        ));
+        assert!(matches!(
+            &decoded.input[1],
+            Node::Video {
+                source: crate::urp::VideoSource::Url { url },
+                ..
+            } if url == "https://example.com/clip.mp4"
+        ));
+
+        let encoded = crate::urp::encode::openai_chat::encode_request(&decoded, "gpt-4o");
+        assert_eq!(
+            encoded["messages"][0]["content"],
+            json!([{ "type": "file", "file": { "file_data": "AAAAIGZ0eXA=" } }])
+        );
+    }
+
     #[test]
     fn chat_request_rejects_multiple_choices() {
         let error = decode_request(&json!({
@@ -1570,17 +1705,21 @@ mod tests {
         .expect("decode audio-only response");
         assert!(matches!(
             &decoded.output[0],
-            Node::ProviderItem {
-                origin_protocol: ProviderProtocol::ChatCompletion,
-                item_type,
-                body,
+            Node::Audio {
+                role: OrdinaryRole::Assistant,
+                source: AudioSource::Base64 { media_type, data },
                 extra_body,
                 ..
-            } if item_type == "audio"
-                && body == &audio
+            } if media_type == "audio/wav"
+                && data == "YXVkaW8="
+                && extra_body.get("id") == Some(&json!("audio_1"))
                 && extra_body.get(crate::urp::CHAT_MESSAGE_AUDIO_EXTRA_KEY)
                     == Some(&Value::Bool(true))
         ));
+        assert!(matches!(
+            &decoded.output[1],
+            Node::Text { content, .. } if content == "hello"
+        ));
 
         let encoded =
             crate::urp::encode::openai_chat::encode_response(&decoded, "gpt-4o-audio-preview");
@@ -1588,6 +1727,87 @@ mod tests {
         assert_eq!(encoded["choices"][0]["message"]["content"], Value::Null);
     }
 
+    #[test]
+    fn repeated_tool_call_ids_in_one_response_stay_distinct() {
+        let response = json!({
+            "id": "chatcmpl_dup",
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "finish_reason": "tool_calls",
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [
+                        { "id": "call_1", "type": "function", "function": { "name": "lookup", "arguments": "{\"q\":1}" } },
+                        { "id": "call_1", "type": "function", "function": { "name": "lookup", "arguments": "{\"q\":2}" } }
+                    ]
+                }
+            }]
+        });
+
+        let decoded = decode_response(&response).expect("decode chat response");
+        let calls: Vec<(&str, &str)> = decoded
+            .output
+            .iter()
+            .filter_map(|node| match node {
+                Node::ToolCall {
+                    call_id, arguments, ..
+                } => Some((call_id.as_str(), arguments.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            calls,
+            vec![("call_1", "{\"q\":1}"), ("call_1_2", "{\"q\":2}")]
+        );
+    }
+
+    #[test]
+    fn chat_audio_response_with_text_round_trips_through_decode_and_encode() {
+        let audio = json!({
+            "id": "audio_2",
+            "data": "UklGRg==",
+            "expires_at": 1_900_000_000,
+            "transcript": "It is sunny in Paris."
+        });
+        let decoded = decode_response(&json!({
+            "id": "chatcmpl_audio_text",
+            "model": "gpt-4o-audio-preview",
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": "Forecast attached.",
+                    "audio": audio.clone()
+                },
+                "finish_reason": "stop"
+            }]
+        }))
+        .expect("decode audio response");
+        let texts: Vec<&str> = decoded
+            .output
+            .iter()
+            .filter_map(|node| match node {
+                Node::Text { content, .. } => Some(content.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(texts, vec!["It is sunny in Paris.", "Forecast attached."]);
+        assert!(
+            decoded
+                .output
+                .iter()
+                .any(|node| matches!(node, Node::Audio { .. }))
+        );
+
+        let encoded =
+            crate::urp::encode::openai_chat::encode_response(&decoded, "gpt-4o-audio-preview");
+        let message = &encoded["choices"][0]["message"];
+        assert_eq!(message["audio"], audio);
+        assert_eq!(message["content"], "Forecast attached.");
+    }
+
     #[test]
     fn chat_tool_choice_rejects_recursive_internal_key_spoofing() {
         let decoded = decode_request(&json!({
@@ -1,7 +1,7 @@
 use crate::urp::decode::{
     deserialize_u64ish_default, normalize_reasoning_effort, parse_file_part_from_obj,
-    parse_image_part_from_obj, parse_tool_definition, remove_untrusted_internal_keys,
-    retain_wire_extra_fields, split_extra, value_to_text,
+    parse_image_part_from_obj, parse_tool_definition, parse_video_part_from_obj,
+    remove_untrusted_internal_keys, retain_wire_extra_fields, split_extra, value_to_text,
 };
 use crate::urp::internal_legacy_bridge::{Part, Role};
 use crate::urp::{
@@ -132,6 +132,9 @@ impl From<OpenAiResponsesUsage> for Usage {
         } = value;
 
         retain_wire_extra_fields(&mut extra);
+        // Encoders derive `total_tokens` from the typed counters; a stale
+        // upstream copy must not override it.
+        extra.remove("total_tokens");
         for details in [&mut input_tokens_details, &mut prompt_tokens_details]
             .into_iter()
             .flatten()
@@ -382,9 +385,18 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
                 .get("effort")
                 .and_then(|v| v.as_str())
                 .map(normalize_reasoning_effort);
-            (!reasoning_obj.is_empty()).then(|| ReasoningConfig {
+            let summary = reasoning_obj
+                .get("summary")
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+            let mut extra_body = split_extra(reasoning_obj, &["effort"]);
+            if summary.is_some() {
+                extra_body.remove("summary");
+            }
+            (!reasoning_obj.is_empty()).then_some(ReasoningConfig {
                 effort,
-                extra_body: split_extra(reasoning_obj, &["effort"]),
+                summary,
+                extra_body,
             })
         });
 
@@ -410,6 +422,10 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
             "parallel_tool_calls",
             "response_format",
             "user",
+            "service_tier",
+            "include",
+            "top_logprobs",
+            "previous_response_id",
         ],
     );
     if let Some(instructions) = obj.get("instructions") {
@@ -425,6 +441,7 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
         stream: obj.get("stream").and_then(|v| v.as_bool()),
         temperature: obj.get("temperature").and_then(|v| v.as_f64()),
         top_p: obj.get("top_p").and_then(|v| v.as_f64()),
+        top_k: None,
         max_output_tokens: obj.get("max_output_tokens").and_then(|v| v.as_u64()),
         reasoning,
         tools,
@@ -446,6 +463,26 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
             .get("user")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string()),
+        service_tier: obj
+            .get("service_tier")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        include: obj.get("include").and_then(Value::as_array).map(|values| {
+            values
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        }),
+        logprobs: None,
+        top_logprobs: obj
+            .get("top_logprobs")
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok()),
+        previous_response_id: obj
+            .get("previous_response_id")
+            .and_then(Value::as_str)
+            .map(str::to_string),
         extra_body,
     })
 }
@@ -457,18 +494,90 @@ fn decode_input_items_nodes(input: &Value, out: &mut Vec<Node>) {
     }
 
     if let Some(obj) = input.as_object() {
-        decode_input_item_nodes(obj, out);
+        if is_bare_input_content_item(obj) {
+            let mut parts = Vec::new();
+            decode_message_content_part(obj, None, &mut parts);
+            push_message_nodes(out, Role::User, None, parts, HashMap::new());
+        } else {
+            decode_input_item_nodes(obj, out);
+        }
         return;
     }
 
     if let Some(arr) = input.as_array() {
+        // Consecutive content items without a message wrapper form one user
+        // message, matching how the Responses API treats a bare content list.
+        let mut bare_parts = Vec::new();
         for item in arr {
             if let Some(obj) = item.as_object() {
+                if is_bare_input_content_item(obj) {
+                    decode_message_content_part(obj, None, &mut bare_parts);
+                    continue;
+                }
+                flush_bare_input_parts(out, &mut bare_parts);
                 decode_input_item_nodes(obj, out);
             } else if let Some(s) = item.as_str() {
+                flush_bare_input_parts(out, &mut bare_parts);
                 out.push(Node::text(OrdinaryRole::User, s));
             }
         }
+        flush_bare_input_parts(out, &mut bare_parts);
+    }
+}
+
+fn is_bare_input_content_item(obj: &Map<String, Value>) -> bool {
+    !obj.contains_key("role")
+        && matches!(
+            obj.get("type").and_then(|v| v.as_str()),
+            Some("input_text" | "input_image" | "input_file")
+        )
+}
+
+fn flush_bare_input_parts(out: &mut Vec<Node>, parts: &mut Vec<Part>) {
+    if !parts.is_empty() {
+        push_message_nodes(out, Role::User, None, std::mem::take(parts), HashMap::new());
+    }
+}
+
+fn decode_message_content_part(
+    pobj: &Map<String, Value>,
+    message_phase: Option<&str>,
+    parts: &mut Vec<Part>,
+) {
+    let ptype = pobj.get("type").and_then(|v| v.as_str()).unwrap_or("");
+    match ptype {
+        "input_text" | "output_text" | "text" => {
+            if let Some(text) = pobj
+                .get("text")
+                .and_then(|v| v.as_str())
+                .or_else(|| pobj.get("content").and_then(|v| v.as_str()))
+            {
+                parts.push(text_part_with_phase(
+                    text,
+                    message_phase,
+                    split_extra(pobj, &["type", "text", "content"]),
+                ));
+            }
+        }
+        "refusal" => {
+            if let Some(text) = pobj.get("refusal").and_then(|v| v.as_str()) {
+                parts.push(Part::Refusal {
+                    content: text.to_string(),
+                    extra_body: split_extra(pobj, &["type", "refusal"]),
+                });
+            }
+        }
+        _ => {
+            if let Some(image) = parse_image_part_from_obj(pobj) {
+                parts.push(image);
+            }
+            if let Some(file) = parse_file_part_from_obj(pobj) {
+                parts.push(file);
+            }
+            if let Some(video) = parse_video_part_from_obj(pobj) {
+                parts.push(video);
+            }
+        }
     }
 }
 
@@ -573,38 +682,7 @@ fn decode_input_item_nodes(obj: &Map<String, Value>, out: &mut Vec<Node>) {
                 } else if let Some(content_arr) = content.as_array() {
                     for p in content_arr {
                         let Some(pobj) = p.as_object() else { continue };
-                        let ptype = pobj.get("type").and_then(|v| v.as_str()).unwrap_or("");
-                        match ptype {
-                            "input_text" | "output_text" | "text" => {
-                                if let Some(text) = pobj
-                                    .get("text")
-                                    .and_then(|v| v.as_str())
-                                    .or_else(|| pobj.get("content").and_then(|v| v.as_str()))
-                                {
-                                    parts.push(text_part_with_phase(
-                                        text,
-                                        message_phase,
-                                        split_extra(pobj, &["type", "text", "content"]),
-                                    ));
-                                }
-                            }
-                            "refusal" => {
-                                if let Some(text) = pobj.get("refusal").and_then(|v| v.as_str()) {
-                                    parts.push(Part::Refusal {
-                                        content: text.to_string(),
-                                        extra_body: split_extra(pobj, &["type", "refusal"]),
-                                    });
-                                }
-                            }
-                            _ => {
-                                if let Some(image) = parse_image_part_from_obj(pobj) {
-                                    parts.push(image);
-                                }
-                                if let Some(file) = parse_file_part_from_obj(pobj) {
-                                    parts.push(file);
-                                }
-                            }
-                        }
+                        decode_message_content_part(pobj, message_phase, &mut parts);
                     }
                 }
             }
@@ -770,6 +848,9 @@ fn decode_response_message_nodes(
                     if let Some(file) = parse_file_part_from_obj(pobj) {
                         parts.push(file);
                     }
+                    if let Some(video) = parse_video_part_from_obj(pobj) {
+                        parts.push(video);
+                    }
                 }
             }
         }
@@ -1021,18 +1102,20 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
         .as_object()
         .ok_or_else(|| "responses response must be object".to_string())?;
 
-    let output_nodes = decode_response_nodes(obj);
+    let mut output_nodes = decode_response_nodes(obj);
+    crate::urp::tool_call_ids::dedupe_tool_call_ids(&mut output_nodes);
     let has_tool_calls = output_nodes
         .iter()
         .any(|node| matches!(node, Node::ToolCall { .. }));
 
-    let finish_reason = match obj.get("status").and_then(|v| v.as_str()) {
+    let status = obj.get("status").and_then(|v| v.as_str());
+    let finish_reason = match status {
         Some("completed") => Some(if has_tool_calls {
             FinishReason::ToolCalls
         } else {
             FinishReason::Stop
         }),
-        Some("incomplete") => Some(FinishReason::Length),
+        Some("incomplete") => Some(incomplete_finish_reason(obj.get("incomplete_details"))),
         Some("failed") => Some(FinishReason::Other),
         _ => None,
     };
@@ -1058,6 +1141,7 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
         RESPONSES_RESPONSE_SOURCE_EXTRA_KEY.to_string(),
         Value::Object(split_extra(obj, &[]).into_iter().collect()),
     );
+    crate::urp::record_raw_finish_reason(&mut extra_body, finish_reason, status);
 
     Ok(UrpResponse {
         id: obj
@@ -1078,6 +1162,18 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
     })
 }
 
+/// `status: "incomplete"` covers both output-token exhaustion and content
+/// filtering; only `incomplete_details.reason` tells them apart.
+pub(crate) fn incomplete_finish_reason(incomplete_details: Option<&Value>) -> FinishReason {
+    match incomplete_details
+        .and_then(|details| details.get("reason"))
+        .and_then(Value::as_str)
+    {
+        Some("content_filter") => FinishReason::ContentFilter,
+        _ => FinishReason::Length,
+    }
+}
+
 fn summary_to_text(item_obj: &Map<String, Value>) -> Option<String> {
     let mut out = String::new();
     if let Some(summary) = item_obj.get("summary").and_then(|v| v.as_array()) {
@@ -1093,7 +1189,7 @@ fn summary_to_text(item_obj: &Map<String, Value>) -> Option<String> {
 }
 
 fn parse_usage_from_responses(obj: &Map<String, Value>) -> Usage {
-    serde_json::from_value::<OpenAiResponsesUsage>(Value::Object(obj.clone()))
+    let mut usage = serde_json::from_value::<OpenAiResponsesUsage>(Value::Object(obj.clone()))
         .map(Usage::from)
         .unwrap_or_else(|_| Usage {
             input_tokens: 0,
@@ -1101,7 +1197,9 @@ fn parse_usage_from_responses(obj: &Map<String, Value>) -> Usage {
             input_details: None,
             output_details: None,
             extra_body: split_extra(obj, &[]),
-        })
+        });
+    usage.clamp_subset_counts("responses");
+    usage
 }
 
 fn responses_selector_to_urp(value: Value) -> Value {
@@ -1278,6 +1376,104 @@ mod tests {
         assert_eq!(encoded["input"].as_array().map(Vec::len), Some(1));
     }
 
+    #[test]
+    fn string_instructions_round_trip_alongside_input_system_messages() {
+        let source = json!({
+            "model": "gpt-5.4",
+            "instructions": "Keep answers short.",
+            "input": [
+                { "type": "message", "role": "system", "content": "Answer in French." },
+                { "type": "message", "role": "user", "content": "hello" }
+            ]
+        });
+
+        let decoded = decode_request(&source).expect("decode Responses request");
+        assert!(matches!(
+            decoded.input.first(),
+            Some(Node::Text {
+                role: OrdinaryRole::Developer,
+                content,
+                ..
+            }) if content == "Keep answers short."
+        ));
+
+        let encoded = crate::urp::encode::openai_responses::encode_request(&decoded, "gpt-5.4");
+        assert_eq!(encoded["instructions"], json!("Keep answers short."));
+        let input = encoded["input"].as_array().expect("responses input");
+        assert_eq!(input.len(), 2);
+        assert_eq!(input[0]["role"], json!("system"));
+        assert_eq!(input[1]["role"], json!("user"));
+    }
+
+    #[test]
+    fn leading_chat_system_messages_merge_into_responses_instructions() {
+        let chat = json!({
+            "model": "gpt-5.4",
+            "messages": [
+                { "role": "system", "content": "Be concise." },
+                { "role": "developer", "content": "Use metric units." },
+                { "role": "user", "content": "hello" },
+                { "role": "system", "content": "Switch to formal tone." },
+                { "role": "user", "content": "again" }
+            ]
+        });
+
+        let decoded =
+            crate::urp::decode::openai_chat::decode_request(&chat).expect("decode chat request");
+        let encoded = crate::urp::encode::openai_responses::encode_request(&decoded, "gpt-5.4");
+        assert_eq!(
+            encoded["instructions"],
+            json!("Be concise.\n\nUse metric units.")
+        );
+        let input = encoded["input"].as_array().expect("responses input");
+        assert_eq!(input.len(), 3);
+        assert_eq!(input[0]["role"], json!("user"));
+        assert_eq!(input[1]["role"], json!("system"));
+        assert_eq!(input[2]["role"], json!("user"));
+
+        let redecoded = decode_request(&encoded).expect("decode encoded Responses request");
+        let reencoded = crate::urp::encode::openai_responses::encode_request(&redecoded, "gpt-5.4");
+        assert_eq!(reencoded["instructions"], encoded["instructions"]);
+        assert_eq!(reencoded["input"], encoded["input"]);
+    }
+
+    #[test]
+    fn include_round_trips_as_typed_field() {
+        let source = json!({
+            "model": "gpt-5.4",
+            "input": "answer",
+            "include": ["reasoning.encrypted_content", "web_search_call.action.sources"]
+        });
+
+        let decoded = decode_request(&source).expect("decode Responses request");
+        assert_eq!(
+            decoded.include,
+            Some(vec![
+                "reasoning.encrypted_content".to_string(),
+                "web_search_call.action.sources".to_string(),
+            ])
+        );
+        assert!(!decoded.extra_body.contains_key("include"));
+
+        let encoded = crate::urp::encode::openai_responses::encode_request(&decoded, "gpt-5.4");
+        assert_eq!(encoded["include"], source["include"]);
+
+        let decoded = decode_request(&json!({
+            "model": "gpt-5.4",
+            "input": "answer",
+            "include": ["message.output_text.logprobs"]
+        }))
+        .expect("decode Responses request");
+        let encoded = crate::urp::encode::openai_responses::encode_request(&decoded, "gpt-5.4");
+        assert_eq!(
+            encoded["include"],
+            json!([
+                "message.output_text.logprobs",
+                "reasoning.encrypted_content"
+            ])
+        );
+    }
+
     #[test]
     fn official_responses_tool_choice_variants_normalize_and_round_trip() {
         let cases = [
@@ -1593,6 +1789,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -1602,6 +1799,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         };
         let encoded_request =
@@ -1631,6 +1833,26 @@ mod tests {
         assert_eq!(details.cache_creation_tokens, 64);
     }
 
+    #[test]
+    fn parse_usage_clamps_reasoning_and_cached_tokens_to_totals() {
+        let usage = parse_usage_from_responses(
+            json!({
+                "input_tokens": 20,
+                "output_tokens": 5,
+                "total_tokens": 999,
+                "input_tokens_details": { "cached_tokens": 32 },
+                "output_tokens_details": { "reasoning_tokens": 12 }
+            })
+            .as_object()
+            .expect("usage json object expected"),
+        );
+
+        assert_eq!(usage.cached_tokens(), Some(20));
+        assert_eq!(usage.reasoning_tokens(), Some(5));
+        assert!(!usage.extra_body.contains_key("total_tokens"));
+        assert_eq!(usage.total_tokens(), 25);
+    }
+
     #[test]
     fn parse_usage_keeps_cache_creation_when_cached_tokens_are_zero() {
         let usage = parse_usage_from_responses(
@@ -1983,7 +2205,39 @@ mod tests {
         let decoded = decode_request(&value).expect("decode_request should succeed");
         let reasoning = decoded.reasoning.expect("reasoning should decode");
         assert!(reasoning.effort.is_none());
-        assert_eq!(reasoning.extra_body.get("summary"), Some(&json!("auto")));
+        assert_eq!(reasoning.summary.as_deref(), Some("auto"));
+        assert!(!reasoning.extra_body.contains_key("summary"));
+    }
+
+    #[test]
+    fn reasoning_summary_reaches_responses_body_only() {
+        let value = json!({
+            "model": "gpt-5",
+            "input": "hello",
+            "reasoning": { "effort": "high", "summary": "detailed" }
+        });
+
+        let decoded = decode_request(&value).expect("decode_request should succeed");
+        assert_eq!(
+            decoded
+                .reasoning
+                .as_ref()
+                .and_then(|reasoning| reasoning.summary.as_deref()),
+            Some("detailed")
+        );
+
+        let responses = crate::urp::encode::openai_responses::encode_request(&decoded, "gpt-5");
+        assert_eq!(
+            responses["reasoning"],
+            json!({ "effort": "high", "summary": "detailed" })
+        );
+
+        let chat = crate::urp::encode::openai_chat::encode_request(&decoded, "gpt-5");
+        assert!(!chat.to_string().contains("detailed"));
+        let messages = crate::urp::encode::anthropic::encode_request(&decoded, "claude-sonnet-4-5");
+        assert!(!messages.to_string().contains("detailed"));
+        let gemini = crate::urp::encode::gemini::encode_request(&decoded, "gemini-2.5-pro");
+        assert!(!gemini.to_string().contains("detailed"));
     }
 
     #[test]
@@ -2218,4 +2472,107 @@ mod tests {
             Some(&json!({ "type": "object", "properties": {} }))
         );
     }
+
+    #[test]
+    fn bare_string_input_decodes_to_one_user_text_node() {
+        let decoded = decode_request(&json!({ "model": "gpt-5.4", "input": "hello" }))
+            .expect("decode Responses request");
+        assert_eq!(decoded.input.len(), 1);
+        assert!(matches!(
+            &decoded.input[0],
+            Node::Text { role: OrdinaryRole::User, content, .. } if content == "hello"
+        ));
+    }
+
+    #[test]
+    fn message_array_input_keeps_roles_and_mixed_content() {
+        let decoded = decode_request(&json!({
+            "model": "gpt-5.4",
+            "input": [
+                { "role": "developer", "content": "be brief" },
+                {
+                    "type": "message",
+                    "role": "user",
+                    "content": [
+                        { "type": "input_text", "text": "what is this?" },
+                        { "type": "input_image", "image_url": "https://example.com/a.png" }
+                    ]
+                }
+            ]
+        }))
+        .expect("decode Responses request");
+        assert_eq!(decoded.input.len(), 3);
+        assert!(matches!(
+            &decoded.input[0],
+            Node::Text { role: OrdinaryRole::Developer, content, .. } if content == "be brief"
+        ));
+        assert!(matches!(
+            &decoded.input[1],
+            Node::Text { role: OrdinaryRole::User, content, .. } if content == "what is this?"
+        ));
+        assert!(matches!(
+            &decoded.input[2],
+            Node::Image {
+                role: OrdinaryRole::User,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn bare_content_item_array_input_becomes_single_user_message() {
+        let decoded = decode_request(&json!({
+            "model": "gpt-5.4",
+            "input": [
+                { "type": "input_text", "text": "describe" },
+                { "type": "input_image", "image_url": "https://example.com/a.png", "detail": "low" },
+                { "type": "input_file", "file_id": "file_123" }
+            ]
+        }))
+        .expect("decode Responses request");
+        assert_eq!(decoded.input.len(), 3);
+        assert!(
+            decoded
+                .input
+                .iter()
+                .all(|node| !matches!(node, Node::ProviderItem { .. }))
+        );
+        assert!(matches!(
+            &decoded.input[0],
+            Node::Text { role: OrdinaryRole::User, content, .. } if content == "describe"
+        ));
+        assert!(matches!(
+            &decoded.input[1],
+            Node::Image {
+                role: OrdinaryRole::User,
+                ..
+            }
+        ));
+        assert!(matches!(
+            &decoded.input[2],
+            Node::File {
+                role: OrdinaryRole::User,
+                ..
+            }
+        ));
+
+        let chat = crate::urp::encode::openai_chat::encode_request(&decoded, "gpt-5.4");
+        let messages = chat["messages"].as_array().expect("chat messages");
+        assert_eq!(messages.len(), 1, "{chat}");
+        assert_eq!(messages[0]["role"], json!("user"));
+        let content = messages[0]["content"].as_array().expect("content parts");
+        assert_eq!(content.len(), 3, "{chat}");
+        assert_eq!(content[0]["type"], json!("text"));
+        assert_eq!(content[1]["type"], json!("image_url"));
+
+        let single = decode_request(&json!({
+            "model": "gpt-5.4",
+            "input": { "type": "input_text", "text": "solo" }
+        }))
+        .expect("decode Responses request");
+        assert!(matches!(
+            single.input.as_slice(),
+            [Node::Text { role: OrdinaryRole::User, content, .. }] if content == "solo"
+        ));
+    }
 }
@@ -73,6 +73,7 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
         stream,
         temperature,
         top_p,
+        top_k: None,
         max_output_tokens: max_tokens,
         reasoning: None,
         tools: None,
@@ -82,6 +83,11 @@ pub fn decode_request(value: &Value) -> Result<UrpRequest, String> {
         verbosity: None,
         response_format: None,
         user: None,
+        service_tier: None,
+        include: None,
+        logprobs: None,
+        top_logprobs: None,
+        previous_response_id: None,
         extra_body: split_extra(obj, &["model", "input", "stream", "version"]),
     })
 }
@@ -128,6 +134,13 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
     }
 
     let usage = parse_replicate_usage(obj);
+    let mut extra_body = split_extra(
+        obj,
+        &[
+            "id", "model", "status", "output", "error", "metrics", "input", "version",
+        ],
+    );
+    crate::urp::record_raw_finish_reason(&mut extra_body, finish_reason, Some(status));
 
     Ok(UrpResponse {
         id,
@@ -136,12 +149,7 @@ pub fn decode_response(value: &Value) -> Result<UrpResponse, String> {
         output: output_nodes,
         finish_reason,
         usage,
-        extra_body: split_extra(
-            obj,
-            &[
-                "id", "model", "status", "output", "error", "metrics", "input", "version",
-            ],
-        ),
+        extra_body,
     })
 }
 
@@ -1,6 +1,7 @@
+use crate::config::ProviderType;
 use crate::urp::encode::{
-    file_id_origin_matches, merge_extra, sanitize_provider_item_wire_body, tool_choice_to_value,
-    usage_input_details, usage_output_details,
+    file_id_origin_matches, merge_extra, messages_service_tier, normalize_tool_choice,
+    sanitize_provider_item_wire_body, usage_input_details, usage_output_details,
 };
 use crate::urp::{
     CHAT_REASONING_DETAIL_EXTRA_KEY, FILE_ID_ORIGIN_MESSAGES, FileSource, FinishReason,
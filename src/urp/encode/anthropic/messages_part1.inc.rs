@@ -103,6 +103,7 @@ fn encode_regular_message_block(node: &Node, sigil_mode: ReasoningSigilMode) ->
             ..
         } => encode_messages_provider_block(*origin_protocol, item_type, body, extra_body),
         Node::Audio { .. }
+        | Node::Video { .. }
         | Node::Refusal { .. }
         | Node::ToolResult { .. }
         | Node::NextDownstreamEnvelopeExtra { .. } => None,
@@ -225,7 +226,13 @@ fn encode_assistant_response_block(node: &Node) -> Option<Value> {
             extra_body,
             ..
         } => encode_messages_provider_block(*origin_protocol, item_type, body, extra_body),
+        // Messages has no refusal block; the stream encoder also emits refusal
+        // text as an ordinary text block.
+        Node::Refusal { content, .. } if !content.is_empty() => {
+            Some(json!({ "type": "text", "text": content }))
+        }
         Node::Audio { .. }
+        | Node::Video { .. }
         | Node::Refusal { .. }
         | Node::ToolResult { .. }
         | Node::NextDownstreamEnvelopeExtra { .. }
@@ -236,12 +243,20 @@ fn encode_assistant_response_block(node: &Node) -> Option<Value> {
     }
 }
 
+/// A result holding one plain text entry is sent as a string; every other
+/// result keeps the block array form so part boundaries, media, and
+/// per-block fields survive.
 fn encode_tool_result_block(
     call_id: &str,
     content: &[ToolResultContent],
     is_error: bool,
     extra_body: &HashMap<String, Value>,
 ) -> Value {
+    if let [ToolResultContent::Text { text, extra_body: text_extra }] = content
+        && text_extra.is_empty()
+    {
+        return tool_result_block(call_id, Value::String(text.clone()), is_error, extra_body);
+    }
     let mut content: Vec<Value> = content
         .iter()
         .filter_map(|item| match item {
@@ -271,14 +286,23 @@ fn encode_tool_result_block(
     if content.is_empty() {
         content.push(json!({ "type": "text", "text": "" }));
     }
-    let mut tool_result_block = json!({
+    tool_result_block(call_id, Value::Array(content), is_error, extra_body)
+}
+
+fn tool_result_block(
+    call_id: &str,
+    content: Value,
+    is_error: bool,
+    extra_body: &HashMap<String, Value>,
+) -> Value {
+    let mut block = json!({
         "type": "tool_result",
         "tool_use_id": call_id,
         "is_error": is_error,
         "content": content
     });
-    if let Some(obj) = tool_result_block.as_object_mut() {
+    if let Some(obj) = block.as_object_mut() {
         merge_extra(obj, extra_body);
     }
-    tool_result_block
+    block
 }
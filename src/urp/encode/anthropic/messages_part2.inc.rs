@@ -67,69 +67,18 @@ fn encode_tool_choice_for_anthropic(
     choice: &crate::urp::ToolChoice,
     parallel_tool_calls: Option<bool>,
 ) -> Value {
-    match tool_choice_to_value(choice) {
-        Value::String(mode) => match mode.as_str() {
-            "auto" => anthropic_tool_choice_object("auto", None, parallel_tool_calls),
-            "required" => anthropic_tool_choice_object("any", None, parallel_tool_calls),
-            "none" => json!({ "type": "none" }),
-            _ => Value::String(mode),
-        },
-        Value::Object(obj) => {
-            let explicit_disable = obj
-                .get("disable_parallel_tool_use")
-                .and_then(|v| v.as_bool());
-            if let Some(name) = obj
-                .get("function")
-                .and_then(|v| v.get("name"))
-                .and_then(|v| v.as_str())
-            {
-                let mut out = Map::new();
-                out.insert("type".to_string(), Value::String("tool".to_string()));
-                out.insert("name".to_string(), Value::String(name.to_string()));
-                insert_anthropic_disable_parallel(&mut out, explicit_disable, parallel_tool_calls);
-                Value::Object(out)
-            } else if let Some(mode) = obj.get("type").and_then(|v| v.as_str()) {
-                match mode {
-                    "auto" => {
-                        anthropic_tool_choice_object("auto", explicit_disable, parallel_tool_calls)
-                    }
-                    "required" | "any" => {
-                        anthropic_tool_choice_object("any", explicit_disable, parallel_tool_calls)
-                    }
-                    "none" => json!({ "type": "none" }),
-                    _ => Value::Object(obj),
-                }
-            } else {
-                Value::Object(obj)
-            }
-        }
-        other => other,
-    }
-}
-
-fn anthropic_tool_choice_object(
-    mode: &str,
-    explicit_disable: Option<bool>,
-    parallel_tool_calls: Option<bool>,
-) -> Value {
-    let mut obj = Map::new();
-    obj.insert("type".to_string(), Value::String(mode.to_string()));
-    insert_anthropic_disable_parallel(&mut obj, explicit_disable, parallel_tool_calls);
-    Value::Object(obj)
-}
-
-fn insert_anthropic_disable_parallel(
-    obj: &mut Map<String, Value>,
-    explicit_disable: Option<bool>,
-    parallel_tool_calls: Option<bool>,
-) {
-    let disable = explicit_disable.or_else(|| (parallel_tool_calls == Some(false)).then_some(true));
-    if let Some(disable) = disable {
-        obj.insert(
-            "disable_parallel_tool_use".to_string(),
-            Value::Bool(disable),
-        );
+    let mut value = normalize_tool_choice(choice, ProviderType::Messages);
+    if parallel_tool_calls == Some(false)
+        && let Value::Object(obj) = &mut value
+        && matches!(
+            obj.get("type").and_then(Value::as_str),
+            Some("auto" | "any" | "tool")
+        )
+    {
+        obj.entry("disable_parallel_tool_use")
+            .or_insert(Value::Bool(true));
     }
+    value
 }
 
 fn encode_anthropic_image(
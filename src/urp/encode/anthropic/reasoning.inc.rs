@@ -361,11 +361,13 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
             } => {
                 flush_pending_anthropic_message(&mut pending_message, &mut messages);
             }
-            Node::Audio { .. } | Node::Refusal { .. } => {}
+            Node::Audio { .. } | Node::Video { .. } | Node::Refusal { .. } => {}
         }
         node_index += consumed_nodes;
     }
     flush_pending_anthropic_message(&mut pending_message, &mut messages);
+    merge_consecutive_same_role_messages(&mut messages);
+    trim_trailing_assistant_prefill_whitespace(&mut messages);
 
     let mut body = json!({
         "model": upstream_model,
@@ -388,6 +390,9 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
     if let Some(top_p) = req.top_p {
         obj.insert("top_p".to_string(), Value::from(top_p));
     }
+    if let Some(top_k) = req.top_k {
+        obj.insert("top_k".to_string(), Value::from(top_k));
+    }
     if let Some(tools) = &req.tools {
         obj.insert("tools".to_string(), Value::Array(encode_tools(tools)));
     }
@@ -436,11 +441,19 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
             }
         } else if !has_explicit_messages_config {
             let effort = reasoning.effort.as_deref().unwrap_or("medium");
+            // A budget written by `reasoning_effort_to_budget` takes precedence
+            // over the built-in effort table.
+            let budget = req
+                .extra_body
+                .get("thinking")
+                .and_then(|thinking| thinking.get("budget_tokens"))
+                .and_then(Value::as_u64)
+                .unwrap_or_else(|| u64::from(effort_to_budget(effort)));
             obj.insert(
                 "thinking".to_string(),
                 json!({
                     "type": "enabled",
-                    "budget_tokens": effort_to_budget(effort)
+                    "budget_tokens": budget
                 }),
             );
         }
@@ -455,6 +468,20 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
         };
         obj.insert("stop_sequences".to_string(), Value::Array(stops));
     }
+    if let Some(tier) = &req.service_tier {
+        match messages_service_tier(tier) {
+            Some(wire_tier) => {
+                obj.insert(
+                    "service_tier".to_string(),
+                    Value::String(wire_tier.to_string()),
+                );
+            }
+            None => tracing::debug!(
+                service_tier = %tier,
+                "dropping service_tier unsupported by messages upstream"
+            ),
+        }
+    }
     merge_extra(obj, &req.extra_body);
     if let Some(user) = &req.user {
         let metadata = obj
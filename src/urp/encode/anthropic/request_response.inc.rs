@@ -41,6 +41,7 @@ pub fn encode_response(resp: &UrpResponse, logical_model: &str) -> Value {
         "model": logical_model,
         "content": content,
         "stop_reason": stop_reason,
+        "stop_sequence": resp.extra_body.get("stop_sequence").cloned().unwrap_or(Value::Null),
     });
 
     let usage = resp.usage.clone().unwrap_or(Usage {
@@ -87,6 +88,80 @@ fn flush_pending_anthropic_message(
     out.push(msg);
 }
 
+/// Messages requires user and assistant turns to alternate, but system nodes,
+/// envelope boundaries, or a tool result following user text can still split
+/// one turn into adjacent same-role messages. Those are merged here regardless
+/// of request transforms. `tool_result` blocks are moved to the front of a
+/// merged user turn, where Messages requires them.
+fn merge_consecutive_same_role_messages(messages: &mut Vec<Value>) {
+    let mut merged: Vec<Value> = Vec::with_capacity(messages.len());
+    for message in messages.drain(..) {
+        let same_role = merged.last().is_some_and(|previous| {
+            previous.get("role").is_some() && previous.get("role") == message.get("role")
+        });
+        let Some(previous) = merged.last_mut().filter(|_| same_role) else {
+            merged.push(message);
+            continue;
+        };
+        let Value::Object(message) = message else {
+            continue;
+        };
+        let previous = previous.as_object_mut().expect("anthropic message object");
+        for (key, value) in message {
+            if key == "content" {
+                if let (Some(Value::Array(content)), Value::Array(blocks)) =
+                    (previous.get_mut("content"), value)
+                {
+                    content.extend(blocks);
+                }
+            } else {
+                previous.entry(key).or_insert(value);
+            }
+        }
+        if let Some(Value::Array(content)) = previous.get_mut("content") {
+            content.sort_by_key(|block| block.get("type").and_then(Value::as_str) != Some("tool_result"));
+        }
+    }
+    *messages = merged;
+}
+
+/// Messages rejects a final assistant turn (a prefill) whose content ends with
+/// whitespace, which is common when a client resumes an interrupted stream by
+/// replaying the partial answer. Only the final message's last text block is
+/// trimmed; a block left empty is dropped, and so is a message left empty.
+fn trim_trailing_assistant_prefill_whitespace(messages: &mut Vec<Value>) {
+    let Some(last) = messages.last_mut() else {
+        return;
+    };
+    if last.get("role").and_then(Value::as_str) != Some("assistant") {
+        return;
+    }
+    let Some(content) = last.get_mut("content").and_then(Value::as_array_mut) else {
+        return;
+    };
+    let Some(block) = content.last_mut() else {
+        return;
+    };
+    if block.get("type").and_then(Value::as_str) != Some("text") {
+        return;
+    }
+    let Some(text) = block.get("text").and_then(Value::as_str) else {
+        return;
+    };
+    let trimmed = text.trim_end();
+    if trimmed.len() == text.len() {
+        return;
+    }
+    if trimmed.is_empty() {
+        content.pop();
+        if content.is_empty() {
+            messages.pop();
+        }
+    } else {
+        block["text"] = Value::String(trimmed.to_string());
+    }
+}
+
 fn append_node_to_pending_anthropic_message(
     pending: &mut Option<AnthropicMessageEnvelope>,
     out: &mut Vec<Value>,
@@ -165,6 +240,7 @@ fn anthropic_message_role_for_node(node: &Node) -> Option<OrdinaryRole> {
         Node::ToolResult { .. }
         | Node::NextDownstreamEnvelopeExtra { .. }
         | Node::Audio { .. }
+        | Node::Video { .. }
         | Node::Refusal { .. }
         | Node::ProviderItem { .. } => None,
     }
@@ -13,6 +13,7 @@ mod provider_item_tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -22,6 +23,11 @@ mod provider_item_tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         }
     }
@@ -151,4 +157,351 @@ mod provider_item_tests {
         assert!(block.get("vendor_message").is_none());
         assert_eq!(block["cache_control"], json!({ "type": "ephemeral" }));
     }
+
+    #[test]
+    fn content_filter_response_encodes_refusal_text_and_refusal_stop_reason() {
+        let response = UrpResponse {
+            id: "msg_1".to_string(),
+            model: "claude-sonnet-4-6".to_string(),
+            created_at: None,
+            output: vec![Node::Refusal {
+                id: None,
+                content: "I can't help with that.".to_string(),
+                extra_body: empty_map(),
+            }],
+            finish_reason: Some(FinishReason::ContentFilter),
+            usage: None,
+            extra_body: empty_map(),
+        };
+
+        let encoded = encode_response(&response, "claude-sonnet-4-6");
+        assert_eq!(encoded["stop_reason"], json!("refusal"));
+        assert_eq!(
+            encoded["content"],
+            json!([{ "type": "text", "text": "I can't help with that." }])
+        );
+    }
+
+    #[test]
+    fn messages_top_k_encodes_and_rejects_non_positive_values() {
+        let mut req = request_with_input(Vec::new());
+        req.top_k = Some(5);
+        let encoded = encode_request(&req, "claude-sonnet-4.5");
+        assert_eq!(encoded["top_k"], json!(5));
+
+        let decoded = crate::urp::decode::anthropic::decode_request(&json!({
+            "model": "claude-sonnet-4.5",
+            "max_tokens": 16,
+            "messages": [],
+            "top_k": 12
+        }))
+        .expect("decode messages");
+        assert_eq!(decoded.top_k, Some(12));
+        assert!(!decoded.extra_body.contains_key("top_k"));
+        for invalid in [json!(0), json!(-3), json!(2.5), json!("7")] {
+            let err = crate::urp::decode::anthropic::decode_request(&json!({
+                "model": "claude-sonnet-4.5",
+                "max_tokens": 16,
+                "messages": [],
+                "top_k": invalid
+            }))
+            .expect_err("invalid top_k");
+            assert!(err.contains("top_k"));
+        }
+    }
+
+    #[test]
+    fn messages_service_tier_maps_to_messages_vocabulary() {
+        let mut req = request_with_input(Vec::new());
+        for (tier, wire) in [
+            ("auto", "auto"),
+            ("priority", "auto"),
+            ("default", "standard_only"),
+            ("standard_only", "standard_only"),
+        ] {
+            req.service_tier = Some(tier.to_string());
+            let encoded = encode_request(&req, "claude-sonnet-4.5");
+            assert_eq!(encoded["service_tier"], json!(wire), "tier {tier}");
+        }
+
+        req.service_tier = Some("flex".to_string());
+        let encoded = encode_request(&req, "claude-sonnet-4.5");
+        assert!(encoded.get("service_tier").is_none());
+
+        let decoded = crate::urp::decode::anthropic::decode_request(&json!({
+            "model": "claude-sonnet-4.5",
+            "max_tokens": 16,
+            "messages": [],
+            "service_tier": "standard_only"
+        }))
+        .expect("decode messages");
+        assert_eq!(decoded.service_tier.as_deref(), Some("standard_only"));
+        assert!(!decoded.extra_body.contains_key("service_tier"));
+    }
+
+    #[test]
+    fn final_assistant_prefill_is_trimmed_of_trailing_whitespace() {
+        let text = |role: OrdinaryRole, content: &str| Node::Text {
+            id: None,
+            role,
+            content: content.to_string(),
+            phase: None,
+            extra_body: empty_map(),
+        };
+        let req = request_with_input(vec![
+            text(OrdinaryRole::User, "write a poem"),
+            text(OrdinaryRole::Assistant, "Roses are red \n"),
+            text(OrdinaryRole::User, "continue "),
+            text(OrdinaryRole::Assistant, "Violets are  \n\t"),
+        ]);
+        let encoded = encode_request(&req, "claude-sonnet-4.5");
+        let messages = encoded["messages"].as_array().expect("messages");
+        assert_eq!(messages[1]["content"][0]["text"], json!("Roses are red \n"));
+        assert_eq!(messages[2]["content"][0]["text"], json!("continue "));
+        assert_eq!(messages[3]["content"][0]["text"], json!("Violets are"));
+
+        let req = request_with_input(vec![
+            text(OrdinaryRole::User, "continue"),
+            text(OrdinaryRole::Assistant, " \n"),
+        ]);
+        let encoded = encode_request(&req, "claude-sonnet-4.5");
+        assert_eq!(
+            encoded["messages"],
+            json!([{ "role": "user", "content": [{ "type": "text", "text": "continue" }] }])
+        );
+    }
+
+    #[test]
+    fn consecutive_same_role_messages_merge_with_tool_results_first() {
+        let text = |role: OrdinaryRole, content: &str| Node::Text {
+            id: None,
+            role,
+            content: content.to_string(),
+            phase: None,
+            extra_body: empty_map(),
+        };
+        let req = request_with_input(vec![
+            text(OrdinaryRole::User, "first"),
+            text(OrdinaryRole::System, "be brief"),
+            text(OrdinaryRole::User, "second"),
+            Node::ToolCall {
+                id: None,
+                tool_type: crate::urp::ToolCallType::Function,
+                call_id: "toolu_1".to_string(),
+                name: "lookup".to_string(),
+                arguments: "{}".to_string(),
+                extra_body: empty_map(),
+            },
+            text(OrdinaryRole::User, "also consider this"),
+            Node::ToolResult {
+                id: None,
+                tool_type: crate::urp::ToolCallType::Function,
+                call_id: "toolu_1".to_string(),
+                is_error: false,
+                content: vec![ToolResultContent::Text {
+                    text: "found".to_string(),
+                    extra_body: empty_map(),
+                }],
+                extra_body: empty_map(),
+            },
+        ]);
+        let encoded = encode_request(&req, "claude-sonnet-4.5");
+        let messages = encoded["messages"].as_array().expect("messages");
+        let roles: Vec<&str> = messages
+            .iter()
+            .map(|message| message["role"].as_str().expect("role"))
+            .collect();
+        assert_eq!(roles, vec!["user", "assistant", "user"]);
+        assert_eq!(
+            messages[0]["content"],
+            json!([
+                { "type": "text", "text": "first" },
+                { "type": "text", "text": "second" }
+            ])
+        );
+        let last_turn: Vec<&str> = messages[2]["content"]
+            .as_array()
+            .expect("content")
+            .iter()
+            .map(|block| block["type"].as_str().expect("type"))
+            .collect();
+        assert_eq!(last_turn, vec!["tool_result", "text"]);
+    }
+
+    #[test]
+    fn error_tool_result_sets_is_error_on_the_tool_result_block() {
+        let req = request_with_input(vec![
+            Node::ToolCall {
+                id: None,
+                tool_type: crate::urp::ToolCallType::Function,
+                call_id: "toolu_1".to_string(),
+                name: "lookup".to_string(),
+                arguments: "{}".to_string(),
+                extra_body: empty_map(),
+            },
+            Node::ToolResult {
+                id: None,
+                tool_type: crate::urp::ToolCallType::Function,
+                call_id: "toolu_1".to_string(),
+                is_error: true,
+                content: vec![ToolResultContent::Text {
+                    text: "lookup timed out".to_string(),
+                    extra_body: empty_map(),
+                }],
+                extra_body: empty_map(),
+            },
+        ]);
+
+        let encoded = encode_request(&req, "claude-sonnet-4.5");
+        let block = &encoded["messages"][1]["content"][0];
+
+        assert_eq!(block["type"], json!("tool_result"));
+        assert_eq!(block["is_error"], json!(true));
+    }
+
+    fn tool_loop_request(content: Vec<ToolResultContent>) -> UrpRequest {
+        request_with_input(vec![
+            Node::ToolCall {
+                id: None,
+                tool_type: crate::urp::ToolCallType::Function,
+                call_id: "toolu_1".to_string(),
+                name: "screenshot".to_string(),
+                arguments: "{}".to_string(),
+                extra_body: empty_map(),
+            },
+            Node::ToolResult {
+                id: None,
+                tool_type: crate::urp::ToolCallType::Function,
+                call_id: "toolu_1".to_string(),
+                is_error: false,
+                content,
+                extra_body: empty_map(),
+            },
+        ])
+    }
+
+    #[test]
+    fn text_and_image_tool_result_encodes_content_as_block_array() {
+        let req = tool_loop_request(vec![
+            ToolResultContent::Text {
+                text: "captured".to_string(),
+                extra_body: empty_map(),
+            },
+            ToolResultContent::Image {
+                source: crate::urp::ImageSource::Base64 {
+                    media_type: "image/png".to_string(),
+                    data: "iVBORw0KGgo=".to_string(),
+                },
+                extra_body: empty_map(),
+            },
+        ]);
+
+        let encoded = encode_request(&req, "claude-sonnet-4.5");
+        let block = &encoded["messages"][1]["content"][0];
+
+        assert_eq!(block["type"], json!("tool_result"));
+        assert_eq!(
+            block["content"],
+            json!([
+                { "type": "text", "text": "captured" },
+                {
+                    "type": "image",
+                    "source": { "type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo=" }
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn single_text_tool_result_encodes_content_as_string() {
+        let req = tool_loop_request(vec![ToolResultContent::Text {
+            text: "line one".to_string(),
+            extra_body: empty_map(),
+        }]);
+
+        let encoded = encode_request(&req, "claude-sonnet-4.5");
+
+        assert_eq!(
+            encoded["messages"][1]["content"][0]["content"],
+            json!("line one")
+        );
+    }
+
+    #[test]
+    fn multi_text_tool_result_keeps_each_part_as_a_block() {
+        let req = tool_loop_request(vec![
+            ToolResultContent::Text {
+                text: "line one".to_string(),
+                extra_body: empty_map(),
+            },
+            ToolResultContent::Text {
+                text: "line two".to_string(),
+                extra_body: empty_map(),
+            },
+        ]);
+
+        let encoded = encode_request(&req, "claude-sonnet-4.5");
+
+        assert_eq!(
+            encoded["messages"][1]["content"][0]["content"],
+            json!([
+                { "type": "text", "text": "line one" },
+                { "type": "text", "text": "line two" }
+            ])
+        );
+    }
+
+    #[test]
+    fn signed_thinking_round_trips_into_the_next_tool_loop_turn() {
+        let upstream = json!({
+            "id": "msg_tool_loop",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-sonnet-4-6",
+            "content": [
+                { "type": "thinking", "thinking": "Need the weather.", "signature": "sig_turn_1" },
+                { "type": "redacted_thinking", "data": "redacted_turn_1" },
+                { "type": "tool_use", "id": "toolu_w", "name": "weather", "input": { "city": "Paris" } }
+            ],
+            "stop_reason": "tool_use",
+            "usage": { "input_tokens": 10, "output_tokens": 5 }
+        });
+        let decoded =
+            crate::urp::decode::anthropic::decode_response(&upstream).expect("response decodes");
+        assert!(matches!(
+            &decoded.output[0],
+            Node::Reasoning { encrypted: Some(Value::String(signature)), .. }
+                if signature == "sig_turn_1"
+        ));
+
+        let mut input = vec![Node::Text {
+            id: None,
+            role: OrdinaryRole::User,
+            content: "Weather in Paris?".to_string(),
+            phase: None,
+            extra_body: empty_map(),
+        }];
+        input.extend(decoded.output);
+        input.push(Node::ToolResult {
+            id: None,
+            tool_type: crate::urp::ToolCallType::Function,
+            call_id: "toolu_w".to_string(),
+            is_error: false,
+            content: vec![ToolResultContent::Text {
+                text: "sunny".to_string(),
+                extra_body: empty_map(),
+            }],
+            extra_body: empty_map(),
+        });
+
+        let encoded = encode_request(&request_with_input(input), "claude-sonnet-4-6");
+        assert_eq!(
+            encoded["messages"][1],
+            json!({
+                "role": "assistant",
+                "content": upstream["content"]
+            })
+        );
+        assert_eq!(encoded["messages"][2]["content"][0]["tool_use_id"], "toolu_w");
+    }
 }
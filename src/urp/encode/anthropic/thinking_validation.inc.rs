@@ -269,9 +269,11 @@ mod thinking_validation_tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: Some(max_tokens),
             reasoning: Some(ReasoningConfig {
                 effort: Some(effort.to_string()),
+                summary: None,
                 extra_body: HashMap::new(),
             }),
             tools: None,
@@ -281,6 +283,11 @@ mod thinking_validation_tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::new(),
         }
     }
@@ -36,6 +36,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(vec![ToolDefinition {
@@ -65,6 +66,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -118,6 +124,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(vec![ToolDefinition {
@@ -146,6 +153,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -196,6 +208,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(vec![parsed_tool]),
@@ -205,6 +218,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -285,6 +303,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(tools),
@@ -294,6 +313,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -343,6 +367,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -352,6 +377,11 @@ mod tests {
             verbosity: None,
             response_format: Some(ResponseFormat::JsonObject),
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -383,9 +413,11 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: Some(crate::urp::ReasoningConfig {
                 effort: Some("high".to_string()),
+                summary: None,
                 extra_body: HashMap::from([(
                     MESSAGES_OUTPUT_CONFIG_EXTRA_KEY.to_string(),
                     json!({
@@ -417,6 +449,11 @@ mod tests {
                 },
             }),
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -458,6 +495,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: Some(321),
             reasoning: None,
             tools: None,
@@ -467,6 +505,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -690,6 +733,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: Some(256),
             reasoning: None,
             tools: None,
@@ -699,6 +743,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -731,9 +780,11 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: Some(crate::urp::ReasoningConfig {
                 effort: Some(effort.to_string()),
+                summary: None,
                 extra_body: HashMap::new(),
             }),
             tools: None,
@@ -743,6 +794,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         }
     }
@@ -1,14 +1,77 @@
+use crate::config::ProviderType;
 use crate::urp::encode::{
-    merge_extra, sanitize_provider_item_wire_body, usage_input_details, usage_output_details,
+    merge_extra, normalize_tool_choice, sanitize_provider_item_wire_body, usage_input_details,
+    usage_output_details,
 };
 use crate::urp::{
     AudioSource, FileSource, FinishReason, FunctionDefinition, ImageSource, Node, OrdinaryRole,
-    ProviderProtocol, ToolChoice, ToolDefinition, ToolResultContent, UrpRequest, UrpResponse,
+    ProviderProtocol, ToolDefinition, ToolResultContent, UrpRequest, UrpResponse, VideoSource,
 };
 use serde_json::{Map, Value, json};
 use std::collections::HashMap;
 
+/// Decoded size above which a base64 file is uploaded through the Files API
+/// instead of sent as `inlineData`; Gemini rejects request bodies over 20 MB.
+pub const INLINE_FILE_MAX_BYTES: usize = 15 * 1024 * 1024;
+
+/// A base64 request file too large to inline, deduplicated by content hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LargeInlineFile<'a> {
+    pub content_hash: String,
+    pub media_type: &'a str,
+    pub data: &'a str,
+}
+
+pub fn inline_file_content_hash(data: &str) -> String {
+    format!("{:016x}", xxhash_rust::xxh3::xxh3_64(data.as_bytes()))
+}
+
+pub fn large_inline_files(req: &UrpRequest, max_inline_bytes: usize) -> Vec<LargeInlineFile<'_>> {
+    let mut out: Vec<LargeInlineFile<'_>> = Vec::new();
+    for node in &req.input {
+        let (media_type, data) = match node {
+            Node::File {
+                role: OrdinaryRole::User | OrdinaryRole::Assistant,
+                source:
+                    FileSource::Base64 {
+                        media_type, data, ..
+                    },
+                ..
+            }
+            | Node::Video {
+                role: OrdinaryRole::User | OrdinaryRole::Assistant,
+                source: VideoSource::Base64 { media_type, data },
+                ..
+            } => (media_type, data),
+            _ => continue,
+        };
+        if data.len() / 4 * 3 <= max_inline_bytes {
+            continue;
+        }
+        let content_hash = inline_file_content_hash(data);
+        if out.iter().any(|file| file.content_hash == content_hash) {
+            continue;
+        }
+        out.push(LargeInlineFile {
+            content_hash,
+            media_type,
+            data,
+        });
+    }
+    out
+}
+
 pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
+    encode_request_with_file_uris(req, upstream_model, &HashMap::new())
+}
+
+/// Encodes like [`encode_request`], but base64 files whose content hash is in
+/// `file_uris` are referenced as `fileData` with the uploaded file URI.
+pub fn encode_request_with_file_uris(
+    req: &UrpRequest,
+    upstream_model: &str,
+    file_uris: &HashMap<String, String>,
+) -> Value {
     let mut contents = Vec::new();
     let mut system_parts = Vec::new();
     let mut tool_names_by_call_id: Map<String, Value> = Map::new();
@@ -51,13 +114,22 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
                 role: OrdinaryRole::User | OrdinaryRole::Assistant,
                 ..
             }
+            | Node::Video {
+                role: OrdinaryRole::User | OrdinaryRole::Assistant,
+                ..
+            }
             | Node::ProviderItem {
                 role: OrdinaryRole::User | OrdinaryRole::Assistant,
                 ..
             }
             | Node::Reasoning { .. }
             | Node::ToolCall { .. } => {
-                append_node_to_pending_gemini_message(&mut pending_content, &mut contents, node);
+                append_node_to_pending_gemini_message(
+                    &mut pending_content,
+                    &mut contents,
+                    node,
+                    file_uris,
+                );
             }
             Node::ToolResult {
                 id: _,
@@ -84,17 +156,28 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
                     .and_then(|v| v.as_str())
                     .or_else(|| tool_names_by_call_id.get(call_id).and_then(|v| v.as_str()))
                     .unwrap_or(call_id);
+                let mut parts = vec![json!({
+                    "functionResponse": {
+                        "name": function_name,
+                        "response": if *is_error {
+                            json!({ "error": result })
+                        } else {
+                            json!({ "result": result })
+                        }
+                    }
+                })];
+                // `functionResponse.response` is JSON only; media returned by the
+                // tool travels as sibling parts of the same user turn.
+                parts.extend(content.iter().filter_map(|entry| match entry {
+                    ToolResultContent::Image { source, .. } => encode_image_part(source),
+                    ToolResultContent::File { source, .. } => {
+                        encode_request_file_part(source, file_uris)
+                    }
+                    ToolResultContent::Text { .. } | ToolResultContent::ProviderItem { .. } => None,
+                }));
                 contents.push(json!({
                     "role": "user",
-                    "parts": [{
-                        "functionResponse": {
-                            "name": function_name,
-                            "response": {
-                                "result": result,
-                                "is_error": is_error
-                            }
-                        }
-                    }]
+                    "parts": parts
                 }));
             }
             Node::NextDownstreamEnvelopeExtra { .. }
@@ -110,6 +193,10 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
                 role: OrdinaryRole::System | OrdinaryRole::Developer,
                 ..
             }
+            | Node::Video {
+                role: OrdinaryRole::System | OrdinaryRole::Developer,
+                ..
+            }
             | Node::ProviderItem {
                 role: OrdinaryRole::System | OrdinaryRole::Developer,
                 ..
@@ -140,6 +227,15 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
     if let Some(top_p) = req.top_p {
         generation_config.insert("topP".to_string(), Value::from(top_p));
     }
+    if let Some(top_k) = req.top_k {
+        generation_config.insert("topK".to_string(), Value::from(top_k));
+    }
+    if let Some(tier) = &req.service_tier {
+        tracing::debug!(
+            service_tier = %tier,
+            "dropping service_tier unsupported by gemini upstream"
+        );
+    }
     if let Some(max_tokens) = req.max_output_tokens {
         generation_config.insert("maxOutputTokens".to_string(), Value::from(max_tokens));
     }
@@ -169,7 +265,8 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
     }
 
     if let Some(tc) = &req.tool_choice {
-        if let Some(cfg) = encode_tool_choice(tc) {
+        let cfg = normalize_tool_choice(tc, ProviderType::Gemini);
+        if !cfg.is_null() {
             obj.insert(
                 "toolConfig".to_string(),
                 json!({ "functionCallingConfig": cfg }),
@@ -242,6 +339,11 @@ pub fn encode_response(resp: &UrpResponse, logical_model: &str) -> Value {
                 source,
                 ..
             } => parts.push(encode_audio_part(source)),
+            Node::Video {
+                role: OrdinaryRole::Assistant,
+                source,
+                ..
+            } => parts.push(encode_video_part(source, &HashMap::new())),
             Node::Refusal { content, .. } => parts.push(json!({ "text": content })),
             Node::ProviderItem {
                 role: OrdinaryRole::Assistant,
@@ -254,6 +356,7 @@ pub fn encode_response(resp: &UrpResponse, logical_model: &str) -> Value {
             | Node::Image { .. }
             | Node::File { .. }
             | Node::Audio { .. }
+            | Node::Video { .. }
             | Node::ProviderItem { .. }
             | Node::ToolResult { .. }
             | Node::NextDownstreamEnvelopeExtra { .. } => continue,
@@ -364,24 +467,6 @@ fn encode_function_declaration(function: &FunctionDefinition) -> Value {
     Value::Object(obj)
 }
 
-fn encode_tool_choice(tc: &ToolChoice) -> Option<Value> {
-    match tc {
-        ToolChoice::Mode(mode) => match mode.as_str() {
-            "none" => Some(json!({ "mode": "NONE" })),
-            "required" => Some(json!({ "mode": "ANY" })),
-            _ => Some(json!({ "mode": "AUTO" })),
-        },
-        ToolChoice::Specific(v) => {
-            let name = v
-                .get("function")
-                .and_then(|f| f.get("name"))
-                .and_then(|n| n.as_str())
-                .map(|s| s.to_string());
-            name.map(|n| json!({ "mode": "ANY", "allowedFunctionNames": [n] }))
-        }
-    }
-}
-
 fn encode_image_part(source: &ImageSource) -> Option<Value> {
     match source {
         ImageSource::Url { url, .. } => {
@@ -406,6 +491,21 @@ fn encode_file_part(source: &FileSource) -> Option<Value> {
     }
 }
 
+fn encode_request_file_part(
+    source: &FileSource,
+    file_uris: &HashMap<String, String>,
+) -> Option<Value> {
+    if let FileSource::Base64 {
+        media_type, data, ..
+    } = source
+        && !file_uris.is_empty()
+        && let Some(uri) = file_uris.get(&inline_file_content_hash(data))
+    {
+        return Some(json!({ "fileData": { "mimeType": media_type, "fileUri": uri } }));
+    }
+    encode_file_part(source)
+}
+
 fn encode_audio_part(source: &AudioSource) -> Value {
     match source {
         AudioSource::Url { url } => {
@@ -417,6 +517,20 @@ fn encode_audio_part(source: &AudioSource) -> Value {
     }
 }
 
+fn encode_video_part(source: &VideoSource, file_uris: &HashMap<String, String>) -> Value {
+    match source {
+        VideoSource::Url { url } => {
+            json!({ "fileData": { "mimeType": "video/*", "fileUri": url } })
+        }
+        VideoSource::Base64 { media_type, data } => {
+            if let Some(uri) = file_uris.get(&inline_file_content_hash(data)) {
+                return json!({ "fileData": { "mimeType": media_type, "fileUri": uri } });
+            }
+            json!({ "inlineData": { "mimeType": media_type, "data": data } })
+        }
+    }
+}
+
 fn effort_to_budget(effort: &str) -> u32 {
     match effort {
         "low" => 512,
@@ -465,8 +579,9 @@ fn append_node_to_pending_gemini_message(
     pending: &mut Option<GeminiMessageEnvelope>,
     out: &mut Vec<Value>,
     node: &Node,
+    file_uris: &HashMap<String, String>,
 ) {
-    let Some((role, part, extra_body)) = encode_request_node_part(node) else {
+    let Some((role, part, extra_body)) = encode_request_node_part(node, file_uris) else {
         return;
     };
     let should_flush = pending
@@ -483,7 +598,10 @@ fn append_node_to_pending_gemini_message(
     entry.parts.push(part);
 }
 
-fn encode_request_node_part(node: &Node) -> Option<(OrdinaryRole, Value, HashMap<String, Value>)> {
+fn encode_request_node_part(
+    node: &Node,
+    file_uris: &HashMap<String, String>,
+) -> Option<(OrdinaryRole, Value, HashMap<String, Value>)> {
     match node {
         Node::Text {
             role,
@@ -502,13 +620,27 @@ fn encode_request_node_part(node: &Node) -> Option<(OrdinaryRole, Value, HashMap
             source,
             extra_body,
             ..
-        } => Some((*role, encode_file_part(source)?, extra_body.clone())),
+        } => Some((
+            *role,
+            encode_request_file_part(source, file_uris)?,
+            extra_body.clone(),
+        )),
         Node::Audio {
             role,
             source,
             extra_body,
             ..
         } => Some((*role, encode_audio_part(source), extra_body.clone())),
+        Node::Video {
+            role,
+            source,
+            extra_body,
+            ..
+        } => Some((
+            *role,
+            encode_video_part(source, file_uris),
+            extra_body.clone(),
+        )),
         Node::Refusal {
             content,
             extra_body,
@@ -596,6 +728,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -605,6 +738,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         }
     }
@@ -722,6 +860,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -731,6 +870,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -747,6 +891,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn error_tool_result_is_sent_as_function_response_error() {
+        let req = request_with_input(vec![
+            Node::ToolCall {
+                id: None,
+                tool_type: crate::urp::ToolCallType::Function,
+                call_id: "call_1".to_string(),
+                name: "lookup".to_string(),
+                arguments: "{}".to_string(),
+                extra_body: empty_map(),
+            },
+            Node::ToolResult {
+                id: None,
+                tool_type: crate::urp::ToolCallType::Function,
+                call_id: "call_1".to_string(),
+                is_error: true,
+                content: vec![ToolResultContent::Text {
+                    text: "lookup timed out".to_string(),
+                    extra_body: empty_map(),
+                }],
+                extra_body: empty_map(),
+            },
+        ]);
+
+        let encoded = encode_request(&req, "gemini-2.5-pro");
+        let function_response = &encoded["contents"][1]["parts"][0]["functionResponse"];
+        assert_eq!(
+            function_response["response"],
+            json!({ "error": "lookup timed out" })
+        );
+
+        let decoded = decode_gemini::decode_request(&encoded).expect("decode request");
+        assert!(
+            decoded
+                .input
+                .iter()
+                .any(|node| matches!(node, Node::ToolResult { is_error: true, .. }))
+        );
+    }
+
+    #[test]
+    fn gemini_base64_video_part_round_trips_as_inline_data() {
+        let wire = json!({
+            "contents": [{
+                "role": "user",
+                "parts": [
+                    { "text": "describe this clip" },
+                    { "inlineData": { "mimeType": "video/mp4", "data": "AAAAIGZ0eXBpc29t" } }
+                ]
+            }]
+        });
+
+        let decoded = decode_gemini::decode_request(&wire).expect("decode request");
+        assert!(decoded.input.iter().any(|node| matches!(
+            node,
+            Node::Video {
+                role: OrdinaryRole::User,
+                source: VideoSource::Base64 { media_type, data },
+                ..
+            } if media_type == "video/mp4" && data == "AAAAIGZ0eXBpc29t"
+        )));
+
+        let encoded = encode_request(&decoded, "gemini-2.5-pro");
+        assert_eq!(encoded["contents"], wire["contents"]);
+
+        let by_url = request_with_input(vec![Node::Video {
+            id: None,
+            role: OrdinaryRole::User,
+            source: VideoSource::Url {
+                url: "https://generativelanguage.googleapis.com/v1beta/files/clip".to_string(),
+            },
+            extra_body: empty_map(),
+        }]);
+        assert_eq!(
+            encode_request(&by_url, "gemini-2.5-pro")["contents"][0]["parts"][0],
+            json!({ "fileData": {
+                "mimeType": "video/*",
+                "fileUri": "https://generativelanguage.googleapis.com/v1beta/files/clip"
+            } })
+        );
+    }
+
     #[test]
     fn gemini_provider_part_round_trips_only_for_gemini_protocol() {
         let native_part = json!({
@@ -815,4 +1041,100 @@ mod tests {
             Node::ProviderItem { body, .. } if body == &native_part
         ));
     }
+
+    #[test]
+    fn gemini_top_k_maps_to_generation_config() {
+        let mut req = request_with_input(Vec::new());
+        req.top_k = Some(40);
+        let encoded = encode_request(&req, "gemini-2.5-pro");
+        assert_eq!(encoded["generationConfig"]["topK"], json!(40));
+
+        let decoded = crate::urp::decode::gemini::decode_request(&json!({
+            "model": "gemini-2.5-pro",
+            "contents": [],
+            "generationConfig": { "topK": 8 }
+        }))
+        .expect("decode gemini");
+        assert_eq!(decoded.top_k, Some(8));
+        assert!(
+            crate::urp::decode::gemini::decode_request(&json!({
+                "model": "gemini-2.5-pro",
+                "contents": [],
+                "generationConfig": { "topK": 0 }
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn gemini_drops_service_tier() {
+        let mut req = request_with_input(Vec::new());
+        req.service_tier = Some("flex".to_string());
+        let encoded = encode_request(&req, "gemini-2.5-pro");
+        assert!(encoded.get("service_tier").is_none());
+        assert!(
+            encoded
+                .get("generationConfig")
+                .and_then(|config| config.get("serviceTier"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn gemini_forced_tool_choice_reaches_allowed_function_names() {
+        let from_chat = crate::urp::decode::openai_chat::decode_request(&json!({
+            "model": "gpt-5",
+            "messages": [{ "role": "user", "content": "hi" }],
+            "tool_choice": { "type": "function", "function": { "name": "lookup" } }
+        }))
+        .expect("decode chat");
+        let from_messages = crate::urp::decode::anthropic::decode_request(&json!({
+            "model": "claude-sonnet-4",
+            "max_tokens": 64,
+            "messages": [{ "role": "user", "content": "hi" }],
+            "tool_choice": { "type": "tool", "name": "lookup" }
+        }))
+        .expect("decode messages");
+        let mut raw_messages_shape = request_with_input(Vec::new());
+        raw_messages_shape.tool_choice = Some(crate::urp::ToolChoice::Specific(
+            json!({ "type": "tool", "name": "lookup" }),
+        ));
+
+        for req in [from_chat, from_messages, raw_messages_shape] {
+            let encoded = encode_request(&req, "gemini-2.5-pro");
+            assert_eq!(
+                encoded["toolConfig"]["functionCallingConfig"],
+                json!({ "mode": "ANY", "allowedFunctionNames": ["lookup"] })
+            );
+        }
+    }
+
+    #[test]
+    fn gemini_merges_every_system_and_developer_message_into_system_instruction() {
+        let req = crate::urp::decode::openai_chat::decode_request(&json!({
+            "model": "gpt-5",
+            "messages": [
+                { "role": "system", "content": "Be terse." },
+                { "role": "user", "content": "hi" },
+                { "role": "system", "content": "Answer in French." },
+                { "role": "developer", "content": "Never mention pricing." }
+            ]
+        }))
+        .expect("decode chat");
+
+        let encoded = encode_request(&req, "gemini-2.5-pro");
+
+        assert_eq!(
+            encoded["systemInstruction"],
+            json!({ "parts": [
+                { "text": "Be terse." },
+                { "text": "Answer in French." },
+                { "text": "Never mention pricing." }
+            ] })
+        );
+        assert_eq!(
+            encoded["contents"],
+            json!([{ "role": "user", "parts": [{ "text": "hi" }] }])
+        );
+    }
 }
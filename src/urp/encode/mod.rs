@@ -5,6 +5,7 @@ pub mod openai_image;
 pub mod openai_responses;
 pub mod replicate;
 
+use crate::config::ProviderType;
 use crate::urp::internal_legacy_bridge::{Part, Role};
 use crate::urp::{
     FILE_ID_ORIGIN_EXTRA_KEY, InputDetails, Node, OrdinaryRole, OutputDetails, ToolChoice, Usage,
@@ -224,6 +225,120 @@ pub fn tool_choice_to_responses_value(tc: &ToolChoice) -> Value {
     }
 }
 
+/// Translates a canonical tool choice into the target provider's wire dialect.
+///
+/// Gemini results are the `functionCallingConfig` object; `Value::Null` means the
+/// choice has no Gemini equivalent and MUST be omitted.
+pub fn normalize_tool_choice(tc: &ToolChoice, provider_type: ProviderType) -> Value {
+    match provider_type {
+        ProviderType::ChatCompletion => tool_choice_to_chat_value(tc),
+        ProviderType::Messages => tool_choice_to_messages_value(tc),
+        ProviderType::Gemini => tool_choice_to_gemini_value(tc),
+        ProviderType::Responses
+        | ProviderType::OpenaiImage
+        | ProviderType::Replicate
+        | ProviderType::Group => tool_choice_to_responses_value(tc),
+    }
+}
+
+/// Responses `include` entry that asks for output token logprobs; the Responses
+/// API has no boolean `logprobs` parameter.
+pub(crate) const RESPONSES_LOGPROBS_INCLUDE: &str = "message.output_text.logprobs";
+
+/// Maps a canonical `service_tier` onto the OpenAI vocabulary
+/// (`auto` / `default` / `flex` / `priority` / `scale`).
+pub(crate) fn openai_service_tier(tier: &str) -> &str {
+    match tier {
+        "standard_only" => "default",
+        other => other,
+    }
+}
+
+/// Maps a canonical `service_tier` onto the Messages vocabulary
+/// (`auto` / `standard_only`). `None` means the tier has no equivalent.
+pub(crate) fn messages_service_tier(tier: &str) -> Option<&'static str> {
+    match tier {
+        "auto" | "priority" => Some("auto"),
+        "default" | "standard_only" => Some("standard_only"),
+        _ => None,
+    }
+}
+
+fn messages_mode_value(mode: &str, explicit_disable: Option<bool>) -> Option<Value> {
+    let wire_mode = match mode {
+        "auto" => "auto",
+        "required" | "any" => "any",
+        "none" => return Some(json!({ "type": "none" })),
+        _ => return None,
+    };
+    let mut out = Map::new();
+    out.insert("type".to_string(), Value::String(wire_mode.to_string()));
+    if let Some(disable) = explicit_disable {
+        out.insert(
+            "disable_parallel_tool_use".to_string(),
+            Value::Bool(disable),
+        );
+    }
+    Some(Value::Object(out))
+}
+
+fn tool_choice_to_messages_value(tc: &ToolChoice) -> Value {
+    match tool_choice_to_value(tc) {
+        Value::String(mode) => messages_mode_value(&mode, None).unwrap_or(Value::String(mode)),
+        Value::Object(obj) => {
+            let explicit_disable = obj
+                .get("disable_parallel_tool_use")
+                .and_then(Value::as_bool);
+            if let Some(name) = obj
+                .get("function")
+                .and_then(|v| v.get("name"))
+                .and_then(Value::as_str)
+            {
+                let mut out = Map::new();
+                out.insert("type".to_string(), Value::String("tool".to_string()));
+                out.insert("name".to_string(), Value::String(name.to_string()));
+                if let Some(disable) = explicit_disable {
+                    out.insert(
+                        "disable_parallel_tool_use".to_string(),
+                        Value::Bool(disable),
+                    );
+                }
+                return Value::Object(out);
+            }
+            obj.get("type")
+                .and_then(Value::as_str)
+                .and_then(|mode| messages_mode_value(mode, explicit_disable))
+                .unwrap_or(Value::Object(obj))
+        }
+        other => other,
+    }
+}
+
+fn gemini_mode_value(mode: &str) -> Value {
+    match mode {
+        "none" => json!({ "mode": "NONE" }),
+        "required" | "any" => json!({ "mode": "ANY" }),
+        _ => json!({ "mode": "AUTO" }),
+    }
+}
+
+fn tool_choice_to_gemini_value(tc: &ToolChoice) -> Value {
+    match tc {
+        ToolChoice::Mode(mode) => gemini_mode_value(mode),
+        ToolChoice::Specific(Value::Object(obj)) => match obj.get("type").and_then(Value::as_str) {
+            Some(mode @ ("auto" | "none" | "required" | "any")) => gemini_mode_value(mode),
+            kind if matches!(kind, Some("function" | "tool")) || obj.contains_key("function") => {
+                selector_name(obj, "function")
+                    .and_then(|name| name.as_str().map(str::to_string))
+                    .map(|name| json!({ "mode": "ANY", "allowedFunctionNames": [name] }))
+                    .unwrap_or(Value::Null)
+            }
+            _ => Value::Null,
+        },
+        ToolChoice::Specific(_) => Value::Null,
+    }
+}
+
 pub fn text_parts(parts: &[Part]) -> String {
     let mut out = String::new();
     for p in parts {
@@ -422,6 +537,72 @@ mod tests {
         assert_eq!(body["vendor_unknown"]["_monoize_nested"], json!("drop"));
     }
 
+    #[test]
+    fn normalize_tool_choice_maps_modes_per_provider() {
+        let cases = [
+            (
+                "auto",
+                json!("auto"),
+                json!({ "type": "auto" }),
+                json!({ "mode": "AUTO" }),
+            ),
+            (
+                "none",
+                json!("none"),
+                json!({ "type": "none" }),
+                json!({ "mode": "NONE" }),
+            ),
+            (
+                "required",
+                json!("required"),
+                json!({ "type": "any" }),
+                json!({ "mode": "ANY" }),
+            ),
+        ];
+        for (mode, chat, messages, gemini) in cases {
+            let choice = ToolChoice::Mode(mode.to_string());
+            assert_eq!(
+                normalize_tool_choice(&choice, ProviderType::ChatCompletion),
+                chat,
+                "{mode}"
+            );
+            assert_eq!(
+                normalize_tool_choice(&choice, ProviderType::Messages),
+                messages,
+                "{mode}"
+            );
+            assert_eq!(
+                normalize_tool_choice(&choice, ProviderType::Gemini),
+                gemini,
+                "{mode}"
+            );
+        }
+
+        let specific = ToolChoice::Specific(json!({
+            "type": "function",
+            "function": { "name": "lookup" }
+        }));
+        assert_eq!(
+            normalize_tool_choice(&specific, ProviderType::ChatCompletion),
+            json!({ "type": "function", "function": { "name": "lookup" } })
+        );
+        assert_eq!(
+            normalize_tool_choice(&specific, ProviderType::Messages),
+            json!({ "type": "tool", "name": "lookup" })
+        );
+        assert_eq!(
+            normalize_tool_choice(&specific, ProviderType::Gemini),
+            json!({ "mode": "ANY", "allowedFunctionNames": ["lookup"] })
+        );
+        assert_eq!(
+            normalize_tool_choice(
+                &ToolChoice::Specific(json!({ "type": "image_generation" })),
+                ProviderType::Gemini
+            ),
+            Value::Null
+        );
+    }
+
     #[test]
     fn target_specific_tool_choice_shapes_preserve_semantics() {
         let function = ToolChoice::Specific(json!({
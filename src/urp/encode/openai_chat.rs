@@ -1,13 +1,16 @@
+use crate::config::ProviderType;
 use crate::urp::encode::{
-    file_id_origin_matches, role_to_str, sanitize_provider_item_wire_body, text_parts,
-    tool_choice_to_chat_value, usage_input_details, usage_output_details,
+    RESPONSES_LOGPROBS_INCLUDE, file_id_origin_matches, normalize_tool_choice, openai_service_tier,
+    role_to_str, sanitize_provider_item_wire_body, text_parts, usage_input_details,
+    usage_output_details,
 };
 use crate::urp::internal_legacy_bridge::{Item, Part, Role, nodes_to_items};
 use crate::urp::stream_helpers::{reasoning_encrypted_detail_value, reasoning_text_detail_value};
 use crate::urp::{
     AudioSource, CHAT_LEGACY_FUNCTION_CALL_EXTRA_KEY, CHAT_LEGACY_FUNCTION_CHOICE_EXTRA_KEY,
     CHAT_LEGACY_FUNCTION_DEFINITION_EXTRA_KEY, CHAT_LEGACY_FUNCTION_RESULT_EXTRA_KEY,
-    CHAT_MESSAGE_AUDIO_EXTRA_KEY, CHAT_REASONING_CONFIG_EXTRA_KEY, CHAT_REASONING_DETAIL_EXTRA_KEY,
+    CHAT_MESSAGE_AUDIO_EXTRA_KEY, CHAT_MESSAGE_AUDIO_TRANSCRIPT_EXTRA_KEY,
+    CHAT_REASONING_CONFIG_EXTRA_KEY, CHAT_REASONING_DETAIL_EXTRA_KEY,
     CHAT_REASONING_SURFACE_EXTRA_KEY, CHAT_REASONING_SURFACE_REASONING_CONTENT,
     CHAT_THINKING_CONFIG_EXTRA_KEY, FILE_ID_ORIGIN_OPENAI, FileSource, FinishReason, ImageSource,
     Node, OrdinaryRole, ProviderProtocol, ResponseFormat, StopControl, ToolCallType, ToolChoice,
@@ -98,28 +101,17 @@ fn encode_chat_content_part(part: &Part) -> Option<Value> {
         }
         Part::Image {
             source, extra_body, ..
-        } => {
-            let mut image = match source {
-                ImageSource::Url { url, detail } => {
-                    json!({ "type": "image_url", "image_url": { "url": url, "detail": detail } })
-                }
-                ImageSource::Base64 { media_type, data } => json!({
-                    "type": "image_url",
-                    "image_url": { "url": format!("data:{};base64,{}", media_type, data) }
-                }),
-                ImageSource::FileId { .. } => return None,
-            };
-            if let Some(obj) = image.as_object_mut() {
-                merge_chat_wire_extra(obj, extra_body);
-            }
-            Some(image)
-        }
+        } => encode_chat_image_part(source, extra_body),
         Part::File {
             source, extra_body, ..
         } => encode_chat_file_part(source, extra_body),
         Part::Audio {
             source, extra_body, ..
         } => encode_chat_audio_part(source, extra_body),
+        // Chat has no video part; videos travel as generic files.
+        Part::Video {
+            source, extra_body, ..
+        } => encode_chat_file_part(&source.to_file_source(), extra_body),
         Part::ProviderItem {
             origin_protocol,
             body,
@@ -130,6 +122,26 @@ fn encode_chat_content_part(part: &Part) -> Option<Value> {
     }
 }
 
+fn encode_chat_image_part(
+    source: &ImageSource,
+    extra_body: &HashMap<String, Value>,
+) -> Option<Value> {
+    let mut image = match source {
+        ImageSource::Url { url, detail } => {
+            json!({ "type": "image_url", "image_url": { "url": url, "detail": detail } })
+        }
+        ImageSource::Base64 { media_type, data } => json!({
+            "type": "image_url",
+            "image_url": { "url": format!("data:{};base64,{}", media_type, data) }
+        }),
+        ImageSource::FileId { .. } => return None,
+    };
+    if let Some(obj) = image.as_object_mut() {
+        merge_chat_wire_extra(obj, extra_body);
+    }
+    Some(image)
+}
+
 fn encode_chat_file_part(
     source: &FileSource,
     extra_body: &HashMap<String, Value>,
@@ -177,6 +189,44 @@ fn encode_chat_audio_part(
     Some(block)
 }
 
+/// Rebuilds a Chat `message.audio` object from a decoded response audio part.
+fn encode_chat_message_audio(
+    source: &AudioSource,
+    extra_body: &HashMap<String, Value>,
+) -> Option<Value> {
+    let AudioSource::Base64 { data, .. } = source else {
+        return None;
+    };
+    let mut audio = Map::new();
+    audio.insert("data".to_string(), Value::String(data.clone()));
+    merge_chat_wire_extra(&mut audio, extra_body);
+    Some(Value::Object(audio))
+}
+
+fn is_chat_message_audio(extra_body: &HashMap<String, Value>) -> bool {
+    extra_body
+        .get(CHAT_MESSAGE_AUDIO_EXTRA_KEY)
+        .and_then(Value::as_bool)
+        == Some(true)
+}
+
+fn is_chat_message_audio_transcript(extra_body: &HashMap<String, Value>) -> bool {
+    extra_body
+        .get(CHAT_MESSAGE_AUDIO_TRANSCRIPT_EXTRA_KEY)
+        .and_then(Value::as_bool)
+        == Some(true)
+}
+
+/// Folds a transcript back into the message's `audio` object; false when the
+/// message has no audio to attach it to.
+fn attach_chat_audio_transcript(message_extra: &mut HashMap<String, Value>, content: &str) -> bool {
+    let Some(Value::Object(audio)) = message_extra.get_mut("audio") else {
+        return false;
+    };
+    audio.insert("transcript".to_string(), Value::String(content.to_string()));
+    true
+}
+
 fn encode_chat_provider_part(
     origin_protocol: ProviderProtocol,
     body: &Value,
@@ -317,9 +367,20 @@ fn push_part_into_pending_chat_message(
                 .message_extra
                 .insert("audio".to_string(), sanitize_provider_item_wire_body(body));
         }
+        Part::Audio { source, extra_body } if is_chat_message_audio(extra_body) => {
+            if let Some(audio) = encode_chat_message_audio(source, extra_body) {
+                entry.message_extra.insert("audio".to_string(), audio);
+            }
+        }
+        Part::Text {
+            content,
+            extra_body,
+        } if is_chat_message_audio_transcript(extra_body)
+            && attach_chat_audio_transcript(&mut entry.message_extra, content) => {}
         Part::Text { .. }
         | Part::Image { .. }
         | Part::Audio { .. }
+        | Part::Video { .. }
         | Part::File { .. }
         | Part::ProviderItem { .. } => {
             if let Some(content) = encode_chat_content_part(part) {
@@ -377,6 +438,12 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
     if let Some(top_p) = req.top_p {
         obj.insert("top_p".to_string(), Value::from(top_p));
     }
+    if let Some(top_k) = req.top_k {
+        tracing::debug!(
+            top_k,
+            "dropping top_k unsupported by chat completions upstream"
+        );
+    }
     if let Some(max) = req.max_output_tokens {
         let key = if is_deepseek_model(upstream_model) {
             "max_tokens"
@@ -449,7 +516,10 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
                 obj.insert("function_call".to_string(), choice);
             }
         } else {
-            obj.insert("tool_choice".to_string(), tool_choice_to_chat_value(tc));
+            obj.insert(
+                "tool_choice".to_string(),
+                normalize_tool_choice(tc, ProviderType::ChatCompletion),
+            );
         }
     }
     if let Some(parallel) = req.parallel_tool_calls {
@@ -479,6 +549,28 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
     if let Some(user) = &req.user {
         obj.insert("user".to_string(), Value::String(user.clone()));
     }
+    if let Some(tier) = &req.service_tier {
+        obj.insert(
+            "service_tier".to_string(),
+            Value::String(openai_service_tier(tier).to_string()),
+        );
+    }
+    // Chat requires `logprobs: true` alongside `top_logprobs`; a Responses
+    // caller opts in through `include` instead.
+    let logprobs = req.logprobs.or_else(|| {
+        let requested_via_include = req
+            .include
+            .iter()
+            .flatten()
+            .any(|entry| entry == RESPONSES_LOGPROBS_INCLUDE);
+        (requested_via_include || req.top_logprobs.is_some()).then_some(true)
+    });
+    if let Some(logprobs) = logprobs {
+        obj.insert("logprobs".to_string(), Value::Bool(logprobs));
+    }
+    if let Some(top_logprobs) = req.top_logprobs {
+        obj.insert("top_logprobs".to_string(), json!(top_logprobs));
+    }
 
     merge_chat_wire_extra(obj, &req.extra_body);
 
@@ -628,6 +720,23 @@ fn encode_assistant_chat_message_from_nodes(nodes: &[Node]) -> Map<String, Value
                         .collect(),
                 );
             }
+            Node::Audio {
+                role: OrdinaryRole::Assistant,
+                source,
+                extra_body,
+                ..
+            } if is_chat_message_audio(extra_body) => {
+                if let Some(audio) = encode_chat_message_audio(source, extra_body) {
+                    message_extra.insert("audio".to_string(), audio);
+                }
+            }
+            Node::Text {
+                role: OrdinaryRole::Assistant,
+                content,
+                extra_body,
+                ..
+            } if is_chat_message_audio_transcript(extra_body)
+                && attach_chat_audio_transcript(&mut message_extra, content) => {}
             Node::Text {
                 role: OrdinaryRole::Assistant,
                 content,
@@ -789,6 +898,7 @@ fn assistant_message_extra_from_node(node: &Node) -> HashMap<String, Value> {
         }
         Node::Image { .. }
         | Node::Audio { .. }
+        | Node::Video { .. }
         | Node::File { .. }
         | Node::Refusal { .. }
         | Node::ToolCall { .. }
@@ -816,16 +926,49 @@ fn merge_chat_wire_extra(
     }
 }
 
+/// Chat tool messages carry text only, so images and files returned by a run of
+/// tool results follow that run as one user message.
+fn flush_tool_result_media(pending: &mut Vec<Value>, out: &mut Vec<Value>) {
+    if pending.is_empty() {
+        return;
+    }
+    out.push(json!({
+        "role": "user",
+        "content": std::mem::take(pending),
+    }));
+}
+
 fn encode_messages(messages: &[Item]) -> Vec<Value> {
     let mut out = Vec::new();
+    let mut pending_tool_media = Vec::new();
     for item in messages {
+        if !matches!(
+            item,
+            Item::ToolResult { .. }
+                | Item::Message {
+                    role: Role::Tool,
+                    ..
+                }
+        ) {
+            flush_tool_result_media(&mut pending_tool_media, &mut out);
+        }
         match item {
             Item::ToolResult {
                 call_id,
                 content,
+                is_error,
                 extra_body,
                 ..
             } => {
+                pending_tool_media.extend(content.iter().filter_map(|content| match content {
+                    ToolResultContent::Image { source, extra_body } => {
+                        encode_chat_image_part(source, extra_body)
+                    }
+                    ToolResultContent::File { source, extra_body } => {
+                        encode_chat_file_part(source, extra_body)
+                    }
+                    ToolResultContent::Text { .. } | ToolResultContent::ProviderItem { .. } => None,
+                }));
                 let text = content
                     .iter()
                     .filter_map(|content| match content {
@@ -834,6 +977,11 @@ fn encode_messages(messages: &[Item]) -> Vec<Value> {
                     })
                     .collect::<Vec<_>>()
                     .join("");
+                let text = if *is_error {
+                    crate::urp::mark_tool_error_text(&text)
+                } else {
+                    text
+                };
                 let mut m = Map::new();
                 if let Some(name) = extra_body
                     .get(CHAT_LEGACY_FUNCTION_RESULT_EXTRA_KEY)
@@ -879,6 +1027,7 @@ fn encode_messages(messages: &[Item]) -> Vec<Value> {
             }
         }
     }
+    flush_tool_result_media(&mut pending_tool_media, &mut out);
     out
 }
 
@@ -1001,7 +1150,11 @@ fn encode_response_format(format: &ResponseFormat) -> Value {
         ResponseFormat::JsonSchema { json_schema } => {
             let mut schema_obj = Map::new();
             schema_obj.insert("name".to_string(), Value::String(json_schema.name.clone()));
-            schema_obj.insert("schema".to_string(), json_schema.schema.clone());
+            // `schema` is optional on the wire; a decoded request without one
+            // carries `Null` and must not gain an explicit `"schema": null`.
+            if !json_schema.schema.is_null() {
+                schema_obj.insert("schema".to_string(), json_schema.schema.clone());
+            }
             if let Some(desc) = &json_schema.description {
                 schema_obj.insert("description".to_string(), Value::String(desc.clone()));
             }
@@ -1222,6 +1375,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -1231,6 +1385,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         }
     }
@@ -1244,6 +1403,163 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn openai_encoders_drop_top_k() {
+        let mut request = base_request(vec![Item::text(Role::User, "hi")]);
+        request.top_k = Some(20);
+        let chat = encode_request(&request, "gpt-5");
+        assert!(chat.get("top_k").is_none());
+        let responses = encode_responses::encode_request(&request, "gpt-5");
+        assert!(responses.get("top_k").is_none());
+    }
+
+    #[test]
+    fn logprobs_request_fields_are_typed_and_chat_response_logprobs_round_trip() {
+        let decoded = crate::urp::decode::openai_chat::decode_request(&json!({
+            "model": "gpt-4o",
+            "messages": [{ "role": "user", "content": "hi" }],
+            "logprobs": true,
+            "top_logprobs": 2
+        }))
+        .expect("decode chat");
+        assert_eq!(decoded.logprobs, Some(true));
+        assert_eq!(decoded.top_logprobs, Some(2));
+        assert!(!decoded.extra_body.contains_key("logprobs"));
+        let chat = encode_request(&decoded, "gpt-4o");
+        assert_eq!(chat["logprobs"], json!(true));
+        assert_eq!(chat["top_logprobs"], json!(2));
+        let responses = encode_responses::encode_request(&decoded, "gpt-4o");
+        assert_eq!(responses["top_logprobs"], json!(2));
+        assert!(
+            responses["include"]
+                .as_array()
+                .expect("include")
+                .contains(&json!("message.output_text.logprobs"))
+        );
+
+        let logprobs = json!({
+            "content": [
+                {
+                    "token": "Hi",
+                    "logprob": -0.01,
+                    "bytes": [72, 105],
+                    "top_logprobs": [
+                        { "token": "Hi", "logprob": -0.01, "bytes": [72, 105] },
+                        { "token": "Hello", "logprob": -4.7, "bytes": [72, 101, 108, 108, 111] }
+                    ]
+                }
+            ],
+            "refusal": null
+        });
+        let response = crate::urp::decode::openai_chat::decode_response(&json!({
+            "id": "chatcmpl_logprobs",
+            "object": "chat.completion",
+            "created": 1,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "Hi" },
+                "logprobs": logprobs,
+                "finish_reason": "stop"
+            }]
+        }))
+        .expect("decode chat response");
+        let encoded = encode_response(&response, "gpt-4o");
+        assert_eq!(encoded["choices"][0]["logprobs"], logprobs);
+    }
+
+    #[test]
+    fn openai_encoders_emit_service_tier_and_decoders_type_it() {
+        let mut request = base_request(vec![Item::text(Role::User, "hi")]);
+        request.service_tier = Some("flex".to_string());
+        let chat = encode_request(&request, "gpt-5");
+        assert_eq!(chat["service_tier"], json!("flex"));
+        let responses = encode_responses::encode_request(&request, "gpt-5");
+        assert_eq!(responses["service_tier"], json!("flex"));
+
+        request.service_tier = Some("standard_only".to_string());
+        let chat = encode_request(&request, "gpt-5");
+        assert_eq!(chat["service_tier"], json!("default"));
+
+        let decoded = crate::urp::decode::openai_chat::decode_request(&json!({
+            "model": "gpt-5",
+            "messages": [{ "role": "user", "content": "hi" }],
+            "service_tier": "priority"
+        }))
+        .expect("decode chat");
+        assert_eq!(decoded.service_tier.as_deref(), Some("priority"));
+        assert!(!decoded.extra_body.contains_key("service_tier"));
+
+        let decoded = crate::urp::decode::openai_responses::decode_request(&json!({
+            "model": "gpt-5",
+            "input": "hi",
+            "service_tier": "flex"
+        }))
+        .expect("decode responses");
+        assert_eq!(decoded.service_tier.as_deref(), Some("flex"));
+        assert!(!decoded.extra_body.contains_key("service_tier"));
+    }
+
+    #[test]
+    fn chat_json_schema_response_format_round_trips_exactly() {
+        let response_format = json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "weather_report",
+                "description": "Structured weather report for one city",
+                "schema": {
+                    "type": "object",
+                    "properties": { "city": { "type": "string" } },
+                    "required": ["city"],
+                    "additionalProperties": false
+                },
+                "strict": true,
+                "x_vendor_hint": { "level": 2 }
+            }
+        });
+        let decoded = decode_chat::decode_request(&json!({
+            "model": "gpt-5",
+            "messages": [{ "role": "user", "content": "hi" }],
+            "response_format": response_format.clone()
+        }))
+        .expect("decode chat");
+        let Some(ResponseFormat::JsonSchema { json_schema }) = &decoded.response_format else {
+            panic!("expected json_schema response format");
+        };
+        assert_eq!(json_schema.name, "weather_report");
+        assert_eq!(
+            json_schema.description.as_deref(),
+            Some("Structured weather report for one city")
+        );
+        assert_eq!(json_schema.strict, Some(true));
+        assert_eq!(json_schema.schema["required"], json!(["city"]));
+        assert_eq!(
+            json_schema.extra_body.get("x_vendor_hint"),
+            Some(&json!({ "level": 2 }))
+        );
+        assert!(!decoded.extra_body.contains_key("response_format"));
+
+        let encoded = encode_request(&decoded, "gpt-5");
+        assert_eq!(encoded["response_format"], response_format);
+    }
+
+    #[test]
+    fn chat_response_format_without_schema_or_as_text_object_round_trips() {
+        for response_format in [
+            json!({ "type": "json_schema", "json_schema": { "name": "free_form" } }),
+            json!({ "type": "text" }),
+        ] {
+            let decoded = decode_chat::decode_request(&json!({
+                "model": "gpt-5",
+                "messages": [{ "role": "user", "content": "hi" }],
+                "response_format": response_format.clone()
+            }))
+            .expect("decode chat");
+            let encoded = encode_request(&decoded, "gpt-5");
+            assert_eq!(encoded["response_format"], response_format);
+        }
+    }
+
     #[test]
     fn chat_tool_definition_conflicts_prefer_semantic_fields() {
         let mut request = base_request(vec![Item::text(Role::User, "use tools")]);
@@ -1644,6 +1960,7 @@ mod tests {
         req.max_output_tokens = Some(2048);
         req.reasoning = Some(crate::urp::ReasoningConfig {
             effort: Some("low".to_string()),
+            summary: None,
             extra_body: empty_map(),
         });
 
@@ -2023,6 +2340,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -2032,6 +2350,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -2118,6 +2441,36 @@ mod tests {
         assert_eq!(msg.get("provider_field"), Some(&json!(true)));
     }
 
+    #[test]
+    fn error_tool_result_text_is_marked_as_an_error() {
+        let tool_result = |text: &str| Item::ToolResult {
+            id: None,
+            tool_type: ToolCallType::Function,
+            call_id: "call_1".to_string(),
+            is_error: true,
+            content: vec![ToolResultContent::Text {
+                text: text.to_string(),
+                extra_body: HashMap::new(),
+            }],
+            extra_body: HashMap::new(),
+        };
+        let req = base_request(vec![
+            tool_result("file not found"),
+            tool_result("Error: already marked"),
+        ]);
+
+        let encoded = encode_request(&req, "gpt-5.4");
+
+        assert_eq!(
+            encoded["messages"][0]["content"],
+            json!("Error: file not found")
+        );
+        assert_eq!(
+            encoded["messages"][1]["content"],
+            json!("Error: already marked")
+        );
+    }
+
     #[test]
     fn chat_usage_round_trips_all_typed_usage_fields_without_extra_leakage() {
         let mut usage_extra = HashMap::new();
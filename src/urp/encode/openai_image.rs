@@ -177,6 +177,7 @@ mod tests {
             stream: Some(true),
             temperature: Some(0.3),
             top_p: Some(0.9),
+            top_k: None,
             max_output_tokens: Some(100),
             reasoning: None,
             tools: None,
@@ -186,6 +187,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::from([
                 ("size".to_string(), json!("1024x1024")),
                 ("n".to_string(), json!(2)),
@@ -217,6 +223,7 @@ mod tests {
             stream: Some(false),
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -226,6 +233,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::from([("size".to_string(), json!("1280x720"))]),
         };
 
@@ -249,6 +261,7 @@ mod tests {
             stream: Some(false),
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -258,6 +271,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: HashMap::from([("stream".to_string(), json!(true))]),
         };
 
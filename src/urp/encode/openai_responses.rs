@@ -1,6 +1,8 @@
+use crate::config::ProviderType;
 use crate::urp::encode::{
-    file_id_origin_matches, merge_extra, role_to_str, sanitize_provider_item_wire_body, text_parts,
-    tool_choice_to_responses_value, usage_input_details, usage_output_details,
+    RESPONSES_LOGPROBS_INCLUDE, file_id_origin_matches, merge_extra, normalize_tool_choice,
+    openai_service_tier, role_to_str, sanitize_provider_item_wire_body, text_parts,
+    usage_input_details, usage_output_details,
 };
 use crate::urp::internal_legacy_bridge::{Item, Part, Role, nodes_to_items};
 use crate::urp::{
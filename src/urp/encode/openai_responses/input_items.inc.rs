@@ -113,8 +113,16 @@ fn merge_responses_text_config(obj: &mut Map<String, Value>, raw_text: Option<&V
 
 fn finish_reason_to_status(finish_reason: Option<FinishReason>) -> &'static str {
     match finish_reason {
-        Some(FinishReason::Length) => "incomplete",
+        Some(FinishReason::Length | FinishReason::ContentFilter) => "incomplete",
         Some(FinishReason::Other) => "failed",
         _ => "completed",
     }
 }
+
+fn finish_reason_to_incomplete_details(finish_reason: Option<FinishReason>) -> Value {
+    match finish_reason {
+        Some(FinishReason::Length) => json!({ "reason": "max_output_tokens" }),
+        Some(FinishReason::ContentFilter) => json!({ "reason": "content_filter" }),
+        _ => Value::Null,
+    }
+}
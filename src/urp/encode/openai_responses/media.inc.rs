@@ -36,6 +36,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -45,6 +46,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -63,6 +69,74 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn error_function_call_output_is_marked_as_an_error() {
+        let tool_result = |call_id: &str, content: Vec<ToolResultContent>| Node::ToolResult {
+            id: None,
+            tool_type: ToolCallType::Function,
+            call_id: call_id.to_string(),
+            is_error: true,
+            content,
+            extra_body: empty_map(),
+        };
+        let req = UrpRequest {
+            model: "gpt-5.4".to_string(),
+            input: vec![
+                tool_result(
+                    "call_text",
+                    vec![ToolResultContent::Text {
+                        text: "file not found".to_string(),
+                        extra_body: empty_map(),
+                    }],
+                ),
+                tool_result(
+                    "call_image",
+                    vec![ToolResultContent::Image {
+                        source: ImageSource::Url {
+                            url: "https://example.com/trace.png".to_string(),
+                            detail: None,
+                        },
+                        extra_body: empty_map(),
+                    }],
+                ),
+            ],
+            stream: None,
+            temperature: None,
+            top_p: None,
+            top_k: None,
+            max_output_tokens: None,
+            reasoning: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            stop: None,
+            verbosity: None,
+            response_format: None,
+            user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
+            extra_body: empty_map(),
+        };
+
+        let encoded = encode_request(&req, "gpt-5.4");
+        let outputs: Vec<&Value> = encoded["input"]
+            .as_array()
+            .expect("input array")
+            .iter()
+            .filter(|item| item["type"] == json!("function_call_output"))
+            .collect();
+
+        assert_eq!(outputs[0]["output"], json!("Error: file not found"));
+        assert_eq!(
+            outputs[1]["output"][0],
+            json!({ "type": "input_text", "text": "Error" })
+        );
+        assert_eq!(outputs[1]["output"][1]["type"], json!("input_image"));
+    }
+
     #[test]
     fn responses_function_tool_preserves_extras() {
         let req = UrpRequest {
@@ -79,6 +153,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(vec![ToolDefinition {
@@ -105,6 +180,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -138,6 +218,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: Some(vec![ToolDefinition {
@@ -180,6 +261,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -414,6 +500,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encode_response_keeps_output_text_annotations() {
+        let citation = json!({
+            "type": "url_citation",
+            "url": "https://example.com",
+            "title": "1",
+            "start_index": 0,
+            "end_index": 4
+        });
+        let resp = UrpResponse {
+            id: "resp_1".to_string(),
+            model: "grok-4".to_string(),
+            created_at: None,
+            output: items_to_nodes(vec![Item::Message {
+                id: None,
+                role: Role::Assistant,
+                parts: vec![Part::Text {
+                    content: "cited".to_string(),
+                    extra_body: {
+                        let mut m = empty_map();
+                        m.insert("annotations".to_string(), json!([citation.clone()]));
+                        m
+                    },
+                }],
+                extra_body: empty_map(),
+            }]),
+            finish_reason: Some(FinishReason::Stop),
+            usage: None,
+            extra_body: empty_map(),
+        };
+
+        let encoded = encode_response(&resp, "grok-4");
+        let part = &encoded["output"][0]["content"][0];
+
+        assert_eq!(part["annotations"], json!([citation]));
+        assert_eq!(part["logprobs"], json!([]));
+    }
+
     #[test]
     fn responses_round_trip_keeps_phase_order_and_unknown_fields() {
         let source = json!({
@@ -565,6 +689,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -574,6 +699,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -621,6 +751,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -630,6 +761,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -683,6 +819,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -692,6 +829,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -739,6 +881,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -748,6 +891,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -775,6 +923,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -784,6 +933,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -811,9 +965,11 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: Some(crate::urp::ReasoningConfig {
                 effort: Some("high".to_string()),
+                summary: None,
                 extra_body: HashMap::from([
                     ("summary".to_string(), json!("concise")),
                     (
@@ -833,6 +989,11 @@ mod tests {
             verbosity: None,
             response_format: None,
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -1162,6 +1323,7 @@ mod tests {
             stream: None,
             temperature: None,
             top_p: None,
+            top_k: None,
             max_output_tokens: None,
             reasoning: None,
             tools: None,
@@ -1171,6 +1333,11 @@ mod tests {
             verbosity: None,
             response_format: Some(ResponseFormat::JsonObject),
             user: None,
+            service_tier: None,
+            include: None,
+            logprobs: None,
+            top_logprobs: None,
+            previous_response_id: None,
             extra_body: empty_map(),
         };
 
@@ -142,13 +142,14 @@ fn encode_message_content_part(part: &Part, output_text_type: bool) -> Option<Va
                 ),
             );
             obj.insert("text".to_string(), Value::String(content.clone()));
+            // Merge first so upstream annotations/logprobs win over the empty defaults.
+            merge_extra(&mut obj, extra_body);
             if output_text_type {
                 obj.entry("annotations".to_string())
                     .or_insert_with(|| Value::Array(Vec::new()));
                 obj.entry("logprobs".to_string())
                     .or_insert_with(|| Value::Array(Vec::new()));
             }
-            merge_extra(&mut obj, extra_body);
             Some(Value::Object(obj))
         }
         Part::Image { source, extra_body } => {
@@ -173,6 +174,14 @@ fn encode_message_content_part(part: &Part, output_text_type: bool) -> Option<Va
             }
             Some(value)
         }
+        // Responses has no video part; videos travel as generic files.
+        Part::Video { source, extra_body } => encode_message_content_part(
+            &Part::File {
+                source: source.to_file_source(),
+                extra_body: extra_body.clone(),
+            },
+            output_text_type,
+        ),
         Part::Refusal {
             content,
             extra_body,
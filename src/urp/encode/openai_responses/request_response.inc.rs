@@ -3,7 +3,7 @@ fn encode_tool_result_item(
     tool_type: ToolCallType,
     call_id: &str,
     content: &[ToolResultContent],
-    _is_error: bool,
+    is_error: bool,
     extra_body: &HashMap<String, Value>,
     out: &mut Vec<Value>,
 ) {
@@ -48,6 +48,25 @@ fn encode_tool_result_item(
         }
     }
 
+    if is_error {
+        match tool_content
+            .iter_mut()
+            .find(|block| block.get("type").and_then(Value::as_str) == Some("input_text"))
+        {
+            Some(block) => {
+                let text = block.get("text").and_then(Value::as_str).unwrap_or("");
+                block["text"] = Value::String(crate::urp::mark_tool_error_text(text));
+            }
+            None => tool_content.insert(
+                0,
+                json!({
+                    "type": "input_text",
+                    "text": crate::urp::mark_tool_error_text(""),
+                }),
+            ),
+        }
+    }
+
     let mut obj = Map::new();
     obj.insert(
         "type".to_string(),
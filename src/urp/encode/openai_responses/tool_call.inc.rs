@@ -3,6 +3,7 @@ fn is_retained_responses_instruction_node(node: &Node) -> bool {
         Node::Text { extra_body, .. }
         | Node::Image { extra_body, .. }
         | Node::Audio { extra_body, .. }
+        | Node::Video { extra_body, .. }
         | Node::File { extra_body, .. }
         | Node::Refusal { extra_body, .. }
         | Node::Reasoning { extra_body, .. }
@@ -35,18 +36,28 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
     let mut input_items = Vec::new();
     let mut instructions = retained_instructions;
 
+    // Without retained instructions, the leading run of plain system/developer
+    // messages is merged into `instructions`; later ones stay in `input` so
+    // their position in the conversation is preserved.
+    let mut leading_instructions = Vec::new();
+    let mut in_leading_run = instructions.is_none();
     for item in &request_items {
-        if instructions.is_none() && can_use_responses_instructions(item) {
-            if let Item::Message { parts, .. } = item {
-                let text = text_parts(parts);
-                if !text.is_empty() {
-                    instructions = Some(Value::String(text));
-                    continue;
-                }
+        if in_leading_run
+            && can_use_responses_instructions(item)
+            && let Item::Message { parts, .. } = item
+        {
+            let text = text_parts(parts);
+            if !text.is_empty() {
+                leading_instructions.push(text);
+                continue;
             }
         }
+        in_leading_run = false;
         encode_message_to_input_items(item, &mut input_items);
     }
+    if !leading_instructions.is_empty() {
+        instructions = Some(Value::String(leading_instructions.join("\n\n")));
+    }
     sanitize_request_input_items(&mut input_items);
 
     let mut body = json!({
@@ -67,6 +78,9 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
     if let Some(top_p) = req.top_p {
         obj.insert("top_p".to_string(), Value::from(top_p));
     }
+    if let Some(top_k) = req.top_k {
+        tracing::debug!(top_k, "dropping top_k unsupported by responses upstream");
+    }
     if let Some(max) = req.max_output_tokens {
         obj.insert("max_output_tokens".to_string(), Value::from(max));
     }
@@ -80,6 +94,9 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
                 reasoning_obj.insert("effort".to_string(), Value::String(effort.clone()));
             }
         }
+        if let Some(summary) = &reasoning.summary {
+            reasoning_obj.insert("summary".to_string(), Value::String(summary.clone()));
+        }
         merge_extra(&mut reasoning_obj, &reasoning.extra_body);
         if !reasoning_obj.is_empty() {
             obj.insert("reasoning".to_string(), Value::Object(reasoning_obj));
@@ -91,7 +108,7 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
     if let Some(choice) = &req.tool_choice {
         obj.insert(
             "tool_choice".to_string(),
-            tool_choice_to_responses_value(choice),
+            normalize_tool_choice(choice, ProviderType::Responses),
         );
     }
     if let Some(parallel) = req.parallel_tool_calls {
@@ -100,6 +117,34 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
     if let Some(user) = &req.user {
         obj.insert("user".to_string(), Value::String(user.clone()));
     }
+    if let Some(tier) = &req.service_tier {
+        obj.insert(
+            "service_tier".to_string(),
+            Value::String(openai_service_tier(tier).to_string()),
+        );
+    }
+    let mut include = req.include.clone();
+    if req.logprobs == Some(true) {
+        let include = include.get_or_insert_with(Vec::new);
+        if !include
+            .iter()
+            .any(|entry| entry == RESPONSES_LOGPROBS_INCLUDE)
+        {
+            include.push(RESPONSES_LOGPROBS_INCLUDE.to_string());
+        }
+    }
+    if let Some(include) = include {
+        obj.insert("include".to_string(), json!(include));
+    }
+    if let Some(top_logprobs) = req.top_logprobs {
+        obj.insert("top_logprobs".to_string(), json!(top_logprobs));
+    }
+    if let Some(previous_response_id) = &req.previous_response_id {
+        obj.insert(
+            "previous_response_id".to_string(),
+            Value::String(previous_response_id.clone()),
+        );
+    }
     if let Some(format) = &req.response_format {
         apply_response_format(obj, format);
     }
@@ -232,7 +277,7 @@ pub fn encode_response(resp: &UrpResponse, logical_model: &str) -> Value {
             "model": logical_model,
             "status": status,
             "output": output.clone(),
-            "incomplete_details": null,
+            "incomplete_details": finish_reason_to_incomplete_details(resp.finish_reason),
             "previous_response_id": null,
             "instructions": null,
             "error": null,
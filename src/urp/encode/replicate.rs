@@ -60,6 +60,15 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
                                 };
                                 input.insert("audio".to_string(), Value::String(url));
                             }
+                            Part::Video { source, .. } => {
+                                let url = match source {
+                                    crate::urp::VideoSource::Url { url } => url.clone(),
+                                    crate::urp::VideoSource::Base64 { media_type, data } => {
+                                        format!("data:{media_type};base64,{data}")
+                                    }
+                                };
+                                input.insert("video".to_string(), Value::String(url));
+                            }
                             _ => {}
                         }
                     }
@@ -117,6 +126,15 @@ pub fn encode_request(req: &UrpRequest, upstream_model: &str) -> Value {
     if let Some(top_p) = req.top_p {
         input.insert("top_p".to_string(), Value::from(top_p));
     }
+    if let Some(top_k) = req.top_k {
+        input.insert("top_k".to_string(), Value::from(top_k));
+    }
+    if let Some(tier) = &req.service_tier {
+        tracing::debug!(
+            service_tier = %tier,
+            "dropping service_tier unsupported by replicate upstream"
+        );
+    }
 
     // extra_body fields that are objects named "input" merge into the input map;
     // all other extra_body fields also go into input (Replicate model params are
@@ -98,6 +98,7 @@ impl NodeGreedyMerger {
             super::Node::Text { .. }
             | super::Node::Image { .. }
             | super::Node::Audio { .. }
+            | super::Node::Video { .. }
             | super::Node::File { .. }
             | super::Node::Refusal { .. } => PartKind::Content,
             super::Node::ToolCall { .. }
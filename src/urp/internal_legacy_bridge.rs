@@ -1,6 +1,6 @@
 use super::{
     AudioSource, FileSource, ImageSource, Node, OrdinaryRole, ProviderProtocol,
-    RESPONSES_IMAGE_GENERATION_CALL_EXTRA_KEY, ToolResultContent,
+    RESPONSES_IMAGE_GENERATION_CALL_EXTRA_KEY, ToolResultContent, VideoSource,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -57,6 +57,11 @@ pub enum Part {
         #[serde(flatten)]
         extra_body: HashMap<String, Value>,
     },
+    Video {
+        source: VideoSource,
+        #[serde(flatten)]
+        extra_body: HashMap<String, Value>,
+    },
     File {
         source: FileSource,
         #[serde(flatten)]
@@ -194,6 +199,10 @@ pub enum PartHeader {
         #[serde(flatten)]
         extra_body: HashMap<String, Value>,
     },
+    Video {
+        #[serde(flatten)]
+        extra_body: HashMap<String, Value>,
+    },
     File {
         #[serde(flatten)]
         extra_body: HashMap<String, Value>,
@@ -235,6 +244,9 @@ pub enum PartDelta {
     Audio {
         source: AudioSource,
     },
+    Video {
+        source: VideoSource,
+    },
     File {
         source: FileSource,
     },
@@ -271,6 +283,12 @@ impl Part {
                 source,
                 extra_body,
             },
+            Part::Video { source, extra_body } => Node::Video {
+                id: None,
+                role,
+                source,
+                extra_body,
+            },
             Part::File { source, extra_body } => Node::File {
                 id: None,
                 role,
@@ -517,6 +535,7 @@ fn bridge_zone_for_node(node: &Node) -> BridgeZone {
         Node::Text { .. }
         | Node::Image { .. }
         | Node::Audio { .. }
+        | Node::Video { .. }
         | Node::File { .. }
         | Node::Refusal { .. } => BridgeZone::Content,
         Node::ToolCall { .. }
@@ -563,6 +582,12 @@ fn node_to_part(node: &Node) -> Part {
             source: source.clone(),
             extra_body: extra_body.clone(),
         },
+        Node::Video {
+            source, extra_body, ..
+        } => Part::Video {
+            source: source.clone(),
+            extra_body: extra_body.clone(),
+        },
         Node::File {
             source, extra_body, ..
         } => Part::File {
@@ -633,6 +658,7 @@ fn message_group_id(node: &Node) -> Option<String> {
         Node::Text { id, .. }
         | Node::Image { id, .. }
         | Node::Audio { id, .. }
+        | Node::Video { id, .. }
         | Node::File { id, .. }
         | Node::Refusal { id, .. } => id.clone(),
         _ => None,
@@ -10,6 +10,8 @@ pub(crate) mod internal_legacy_bridge;
 pub mod stream_decode;
 pub mod stream_encode;
 pub mod stream_helpers;
+pub mod tool_call_ids;
+pub mod validate;
 
 pub fn synthetic_message_id() -> String {
     format!("msg_urp_{}", uuid::Uuid::new_v4().simple())
@@ -51,6 +53,8 @@ pub const CHAT_REASONING_SURFACE_REASONING_CONTENT: &str = "reasoning_content";
 pub const CHAT_REASONING_CONFIG_EXTRA_KEY: &str = "_monoize_chat_reasoning_config";
 pub const CHAT_THINKING_CONFIG_EXTRA_KEY: &str = "_monoize_chat_thinking_config";
 pub const CHAT_MESSAGE_AUDIO_EXTRA_KEY: &str = "_monoize_chat_message_audio";
+/// Marks the text part decoded from a Chat response `message.audio.transcript`.
+pub const CHAT_MESSAGE_AUDIO_TRANSCRIPT_EXTRA_KEY: &str = "_monoize_chat_message_audio_transcript";
 pub const CHAT_LEGACY_FUNCTION_DEFINITION_EXTRA_KEY: &str =
     "_monoize_chat_legacy_function_definition";
 pub const CHAT_LEGACY_FUNCTION_CHOICE_EXTRA_KEY: &str = "_monoize_chat_legacy_function_choice";
@@ -405,6 +409,8 @@ pub struct UrpRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_output_tokens: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reasoning: Option<ReasoningConfig>,
@@ -422,6 +428,18 @@ pub struct UrpRequest {
     pub response_format: Option<ResponseFormat>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_logprobs: Option<u32>,
+    /// Responses conversation chaining reference. Only a Responses upstream
+    /// can resolve it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_response_id: Option<String>,
     #[serde(flatten)]
     pub extra_body: HashMap<String, Value>,
 }
@@ -455,6 +473,14 @@ pub enum Node {
         #[serde(flatten)]
         extra_body: HashMap<String, Value>,
     },
+    Video {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        role: OrdinaryRole,
+        source: VideoSource,
+        #[serde(flatten)]
+        extra_body: HashMap<String, Value>,
+    },
     File {
         #[serde(skip_serializing_if = "Option::is_none")]
         id: Option<String>,
@@ -566,6 +592,28 @@ pub enum AudioSource {
     Base64 { media_type: String, data: String },
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VideoSource {
+    Url { url: String },
+    Base64 { media_type: String, data: String },
+}
+
+impl VideoSource {
+    /// Generic file view of the video, used by encoders whose protocol has no
+    /// video part.
+    pub fn to_file_source(&self) -> FileSource {
+        match self {
+            VideoSource::Url { url } => FileSource::Url { url: url.clone() },
+            VideoSource::Base64 { media_type, data } => FileSource::Base64 {
+                filename: None,
+                media_type: media_type.clone(),
+                data: data.clone(),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum FileSource {
@@ -631,6 +679,10 @@ impl ToolResultContent {
 pub struct ReasoningConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub effort: Option<String>,
+    /// Responses reasoning summary mode (`auto`, `concise`, or `detailed`).
+    /// Only the Responses encoder emits it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
     #[serde(flatten)]
     pub extra_body: HashMap<String, Value>,
 }
@@ -729,6 +781,46 @@ pub enum FinishReason {
     Other,
 }
 
+/// Response `extra_body` key carrying the upstream finish reason string that
+/// a decoder could only map to [`FinishReason::Other`].
+pub const RAW_FINISH_REASON_EXTRA_KEY: &str = "raw_finish_reason";
+
+/// Records `raw` under [`RAW_FINISH_REASON_EXTRA_KEY`] when it decoded to
+/// [`FinishReason::Other`], so the upstream value survives the catch-all.
+pub fn record_raw_finish_reason(
+    extra_body: &mut HashMap<String, Value>,
+    finish_reason: Option<FinishReason>,
+    raw: Option<&str>,
+) {
+    if finish_reason != Some(FinishReason::Other) {
+        return;
+    }
+    if let Some(raw) = raw.filter(|raw| !raw.is_empty()) {
+        extra_body
+            .entry(RAW_FINISH_REASON_EXTRA_KEY.to_string())
+            .or_insert_with(|| Value::String(raw.to_string()));
+    }
+}
+
+/// Replaces an `Other` finish reason with the operator mapping for its raw
+/// upstream value. The raw value stays in `extra_body`.
+pub fn normalize_finish_reason_other(
+    finish_reason: &mut Option<FinishReason>,
+    extra_body: &HashMap<String, Value>,
+    mapping: &HashMap<String, FinishReason>,
+) {
+    if *finish_reason != Some(FinishReason::Other) {
+        return;
+    }
+    if let Some(mapped) = extra_body
+        .get(RAW_FINISH_REASON_EXTRA_KEY)
+        .and_then(Value::as_str)
+        .and_then(|raw| mapping.get(raw))
+    {
+        *finish_reason = Some(*mapped);
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ModalityBreakdown {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -777,7 +869,7 @@ pub struct OutputDetails {
     pub modality_breakdown: Option<ModalityBreakdown>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Usage {
     pub input_tokens: u64,
     pub output_tokens: u64,
@@ -807,6 +899,36 @@ impl Usage {
             .map(|d| d.reasoning_tokens)
             .filter(|&v| v > 0)
     }
+
+    /// Clamps subset counters that upstreams occasionally over-report:
+    /// reasoning tokens to the output total and cache reads to the input
+    /// total. Only valid for wire formats whose totals include those subsets
+    /// (OpenAI Chat and Responses); Gemini reports thoughts outside
+    /// `candidatesTokenCount`.
+    pub fn clamp_subset_counts(&mut self, source: &'static str) {
+        if let Some(details) = self.output_details.as_mut()
+            && details.reasoning_tokens > self.output_tokens
+        {
+            tracing::warn!(
+                source,
+                reasoning_tokens = details.reasoning_tokens,
+                output_tokens = self.output_tokens,
+                "upstream usage reports more reasoning tokens than output tokens; clamping"
+            );
+            details.reasoning_tokens = self.output_tokens;
+        }
+        if let Some(details) = self.input_details.as_mut()
+            && details.cache_read_tokens > self.input_tokens
+        {
+            tracing::warn!(
+                source,
+                cache_read_tokens = details.cache_read_tokens,
+                input_tokens = self.input_tokens,
+                "upstream usage reports more cached tokens than input tokens; clamping"
+            );
+            details.cache_read_tokens = self.input_tokens;
+        }
+    }
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -886,6 +1008,11 @@ pub enum NodeHeader {
         id: Option<String>,
         role: OrdinaryRole,
     },
+    Video {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        role: OrdinaryRole,
+    },
     File {
         #[serde(skip_serializing_if = "Option::is_none")]
         id: Option<String>,
@@ -952,6 +1079,9 @@ pub enum NodeDelta {
     Audio {
         source: AudioSource,
     },
+    Video {
+        source: VideoSource,
+    },
     File {
         source: FileSource,
     },
@@ -975,11 +1105,32 @@ impl Node {
         Self::text(OrdinaryRole::Assistant, content)
     }
 
+    /// `File` view of a `Video` node for protocols without a video part;
+    /// `None` for every other node.
+    pub fn video_as_file(&self) -> Option<Node> {
+        let Node::Video {
+            id,
+            role,
+            source,
+            extra_body,
+        } = self
+        else {
+            return None;
+        };
+        Some(Node::File {
+            id: id.clone(),
+            role: *role,
+            source: source.to_file_source(),
+            extra_body: extra_body.clone(),
+        })
+    }
+
     pub fn role(&self) -> Option<OrdinaryRole> {
         match self {
             Node::Text { role, .. }
             | Node::Image { role, .. }
             | Node::Audio { role, .. }
+            | Node::Video { role, .. }
             | Node::File { role, .. }
             | Node::ProviderItem { role, .. } => Some(*role),
             Node::Refusal { .. } | Node::Reasoning { .. } | Node::ToolCall { .. } => {
@@ -994,6 +1145,7 @@ impl Node {
             Node::Text { extra_body, .. }
             | Node::Image { extra_body, .. }
             | Node::Audio { extra_body, .. }
+            | Node::Video { extra_body, .. }
             | Node::File { extra_body, .. }
             | Node::Refusal { extra_body, .. }
             | Node::Reasoning { extra_body, .. }
@@ -1008,6 +1160,7 @@ impl Node {
             Node::Text { id, .. }
             | Node::Image { id, .. }
             | Node::Audio { id, .. }
+            | Node::Video { id, .. }
             | Node::File { id, .. }
             | Node::Refusal { id, .. }
             | Node::Reasoning { id, .. }
@@ -1023,6 +1176,7 @@ impl Node {
             Node::Text { id, .. }
             | Node::Image { id, .. }
             | Node::Audio { id, .. }
+            | Node::Video { id, .. }
             | Node::File { id, .. }
             | Node::Refusal { id, .. }
             | Node::Reasoning { id, .. }
@@ -1034,6 +1188,22 @@ impl Node {
     }
 }
 
+/// OpenAI tool outputs carry no error flag, so a failed tool result is marked
+/// in its text instead.
+pub(crate) const TOOL_ERROR_TEXT_PREFIX: &str = "Error: ";
+
+/// Returns `text` marked as a failed tool output, leaving text that already
+/// starts with `Error` unchanged.
+pub(crate) fn mark_tool_error_text(text: &str) -> String {
+    if text.is_empty() {
+        "Error".to_string()
+    } else if text.trim_start().starts_with("Error") {
+        text.to_string()
+    } else {
+        format!("{TOOL_ERROR_TEXT_PREFIX}{text}")
+    }
+}
+
 pub fn node_is_empty_text(node: &Node) -> bool {
     matches!(node, Node::Text { content, .. } if content.is_empty())
 }
@@ -1074,6 +1244,7 @@ pub fn nodes_semantically_match(left: &Node, right: &Node) -> bool {
             },
         ) => (left_id.is_some() && left_id == right_id) || left_source == right_source,
         (Node::Audio { id: left_id, .. }, Node::Audio { id: right_id, .. })
+        | (Node::Video { id: left_id, .. }, Node::Video { id: right_id, .. })
         | (Node::File { id: left_id, .. }, Node::File { id: right_id, .. })
         | (Node::Refusal { id: left_id, .. }, Node::Refusal { id: right_id, .. }) => {
             left_id.is_some() && left_id == right_id
@@ -1165,6 +1336,7 @@ pub fn strip_nested_extra_body(nodes: &mut Vec<Node>) {
             Node::Text { extra_body, .. }
             | Node::Image { extra_body, .. }
             | Node::Audio { extra_body, .. }
+            | Node::Video { extra_body, .. }
             | Node::File { extra_body, .. }
             | Node::Refusal { extra_body, .. }
             | Node::Reasoning { extra_body, .. }
@@ -1230,6 +1402,27 @@ pub fn remove_downstream_only_reasoning_for_responses(nodes: &mut Vec<Node>) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn finish_reason_other_maps_through_raw_value_table() {
+        let mut extra_body = HashMap::new();
+        record_raw_finish_reason(
+            &mut extra_body,
+            Some(FinishReason::Other),
+            Some("guardrail"),
+        );
+        record_raw_finish_reason(&mut extra_body, Some(FinishReason::Stop), Some("stop"));
+        assert_eq!(extra_body[RAW_FINISH_REASON_EXTRA_KEY], "guardrail");
+
+        let mapping = HashMap::from([("guardrail".to_string(), FinishReason::ContentFilter)]);
+        let mut finish_reason = Some(FinishReason::Other);
+        normalize_finish_reason_other(&mut finish_reason, &extra_body, &mapping);
+        assert_eq!(finish_reason, Some(FinishReason::ContentFilter));
+
+        let mut unmapped = Some(FinishReason::Other);
+        normalize_finish_reason_other(&mut unmapped, &extra_body, &HashMap::new());
+        assert_eq!(unmapped, Some(FinishReason::Other));
+    }
+
     #[test]
     fn node_role_returns_explicit_role_for_ordinary_nodes() {
         let text = Node::text(OrdinaryRole::User, "hello");
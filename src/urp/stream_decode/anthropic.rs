@@ -525,7 +525,8 @@ pub(crate) async fn stream_messages_to_urp_events(
             let output_nodes = ordered_completed_nodes(&state);
             crate::handlers::usage::increment_estimated_output_tokens(
                 &runtime_metrics,
-                estimated_output_chars(&output_nodes),
+                &urp.model,
+                &output_nodes,
             )
             .await;
             record_stream_terminal_event(
@@ -933,6 +934,10 @@ fn node_header_from_node(node: &Node) -> NodeHeader {
             id: node.id().cloned(),
             role: *role,
         },
+        Node::Video { role, .. } => NodeHeader::Video {
+            id: node.id().cloned(),
+            role: *role,
+        },
         Node::File { role, .. } => NodeHeader::File {
             id: node.id().cloned(),
             role: *role,
@@ -984,6 +989,11 @@ fn take_response_done(
             "stop_reason".to_string(),
             Value::String(stop_reason.clone()),
         );
+        crate::urp::record_raw_finish_reason(
+            &mut extra_body,
+            state.finish_reason,
+            Some(stop_reason),
+        );
     }
     if let Some(stop_sequence) = state.exact_stop_sequence.as_ref() {
         extra_body.insert("stop_sequence".to_string(), stop_sequence.clone());
@@ -996,22 +1006,6 @@ fn take_response_done(
     })
 }
 
-fn estimated_output_chars(nodes: &[Node]) -> u64 {
-    nodes
-        .iter()
-        .map(|node| match node {
-            Node::Text { content, .. } | Node::Refusal { content, .. } => content.len() as u64,
-            Node::Reasoning {
-                content, summary, ..
-            } => {
-                content.as_ref().map_or(0, |content| content.len() as u64)
-                    + summary.as_ref().map_or(0, |summary| summary.len() as u64)
-            }
-            _ => 0,
-        })
-        .sum()
-}
-
 fn json_value_to_string(value: Option<&Value>) -> String {
     match value {
         None | Some(Value::Null) => String::new(),
@@ -1130,7 +1124,7 @@ fn merge_message_delta_state(state: &mut AnthropicMessagesStreamState, event: &V
 fn map_finish_reason(reason: &str) -> Option<FinishReason> {
     match reason {
         "end_turn" => Some(FinishReason::Stop),
-        "max_tokens" => Some(FinishReason::Length),
+        "max_tokens" | "model_context_window_exceeded" => Some(FinishReason::Length),
         "tool_use" => Some(FinishReason::ToolCalls),
         "refusal" => Some(FinishReason::ContentFilter),
         "stop_sequence" => Some(FinishReason::Stop),
@@ -1576,6 +1570,40 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn message_delta_stop_reasons_map_to_finish_reasons() {
+        for (stop_reason, expected) in [
+            ("end_turn", FinishReason::Stop),
+            ("stop_sequence", FinishReason::Stop),
+            ("max_tokens", FinishReason::Length),
+            ("model_context_window_exceeded", FinishReason::Length),
+            ("tool_use", FinishReason::ToolCalls),
+            ("refusal", FinishReason::ContentFilter),
+            ("pause_turn", FinishReason::Other),
+        ] {
+            let mut state = AnthropicMessagesStreamState::default();
+            merge_message_delta_state(
+                &mut state,
+                &json!({
+                    "type": "message_delta",
+                    "delta": { "stop_reason": stop_reason }
+                }),
+            );
+
+            let event = take_response_done(&mut state, &HashMap::new()).expect("terminal event");
+            let UrpStreamEvent::ResponseDone {
+                finish_reason,
+                extra_body,
+                ..
+            } = event
+            else {
+                panic!("expected ResponseDone");
+            };
+            assert_eq!(finish_reason, Some(expected), "{stop_reason}");
+            assert_eq!(extra_body.get("stop_reason"), Some(&json!(stop_reason)));
+        }
+    }
+
     #[test]
     fn anthropic_terminal_event_preserves_stop_sequence() {
         let mut state = AnthropicMessagesStreamState::default();
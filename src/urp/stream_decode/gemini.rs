@@ -1,10 +1,12 @@
 use crate::error::{AppError, AppResult};
 use crate::handlers::usage::{
-    latest_stream_usage_snapshot, mark_stream_ttfb_if_needed, parse_usage_from_gemini_object,
-    record_stream_done_sentinel, record_stream_terminal_event, record_stream_usage_if_present,
+    increment_estimated_output_tokens, latest_stream_usage_snapshot, mark_stream_ttfb_if_needed,
+    parse_usage_from_gemini_object, record_stream_done_sentinel, record_stream_terminal_error,
+    record_stream_terminal_event, record_stream_usage_if_present,
     record_visible_stream_event_delta,
 };
-use crate::handlers::{StreamRuntimeMetrics, UrpRequest as HandlerUrpRequest};
+use crate::handlers::{StreamRuntimeMetrics, StreamTerminalError, UrpRequest as HandlerUrpRequest};
+use crate::urp::decode::gemini::blocked_response_refusal;
 use crate::urp::{
     FinishReason, Node, NodeDelta, NodeHeader, OrdinaryRole, ProviderProtocol, UrpStreamEvent,
 };
@@ -61,6 +63,8 @@ pub(crate) async fn stream_gemini_to_urp_events(
     let response_id = format!("resp_{}", uuid::Uuid::new_v4());
     let mut started_response = false;
     let mut finish_reason: Option<FinishReason> = None;
+    let mut raw_finish_reason: Option<String> = None;
+    let mut blocked_refusal: Option<String> = None;
     let mut state = GeminiStreamState::default();
 
     let idle_timeout = std::time::Duration::from_millis(idle_timeout_ms.max(1));
@@ -97,11 +101,26 @@ pub(crate) async fn stream_gemini_to_urp_events(
         record_stream_usage_if_present(&runtime_metrics, parse_usage_from_gemini_object(&data_val))
             .await;
 
+        if let Some(error) = data_val.get("error").filter(|error| !error.is_null()) {
+            emit_gemini_stream_error(&tx, &runtime_metrics, error).await;
+            return Ok(());
+        }
+
         let Some(candidate) = data_val
             .get("candidates")
             .and_then(|v| v.as_array())
             .and_then(|arr| arr.first())
         else {
+            if data_val
+                .get("promptFeedback")
+                .and_then(|v| v.get("blockReason"))
+                .and_then(|v| v.as_str())
+                .is_some_and(|reason| !reason.is_empty())
+            {
+                finish_reason = Some(FinishReason::ContentFilter);
+                blocked_refusal = blocked_response_refusal(&data_val);
+                break;
+            }
             continue;
         };
 
@@ -110,6 +129,13 @@ pub(crate) async fn stream_gemini_to_urp_events(
             .and_then(|v| v.get("parts"))
             .and_then(|v| v.as_array())
         else {
+            // Safety-terminated candidates often carry no content at all.
+            if let Some(reason) = candidate.get("finishReason").and_then(|v| v.as_str()) {
+                finish_reason = Some(parse_finish_reason(reason));
+                raw_finish_reason = Some(reason.to_string());
+                blocked_refusal = blocked_response_refusal(&data_val);
+                break;
+            }
             continue;
         };
 
@@ -171,10 +197,57 @@ pub(crate) async fn stream_gemini_to_urp_events(
 
         if let Some(reason) = candidate.get("finishReason").and_then(|v| v.as_str()) {
             finish_reason = Some(parse_finish_reason(reason));
+            raw_finish_reason = Some(reason.to_string());
             break;
         }
     }
 
+    if !started_response && finish_reason.is_some() {
+        let _ = tx
+            .send(UrpStreamEvent::ResponseStart {
+                id: response_id.clone(),
+                model: urp.model.clone(),
+                extra_body: HashMap::new(),
+            })
+            .await;
+        started_response = true;
+    }
+
+    // A block with nothing streamed yet surfaces as a refusal, not silence.
+    if let Some(content) = blocked_refusal.filter(|_| state.node_order.is_empty()) {
+        let node_index = 0;
+        let node = Node::Refusal {
+            id: None,
+            content: content.clone(),
+            extra_body: HashMap::new(),
+        };
+        let events = [
+            UrpStreamEvent::NodeStart {
+                node_index,
+                header: node_header_from_node(&node),
+                extra_body: HashMap::new(),
+            },
+            UrpStreamEvent::NodeDelta {
+                node_index,
+                delta: NodeDelta::Refusal { content },
+                usage: None,
+                extra_body: HashMap::new(),
+            },
+            UrpStreamEvent::NodeDone {
+                node_index,
+                node: node.clone(),
+                usage: None,
+                extra_body: HashMap::new(),
+            },
+        ];
+        for event in events {
+            record_visible_stream_event_delta(started_at, &runtime_metrics, &event).await;
+            let _ = tx.send(event).await;
+        }
+        state.node_order.push(node_index);
+        state.completed_nodes.insert(node_index, node);
+    }
+
     let active_indices: Vec<u32> = state.node_order.clone();
     for node_index in active_indices {
         let Some(active_node) = state.active_nodes.remove(&node_index) else {
@@ -195,15 +268,22 @@ pub(crate) async fn stream_gemini_to_urp_events(
     }
 
     let output_nodes = ordered_completed_nodes(&state);
+    increment_estimated_output_tokens(&runtime_metrics, &urp.model, &output_nodes).await;
     let usage = latest_stream_usage_snapshot(&runtime_metrics).await;
 
     if started_response {
+        let mut extra_body = HashMap::new();
+        crate::urp::record_raw_finish_reason(
+            &mut extra_body,
+            finish_reason,
+            raw_finish_reason.as_deref(),
+        );
         let _ = tx
             .send(UrpStreamEvent::ResponseDone {
                 finish_reason,
                 usage,
                 output: output_nodes,
-                extra_body: HashMap::new(),
+                extra_body,
             })
             .await;
     }
@@ -212,6 +292,62 @@ pub(crate) async fn stream_gemini_to_urp_events(
     Ok(())
 }
 
+/// Forwards a mid-stream Gemini `{"error": {code, message, status}}` chunk as
+/// a terminal URP error. `status` (e.g. `RESOURCE_EXHAUSTED`) becomes the
+/// error code and the numeric `code` the HTTP status.
+async fn emit_gemini_stream_error(
+    tx: &mpsc::Sender<UrpStreamEvent>,
+    runtime_metrics: &Option<Arc<Mutex<StreamRuntimeMetrics>>>,
+    error: &Value,
+) {
+    let code = error
+        .get("status")
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.is_empty())
+        .unwrap_or("upstream_gemini_error")
+        .to_string();
+    let message = error
+        .get("message")
+        .and_then(|v| v.as_str())
+        .or_else(|| error.as_str())
+        .unwrap_or("upstream Gemini stream error")
+        .to_string();
+    let http_status = error
+        .get("code")
+        .and_then(|v| v.as_u64())
+        .filter(|status| (400..=599).contains(status))
+        .and_then(|status| u16::try_from(status).ok())
+        .unwrap_or(StatusCode::BAD_GATEWAY.as_u16());
+    let extra_body = error
+        .as_object()
+        .map(|obj| {
+            obj.iter()
+                .filter(|(key, _)| !matches!(key.as_str(), "status" | "message"))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let _ = tx
+        .send(UrpStreamEvent::Error {
+            code: Some(code.clone()),
+            message: message.clone(),
+            extra_body,
+        })
+        .await;
+    record_stream_terminal_error(
+        runtime_metrics,
+        "error",
+        StreamTerminalError {
+            code: code.clone(),
+            message,
+            http_status,
+            error_type: Some(code),
+            param: None,
+        },
+    )
+    .await;
+}
+
 #[derive(Debug, Clone)]
 struct CandidatePartState {
     node_index: u32,
@@ -507,6 +643,10 @@ fn node_header_from_node(node: &Node) -> NodeHeader {
             id: node.id().cloned(),
             role: *role,
         },
+        Node::Video { role, .. } => NodeHeader::Video {
+            id: node.id().cloned(),
+            role: *role,
+        },
         Node::File { role, .. } => NodeHeader::File {
             id: node.id().cloned(),
             role: *role,
@@ -547,7 +687,14 @@ fn ordered_completed_nodes(state: &GeminiStreamState) -> Vec<Node> {
 fn parse_finish_reason(reason: &str) -> FinishReason {
     match reason {
         "MAX_TOKENS" => FinishReason::Length,
-        "SAFETY" => FinishReason::ContentFilter,
+        "SAFETY"
+        | "RECITATION"
+        | "BLOCKLIST"
+        | "PROHIBITED_CONTENT"
+        | "SPII"
+        | "IMAGE_SAFETY"
+        | "IMAGE_PROHIBITED_CONTENT"
+        | "IMAGE_RECITATION" => FinishReason::ContentFilter,
         "STOP" => FinishReason::Stop,
         _ => FinishReason::Other,
     }
@@ -672,4 +819,27 @@ mod tests {
                 && matches!(&output[2], Node::ToolCall { call_id, arguments, .. } if call_id == "call_1" && arguments == "{\"a\":1}")
         ));
     }
+
+    #[tokio::test]
+    async fn gemini_error_chunk_emits_terminal_urp_error() {
+        let (tx, mut rx) = mpsc::channel(4);
+        emit_gemini_stream_error(
+            &tx,
+            &None,
+            &serde_json::json!({
+                "code": 429,
+                "message": "quota exceeded",
+                "status": "RESOURCE_EXHAUSTED"
+            }),
+        )
+        .await;
+
+        assert!(matches!(
+            rx.recv().await,
+            Some(UrpStreamEvent::Error { code: Some(code), message, extra_body })
+                if code == "RESOURCE_EXHAUSTED"
+                    && message == "quota exceeded"
+                    && extra_body.get("code") == Some(&serde_json::json!(429))
+        ));
+    }
 }
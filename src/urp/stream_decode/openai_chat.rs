@@ -9,6 +9,7 @@ use crate::urp::decode::parse_tool_call_arguments_value;
 use crate::urp::stream_helpers::{
     extract_chat_reasoning_content_block, extract_chat_reasoning_delta_chunks,
 };
+use crate::urp::tool_call_ids::{disambiguate_call_id, warn_duplicate_call_id};
 use crate::urp::{
     CHAT_LEGACY_FUNCTION_CALL_EXTRA_KEY, CHAT_REASONING_DETAIL_EXTRA_KEY, FinishReason, Node,
     NodeDelta, NodeHeader, OrdinaryRole, ProviderProtocol, ToolCallType, UrpStreamEvent,
@@ -179,6 +180,11 @@ pub(crate) async fn stream_chat_to_urp_events(
             }
             protocol_terminal_seen = true;
             finish_reason = Some(parse_finish_reason(reason));
+            crate::urp::record_raw_finish_reason(
+                &mut terminal_extra_body,
+                finish_reason,
+                Some(reason),
+            );
             terminal_extra_body.insert(
                 CHAT_NATIVE_FINISH_REASON_EXTRA_KEY.to_string(),
                 Value::String(reason.to_string()),
@@ -588,18 +594,6 @@ pub(crate) async fn stream_chat_to_urp_events(
     }
 
     let usage = latest_stream_usage_snapshot(&runtime_metrics).await;
-    {
-        let total_output_chars = (output_text.len()
-            + reasoning_text.len()
-            + reasoning_summary.len()
-            + reasoning_detail_text_len(&reasoning_detail_nodes))
-            as u64;
-        crate::handlers::usage::increment_estimated_output_tokens(
-            &runtime_metrics,
-            total_output_chars,
-        )
-        .await;
-    }
     let output_nodes = sorted_nodes(
         assistant_message_phase.as_deref(),
         text_node_index,
@@ -615,6 +609,13 @@ pub(crate) async fn stream_chat_to_urp_events(
         &tool_node_index_by_call_id,
         &provider_items,
     );
+    let estimated_nodes: Vec<Node> = output_nodes.iter().map(|(_, node)| node.clone()).collect();
+    crate::handlers::usage::increment_estimated_output_tokens(
+        &runtime_metrics,
+        &urp.model,
+        &estimated_nodes,
+    )
+    .await;
 
     for (node_index, node) in &output_nodes {
         send_event(
@@ -1452,21 +1453,41 @@ fn terminal_tool_call_id(
     call_order: &[String],
     call_id_by_index: &HashMap<usize, String>,
 ) -> Option<String> {
-    tool_call
+    let index = tool_call
+        .get("index")
+        .and_then(Value::as_u64)
+        .map(|index| index as usize);
+    let raw_call_id = tool_call
         .get("id")
         .or_else(|| tool_call.get("call_id"))
         .and_then(Value::as_str)
-        .filter(|call_id| !call_id.is_empty())
-        .map(str::to_string)
-        .or_else(|| {
-            tool_call
-                .get("index")
-                .and_then(Value::as_u64)
-                .and_then(|index| call_id_by_index.get(&(index as usize)).cloned())
-        })
+        .filter(|call_id| !call_id.is_empty());
+    if let Some(raw_call_id) = raw_call_id {
+        if let Some(index) = index
+            && call_id_owned_by_other_index(call_id_by_index, raw_call_id, index)
+            && let Some(existing) = call_id_by_index.get(&index)
+        {
+            return Some(existing.clone());
+        }
+        return Some(raw_call_id.to_string());
+    }
+    index
+        .and_then(|index| call_id_by_index.get(&index).cloned())
         .or_else(|| call_order.get(tool_call_pos).cloned())
 }
 
+/// Whether `call_id` was already claimed by the tool call at another stream
+/// index, i.e. the upstream reused one id for two distinct calls.
+fn call_id_owned_by_other_index(
+    call_id_by_index: &HashMap<usize, String>,
+    call_id: &str,
+    index: usize,
+) -> bool {
+    call_id_by_index
+        .iter()
+        .any(|(owner, owned)| *owner != index && owned == call_id)
+}
+
 fn tool_call_with_arguments_delta(raw: &Value, arguments: &str) -> Value {
     let mut raw = raw.clone();
     let Some(obj) = raw.as_object_mut() else {
@@ -1871,6 +1892,20 @@ async fn process_tool_call_delta(
         .and_then(|v| v.as_str())
         .unwrap_or("")
         .to_string();
+    if let Some(idx) = tc_index
+        && !call_id.is_empty()
+        && call_id_owned_by_other_index(state.call_id_by_index, &call_id, idx)
+    {
+        call_id = match state.call_id_by_index.get(&idx) {
+            Some(existing) => existing.clone(),
+            None => {
+                let unique =
+                    disambiguate_call_id(&call_id, |candidate| state.calls.contains_key(candidate));
+                warn_duplicate_call_id(&call_id, &unique);
+                unique
+            }
+        };
+    }
     if call_id.is_empty() {
         if let Some(idx) = tc_index {
             if let Some(existing) = state.call_id_by_index.get(&idx) {
@@ -2106,21 +2141,6 @@ fn parse_finish_reason(s: &str) -> FinishReason {
     }
 }
 
-fn reasoning_detail_text_len(nodes: &[(u32, Node)]) -> usize {
-    nodes
-        .iter()
-        .map(|(_, node)| match node {
-            Node::Reasoning {
-                content, summary, ..
-            } => {
-                content.as_deref().map(str::len).unwrap_or_default()
-                    + summary.as_deref().map(str::len).unwrap_or_default()
-            }
-            _ => 0,
-        })
-        .sum()
-}
-
 #[allow(clippy::too_many_arguments)]
 fn sorted_nodes(
     assistant_message_phase: Option<&str>,
@@ -2530,6 +2550,42 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn repeated_tool_call_ids_on_distinct_indices_stay_distinct_calls() {
+        let (tx, _rx) = mpsc::channel(32);
+        let mut call_order = Vec::new();
+        let mut calls = HashMap::new();
+        let mut call_id_by_index = HashMap::new();
+        let mut response_started = false;
+        let mut next_node_index = 0;
+        let mut tool_node_index_by_call_id = HashMap::new();
+        let mut delta_extra = Map::new();
+
+        for tool_call in [
+            json!({ "index": 0, "id": "call_1", "type": "function", "function": { "name": "lookup", "arguments": "{\"q\":" } }),
+            json!({ "index": 1, "id": "call_1", "type": "function", "function": { "name": "lookup", "arguments": "{\"q\":" } }),
+            json!({ "index": 0, "function": { "arguments": "1}" } }),
+            json!({ "index": 1, "id": "call_1", "function": { "arguments": "2}" } }),
+        ] {
+            let mut state = ChatToolCallStreamState {
+                call_order: &mut call_order,
+                calls: &mut calls,
+                call_id_by_index: &mut call_id_by_index,
+                response_started: &mut response_started,
+                next_node_index: &mut next_node_index,
+                tool_node_index_by_call_id: &mut tool_node_index_by_call_id,
+                delta_extra: &mut delta_extra,
+            };
+            process_tool_call_delta(&tx, "resp_dup", "gpt-4", &tool_call, 0, false, &mut state)
+                .await
+                .expect("tool call delta");
+        }
+
+        assert_eq!(call_order, vec!["call_1", "call_1_2"]);
+        assert_eq!(calls["call_1"].2, "{\"q\":1}");
+        assert_eq!(calls["call_1_2"].2, "{\"q\":2}");
+    }
+
     #[tokio::test]
     async fn chat_stream_rejects_reserved_wire_extras_and_modern_legacy_marker_spoof() {
         let choice = chat_choice_extra(
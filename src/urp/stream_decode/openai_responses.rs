@@ -12,6 +12,7 @@ use crate::urp::internal_legacy_bridge::{Item, Part, Role};
 use crate::urp::stream_helpers::{
     extract_reasoning_parts, extract_responses_message_phase, extract_responses_message_text,
 };
+use crate::urp::tool_call_ids::{ToolCallIdDeduper, disambiguate_call_id};
 use crate::urp::{
     FinishReason, Node, NodeDelta, NodeHeader, OrdinaryRole, ProviderProtocol,
     RESPONSES_IMAGE_GENERATION_CALL_EXTRA_KEY, RESPONSES_STREAM_START_SOURCE_EXTRA_KEY,
@@ -21,7 +22,7 @@ use axum::http::StatusCode;
 use eventsource_stream::Eventsource;
 use futures_util::StreamExt;
 use serde_json::{Value, json};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::{Mutex, mpsc};
 
@@ -393,6 +393,50 @@ fn build_accumulated_output_nodes(
     )
 }
 
+/// Resolves the bookkeeping call id of a tool call event. When the upstream
+/// reuses one `call_id` at another output index, the later call is tracked
+/// under a suffixed id so its arguments never accumulate into the first one.
+fn responses_stream_call_id(
+    raw_call_id: Option<&str>,
+    output_index: Option<u64>,
+    call_ids_by_output_index: &HashMap<u64, String>,
+    calls: &HashMap<String, (ToolCallType, String, String)>,
+) -> Option<String> {
+    let assigned = output_index.and_then(|idx| call_ids_by_output_index.get(&idx));
+    let Some(raw_call_id) = raw_call_id else {
+        return assigned.cloned();
+    };
+    let owned_by_other_index = output_index.is_some_and(|idx| {
+        call_ids_by_output_index
+            .iter()
+            .any(|(owner, call_id)| *owner != idx && call_id == raw_call_id)
+    });
+    if !owned_by_other_index {
+        return Some(raw_call_id.to_string());
+    }
+    Some(match assigned {
+        Some(assigned) => assigned.clone(),
+        None => disambiguate_call_id(raw_call_id, |candidate| calls.contains_key(candidate)),
+    })
+}
+
+/// Renames terminal tool calls that repeat an earlier call id, in output
+/// order, matching the ids the streamed bookkeeping assigned.
+fn dedupe_terminal_tool_call_ids(terminal_outputs: &mut [AccumulatedOutputEntry]) {
+    let mut seen = HashSet::new();
+    for node in terminal_outputs
+        .iter_mut()
+        .flat_map(|entry| entry.nodes.iter_mut())
+    {
+        if let Node::ToolCall { call_id, .. } = node {
+            if seen.contains(call_id.as_str()) {
+                *call_id = disambiguate_call_id(call_id, |candidate| seen.contains(candidate));
+            }
+            seen.insert(call_id.clone());
+        }
+    }
+}
+
 fn output_index_for_call_id(
     call_ids_by_output_index: &HashMap<u64, String>,
     target_call_id: &str,
@@ -72,6 +72,10 @@ fn node_header_from_node(node: &Node) -> NodeHeader {
             id: id.clone(),
             role: *role,
         },
+        Node::Video { id, role, .. } => NodeHeader::Video {
+            id: id.clone(),
+            role: *role,
+        },
         Node::File { id, role, .. } => NodeHeader::File {
             id: id.clone(),
             role: *role,
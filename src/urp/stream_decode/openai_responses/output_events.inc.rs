@@ -14,7 +14,7 @@ fn map_response_completed_with_accumulated(
     };
     let response_value = Value::Object(response_obj.clone());
     let decoded = crate::urp::decode::openai_responses::decode_response(&response_value).ok();
-    let terminal_outputs = response_obj
+    let mut terminal_outputs = response_obj
         .get("output")
         .and_then(|v| v.as_array())
         .map(|items| {
@@ -66,6 +66,7 @@ fn map_response_completed_with_accumulated(
                 .collect::<Vec<_>>()
         })
         .unwrap_or_default();
+    dedupe_terminal_tool_call_ids(&mut terminal_outputs);
     let outputs = match merge_response_completed_outputs(terminal_outputs, accumulated_outputs) {
         Ok(outputs) => outputs,
         Err(reason) => {
@@ -77,8 +78,11 @@ fn map_response_completed_with_accumulated(
             return events;
         }
     };
-    let finish_reason = match response_obj.get("status").and_then(Value::as_str) {
-        Some("incomplete") => Some(FinishReason::Length),
+    let status = response_obj.get("status").and_then(Value::as_str);
+    let finish_reason = match status {
+        Some("incomplete") => Some(crate::urp::decode::openai_responses::incomplete_finish_reason(
+            response_obj.get("incomplete_details"),
+        )),
         Some("failed" | "cancelled") => Some(FinishReason::Other),
         Some("completed") if outputs_have_tool_calls(&outputs) => Some(FinishReason::ToolCalls),
         Some("completed") => decoded
@@ -87,24 +91,27 @@ fn map_response_completed_with_accumulated(
             .or(Some(FinishReason::Stop)),
         _ => decoded.as_ref().and_then(|resp| resp.finish_reason),
     };
+    let usage = decoded
+        .and_then(|resp| resp.usage)
+        .or_else(|| parse_usage_from_responses_object(&response_value));
+    let mut extra_body = split_known_fields(
+        response_value,
+        &[
+            "id",
+            "object",
+            "created",
+            "created_at",
+            "model",
+            "output",
+            "usage",
+        ],
+    );
+    crate::urp::record_raw_finish_reason(&mut extra_body, finish_reason, status);
     events.push(UrpStreamEvent::ResponseDone {
         finish_reason,
-        usage: decoded
-            .and_then(|resp| resp.usage)
-            .or_else(|| parse_usage_from_responses_object(&response_value)),
+        usage,
         output: outputs,
-        extra_body: split_known_fields(
-            response_value,
-            &[
-                "id",
-                "object",
-                "created",
-                "created_at",
-                "model",
-                "output",
-                "usage",
-            ],
-        ),
+        extra_body,
     });
     events
 }
@@ -458,6 +458,7 @@ fn node_output_kind(node: &Node) -> Option<OutputEntryKind> {
         Node::Text { .. }
         | Node::Image { .. }
         | Node::Audio { .. }
+        | Node::Video { .. }
         | Node::File { .. }
         | Node::Refusal { .. } => Some(OutputEntryKind::Message),
         Node::ToolCall { .. } => Some(OutputEntryKind::ToolCall),
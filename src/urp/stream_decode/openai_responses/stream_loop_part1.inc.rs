@@ -19,6 +19,7 @@ pub(crate) async fn stream_responses_to_urp_events(
     let mut call_order: Vec<String> = Vec::new();
     let mut calls: HashMap<String, (ToolCallType, String, String)> = HashMap::new();
     let mut call_ids_by_output_index: HashMap<u64, String> = HashMap::new();
+    let mut tool_call_ids = ToolCallIdDeduper::default();
     let mut saw_text_delta = false;
     let mut response_done_sent = false;
     let mut terminal_event_name: Option<String> = None;
@@ -285,7 +286,13 @@ pub(crate) async fn stream_responses_to_urp_events(
                 } else {
                     ToolCallType::Function
                 };
-                if let Some(call_id) = item.get("call_id").and_then(|v| v.as_str()) {
+                if let Some(call_id) = responses_stream_call_id(
+                    item.get("call_id").and_then(|v| v.as_str()),
+                    data_val.get("output_index").and_then(|v| v.as_u64()),
+                    &call_ids_by_output_index,
+                    &calls,
+                ) {
+                    let call_id = call_id.as_str();
                     if !calls.contains_key(call_id) {
                         call_order.push(call_id.to_string());
                         calls.insert(
@@ -344,16 +351,12 @@ pub(crate) async fn stream_responses_to_urp_events(
             } else {
                 ToolCallType::Function
             };
-            let call_id_opt = data_val
-                .get("call_id")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .or_else(|| {
-                    data_val
-                        .get("output_index")
-                        .and_then(|v| v.as_u64())
-                        .and_then(|idx| call_ids_by_output_index.get(&idx).cloned())
-                });
+            let call_id_opt = responses_stream_call_id(
+                data_val.get("call_id").and_then(|v| v.as_str()),
+                data_val.get("output_index").and_then(|v| v.as_u64()),
+                &call_ids_by_output_index,
+                &calls,
+            );
             if let Some(call_id) = call_id_opt {
                 let name = data_val.get("name").and_then(|v| v.as_str()).unwrap_or("");
                 let delta = data_val.get("delta").and_then(|v| v.as_str()).unwrap_or("");
@@ -379,16 +382,12 @@ pub(crate) async fn stream_responses_to_urp_events(
             ev.event.as_str(),
             "response.function_call_arguments.done" | "response.custom_tool_call_input.done"
         ) {
-            let call_id_opt = data_val
-                .get("call_id")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .or_else(|| {
-                    data_val
-                        .get("output_index")
-                        .and_then(|v| v.as_u64())
-                        .and_then(|idx| call_ids_by_output_index.get(&idx).cloned())
-                });
+            let call_id_opt = responses_stream_call_id(
+                data_val.get("call_id").and_then(|v| v.as_str()),
+                data_val.get("output_index").and_then(|v| v.as_u64()),
+                &call_ids_by_output_index,
+                &calls,
+            );
             if let Some(call_id) = call_id_opt {
                 let args = data_val
                     .get(if ev.event == "response.custom_tool_call_input.done" {
@@ -428,7 +427,13 @@ pub(crate) async fn stream_responses_to_urp_events(
                 } else {
                     ToolCallType::Function
                 };
-                if let Some(call_id) = item.get("call_id").and_then(|v| v.as_str()) {
+                if let Some(call_id) = responses_stream_call_id(
+                    item.get("call_id").and_then(|v| v.as_str()),
+                    data_val.get("output_index").and_then(|v| v.as_u64()),
+                    &call_ids_by_output_index,
+                    &calls,
+                ) {
+                    let call_id = call_id.as_str();
                     let name = item.get("name").and_then(|v| v.as_str()).unwrap_or("");
                     let args = item
                         .get(if tool_type == ToolCallType::Custom {
@@ -523,7 +528,8 @@ pub(crate) async fn stream_responses_to_urp_events(
                 &mut index_state,
             )
         };
-        for stream_event in stream_events {
+        for mut stream_event in stream_events {
+            tool_call_ids.apply_to_event(&mut stream_event);
             if let UrpStreamEvent::Error {
                 code,
                 message,
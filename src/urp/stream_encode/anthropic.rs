@@ -491,6 +491,7 @@ fn anthropic_block_from_node(node: &Node) -> Option<AnthropicBlockPayload> {
         }),
         Node::Image { .. }
         | Node::Audio { .. }
+        | Node::Video { .. }
         | Node::File { .. }
         | Node::ProviderItem { .. }
         | Node::ToolResult { .. }
@@ -548,6 +549,7 @@ fn anthropic_block_from_node_header(
         }
         NodeHeader::Image { .. }
         | NodeHeader::Audio { .. }
+        | NodeHeader::Video { .. }
         | NodeHeader::File { .. }
         | NodeHeader::ProviderItem { .. }
         | NodeHeader::ToolResult { .. }
@@ -1746,7 +1748,10 @@ pub(crate) async fn encode_urp_stream_as_messages(
                 if let Some(usage) = &usage {
                     response_usage = Some(usage.clone());
                 }
+                // A content-filtered response may carry no blocks at all, but the
+                // client still needs `stop_reason: "refusal"`.
                 should_emit_terminal_message = should_emit_terminal_message
+                    || finish_reason == Some(FinishReason::ContentFilter)
                     || !pending_node_blocks.is_empty()
                     || !live_node_blocks.is_empty()
                     || output
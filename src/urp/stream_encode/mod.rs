@@ -15,6 +15,7 @@ pub(crate) async fn emit_synthetic_stream_from_urp_response(
     resp: &urp::UrpResponse,
     synthetic_reasoning_duration_secs: Option<u64>,
     sse_max_frame_length: Option<usize>,
+    chat_include_usage: bool,
     tx: mpsc::Sender<Event>,
 ) -> AppResult<()> {
     match downstream {
@@ -29,8 +30,14 @@ pub(crate) async fn emit_synthetic_stream_from_urp_response(
             .await
         }
         DownstreamProtocol::ChatCompletions => {
-            openai_chat::emit_synthetic_chat_stream(logical_model, resp, sse_max_frame_length, tx)
-                .await
+            openai_chat::emit_synthetic_chat_stream(
+                logical_model,
+                resp,
+                sse_max_frame_length,
+                chat_include_usage,
+                tx,
+            )
+            .await
         }
         DownstreamProtocol::AnthropicMessages => {
             anthropic::emit_synthetic_messages_stream(logical_model, resp, sse_max_frame_length, tx)
@@ -46,6 +53,7 @@ pub(crate) async fn encode_urp_stream(
     logical_model: &str,
     stream_started_at: Instant,
     sse_max_frame_length: Option<usize>,
+    chat_include_usage: bool,
 ) -> AppResult<()> {
     match downstream {
         DownstreamProtocol::Responses => {
@@ -59,8 +67,14 @@ pub(crate) async fn encode_urp_stream(
             .await
         }
         DownstreamProtocol::ChatCompletions => {
-            openai_chat::encode_urp_stream_as_chat(rx, tx, logical_model, sse_max_frame_length)
-                .await
+            openai_chat::encode_urp_stream_as_chat(
+                rx,
+                tx,
+                logical_model,
+                sse_max_frame_length,
+                chat_include_usage,
+            )
+            .await
         }
         DownstreamProtocol::AnthropicMessages => {
             anthropic::encode_urp_stream_as_messages(rx, tx, logical_model, sse_max_frame_length)
@@ -238,6 +238,7 @@ pub(crate) async fn emit_synthetic_chat_stream(
     logical_model: &str,
     resp: &urp::UrpResponse,
     sse_max_frame_length: Option<usize>,
+    include_usage: bool,
     tx: mpsc::Sender<Event>,
 ) -> AppResult<()> {
     let id = format!("chatcmpl_{}", uuid::Uuid::new_v4());
@@ -480,7 +481,7 @@ pub(crate) async fn emit_synthetic_chat_stream(
         created,
         logical_model,
         finish_reason,
-        resp.usage.as_ref(),
+        resp.usage.as_ref().filter(|_| include_usage),
         resp.extra_body
             .get(CHAT_CHOICE_EXTRA_BODY_KEY)
             .and_then(Value::as_object),
@@ -549,6 +550,7 @@ pub(crate) async fn encode_urp_stream_as_chat(
     tx: mpsc::Sender<Event>,
     logical_model: &str,
     sse_max_frame_length: Option<usize>,
+    include_usage: bool,
 ) -> AppResult<()> {
     let mut chat_id = String::new();
     let mut created = 0i64;
@@ -1045,7 +1047,7 @@ pub(crate) async fn encode_urp_stream_as_chat(
                     created,
                     logical_model,
                     finish_reason,
-                    usage.as_ref(),
+                    usage.as_ref().filter(|_| include_usage),
                     extra_body
                         .get(CHAT_CHOICE_EXTRA_BODY_KEY)
                         .and_then(Value::as_object),
@@ -1583,7 +1585,7 @@ mod tests {
             .expect("response done");
         drop(event_tx);
 
-        encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-5.4", None)
+        encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-5.4", None, true)
             .await
             .expect("encode stream");
 
@@ -1638,7 +1640,7 @@ mod tests {
         drop(event_tx);
 
         with_sse_capture(frames.clone(), async {
-            encode_urp_stream_as_chat(event_rx, sse_tx, "deepseek-chat", None)
+            encode_urp_stream_as_chat(event_rx, sse_tx, "deepseek-chat", None, true)
                 .await
                 .unwrap();
         })
@@ -1687,7 +1689,7 @@ mod tests {
             extra_body: HashMap::new(),
         };
 
-        emit_synthetic_chat_stream("gpt-5.4", &response, None, sse_tx)
+        emit_synthetic_chat_stream("gpt-5.4", &response, None, true, sse_tx)
             .await
             .expect("emit synthetic chat stream");
 
@@ -1723,7 +1725,7 @@ mod tests {
         };
 
         with_sse_capture(frames.clone(), async {
-            emit_synthetic_chat_stream("gpt-5.4", &response, None, sse_tx)
+            emit_synthetic_chat_stream("gpt-5.4", &response, None, true, sse_tx)
                 .await
                 .expect("emit synthetic chat stream");
         })
@@ -1813,7 +1815,7 @@ mod tests {
         drop(event_tx);
 
         with_sse_capture(frames.clone(), async {
-            encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-5.4", None)
+            encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-5.4", None, true)
                 .await
                 .expect("encode stream");
         })
@@ -1888,7 +1890,7 @@ mod tests {
             .expect("response done");
         drop(event_tx);
 
-        encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-5.4", None)
+        encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-5.4", None, true)
             .await
             .expect("encode stream");
 
@@ -1972,7 +1974,7 @@ mod tests {
         drop(event_tx);
 
         with_sse_capture(frames.clone(), async {
-            encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-5.4", None)
+            encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-5.4", None, true)
                 .await
                 .expect("encode stream");
         })
@@ -2067,7 +2069,7 @@ mod tests {
         drop(event_tx);
 
         with_sse_capture(frames.clone(), async {
-            encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-4", None)
+            encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-4", None, true)
                 .await
                 .unwrap();
         })
@@ -2185,7 +2187,7 @@ mod tests {
         drop(event_tx);
 
         with_sse_capture(frames.clone(), async {
-            encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-5.4", None)
+            encode_urp_stream_as_chat(event_rx, sse_tx, "gpt-5.4", None, true)
                 .await
                 .expect("encode stream");
         })
@@ -2227,7 +2229,7 @@ mod tests {
         .expect("decode response");
 
         let (sse_tx, mut sse_rx) = mpsc::channel(16);
-        emit_synthetic_chat_stream("gpt-5.4", &response, None, sse_tx)
+        emit_synthetic_chat_stream("gpt-5.4", &response, None, true, sse_tx)
             .await
             .expect("emit synthetic chat stream");
 
@@ -2264,7 +2266,7 @@ mod tests {
         .expect("decode response");
 
         let (sse_tx, mut sse_rx) = mpsc::channel(16);
-        emit_synthetic_chat_stream("gpt-5.4", &response, None, sse_tx)
+        emit_synthetic_chat_stream("gpt-5.4", &response, None, true, sse_tx)
             .await
             .expect("emit synthetic chat stream");
 
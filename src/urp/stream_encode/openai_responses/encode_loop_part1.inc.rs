@@ -730,6 +730,7 @@ pub(crate) async fn encode_urp_stream_as_responses(
                     }
                     urp::NodeDelta::Image { .. }
                     | urp::NodeDelta::Audio { .. }
+                    | urp::NodeDelta::Video { .. }
                     | urp::NodeDelta::File { .. } => {}
                 }
             }
@@ -1567,6 +1568,7 @@ fn node_header_id(header: &urp::NodeHeader) -> Option<String> {
         urp::NodeHeader::Text { id, .. }
         | urp::NodeHeader::Image { id, .. }
         | urp::NodeHeader::Audio { id, .. }
+        | urp::NodeHeader::Video { id, .. }
         | urp::NodeHeader::File { id, .. }
         | urp::NodeHeader::Refusal { id }
         | urp::NodeHeader::Reasoning { id }
@@ -1692,6 +1694,7 @@ fn stream_output_item_start_stub_from_node_header(
                 urp::NodeHeader::Text { role, .. }
                 | urp::NodeHeader::Image { role, .. }
                 | urp::NodeHeader::Audio { role, .. }
+                | urp::NodeHeader::Video { role, .. }
                 | urp::NodeHeader::File { role, .. }
                 | urp::NodeHeader::ProviderItem { role, .. } => ordinary_role_to_str(*role),
                 urp::NodeHeader::Refusal { .. } => "assistant",
@@ -1871,7 +1874,9 @@ fn encode_node_start_content_part(header: &urp::NodeHeader) -> Value {
         urp::NodeHeader::Refusal { .. } => json!({ "type": "refusal", "refusal": "" }),
         urp::NodeHeader::Image { .. } => json!({ "type": "output_image" }),
         urp::NodeHeader::Audio { .. } => json!({ "type": "audio" }),
-        urp::NodeHeader::File { .. } => json!({ "type": "output_file" }),
+        urp::NodeHeader::File { .. } | urp::NodeHeader::Video { .. } => {
+            json!({ "type": "output_file" })
+        }
         urp::NodeHeader::ProviderItem { .. } => Value::Null,
         _ => Value::Null,
     }
@@ -1918,6 +1923,9 @@ fn encode_node_done_content_part(node: &urp::Node) -> Option<Value> {
         urp::Node::File {
             source, extra_body, ..
         } => encode_file_part(source, extra_body),
+        urp::Node::Video {
+            source, extra_body, ..
+        } => encode_file_part(&source.to_file_source(), extra_body),
         urp::Node::ProviderItem {
             origin_protocol,
             item_type,
@@ -2198,6 +2206,9 @@ fn encode_stream_output_item_from_node(node: &urp::Node) -> Value {
             merge_json_extra(&mut obj, extra_body);
             Value::Object(obj)
         }
+        urp::Node::Video { .. } => node
+            .video_as_file()
+            .map_or(Value::Null, |file| encode_stream_output_item_from_node(&file)),
         urp::Node::ProviderItem {
             origin_protocol,
             item_type,
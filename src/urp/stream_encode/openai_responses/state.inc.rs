@@ -70,6 +70,7 @@ fn terminal_output_node_matches_state(node: &urp::Node, state: &StreamedNodeStat
     match node {
         urp::Node::Text { id, .. }
         | urp::Node::Audio { id, .. }
+        | urp::Node::Video { id, .. }
         | urp::Node::File { id, .. }
         | urp::Node::Refusal { id, .. }
         => {
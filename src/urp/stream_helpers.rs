@@ -25,7 +25,14 @@ pub(crate) async fn send_named_sse_json(
     name: &str,
     data: Value,
 ) -> AppResult<()> {
-    let data = data.to_string();
+    send_named_sse_data(tx, name, data.to_string()).await
+}
+
+pub(crate) async fn send_named_sse_data(
+    tx: &mpsc::Sender<Event>,
+    name: &str,
+    data: String,
+) -> AppResult<()> {
     crate::request_capture::capture_sse_frame(format!("event: {name}\ndata: {data}\n\n")).await;
     send_sse_event(tx, Event::default().event(name).data(data)).await;
     Ok(())
@@ -0,0 +1,87 @@
+//! Disambiguation of tool call ids that an upstream repeats within one
+//! response. Downstream clients key tool results by `call_id`, so two calls
+//! sharing one id would collapse into a single call.
+
+use super::{Node, NodeHeader, UrpStreamEvent};
+use std::collections::{HashMap, HashSet};
+
+/// Returns `call_id` suffixed with the smallest `_<n>` (`n >= 2`) for which
+/// `taken` is false.
+pub fn disambiguate_call_id(call_id: &str, taken: impl Fn(&str) -> bool) -> String {
+    (2u32..)
+        .map(|n| format!("{call_id}_{n}"))
+        .find(|candidate| !taken(candidate))
+        .expect("unbounded suffix range")
+}
+
+pub fn warn_duplicate_call_id(call_id: &str, renamed_to: &str) {
+    tracing::warn!(
+        call_id = %call_id,
+        renamed_to = %renamed_to,
+        "upstream repeated a tool call id within one response; renaming the later call"
+    );
+}
+
+/// Tracks the tool call ids seen in one response. The first call keeps its
+/// id; every later call reusing it gets a suffixed id instead.
+#[derive(Debug, Default)]
+pub struct ToolCallIdDeduper {
+    seen: HashSet<String>,
+    call_id_by_node_index: HashMap<u32, String>,
+}
+
+impl ToolCallIdDeduper {
+    /// Returns the id to emit for a new call carrying `call_id`.
+    pub fn claim(&mut self, call_id: &str) -> String {
+        let unique = if self.seen.contains(call_id) {
+            let unique = disambiguate_call_id(call_id, |candidate| self.seen.contains(candidate));
+            warn_duplicate_call_id(call_id, &unique);
+            unique
+        } else {
+            call_id.to_string()
+        };
+        self.seen.insert(unique.clone());
+        unique
+    }
+
+    /// Rewrites the tool call ids of one stream event. Start and done events
+    /// of the same node always agree; the terminal output is deduplicated in
+    /// order, which matches the ids handed out per node.
+    pub fn apply_to_event(&mut self, event: &mut UrpStreamEvent) {
+        let (node_index, call_id) = match event {
+            UrpStreamEvent::NodeStart {
+                node_index,
+                header: NodeHeader::ToolCall { call_id, .. },
+                ..
+            }
+            | UrpStreamEvent::NodeDone {
+                node_index,
+                node: Node::ToolCall { call_id, .. },
+                ..
+            } => (*node_index, call_id),
+            UrpStreamEvent::ResponseDone { output, .. } => {
+                dedupe_tool_call_ids(output);
+                return;
+            }
+            _ => return,
+        };
+        if let Some(assigned) = self.call_id_by_node_index.get(&node_index) {
+            *call_id = assigned.clone();
+            return;
+        }
+        let assigned = self.claim(call_id);
+        self.call_id_by_node_index
+            .insert(node_index, assigned.clone());
+        *call_id = assigned;
+    }
+}
+
+/// Renames every tool call whose id repeats an earlier call in `nodes`.
+pub fn dedupe_tool_call_ids(nodes: &mut [Node]) {
+    let mut deduper = ToolCallIdDeduper::default();
+    for node in nodes {
+        if let Node::ToolCall { call_id, .. } = node {
+            *call_id = deduper.claim(call_id);
+        }
+    }
+}
@@ -0,0 +1,458 @@
+//! Invariant checks for decoded requests, used by strict gateway mode to
+//! reject malformed input before any upstream sees it.
+
+use super::{
+    ImageSource, Node, OrdinaryRole, ProviderProtocol, ToolCallType, ToolResultContent, UrpRequest,
+};
+use base64::Engine as _;
+use serde::Serialize;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RequestViolation {
+    /// Location in the decoded request, e.g. `input[2]` or `input[4].content[0]`.
+    pub path: String,
+    pub message: String,
+}
+
+impl RequestViolation {
+    fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Returns every invariant the request breaks, in input order. An empty list
+/// means the request is well-formed.
+pub fn validate_request(req: &UrpRequest) -> Vec<RequestViolation> {
+    let mut violations = Vec::new();
+    // Responses requests that continue stored state may answer tool calls the
+    // input does not contain.
+    let continues_stored_state = req.previous_response_id.is_some()
+        || req
+            .extra_body
+            .get("conversation")
+            .is_some_and(|value| !value.is_null());
+
+    if req.input.is_empty() {
+        if !continues_stored_state {
+            violations.push(RequestViolation::new(
+                "input",
+                "must contain at least one message",
+            ));
+        }
+        return violations;
+    }
+    let has_user_turn = req.input.iter().any(|node| {
+        matches!(node, Node::ToolResult { .. }) || node.role() == Some(OrdinaryRole::User)
+    });
+    if !has_user_turn && !continues_stored_state {
+        violations.push(RequestViolation::new(
+            "input",
+            "must contain a user message or a tool result",
+        ));
+    }
+
+    let mut seen_calls: HashSet<&str> = HashSet::new();
+    let mut unanswered: Vec<(usize, &str)> = Vec::new();
+    // A user turn is a run of user-role nodes and tool results. Calls still
+    // unanswered when it ends were skipped, wherever in the turn the results
+    // and any user text appear.
+    let mut in_user_turn = false;
+    for (index, node) in req.input.iter().enumerate() {
+        let path = format!("input[{index}]");
+        let user_side =
+            matches!(node, Node::ToolResult { .. }) || node.role() == Some(OrdinaryRole::User);
+        if user_side {
+            in_user_turn = true;
+        } else if in_user_turn && node.role().is_some() {
+            in_user_turn = false;
+            report_unanswered(&mut unanswered, &mut violations);
+        }
+        match node {
+            Node::ToolCall {
+                tool_type,
+                call_id,
+                name,
+                arguments,
+                ..
+            } => {
+                if call_id.is_empty() {
+                    violations.push(RequestViolation::new(&path, "tool call is missing call_id"));
+                } else if !seen_calls.insert(call_id) {
+                    violations.push(RequestViolation::new(
+                        &path,
+                        format!("duplicate tool call id `{call_id}`"),
+                    ));
+                } else {
+                    unanswered.push((index, call_id));
+                }
+                if name.is_empty() {
+                    violations.push(RequestViolation::new(&path, "tool call is missing name"));
+                }
+                if *tool_type == ToolCallType::Function
+                    && !arguments.is_empty()
+                    && serde_json::from_str::<serde_json::Value>(arguments).is_err()
+                {
+                    violations.push(RequestViolation::new(
+                        &path,
+                        "function tool call arguments must be valid JSON",
+                    ));
+                }
+            }
+            Node::ToolResult {
+                call_id, content, ..
+            } => {
+                if seen_calls.contains(call_id.as_str()) {
+                    unanswered.retain(|(_, pending)| *pending != call_id);
+                } else if !continues_stored_state {
+                    violations.push(RequestViolation::new(
+                        &path,
+                        format!("tool result `{call_id}` has no preceding tool call"),
+                    ));
+                }
+                for (part_index, part) in content.iter().enumerate() {
+                    if let ToolResultContent::Image { source, .. } = part {
+                        check_image_source(
+                            &format!("{path}.content[{part_index}]"),
+                            source,
+                            &mut violations,
+                        );
+                    }
+                }
+            }
+            Node::Image { source, .. } => check_image_source(&path, source, &mut violations),
+            _ => {}
+        }
+    }
+    if in_user_turn {
+        report_unanswered(&mut unanswered, &mut violations);
+    }
+    violations.sort_by_key(|violation| input_index(&violation.path));
+    violations
+}
+
+/// Returns the role-order rules of `target` that the request breaks. Only
+/// orders the target's encoder cannot repair are checked: the Messages and
+/// Gemini encoders merge consecutive same-role turns and lift system nodes,
+/// but neither can open a conversation that starts on the assistant side, and
+/// Chat Completions needs each tool result right after the assistant message
+/// holding its call.
+pub fn validate_role_order(req: &UrpRequest, target: ProviderProtocol) -> Vec<RequestViolation> {
+    let mut violations = Vec::new();
+    match target {
+        ProviderProtocol::Messages | ProviderProtocol::Gemini => {
+            let first_turn = req.input.iter().enumerate().find(|(_, node)| {
+                !matches!(
+                    node.role(),
+                    Some(OrdinaryRole::System | OrdinaryRole::Developer)
+                )
+            });
+            if let Some((index, node)) = first_turn
+                && node.role() == Some(OrdinaryRole::Assistant)
+            {
+                violations.push(RequestViolation::new(
+                    format!("input[{index}]"),
+                    format!(
+                        "{} conversations must start with a user message",
+                        target.as_str()
+                    ),
+                ));
+            }
+        }
+        ProviderProtocol::ChatCompletion => {
+            for (index, pair) in req.input.windows(2).enumerate() {
+                if matches!(pair[1], Node::ToolResult { .. })
+                    && matches!(
+                        pair[0].role(),
+                        Some(OrdinaryRole::User | OrdinaryRole::System | OrdinaryRole::Developer)
+                    )
+                {
+                    violations.push(RequestViolation::new(
+                        format!("input[{}]", index + 1),
+                        "tool result must directly follow the assistant message with its tool call",
+                    ));
+                }
+            }
+        }
+        ProviderProtocol::Responses
+        | ProviderProtocol::OpenaiImage
+        | ProviderProtocol::Replicate => {}
+    }
+    violations
+}
+
+fn report_unanswered(unanswered: &mut Vec<(usize, &str)>, violations: &mut Vec<RequestViolation>) {
+    for (call_index, call_id) in unanswered.drain(..) {
+        violations.push(RequestViolation::new(
+            format!("input[{call_index}]"),
+            format!("tool call `{call_id}` has no tool result in the next user turn"),
+        ));
+    }
+}
+
+fn input_index(path: &str) -> usize {
+    path.strip_prefix("input[")
+        .and_then(|rest| rest.split(']').next())
+        .and_then(|index| index.parse().ok())
+        .unwrap_or(0)
+}
+
+fn check_image_source(path: &str, source: &ImageSource, violations: &mut Vec<RequestViolation>) {
+    match source {
+        ImageSource::Url { url, .. } => {
+            if let Some(rest) = url.strip_prefix("data:") {
+                let well_formed = rest.split_once(',').is_some_and(|(header, data)| {
+                    header.starts_with("image/") && header.ends_with(";base64") && is_base64(data)
+                });
+                if !well_formed {
+                    violations.push(RequestViolation::new(
+                        path,
+                        "image data URL must be a base64-encoded image",
+                    ));
+                }
+            } else if !(url.starts_with("https://") || url.starts_with("http://")) {
+                violations.push(RequestViolation::new(
+                    path,
+                    "image url must be an http(s) or data URL",
+                ));
+            }
+        }
+        ImageSource::Base64 { media_type, data } => {
+            if !media_type.starts_with("image/") {
+                violations.push(RequestViolation::new(
+                    path,
+                    format!("image media type `{media_type}` is not an image type"),
+                ));
+            }
+            if !is_base64(data) {
+                violations.push(RequestViolation::new(
+                    path,
+                    "image data must be non-empty base64",
+                ));
+            }
+        }
+        ImageSource::FileId { file_id, .. } => {
+            if file_id.is_empty() {
+                violations.push(RequestViolation::new(
+                    path,
+                    "image file_id must not be empty",
+                ));
+            }
+        }
+    }
+}
+
+fn is_base64(data: &str) -> bool {
+    !data.is_empty()
+        && base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn request(input: Vec<Node>) -> UrpRequest {
+        serde_json::from_value(json!({ "model": "m", "input": [] }))
+            .map(|mut req: UrpRequest| {
+                req.input = input;
+                req
+            })
+            .expect("request")
+    }
+
+    fn text(role: OrdinaryRole, content: &str) -> Node {
+        Node::text(role, content)
+    }
+
+    fn tool_call(call_id: &str, arguments: &str) -> Node {
+        Node::ToolCall {
+            id: None,
+            tool_type: ToolCallType::Function,
+            call_id: call_id.to_string(),
+            name: "lookup".to_string(),
+            arguments: arguments.to_string(),
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn tool_result(call_id: &str) -> Node {
+        Node::ToolResult {
+            id: None,
+            tool_type: ToolCallType::Function,
+            call_id: call_id.to_string(),
+            is_error: false,
+            content: Vec::new(),
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn image(source: ImageSource) -> Node {
+        Node::Image {
+            id: None,
+            role: OrdinaryRole::User,
+            source,
+            extra_body: HashMap::new(),
+        }
+    }
+
+    fn paths(violations: &[RequestViolation]) -> Vec<&str> {
+        violations
+            .iter()
+            .map(|violation| violation.path.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn well_formed_tool_loop_has_no_violations() {
+        let req = request(vec![
+            text(OrdinaryRole::System, "be brief"),
+            text(OrdinaryRole::User, "weather?"),
+            tool_call("call_1", r#"{"city":"Paris"}"#),
+            tool_result("call_1"),
+            image(ImageSource::Url {
+                url: "data:image/png;base64,iVBORw0KGgo=".to_string(),
+                detail: None,
+            }),
+        ]);
+        assert!(validate_request(&req).is_empty());
+    }
+
+    #[test]
+    fn user_text_before_tool_result_in_the_same_turn_is_accepted() {
+        let req = request(vec![
+            text(OrdinaryRole::User, "weather?"),
+            tool_call("call_1", r#"{"city":"Paris"}"#),
+            text(OrdinaryRole::User, "here is the result"),
+            tool_result("call_1"),
+            text(OrdinaryRole::Assistant, "sunny"),
+        ]);
+        assert!(validate_request(&req).is_empty());
+
+        let skipped = validate_request(&request(vec![
+            text(OrdinaryRole::User, "weather?"),
+            tool_call("call_1", "{}"),
+            text(OrdinaryRole::User, "never mind"),
+            text(OrdinaryRole::Assistant, "ok"),
+            tool_result("call_1"),
+        ]));
+        assert_eq!(paths(&skipped), vec!["input[1]"]);
+    }
+
+    #[test]
+    fn empty_and_assistant_only_inputs_are_rejected() {
+        let empty = validate_request(&request(Vec::new()));
+        assert_eq!(paths(&empty), vec!["input"]);
+
+        let assistant_only =
+            validate_request(&request(vec![text(OrdinaryRole::Assistant, "hello")]));
+        assert_eq!(
+            assistant_only[0].message,
+            "must contain a user message or a tool result"
+        );
+
+        let mut continued = request(Vec::new());
+        continued.previous_response_id = Some("resp_1".to_string());
+        assert!(validate_request(&continued).is_empty());
+    }
+
+    #[test]
+    fn tool_pairing_violations_are_all_reported() {
+        let req = request(vec![
+            text(OrdinaryRole::User, "go"),
+            tool_call("call_1", "{not json"),
+            tool_call("call_1", "{}"),
+            text(OrdinaryRole::User, "and then?"),
+            tool_result("call_missing"),
+        ]);
+        let violations = validate_request(&req);
+        assert_eq!(
+            paths(&violations),
+            vec!["input[1]", "input[1]", "input[2]", "input[4]"]
+        );
+        let messages: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+        assert!(messages.contains(&"function tool call arguments must be valid JSON"));
+        assert!(messages.contains(&"duplicate tool call id `call_1`"));
+        assert!(messages.contains(&"tool call `call_1` has no tool result in the next user turn"));
+        assert!(messages.contains(&"tool result `call_missing` has no preceding tool call"));
+    }
+
+    #[test]
+    fn role_order_is_checked_against_the_target_provider() {
+        let assistant_first = request(vec![
+            text(OrdinaryRole::System, "be brief"),
+            text(OrdinaryRole::Assistant, "hi, how can I help?"),
+            text(OrdinaryRole::User, "weather?"),
+        ]);
+        for target in [ProviderProtocol::Messages, ProviderProtocol::Gemini] {
+            let violations = validate_role_order(&assistant_first, target);
+            assert_eq!(paths(&violations), vec!["input[1]"]);
+            assert_eq!(
+                violations[0].message,
+                format!(
+                    "{} conversations must start with a user message",
+                    target.as_str()
+                )
+            );
+        }
+        for target in [
+            ProviderProtocol::Responses,
+            ProviderProtocol::ChatCompletion,
+        ] {
+            assert!(validate_role_order(&assistant_first, target).is_empty());
+        }
+
+        let text_before_result = request(vec![
+            text(OrdinaryRole::User, "weather?"),
+            tool_call("call_1", "{}"),
+            tool_call("call_2", "{}"),
+            tool_result("call_1"),
+            text(OrdinaryRole::User, "here is the other one"),
+            tool_result("call_2"),
+        ]);
+        assert_eq!(
+            paths(&validate_role_order(
+                &text_before_result,
+                ProviderProtocol::ChatCompletion
+            )),
+            vec!["input[5]"]
+        );
+        for target in [
+            ProviderProtocol::Responses,
+            ProviderProtocol::Messages,
+            ProviderProtocol::Gemini,
+        ] {
+            assert!(validate_role_order(&text_before_result, target).is_empty());
+        }
+    }
+
+    #[test]
+    fn malformed_image_sources_are_rejected() {
+        let req = request(vec![
+            text(OrdinaryRole::User, "look"),
+            image(ImageSource::Url {
+                url: "ftp://example.com/cat.png".to_string(),
+                detail: None,
+            }),
+            image(ImageSource::Url {
+                url: "data:text/plain;base64,aGk=".to_string(),
+                detail: None,
+            }),
+            image(ImageSource::Base64 {
+                media_type: "application/pdf".to_string(),
+                data: "not base64!".to_string(),
+            }),
+        ]);
+        let violations = validate_request(&req);
+        assert_eq!(
+            paths(&violations),
+            vec!["input[1]", "input[2]", "input[3]", "input[3]"]
+        );
+    }
+}
@@ -148,6 +148,12 @@ pub struct ApiKey {
     /// List of allowed model IDs (empty = all models when model_limits_enabled is false)
     #[serde(default)]
     pub model_limits: Vec<String>,
+    /// Model globs checked after alias and suffix resolution (empty = all models)
+    #[serde(default)]
+    pub allow_models: Vec<String>,
+    /// Model globs rejected after alias and suffix resolution; wins over `allow_models`
+    #[serde(default)]
+    pub deny_models: Vec<String>,
     /// List of allowed IP addresses/CIDRs (empty = any IP)
     #[serde(default)]
     pub ip_whitelist: Vec<String>,
@@ -165,6 +171,10 @@ pub struct ApiKey {
     pub reasoning_envelope_enabled: bool,
     #[serde(default)]
     pub request_capture_mode: RequestCaptureMode,
+    #[serde(default)]
+    pub response_cache_enabled: bool,
+    #[serde(default)]
+    pub max_request_charge_nano_usd: Option<i64>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -226,6 +236,10 @@ pub struct CreateApiKeyInput {
     #[serde(default)]
     pub model_limits: Vec<String>,
     #[serde(default)]
+    pub allow_models: Vec<String>,
+    #[serde(default)]
+    pub deny_models: Vec<String>,
+    #[serde(default)]
     pub ip_whitelist: Vec<String>,
     #[serde(default)]
     pub allowed_groups: Vec<String>,
@@ -239,6 +253,10 @@ pub struct CreateApiKeyInput {
     pub reasoning_envelope_enabled: bool,
     #[serde(default)]
     pub request_capture_mode: RequestCaptureMode,
+    #[serde(default)]
+    pub response_cache_enabled: bool,
+    #[serde(default)]
+    pub max_request_charge_nano_usd: Option<i64>,
 }
 
 fn default_true() -> bool {
@@ -351,6 +369,8 @@ pub struct UpdateApiKeyInput {
     pub sub_account_enabled: Option<bool>,
     pub model_limits_enabled: Option<bool>,
     pub model_limits: Option<Vec<String>>,
+    pub allow_models: Option<Vec<String>>,
+    pub deny_models: Option<Vec<String>>,
     pub ip_whitelist: Option<Vec<String>>,
     pub allowed_groups: Option<Vec<String>>,
     pub max_multiplier: Option<f64>,
@@ -358,6 +378,9 @@ pub struct UpdateApiKeyInput {
     pub model_redirects: Option<Vec<ModelRedirectRule>>,
     pub reasoning_envelope_enabled: Option<bool>,
     pub request_capture_mode: Option<RequestCaptureMode>,
+    pub response_cache_enabled: Option<bool>,
+    /// Positive values set the per-request charge cap; zero or negative clears it.
+    pub max_request_charge_nano_usd: Option<i64>,
     pub expires_at: Option<String>, // RFC3339 format or null
 }
 
@@ -368,6 +391,7 @@ pub struct UserStore {
     pub(crate) request_log_batcher: crate::db_cache::RequestLogBatcher,
     pub(crate) api_key_cache: crate::db_cache::ApiKeyCache,
     pub(crate) balance_cache: crate::db_cache::BalanceCache,
+    pub(crate) balance_holds: crate::db_cache::BalanceHolds,
 }
 
 pub(crate) const RESERVED_INTERNAL_USER_PREFIX: &str = "_monoize_";
@@ -408,6 +432,7 @@ pub struct InsertRequestLog {
     pub request_ip: Option<String>,
     pub reasoning_effort: Option<String>,
     pub tried_providers_json: Option<Value>,
+    pub metadata_json: Option<Value>,
     pub request_kind: Option<String>,
     pub effective_provider_type: Option<String>,
     pub affinity_hit: Option<bool>,
@@ -511,6 +536,7 @@ pub struct RequestLogRow {
     pub reasoning_effort: Option<String>,
     pub request_ip: Option<String>,
     pub tried_providers: Option<Value>,
+    pub metadata: Option<Value>,
     pub provider: RequestLogProvider,
     pub channel: RequestLogChannel,
     pub affinity: RequestLogAffinity,
@@ -545,7 +571,10 @@ pub struct DashboardAnalyticsRaw {
     pub today_calls: i64,
 }
 
-pub use utils::{format_nano_to_usd, parse_nano_usd, parse_usd_to_nano};
+pub use utils::{
+    format_nano_to_usd, multiplier_to_nano, parse_nano_usd, parse_usd_to_nano,
+    scale_nano_by_multiplier,
+};
 
 #[cfg(test)]
 mod tests {
@@ -226,6 +226,10 @@ fn row_to_request_log(row: &sea_orm::QueryResult) -> RequestLogRow {
             row.try_get::<Option<String>>("", "tried_providers_json")
                 .unwrap_or(None),
         ),
+        metadata: parse_optional_json_text(
+            row.try_get::<Option<String>>("", "metadata_json")
+                .unwrap_or(None),
+        ),
         provider: RequestLogProvider {
             id: row.try_get("", "provider_id").unwrap_or(None),
             name: row.try_get("", "provider_name").unwrap_or(None),
@@ -477,7 +481,7 @@ impl UserStore {
                       rl.error_code, rl.error_message, rl.error_http_status,
                       rl.duration_ms, rl.ttfb_ms, rl.first_visible_output_ms, rl.last_visible_output_ms,
                       rl.visible_generation_ms, rl.visible_output_tokens, rl.tps_mode,
-                      rl.request_ip, rl.reasoning_effort, rl.request_kind,
+                      rl.request_ip, rl.reasoning_effort, rl.request_kind, rl.metadata_json,
                       rl.effective_provider_type, rl.affinity_hit, rl.affinity_key_hash, rl.affinity_target,
                       rl.created_at,
                       u.username AS username, ak.name AS api_key_name, ch.name AS channel_name, p.name AS provider_name
@@ -638,7 +642,7 @@ impl UserStore {
                       rl.error_code, rl.error_message, rl.error_http_status,
                       rl.duration_ms, rl.ttfb_ms, rl.first_visible_output_ms, rl.last_visible_output_ms,
                       rl.visible_generation_ms, rl.visible_output_tokens, rl.tps_mode,
-                      rl.request_ip, rl.reasoning_effort, rl.request_kind,
+                      rl.request_ip, rl.reasoning_effort, rl.request_kind, rl.metadata_json,
                       rl.effective_provider_type, rl.affinity_hit, rl.affinity_key_hash, rl.affinity_target,
                       rl.created_at,
                       u.username AS username, ak.name AS api_key_name, ch.name AS channel_name, p.name AS provider_name
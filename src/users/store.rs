@@ -5,6 +5,7 @@ use super::{
     UserBalance, UserRole, UserStore, canonicalize_groups, parse_groups_json,
     validate_model_redirects,
 };
+use crate::db_cache::BalanceHold;
 use crate::transforms::{
     TransformRuleConfig, canonical_transform_id, canonicalize_transform_rule,
     canonicalize_transform_rules,
@@ -157,6 +158,11 @@ fn validate_api_key_allowed_groups_subset(
     }
 }
 
+/// Sub-account holds share the hold table with user holds under a prefixed key.
+fn sub_account_hold_key(api_key_id: &str) -> String {
+    format!("api_key:{api_key_id}")
+}
+
 impl UserStore {
     pub fn is_reserved_internal_username(username: &str) -> bool {
         username
@@ -189,6 +195,7 @@ impl UserStore {
             ),
             api_key_cache: crate::db_cache::ApiKeyCache::new(Duration::from_secs(60)),
             balance_cache: crate::db_cache::BalanceCache::new(Duration::from_secs(30)),
+            balance_holds: crate::db_cache::BalanceHolds::new(),
         };
         store.migrate_transform_rule_ids().await?;
         Ok(store)
@@ -623,6 +630,8 @@ impl UserStore {
                 sub_account_enabled: false,
                 model_limits_enabled: false,
                 model_limits: Vec::new(),
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
                 ip_whitelist: Vec::new(),
                 allowed_groups: Vec::new(),
                 max_multiplier: None,
@@ -630,6 +639,8 @@ impl UserStore {
                 model_redirects: Vec::new(),
                 reasoning_envelope_enabled: true,
                 request_capture_mode: RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -663,6 +674,10 @@ impl UserStore {
 
         let model_limits_json =
             serde_json::to_string(&input.model_limits).map_err(|e| e.to_string())?;
+        let allow_models_json =
+            serde_json::to_string(&input.allow_models).map_err(|e| e.to_string())?;
+        let deny_models_json =
+            serde_json::to_string(&input.deny_models).map_err(|e| e.to_string())?;
         let ip_whitelist_json =
             serde_json::to_string(&input.ip_whitelist).map_err(|e| e.to_string())?;
         let allowed_groups_json = serialize_allowed_groups_json(&allowed_groups)?;
@@ -671,8 +686,8 @@ impl UserStore {
 
         self.db.write().await
             .execute(self.db.stmt(
-                r#"INSERT INTO api_keys (id, user_id, name, key_prefix, key, key_hash, created_at, expires_at, enabled, sub_account_enabled, sub_account_balance_nano, model_limits_enabled, model_limits, ip_whitelist, allowed_groups, token_group, max_multiplier, transforms, model_redirects, reasoning_envelope_enabled, request_capture_enabled, request_capture_mode)
-                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 1, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21)"#,
+                r#"INSERT INTO api_keys (id, user_id, name, key_prefix, key, key_hash, created_at, expires_at, enabled, sub_account_enabled, sub_account_balance_nano, model_limits_enabled, model_limits, ip_whitelist, allowed_groups, token_group, max_multiplier, transforms, model_redirects, reasoning_envelope_enabled, request_capture_enabled, request_capture_mode, response_cache_enabled, max_request_charge_nano_usd, allow_models, deny_models)
+                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 1, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25)"#,
                 vec![
                     id.clone().into(),
                     user_id.into(),
@@ -699,6 +714,10 @@ impl UserStore {
                         0
                     })),
                     input.request_capture_mode.as_str().into(),
+                    SeaValue::Int(Some(if input.response_cache_enabled { 1 } else { 0 })),
+                    SeaValue::BigInt(input.max_request_charge_nano_usd.filter(|v| *v > 0)),
+                    allow_models_json.into(),
+                    deny_models_json.into(),
                 ],
             ))
             .await
@@ -719,6 +738,8 @@ impl UserStore {
             sub_account_balance_nano: "0".to_string(),
             model_limits_enabled: input.model_limits_enabled,
             model_limits: input.model_limits,
+            allow_models: input.allow_models,
+            deny_models: input.deny_models,
             ip_whitelist: input.ip_whitelist,
             allowed_groups,
             max_multiplier: input.max_multiplier,
@@ -726,6 +747,8 @@ impl UserStore {
             model_redirects: input.model_redirects,
             reasoning_envelope_enabled: input.reasoning_envelope_enabled,
             request_capture_mode: input.request_capture_mode,
+            response_cache_enabled: input.response_cache_enabled,
+            max_request_charge_nano_usd: input.max_request_charge_nano_usd.filter(|v| *v > 0),
         };
 
         Ok((api_key, key))
@@ -734,7 +757,7 @@ impl UserStore {
     pub async fn get_api_key_by_prefix(&self, prefix: &str) -> Result<Option<ApiKey>, String> {
         let row = self.db.read()
             .query_one(self.db.stmt(
-                "SELECT id, user_id, name, key_prefix, key, key_hash, created_at, expires_at, last_used_at, enabled, sub_account_enabled, sub_account_balance_nano, model_limits_enabled, model_limits, ip_whitelist, allowed_groups, token_group, max_multiplier, transforms, model_redirects, reasoning_envelope_enabled, request_capture_enabled, request_capture_mode FROM api_keys WHERE key_prefix = $1",
+                "SELECT id, user_id, name, key_prefix, key, key_hash, created_at, expires_at, last_used_at, enabled, sub_account_enabled, sub_account_balance_nano, model_limits_enabled, model_limits, ip_whitelist, allowed_groups, token_group, max_multiplier, transforms, model_redirects, reasoning_envelope_enabled, request_capture_enabled, request_capture_mode, response_cache_enabled, max_request_charge_nano_usd, allow_models, deny_models FROM api_keys WHERE key_prefix = $1",
                 vec![prefix.into()],
             ))
             .await
@@ -750,7 +773,7 @@ impl UserStore {
     pub async fn list_user_api_keys(&self, user_id: &str) -> Result<Vec<ApiKey>, String> {
         let rows = self.db.read()
             .query_all(self.db.stmt(
-                "SELECT id, user_id, name, key_prefix, key, key_hash, created_at, expires_at, last_used_at, enabled, sub_account_enabled, sub_account_balance_nano, model_limits_enabled, model_limits, ip_whitelist, allowed_groups, token_group, max_multiplier, transforms, model_redirects, reasoning_envelope_enabled, request_capture_enabled, request_capture_mode FROM api_keys WHERE user_id = $1 ORDER BY created_at DESC",
+                "SELECT id, user_id, name, key_prefix, key, key_hash, created_at, expires_at, last_used_at, enabled, sub_account_enabled, sub_account_balance_nano, model_limits_enabled, model_limits, ip_whitelist, allowed_groups, token_group, max_multiplier, transforms, model_redirects, reasoning_envelope_enabled, request_capture_enabled, request_capture_mode, response_cache_enabled, max_request_charge_nano_usd, allow_models, deny_models FROM api_keys WHERE user_id = $1 ORDER BY created_at DESC",
                 vec![user_id.into()],
             ))
             .await
@@ -925,6 +948,15 @@ impl UserStore {
             .unwrap_or_else(|_| "[]".to_string());
         let model_limits: Vec<String> = serde_json::from_str(&model_limits_str).unwrap_or_default();
 
+        let allow_models_str: String = row
+            .try_get("", "allow_models")
+            .unwrap_or_else(|_| "[]".to_string());
+        let allow_models: Vec<String> = serde_json::from_str(&allow_models_str).unwrap_or_default();
+        let deny_models_str: String = row
+            .try_get("", "deny_models")
+            .unwrap_or_else(|_| "[]".to_string());
+        let deny_models: Vec<String> = serde_json::from_str(&deny_models_str).unwrap_or_default();
+
         let ip_whitelist_str: String = row
             .try_get("", "ip_whitelist")
             .unwrap_or_else(|_| "[]".to_string());
@@ -968,6 +1000,10 @@ impl UserStore {
                     RequestCaptureMode::Off
                 }
             });
+        let response_cache_enabled: i32 = row.try_get("", "response_cache_enabled").unwrap_or(0);
+        let max_request_charge_nano_usd: Option<i64> = row
+            .try_get("", "max_request_charge_nano_usd")
+            .unwrap_or(None);
 
         Ok(ApiKey {
             id: row.try_get("", "id").map_err(|e| e.to_string())?,
@@ -992,6 +1028,8 @@ impl UserStore {
             sub_account_balance_nano,
             model_limits_enabled: model_limits_enabled == 1,
             model_limits,
+            allow_models,
+            deny_models,
             ip_whitelist,
             allowed_groups,
             max_multiplier,
@@ -999,6 +1037,8 @@ impl UserStore {
             model_redirects,
             reasoning_envelope_enabled: reasoning_envelope_enabled == 1,
             request_capture_mode,
+            response_cache_enabled: response_cache_enabled == 1,
+            max_request_charge_nano_usd,
         })
     }
 
@@ -1111,6 +1151,24 @@ impl UserStore {
             );
             idx += 1;
         }
+        if let Some(allow_models) = &input.allow_models {
+            set_clauses.push(format!("allow_models = ${idx}"));
+            values.push(
+                serde_json::to_string(allow_models)
+                    .map_err(|e| e.to_string())?
+                    .into(),
+            );
+            idx += 1;
+        }
+        if let Some(deny_models) = &input.deny_models {
+            set_clauses.push(format!("deny_models = ${idx}"));
+            values.push(
+                serde_json::to_string(deny_models)
+                    .map_err(|e| e.to_string())?
+                    .into(),
+            );
+            idx += 1;
+        }
         if let Some(ip_whitelist) = &input.ip_whitelist {
             set_clauses.push(format!("ip_whitelist = ${idx}"));
             values.push(
@@ -1173,6 +1231,22 @@ impl UserStore {
             values.push(request_capture_mode.as_str().into());
             idx += 1;
         }
+        if let Some(response_cache_enabled) = input.response_cache_enabled {
+            set_clauses.push(format!("response_cache_enabled = ${idx}"));
+            values.push(SeaValue::Int(Some(if response_cache_enabled {
+                1
+            } else {
+                0
+            })));
+            idx += 1;
+        }
+        if let Some(max_request_charge_nano_usd) = input.max_request_charge_nano_usd {
+            set_clauses.push(format!("max_request_charge_nano_usd = ${idx}"));
+            values.push(SeaValue::BigInt(
+                (max_request_charge_nano_usd > 0).then_some(max_request_charge_nano_usd),
+            ));
+            idx += 1;
+        }
         if let Some(expires_at) = &input.expires_at {
             set_clauses.push(format!("expires_at = ${idx}"));
             values.push(expires_at.clone().into());
@@ -1215,7 +1289,7 @@ impl UserStore {
     pub async fn get_api_key_by_id(&self, id: &str) -> Result<Option<ApiKey>, String> {
         let row = self.db.read()
             .query_one(self.db.stmt(
-                "SELECT id, user_id, name, key_prefix, key, key_hash, created_at, expires_at, last_used_at, enabled, sub_account_enabled, sub_account_balance_nano, model_limits_enabled, model_limits, ip_whitelist, allowed_groups, token_group, max_multiplier, transforms, model_redirects, reasoning_envelope_enabled, request_capture_enabled, request_capture_mode FROM api_keys WHERE id = $1",
+                "SELECT id, user_id, name, key_prefix, key, key_hash, created_at, expires_at, last_used_at, enabled, sub_account_enabled, sub_account_balance_nano, model_limits_enabled, model_limits, ip_whitelist, allowed_groups, token_group, max_multiplier, transforms, model_redirects, reasoning_envelope_enabled, request_capture_enabled, request_capture_mode, response_cache_enabled, max_request_charge_nano_usd, allow_models, deny_models FROM api_keys WHERE id = $1",
                 vec![id.into()],
             ))
             .await
@@ -1289,7 +1363,13 @@ impl UserStore {
         Ok(Some(balance))
     }
 
-    pub async fn ensure_user_can_spend(&self, user_id: &str) -> Result<(), BillingError> {
+    /// Fails unless the user's balance, minus held amounts, is positive and
+    /// covers `min_amount_nano_usd`.
+    pub async fn ensure_user_can_spend(
+        &self,
+        user_id: &str,
+        min_amount_nano_usd: i128,
+    ) -> Result<(), BillingError> {
         let Some(balance) = self
             .get_user_balance(user_id)
             .await
@@ -1304,7 +1384,8 @@ impl UserStore {
         if balance.balance_unlimited {
             return Ok(());
         }
-        if balance.balance_nano_usd <= 0 {
+        let available = balance.balance_nano_usd - self.balance_holds.held(user_id);
+        if available <= 0 || available < min_amount_nano_usd {
             return Err(BillingError::new(
                 BillingErrorKind::InsufficientBalance,
                 "insufficient balance",
@@ -1313,6 +1394,77 @@ impl UserStore {
         Ok(())
     }
 
+    /// Reserves `amount_nano_usd` of the user's balance until the returned
+    /// hold is released or dropped. Unlimited users and non-positive amounts
+    /// need no hold and yield `None`.
+    pub async fn place_hold(
+        &self,
+        user_id: &str,
+        amount_nano_usd: i128,
+    ) -> Result<Option<BalanceHold>, BillingError> {
+        if amount_nano_usd <= 0 {
+            return Ok(None);
+        }
+        let Some(balance) = self
+            .get_user_balance(user_id)
+            .await
+            .map_err(|e| BillingError::new(BillingErrorKind::Internal, e))?
+        else {
+            return Err(BillingError::new(
+                BillingErrorKind::NotFound,
+                "user not found",
+            ));
+        };
+        if balance.balance_unlimited {
+            return Ok(None);
+        }
+        self.balance_holds
+            .try_hold(user_id, balance.balance_nano_usd, amount_nano_usd)
+            .map(Some)
+            .ok_or_else(|| {
+                BillingError::new(
+                    BillingErrorKind::InsufficientBalance,
+                    "insufficient balance",
+                )
+            })
+    }
+
+    /// Like [`Self::place_hold`], against an API key's sub-account balance.
+    pub async fn place_sub_account_hold(
+        &self,
+        api_key_id: &str,
+        amount_nano_usd: i128,
+    ) -> Result<Option<BalanceHold>, BillingError> {
+        if amount_nano_usd <= 0 {
+            return Ok(None);
+        }
+        let key = self
+            .get_api_key_by_id(api_key_id)
+            .await
+            .map_err(|e| BillingError::new(BillingErrorKind::Internal, e))?
+            .ok_or_else(|| BillingError::new(BillingErrorKind::NotFound, "api key not found"))?;
+        let balance = parse_nano_usd(&key.sub_account_balance_nano)
+            .map_err(|e| BillingError::new(BillingErrorKind::InvalidStoredBalance, e))?;
+        self.balance_holds
+            .try_hold(&sub_account_hold_key(api_key_id), balance, amount_nano_usd)
+            .map(Some)
+            .ok_or_else(|| {
+                BillingError::new(
+                    BillingErrorKind::InsufficientBalance,
+                    "insufficient balance",
+                )
+            })
+    }
+
+    pub fn release_hold(&self, hold: BalanceHold) {
+        drop(hold);
+    }
+
+    /// Total currently held against the user's own balance.
+    pub fn held_balance_nano(&self, user_id: &str) -> i128 {
+        self.balance_holds.held(user_id)
+    }
+
     pub async fn charge_user_balance_nano(
         &self,
         user_id: &str,
@@ -1688,7 +1840,11 @@ impl UserStore {
         Ok((new_key_balance, new_user_balance))
     }
 
-    pub async fn ensure_sub_account_can_spend(&self, api_key_id: &str) -> Result<(), BillingError> {
+    pub async fn ensure_sub_account_can_spend(
+        &self,
+        api_key_id: &str,
+        min_amount_nano_usd: i128,
+    ) -> Result<(), BillingError> {
         let key = self
             .get_api_key_by_id(api_key_id)
             .await
@@ -1696,7 +1852,8 @@ impl UserStore {
             .ok_or_else(|| BillingError::new(BillingErrorKind::NotFound, "api key not found"))?;
         let balance = parse_nano_usd(&key.sub_account_balance_nano)
             .map_err(|e| BillingError::new(BillingErrorKind::InvalidStoredBalance, e))?;
-        if balance <= 0 {
+        let available = balance - self.balance_holds.held(&sub_account_hold_key(api_key_id));
+        if available <= 0 || available < min_amount_nano_usd {
             return Err(BillingError::new(
                 BillingErrorKind::InsufficientBalance,
                 "insufficient balance",
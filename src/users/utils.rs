@@ -86,3 +86,59 @@ pub fn format_nano_to_usd(nano: i128) -> String {
         format!("{whole}.{frac_str}")
     }
 }
+
+const NANO_SCALE: i128 = 1_000_000_000;
+const ATTO_SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// Converts a configured price multiplier to a nano-scaled integer (1.0 ->
+/// 1_000_000_000). The value is rounded half-to-even at 18 decimal places
+/// from the exact binary value of the `f64`, then truncated to 9 places, so
+/// that float noise such as `1.1 == 1.100000000000000088…` never bills an
+/// extra nano. Returns `None` for negative, non-finite, or overflowing input.
+pub fn multiplier_to_nano(multiplier: f64) -> Option<i128> {
+    if !multiplier.is_finite() || multiplier < 0.0 {
+        return None;
+    }
+    let bits = multiplier.to_bits();
+    let biased_exp = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = u128::from(bits & ((1u64 << 52) - 1));
+    // multiplier == mantissa * 2^exp exactly.
+    let (mantissa, exp) = if biased_exp == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | (1u128 << 52), biased_exp - 1075)
+    };
+    // mantissa < 2^53 and ATTO_SCALE < 2^60, so this cannot overflow.
+    let scaled = mantissa * ATTO_SCALE;
+    let atto = if exp >= 0 {
+        let shift = exp as u32;
+        if shift >= scaled.leading_zeros() {
+            return None;
+        }
+        scaled << shift
+    } else {
+        let shift = exp.unsigned_abs();
+        if shift > 113 {
+            // scaled < 2^113, so the quotient rounds to zero.
+            0
+        } else {
+            let quotient = scaled >> shift;
+            let remainder = scaled & ((1u128 << shift) - 1);
+            let half = 1u128 << (shift - 1);
+            if remainder > half || (remainder == half && quotient & 1 == 1) {
+                quotient + 1
+            } else {
+                quotient
+            }
+        }
+    };
+    i128::try_from(atto / NANO_SCALE as u128).ok()
+}
+
+/// Applies a nano-scaled multiplier (see [`multiplier_to_nano`]) to a nano
+/// USD amount, truncating toward zero.
+pub fn scale_nano_by_multiplier(base_nano: i128, multiplier_nano: i128) -> Option<i128> {
+    base_nano
+        .checked_mul(multiplier_nano)?
+        .checked_div(NANO_SCALE)
+}
@@ -29,3 +29,6 @@ mod streaming_messages;
 
 #[path = "api/request_capture.rs"]
 mod request_capture;
+
+#[path = "api/transform_preview.rs"]
+mod transform_preview;
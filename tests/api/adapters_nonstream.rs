@@ -250,3 +250,111 @@ async fn responses_nonstream_usage_preserves_nested_unknown_details() {
     assert!(response["usage"].get("vendor_input_detail").is_none());
     assert!(response["usage"].get("vendor_output_detail").is_none());
 }
+
+#[tokio::test]
+async fn gemini_content_filter_maps_to_each_downstream_nonstream() {
+    let ctx = setup().await;
+    for mode in ["gemini_safety_block", "gemini_prompt_blocked"] {
+        let (status, body) = json_post(
+            &ctx,
+            "/v1/chat/completions",
+            json!({
+                "model": "gemini-2.5-flash",
+                "messages": [{ "role": "user", "content": "blocked" }],
+                "native_response_mode": mode
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{mode}: {body}");
+        let chat: Value = serde_json::from_str(&body).expect("chat JSON");
+        assert_eq!(chat["choices"][0]["finish_reason"], json!("content_filter"));
+        assert!(
+            chat["choices"][0]["message"]["refusal"]
+                .as_str()
+                .is_some_and(|refusal| refusal.contains("blocked by Gemini")),
+            "{mode}: {body}"
+        );
+
+        let (status, body) = json_post(
+            &ctx,
+            "/v1/messages",
+            json!({
+                "model": "gemini-2.5-flash",
+                "max_tokens": 64,
+                "messages": [{ "role": "user", "content": "blocked" }],
+                "native_response_mode": mode
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{mode}: {body}");
+        let messages: Value = serde_json::from_str(&body).expect("messages JSON");
+        assert_eq!(messages["stop_reason"], json!("refusal"));
+
+        let (status, body) = json_post(
+            &ctx,
+            "/v1/responses",
+            json!({
+                "model": "gemini-2.5-flash",
+                "input": "blocked",
+                "native_response_mode": mode
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{mode}: {body}");
+        let responses: Value = serde_json::from_str(&body).expect("responses JSON");
+        assert_eq!(responses["status"], json!("incomplete"));
+        assert_eq!(
+            responses["incomplete_details"]["reason"],
+            json!("content_filter")
+        );
+    }
+}
+
+#[tokio::test]
+async fn strict_validation_mode_rejects_malformed_requests_with_every_violation() {
+    let ctx = setup().await;
+    let body = json!({
+        "model": "gpt-5-mini-chat",
+        "messages": [
+            { "role": "user", "content": [
+                { "type": "image_url", "image_url": { "url": "ftp://example.com/cat.png" } }
+            ] },
+            { "role": "tool", "tool_call_id": "call_missing", "content": "orphan" }
+        ]
+    });
+
+    let (status, _) = json_post(&ctx, "/v1/chat/completions", body.clone()).await;
+    assert_eq!(status, StatusCode::OK, "validation is off by default");
+
+    ctx.state.monoize_runtime.write().await.validate_requests = true;
+    let (status, response) = json_post(&ctx, "/v1/chat/completions", body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let response: Value = serde_json::from_str(&response).expect("error json");
+    assert_eq!(response["error"]["code"], "request_validation_failed");
+    let violations = response["error"]["details"]["violations"]
+        .as_array()
+        .expect("violations list");
+    assert_eq!(violations.len(), 2, "{response}");
+    assert_eq!(violations[0]["path"], "input[0]");
+    assert_eq!(
+        violations[1]["message"],
+        "tool result `call_missing` has no preceding tool call"
+    );
+
+    let (status, response) = json_post(
+        &ctx,
+        "/v1/messages",
+        json!({
+            "model": "gpt-5-mini-msg",
+            "max_tokens": 16,
+            "messages": [{ "role": "assistant", "content": "prefill only" }]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let response: Value = serde_json::from_str(&response).expect("error json");
+    assert_eq!(
+        response["error"]["details"]["violations"][0]["message"],
+        "must contain a user message or a tool result"
+    );
+}
@@ -669,6 +669,7 @@ async fn responses_nonstream_markdown_image_transforms_extract_and_append_markdo
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: None,
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     ctx.state
@@ -683,7 +684,12 @@ async fn responses_nonstream_markdown_image_transforms_extract_and_append_markdo
                 provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -723,6 +729,9 @@ async fn responses_nonstream_markdown_image_transforms_extract_and_append_markdo
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-1),
         })
@@ -786,3 +786,107 @@ async fn responses_context_management_and_compaction_item_round_trip_same_family
     );
     assert_eq!(response["output"][1]["type"], json!("message"));
 }
+
+#[tokio::test]
+async fn responses_previous_response_id_passes_through_to_responses_upstream() {
+    let ctx = setup().await;
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({
+            "model": "gpt-5-mini",
+            "previous_response_id": "resp_prev_1",
+            "input": "continue"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let upstream = last_captured_body(&ctx, "responses");
+    assert_eq!(upstream["previous_response_id"], json!("resp_prev_1"));
+}
+
+#[tokio::test]
+async fn responses_previous_response_id_skips_non_responses_attempts() {
+    let ctx = setup().await;
+    let (upstream_addr, _, captured_bodies) = start_upstream().await;
+    let base_url = format!("http://{upstream_addr}");
+    seed_test_model_pricing(&ctx.state, &["chained-fallback"]).await;
+    create_test_provider(
+        &ctx.state,
+        "chained-chat",
+        monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
+        "chained-fallback",
+        &base_url,
+        "upstream-key",
+    )
+    .await;
+    create_test_provider(
+        &ctx.state,
+        "chained-responses",
+        monoize::monoize_routing::MonoizeProviderType::Responses,
+        "chained-fallback",
+        &base_url,
+        "upstream-key",
+    )
+    .await;
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({
+            "model": "chained-fallback",
+            "previous_response_id": "resp_prev_1",
+            "input": "continue"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let captured = captured_bodies.lock().expect("captured bodies lock");
+    assert!(captured.iter().all(|(name, _)| name != "chat"));
+    let upstream = &captured
+        .iter()
+        .find(|(name, _)| name == "responses")
+        .expect("responses upstream called")
+        .1;
+    assert_eq!(upstream["previous_response_id"], json!("resp_prev_1"));
+}
+
+#[tokio::test]
+async fn responses_previous_response_id_rejected_for_non_responses_provider() {
+    let ctx = setup().await;
+    let before = ctx
+        .captured_bodies
+        .lock()
+        .expect("captured bodies lock")
+        .iter()
+        .filter(|(name, _)| name == "chat")
+        .count();
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({
+            "model": "gpt-5-mini-chat",
+            "previous_response_id": "resp_prev_1",
+            "input": "continue"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    let error: Value = serde_json::from_str(&body).expect("chaining error JSON");
+    assert_eq!(
+        error["error"]["code"],
+        json!("conversation_chaining_not_supported")
+    );
+    assert_eq!(
+        error["error"]["message"],
+        json!("conversation chaining not supported for provider chat_completion")
+    );
+    let after = ctx
+        .captured_bodies
+        .lock()
+        .expect("captured bodies lock")
+        .iter()
+        .filter(|(name, _)| name == "chat")
+        .count();
+    assert_eq!(after, before, "chained request reached a chat upstream");
+}
@@ -39,6 +39,9 @@ async fn set_messages_metadata_collision_transform(ctx: &TestContext) {
                 request_timeout_ms_override: None,
                 extra_fields_whitelist: None,
                 strip_cross_protocol_nested_extra: None,
+                debug_log_bodies: None,
+                default_temperature: None,
+                default_top_p: None,
                 groups: None,
                 enabled: None,
                 priority: None,
@@ -210,3 +213,226 @@ async fn messages_metadata_siblings_survive_and_typed_user_wins() {
         json!({ "enabled": true })
     );
 }
+
+#[tokio::test]
+async fn provider_sampling_defaults_fill_only_absent_client_values() {
+    let ctx = setup().await;
+    let provider = ctx
+        .state
+        .monoize_store
+        .list_providers()
+        .await
+        .expect("list providers")
+        .into_iter()
+        .find(|provider| provider.name == "up-chat")
+        .expect("chat provider");
+
+    ctx.state
+        .monoize_store
+        .update_provider(
+            &provider.id,
+            monoize::monoize_routing::UpdateMonoizeProviderInput {
+                name: None,
+                channels: None,
+                max_retries: None,
+                channel_max_retries: None,
+                channel_retry_interval_ms: None,
+                circuit_breaker_enabled: None,
+                per_model_circuit_break: None,
+                transforms: None,
+                active_probe_enabled_override: None,
+                api_type_overrides: None,
+                active_probe_interval_seconds_override: None,
+                active_probe_success_threshold_override: None,
+                active_probe_model_override: None,
+                request_timeout_ms_override: None,
+                extra_fields_whitelist: None,
+                strip_cross_protocol_nested_extra: None,
+                debug_log_bodies: None,
+                default_temperature: Some(Some(0.25)),
+                default_top_p: Some(Some(0.5)),
+                groups: None,
+                enabled: None,
+                priority: None,
+            },
+        )
+        .await
+        .expect("set provider sampling defaults");
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gpt-5-mini-chat",
+            "messages": [{ "role": "user", "content": "defaults" }]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let upstream = last_captured_body(&ctx, "chat");
+    assert_eq!(upstream["temperature"], json!(0.25));
+    assert_eq!(upstream["top_p"], json!(0.5));
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gpt-5-mini-chat",
+            "messages": [{ "role": "user", "content": "client wins" }],
+            "temperature": 1.5
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let upstream = last_captured_body(&ctx, "chat");
+    assert_eq!(upstream["temperature"], json!(1.5));
+    assert_eq!(upstream["top_p"], json!(0.5));
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gpt-5-mini-msg",
+            "max_tokens": 64,
+            "messages": [{ "role": "user", "content": "other provider" }]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let upstream = last_captured_body(&ctx, "messages");
+    assert!(upstream.get("temperature").is_none(), "{upstream}");
+    assert!(upstream.get("top_p").is_none(), "{upstream}");
+}
+
+#[tokio::test]
+async fn developer_role_is_downgraded_only_for_providers_without_one() {
+    let ctx = setup().await;
+    let input = json!([
+        { "type": "message", "role": "developer", "content": "dev rules" },
+        { "type": "message", "role": "user", "content": "hi" },
+        { "type": "message", "role": "developer", "content": "late dev note" },
+        { "type": "message", "role": "user", "content": "again" }
+    ]);
+    let no_developer_role = |upstream: &Value| {
+        !upstream.to_string().contains("\"role\":\"developer\"")
+    };
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({ "model": "gpt-5-mini-msg", "max_output_tokens": 64, "input": input }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let upstream = last_captured_body(&ctx, "messages");
+    let system_texts: Vec<&str> = upstream["system"]
+        .as_array()
+        .expect("messages system blocks")
+        .iter()
+        .filter_map(|block| block["text"].as_str())
+        .collect();
+    assert_eq!(system_texts, vec!["dev rules", "late dev note"]);
+    assert!(no_developer_role(&upstream), "{upstream}");
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({ "model": "gemini-2.5-flash", "input": input }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let upstream = ctx
+        .captured_bodies
+        .lock()
+        .expect("captured bodies lock")
+        .iter()
+        .rev()
+        .find(|(name, _)| name.starts_with("gemini:"))
+        .map(|(_, body)| body.clone())
+        .expect("gemini upstream body");
+    assert_eq!(
+        upstream["systemInstruction"]["parts"],
+        json!([{ "text": "dev rules" }, { "text": "late dev note" }])
+    );
+    assert!(no_developer_role(&upstream), "{upstream}");
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gpt-5-mini-chat",
+            "messages": [
+                { "role": "developer", "content": "dev rules" },
+                { "role": "user", "content": "hi" }
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let upstream = last_captured_body(&ctx, "chat");
+    assert_eq!(upstream["messages"][0]["role"], json!("developer"));
+
+    let provider = ctx
+        .state
+        .monoize_store
+        .list_providers()
+        .await
+        .expect("list providers")
+        .into_iter()
+        .find(|provider| provider.name == "up-chat")
+        .expect("chat provider");
+    ctx.state
+        .monoize_store
+        .update_provider(
+            &provider.id,
+            monoize::monoize_routing::UpdateMonoizeProviderInput {
+                name: None,
+                channels: None,
+                max_retries: None,
+                channel_max_retries: None,
+                channel_retry_interval_ms: None,
+                circuit_breaker_enabled: None,
+                per_model_circuit_break: None,
+                transforms: Some(vec![monoize::transforms::TransformRuleConfig {
+                    transform: "developer_to_system_role".to_string(),
+                    enabled: true,
+                    models: None,
+                    phase: monoize::transforms::Phase::Request,
+                    config: json!({}),
+                }]),
+                active_probe_enabled_override: None,
+                api_type_overrides: None,
+                active_probe_interval_seconds_override: None,
+                active_probe_success_threshold_override: None,
+                active_probe_model_override: None,
+                request_timeout_ms_override: None,
+                extra_fields_whitelist: None,
+                strip_cross_protocol_nested_extra: None,
+                debug_log_bodies: None,
+                default_temperature: None,
+                default_top_p: None,
+                groups: None,
+                enabled: None,
+                priority: None,
+            },
+        )
+        .await
+        .expect("install developer_to_system_role");
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gpt-5-mini-chat",
+            "messages": [
+                { "role": "developer", "content": "dev rules" },
+                { "role": "user", "content": "hi" }
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let upstream = last_captured_body(&ctx, "chat");
+    assert_eq!(upstream["messages"][0]["role"], json!("system"));
+    assert!(no_developer_role(&upstream), "{upstream}");
+}
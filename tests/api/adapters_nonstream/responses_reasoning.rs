@@ -299,6 +299,8 @@ async fn responses_reasoning_envelope_can_be_disabled_per_api_key() {
                 sub_account_enabled: None,
                 model_limits_enabled: None,
                 model_limits: None,
+                allow_models: None,
+                deny_models: None,
                 ip_whitelist: None,
                 allowed_groups: None,
                 max_multiplier: None,
@@ -306,6 +308,8 @@ async fn responses_reasoning_envelope_can_be_disabled_per_api_key() {
                 model_redirects: None,
                 reasoning_envelope_enabled: Some(false),
                 request_capture_mode: None,
+                response_cache_enabled: None,
+                max_request_charge_nano_usd: None,
                 expires_at: None,
             },
             false,
@@ -374,6 +378,7 @@ async fn responses_nonstream_collects_completed_snapshot_image_generation_result
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: Some("gpt-5-mini".to_string()),
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     ctx.state
@@ -391,7 +396,12 @@ async fn responses_nonstream_collects_completed_snapshot_image_generation_result
                 provider_type: monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -426,6 +436,9 @@ async fn responses_nonstream_collects_completed_snapshot_image_generation_result
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-1),
         })
@@ -401,6 +401,8 @@ async fn sub_account_zero_balance_returns_402() {
                 sub_account_enabled: true,
                 model_limits_enabled: false,
                 model_limits: vec![],
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
                 ip_whitelist: Vec::new(),
 
                 allowed_groups: Vec::new(),
@@ -409,6 +411,8 @@ async fn sub_account_zero_balance_returns_402() {
                 model_redirects: Vec::new(),
                 reasoning_envelope_enabled: true,
                 request_capture_mode: monoize::users::RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -452,6 +456,8 @@ async fn ip_whitelist_blocks_non_whitelisted() {
                 sub_account_enabled: false,
                 model_limits_enabled: false,
                 model_limits: vec![],
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
                 ip_whitelist: vec!["192.168.1.1".to_string()],
 
                 allowed_groups: Vec::new(),
@@ -460,6 +466,8 @@ async fn ip_whitelist_blocks_non_whitelisted() {
                 model_redirects: Vec::new(),
                 reasoning_envelope_enabled: true,
                 request_capture_mode: monoize::users::RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
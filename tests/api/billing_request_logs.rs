@@ -143,6 +143,62 @@ async fn chat_streaming_requests_upstream_include_usage_by_default() {
     assert!(log.timing.ttfb_ms.is_some());
 }
 
+#[tokio::test]
+async fn chat_streaming_without_usage_bills_tiktoken_estimate_when_selected() {
+    let ctx = setup().await;
+    ctx.state.monoize_runtime.write().await.token_estimator =
+        monoize::token_estimator::TokenEstimatorKind::Tiktoken;
+    let req = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, ctx.auth_header.clone())
+        .body(Body::from(
+            json!({
+                "model":"gpt-5-mini-chat",
+                "messages":[{"role":"user","content":"お誕生日おめでとう"}],
+                "stream": true,
+                "suppress_usage": true
+            })
+            .to_string(),
+        ))
+        .unwrap();
+
+    let resp = ctx.router.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let _ = resp.into_body().collect().await.unwrap().to_bytes();
+
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("query user")
+        .expect("user exists");
+
+    let mut matched = None;
+    for _ in 0..20 {
+        ctx.state.user_store.flush_all_batchers().await;
+        let (logs, _, _) = ctx
+            .state
+            .user_store
+            .list_request_logs_by_user(&user.id, 100, 0, None, None, None, None, None, None)
+            .await
+            .expect("list request logs");
+        matched = logs
+            .into_iter()
+            .find(|log| log.model == "gpt-5-mini-chat" && log.is_stream);
+        if matched.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let log = matched.expect("request log should be inserted");
+    // The echoed reply is 8 o200k_base tokens; the byte heuristic would bill 7.
+    assert_eq!(log.tokens.output, Some(8));
+}
+
 #[tokio::test]
 async fn chat_streaming_downstream_disconnect_still_drains_final_usage_and_bills() {
     let ctx = setup().await;
@@ -434,6 +490,172 @@ async fn request_logs_pending_transitions_to_success_and_charges_once() {
     assert_eq!(before_nano - after_nano, 20000);
 }
 
+async fn set_finite_balance(ctx: &TestContext, balance_nano: &str) -> String {
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("query user")
+        .expect("user exists");
+    ctx.state
+        .user_store
+        .update_user(
+            &user.id,
+            None,
+            None,
+            None,
+            None,
+            Some(balance_nano),
+            Some(false),
+            None,
+            None,
+        )
+        .await
+        .expect("set finite balance");
+    user.id
+}
+
+async fn user_balance_nano(ctx: &TestContext) -> i128 {
+    ctx.state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("query user")
+        .expect("user exists")
+        .balance_nano_usd
+        .parse()
+        .expect("parse balance")
+}
+
+fn chat_stream_request(auth_header: String, body: Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, auth_header)
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn chat_streaming_balance_hold_settles_to_actual_charge() {
+    let ctx = setup().await;
+    let user_id = set_finite_balance(&ctx, "100000").await;
+
+    let router = ctx.router.clone();
+    let auth_header = ctx.auth_header.clone();
+    let request_task = tokio::spawn(async move {
+        let resp = router
+            .oneshot(chat_stream_request(
+                auth_header,
+                json!({
+                    "model":"gpt-5-mini-chat",
+                    "messages":[{"role":"user","content":"hold-settle"}],
+                    "stream": true,
+                    "max_tokens": 50,
+                    "emit_usage": true,
+                    "force_upstream_delay_ms": 800
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let _ = resp.into_body().collect().await.unwrap().to_bytes();
+    });
+
+    let mut held = 0;
+    for _ in 0..50 {
+        held = ctx.state.user_store.held_balance_nano(&user_id);
+        if held > 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(
+        held >= 50_000,
+        "in-flight stream should hold its max_output_tokens budget, held {held}"
+    );
+
+    request_task.await.expect("request task");
+    assert_eq!(ctx.state.user_store.held_balance_nano(&user_id), 0);
+    assert_eq!(user_balance_nano(&ctx).await, 80_000);
+
+    let resp = ctx
+        .router
+        .clone()
+        .oneshot(chat_stream_request(
+            ctx.auth_header.clone(),
+            json!({
+                "model":"gpt-5-mini-chat",
+                "messages":[{"role":"user","content":"hold-reject"}],
+                "stream": true,
+                "max_tokens": 1000
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::PAYMENT_REQUIRED);
+    assert_eq!(ctx.state.user_store.held_balance_nano(&user_id), 0);
+    assert_eq!(user_balance_nano(&ctx).await, 80_000);
+}
+
+#[tokio::test]
+async fn chat_streaming_balance_hold_is_released_on_upstream_error() {
+    let ctx = setup().await;
+    let user_id = set_finite_balance(&ctx, "100000").await;
+
+    let resp = ctx
+        .router
+        .clone()
+        .oneshot(chat_stream_request(
+            ctx.auth_header.clone(),
+            json!({
+                "model":"gpt-5-mini-chat",
+                "messages":[{"role":"user","content":"force stream error"}],
+                "stream": true,
+                "max_tokens": 50,
+                "force_upstream_error_status": 400,
+                "force_upstream_error_code": "cyber_policy",
+                "force_upstream_error_message": "mock cybersecurity policy block"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let _ = resp.into_body().collect().await.unwrap().to_bytes();
+
+    assert_eq!(ctx.state.user_store.held_balance_nano(&user_id), 0);
+    assert_eq!(user_balance_nano(&ctx).await, 100_000);
+}
+
+#[tokio::test]
+async fn balance_precheck_rejects_output_budgets_the_balance_cannot_cover() {
+    let ctx = setup().await;
+    set_finite_balance(&ctx, "100000").await;
+    let body = |max_tokens: u64, n: u64| {
+        json!({
+            "model":"gpt-5-mini-chat",
+            "messages":[{"role":"user","content":"n-guard"}],
+            "max_tokens": max_tokens,
+            "n": n
+        })
+    };
+
+    let (status, response) = json_post(&ctx, "/v1/chat/completions", body(50, 1)).await;
+    assert_eq!(status, StatusCode::OK, "{response}");
+
+    let balance = user_balance_nano(&ctx).await;
+    let (status, response) = json_post(&ctx, "/v1/chat/completions", body(4000, 1)).await;
+    assert_eq!(status, StatusCode::PAYMENT_REQUIRED);
+    assert!(response.contains("insufficient_balance"), "{response}");
+
+    // Chat still accepts only one choice, so a high `n` never reaches billing.
+    let (status, _) = json_post(&ctx, "/v1/chat/completions", body(50, 5)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(user_balance_nano(&ctx).await, balance);
+}
+
 #[tokio::test]
 async fn request_logs_pending_usage_can_be_updated_incrementally() {
     // With the batcher pattern, insert_request_log_pending and
@@ -547,6 +769,7 @@ async fn request_log_batcher_broadcasts_immediately_before_flush() {
         request_ip: Some("127.0.0.1".to_string()),
         reasoning_effort: None,
         tried_providers_json: None,
+        metadata_json: None,
         effective_provider_type: None,
         affinity_hit: None,
         affinity_key_hash: None,
@@ -582,6 +805,80 @@ async fn request_log_batcher_broadcasts_immediately_before_flush() {
     );
 }
 
+#[tokio::test]
+async fn request_metadata_header_round_trips_into_request_log() {
+    let ctx = setup().await;
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("query user")
+        .expect("user exists");
+
+    for (request_id, metadata) in [
+        (
+            "metadata-tagged",
+            r#"{"trace_id":"trace-1","session_id":"sess-9"}"#,
+        ),
+        ("metadata-invalid", "{not json"),
+    ] {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/chat/completions")
+            .header(CONTENT_TYPE, "application/json")
+            .header(AUTHORIZATION, ctx.auth_header.clone())
+            .header("x-request-id", request_id)
+            .header("x-monoize-metadata", metadata)
+            .body(Body::from(
+                json!({
+                    "model": "gpt-5-mini-chat",
+                    "messages": [{ "role": "user", "content": "hi" }]
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let resp = ctx.router.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    let mut logs = Vec::new();
+    for _ in 0..20 {
+        ctx.state.user_store.flush_all_batchers().await;
+        logs = ctx
+            .state
+            .user_store
+            .list_request_logs_by_user(&user.id, 100, 0, None, None, None, None, None, None)
+            .await
+            .expect("list request logs")
+            .0;
+        if logs
+            .iter()
+            .filter(|log| {
+                log.request_id
+                    .as_deref()
+                    .is_some_and(|id| id.starts_with("metadata-"))
+            })
+            .count()
+            == 2
+        {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let find = |request_id: &str| {
+        logs.iter()
+            .find(|log| log.request_id.as_deref() == Some(request_id))
+            .expect("request log should be inserted")
+    };
+    assert_eq!(
+        find("metadata-tagged").metadata,
+        Some(json!({ "trace_id": "trace-1", "session_id": "sess-9" }))
+    );
+    assert_eq!(find("metadata-invalid").metadata, None);
+}
+
 #[tokio::test]
 async fn chat_upstream_error_is_logged_and_not_billed() {
     let ctx = setup().await;
@@ -953,6 +1250,75 @@ async fn responses_streaming_response_failed_is_logged_as_error_and_not_billed()
     assert_eq!(user_before.balance_nano_usd, user_after.balance_nano_usd);
 }
 
+#[tokio::test]
+async fn messages_streaming_mid_stream_error_logs_partial_usage_without_charge() {
+    let ctx = setup().await;
+    let req = Request::builder()
+        .method("POST")
+        .uri("/v1/messages")
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, ctx.auth_header.clone())
+        .body(Body::from(
+            json!({
+                "model":"gpt-5-mini-msg",
+                "max_tokens": 64,
+                "messages":[{"role":"user","content":[{"type":"text","text":"stream"}]}],
+                "stream": true,
+                "stream_mode": "messages_mid_error"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let resp = ctx.router.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let _ = resp.into_body().collect().await.unwrap().to_bytes();
+
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("query user")
+        .expect("user exists");
+    let mut matched = None;
+    for _ in 0..20 {
+        ctx.state.user_store.flush_all_batchers().await;
+        let (logs, _, _) = ctx
+            .state
+            .user_store
+            .list_request_logs_by_user(
+                &user.id,
+                100,
+                0,
+                Some("gpt-5-mini-msg"),
+                Some("error"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .expect("list request logs");
+        matched = logs
+            .into_iter()
+            .find(|log| log.error.code.as_deref() == Some("overloaded_error"));
+        if matched.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let log = matched.expect("mid-stream error request log should be inserted");
+    assert_eq!(log.status, "error");
+    assert!(log.is_stream);
+    assert_eq!(log.billing.charge_nano_usd, None);
+    assert_eq!(log.tokens.input, Some(7));
+    assert_eq!(
+        log.error.message.as_deref(),
+        Some("mock mid-stream overload")
+    );
+}
+
 #[tokio::test]
 async fn messages_streaming_upstream_error_is_logged_as_error_and_not_billed() {
     let ctx = setup().await;
@@ -1101,6 +1467,7 @@ async fn request_log_retention_deletes_only_rows_older_than_ninety_days() {
             request_ip: None,
             reasoning_effort: None,
             tried_providers_json: None,
+            metadata_json: None,
             effective_provider_type: None,
             affinity_hit: None,
             affinity_key_hash: None,
@@ -1148,6 +1515,7 @@ async fn request_log_retention_deletes_only_rows_older_than_ninety_days() {
             request_ip: None,
             reasoning_effort: None,
             tried_providers_json: None,
+            metadata_json: None,
             effective_provider_type: None,
             affinity_hit: None,
             affinity_key_hash: None,
@@ -1402,6 +1770,7 @@ async fn billing_model_field_does_not_affect_upstream_charge() {
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: Some("gpt-5-mini".to_string()),
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     ctx.state
@@ -1416,7 +1785,12 @@ async fn billing_model_field_does_not_affect_upstream_charge() {
                 provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -1441,6 +1815,9 @@ async fn billing_model_field_does_not_affect_upstream_charge() {
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-50),
         })
@@ -1459,6 +1836,8 @@ async fn billing_model_field_does_not_affect_upstream_charge() {
                 cache_read_input_cost_per_token_nano: None,
                 cache_creation_input_cost_per_token_nano: None,
                 output_cost_per_reasoning_token_nano: None,
+                image_cost_per_image_nano: None,
+                tts_cost_per_char_nano: None,
                 max_input_tokens: None,
                 max_output_tokens: None,
                 max_tokens: None,
@@ -1526,7 +1905,7 @@ async fn billing_model_field_does_not_affect_upstream_charge() {
 }
 
 #[tokio::test]
-async fn redirected_model_pricing_falls_back_to_logical_model_when_upstream_unpriced() {
+async fn max_multiplier_gate_uses_the_billed_multiplier_of_a_redirected_entry() {
     let ctx = setup().await;
 
     let providers = ctx
@@ -1540,12 +1919,133 @@ async fn redirected_model_pricing_falls_back_to_logical_model_when_upstream_unpr
         .find_map(|p| p.channels.first().map(|c| c.base_url.clone()))
         .expect("base_url");
 
+    // The alias is billed at its own 3x multiplier even though the redirect
+    // target has a cheaper entry on the same channel.
     let mut models = HashMap::new();
     models.insert(
-        "alias-fallback-model".to_string(),
+        "premium-alias-model".to_string(),
         monoize::monoize_routing::MonoizeModelEntry {
-            redirect: Some("gpt-5-unpriced-upstream".to_string()),
+            redirect: Some("gpt-5-mini".to_string()),
+            multiplier: 3.0,
+            supports_streaming: None,
+        },
+    );
+    models.insert(
+        "gpt-5-mini".to_string(),
+        monoize::monoize_routing::MonoizeModelEntry {
+            redirect: None,
+            multiplier: 0.5,
+            supports_streaming: None,
+        },
+    );
+    ctx.state
+        .monoize_store
+        .create_provider(monoize::monoize_routing::CreateMonoizeProviderInput {
+            name: "premium-alias-provider".to_string(),
+            api_type_overrides: Vec::new(),
+            groups: Vec::new(),
+            channels: vec![monoize::monoize_routing::CreateMonoizeChannelInput {
+                id: Some("premium-alias-ch".to_string()),
+                name: "premium-alias-ch".to_string(),
+                provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
+                base_url,
+                api_key: Some("upstream-key".to_string()),
+                api_keys: None,
+                weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
+                enabled: true,
+                passive_failure_count_threshold_override: None,
+                passive_cooldown_seconds_override: None,
+                passive_window_seconds_override: None,
+                passive_rate_limit_cooldown_seconds_override: None,
+                models,
+                active_probe_enabled_override: None,
+                active_probe_interval_seconds_override: None,
+                active_probe_success_threshold_override: None,
+                active_probe_model_override: None,
+            }],
+            max_retries: -1,
+            channel_max_retries: 0,
+            channel_retry_interval_ms: 0,
+            circuit_breaker_enabled: true,
+            per_model_circuit_break: false,
+            transforms: Vec::new(),
+            active_probe_enabled_override: None,
+            active_probe_interval_seconds_override: None,
+            active_probe_success_threshold_override: None,
+            active_probe_model_override: None,
+            request_timeout_ms_override: None,
+            extra_fields_whitelist: None,
+            strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
+            enabled: true,
+            priority: Some(-50),
+        })
+        .await
+        .expect("create premium alias provider");
+    set_finite_balance(&ctx, "1000000000").await;
+    let before = user_balance_nano(&ctx).await;
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({
+            "model": "premium-alias-model",
+            "input": "over the ceiling",
+            "max_multiplier": 2.0,
+            "emit_usage": true
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_GATEWAY, "{body}");
+    ctx.state.user_store.flush_all_batchers().await;
+    assert_eq!(user_balance_nano(&ctx).await, before);
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({
+            "model": "premium-alias-model",
+            "input": "at the ceiling",
+            "stream": true,
+            "max_multiplier": 3.0,
+            "emit_usage": true
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    ctx.state.user_store.flush_all_batchers().await;
+    // 12 input + 8 output tokens at 1000 nano each, scaled by the alias's 3x.
+    assert_eq!(before - user_balance_nano(&ctx).await, 60000);
+}
+
+#[tokio::test]
+async fn redirected_model_pricing_falls_back_to_logical_model_when_upstream_unpriced() {
+    let ctx = setup().await;
+
+    let providers = ctx
+        .state
+        .monoize_store
+        .list_providers()
+        .await
+        .expect("list providers");
+    let base_url = providers
+        .iter()
+        .find_map(|p| p.channels.first().map(|c| c.base_url.clone()))
+        .expect("base_url");
+
+    let mut models = HashMap::new();
+    models.insert(
+        "alias-fallback-model".to_string(),
+        monoize::monoize_routing::MonoizeModelEntry {
+            redirect: Some("gpt-5-unpriced-upstream".to_string()),
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     ctx.state
@@ -1560,7 +2060,12 @@ async fn redirected_model_pricing_falls_back_to_logical_model_when_upstream_unpr
                 provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -1585,6 +2090,9 @@ async fn redirected_model_pricing_falls_back_to_logical_model_when_upstream_unpr
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-50),
         })
@@ -1603,6 +2111,8 @@ async fn redirected_model_pricing_falls_back_to_logical_model_when_upstream_unpr
                 cache_read_input_cost_per_token_nano: None,
                 cache_creation_input_cost_per_token_nano: None,
                 output_cost_per_reasoning_token_nano: None,
+                image_cost_per_image_nano: None,
+                tts_cost_per_char_nano: None,
                 max_input_tokens: None,
                 max_output_tokens: None,
                 max_tokens: None,
@@ -1690,6 +2200,7 @@ async fn suffixed_model_pricing_uses_base_model_metadata_without_separate_alias_
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: None,
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     ctx.state
@@ -1704,7 +2215,12 @@ async fn suffixed_model_pricing_uses_base_model_metadata_without_separate_alias_
                 provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -1729,6 +2245,9 @@ async fn suffixed_model_pricing_uses_base_model_metadata_without_separate_alias_
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-50),
         })
@@ -1830,6 +2349,80 @@ async fn balance_zero_returns_payment_required() {
     assert_eq!(v["error"]["code"].as_str(), Some("insufficient_balance"));
 }
 
+#[tokio::test]
+async fn balance_zero_can_use_zero_priced_model() {
+    let ctx = setup().await;
+    ctx.state
+        .model_registry_store
+        .upsert_model_metadata(
+            "gpt-5-mini",
+            monoize::model_registry_store::UpsertModelMetadataInput {
+                models_dev_provider: Some("openai".to_string()),
+                mode: Some("chat".to_string()),
+                input_cost_per_token_nano: Some("0".to_string()),
+                output_cost_per_token_nano: Some("0".to_string()),
+                cache_read_input_cost_per_token_nano: None,
+                cache_creation_input_cost_per_token_nano: None,
+                output_cost_per_reasoning_token_nano: None,
+                image_cost_per_image_nano: None,
+                tts_cost_per_char_nano: None,
+                max_input_tokens: None,
+                max_output_tokens: None,
+                max_tokens: None,
+            },
+        )
+        .await
+        .expect("make model free");
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("get user")
+        .expect("user exists");
+    ctx.state
+        .user_store
+        .update_user(
+            &user.id,
+            None,
+            None,
+            None,
+            None,
+            Some("0"),
+            Some(false),
+            None,
+            None,
+        )
+        .await
+        .expect("update user");
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({"model":"gpt-5-mini","input":"hi","emit_usage":true}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({"model":"gpt-5-mini-chat","input":"hi"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PAYMENT_REQUIRED, "{body}");
+
+    ctx.state.user_store.flush_all_batchers().await;
+    let user_after = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("get user")
+        .expect("user exists");
+    assert_eq!(user_after.balance_nano_usd, "0");
+}
+
 #[tokio::test]
 async fn balance_exact_covers_request() {
     let ctx = setup().await;
@@ -2004,3 +2597,695 @@ async fn extra_fields_do_not_corrupt_response() {
     let after: i64 = user_after.balance_nano_usd.parse().unwrap();
     assert_eq!(before - after, 20000);
 }
+
+#[tokio::test]
+async fn response_cache_hit_is_logged_as_cached_without_charge() {
+    let ctx = setup().await;
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("query user")
+        .expect("user exists");
+    let (_, token) = ctx
+        .state
+        .user_store
+        .create_api_key_extended(
+            &user.id,
+            monoize::users::CreateApiKeyInput {
+                name: "cached-key".to_string(),
+                expires_in_days: None,
+                sub_account_enabled: false,
+                model_limits_enabled: false,
+                model_limits: Vec::new(),
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
+                ip_whitelist: Vec::new(),
+                allowed_groups: Vec::new(),
+                max_multiplier: None,
+                transforms: Vec::new(),
+                model_redirects: Vec::new(),
+                reasoning_envelope_enabled: true,
+                request_capture_mode: monoize::users::RequestCaptureMode::Off,
+                response_cache_enabled: true,
+                max_request_charge_nano_usd: None,
+            },
+            false,
+        )
+        .await
+        .expect("create cached api key");
+
+    let send = |body: Value| {
+        let router = ctx.router.clone();
+        let token = token.clone();
+        async move {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/v1/chat/completions")
+                .header(CONTENT_TYPE, "application/json")
+                .header(AUTHORIZATION, format!("Bearer {token}"))
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            let resp = router.oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+            serde_json::from_slice::<Value>(&bytes).unwrap()
+        }
+    };
+    let deterministic = json!({
+        "model": "gpt-5-mini-chat",
+        "messages": [{ "role": "user", "content": "cache me" }],
+        "temperature": 0
+    });
+    let first = send(deterministic.clone()).await;
+    let second = send(deterministic).await;
+    assert_eq!(
+        first["choices"][0]["message"]["content"],
+        second["choices"][0]["message"]["content"]
+    );
+    assert_eq!(ctx.state.response_cache.len(), 1);
+
+    send(json!({
+        "model": "gpt-5-mini-chat",
+        "messages": [{ "role": "user", "content": "cache me" }],
+        "temperature": 0.7
+    }))
+    .await;
+    assert_eq!(ctx.state.response_cache.len(), 1);
+
+    let mut logs = Vec::new();
+    for _ in 0..20 {
+        ctx.state.user_store.flush_all_batchers().await;
+        (logs, _, _) = ctx
+            .state
+            .user_store
+            .list_request_logs_by_user(
+                &user.id,
+                100,
+                0,
+                Some("gpt-5-mini-chat"),
+                Some("success"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .expect("list request logs");
+        if logs.len() == 3 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(logs.len(), 3);
+    let cached: Vec<_> = logs
+        .iter()
+        .filter(|log| log.request_kind.as_deref() == Some("cached"))
+        .collect();
+    assert_eq!(cached.len(), 1);
+    assert_eq!(cached[0].billing.charge_nano_usd.as_deref(), Some("0"));
+}
+
+#[tokio::test]
+async fn idempotency_key_replays_response_without_upstream_call_or_charge() {
+    let ctx = setup().await;
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("query user")
+        .expect("user exists");
+    ctx.state
+        .user_store
+        .update_user(
+            &user.id,
+            None,
+            None,
+            None,
+            None,
+            Some("1000000000"),
+            Some(false),
+            None,
+            None,
+        )
+        .await
+        .expect("update user");
+    let before: i64 = 1_000_000_000;
+
+    let send = |idempotency_key: &'static str| {
+        let router = ctx.router.clone();
+        let auth_header = ctx.auth_header.clone();
+        async move {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/v1/chat/completions")
+                .header(CONTENT_TYPE, "application/json")
+                .header(AUTHORIZATION, auth_header)
+                .header("idempotency-key", idempotency_key)
+                .body(Body::from(
+                    json!({
+                        "model": "gpt-5-mini-chat",
+                        "messages": [{ "role": "user", "content": "idempotent me" }],
+                        "temperature": 0.7,
+                        "stream_mode": "nested_usage_details"
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            let resp = router.oneshot(req).await.unwrap();
+            let status = resp.status();
+            let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+            (status, serde_json::from_slice::<Value>(&bytes).unwrap())
+        }
+    };
+    let upstream_calls = || {
+        ctx.captured_bodies
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, body)| body.to_string().contains("idempotent me"))
+            .count()
+    };
+
+    let (status, first) = send("retry-1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(upstream_calls(), 1);
+    let logs = wait_for_success_logs(&ctx, &user.id, "gpt-5-mini-chat", 1).await;
+    assert_eq!(logs.len(), 1);
+    ctx.state.user_store.flush_all_batchers().await;
+    let after_first: i64 = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("get user")
+        .expect("user exists")
+        .balance_nano_usd
+        .parse()
+        .unwrap();
+    assert_eq!(before - after_first, 20_000);
+
+    let (status, replay) = send("retry-1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(replay, first);
+    assert_eq!(upstream_calls(), 1);
+    assert_eq!(ctx.state.idempotency.len(), 1);
+
+    let (status, _) = send("  ").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send("retry-2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(upstream_calls(), 2);
+    assert_eq!(ctx.state.idempotency.len(), 2);
+
+    let logs = wait_for_success_logs(&ctx, &user.id, "gpt-5-mini-chat", 2).await;
+    assert_eq!(logs.len(), 2);
+    ctx.state.user_store.flush_all_batchers().await;
+    let after: i64 = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("get user")
+        .expect("user exists")
+        .balance_nano_usd
+        .parse()
+        .unwrap();
+    assert_eq!(before - after, 40_000);
+}
+
+async fn create_unpriced_responses_provider(ctx: &TestContext, logical_model: &str) {
+    let providers = ctx
+        .state
+        .monoize_store
+        .list_providers()
+        .await
+        .expect("list providers");
+    let base_url = providers
+        .iter()
+        .find_map(|p| p.channels.first().map(|c| c.base_url.clone()))
+        .expect("base_url");
+
+    let mut models = HashMap::new();
+    models.insert(
+        logical_model.to_string(),
+        monoize::monoize_routing::MonoizeModelEntry {
+            redirect: Some("gpt-5-unpriced-upstream".to_string()),
+            multiplier: 1.0,
+            supports_streaming: None,
+        },
+    );
+    ctx.state
+        .monoize_store
+        .create_provider(monoize::monoize_routing::CreateMonoizeProviderInput {
+            name: "unpriced-provider".to_string(),
+            api_type_overrides: Vec::new(),
+            groups: Vec::new(),
+            channels: vec![monoize::monoize_routing::CreateMonoizeChannelInput {
+                id: Some("unpriced-ch".to_string()),
+                name: "unpriced-ch".to_string(),
+                provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
+                base_url,
+                api_key: Some("upstream-key".to_string()),
+                api_keys: None,
+                weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
+                enabled: true,
+                passive_failure_count_threshold_override: None,
+                passive_cooldown_seconds_override: None,
+                passive_window_seconds_override: None,
+                passive_rate_limit_cooldown_seconds_override: None,
+                models,
+                active_probe_enabled_override: None,
+                active_probe_interval_seconds_override: None,
+                active_probe_success_threshold_override: None,
+                active_probe_model_override: None,
+            }],
+            max_retries: -1,
+            channel_max_retries: 0,
+            channel_retry_interval_ms: 0,
+            circuit_breaker_enabled: true,
+            per_model_circuit_break: false,
+            transforms: Vec::new(),
+            active_probe_enabled_override: None,
+            active_probe_interval_seconds_override: None,
+            active_probe_success_threshold_override: None,
+            active_probe_model_override: None,
+            request_timeout_ms_override: None,
+            extra_fields_whitelist: None,
+            strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
+            enabled: true,
+            priority: Some(-50),
+        })
+        .await
+        .expect("create unpriced provider");
+}
+
+async fn wait_for_success_logs(
+    ctx: &TestContext,
+    user_id: &str,
+    model: &str,
+    expected: usize,
+) -> Vec<monoize::users::RequestLogRow> {
+    let mut logs = Vec::new();
+    for _ in 0..20 {
+        ctx.state.user_store.flush_all_batchers().await;
+        (logs, _, _) = ctx
+            .state
+            .user_store
+            .list_request_logs_by_user(
+                user_id,
+                100,
+                0,
+                Some(model),
+                Some("success"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .expect("list request logs");
+        if logs.len() == expected {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    logs
+}
+
+#[tokio::test]
+async fn missing_pricing_policy_reject_blocks_unpriced_model() {
+    let ctx = setup().await;
+    create_unpriced_responses_provider(&ctx, "unpriced-model").await;
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({"model":"unpriced-model","input":"hi","stream":true,"emit_usage":true}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PAYMENT_REQUIRED, "{body}");
+    let v: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(v["error"]["code"].as_str(), Some("pricing_unavailable"));
+}
+
+#[tokio::test]
+async fn missing_pricing_policy_free_serves_unpriced_model_without_charge() {
+    let ctx = setup().await;
+    create_unpriced_responses_provider(&ctx, "unpriced-model").await;
+    ctx.state
+        .monoize_runtime
+        .write()
+        .await
+        .missing_pricing_policy = monoize::settings::MissingPricingPolicy::Free;
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("get user")
+        .expect("user exists");
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({"model":"unpriced-model","input":"hi","stream":true,"emit_usage":true}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let logs = wait_for_success_logs(&ctx, &user.id, "unpriced-model", 1).await;
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].billing.charge_nano_usd, None);
+    assert_eq!(logs[0].billing.breakdown, None);
+    let user_after = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("get user")
+        .expect("user exists");
+    assert_eq!(user_after.balance_nano_usd, user.balance_nano_usd);
+}
+
+#[tokio::test]
+async fn missing_pricing_policy_flag_marks_request_log() {
+    let ctx = setup().await;
+    create_unpriced_responses_provider(&ctx, "unpriced-model").await;
+    ctx.state
+        .monoize_runtime
+        .write()
+        .await
+        .missing_pricing_policy = monoize::settings::MissingPricingPolicy::Flag;
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("get user")
+        .expect("user exists");
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({"model":"unpriced-model","input":"hi","stream":true,"emit_usage":true}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let logs = wait_for_success_logs(&ctx, &user.id, "unpriced-model", 1).await;
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].billing.charge_nano_usd, None);
+    let breakdown = logs[0]
+        .billing
+        .breakdown
+        .as_ref()
+        .expect("billing breakdown");
+    assert_eq!(breakdown["pricing_missing"], json!(true));
+    assert_eq!(
+        breakdown["upstream_model"],
+        json!("gpt-5-unpriced-upstream")
+    );
+    assert_eq!(breakdown["final_charge_nano"], json!("0"));
+}
+
+#[tokio::test]
+async fn request_charge_cap_charges_cap_and_flags_request_log() {
+    let ctx = setup().await;
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("get user")
+        .expect("user exists");
+    ctx.state
+        .user_store
+        .update_user(
+            &user.id,
+            None,
+            None,
+            None,
+            None,
+            Some("100000"),
+            Some(false),
+            None,
+            None,
+        )
+        .await
+        .expect("update user");
+    let request = json!({
+        "model":"gpt-5-mini-chat",
+        "messages":[{"role":"user","content":"hi"}],
+        "stream": true,
+        "emit_usage": true
+    });
+
+    ctx.state
+        .monoize_runtime
+        .write()
+        .await
+        .max_request_charge_nano_usd = 20_000;
+    let (status, body) = json_post(&ctx, "/v1/chat/completions", request.clone()).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let logs = wait_for_success_logs(&ctx, &user.id, "gpt-5-mini-chat", 1).await;
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].billing.charge_nano_usd.as_deref(), Some("20000"));
+    let breakdown = logs[0]
+        .billing
+        .breakdown
+        .as_ref()
+        .expect("billing breakdown");
+    assert!(breakdown.get("charge_capped").is_none(), "{breakdown}");
+
+    ctx.state
+        .monoize_runtime
+        .write()
+        .await
+        .max_request_charge_nano_usd = 19_999;
+    let (status, body) = json_post(&ctx, "/v1/chat/completions", request).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let logs = wait_for_success_logs(&ctx, &user.id, "gpt-5-mini-chat", 2).await;
+    assert_eq!(logs.len(), 2);
+    let capped = logs
+        .iter()
+        .find(|log| log.billing.charge_nano_usd.as_deref() == Some("19999"))
+        .expect("capped request log");
+    let breakdown = capped
+        .billing
+        .breakdown
+        .as_ref()
+        .expect("billing breakdown");
+    assert_eq!(breakdown["charge_capped"], json!(true));
+    assert_eq!(breakdown["uncapped_charge_nano"], json!("20000"));
+    assert_eq!(breakdown["final_charge_nano"], json!("19999"));
+
+    let user_after = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("get user")
+        .expect("user exists");
+    let after: i64 = user_after.balance_nano_usd.parse().unwrap();
+    assert_eq!(after, 100_000 - 20_000 - 19_999);
+}
+
+#[tokio::test]
+async fn embeddings_split_oversized_input_and_bill_summed_usage_once() {
+    let ctx = setup().await;
+    ctx.state.monoize_runtime.write().await.max_embedding_batch = 2;
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("get user")
+        .expect("user exists");
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/embeddings",
+        json!({"model":"gpt-5-mini-chat","input":["0","1","2","3","4"]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(v["model"], json!("gpt-5-mini-chat"));
+    let data = v["data"].as_array().expect("data array");
+    assert_eq!(data.len(), 5);
+    for (i, item) in data.iter().enumerate() {
+        assert_eq!(item["index"], json!(i), "{body}");
+        assert_eq!(item["embedding"], json!([i as f64]), "{body}");
+    }
+    assert_eq!(v["usage"]["prompt_tokens"], json!(5));
+    assert_eq!(v["usage"]["total_tokens"], json!(5));
+
+    let chunk_inputs: Vec<Value> = ctx
+        .captured_bodies
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(kind, _)| kind == "embeddings")
+        .map(|(_, body)| body["input"].clone())
+        .collect();
+    assert_eq!(chunk_inputs.len(), 3);
+    assert!(chunk_inputs.contains(&json!(["0", "1"])));
+    assert!(chunk_inputs.contains(&json!(["2", "3"])));
+    assert!(chunk_inputs.contains(&json!(["4"])));
+
+    let logs = wait_for_success_logs(&ctx, &user.id, "gpt-5-mini-chat", 1).await;
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].tokens.input, Some(5));
+    assert!(logs[0].billing.charge_nano_usd.is_some());
+}
+
+#[tokio::test]
+async fn embeddings_reencode_float_upstream_vectors_as_requested_base64() {
+    let ctx = setup().await;
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/embeddings",
+        json!({"model":"gpt-5-mini-chat","input":"0.5","encoding_format":"base64"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: Value = serde_json::from_str(&body).unwrap();
+    // The mock upstream always answers with float arrays.
+    assert_eq!(v["data"][0]["embedding"], json!("AAAAPw=="), "{body}");
+}
+
+#[tokio::test]
+async fn speech_streams_binary_audio_and_bills_per_input_character() {
+    let ctx = setup().await;
+    let providers = ctx
+        .state
+        .monoize_store
+        .list_providers()
+        .await
+        .expect("list providers");
+    let base_url = providers
+        .iter()
+        .find_map(|p| p.channels.first().map(|c| c.base_url.clone()))
+        .expect("base_url");
+    create_test_provider(
+        &ctx.state,
+        "up-tts",
+        monoize::monoize_routing::MonoizeProviderType::Responses,
+        "tts-1",
+        &base_url,
+        "upstream-key-tts",
+    )
+    .await;
+    ctx.state
+        .model_registry_store
+        .upsert_model_metadata(
+            "tts-1",
+            monoize::model_registry_store::UpsertModelMetadataInput {
+                models_dev_provider: Some("default".to_string()),
+                mode: Some("audio_speech".to_string()),
+                input_cost_per_token_nano: None,
+                output_cost_per_token_nano: None,
+                cache_read_input_cost_per_token_nano: None,
+                cache_creation_input_cost_per_token_nano: None,
+                output_cost_per_reasoning_token_nano: None,
+                image_cost_per_image_nano: None,
+                tts_cost_per_char_nano: Some("15000".to_string()),
+                max_input_tokens: None,
+                max_output_tokens: None,
+                max_tokens: None,
+            },
+        )
+        .await
+        .expect("seed tts pricing");
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("get user")
+        .expect("user exists");
+    ctx.state
+        .user_store
+        .update_user(
+            &user.id,
+            None,
+            None,
+            None,
+            None,
+            Some("1000000000"),
+            Some(false),
+            None,
+            None,
+        )
+        .await
+        .expect("update user");
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/v1/audio/speech")
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, ctx.auth_header.clone())
+        .body(Body::from(
+            json!({
+                "model": "tts-1",
+                "input": "héllo",
+                "voice": "alloy",
+                "response_format": "wav"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let resp = ctx.router.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok()),
+        Some("audio/wav")
+    );
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(bytes.as_ref(), &[0x49, 0x44, 0x33, 0x04, 0x00, 0xff, 0xfb]);
+
+    let captured = ctx.captured_bodies.lock().unwrap().clone();
+    let (_, upstream_body) = captured
+        .iter()
+        .find(|(kind, _)| kind == "audio_speech")
+        .expect("speech forwarded upstream");
+    assert_eq!(upstream_body["voice"], json!("alloy"));
+    assert_eq!(upstream_body["input"], json!("héllo"));
+
+    let logs = wait_for_success_logs(&ctx, &user.id, "tts-1", 1).await;
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].request_kind.as_deref(), Some("speech"));
+    assert_eq!(logs[0].billing.charge_nano_usd.as_deref(), Some("75000"));
+}
+
+#[tokio::test]
+async fn speech_rejects_invalid_response_format() {
+    let ctx = setup().await;
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/audio/speech",
+        json!({
+            "model": "gpt-5-mini",
+            "input": "hi",
+            "voice": "alloy",
+            "response_format": "ogg"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    let v: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(v["error"]["code"].as_str(), Some("invalid_request"));
+}
@@ -50,6 +50,8 @@ async fn enable_request_capture(ctx: &TestContext) {
                 sub_account_enabled: None,
                 model_limits_enabled: None,
                 model_limits: None,
+                allow_models: None,
+                deny_models: None,
                 ip_whitelist: None,
                 allowed_groups: None,
                 max_multiplier: None,
@@ -57,6 +59,8 @@ async fn enable_request_capture(ctx: &TestContext) {
                 model_redirects: None,
                 reasoning_envelope_enabled: None,
                 request_capture_mode: Some(monoize::users::RequestCaptureMode::CaptureAll),
+                response_cache_enabled: None,
+                max_request_charge_nano_usd: None,
                 expires_at: None,
             },
             false,
@@ -100,6 +100,7 @@ async fn channel_passive_override_threshold_takes_precedence_over_global_default
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: None,
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     let created = ctx
@@ -115,7 +116,12 @@ async fn channel_passive_override_threshold_takes_precedence_over_global_default
                 provider_type: monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: Some(1),
                 passive_cooldown_seconds_override: None,
@@ -140,6 +146,9 @@ async fn channel_passive_override_threshold_takes_precedence_over_global_default
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-10),
         })
@@ -180,6 +189,89 @@ async fn channel_passive_override_threshold_takes_precedence_over_global_default
     );
 }
 
+#[tokio::test]
+async fn dashboard_channel_health_lists_and_resets_cooldowns() {
+    let mut ctx = setup().await;
+    let admin = ctx
+        .state
+        .user_store
+        .create_user(
+            "health-admin",
+            "test-password",
+            monoize::users::UserRole::Admin,
+            &[],
+        )
+        .await
+        .expect("admin created");
+    let session = ctx
+        .state
+        .user_store
+        .create_session(&admin.id, 7)
+        .await
+        .expect("session created");
+
+    let providers = ctx
+        .state
+        .monoize_store
+        .list_providers()
+        .await
+        .expect("list providers");
+    let channel_id = providers[0].channels[0].id.clone();
+    let now = chrono::Utc::now().timestamp();
+    {
+        let mut health = ctx.state.channel_health.lock().await;
+        let mut entry = monoize::monoize_routing::ChannelHealthState::new();
+        entry.healthy = false;
+        entry.cooldown_until = Some(now + 600);
+        entry
+            .passive_samples
+            .push_back(monoize::monoize_routing::PassiveHealthSample {
+                at_ts: now,
+                failed: true,
+            });
+        health.insert(channel_id.clone(), entry);
+    }
+
+    let (status, _body) = json_get(&ctx, "/api/dashboard/channels/health").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    ctx.auth_header = format!("Bearer {}", session.token);
+
+    let (status, body) = json_get(&ctx, "/api/dashboard/channels/health").await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let entries: Value = serde_json::from_str(&body).unwrap();
+    let entry = entries
+        .as_array()
+        .expect("array")
+        .iter()
+        .find(|item| item["channel_id"] == channel_id.as_str())
+        .expect("seeded channel is listed");
+    assert_eq!(entry["model"], Value::Null);
+    assert_eq!(entry["healthy"], false);
+    assert_eq!(entry["status"], "unhealthy");
+    assert_eq!(entry["failure_count"], 1);
+    assert_eq!(entry["cooldown_until"], now + 600);
+    assert!(entry.get("passive_samples").is_none());
+
+    let reset_path = format!("/api/dashboard/channels/{channel_id}/reset");
+    for _ in 0..2 {
+        let (status, body) = json_post(&ctx, &reset_path, json!({})).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        let health = ctx.state.channel_health.lock().await;
+        let state = health.get(&channel_id).expect("entry kept");
+        assert!(state.healthy);
+        assert_eq!(state.cooldown_until, None);
+        assert_eq!(state.failure_count(), 0);
+    }
+
+    let (status, _body) = json_post(
+        &ctx,
+        "/api/dashboard/channels/missing-channel/reset",
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
 #[tokio::test]
 async fn provider_request_transform_matches_normalized_model_before_redirect() {
     let ctx = setup().await;
@@ -193,6 +285,7 @@ async fn provider_request_transform_matches_normalized_model_before_redirect() {
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: Some("gpt-5-target".to_string()),
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
 
@@ -206,7 +299,12 @@ async fn provider_request_transform_matches_normalized_model_before_redirect() {
             provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
             base_url,
             api_key: Some("upstream-key".to_string()),
+            api_keys: None,
             weight: 1,
+            priority: 0,
+            max_concurrency: None,
+            org_id: None,
+            project_id: None,
             enabled: true,
             passive_failure_count_threshold_override: None,
             passive_cooldown_seconds_override: None,
@@ -240,6 +338,9 @@ async fn provider_request_transform_matches_normalized_model_before_redirect() {
         request_timeout_ms_override: None,
         extra_fields_whitelist: None,
         strip_cross_protocol_nested_extra: None,
+        debug_log_bodies: false,
+        default_temperature: None,
+        default_top_p: None,
         enabled: true,
         priority: Some(-1),
     };
@@ -292,6 +393,7 @@ async fn provider_api_type_override_matches_logical_model_before_provider_redire
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: Some("gpt-5.4".to_string()),
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
 
@@ -308,7 +410,12 @@ async fn provider_api_type_override_matches_logical_model_before_provider_redire
             provider_type: monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
             base_url,
             api_key: Some("upstream-key".to_string()),
+            api_keys: None,
             weight: 1,
+            priority: 0,
+            max_concurrency: None,
+            org_id: None,
+            project_id: None,
             enabled: true,
             passive_failure_count_threshold_override: None,
             passive_cooldown_seconds_override: None,
@@ -342,6 +449,9 @@ async fn provider_api_type_override_matches_logical_model_before_provider_redire
         request_timeout_ms_override: None,
         extra_fields_whitelist: None,
         strip_cross_protocol_nested_extra: None,
+        debug_log_bodies: false,
+        default_temperature: None,
+        default_top_p: None,
         enabled: true,
         priority: Some(-1),
     };
@@ -411,6 +521,8 @@ async fn models_list_respects_api_key_model_limits() {
                 sub_account_enabled: false,
                 model_limits_enabled: true,
                 model_limits: vec!["gpt-5-mini".to_string(), "grok-4".to_string()],
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
                 ip_whitelist: Vec::new(),
 
                 allowed_groups: Vec::new(),
@@ -419,6 +531,8 @@ async fn models_list_respects_api_key_model_limits() {
                 model_redirects: Vec::new(),
                 reasoning_envelope_enabled: true,
                 request_capture_mode: monoize::users::RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -467,6 +581,8 @@ async fn models_list_model_limits_disabled_shows_all() {
                 sub_account_enabled: false,
                 model_limits_enabled: false,
                 model_limits: vec!["gpt-5-mini".to_string()],
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
                 ip_whitelist: Vec::new(),
 
                 allowed_groups: Vec::new(),
@@ -475,6 +591,8 @@ async fn models_list_model_limits_disabled_shows_all() {
                 model_redirects: Vec::new(),
                 reasoning_envelope_enabled: true,
                 request_capture_mode: monoize::users::RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -526,6 +644,8 @@ async fn forwarding_rejects_models_outside_api_key_model_limits() {
                 sub_account_enabled: false,
                 model_limits_enabled: true,
                 model_limits: vec!["gpt-5-mini".to_string()],
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
                 ip_whitelist: vec![],
 
                 allowed_groups: Vec::new(),
@@ -534,6 +654,8 @@ async fn forwarding_rejects_models_outside_api_key_model_limits() {
                 model_redirects: Vec::new(),
                 reasoning_envelope_enabled: true,
                 request_capture_mode: monoize::users::RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -583,6 +705,8 @@ async fn forwarding_applies_api_key_model_redirects_before_model_limits_and_rout
                 sub_account_enabled: false,
                 model_limits_enabled: true,
                 model_limits: vec!["gpt-5-mini".to_string()],
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
                 ip_whitelist: vec![],
                 allowed_groups: Vec::new(),
                 max_multiplier: None,
@@ -593,6 +717,8 @@ async fn forwarding_applies_api_key_model_redirects_before_model_limits_and_rout
                 }],
                 reasoning_envelope_enabled: true,
                 request_capture_mode: monoize::users::RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -620,6 +746,151 @@ async fn forwarding_applies_api_key_model_redirects_before_model_limits_and_rout
     assert_eq!(v["model"].as_str(), Some("gpt-5-mini"));
 }
 
+#[tokio::test]
+async fn forwarding_resolves_settings_model_alias_before_routing() {
+    let ctx = setup().await;
+    ctx.state.monoize_runtime.write().await.model_aliases = std::collections::HashMap::from([
+        ("smart".to_string(), "gpt-5-mini".to_string()),
+        ("loop-a".to_string(), "loop-b".to_string()),
+        ("loop-b".to_string(), "loop-a".to_string()),
+    ]);
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({ "model": "smart", "input": "hi" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(v["model"].as_str(), Some("gpt-5-mini"));
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({ "model": "loop-a", "input": "hi" }),
+    )
+    .await;
+    assert_ne!(status, StatusCode::OK, "{body}");
+}
+
+#[tokio::test]
+async fn forwarding_applies_api_key_allow_and_deny_model_globs_after_alias_resolution() {
+    let ctx = setup().await;
+    ctx.state.monoize_runtime.write().await.model_aliases =
+        std::collections::HashMap::from([("smart".to_string(), "gpt-5-mini-msg".to_string())]);
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .unwrap()
+        .unwrap();
+
+    let (_, token) = ctx
+        .state
+        .user_store
+        .create_api_key_extended(
+            &user.id,
+            monoize::users::CreateApiKeyInput {
+                name: "glob-filtered-key".to_string(),
+                expires_in_days: None,
+                sub_account_enabled: false,
+                model_limits_enabled: false,
+                model_limits: Vec::new(),
+                allow_models: vec!["gpt-5*".to_string()],
+                deny_models: vec!["*-msg".to_string()],
+                ip_whitelist: vec![],
+                allowed_groups: Vec::new(),
+                max_multiplier: None,
+                transforms: vec![],
+                model_redirects: Vec::new(),
+                reasoning_envelope_enabled: true,
+                request_capture_mode: monoize::users::RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
+            },
+            false,
+        )
+        .await
+        .unwrap();
+
+    let send = |model: &str| {
+        Request::builder()
+            .method("POST")
+            .uri("/v1/responses")
+            .header(CONTENT_TYPE, "application/json")
+            .header(AUTHORIZATION, format!("Bearer {token}"))
+            .body(Body::from(
+                json!({ "model": model, "input": "hi" }).to_string(),
+            ))
+            .unwrap()
+    };
+
+    let resp = ctx
+        .router
+        .clone()
+        .oneshot(send("gpt-5-mini"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // "grok-4" matches no allow glob; "gpt-5-mini-msg" matches both and deny
+    // wins; "smart" is only rejected once its alias has been resolved.
+    for model in ["grok-4", "gpt-5-mini-msg", "smart"] {
+        let resp = ctx.router.clone().oneshot(send(model)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN, "{model}");
+        let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+        let v: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(v["error"]["code"].as_str(), Some("model_not_allowed"));
+    }
+}
+
+#[tokio::test]
+async fn forwarding_falls_back_to_secondary_model_when_primary_has_no_channels() {
+    let ctx = setup().await;
+    ctx.state.monoize_runtime.write().await.model_fallbacks = std::collections::HashMap::from([(
+        "gpt-4o-unrouted".to_string(),
+        vec!["missing-fallback".to_string(), "gpt-5-mini".to_string()],
+    )]);
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({ "model": "gpt-4o-unrouted", "input": "hi" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(v["model"].as_str(), Some("gpt-4o-unrouted"));
+
+    ctx.state
+        .monoize_runtime
+        .write()
+        .await
+        .model_fallback_report_actual_model = true;
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gpt-4o-unrouted",
+            "messages": [{ "role": "user", "content": "hi" }]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(v["model"].as_str(), Some("gpt-5-mini"));
+
+    let (status, _body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({ "model": "other-unrouted", "input": "hi" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+}
+
 #[tokio::test]
 async fn image_generation_applies_api_key_model_redirects_before_model_limits() {
     let ctx = setup().await;
@@ -642,6 +913,8 @@ async fn image_generation_applies_api_key_model_redirects_before_model_limits()
                 sub_account_enabled: false,
                 model_limits_enabled: true,
                 model_limits: vec!["gpt-5-mini".to_string()],
+                allow_models: Vec::new(),
+                deny_models: Vec::new(),
                 ip_whitelist: vec![],
                 allowed_groups: Vec::new(),
                 max_multiplier: None,
@@ -652,6 +925,8 @@ async fn image_generation_applies_api_key_model_redirects_before_model_limits()
                 }],
                 reasoning_envelope_enabled: true,
                 request_capture_mode: monoize::users::RequestCaptureMode::Off,
+                response_cache_enabled: false,
+                max_request_charge_nano_usd: None,
             },
             false,
         )
@@ -725,3 +1000,995 @@ async fn exhausted_upstream_error_preserves_last_upstream_error_fields() {
         "downstream error message must include final upstream detail: {body}"
     );
 }
+
+fn concurrency_test_channel(
+    id: &str,
+    base_url: &str,
+    priority: i32,
+    max_concurrency: Option<u32>,
+) -> monoize::monoize_routing::CreateMonoizeChannelInput {
+    let mut models = HashMap::new();
+    models.insert(
+        "concurrency-model".to_string(),
+        monoize::monoize_routing::MonoizeModelEntry {
+            redirect: None,
+            multiplier: 1.0,
+            supports_streaming: None,
+        },
+    );
+    monoize::monoize_routing::CreateMonoizeChannelInput {
+        id: Some(id.to_string()),
+        name: id.to_string(),
+        provider_type: monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
+        base_url: base_url.to_string(),
+        api_key: Some("upstream-key".to_string()),
+        api_keys: None,
+        weight: 1,
+        priority,
+        max_concurrency,
+        org_id: None,
+        project_id: None,
+        enabled: true,
+        passive_failure_count_threshold_override: None,
+        passive_cooldown_seconds_override: None,
+        passive_window_seconds_override: None,
+        passive_rate_limit_cooldown_seconds_override: None,
+        models,
+        active_probe_enabled_override: None,
+        active_probe_interval_seconds_override: None,
+        active_probe_success_threshold_override: None,
+        active_probe_model_override: None,
+    }
+}
+
+fn channel_has_success(
+    health: &HashMap<String, monoize::monoize_routing::ChannelHealthState>,
+    channel_id: &str,
+) -> bool {
+    health
+        .get(channel_id)
+        .is_some_and(|state| state.last_success_at.is_some())
+}
+
+async fn existing_channel_base_url(ctx: &TestContext) -> String {
+    ctx.state
+        .monoize_store
+        .list_providers()
+        .await
+        .expect("list providers")
+        .iter()
+        .find_map(|p| p.channels.first().map(|c| c.base_url.clone()))
+        .expect("at least one existing channel base url")
+}
+
+async fn create_routing_test_provider(
+    ctx: &TestContext,
+    name: &str,
+    channels: Vec<monoize::monoize_routing::CreateMonoizeChannelInput>,
+    transforms: Vec<monoize::transforms::TransformRuleConfig>,
+    priority: Option<i32>,
+) -> Result<monoize::monoize_routing::MonoizeProvider, String> {
+    ctx.state
+        .monoize_store
+        .create_provider(monoize::monoize_routing::CreateMonoizeProviderInput {
+            name: name.to_string(),
+            api_type_overrides: Vec::new(),
+            groups: Vec::new(),
+            channels,
+            max_retries: -1,
+            channel_max_retries: 0,
+            channel_retry_interval_ms: 0,
+            circuit_breaker_enabled: true,
+            per_model_circuit_break: false,
+            transforms,
+            active_probe_enabled_override: None,
+            active_probe_interval_seconds_override: None,
+            active_probe_success_threshold_override: None,
+            active_probe_model_override: None,
+            request_timeout_ms_override: None,
+            extra_fields_whitelist: None,
+            strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
+            enabled: true,
+            priority,
+        })
+        .await
+}
+
+#[tokio::test]
+async fn saturated_channel_concurrency_routes_to_next_channel_and_releases_permits() {
+    let ctx = setup().await;
+    seed_test_model_pricing(&ctx.state, &["concurrency-model"]).await;
+    let base_url = existing_channel_base_url(&ctx).await;
+    create_routing_test_provider(
+        &ctx,
+        "concurrency-provider",
+        vec![
+            concurrency_test_channel("limited-ch", &base_url, 0, Some(1)),
+            concurrency_test_channel("fallback-ch", &base_url, 1, None),
+        ],
+        Vec::new(),
+        None,
+    )
+    .await
+    .expect("create provider with limited channel");
+
+    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(1));
+    ctx.state.channel_concurrency.lock().await.insert(
+        "limited-ch".to_string(),
+        monoize::monoize_routing::ChannelConcurrencyLimit {
+            limit: 1,
+            semaphore: semaphore.clone(),
+        },
+    );
+    let held = semaphore.clone().try_acquire_owned().expect("saturate");
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "concurrency-model",
+            "messages": [{ "role": "user", "content": "saturated" }]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    {
+        let health = ctx.state.channel_health.lock().await;
+        assert!(channel_has_success(&health, "fallback-ch"));
+        assert!(!channel_has_success(&health, "limited-ch"));
+    }
+    drop(held);
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "concurrency-model",
+            "messages": [{ "role": "user", "content": "free" }],
+            "stream": true
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(channel_has_success(
+        &*ctx.state.channel_health.lock().await,
+        "limited-ch"
+    ));
+
+    let mut released = false;
+    for _ in 0..50 {
+        if semaphore.available_permits() == 1 {
+            released = true;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert!(
+        released,
+        "streaming attempt must release its channel permit"
+    );
+}
+
+#[tokio::test]
+async fn half_open_channel_recovers_only_after_two_consecutive_probe_successes() {
+    let ctx = setup().await;
+    seed_test_model_pricing(&ctx.state, &["concurrency-model"]).await;
+    let base_url = existing_channel_base_url(&ctx).await;
+    let mut channel = concurrency_test_channel("half-open-ch", &base_url, 0, None);
+    channel.active_probe_enabled_override = Some(false);
+    channel.active_probe_success_threshold_override = Some(2);
+    create_routing_test_provider(
+        &ctx,
+        "half-open-provider",
+        vec![channel],
+        Vec::new(),
+        Some(-10),
+    )
+    .await
+    .expect("create provider with half-open channel");
+
+    let now = chrono::Utc::now().timestamp();
+    {
+        let mut health = ctx.state.channel_health.lock().await;
+        let mut entry = monoize::monoize_routing::ChannelHealthState::new();
+        entry.healthy = false;
+        entry.cooldown_until = Some(now - 1);
+        health.insert("half-open-ch".to_string(), entry);
+    }
+
+    let request = json!({
+        "model": "concurrency-model",
+        "messages": [{ "role": "user", "content": "probe" }]
+    });
+    let (status, body) = json_post(&ctx, "/v1/chat/completions", request.clone()).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    {
+        let health = ctx.state.channel_health.lock().await;
+        let state = health.get("half-open-ch").expect("health entry kept");
+        assert!(
+            !state.healthy,
+            "one probe success must not close the breaker when the threshold is 2"
+        );
+        assert_eq!(state.probe_success_count, 1);
+    }
+
+    let (status, body) = json_post(&ctx, "/v1/chat/completions", request).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let health = ctx.state.channel_health.lock().await;
+    let state = health.get("half-open-ch").expect("health entry kept");
+    assert!(state.healthy);
+    assert_eq!(state.probe_success_count, 0);
+    assert_eq!(state.cooldown_until, None);
+}
+
+#[tokio::test]
+async fn channel_openai_org_and_project_headers_reach_only_openai_upstreams() {
+    let ctx = setup().await;
+    let models = ["org-chat-model", "plain-chat-model", "org-messages-model"];
+    seed_test_model_pricing(&ctx.state, &models).await;
+    let base_url = existing_channel_base_url(&ctx).await;
+    let channel = |id: &str,
+                   model: &str,
+                   provider_type: monoize::monoize_routing::MonoizeProviderType,
+                   with_org: bool| {
+        let mut channel = concurrency_test_channel(id, &base_url, 0, None);
+        channel.provider_type = provider_type;
+        channel.models = HashMap::from([(
+            model.to_string(),
+            monoize::monoize_routing::MonoizeModelEntry {
+                redirect: None,
+                multiplier: 1.0,
+                supports_streaming: None,
+            },
+        )]);
+        if with_org {
+            channel.org_id = Some("org-123".to_string());
+            channel.project_id = Some("proj_456".to_string());
+        }
+        channel
+    };
+    create_routing_test_provider(
+        &ctx,
+        "org-provider",
+        vec![
+            channel(
+                "org-chat-ch",
+                "org-chat-model",
+                monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
+                true,
+            ),
+            channel(
+                "plain-chat-ch",
+                "plain-chat-model",
+                monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
+                false,
+            ),
+            channel(
+                "org-messages-ch",
+                "org-messages-model",
+                monoize::monoize_routing::MonoizeProviderType::Messages,
+                true,
+            ),
+        ],
+        Vec::new(),
+        None,
+    )
+    .await
+    .expect("create provider with org channels");
+
+    for (model, expected) in [
+        (
+            "org-chat-model",
+            vec![
+                ("openai-organization".to_string(), "org-123".to_string()),
+                ("openai-project".to_string(), "proj_456".to_string()),
+            ],
+        ),
+        ("plain-chat-model", Vec::new()),
+        ("org-messages-model", Vec::new()),
+    ] {
+        ctx.captured_headers.lock().unwrap().clear();
+        let (status, body) = json_post(
+            &ctx,
+            "/v1/chat/completions",
+            json!({
+                "model": model,
+                "messages": [{ "role": "user", "content": "hi" }]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{model}: {body}");
+        let captured: Vec<(String, String)> = ctx
+            .captured_headers
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| name.starts_with("openai-"))
+            .cloned()
+            .collect();
+        assert_eq!(captured, expected, "{model}");
+    }
+}
+
+#[tokio::test]
+async fn undecodable_upstream_body_retries_next_provider_up_to_decode_retry_limit() {
+    let ctx = setup().await;
+    let base_url = ctx
+        .state
+        .monoize_store
+        .list_providers()
+        .await
+        .expect("list providers")
+        .iter()
+        .find_map(|p| p.channels.first().map(|c| c.base_url.clone()))
+        .expect("at least one existing channel base url");
+    seed_test_model_pricing(&ctx.state, &["gpt-decode-retry"]).await;
+    create_test_provider(
+        &ctx.state,
+        "up-undecodable",
+        monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
+        "gpt-decode-retry",
+        &base_url,
+        "undecodable-key",
+    )
+    .await;
+    create_test_provider(
+        &ctx.state,
+        "up-decodable",
+        monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
+        "gpt-decode-retry",
+        &base_url,
+        "upstream-key",
+    )
+    .await;
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gpt-decode-retry",
+            "messages": [{ "role": "user", "content": "retry on empty body" }]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let body: Value = serde_json::from_str(&body).expect("json body");
+    assert_eq!(body["object"], json!("chat.completion"), "{body}");
+    let chat_calls = ctx
+        .captured_bodies
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(name, body)| name == "chat" && body["model"] == json!("gpt-decode-retry"))
+        .count();
+    assert_eq!(chat_calls, 2);
+
+    ctx.state.monoize_runtime.write().await.decode_retry_limit = 0;
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gpt-decode-retry",
+            "messages": [{ "role": "user", "content": "no decode retries" }]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_GATEWAY, "{body}");
+    let body: Value = serde_json::from_str(&body).expect("json body");
+    assert_eq!(
+        body["error"]["code"],
+        json!("invalid_upstream_response"),
+        "{body}"
+    );
+}
+
+#[tokio::test]
+async fn rejected_pool_key_is_retried_with_the_next_key_on_the_same_channel() {
+    // Each case gets a fresh state so the first send always uses the rejected key.
+    for stream in [false, true] {
+        let ctx = setup().await;
+        let base_url = existing_channel_base_url(&ctx).await;
+        seed_test_model_pricing(&ctx.state, &["gpt-key-pool"]).await;
+        let mut models = HashMap::new();
+        models.insert(
+            "gpt-key-pool".to_string(),
+            monoize::monoize_routing::MonoizeModelEntry {
+                redirect: None,
+                multiplier: 1.0,
+                supports_streaming: None,
+            },
+        );
+        ctx.state
+            .monoize_store
+            .create_provider(monoize::monoize_routing::CreateMonoizeProviderInput {
+                name: "up-key-pool".to_string(),
+                api_type_overrides: Vec::new(),
+                groups: Vec::new(),
+                channels: vec![monoize::monoize_routing::CreateMonoizeChannelInput {
+                    id: Some("up-key-pool-ch1".to_string()),
+                    name: "up-key-pool-ch1".to_string(),
+                    provider_type: monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
+                    base_url,
+                    api_key: Some("rejected-key".to_string()),
+                    api_keys: Some(vec!["upstream-key".to_string()]),
+                    weight: 1,
+                    priority: 0,
+                    max_concurrency: None,
+                    org_id: None,
+                    project_id: None,
+                    enabled: true,
+                    passive_failure_count_threshold_override: None,
+                    passive_cooldown_seconds_override: None,
+                    passive_window_seconds_override: None,
+                    passive_rate_limit_cooldown_seconds_override: None,
+                    models,
+                    active_probe_enabled_override: None,
+                    active_probe_interval_seconds_override: None,
+                    active_probe_success_threshold_override: None,
+                    active_probe_model_override: None,
+                }],
+                max_retries: -1,
+                channel_max_retries: 0,
+                channel_retry_interval_ms: 0,
+                circuit_breaker_enabled: true,
+                per_model_circuit_break: false,
+                transforms: Vec::new(),
+                active_probe_enabled_override: None,
+                active_probe_interval_seconds_override: None,
+                active_probe_success_threshold_override: None,
+                active_probe_model_override: None,
+                request_timeout_ms_override: None,
+                extra_fields_whitelist: None,
+                strip_cross_protocol_nested_extra: None,
+                debug_log_bodies: false,
+                default_temperature: None,
+                default_top_p: None,
+                enabled: true,
+                priority: Some(-1),
+            })
+            .await
+            .unwrap();
+
+        let (status, body) = json_post(
+            &ctx,
+            "/v1/chat/completions",
+            json!({
+                "model": "gpt-key-pool",
+                "stream": stream,
+                "messages": [{ "role": "user", "content": "rotate past the rejected key" }]
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "stream={stream}: {body}");
+        assert!(!body.contains("invalid api key"), "stream={stream}: {body}");
+    }
+}
+
+#[tokio::test]
+async fn oversized_body_skips_the_attempt_and_fails_only_when_every_attempt_is_too_large() {
+    let ctx = setup().await;
+    let base_url = existing_channel_base_url(&ctx).await;
+    seed_test_model_pricing(&ctx.state, &["large-body-fallback", "large-body-only"]).await;
+    create_test_provider(
+        &ctx.state,
+        "up-large-messages",
+        monoize::monoize_routing::MonoizeProviderType::Messages,
+        "large-body-fallback",
+        &base_url,
+        "upstream-key",
+    )
+    .await;
+    create_test_provider(
+        &ctx.state,
+        "up-large-chat",
+        monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
+        "large-body-fallback",
+        &base_url,
+        "upstream-key",
+    )
+    .await;
+    create_test_provider(
+        &ctx.state,
+        "up-large-only",
+        monoize::monoize_routing::MonoizeProviderType::Messages,
+        "large-body-only",
+        &base_url,
+        "upstream-key",
+    )
+    .await;
+    let content = "a".repeat(33 * 1024 * 1024);
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "large-body-fallback",
+            "messages": [{ "role": "user", "content": content }]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", &body[..body.len().min(500)]);
+    let upstream_calls = ctx
+        .captured_bodies
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(_, body)| body["model"] == json!("large-body-fallback"))
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(upstream_calls, vec!["chat".to_string()]);
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "large-body-only",
+            "messages": [{ "role": "user", "content": content }]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE, "{body}");
+    let body: Value = serde_json::from_str(&body).expect("json body");
+    assert_eq!(body["error"]["code"], json!("payload_too_large"), "{body}");
+}
+
+#[tokio::test]
+async fn admin_reload_refreshes_runtime_and_models_list_sees_new_provider() {
+    let ctx = setup().await;
+
+    let post_reload = |cookie: String| {
+        let router = ctx.router.clone();
+        async move {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/api/dashboard/admin/reload")
+                .header("cookie", cookie)
+                .body(Body::empty())
+                .unwrap();
+            let resp = router.oneshot(req).await.unwrap();
+            let status = resp.status();
+            let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+            let body: Value = serde_json::from_slice(&bytes).unwrap();
+            (status, body)
+        }
+    };
+
+    let tenant_cookie = dashboard_session_cookie(&ctx, "tenant-1", "test-password").await;
+    let (status, _) = post_reload(tenant_cookie).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    ctx.state
+        .user_store
+        .create_user(
+            "reload-admin",
+            "admin-password",
+            monoize::users::UserRole::Admin,
+            &[],
+        )
+        .await
+        .expect("create admin");
+    let admin_cookie = dashboard_session_cookie(&ctx, "reload-admin", "admin-password").await;
+
+    create_test_provider(
+        &ctx.state,
+        "up-reloaded",
+        monoize::monoize_routing::MonoizeProviderType::Responses,
+        "reloaded-model",
+        "http://127.0.0.1:1",
+        "upstream-key",
+    )
+    .await;
+    let mut settings = ctx.state.settings_store.get_all().await.unwrap();
+    settings
+        .model_aliases
+        .insert("reload-alias".to_string(), "reloaded-model".to_string());
+    ctx.state
+        .settings_store
+        .update_all(&settings)
+        .await
+        .unwrap();
+    assert!(
+        !ctx.state
+            .monoize_runtime
+            .read()
+            .await
+            .model_aliases
+            .contains_key("reload-alias")
+    );
+
+    let (status, body) = post_reload(admin_cookie).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(
+        body["restart_required"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("listen"))
+    );
+    assert_eq!(
+        ctx.state
+            .monoize_runtime
+            .read()
+            .await
+            .model_aliases
+            .get("reload-alias")
+            .map(String::as_str),
+        Some("reloaded-model")
+    );
+
+    let (status, body) = json_get(&ctx, "/v1/models").await;
+    assert_eq!(status, StatusCode::OK);
+    let v: Value = serde_json::from_str(&body).unwrap();
+    assert!(
+        v["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|item| item["id"] == "reloaded-model"),
+        "{body}"
+    );
+}
+
+#[tokio::test]
+async fn routing_headers_name_the_serving_attempt_only_when_enabled() {
+    let ctx = setup().await;
+    let provider = ctx
+        .state
+        .monoize_store
+        .list_providers()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|provider| provider.name == "up-resp")
+        .expect("up-resp provider");
+    let channel_id = provider.channels[0].id.clone();
+
+    let send = || {
+        Request::builder()
+            .method("POST")
+            .uri("/v1/responses")
+            .header(CONTENT_TYPE, "application/json")
+            .header(AUTHORIZATION, ctx.auth_header.clone())
+            .body(Body::from(
+                json!({ "model": "gpt-5-mini", "input": "hi" }).to_string(),
+            ))
+            .unwrap()
+    };
+
+    let resp = ctx.router.clone().oneshot(send()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers().get("x-monoize-provider-id").is_none());
+    assert!(resp.headers().get("x-monoize-channel-id").is_none());
+    assert!(resp.headers().get("x-monoize-upstream-model").is_none());
+
+    ctx.state
+        .monoize_runtime
+        .write()
+        .await
+        .expose_routing_headers = true;
+    let resp = ctx.router.clone().oneshot(send()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let header = |name: &str| {
+        resp.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    assert_eq!(header("x-monoize-provider-id"), Some(provider.id.clone()));
+    assert_eq!(header("x-monoize-channel-id"), Some(channel_id));
+    assert_eq!(
+        header("x-monoize-upstream-model").as_deref(),
+        Some("gpt-5-mini")
+    );
+}
+
+#[tokio::test]
+async fn force_channel_headers_pin_routing_for_admin_keys_only() {
+    let ctx = setup().await;
+    let base_url = ctx
+        .state
+        .monoize_store
+        .list_providers()
+        .await
+        .unwrap()
+        .iter()
+        .find_map(|p| p.channels.first().map(|c| c.base_url.clone()))
+        .expect("at least one existing channel base url");
+    create_test_provider(
+        &ctx.state,
+        "up-resp-forced",
+        monoize::monoize_routing::MonoizeProviderType::Responses,
+        "gpt-5-mini",
+        &base_url,
+        "upstream-key",
+    )
+    .await;
+    let providers = ctx.state.monoize_store.list_providers().await.unwrap();
+    let channel_of = |name: &str| {
+        providers
+            .iter()
+            .find(|provider| provider.name == name)
+            .map(|provider| provider.channels[0].id.clone())
+            .expect("provider channel")
+    };
+    let default_channel = channel_of("up-resp");
+    let forced_channel = channel_of("up-resp-forced");
+    ctx.state
+        .monoize_runtime
+        .write()
+        .await
+        .expose_routing_headers = true;
+
+    let send = || async {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/v1/responses")
+            .header(CONTENT_TYPE, "application/json")
+            .header(AUTHORIZATION, ctx.auth_header.clone())
+            .header("x-monoize-force-channel", forced_channel.as_str())
+            .header("x-monoize-force-upstream-model", "gpt-5-mini-chat")
+            .body(Body::from(
+                json!({ "model": "gpt-5-mini", "input": "hi" }).to_string(),
+            ))
+            .unwrap();
+        let resp = ctx.router.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let header = |name: &str| {
+            resp.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        (
+            header("x-monoize-channel-id"),
+            header("x-monoize-upstream-model"),
+        )
+    };
+
+    let (channel, model) = send().await;
+    assert_eq!(channel.as_deref(), Some(default_channel.as_str()));
+    assert_eq!(model.as_deref(), Some("gpt-5-mini"));
+
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .unwrap()
+        .expect("tenant user");
+    ctx.state
+        .user_store
+        .update_user(
+            &user.id,
+            None,
+            None,
+            Some(monoize::users::UserRole::Admin),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect("promote to admin");
+
+    let (channel, model) = send().await;
+    assert_eq!(channel.as_deref(), Some(forced_channel.as_str()));
+    assert_eq!(model.as_deref(), Some("gpt-5-mini-chat"));
+}
+
+#[tokio::test]
+async fn slow_attempt_is_hedged_onto_the_next_channel_after_the_configured_delay() {
+    let ctx = setup().await;
+    let base_url = existing_channel_base_url(&ctx).await;
+    let mut channel = concurrency_test_channel("up-resp-slow-ch1", &base_url, 0, None);
+    channel.provider_type = monoize::monoize_routing::MonoizeProviderType::Responses;
+    channel.models = HashMap::from([(
+        "gpt-5-mini".to_string(),
+        monoize::monoize_routing::MonoizeModelEntry {
+            redirect: None,
+            multiplier: 1.0,
+            supports_streaming: None,
+        },
+    )]);
+    let slow = create_routing_test_provider(
+        &ctx,
+        "up-resp-slow",
+        vec![channel],
+        vec![monoize::transforms::TransformRuleConfig {
+            transform: "set_field".to_string(),
+            enabled: true,
+            models: None,
+            phase: monoize::transforms::Phase::Request,
+            config: json!({
+                "path": "force_upstream_delay_ms",
+                "value": 3000
+            }),
+        }],
+        Some(-1),
+    )
+    .await
+    .unwrap();
+    {
+        let mut runtime = ctx.state.monoize_runtime.write().await;
+        runtime.expose_routing_headers = true;
+        runtime
+            .model_hedge_after_ms
+            .insert("gpt-5-mini".to_string(), 100);
+    }
+
+    let started = std::time::Instant::now();
+    let req = Request::builder()
+        .method("POST")
+        .uri("/v1/responses")
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, ctx.auth_header.clone())
+        .body(Body::from(
+            json!({ "model": "gpt-5-mini", "input": "hi" }).to_string(),
+        ))
+        .unwrap();
+    let resp = ctx.router.clone().oneshot(req).await.unwrap();
+    let elapsed = started.elapsed();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let channel = resp
+        .headers()
+        .get("x-monoize-channel-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    assert!(channel.is_some());
+    assert_ne!(channel.as_deref(), Some(slow.channels[0].id.as_str()));
+    assert!(
+        elapsed < std::time::Duration::from_millis(2000),
+        "hedged request should not wait for the slow channel: {elapsed:?}"
+    );
+}
+
+async fn post_delayed_responses_request(
+    ctx: &TestContext,
+    timeout_header: &str,
+) -> (StatusCode, std::time::Duration) {
+    let started = std::time::Instant::now();
+    let req = Request::builder()
+        .method("POST")
+        .uri("/v1/responses")
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, ctx.auth_header.clone())
+        .header("x-monoize-timeout-ms", timeout_header)
+        .body(Body::from(
+            json!({
+                "model": "gpt-5-mini",
+                "input": "hi",
+                "force_upstream_delay_ms": 1500
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let resp = ctx.router.clone().oneshot(req).await.unwrap();
+    (resp.status(), started.elapsed())
+}
+
+#[tokio::test]
+async fn request_timeout_header_lowers_the_upstream_timeout() {
+    let ctx = setup().await;
+
+    let (status, elapsed) = post_delayed_responses_request(&ctx, "200").await;
+    assert_ne!(status, StatusCode::OK);
+    assert!(
+        elapsed < std::time::Duration::from_millis(1000),
+        "the 200ms header timeout should cut the delayed call short: {elapsed:?}"
+    );
+
+    let (status, _) = post_delayed_responses_request(&ctx, "not-a-number").await;
+    assert_eq!(status, StatusCode::OK, "invalid header keeps the default");
+}
+
+#[tokio::test]
+async fn request_timeout_header_is_clamped_to_the_server_ceiling() {
+    let ctx = setup().await;
+    ctx.state
+        .monoize_runtime
+        .write()
+        .await
+        .max_request_timeout_ms = 200;
+
+    let (status, elapsed) = post_delayed_responses_request(&ctx, "60000").await;
+    assert_ne!(status, StatusCode::OK);
+    assert!(
+        elapsed < std::time::Duration::from_millis(1000),
+        "an over-ceiling header should be clamped to 200ms: {elapsed:?}"
+    );
+}
+
+#[tokio::test]
+async fn echo_model_replies_with_last_user_message_and_logs_zero_charge() {
+    let ctx = setup().await;
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "monoize-echo",
+            "messages": [
+                {"role": "system", "content": "be brief"},
+                {"role": "user", "content": "first question"},
+                {"role": "assistant", "content": "first answer"},
+                {"role": "user", "content": "say it back please"}
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let v: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(v["model"], "monoize-echo");
+    assert_eq!(v["choices"][0]["message"]["content"], "say it back please");
+    assert_eq!(v["choices"][0]["finish_reason"], "stop");
+    assert_eq!(v["usage"]["prompt_tokens"], 10);
+    assert_eq!(v["usage"]["completion_tokens"], 4);
+
+    let user = ctx
+        .state
+        .user_store
+        .get_user_by_username("tenant-1")
+        .await
+        .expect("query user")
+        .expect("user exists");
+    let mut matched = None;
+    for _ in 0..20 {
+        ctx.state.user_store.flush_all_batchers().await;
+        let (logs, _, _) = ctx
+            .state
+            .user_store
+            .list_request_logs_by_user(&user.id, 100, 0, None, None, None, None, None, None)
+            .await
+            .expect("list request logs");
+        matched = logs
+            .into_iter()
+            .find(|log| log.model == "monoize-echo" && !log.is_stream);
+        if matched.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    let log = matched.expect("echo request should be logged");
+    assert_eq!(log.tokens.input, Some(10));
+    assert_eq!(log.tokens.output, Some(4));
+    assert_eq!(log.billing.charge_nano_usd.as_deref(), Some("0"));
+}
+
+#[tokio::test]
+async fn echo_model_streams_the_last_user_message() {
+    let ctx = setup().await;
+    let req = Request::builder()
+        .method("POST")
+        .uri("/v1/responses")
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, ctx.auth_header.clone())
+        .body(Body::from(
+            json!({
+                "model": "monoize-echo",
+                "input": "stream me back",
+                "stream": true
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let resp = ctx.router.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8_lossy(&bytes).to_string();
+
+    let events = parse_responses_sse_json(&text);
+    let deltas: String = events
+        .iter()
+        .filter(|(event, _)| event == "response.output_text.delta")
+        .filter_map(|(_, data)| data["delta"].as_str())
+        .collect();
+    assert_eq!(deltas, "stream me back", "{text}");
+    let (_, completed) = events
+        .iter()
+        .find(|(event, _)| event == "response.completed")
+        .expect("response.completed event");
+    assert_eq!(completed["response"]["model"], "monoize-echo");
+    assert_eq!(completed["response"]["usage"]["output_tokens"], 3);
+}
@@ -1005,6 +1005,7 @@ async fn chat_streaming_from_responses_includes_terminal_usage() {
                 "model":"gpt-5-mini",
                 "messages":[{"role":"user","content":"stream usage"}],
                 "stream": true,
+                "stream_options": {"include_usage": true},
                 "emit_usage": true
             })
             .to_string(),
@@ -1051,6 +1052,7 @@ async fn chat_streaming_openrouter_final_usage_chunk_shape() {
                 "model":"gpt-5-mini",
                 "messages":[{"role":"user","content":"stream usage"}],
                 "stream": true,
+                "stream_options": {"include_usage": true},
                 "emit_usage": true
             })
             .to_string(),
@@ -1122,6 +1124,43 @@ async fn chat_streaming_openrouter_final_usage_chunk_shape() {
     assert_eq!(usage_chunk["usage"]["completion_tokens"].as_u64(), Some(8));
 }
 
+#[tokio::test]
+async fn chat_streaming_usage_chunk_follows_stream_options_include_usage() {
+    let ctx = setup().await;
+    for (stream_options, expect_usage) in [
+        (Some(json!({"include_usage": true})), true),
+        (Some(json!({"include_usage": false})), false),
+        (None, false),
+    ] {
+        let mut body = json!({
+            "model":"gpt-5-mini",
+            "messages":[{"role":"user","content":"stream usage"}],
+            "stream": true,
+            "emit_usage": true
+        });
+        if let Some(stream_options) = stream_options.clone() {
+            body["stream_options"] = stream_options;
+        }
+        let (status, text) = json_post(&ctx, "/v1/chat/completions", body).await;
+        assert_eq!(status, StatusCode::OK, "{text}");
+
+        let usage_chunks = text
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .filter_map(|payload| serde_json::from_str::<Value>(payload).ok())
+            .filter(|chunk| chunk.get("usage").is_some_and(|usage| !usage.is_null()))
+            .collect::<Vec<_>>();
+        if expect_usage {
+            assert_eq!(usage_chunks.len(), 1, "{stream_options:?}: {text}");
+            assert_eq!(usage_chunks[0]["choices"], json!([]));
+            assert_eq!(usage_chunks[0]["usage"]["prompt_tokens"].as_u64(), Some(12));
+        } else {
+            assert!(usage_chunks.is_empty(), "{stream_options:?}: {text}");
+        }
+        assert_eq!(count_done_sentinels(&text), 1, "{text}");
+    }
+}
+
 #[tokio::test]
 async fn chat_streaming_plaintext_reasoning_to_summary_rewrites_reasoning_events() {
     let ctx = setup().await;
@@ -1134,6 +1173,7 @@ async fn chat_streaming_plaintext_reasoning_to_summary_rewrites_reasoning_events
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: None,
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     ctx.state
@@ -1148,7 +1188,12 @@ async fn chat_streaming_plaintext_reasoning_to_summary_rewrites_reasoning_events
                 provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -1179,6 +1224,9 @@ async fn chat_streaming_plaintext_reasoning_to_summary_rewrites_reasoning_events
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-1),
         })
@@ -1231,6 +1279,7 @@ async fn chat_streaming_plaintext_reasoning_to_summary_preserves_encrypted_reaso
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: None,
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     ctx.state
@@ -1245,7 +1294,12 @@ async fn chat_streaming_plaintext_reasoning_to_summary_preserves_encrypted_reaso
                 provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -1276,6 +1330,9 @@ async fn chat_streaming_plaintext_reasoning_to_summary_preserves_encrypted_reaso
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-1),
         })
@@ -1338,7 +1395,8 @@ async fn chat_streaming_content_only_from_chat_upstream_has_terminal_chunk_and_u
             json!({
                 "model":"gpt-5-mini-chat",
                 "messages":[{"role":"user","content":"hello"}],
-                "stream": true
+                "stream": true,
+                "stream_options": {"include_usage": true}
             })
             .to_string(),
         ))
@@ -1577,6 +1635,46 @@ async fn chat_streaming_openrouter_error_chunks_are_terminal_and_preserved() {
     );
 }
 
+#[tokio::test]
+async fn chat_streaming_translates_mid_stream_messages_error_into_chat_error() {
+    let ctx = setup().await;
+    let req = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, ctx.auth_header.clone())
+        .body(Body::from(
+            json!({
+                "model": "gpt-5-mini-msg",
+                "messages": [{ "role": "user", "content": "stream error" }],
+                "stream": true,
+                "stream_mode": "messages_mid_error"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let resp = ctx.router.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8_lossy(&bytes).to_string();
+
+    assert!(text.contains("partial answer"), "{text}");
+    let error = parse_sse_frames(&text)
+        .into_iter()
+        .filter_map(|(_, data)| serde_json::from_str::<Value>(&data).ok())
+        .find(|payload| payload.get("error").is_some())
+        .expect("chat error frame");
+    assert_eq!(error["error"]["code"].as_str(), Some("overloaded_error"));
+    assert_eq!(
+        error["error"]["message"].as_str(),
+        Some("mock mid-stream overload")
+    );
+    assert!(
+        !text.contains("\"finish_reason\":\"stop\""),
+        "error stream must not synthesize success: {text}"
+    );
+}
+
 #[tokio::test]
 async fn chat_streaming_preserves_choice_logprobs_on_nonterminal_frame() {
     let ctx = setup().await;
@@ -1638,3 +1736,149 @@ async fn chat_streaming_deepseek_resource_finish_reason_round_trips() {
     assert_eq!(terminal["choices"][0]["provider_marker"], json!("deepseek"));
     assert!(terminal.get("error").is_none(), "{text}");
 }
+
+#[tokio::test]
+async fn chat_streaming_maps_gemini_safety_block_to_content_filter() {
+    let ctx = setup().await;
+    let (status, text) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gemini-2.5-flash",
+            "messages": [{ "role": "user", "content": "blocked" }],
+            "stream": true,
+            "native_response_mode": "gemini_safety_block"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let finish_reasons: Vec<Value> = parse_sse_frames(&text)
+        .into_iter()
+        .filter_map(|(_, data)| serde_json::from_str::<Value>(&data).ok())
+        .filter_map(|chunk| {
+            chunk["choices"]
+                .get(0)
+                .map(|choice| choice["finish_reason"].clone())
+        })
+        .filter(|reason| !reason.is_null())
+        .collect();
+    assert_eq!(finish_reasons, vec![json!("content_filter")]);
+    assert!(
+        text.contains("The response was blocked by Gemini (SAFETY)."),
+        "{text}"
+    );
+    assert!(text.contains("data: [DONE]"));
+}
+
+#[tokio::test]
+async fn chat_streaming_to_non_streaming_model_synthesizes_stream_from_nonstream_call() {
+    let ctx = setup().await;
+    let (upstream_addr, _, captured_bodies) = start_upstream().await;
+    seed_test_model_pricing(&ctx.state, &["gpt-5-mini-nostream"]).await;
+
+    let mut models = HashMap::new();
+    models.insert(
+        "gpt-5-mini-nostream".to_string(),
+        monoize::monoize_routing::MonoizeModelEntry {
+            redirect: None,
+            multiplier: 1.0,
+            supports_streaming: Some(false),
+        },
+    );
+    ctx.state
+        .monoize_store
+        .create_provider(monoize::monoize_routing::CreateMonoizeProviderInput {
+            name: "mono-nostream-chat".to_string(),
+            api_type_overrides: Vec::new(),
+            groups: Vec::new(),
+            channels: vec![monoize::monoize_routing::CreateMonoizeChannelInput {
+                id: Some("mono-nostream-chat-ch1".to_string()),
+                name: "mono-nostream-chat-ch1".to_string(),
+                provider_type: monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
+                base_url: format!("http://{upstream_addr}"),
+                api_key: Some("upstream-key".to_string()),
+                api_keys: None,
+                weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
+                enabled: true,
+                passive_failure_count_threshold_override: None,
+                passive_cooldown_seconds_override: None,
+                passive_window_seconds_override: None,
+                passive_rate_limit_cooldown_seconds_override: None,
+                models,
+                active_probe_enabled_override: None,
+                active_probe_interval_seconds_override: None,
+                active_probe_success_threshold_override: None,
+                active_probe_model_override: None,
+            }],
+            max_retries: -1,
+            channel_max_retries: 0,
+            channel_retry_interval_ms: 0,
+            circuit_breaker_enabled: true,
+            per_model_circuit_break: false,
+            transforms: Vec::new(),
+            active_probe_enabled_override: None,
+            active_probe_interval_seconds_override: None,
+            active_probe_success_threshold_override: None,
+            active_probe_model_override: None,
+            request_timeout_ms_override: None,
+            extra_fields_whitelist: None,
+            strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
+            enabled: true,
+            priority: None,
+        })
+        .await
+        .unwrap();
+
+    let (status, text) = json_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gpt-5-mini-nostream",
+            "messages": [{ "role": "user", "content": "hello" }],
+            "stream": true
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{text}");
+
+    let upstream_body = captured_bodies
+        .lock()
+        .expect("captured bodies lock")
+        .iter()
+        .rev()
+        .find(|(name, _)| name == "chat")
+        .map(|(_, body)| body.clone())
+        .expect("chat upstream body");
+    assert_ne!(upstream_body["stream"], json!(true), "{upstream_body}");
+
+    let mut content = String::new();
+    let mut finish_reasons = Vec::new();
+    for payload in text.lines().filter_map(|line| line.strip_prefix("data: ")) {
+        if payload == "[DONE]" {
+            continue;
+        }
+        let chunk: Value = serde_json::from_str(payload).expect("chat chunk json");
+        assert_eq!(chunk["object"], "chat.completion.chunk", "{text}");
+        if let Some(choice) = chunk["choices"].get(0) {
+            if let Some(delta) = choice["delta"]["content"].as_str() {
+                content.push_str(delta);
+            }
+            if let Some(reason) = choice["finish_reason"].as_str() {
+                finish_reasons.push(reason.to_string());
+            }
+        }
+    }
+    assert!(
+        !content.is_empty(),
+        "synthetic stream carries content: {text}"
+    );
+    assert_eq!(finish_reasons, vec!["stop".to_string()], "{text}");
+    assert_eq!(count_done_sentinels(&text), 1, "{text}");
+}
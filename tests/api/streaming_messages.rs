@@ -581,6 +581,7 @@ async fn messages_streaming_plaintext_reasoning_to_summary_preserves_thinking_de
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: None,
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     ctx.state
@@ -595,7 +596,12 @@ async fn messages_streaming_plaintext_reasoning_to_summary_preserves_thinking_de
                 provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -626,6 +632,9 @@ async fn messages_streaming_plaintext_reasoning_to_summary_preserves_thinking_de
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-1),
         })
@@ -1249,6 +1258,7 @@ async fn messages_stream_passthrough_transform_preserves_plaintext_reasoning_chu
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: Some("gpt-5-mini-msg".to_string()),
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     ctx.state
@@ -1263,7 +1273,12 @@ async fn messages_stream_passthrough_transform_preserves_plaintext_reasoning_chu
                 provider_type: monoize::monoize_routing::MonoizeProviderType::Messages,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -1294,6 +1309,9 @@ async fn messages_stream_passthrough_transform_preserves_plaintext_reasoning_chu
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-1),
         })
@@ -1378,6 +1396,48 @@ async fn messages_stream_passthrough_preserves_messages_upstream_error() {
     );
 }
 
+#[tokio::test]
+async fn messages_stream_forwards_mid_stream_upstream_error_after_partial_output() {
+    let ctx = setup().await;
+    let text = collect_messages_stream_text(
+        &ctx,
+        json!({
+            "model": "gpt-5-mini-msg",
+            "max_tokens": 64,
+            "messages": [{ "role": "user", "content": [{ "type": "text", "text": "stream error" }] }],
+            "stream": true,
+            "stream_mode": "messages_mid_error"
+        }),
+    )
+    .await;
+    let events: Vec<Value> = parse_sse_frames(&text)
+        .into_iter()
+        .filter_map(|(_, data)| serde_json::from_str::<Value>(&data).ok())
+        .collect();
+
+    let partial = events
+        .iter()
+        .position(|event| event["delta"]["text"].as_str() == Some("partial answer"))
+        .expect("partial output must reach the client before the error");
+    let error = events
+        .iter()
+        .position(|event| event["type"].as_str() == Some("error"))
+        .expect("mid-stream upstream error must be forwarded as a Messages error event");
+    assert!(partial < error, "{text}");
+    assert_eq!(
+        events[error]["error"]["type"].as_str(),
+        Some("overloaded_error")
+    );
+    assert_eq!(
+        events[error]["error"]["message"].as_str(),
+        Some("mock mid-stream overload")
+    );
+    assert!(
+        events[error + 1..].is_empty(),
+        "the stream must stop after the error event: {text}"
+    );
+}
+
 #[tokio::test]
 async fn messages_stream_malformed_json_is_terminal_even_before_message_stop() {
     let ctx = setup().await;
@@ -2231,3 +2291,26 @@ async fn messages_stream_signature_delta_carries_sigil_from_responses_upstream()
         "ordinary thinking content_block_start must carry an empty signature stub"
     );
 }
+
+#[tokio::test]
+async fn messages_stream_maps_gemini_prompt_block_to_refusal_stop_reason() {
+    let ctx = setup().await;
+    let events = collect_messages_stream_events(
+        &ctx,
+        json!({
+            "model": "gemini-2.5-flash",
+            "max_tokens": 64,
+            "messages": [{ "role": "user", "content": "blocked" }],
+            "stream": true,
+            "native_response_mode": "gemini_prompt_blocked"
+        }),
+    )
+    .await;
+
+    assert_messages_stream_invariants(&events, "gemini prompt block→msg stream");
+    let delta = events
+        .iter()
+        .find(|e| e["type"] == "message_delta")
+        .expect("message_delta");
+    assert_eq!(delta["delta"]["stop_reason"], json!("refusal"));
+}
@@ -42,6 +42,11 @@ mod completed_state {
     include!("streaming_responses/completed_state.rs");
 }
 
+mod grok_fixtures {
+    use super::*;
+    include!("streaming_responses/grok_fixtures.rs");
+}
+
 #[tokio::test]
 async fn responses_stream_success_binds_native_response_id_for_stateful_continuation() {
     let ctx = setup().await;
@@ -74,3 +79,154 @@ async fn responses_stream_success_binds_native_response_id_for_stateful_continua
     }
     assert!(bound, "successful Responses stream id was not bound");
 }
+
+#[tokio::test]
+async fn responses_to_responses_stream_passes_upstream_frames_through() {
+    let ctx = setup().await;
+    let token = ctx
+        .auth_header
+        .strip_prefix("Bearer ")
+        .expect("bearer token");
+    let key = ctx
+        .state
+        .user_store
+        .get_api_key_by_prefix(&token[..12])
+        .await
+        .expect("load key by prefix")
+        .expect("api key exists");
+    ctx.state
+        .user_store
+        .update_api_key(
+            &key.id,
+            monoize::users::UpdateApiKeyInput {
+                name: None,
+                enabled: None,
+                sub_account_enabled: None,
+                model_limits_enabled: None,
+                model_limits: None,
+                allow_models: None,
+                deny_models: None,
+                ip_whitelist: None,
+                allowed_groups: None,
+                max_multiplier: None,
+                transforms: None,
+                model_redirects: None,
+                reasoning_envelope_enabled: Some(false),
+                request_capture_mode: None,
+                response_cache_enabled: None,
+                max_request_charge_nano_usd: None,
+                expires_at: None,
+            },
+            false,
+        )
+        .await
+        .expect("disable reasoning envelope");
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({
+            "model": "gpt-5-mini",
+            "input": "stream",
+            "stream": true,
+            "stream_mode": "unmodeled_event"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let frames = parse_responses_sse_json(&body);
+    let events: Vec<&str> = frames.iter().map(|(event, _)| event.as_str()).collect();
+    assert_eq!(
+        events,
+        vec![
+            "response.created",
+            "response.custom_debug",
+            "response.output_text.delta",
+            "response.completed"
+        ]
+    );
+    let sequence_numbers: Vec<u64> = frames
+        .iter()
+        .map(|(_, payload)| {
+            payload["sequence_number"]
+                .as_u64()
+                .expect("sequence_number")
+        })
+        .collect();
+    assert_eq!(sequence_numbers, vec![40, 41, 42, 43]);
+    assert_eq!(
+        frames[1].1,
+        json!({
+            "type": "response.custom_debug",
+            "sequence_number": 41,
+            "trace": { "shard": "b", "hops": [1, 2] }
+        })
+    );
+    assert_eq!(frames[0].1["response"]["model"], "gpt-5-mini");
+    assert_eq!(frames[3].1["response"]["model"], "gpt-5-mini");
+    assert_eq!(count_done_sentinels(&body), 1);
+}
+
+#[tokio::test]
+async fn responses_stream_emits_retry_hint_and_sequential_ids_when_enabled() {
+    let ctx = setup().await;
+    {
+        let mut runtime = ctx.state.monoize_runtime.write().await;
+        runtime.sse_retry_ms = 3000;
+        runtime.sse_event_ids = true;
+    }
+
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({
+            "model": "gpt-5-mini",
+            "input": "framed stream",
+            "stream": true
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+
+    let frames: Vec<&str> = body
+        .split("\n\n")
+        .filter(|frame| frame.lines().any(|line| line.starts_with("data:")))
+        .collect();
+    assert!(frames.len() > 2, "{body}");
+    assert!(
+        frames[0].lines().any(|line| line == "retry: 3000"),
+        "{body}"
+    );
+    assert_eq!(body.matches("retry:").count(), 1, "{body}");
+    for (index, frame) in frames.iter().enumerate() {
+        let id = frame
+            .lines()
+            .find_map(|line| line.strip_prefix("id: "))
+            .unwrap_or_else(|| panic!("frame without id: {frame}"));
+        assert_eq!(id, (index + 1).to_string(), "{body}");
+    }
+}
+
+#[tokio::test]
+async fn responses_stream_omits_retry_and_ids_by_default() {
+    let ctx = setup().await;
+    let (status, body) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({
+            "model": "gpt-5-mini",
+            "input": "plain stream",
+            "stream": true
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(body.contains("event: response.completed"), "{body}");
+    assert!(
+        !body
+            .lines()
+            .any(|line| line.starts_with("id:") || line.starts_with("retry:")),
+        "{body}"
+    );
+}
@@ -399,3 +399,32 @@ async fn responses_streaming_reencodes_greedy_merged_items_with_canonical_sse_bo
     assert!(!text.contains("\"part\":{\"text\":\"\",\"type\":\"reasoning\"}"));
     assert!(!text.contains("event: response.content_part.added\ndata: {\"content_index\":2"));
 }
+
+#[tokio::test]
+async fn responses_streaming_maps_gemini_safety_block_to_content_filter_incomplete() {
+    let ctx = setup().await;
+    let (status, text) = json_post(
+        &ctx,
+        "/v1/responses",
+        json!({
+            "model": "gemini-2.5-flash",
+            "input": "blocked",
+            "stream": true,
+            "native_response_mode": "gemini_safety_block"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let frames = parse_responses_sse_json(&text);
+
+    assert!(!frames.iter().any(|(event, _)| event == "response.completed"));
+    let incomplete = frames
+        .iter()
+        .find(|(event, _)| event == "response.incomplete")
+        .map(|(_, payload)| payload)
+        .expect("response.incomplete frame");
+    assert_eq!(
+        incomplete["response"]["incomplete_details"]["reason"],
+        json!("content_filter")
+    );
+}
@@ -11,6 +11,7 @@ async fn responses_streaming_plaintext_reasoning_to_summary_rewrites_reasoning_e
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: None,
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     ctx.state
@@ -25,7 +26,12 @@ async fn responses_streaming_plaintext_reasoning_to_summary_rewrites_reasoning_e
                 provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -56,6 +62,9 @@ async fn responses_streaming_plaintext_reasoning_to_summary_rewrites_reasoning_e
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-1),
         })
@@ -208,6 +217,7 @@ async fn responses_streaming_markdown_image_transforms_emit_image_part_and_appen
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: None,
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     ctx.state
@@ -222,7 +232,12 @@ async fn responses_streaming_markdown_image_transforms_emit_image_part_and_appen
                 provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -262,6 +277,9 @@ async fn responses_streaming_markdown_image_transforms_emit_image_part_and_appen
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-1),
         })
@@ -0,0 +1,211 @@
+fn chat_stream_chunks(text: &str) -> Vec<Value> {
+    text.lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter(|payload| *payload != "[DONE]")
+        .filter_map(|payload| serde_json::from_str::<Value>(payload).ok())
+        .collect()
+}
+
+async fn stream_post(ctx: &TestContext, path: &str, body: Value) -> String {
+    let req = Request::builder()
+        .method("POST")
+        .uri(path)
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, ctx.auth_header.clone())
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let resp = ctx.router.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+fn weather_tool() -> Value {
+    json!({
+        "type": "function",
+        "name": "get_weather",
+        "parameters": {
+            "type": "object",
+            "properties": { "city": { "type": "string" }, "unit": { "type": "string" } }
+        }
+    })
+}
+
+#[tokio::test]
+async fn grok_text_stream_keeps_citations_and_usage_on_responses_downstream() {
+    let ctx = setup().await;
+    let text = stream_post(
+        &ctx,
+        "/v1/responses",
+        json!({
+            "model": "gpt-5-mini",
+            "input": "what is monoize?",
+            "stream": true,
+            "stream_mode": "grok_text_citations"
+        }),
+    )
+    .await;
+
+    let frames = parse_responses_sse_json(&text);
+    let deltas: String = frames
+        .iter()
+        .filter(|(event, _)| event == "response.output_text.delta")
+        .filter_map(|(_, payload)| payload["delta"].as_str())
+        .collect();
+    assert_eq!(deltas, "Monoize is an LLM proxy.", "{text}");
+
+    let completed = frames
+        .iter()
+        .find(|(event, _)| event == "response.completed")
+        .map(|(_, payload)| &payload["response"])
+        .unwrap_or_else(|| panic!("missing response.completed: {text}"));
+    let message = completed["output"]
+        .as_array()
+        .and_then(|output| output.iter().find(|item| item["type"] == "message"))
+        .unwrap_or_else(|| panic!("missing message output: {completed}"));
+    assert_eq!(message["content"][0]["text"], "Monoize is an LLM proxy.");
+    assert_eq!(
+        message["content"][0]["annotations"],
+        json!([{
+            "type": "url_citation",
+            "url": "https://github.com/Ikaleio/monoize",
+            "title": "1",
+            "start_index": 11,
+            "end_index": 24
+        }]),
+        "{completed}"
+    );
+
+    let usage = &completed["usage"];
+    assert_eq!(usage["input_tokens"], 12, "{usage}");
+    assert_eq!(usage["output_tokens"], 8, "{usage}");
+    assert_eq!(usage["input_tokens_details"]["cached_tokens"], 4, "{usage}");
+    assert_eq!(usage["output_tokens_details"]["reasoning_tokens"], 3, "{usage}");
+    assert_eq!(usage["num_sources_used"], 1, "{usage}");
+    assert_eq!(usage["cost_in_usd_ticks"], 41250000, "{usage}");
+}
+
+#[tokio::test]
+async fn grok_text_stream_translates_to_chat_with_terminal_usage() {
+    let ctx = setup().await;
+    let text = stream_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gpt-5-mini",
+            "messages": [{ "role": "user", "content": "what is monoize?" }],
+            "stream": true,
+            "stream_options": { "include_usage": true },
+            "stream_mode": "grok_text_citations"
+        }),
+    )
+    .await;
+
+    let chunks = chat_stream_chunks(&text);
+    let content: String = chunks
+        .iter()
+        .filter_map(|chunk| chunk["choices"][0]["delta"]["content"].as_str())
+        .collect();
+    assert_eq!(content, "Monoize is an LLM proxy.", "{text}");
+    let finish_reasons: Vec<&str> = chunks
+        .iter()
+        .filter_map(|chunk| chunk["choices"][0]["finish_reason"].as_str())
+        .collect();
+    assert_eq!(finish_reasons, vec!["stop"], "{text}");
+    let usage = chunks
+        .iter()
+        .find_map(|chunk| chunk.get("usage").filter(|usage| !usage.is_null()))
+        .unwrap_or_else(|| panic!("missing usage chunk: {text}"));
+    assert_eq!(usage["prompt_tokens"], 12, "{usage}");
+    assert_eq!(usage["completion_tokens"], 8, "{usage}");
+    assert_eq!(count_done_sentinels(&text), 1, "{text}");
+}
+
+#[tokio::test]
+async fn grok_tool_call_stream_translates_to_responses_function_call() {
+    let ctx = setup().await;
+    let text = stream_post(
+        &ctx,
+        "/v1/responses",
+        json!({
+            "model": "gpt-5-mini",
+            "input": "weather in Paris?",
+            "tools": [weather_tool()],
+            "stream": true,
+            "stream_mode": "grok_tool_call"
+        }),
+    )
+    .await;
+
+    let frames = parse_responses_sse_json(&text);
+    let arguments: String = frames
+        .iter()
+        .filter(|(event, _)| event == "response.function_call_arguments.delta")
+        .filter_map(|(_, payload)| payload["delta"].as_str())
+        .collect();
+    assert_eq!(arguments, r#"{"city":"Paris","unit":"celsius"}"#, "{text}");
+
+    let completed = frames
+        .iter()
+        .find(|(event, _)| event == "response.completed")
+        .map(|(_, payload)| &payload["response"])
+        .unwrap_or_else(|| panic!("missing response.completed: {text}"));
+    let calls: Vec<&Value> = completed["output"]
+        .as_array()
+        .expect("output array")
+        .iter()
+        .filter(|item| item["type"] == "function_call")
+        .collect();
+    assert_eq!(calls.len(), 1, "{completed}");
+    assert_eq!(calls[0]["call_id"], "call_grok_1");
+    assert_eq!(calls[0]["name"], "get_weather");
+    assert_eq!(calls[0]["arguments"], r#"{"city":"Paris","unit":"celsius"}"#);
+    assert_eq!(completed["usage"]["input_tokens"], 40);
+    assert_eq!(completed["usage"]["output_tokens"], 18);
+}
+
+#[tokio::test]
+async fn grok_tool_call_stream_translates_to_chat_tool_calls() {
+    let ctx = setup().await;
+    let text = stream_post(
+        &ctx,
+        "/v1/chat/completions",
+        json!({
+            "model": "gpt-5-mini",
+            "messages": [{ "role": "user", "content": "weather in Paris?" }],
+            "tools": [{
+                "type": "function",
+                "function": {
+                    "name": "get_weather",
+                    "parameters": weather_tool()["parameters"].clone()
+                }
+            }],
+            "stream": true,
+            "stream_mode": "grok_tool_call"
+        }),
+    )
+    .await;
+
+    let chunks = chat_stream_chunks(&text);
+    let tool_deltas: Vec<&Value> = chunks
+        .iter()
+        .filter_map(|chunk| chunk["choices"][0]["delta"]["tool_calls"].as_array())
+        .flatten()
+        .collect();
+    let header = tool_deltas
+        .iter()
+        .find(|delta| delta["id"].is_string())
+        .unwrap_or_else(|| panic!("missing tool call header: {text}"));
+    assert_eq!(header["id"], "call_grok_1");
+    assert_eq!(header["function"]["name"], "get_weather");
+    let arguments: String = tool_deltas
+        .iter()
+        .filter_map(|delta| delta["function"]["arguments"].as_str())
+        .collect();
+    assert_eq!(arguments, r#"{"city":"Paris","unit":"celsius"}"#, "{text}");
+    let finish_reasons: Vec<&str> = chunks
+        .iter()
+        .filter_map(|chunk| chunk["choices"][0]["finish_reason"].as_str())
+        .collect();
+    assert_eq!(finish_reasons, vec!["tool_calls"], "{text}");
+}
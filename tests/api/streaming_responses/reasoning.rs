@@ -92,7 +92,12 @@ async fn responses_streaming_omits_multiple_reasoning_items_emptied_by_response_
                 provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
                 base_url,
                 api_key: Some("upstream-key".to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -103,6 +108,7 @@ async fn responses_streaming_omits_multiple_reasoning_items_emptied_by_response_
                     monoize::monoize_routing::MonoizeModelEntry {
                         redirect: None,
                         multiplier: 1.0,
+                        supports_streaming: None,
                     },
                 )]),
                 active_probe_enabled_override: None,
@@ -129,6 +135,9 @@ async fn responses_streaming_omits_multiple_reasoning_items_emptied_by_response_
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: Some(-1),
         })
@@ -295,6 +304,7 @@ async fn responses_streaming_applies_response_transform_from_provider() {
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: None,
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     let create_input = monoize::monoize_routing::CreateMonoizeProviderInput {
@@ -307,7 +317,12 @@ async fn responses_streaming_applies_response_transform_from_provider() {
             provider_type: monoize::monoize_routing::MonoizeProviderType::ChatCompletion,
             base_url,
             api_key: Some("upstream-key".to_string()),
+            api_keys: None,
             weight: 1,
+            priority: 0,
+            max_concurrency: None,
+            org_id: None,
+            project_id: None,
             enabled: true,
             passive_failure_count_threshold_override: None,
             passive_cooldown_seconds_override: None,
@@ -338,6 +353,9 @@ async fn responses_streaming_applies_response_transform_from_provider() {
         request_timeout_ms_override: None,
         extra_fields_whitelist: None,
         strip_cross_protocol_nested_extra: None,
+        debug_log_bodies: false,
+        default_temperature: None,
+        default_top_p: None,
         enabled: true,
         priority: Some(-1),
     };
@@ -368,6 +386,7 @@ async fn responses_streaming_applies_response_transform_from_provider() {
     let text = String::from_utf8_lossy(&bytes).to_string();
     assert!(!text.contains("event: response.reasoning_text.delta"));
     assert!(!text.contains("event: response.reasoning.delta"));
+    assert!(!text.contains("\"type\":\"reasoning\""));
     assert!(text.contains("event: response.output_text.delta"));
     assert!(text.contains("event: response.completed"));
 }
@@ -385,6 +404,7 @@ async fn responses_streaming_split_sse_frames_breaks_large_delta_frames() {
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: None,
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     let create_input = monoize::monoize_routing::CreateMonoizeProviderInput {
@@ -397,7 +417,12 @@ async fn responses_streaming_split_sse_frames_breaks_large_delta_frames() {
             provider_type: monoize::monoize_routing::MonoizeProviderType::Responses,
             base_url,
             api_key: Some("upstream-key".to_string()),
+            api_keys: None,
             weight: 1,
+            priority: 0,
+            max_concurrency: None,
+            org_id: None,
+            project_id: None,
             enabled: true,
             passive_failure_count_threshold_override: None,
             passive_cooldown_seconds_override: None,
@@ -428,6 +453,9 @@ async fn responses_streaming_split_sse_frames_breaks_large_delta_frames() {
         request_timeout_ms_override: None,
         extra_fields_whitelist: None,
         strip_cross_protocol_nested_extra: None,
+        debug_log_bodies: false,
+        default_temperature: None,
+        default_top_p: None,
         enabled: true,
         priority: Some(-1),
     };
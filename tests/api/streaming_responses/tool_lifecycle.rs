@@ -583,3 +583,62 @@ async fn responses_streaming_response_done_output_is_authoritative_terminal_stat
         }
     }
 }
+
+#[tokio::test]
+async fn responses_streaming_renames_repeated_upstream_call_ids() {
+    let ctx = setup().await;
+    let req = Request::builder()
+        .method("POST")
+        .uri("/v1/responses")
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, ctx.auth_header.clone())
+        .body(Body::from(
+            json!({
+                "model":"gpt-5-mini",
+                "input":"stream",
+                "tools":[{ "type":"function","name":"lookup","parameters":{ "type":"object","additionalProperties":true }}],
+                "stream": true,
+                "stream_mode": "duplicate_call_ids"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let resp = ctx.router.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8_lossy(&bytes).to_string();
+    let frames = parse_responses_sse_json(&text);
+    assert!(
+        frames.iter().all(|(event, _)| event != "error"),
+        "duplicate call ids must not surface as a terminal conflict: {text}"
+    );
+
+    let added_call_ids: Vec<&str> = frames
+        .iter()
+        .filter(|(event, _)| event == "response.output_item.added")
+        .filter_map(|(_, payload)| payload["item"]["call_id"].as_str())
+        .collect();
+    assert_eq!(added_call_ids, vec!["call_dup", "call_dup_2"], "{text}");
+
+    let completed = frames
+        .iter()
+        .find(|(event, _)| event == "response.completed")
+        .map(|(_, payload)| payload)
+        .expect("response.completed frame");
+    let calls: Vec<(&str, &str)> = completed["response"]["output"]
+        .as_array()
+        .expect("completed output")
+        .iter()
+        .map(|item| {
+            (
+                item["call_id"].as_str().unwrap_or_default(),
+                item["arguments"].as_str().unwrap_or_default(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        calls,
+        vec![("call_dup", "{\"q\":1}"), ("call_dup_2", "{\"q\":2}")],
+        "{text}"
+    );
+}
@@ -82,6 +82,87 @@ async fn start_upstream() -> (SocketAddr, CapturedHeaders, CapturedBodies) {
         }
 
         if body.get("stream").and_then(|v| v.as_bool()) == Some(true) {
+            // Synthetic xAI Grok Responses streams, hand-written to mirror the
+            // xAI wire shape; like xAI they end on `response.completed` without
+            // a `[DONE]` sentinel.
+            let grok_fixture = match body.get("stream_mode").and_then(|v| v.as_str()) {
+                Some("grok_text_citations") => {
+                    Some(include_str!("../fixtures/grok/text_citations.sse"))
+                }
+                Some("grok_tool_call") => Some(include_str!("../fixtures/grok/tool_call.sse")),
+                _ => None,
+            };
+            if let Some(fixture) = grok_fixture {
+                return ([(CONTENT_TYPE, "text/event-stream")], fixture).into_response();
+            }
+
+            if body.get("stream_mode").and_then(|v| v.as_str()) == Some("duplicate_call_ids") {
+                let call = |arguments: &str| {
+                    json!({
+                        "type": "function_call",
+                        "call_id": "call_dup",
+                        "name": "lookup",
+                        "arguments": arguments
+                    })
+                };
+                let mut events = Vec::new();
+                for (output_index, arguments) in [(0, "{\"q\":1}"), (1, "{\"q\":2}")] {
+                    events.push(
+                        Event::default().event("response.output_item.added").data(
+                            json!({
+                                "type": "response.output_item.added",
+                                "output_index": output_index,
+                                "item": call("")
+                            })
+                            .to_string(),
+                        ),
+                    );
+                    events.push(
+                        Event::default()
+                            .event("response.function_call_arguments.delta")
+                            .data(
+                                json!({
+                                    "type": "response.function_call_arguments.delta",
+                                    "output_index": output_index,
+                                    "delta": arguments
+                                })
+                                .to_string(),
+                            ),
+                    );
+                    events.push(
+                        Event::default().event("response.output_item.done").data(
+                            json!({
+                                "type": "response.output_item.done",
+                                "output_index": output_index,
+                                "item": call(arguments)
+                            })
+                            .to_string(),
+                        ),
+                    );
+                }
+                events.push(
+                    Event::default().event("response.completed").data(
+                        json!({
+                            "type": "response.completed",
+                            "response": {
+                                "id": "resp_dup",
+                                "object": "response",
+                                "created_at": 0,
+                                "model": model,
+                                "status": "completed",
+                                "output": [call("{\"q\":1}"), call("{\"q\":2}")]
+                            }
+                        })
+                        .to_string(),
+                    ),
+                );
+                events.push(Event::default().data("[DONE]"));
+                return Sse::new(futures_util::stream::iter(
+                    events.into_iter().map(Ok::<_, Infallible>),
+                ))
+                .into_response();
+            }
+
             // If tools are present and no tool outputs were provided yet, stream a tool call.
             let image_b64 = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mP8/x8AAusB9p4N2VwAAAAASUVORK5CYII=";
             if body.get("stream_mode").and_then(|v| v.as_str())
@@ -1734,6 +1815,67 @@ async fn start_upstream() -> (SocketAddr, CapturedHeaders, CapturedBodies) {
                 return Sse::new(stream).into_response();
             }
 
+            if body.get("stream_mode").and_then(|v| v.as_str()) == Some("unmodeled_event") {
+                let response = |status: &str| {
+                    json!({
+                        "id": "resp_unmodeled",
+                        "object": "response",
+                        "created_at": 1700000000,
+                        "model": "gpt-5-mini-2026-01-01",
+                        "status": status,
+                        "output": [],
+                        "usage": { "input_tokens": 3, "output_tokens": 2, "total_tokens": 5 }
+                    })
+                };
+                let stream = futures_util::stream::iter(vec![
+                    Ok::<_, Infallible>(
+                        Event::default().event("response.created").data(
+                            json!({
+                                "type": "response.created",
+                                "sequence_number": 40,
+                                "response": response("in_progress")
+                            })
+                            .to_string(),
+                        ),
+                    ),
+                    Ok::<_, Infallible>(
+                        Event::default().event("response.custom_debug").data(
+                            json!({
+                                "type": "response.custom_debug",
+                                "sequence_number": 41,
+                                "trace": { "shard": "b", "hops": [1, 2] }
+                            })
+                            .to_string(),
+                        ),
+                    ),
+                    Ok::<_, Infallible>(
+                        Event::default().event("response.output_text.delta").data(
+                            json!({
+                                "type": "response.output_text.delta",
+                                "sequence_number": 42,
+                                "item_id": "msg_unmodeled",
+                                "output_index": 0,
+                                "content_index": 0,
+                                "delta": "hi"
+                            })
+                            .to_string(),
+                        ),
+                    ),
+                    Ok::<_, Infallible>(
+                        Event::default().event("response.completed").data(
+                            json!({
+                                "type": "response.completed",
+                                "sequence_number": 43,
+                                "response": response("completed")
+                            })
+                            .to_string(),
+                        ),
+                    ),
+                    Ok::<_, Infallible>(Event::default().data("[DONE]")),
+                ]);
+                return Sse::new(stream).into_response();
+            }
+
             if body.get("stream_mode").and_then(|v| v.as_str()) == Some("missing_terminal") {
                 let stream = futures_util::stream::iter(vec![
                     Ok::<_, Infallible>(
@@ -2321,9 +2463,28 @@ async fn start_upstream() -> (SocketAddr, CapturedHeaders, CapturedBodies) {
                 lock.push(("x-goog-api-key".to_string(), v.to_string()));
             }
         }
+        capture_header_values(
+            &headers,
+            &captured_headers,
+            &["openai-organization", "openai-project"],
+        );
         if let Some(resp) = maybe_forced_upstream_error(&body) {
             return resp;
         }
+        if headers.get("authorization").and_then(|h| h.to_str().ok())
+            == Some("Bearer undecodable-key")
+        {
+            return (StatusCode::OK, "").into_response();
+        }
+        if headers.get("authorization").and_then(|h| h.to_str().ok())
+            == Some("Bearer rejected-key")
+        {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({ "error": { "message": "invalid api key", "type": "invalid_request_error" } })),
+            )
+                .into_response();
+        }
         maybe_forced_upstream_delay(&body).await;
         let model = body.get("model").and_then(|v| v.as_str()).unwrap_or("mock");
         let messages = body
@@ -2394,7 +2555,7 @@ async fn start_upstream() -> (SocketAddr, CapturedHeaders, CapturedBodies) {
             }
             detail
         };
-        let emit_usage = body
+        let emit_usage = (body
             .get("emit_usage")
             .and_then(|v| v.as_bool())
             .unwrap_or(false)
@@ -2402,7 +2563,8 @@ async fn start_upstream() -> (SocketAddr, CapturedHeaders, CapturedBodies) {
                 .get("stream_options")
                 .and_then(|v| v.get("include_usage"))
                 .and_then(|v| v.as_bool())
-                .unwrap_or(false);
+                .unwrap_or(false))
+            && body.get("suppress_usage").and_then(|v| v.as_bool()) != Some(true);
         let finish_reason = body
             .get("force_finish_reason")
             .and_then(|v| v.as_str())
@@ -3342,6 +3504,11 @@ async fn start_upstream() -> (SocketAddr, CapturedHeaders, CapturedBodies) {
                 lock.push(("x-goog-api-key".to_string(), v.to_string()));
             }
         }
+        capture_header_values(
+            &headers,
+            &captured_headers,
+            &["openai-organization", "openai-project"],
+        );
         if let Some(resp) = maybe_forced_upstream_error(&body) {
             return resp;
         }
@@ -3432,6 +3599,57 @@ async fn start_upstream() -> (SocketAddr, CapturedHeaders, CapturedBodies) {
                 return Sse::new(stream).into_response();
             }
 
+            if body.get("stream_mode").and_then(|v| v.as_str()) == Some("messages_mid_error") {
+                let frames = [
+                    (
+                        "message_start",
+                        json!({
+                            "type": "message_start",
+                            "message": {
+                                "id": "msg_mid_error",
+                                "type": "message",
+                                "role": "assistant",
+                                "model": model,
+                                "content": [],
+                                "stop_reason": Value::Null,
+                                "stop_sequence": Value::Null,
+                                "usage": { "input_tokens": 7, "output_tokens": 1 }
+                            }
+                        }),
+                    ),
+                    (
+                        "content_block_start",
+                        json!({
+                            "type": "content_block_start",
+                            "index": 0,
+                            "content_block": { "type": "text", "text": "" }
+                        }),
+                    ),
+                    (
+                        "content_block_delta",
+                        json!({
+                            "type": "content_block_delta",
+                            "index": 0,
+                            "delta": { "type": "text_delta", "text": "partial answer" }
+                        }),
+                    ),
+                    (
+                        "error",
+                        json!({
+                            "type": "error",
+                            "error": {
+                                "type": "overloaded_error",
+                                "message": "mock mid-stream overload"
+                            }
+                        }),
+                    ),
+                ];
+                let stream = futures_util::stream::iter(frames.into_iter().map(|(event, data)| {
+                    Ok::<_, Infallible>(Event::default().event(event).data(data.to_string()))
+                }));
+                return Sse::new(stream).into_response();
+            }
+
             if body.get("stream_mode").and_then(|v| v.as_str())
                 == Some("messages_malformed_then_stop")
             {
@@ -4384,6 +4602,27 @@ async fn start_upstream() -> (SocketAddr, CapturedHeaders, CapturedBodies) {
             return StatusCode::NOT_FOUND.into_response();
         };
 
+        let blocked = match body.get("native_response_mode").and_then(Value::as_str) {
+            Some("gemini_safety_block") => Some(json!({
+                "candidates": [{ "finishReason": "SAFETY", "index": 0 }],
+                "modelVersion": model
+            })),
+            Some("gemini_prompt_blocked") => Some(json!({
+                "promptFeedback": { "blockReason": "PROHIBITED_CONTENT" },
+                "modelVersion": model
+            })),
+            _ => None,
+        };
+        if let Some(blocked) = blocked {
+            if stream_mode {
+                let stream = futures_util::stream::iter(vec![Ok::<_, Infallible>(
+                    Event::default().data(blocked.to_string()),
+                )]);
+                return Sse::new(stream).into_response();
+            }
+            return Json(blocked).into_response();
+        }
+
         let text = body
             .get("contents")
             .and_then(|v| v.as_array())
@@ -4523,14 +4762,71 @@ async fn start_upstream() -> (SocketAddr, CapturedHeaders, CapturedBodies) {
         }))
     }
 
+    async fn audio_speech(
+        axum::extract::State((_captured_headers, captured_bodies)): axum::extract::State<(
+            CapturedHeaders,
+            CapturedBodies,
+        )>,
+        Json(body): Json<Value>,
+    ) -> impl axum::response::IntoResponse {
+        if let Ok(mut lock) = captured_bodies.lock() {
+            lock.push(("audio_speech".to_string(), body.clone()));
+        }
+        let content_type = match body.get("response_format").and_then(|v| v.as_str()) {
+            Some("wav") => "audio/wav",
+            _ => "audio/mpeg",
+        };
+        (
+            [(axum::http::header::CONTENT_TYPE, content_type)],
+            vec![0x49u8, 0x44, 0x33, 0x04, 0x00, 0xff, 0xfb],
+        )
+    }
+
+    async fn embeddings(
+        axum::extract::State((_captured_headers, captured_bodies)): axum::extract::State<(
+            CapturedHeaders,
+            CapturedBodies,
+        )>,
+        Json(body): Json<Value>,
+    ) -> Json<Value> {
+        if let Ok(mut lock) = captured_bodies.lock() {
+            lock.push(("embeddings".to_string(), body.clone()));
+        }
+        let inputs = match body.get("input") {
+            Some(Value::Array(items)) => items.clone(),
+            Some(other) => vec![other.clone()],
+            None => Vec::new(),
+        };
+        let data = inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| {
+                let value = input
+                    .as_str()
+                    .and_then(|s| s.parse::<f64>().ok())
+                    .unwrap_or(0.0);
+                json!({ "object": "embedding", "index": index, "embedding": [value] })
+            })
+            .collect::<Vec<_>>();
+        Json(json!({
+            "object": "list",
+            "model": body.get("model").cloned().unwrap_or(Value::Null),
+            "data": data,
+            "usage": { "prompt_tokens": inputs.len(), "total_tokens": inputs.len() }
+        }))
+    }
+
     let router = Router::new()
+        .route("/v1/embeddings", post(embeddings))
         .route("/v1/responses", post(responses))
         .route("/v1/responses/compact", post(responses_compact))
         .route("/v1/images/generations", post(image_generations))
         .route("/v1/images/edits", post(image_edits))
+        .route("/v1/audio/speech", post(audio_speech))
         .route("/v1/chat/completions", post(chat))
         .route("/v1/messages", post(messages))
         .route("/v1beta/models/{*rest}", post(gemini_dispatch))
+        .layer(axum::extract::DefaultBodyLimit::disable())
         .with_state((Arc::clone(&captured_headers), Arc::clone(&captured_bodies)));
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
@@ -4688,6 +4984,7 @@ async fn create_test_provider(
         monoize::monoize_routing::MonoizeModelEntry {
             redirect: None,
             multiplier: 1.0,
+            supports_streaming: None,
         },
     );
     state
@@ -4702,7 +4999,12 @@ async fn create_test_provider(
                 provider_type,
                 base_url: base_url.to_string(),
                 api_key: Some(api_key.to_string()),
+                api_keys: None,
                 weight: 1,
+                priority: 0,
+                max_concurrency: None,
+                org_id: None,
+                project_id: None,
                 enabled: true,
                 passive_failure_count_threshold_override: None,
                 passive_cooldown_seconds_override: None,
@@ -4727,6 +5029,9 @@ async fn create_test_provider(
             request_timeout_ms_override: None,
             extra_fields_whitelist: None,
             strip_cross_protocol_nested_extra: None,
+            debug_log_bodies: false,
+            default_temperature: None,
+            default_top_p: None,
             enabled: true,
             priority: None,
         })
@@ -4759,6 +5064,8 @@ async fn seed_test_model_pricing(state: &monoize::app::AppState, model_ids: &[&s
                     cache_read_input_cost_per_token_nano: None,
                     cache_creation_input_cost_per_token_nano: None,
                     output_cost_per_reasoning_token_nano: None,
+                    image_cost_per_image_nano: None,
+                    tts_cost_per_char_nano: None,
                     max_input_tokens: None,
                     max_output_tokens: None,
                     max_tokens: None,
@@ -4839,6 +5146,7 @@ async fn setup_with_unknown_fields() -> TestContext {
         listen: "127.0.0.1:0".to_string(),
         metrics_path: "/metrics".to_string(),
         database_dsn: format!("sqlite://{}", db_path.display()),
+        max_request_body_bytes: monoize::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
     })
     .await
     .expect("load state");
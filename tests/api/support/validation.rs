@@ -27,6 +27,20 @@ fn maybe_forced_upstream_error(body: &Value) -> Option<axum::response::Response>
     )
 }
 
+fn capture_header_values(
+    headers: &axum::http::HeaderMap,
+    captured_headers: &CapturedHeaders,
+    names: &[&str],
+) {
+    for name in names {
+        if let Some(v) = headers.get(*name).and_then(|h| h.to_str().ok())
+            && let Ok(mut lock) = captured_headers.lock()
+        {
+            lock.push((name.to_string(), v.to_string()));
+        }
+    }
+}
+
 fn maybe_reasoning_summary_validation_error(body: &Value) -> Option<axum::response::Response> {
     if body
         .get("require_reasoning_input_summary")
@@ -0,0 +1,220 @@
+use axum::body::Body;
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use monoize::app::{RuntimeConfig, build_app, load_state_with_runtime};
+use monoize::users::UserRole;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+struct TestContext {
+    router: axum::Router,
+    admin_auth: String,
+    user_auth: String,
+}
+
+async fn setup() -> TestContext {
+    let state = load_state_with_runtime(RuntimeConfig {
+        listen: "127.0.0.1:0".to_string(),
+        metrics_path: "/metrics".to_string(),
+        database_dsn: "sqlite::memory:".to_string(),
+        max_request_body_bytes: monoize::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
+    })
+    .await
+    .expect("state loads");
+    let admin = state
+        .user_store
+        .create_user("admin_preview", "password", UserRole::Admin, &[])
+        .await
+        .expect("admin created");
+    let user = state
+        .user_store
+        .create_user("user_preview", "password", UserRole::User, &[])
+        .await
+        .expect("user created");
+    let admin_session = state
+        .user_store
+        .create_session(&admin.id, 7)
+        .await
+        .expect("admin session created");
+    let user_session = state
+        .user_store
+        .create_session(&user.id, 7)
+        .await
+        .expect("user session created");
+
+    TestContext {
+        router: build_app(state),
+        admin_auth: format!("Bearer {}", admin_session.token),
+        user_auth: format!("Bearer {}", user_session.token),
+    }
+}
+
+async fn preview(ctx: &TestContext, auth: &str, body: Value) -> (StatusCode, Value) {
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/dashboard/transforms/preview")
+        .header(CONTENT_TYPE, "application/json")
+        .header(AUTHORIZATION, auth)
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let resp = ctx.router.clone().oneshot(req).await.unwrap();
+    let status = resp.status();
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    let value = serde_json::from_slice(&bytes).unwrap_or_else(|_| json!({}));
+    (status, value)
+}
+
+#[tokio::test]
+async fn request_preview_returns_transformed_urp_and_upstream_body() {
+    let ctx = setup().await;
+    let (status, body) = preview(
+        &ctx,
+        &ctx.admin_auth,
+        json!({
+            "phase": "request",
+            "model": "gpt-5",
+            "provider_type": "chat_completion",
+            "transforms": [
+                {
+                    "transform": "default_reasoning_effort",
+                    "phase": "request",
+                    "config": { "effort": "high" }
+                },
+                {
+                    "transform": "override_max_tokens",
+                    "phase": "request",
+                    "models": ["claude-*"],
+                    "config": { "value": 16 }
+                }
+            ],
+            "body": {
+                "model": "gpt-5",
+                "input": "hello"
+            }
+        }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["phase"], json!("request"));
+    assert_eq!(body["urp"]["reasoning"]["effort"], json!("high"));
+    assert!(body["urp"]["max_output_tokens"].is_null());
+    assert_eq!(body["upstream_body"]["model"], json!("gpt-5"));
+    assert_eq!(body["upstream_body"]["reasoning_effort"], json!("high"));
+    assert_eq!(
+        body["upstream_body"]["messages"][0]["content"],
+        json!("hello")
+    );
+}
+
+#[tokio::test]
+async fn response_preview_reencodes_for_downstream() {
+    let ctx = setup().await;
+    let (status, body) = preview(
+        &ctx,
+        &ctx.admin_auth,
+        json!({
+            "phase": "response",
+            "model": "gpt-5",
+            "provider_type": "chat_completion",
+            "downstream": "responses",
+            "transforms": [
+                {
+                    "transform": "override_max_tokens",
+                    "phase": "request",
+                    "config": { "value": 16 }
+                }
+            ],
+            "body": {
+                "id": "chatcmpl_preview",
+                "object": "chat.completion",
+                "model": "gpt-5",
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": "hi there" },
+                    "finish_reason": "stop"
+                }]
+            }
+        }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["phase"], json!("response"));
+    assert_eq!(body["downstream_body"]["object"], json!("response"));
+    assert_eq!(body["downstream_body"]["model"], json!("gpt-5"));
+    assert!(
+        body["downstream_body"].to_string().contains("hi there"),
+        "{body}"
+    );
+
+    let (status, body) = preview(
+        &ctx,
+        &ctx.admin_auth,
+        json!({
+            "phase": "response",
+            "model": "gpt-5",
+            "transforms": [],
+            "body": {}
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], json!("invalid_request"));
+}
+
+#[tokio::test]
+async fn preview_reports_transform_errors_as_bad_request() {
+    let ctx = setup().await;
+    let request_body = json!({ "model": "gpt-5", "input": "hello" });
+
+    let (status, body) = preview(
+        &ctx,
+        &ctx.admin_auth,
+        json!({
+            "phase": "request",
+            "model": "gpt-5",
+            "transforms": [{
+                "transform": "default_reasoning_effort",
+                "phase": "request",
+                "config": { "effort": "extreme" }
+            }],
+            "body": request_body,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], json!("transform_invalid_config"));
+
+    let (status, body) = preview(
+        &ctx,
+        &ctx.admin_auth,
+        json!({
+            "phase": "request",
+            "model": "gpt-5",
+            "transforms": [{ "transform": "no_such_transform", "phase": "request" }],
+            "body": request_body,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], json!("transform_not_found"));
+}
+
+#[tokio::test]
+async fn preview_requires_admin() {
+    let ctx = setup().await;
+    let body = json!({
+        "phase": "request",
+        "model": "gpt-5",
+        "transforms": [],
+        "body": { "model": "gpt-5", "input": "hello" }
+    });
+
+    let (status, _) = preview(&ctx, &ctx.user_auth, body.clone()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = preview(&ctx, "Bearer invalid", body).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
@@ -5,6 +5,7 @@ fn test_runtime(database_dsn: String) -> monoize::app::RuntimeConfig {
         listen: "127.0.0.1:0".to_string(),
         metrics_path: "/metrics".to_string(),
         database_dsn,
+        max_request_body_bytes: monoize::app::DEFAULT_MAX_REQUEST_BODY_BYTES,
     }
 }
 
@@ -29,3 +30,36 @@ async fn sqlite_memory_dsn_starts_without_files() {
         .await
         .expect("load state");
 }
+
+#[tokio::test]
+async fn oversized_request_body_is_rejected_before_decode() {
+    use tower::ServiceExt;
+
+    let mut runtime = test_runtime("sqlite::memory:".to_string());
+    runtime.max_request_body_bytes = 1024;
+    let state = monoize::app::load_state_with_runtime(runtime)
+        .await
+        .expect("load state");
+    let app = monoize::app::build_app(state);
+
+    let body = serde_json::json!({
+        "model": "gpt-5-mini",
+        "messages": [{ "role": "user", "content": "x".repeat(4096) }]
+    })
+    .to_string();
+    let response = app
+        .oneshot(
+            axum::http::Request::builder()
+                .method("POST")
+                .uri("/v1/chat/completions")
+                .header("content-type", "application/json")
+                .header("content-length", body.len())
+                .body(axum::body::Body::from(body))
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+
+    // No API key is sent, so reaching the handler would yield 401 instead.
+    assert_eq!(response.status(), axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+}